@@ -0,0 +1,4 @@
+-- Add push_remote and pr_remote columns to projects table
+-- This allows fork workflows: push attempt branches to one remote and open PRs against another
+ALTER TABLE projects ADD COLUMN push_remote TEXT DEFAULT NULL;
+ALTER TABLE projects ADD COLUMN pr_remote TEXT DEFAULT NULL;
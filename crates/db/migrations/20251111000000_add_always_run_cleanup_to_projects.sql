@@ -0,0 +1,3 @@
+-- Add always_run_cleanup column to projects table
+-- When set, the cleanup script runs after the coding agent even if it made no changes
+ALTER TABLE projects ADD COLUMN always_run_cleanup BOOLEAN NOT NULL DEFAULT 0;
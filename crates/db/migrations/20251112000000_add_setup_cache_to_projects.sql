@@ -0,0 +1,5 @@
+-- Add setup script cache settings to projects table
+-- setup_cache_files: comma-separated lockfile globs hashed to key the cache (NULL uses defaults)
+-- setup_cache_artifacts: comma-separated paths restored from the cache (NULL disables caching)
+ALTER TABLE projects ADD COLUMN setup_cache_files TEXT DEFAULT NULL;
+ALTER TABLE projects ADD COLUMN setup_cache_artifacts TEXT DEFAULT NULL;
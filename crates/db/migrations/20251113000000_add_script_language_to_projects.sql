@@ -0,0 +1,4 @@
+-- Add script_language column to projects table
+-- ScriptRequestLanguage (Bash, Sh, PowerShell or Node) used for setup, dev server and cleanup scripts
+-- NULL uses the platform default (PowerShell on Windows, Bash elsewhere)
+ALTER TABLE projects ADD COLUMN script_language TEXT DEFAULT NULL;
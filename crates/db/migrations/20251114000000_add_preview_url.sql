@@ -0,0 +1,5 @@
+-- URL detected in a dev server's output, shown as the attempt's preview
+ALTER TABLE execution_processes ADD COLUMN preview_url TEXT DEFAULT NULL;
+
+-- Optional per-project regex overriding the default preview URL detection
+ALTER TABLE projects ADD COLUMN preview_url_pattern TEXT DEFAULT NULL;
@@ -0,0 +1,3 @@
+-- OS process id of the spawned process group leader, used to kill processes left over
+-- from a previous server run
+ALTER TABLE execution_processes ADD COLUMN pid INTEGER DEFAULT NULL;
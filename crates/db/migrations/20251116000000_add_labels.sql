@@ -0,0 +1,22 @@
+-- Per-project labels (bug, feature, chore, ...) that can be attached to tasks
+CREATE TABLE labels (
+    id            BLOB PRIMARY KEY,
+    project_id    BLOB NOT NULL,
+    name          TEXT NOT NULL CHECK(name != ''),
+    color         TEXT NOT NULL DEFAULT '#6b7280',
+    created_at    TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at    TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE,
+    UNIQUE (project_id, name)
+);
+
+CREATE TABLE task_labels (
+    task_id       BLOB NOT NULL,
+    label_id      BLOB NOT NULL,
+    created_at    TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    PRIMARY KEY (task_id, label_id),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (label_id) REFERENCES labels(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_task_labels_label_id ON task_labels(label_id);
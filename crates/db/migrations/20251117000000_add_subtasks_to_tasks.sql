@@ -0,0 +1,8 @@
+-- Direct parent/child links for tasks split into subtasks. Unlike parent_task_attempt this
+-- doesn't require the parent to have been started.
+ALTER TABLE tasks ADD COLUMN parent_task_id BLOB REFERENCES tasks(id) ON DELETE SET NULL;
+
+-- Parent tasks held back until all of their subtasks are done or cancelled
+ALTER TABLE tasks ADD COLUMN blocked_on_subtasks INTEGER NOT NULL DEFAULT 0;
+
+CREATE INDEX idx_tasks_parent_task_id ON tasks(parent_task_id);
@@ -0,0 +1,2 @@
+-- Branch new task attempts start from when the request doesn't name one
+ALTER TABLE projects ADD COLUMN default_base_branch TEXT;
@@ -0,0 +1,17 @@
+-- Chronological audit trail of what happened to a task (created, status changes, attempts,
+-- merges, PRs). Higher level than execution process logs.
+CREATE TABLE task_events (
+    id              BLOB PRIMARY KEY,
+    task_id         BLOB NOT NULL,
+    task_attempt_id BLOB,
+    event_type      TEXT NOT NULL,
+    from_status     TEXT,
+    to_status       TEXT,
+    details         TEXT,
+    actor           TEXT,
+    created_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (task_attempt_id) REFERENCES task_attempts(id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_task_events_task_id_created_at ON task_events(task_id, created_at);
@@ -0,0 +1,2 @@
+-- Commit the user last reviewed an attempt at; diffs can be limited to changes since then
+ALTER TABLE task_attempts ADD COLUMN reviewed_commit TEXT;
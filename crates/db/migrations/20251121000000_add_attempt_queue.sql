@@ -0,0 +1,14 @@
+-- Attempts waiting for a free execution slot. Rows are pending requests: the task attempt itself
+-- is only created when the scheduler starts it, so cancelling just deletes the row.
+CREATE TABLE attempt_queue (
+    id                  BLOB PRIMARY KEY,
+    task_id             BLOB NOT NULL,
+    executor_profile_id TEXT NOT NULL,
+    base_branch         TEXT NOT NULL,
+    force_setup         BOOLEAN NOT NULL DEFAULT FALSE,
+    position            INTEGER NOT NULL,
+    created_at          TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_attempt_queue_position ON attempt_queue(position);
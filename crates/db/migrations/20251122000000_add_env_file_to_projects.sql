@@ -0,0 +1,3 @@
+-- Optional .env-style file injected into the environment of processes run for the project
+ALTER TABLE projects ADD COLUMN env_file TEXT;
+ALTER TABLE projects ADD COLUMN env_file_required BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,2 @@
+-- Free-text markdown notes reviewers keep about an attempt
+ALTER TABLE task_attempts ADD COLUMN notes TEXT;
@@ -0,0 +1,2 @@
+-- Users pin the canonical attempt of a task so it is listed first
+ALTER TABLE task_attempts ADD COLUMN pinned BOOLEAN NOT NULL DEFAULT FALSE;
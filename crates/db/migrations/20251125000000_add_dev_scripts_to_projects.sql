@@ -0,0 +1,2 @@
+-- Additional named dev servers, stored as a JSON array of {"name", "script"} objects
+ALTER TABLE projects ADD COLUMN dev_scripts TEXT NOT NULL DEFAULT '[]';
@@ -0,0 +1,2 @@
+-- Queued attempts of higher priority tasks start first; equal priorities keep queue order
+ALTER TABLE tasks ADD COLUMN priority INTEGER NOT NULL DEFAULT 0;
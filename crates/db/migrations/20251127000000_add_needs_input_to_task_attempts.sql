@@ -0,0 +1,2 @@
+-- Set when the coding agent's last turn ended by asking the user something
+ALTER TABLE task_attempts ADD COLUMN needs_input BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,10 @@
+-- Files of an attempt's diff that a reviewer has checked off, with the contents they were reviewed at
+CREATE TABLE file_reviews (
+    task_attempt_id BLOB NOT NULL,
+    path            TEXT NOT NULL,
+    -- Blob OID of the file when it was reviewed; NULL when the change deleted it
+    content_oid     TEXT,
+    created_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    PRIMARY KEY (task_attempt_id, path),
+    FOREIGN KEY (task_attempt_id) REFERENCES task_attempts(id) ON DELETE CASCADE
+);
@@ -0,0 +1,2 @@
+-- Projects can opt into sharing one worktree between all attempts of a task
+ALTER TABLE projects ADD COLUMN reuse_worktree BOOLEAN NOT NULL DEFAULT FALSE;
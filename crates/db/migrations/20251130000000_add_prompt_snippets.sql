@@ -0,0 +1,14 @@
+-- Reusable prompt fragments referenced as {{snippet:name}}; global when project_id is NULL
+CREATE TABLE prompt_snippets (
+    id            BLOB PRIMARY KEY,
+    project_id    BLOB,
+    name          TEXT NOT NULL CHECK(name != ''),
+    content       TEXT NOT NULL,
+    created_at    TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at    TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+-- NULLs never collide in a plain UNIQUE constraint, so key global snippets on an empty blob
+CREATE UNIQUE INDEX idx_prompt_snippets_scope_name
+    ON prompt_snippets(IFNULL(project_id, x''), name);
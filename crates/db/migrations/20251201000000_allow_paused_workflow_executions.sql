@@ -0,0 +1,21 @@
+-- Allow workflow executions to be paused between stations
+-- SQLite can't modify a CHECK constraint directly, so recreate the status column
+
+-- Step 1: Drop the existing index (will be recreated later)
+DROP INDEX IF EXISTS idx_workflow_executions_status;
+
+-- Step 2: Create replacement column with the extended constraint
+ALTER TABLE workflow_executions ADD COLUMN status_new TEXT NOT NULL DEFAULT 'pending'
+    CHECK(status_new IN ('pending', 'running', 'paused', 'completed', 'failed', 'cancelled'));
+
+-- Step 3: Copy existing data
+UPDATE workflow_executions SET status_new = status;
+
+-- Step 4: Drop old column
+ALTER TABLE workflow_executions DROP COLUMN status;
+
+-- Step 5: Rename new column to original name
+ALTER TABLE workflow_executions RENAME COLUMN status_new TO status;
+
+-- Step 6: Recreate index
+CREATE INDEX idx_workflow_executions_status ON workflow_executions(status);
@@ -0,0 +1,23 @@
+-- Allow only one active (pending, running or paused) execution of a workflow per task, so a
+-- double-start can't run two executions of it. Every start creates a new attempt, so the index
+-- is on the task rather than the attempt.
+
+-- Cancel all but the newest active execution where duplicates already exist
+UPDATE workflow_executions
+SET status = 'cancelled',
+    completed_at = CURRENT_TIMESTAMP,
+    updated_at = CURRENT_TIMESTAMP
+WHERE status IN ('pending', 'running', 'paused')
+  AND EXISTS (
+      SELECT 1 FROM workflow_executions newer
+      WHERE newer.workflow_id = workflow_executions.workflow_id
+        AND newer.task_id = workflow_executions.task_id
+        AND newer.status IN ('pending', 'running', 'paused')
+        AND (newer.created_at > workflow_executions.created_at
+             OR (newer.created_at = workflow_executions.created_at
+                 AND newer.id > workflow_executions.id))
+  );
+
+CREATE UNIQUE INDEX idx_workflow_executions_active_task
+    ON workflow_executions(workflow_id, task_id)
+    WHERE status IN ('pending', 'running', 'paused');
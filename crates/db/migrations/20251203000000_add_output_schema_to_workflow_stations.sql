@@ -0,0 +1,3 @@
+-- Stations can declare a JSON Schema that their extracted output must satisfy
+-- before the workflow advances past them
+ALTER TABLE workflow_stations ADD COLUMN output_schema TEXT;
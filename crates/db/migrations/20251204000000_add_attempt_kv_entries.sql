@@ -0,0 +1,11 @@
+-- Scratch state agents keep across the turns of an attempt: small JSON values by key,
+-- removed together with the attempt
+CREATE TABLE attempt_kv_entries (
+    task_attempt_id BLOB NOT NULL,
+    key             TEXT NOT NULL,
+    value           TEXT NOT NULL,
+    created_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    PRIMARY KEY (task_attempt_id, key),
+    FOREIGN KEY (task_attempt_id) REFERENCES task_attempts(id) ON DELETE CASCADE
+);
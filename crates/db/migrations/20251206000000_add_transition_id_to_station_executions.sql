@@ -0,0 +1,3 @@
+-- Record the transition that led to each station execution, so a workflow's history shows
+-- the path actually taken rather than guessing from the station pair
+ALTER TABLE station_executions ADD COLUMN transition_id TEXT REFERENCES station_transitions(id) ON DELETE SET NULL;
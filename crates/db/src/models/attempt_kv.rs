@@ -0,0 +1,130 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Longest key an entry may have, in characters
+pub const MAX_KV_KEY_CHARS: usize = 128;
+/// Largest value an entry may hold, measured as serialized JSON
+pub const MAX_KV_VALUE_BYTES: usize = 16 * 1024;
+/// Most entries a single attempt may hold
+pub const MAX_KV_ENTRIES_PER_ATTEMPT: i64 = 256;
+
+/// A small JSON value an agent keeps across the turns of an attempt. Entries are removed
+/// together with their attempt.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct AttemptKvEntry {
+    pub task_attempt_id: Uuid,
+    pub key: String,
+    #[ts(type = "JsonValue")]
+    pub value: sqlx::types::Json<JsonValue>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Non-empty, at most [`MAX_KV_KEY_CHARS`] characters and free of control characters
+pub fn is_valid_kv_key(key: &str) -> bool {
+    !key.is_empty() && key.chars().count() <= MAX_KV_KEY_CHARS && !key.chars().any(char::is_control)
+}
+
+/// Check a key and value against the store's limits, describing the first one violated
+pub fn validate_kv_entry(key: &str, value: &JsonValue) -> Result<(), String> {
+    if !is_valid_kv_key(key) {
+        return Err(format!(
+            "Invalid key '{key}'; use 1 to {MAX_KV_KEY_CHARS} characters without control characters"
+        ));
+    }
+    let size = serde_json::to_vec(value)
+        .map_err(|e| format!("Value is not serializable: {e}"))?
+        .len();
+    if size > MAX_KV_VALUE_BYTES {
+        return Err(format!(
+            "Value for '{key}' is {size} bytes; the limit is {MAX_KV_VALUE_BYTES}"
+        ));
+    }
+    Ok(())
+}
+
+impl AttemptKvEntry {
+    pub async fn find_by_task_attempt_id(
+        pool: &SqlitePool,
+        task_attempt_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            AttemptKvEntry,
+            r#"SELECT task_attempt_id as "task_attempt_id!: Uuid", key, value as "value!: sqlx::types::Json<JsonValue>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM attempt_kv_entries
+               WHERE task_attempt_id = $1
+               ORDER BY key ASC"#,
+            task_attempt_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find(
+        pool: &SqlitePool,
+        task_attempt_id: Uuid,
+        key: &str,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            AttemptKvEntry,
+            r#"SELECT task_attempt_id as "task_attempt_id!: Uuid", key, value as "value!: sqlx::types::Json<JsonValue>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM attempt_kv_entries
+               WHERE task_attempt_id = $1 AND key = $2"#,
+            task_attempt_id,
+            key
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Store `value` under `key`, replacing any previous value. Returns `None` without storing
+    /// anything when `key` is new and the attempt already holds [`MAX_KV_ENTRIES_PER_ATTEMPT`]
+    /// entries.
+    pub async fn set(
+        pool: &SqlitePool,
+        task_attempt_id: Uuid,
+        key: &str,
+        value: &JsonValue,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        let value = sqlx::types::Json(value);
+        let max_entries = MAX_KV_ENTRIES_PER_ATTEMPT;
+        sqlx::query_as!(
+            AttemptKvEntry,
+            r#"INSERT INTO attempt_kv_entries (task_attempt_id, key, value)
+               SELECT $1, $2, $3
+               WHERE EXISTS (
+                   SELECT 1 FROM attempt_kv_entries WHERE task_attempt_id = $1 AND key = $2
+               ) OR (
+                   SELECT COUNT(*) FROM attempt_kv_entries WHERE task_attempt_id = $1
+               ) < $4
+               ON CONFLICT(task_attempt_id, key) DO UPDATE
+               SET value = excluded.value, updated_at = datetime('now', 'subsec')
+               RETURNING task_attempt_id as "task_attempt_id!: Uuid", key, value as "value!: sqlx::types::Json<JsonValue>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            task_attempt_id,
+            key,
+            value,
+            max_entries
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn delete(
+        pool: &SqlitePool,
+        task_attempt_id: Uuid,
+        key: &str,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM attempt_kv_entries WHERE task_attempt_id = $1 AND key = $2",
+            task_attempt_id,
+            key
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
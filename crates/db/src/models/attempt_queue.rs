@@ -0,0 +1,119 @@
+use chrono::{DateTime, Utc};
+use executors::profile::ExecutorProfileId;
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// An attempt waiting for a free execution slot. Entries of higher priority tasks start
+/// first, otherwise they start in `position` order.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct QueuedAttempt {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    #[ts(type = "ExecutorProfileId")]
+    pub executor_profile_id: sqlx::types::Json<ExecutorProfileId>,
+    pub base_branch: String,
+    pub force_setup: bool,
+    pub position: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateQueuedAttempt {
+    pub task_id: Uuid,
+    pub executor_profile_id: ExecutorProfileId,
+    pub base_branch: String,
+    pub force_setup: bool,
+}
+
+impl QueuedAttempt {
+    /// The whole queue, next to start first
+    pub async fn find_all(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            QueuedAttempt,
+            r#"SELECT q.id as "id!: Uuid", q.task_id as "task_id!: Uuid", q.executor_profile_id as "executor_profile_id!: sqlx::types::Json<ExecutorProfileId>", q.base_branch, q.force_setup as "force_setup!: bool", q.position, q.created_at as "created_at!: DateTime<Utc>"
+               FROM attempt_queue q
+               LEFT JOIN tasks t ON t.id = q.task_id
+               ORDER BY COALESCE(t.priority, 0) DESC, q.position ASC, q.created_at ASC"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            QueuedAttempt,
+            r#"SELECT id as "id!: Uuid", task_id as "task_id!: Uuid", executor_profile_id as "executor_profile_id!: sqlx::types::Json<ExecutorProfileId>", base_branch, force_setup as "force_setup!: bool", position, created_at as "created_at!: DateTime<Utc>"
+               FROM attempt_queue
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Append an entry to the end of the queue
+    pub async fn create(
+        pool: &SqlitePool,
+        data: &CreateQueuedAttempt,
+        id: Uuid,
+    ) -> Result<Self, sqlx::Error> {
+        let executor_profile_id = sqlx::types::Json(&data.executor_profile_id);
+        sqlx::query_as!(
+            QueuedAttempt,
+            r#"INSERT INTO attempt_queue (id, task_id, executor_profile_id, base_branch, force_setup, position)
+               VALUES ($1, $2, $3, $4, $5, (SELECT COALESCE(MAX(position) + 1, 0) FROM attempt_queue))
+               RETURNING id as "id!: Uuid", task_id as "task_id!: Uuid", executor_profile_id as "executor_profile_id!: sqlx::types::Json<ExecutorProfileId>", base_branch, force_setup as "force_setup!: bool", position, created_at as "created_at!: DateTime<Utc>""#,
+            id,
+            data.task_id,
+            executor_profile_id,
+            data.base_branch,
+            data.force_setup
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Remove an entry. Returns the number of rows deleted, so callers racing for the same
+    /// entry can tell whether they claimed it.
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM attempt_queue WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Move an entry to `index` (0 = next to start), clamped to the end of the queue.
+    /// Task priority still comes first, so an entry only moves among equal priorities.
+    /// Returns false if the entry is no longer queued.
+    pub async fn move_to(pool: &SqlitePool, id: Uuid, index: usize) -> Result<bool, sqlx::Error> {
+        let mut tx = pool.begin().await?;
+        let mut ids = sqlx::query_scalar!(
+            r#"SELECT q.id as "id!: Uuid" FROM attempt_queue q
+               LEFT JOIN tasks t ON t.id = q.task_id
+               ORDER BY COALESCE(t.priority, 0) DESC, q.position ASC, q.created_at ASC"#
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let Some(current) = ids.iter().position(|queued| *queued == id) else {
+            return Ok(false);
+        };
+        ids.remove(current);
+        ids.insert(index.min(ids.len()), id);
+
+        for (position, queued_id) in ids.iter().enumerate() {
+            let position = position as i64;
+            sqlx::query!(
+                "UPDATE attempt_queue SET position = $1 WHERE id = $2",
+                position,
+                queued_id
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(true)
+    }
+}
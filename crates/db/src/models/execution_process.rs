@@ -1,6 +1,8 @@
 use chrono::{DateTime, Utc};
 use executors::{
-    actions::{ExecutorAction, ExecutorActionType},
+    actions::{
+        ExecutorAction, ExecutorActionType, coding_agent_initial::CodingAgentInitialRequest,
+    },
     profile::ExecutorProfileId,
 };
 use serde::{Deserialize, Serialize};
@@ -66,12 +68,26 @@ pub struct ExecutionProcess {
     /// history view (due to restore/trimming). Hidden from logs/timeline;
     /// still listed in the Processes tab.
     pub dropped: bool,
+    /// URL the dev server reported it is listening on, detected from its output
+    pub preview_url: Option<String>,
     pub started_at: DateTime<Utc>,
     pub completed_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+/// A running execution process together with the attempt, task and project it belongs to
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct RunningExecutionProcess {
+    pub id: Uuid,
+    pub task_attempt_id: Uuid,
+    pub task_id: Uuid,
+    pub project_id: Uuid,
+    pub project_name: String,
+    pub run_reason: ExecutionProcessRunReason,
+    pub started_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Deserialize, TS)]
 pub struct CreateExecutionProcess {
     pub task_attempt_id: Uuid,
@@ -87,7 +103,7 @@ pub struct UpdateExecutionProcess {
     pub completed_at: Option<DateTime<Utc>>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ExecutionContext {
     pub execution_process: ExecutionProcess,
     pub task_attempt: TaskAttempt,
@@ -116,7 +132,7 @@ impl ExecutionProcess {
         sqlx::query_as!(
             ExecutionProcess,
             r#"SELECT id as "id!: Uuid", task_attempt_id as "task_attempt_id!: Uuid", run_reason as "run_reason!: ExecutionProcessRunReason", executor_action as "executor_action!: sqlx::types::Json<ExecutorActionField>", before_head_commit,
-                      after_head_commit, status as "status!: ExecutionProcessStatus", exit_code, dropped, started_at as "started_at!: DateTime<Utc>", completed_at as "completed_at?: DateTime<Utc>",
+                      after_head_commit, status as "status!: ExecutionProcessStatus", exit_code, dropped, preview_url, started_at as "started_at!: DateTime<Utc>", completed_at as "completed_at?: DateTime<Utc>",
                       created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
                FROM execution_processes WHERE id = ?"#,
             id
@@ -192,7 +208,7 @@ impl ExecutionProcess {
         sqlx::query_as!(
             ExecutionProcess,
             r#"SELECT id as "id!: Uuid", task_attempt_id as "task_attempt_id!: Uuid", run_reason as "run_reason!: ExecutionProcessRunReason", executor_action as "executor_action!: sqlx::types::Json<ExecutorActionField>", before_head_commit,
-                      after_head_commit, status as "status!: ExecutionProcessStatus", exit_code, dropped, started_at as "started_at!: DateTime<Utc>", completed_at as "completed_at?: DateTime<Utc>",
+                      after_head_commit, status as "status!: ExecutionProcessStatus", exit_code, dropped, preview_url, started_at as "started_at!: DateTime<Utc>", completed_at as "completed_at?: DateTime<Utc>",
                       created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
                FROM execution_processes WHERE rowid = ?"#,
             rowid
@@ -218,6 +234,7 @@ impl ExecutionProcess {
                       status          as "status!: ExecutionProcessStatus",
                       exit_code,
                       dropped,
+                      preview_url,
                       started_at      as "started_at!: DateTime<Utc>",
                       completed_at    as "completed_at?: DateTime<Utc>",
                       created_at      as "created_at!: DateTime<Utc>",
@@ -238,7 +255,7 @@ impl ExecutionProcess {
         sqlx::query_as!(
             ExecutionProcess,
             r#"SELECT id as "id!: Uuid", task_attempt_id as "task_attempt_id!: Uuid", run_reason as "run_reason!: ExecutionProcessRunReason", executor_action as "executor_action!: sqlx::types::Json<ExecutorActionField>", before_head_commit,
-                      after_head_commit, status as "status!: ExecutionProcessStatus", exit_code, dropped, started_at as "started_at!: DateTime<Utc>", completed_at as "completed_at?: DateTime<Utc>",
+                      after_head_commit, status as "status!: ExecutionProcessStatus", exit_code, dropped, preview_url, started_at as "started_at!: DateTime<Utc>", completed_at as "completed_at?: DateTime<Utc>",
                       created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
                FROM execution_processes WHERE status = 'running' ORDER BY created_at ASC"#,
         )
@@ -246,6 +263,37 @@ impl ExecutionProcess {
         .await
     }
 
+    /// Find running execution processes across all projects, with the task and project they
+    /// belong to
+    pub async fn find_running_with_context(
+        pool: &SqlitePool,
+    ) -> Result<Vec<RunningExecutionProcess>, sqlx::Error> {
+        sqlx::query_as!(
+            RunningExecutionProcess,
+            r#"SELECT ep.id as "id!: Uuid", ep.task_attempt_id as "task_attempt_id!: Uuid", ta.task_id as "task_id!: Uuid", t.project_id as "project_id!: Uuid",
+                      p.name as project_name, ep.run_reason as "run_reason!: ExecutionProcessRunReason", ep.started_at as "started_at!: DateTime<Utc>"
+               FROM execution_processes ep
+               JOIN task_attempts ta ON ta.id = ep.task_attempt_id
+               JOIN tasks t ON t.id = ta.task_id
+               JOIN projects p ON p.id = t.project_id
+               WHERE ep.status = 'running'
+               ORDER BY ep.started_at ASC"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Number of attempts with a setup, coding agent or cleanup process running. Dev servers
+    /// don't occupy an execution slot.
+    pub async fn count_running_attempts(pool: &SqlitePool) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT COUNT(DISTINCT task_attempt_id) as "count!: i64" FROM execution_processes
+               WHERE status = 'running' AND run_reason != 'devserver'"#
+        )
+        .fetch_one(pool)
+        .await
+    }
+
     /// Find running dev servers for a specific project
     pub async fn find_running_dev_servers_by_project(
         pool: &SqlitePool,
@@ -255,7 +303,7 @@ impl ExecutionProcess {
             ExecutionProcess,
             r#"SELECT ep.id as "id!: Uuid", ep.task_attempt_id as "task_attempt_id!: Uuid", ep.run_reason as "run_reason!: ExecutionProcessRunReason", ep.executor_action as "executor_action!: sqlx::types::Json<ExecutorActionField>",
                       ep.before_head_commit, ep.after_head_commit, ep.status as "status!: ExecutionProcessStatus", ep.exit_code,
-                      ep.dropped, ep.started_at as "started_at!: DateTime<Utc>", ep.completed_at as "completed_at?: DateTime<Utc>", ep.created_at as "created_at!: DateTime<Utc>", ep.updated_at as "updated_at!: DateTime<Utc>"
+                      ep.dropped, ep.preview_url, ep.started_at as "started_at!: DateTime<Utc>", ep.completed_at as "completed_at?: DateTime<Utc>", ep.created_at as "created_at!: DateTime<Utc>", ep.updated_at as "updated_at!: DateTime<Utc>"
                FROM execution_processes ep
                JOIN task_attempts ta ON ep.task_attempt_id = ta.id
                JOIN tasks t ON ta.task_id = t.id
@@ -305,7 +353,7 @@ impl ExecutionProcess {
         sqlx::query_as!(
             ExecutionProcess,
             r#"SELECT id as "id!: Uuid", task_attempt_id as "task_attempt_id!: Uuid", run_reason as "run_reason!: ExecutionProcessRunReason", executor_action as "executor_action!: sqlx::types::Json<ExecutorActionField>", before_head_commit,
-                      after_head_commit, status as "status!: ExecutionProcessStatus", exit_code, dropped, started_at as "started_at!: DateTime<Utc>", completed_at as "completed_at?: DateTime<Utc>",
+                      after_head_commit, status as "status!: ExecutionProcessStatus", exit_code, dropped, preview_url, started_at as "started_at!: DateTime<Utc>", completed_at as "completed_at?: DateTime<Utc>",
                       created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
                FROM execution_processes
                WHERE task_attempt_id = ? AND run_reason = ? AND dropped = FALSE
@@ -317,6 +365,40 @@ impl ExecutionProcess {
         .await
     }
 
+    /// Find the execution process of a task attempt that left HEAD at the given commit.
+    /// If several processes share the same after-commit (later ones made no commits),
+    /// the earliest one is the one that produced it.
+    pub async fn find_by_after_head_commit(
+        pool: &SqlitePool,
+        task_attempt_id: Uuid,
+        after_head_commit: &str,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ExecutionProcess,
+            r#"SELECT id              as "id!: Uuid",
+                      task_attempt_id as "task_attempt_id!: Uuid",
+                      run_reason      as "run_reason!: ExecutionProcessRunReason",
+                      executor_action as "executor_action!: sqlx::types::Json<ExecutorActionField>",
+                      before_head_commit,
+                      after_head_commit,
+                      status          as "status!: ExecutionProcessStatus",
+                      exit_code,
+                      dropped,
+                      preview_url,
+                      started_at      as "started_at!: DateTime<Utc>",
+                      completed_at    as "completed_at?: DateTime<Utc>",
+                      created_at      as "created_at!: DateTime<Utc>",
+                      updated_at      as "updated_at!: DateTime<Utc>"
+               FROM execution_processes
+               WHERE task_attempt_id = ? AND after_head_commit = ?
+               ORDER BY created_at ASC LIMIT 1"#,
+            task_attempt_id,
+            after_head_commit
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
     /// Create a new execution process
     pub async fn create(
         pool: &SqlitePool,
@@ -334,7 +416,7 @@ impl ExecutionProcess {
                     after_head_commit, status, exit_code, started_at, completed_at, created_at, updated_at
                 ) VALUES (?, ?, ?, ?, ?, NULL, ?, ?, ?, ?, ?, ?) RETURNING
                     id as "id!: Uuid", task_attempt_id as "task_attempt_id!: Uuid", run_reason as "run_reason!: ExecutionProcessRunReason", executor_action as "executor_action!: sqlx::types::Json<ExecutorActionField>", before_head_commit,
-                    after_head_commit, status as "status!: ExecutionProcessStatus", exit_code, dropped, started_at as "started_at!: DateTime<Utc>", completed_at as "completed_at?: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+                    after_head_commit, status as "status!: ExecutionProcessStatus", exit_code, dropped, preview_url, started_at as "started_at!: DateTime<Utc>", completed_at as "completed_at?: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
             process_id,
             data.task_attempt_id,
             data.run_reason,
@@ -427,6 +509,47 @@ impl ExecutionProcess {
         Ok(())
     }
 
+    /// Record the preview URL detected in a dev server's output
+    pub async fn update_preview_url(
+        pool: &SqlitePool,
+        id: Uuid,
+        preview_url: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE execution_processes
+               SET preview_url = $1
+               WHERE id = $2"#,
+            preview_url,
+            id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Record the OS process id of the spawned process group leader, so the process can still
+    /// be killed if the server restarts while it is running
+    pub async fn update_pid(pool: &SqlitePool, id: Uuid, pid: i64) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE execution_processes
+               SET pid = $1
+               WHERE id = $2"#,
+            pid,
+            id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// OS process id recorded when the process was spawned
+    pub async fn find_pid(pool: &SqlitePool, id: Uuid) -> Result<Option<i64>, sqlx::Error> {
+        sqlx::query_scalar!(r#"SELECT pid FROM execution_processes WHERE id = $1"#, id)
+            .fetch_optional(pool)
+            .await
+            .map(Option::flatten)
+    }
+
     pub async fn delete_by_task_attempt_id(
         pool: &SqlitePool,
         task_attempt_id: Uuid,
@@ -440,6 +563,15 @@ impl ExecutionProcess {
         Ok(())
     }
 
+    /// Prompt sent to the coding agent by this process, if it was a coding agent request
+    pub fn prompt(&self) -> Option<&str> {
+        match self.executor_action().ok()?.typ() {
+            ExecutorActionType::CodingAgentInitialRequest(request) => Some(&request.prompt),
+            ExecutorActionType::CodingAgentFollowUpRequest(request) => Some(&request.prompt),
+            ExecutorActionType::ScriptRequest(_) => None,
+        }
+    }
+
     pub fn executor_action(&self) -> Result<&ExecutorAction, anyhow::Error> {
         match &self.executor_action.0 {
             ExecutorActionField::ExecutorAction(action) => Ok(action),
@@ -449,6 +581,15 @@ impl ExecutionProcess {
         }
     }
 
+    /// Name of the project dev server this process runs; `None` for the main `dev_script` and
+    /// for processes that are not dev servers
+    pub fn dev_server_name(&self) -> Option<&str> {
+        match self.executor_action().ok()?.typ() {
+            ExecutorActionType::ScriptRequest(request) => request.name.as_deref(),
+            _ => None,
+        }
+    }
+
     /// Set restore boundary: drop processes newer than the specified process, undrop older/equal
     pub async fn set_restore_boundary(
         pool: &SqlitePool,
@@ -491,6 +632,23 @@ impl ExecutionProcess {
         Ok(result.rows_affected() as i64)
     }
 
+    /// Soft-drop every remaining process of an attempt
+    pub async fn drop_all_for_attempt(
+        pool: &SqlitePool,
+        task_attempt_id: Uuid,
+    ) -> Result<i64, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"UPDATE execution_processes
+               SET dropped = TRUE
+             WHERE task_attempt_id = $1
+               AND dropped = FALSE"#,
+            task_attempt_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected() as i64)
+    }
+
     /// Find the previous process's after_head_commit before the given boundary process
     pub async fn find_prev_after_head_commit(
         pool: &SqlitePool,
@@ -576,4 +734,35 @@ impl ExecutionProcess {
             )),
         }
     }
+
+    /// Fetch the initial coding agent request that started a task attempt.
+    /// The first execution process may be a setup script, in which case the request is
+    /// found further down its action chain.
+    pub async fn initial_request_for_attempt(
+        pool: &SqlitePool,
+        attempt_id: Uuid,
+    ) -> Result<CodingAgentInitialRequest, ExecutionProcessError> {
+        let processes = Self::find_by_task_attempt_id(pool, attempt_id, true).await?;
+        let first_process = processes.first().ok_or_else(|| {
+            ExecutionProcessError::ValidationError(
+                "Couldn't find initial execution process, has the attempt started?".to_string(),
+            )
+        })?;
+
+        let mut action = Some(
+            first_process
+                .executor_action()
+                .map_err(|e| ExecutionProcessError::ValidationError(e.to_string()))?,
+        );
+        while let Some(current) = action {
+            if let ExecutorActionType::CodingAgentInitialRequest(request) = current.typ() {
+                return Ok(request.clone());
+            }
+            action = current.next_action();
+        }
+
+        Err(ExecutionProcessError::ValidationError(
+            "Couldn't find initial coding agent request for attempt".to_string(),
+        ))
+    }
 }
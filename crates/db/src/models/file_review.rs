@@ -0,0 +1,69 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// A file of an attempt's diff that a reviewer has checked off
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct FileReview {
+    pub task_attempt_id: Uuid,
+    pub path: String,
+    /// Blob OID of the file's contents when it was reviewed, `None` if the change deleted it
+    pub content_oid: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl FileReview {
+    pub async fn find_by_task_attempt_id(
+        pool: &SqlitePool,
+        task_attempt_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            FileReview,
+            r#"SELECT task_attempt_id as "task_attempt_id!: Uuid", path, content_oid, created_at as "created_at!: DateTime<Utc>"
+               FROM file_reviews
+               WHERE task_attempt_id = $1
+               ORDER BY path ASC"#,
+            task_attempt_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Mark a file reviewed with the given contents; marking it again moves it to the new ones
+    pub async fn mark_reviewed(
+        pool: &SqlitePool,
+        task_attempt_id: Uuid,
+        path: &str,
+        content_oid: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"INSERT INTO file_reviews (task_attempt_id, path, content_oid)
+               VALUES ($1, $2, $3)
+               ON CONFLICT(task_attempt_id, path) DO UPDATE
+               SET content_oid = excluded.content_oid, created_at = datetime('now', 'subsec')"#,
+            task_attempt_id,
+            path,
+            content_oid
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn unmark_reviewed(
+        pool: &SqlitePool,
+        task_attempt_id: Uuid,
+        path: &str,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM file_reviews WHERE task_attempt_id = $1 AND path = $2",
+            task_attempt_id,
+            path
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -84,6 +84,28 @@ impl Image {
         .await
     }
 
+    pub async fn find_by_file_path(
+        pool: &SqlitePool,
+        file_path: &str,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Image,
+            r#"SELECT id as "id!: Uuid",
+                      file_path as "file_path!",
+                      original_name as "original_name!",
+                      mime_type,
+                      size_bytes as "size_bytes!",
+                      hash as "hash!",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM images
+               WHERE file_path = $1"#,
+            file_path
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
     pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
         sqlx::query_as!(
             Image,
@@ -134,7 +156,12 @@ impl Image {
         Ok(())
     }
 
-    pub async fn find_orphaned_images(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+    /// Images no task uses that were uploaded before `created_before`. Images attached to a
+    /// draft are not orphans: the draft's task or follow-up hasn't been sent yet.
+    pub async fn find_orphaned_images(
+        pool: &SqlitePool,
+        created_before: DateTime<Utc>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
         sqlx::query_as!(
             Image,
             r#"SELECT i.id as "id!: Uuid",
@@ -147,7 +174,13 @@ impl Image {
                       i.updated_at as "updated_at!: DateTime<Utc>"
                FROM images i
                LEFT JOIN task_images ti ON i.id = ti.image_id
-               WHERE ti.task_id IS NULL"#
+               WHERE ti.task_id IS NULL
+                 AND datetime(i.created_at) < datetime($1)
+                 AND NOT EXISTS (
+                     SELECT 1 FROM drafts d, json_each(d.image_ids) j
+                     WHERE lower(replace(j.value, '-', '')) = lower(hex(i.id))
+                 )"#,
+            created_before
         )
         .fetch_all(pool)
         .await
@@ -0,0 +1,263 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+pub const DEFAULT_LABEL_COLOR: &str = "#6b7280";
+
+/// A project-scoped category (bug, feature, chore, ...) that tasks can be tagged with
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct Label {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub name: String,
+    /// Hex color used to render the label, e.g. `#ef4444`
+    pub color: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct CreateLabel {
+    pub project_id: Uuid,
+    pub name: String,
+    pub color: Option<String>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct UpdateLabel {
+    pub name: Option<String>,
+    pub color: Option<String>,
+}
+
+/// How a multi-label task filter combines its labels
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "lowercase")]
+pub enum LabelMatch {
+    /// Tasks carrying every requested label
+    #[default]
+    All,
+    /// Tasks carrying at least one requested label
+    Any,
+}
+
+impl LabelMatch {
+    pub fn matches(self, task_labels: &[Label], wanted: &[Uuid]) -> bool {
+        let has = |id: &Uuid| task_labels.iter().any(|label| label.id == *id);
+        match self {
+            LabelMatch::All => wanted.iter().all(has),
+            LabelMatch::Any => wanted.iter().any(has),
+        }
+    }
+}
+
+/// Labels a task list is filtered by; with no labels every task matches
+#[derive(Debug, Clone, Default)]
+pub struct LabelFilter {
+    pub label_ids: Vec<Uuid>,
+    pub label_match: LabelMatch,
+}
+
+impl LabelFilter {
+    pub fn matches(&self, task_labels: &[Label]) -> bool {
+        self.label_ids.is_empty() || self.label_match.matches(task_labels, &self.label_ids)
+    }
+
+    /// The requested ids as a JSON array of simple-format uuids, which is how
+    /// `lower(hex(label_id))` renders a stored label id in SQL
+    pub fn label_ids_json(&self) -> String {
+        let ids: Vec<String> = self
+            .label_ids
+            .iter()
+            .map(|id| id.simple().to_string())
+            .collect();
+        serde_json::to_string(&ids).unwrap_or_else(|_| "[]".to_string())
+    }
+
+    /// How many of the requested labels a task must carry to match
+    pub fn required_matches(&self) -> i64 {
+        match self.label_match {
+            _ if self.label_ids.is_empty() => 0,
+            LabelMatch::All => self.label_ids.iter().collect::<HashSet<_>>().len() as i64,
+            LabelMatch::Any => 1,
+        }
+    }
+}
+
+/// `#rgb` or `#rrggbb`
+pub fn is_valid_label_color(color: &str) -> bool {
+    color
+        .strip_prefix('#')
+        .is_some_and(|hex| matches!(hex.len(), 3 | 6) && hex.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+impl Label {
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Label,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", name, color, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM labels
+               WHERE project_id = $1
+               ORDER BY name ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Label,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", name, color, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM labels
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn find_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Label,
+            r#"SELECT l.id as "id!: Uuid", l.project_id as "project_id!: Uuid", l.name, l.color, l.created_at as "created_at!: DateTime<Utc>", l.updated_at as "updated_at!: DateTime<Utc>"
+               FROM labels l
+               JOIN task_labels tl ON tl.label_id = l.id
+               WHERE tl.task_id = $1
+               ORDER BY l.name ASC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Labels of every task in a project, keyed by task id
+    pub async fn find_by_project_id_grouped_by_task(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<HashMap<Uuid, Vec<Self>>, sqlx::Error> {
+        let records = sqlx::query!(
+            r#"SELECT tl.task_id as "task_id!: Uuid", l.id as "id!: Uuid", l.project_id as "project_id!: Uuid", l.name, l.color, l.created_at as "created_at!: DateTime<Utc>", l.updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_labels tl
+               JOIN labels l ON l.id = tl.label_id
+               WHERE l.project_id = $1
+               ORDER BY l.name ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        let mut grouped: HashMap<Uuid, Vec<Self>> = HashMap::new();
+        for rec in records {
+            grouped.entry(rec.task_id).or_default().push(Label {
+                id: rec.id,
+                project_id: rec.project_id,
+                name: rec.name,
+                color: rec.color,
+                created_at: rec.created_at,
+                updated_at: rec.updated_at,
+            });
+        }
+        Ok(grouped)
+    }
+
+    /// Ids of the tasks currently carrying this label
+    pub async fn find_task_ids(pool: &SqlitePool, id: Uuid) -> Result<Vec<Uuid>, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT task_id as "task_id!: Uuid" FROM task_labels WHERE label_id = $1"#,
+            id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn create(pool: &SqlitePool, data: &CreateLabel) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let color = data.color.as_deref().unwrap_or(DEFAULT_LABEL_COLOR);
+        sqlx::query_as!(
+            Label,
+            r#"INSERT INTO labels (id, project_id, name, color)
+               VALUES ($1, $2, $3, $4)
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", name, color, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            data.project_id,
+            data.name,
+            color
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn update(
+        pool: &SqlitePool,
+        id: Uuid,
+        data: &UpdateLabel,
+    ) -> Result<Self, sqlx::Error> {
+        let existing = Self::find_by_id(pool, id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)?;
+
+        let name = data.name.as_ref().unwrap_or(&existing.name);
+        let color = data.color.as_ref().unwrap_or(&existing.color);
+
+        sqlx::query_as!(
+            Label,
+            r#"UPDATE labels
+               SET name = $2, color = $3, updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", name, color, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            name,
+            color
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM labels WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Attach the label to a task; attaching twice is a no-op
+    pub async fn add_to_task(
+        pool: &SqlitePool,
+        id: Uuid,
+        task_id: Uuid,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "INSERT OR IGNORE INTO task_labels (task_id, label_id) VALUES ($1, $2)",
+            task_id,
+            id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn remove_from_task(
+        pool: &SqlitePool,
+        id: Uuid,
+        task_id: Uuid,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM task_labels WHERE task_id = $1 AND label_id = $2",
+            task_id,
+            id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
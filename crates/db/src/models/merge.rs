@@ -1,10 +1,12 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use sqlx::{FromRow, SqlitePool, Type};
+use sqlx::{FromRow, SqliteConnection, SqlitePool, Type};
 use ts_rs::TS;
 use uuid::Uuid;
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS, Type)]
+use super::task_event::{TaskEvent, TaskEventType};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS, Type)]
 #[sqlx(type_name = "merge_status", rename_all = "snake_case")]
 #[serde(rename_all = "snake_case")]
 pub enum MergeStatus {
@@ -89,7 +91,8 @@ impl Merge {
         let id = Uuid::new_v4();
         let now = Utc::now();
 
-        sqlx::query_as!(
+        let mut tx = pool.begin().await?;
+        let row = sqlx::query_as!(
             MergeRow,
             r#"INSERT INTO merges (
                 id, task_attempt_id, merge_type, merge_commit, created_at, target_branch_name
@@ -113,9 +116,18 @@ impl Merge {
             now,
             target_branch_name
         )
-        .fetch_one(pool)
-        .await
-        .map(Into::into)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        Self::record_for_attempt(
+            &mut tx,
+            task_attempt_id,
+            TaskEventType::Merged,
+            target_branch_name,
+        )
+        .await?;
+        tx.commit().await?;
+        Ok(row.into())
     }
     /// Create a new PR record (when PR is opened)
     pub async fn create_pr(
@@ -128,7 +140,8 @@ impl Merge {
         let id = Uuid::new_v4();
         let now = Utc::now();
 
-        sqlx::query_as!(
+        let mut tx = pool.begin().await?;
+        let row = sqlx::query_as!(
             MergeRow,
             r#"INSERT INTO merges (
                 id, task_attempt_id, merge_type, pr_number, pr_url, pr_status, created_at, target_branch_name
@@ -153,9 +166,37 @@ impl Merge {
             now,
             target_branch_name
         )
-        .fetch_one(pool)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        Self::record_for_attempt(&mut tx, task_attempt_id, TaskEventType::PrCreated, pr_url)
+            .await?;
+        tx.commit().await?;
+        Ok(row.into())
+    }
+
+    async fn record_for_attempt(
+        conn: &mut SqliteConnection,
+        task_attempt_id: Uuid,
+        event_type: TaskEventType,
+        details: &str,
+    ) -> Result<(), sqlx::Error> {
+        let task_id = sqlx::query_scalar!(
+            r#"SELECT task_id as "task_id!: Uuid" FROM task_attempts WHERE id = $1"#,
+            task_attempt_id
+        )
+        .fetch_one(&mut *conn)
+        .await?;
+        TaskEvent::record(
+            conn,
+            task_id,
+            Some(task_attempt_id),
+            event_type,
+            None,
+            None,
+            Some(details),
+        )
         .await
-        .map(Into::into)
     }
 
     /// Get all open PRs for monitoring
@@ -184,6 +225,37 @@ impl Merge {
         Ok(rows.into_iter().map(Into::into).collect())
     }
 
+    /// Find the PR merge tracking `pr_url`, used to match incoming GitHub webhooks
+    pub async fn find_pr_by_url(
+        pool: &SqlitePool,
+        pr_url: &str,
+    ) -> Result<Option<PrMerge>, sqlx::Error> {
+        let row = sqlx::query_as!(
+            MergeRow,
+            r#"SELECT 
+                id as "id!: Uuid",
+                task_attempt_id as "task_attempt_id!: Uuid",
+                merge_type as "merge_type!: MergeType",
+                merge_commit,
+                pr_number,
+                pr_url,
+                pr_status as "pr_status?: MergeStatus",
+                pr_merged_at as "pr_merged_at?: DateTime<Utc>",
+                pr_merge_commit_sha,
+                created_at as "created_at!: DateTime<Utc>",
+                target_branch_name as "target_branch_name!: String"
+               FROM merges 
+               WHERE merge_type = 'pr' AND pr_url = $1
+               ORDER BY created_at DESC
+               LIMIT 1"#,
+            pr_url
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(row.map(Into::into))
+    }
+
     /// Update PR status for a task attempt
     pub async fn update_status(
         pool: &SqlitePool,
@@ -197,6 +269,8 @@ impl Merge {
             None
         };
 
+        let mut tx = pool.begin().await?;
+        TaskEvent::record_pr_status_change(&mut *tx, merge_id, &pr_status).await?;
         sqlx::query!(
             r#"UPDATE merges 
             SET pr_status = $1, 
@@ -208,8 +282,9 @@ impl Merge {
             merged_at,
             merge_id
         )
-        .execute(pool)
+        .execute(&mut *tx)
         .await?;
+        tx.commit().await?;
 
         Ok(())
     }
@@ -1,17 +1,23 @@
 pub mod agent;
+pub mod attempt_kv;
+pub mod attempt_queue;
 pub mod draft;
 pub mod execution_process;
 pub mod execution_process_logs;
 pub mod executor_session;
+pub mod file_review;
 pub mod image;
+pub mod label;
 pub mod merge;
 pub mod project;
+pub mod prompt_snippet;
 pub mod station_context;
 pub mod station_execution;
 pub mod station_transition;
 pub mod tag;
 pub mod task;
 pub mod task_attempt;
+pub mod task_event;
 pub mod task_station_execution;
 pub mod workflow;
 pub mod workflow_execution;
@@ -1,6 +1,7 @@
 use std::path::PathBuf;
 
 use chrono::{DateTime, Utc};
+use executors::actions::script::ScriptRequestLanguage;
 use serde::{Deserialize, Serialize};
 use sqlx::{FromRow, SqlitePool};
 use thiserror::Error;
@@ -31,6 +32,41 @@ pub struct Project {
     pub cleanup_script: Option<String>,
     pub copy_files: Option<String>,
     pub worktree_dir: Option<String>,
+    /// Remote attempt branches are pushed to, e.g. a fork. Defaults to the first remote.
+    pub push_remote: Option<String>,
+    /// Remote pull requests are opened against, e.g. upstream. Defaults to the first remote.
+    pub pr_remote: Option<String>,
+    /// Run the cleanup script even when the coding agent made no changes
+    pub always_run_cleanup: bool,
+    /// Comma-separated lockfile globs hashed to decide whether the setup script can be skipped.
+    /// Defaults to common lockfiles such as `package-lock.json` and `Cargo.lock`.
+    pub setup_cache_files: Option<String>,
+    /// Comma-separated paths (e.g. `node_modules`) restored from the setup cache instead of
+    /// re-running the setup script. Caching is disabled when unset.
+    pub setup_cache_artifacts: Option<String>,
+    /// Interpreter for the setup, dev server and cleanup scripts. Defaults to PowerShell on
+    /// Windows and Bash elsewhere.
+    pub script_language: Option<ScriptRequestLanguage>,
+    /// Regex matched against dev server output to find the preview URL. Its first capture
+    /// group (or the whole match) is used; a bare port number becomes `http://localhost:<port>`.
+    pub preview_url_pattern: Option<String>,
+    /// Branch new task attempts are based on when no base branch is given. Detected from the
+    /// repository when the project is created; attempts fall back to the repo's current
+    /// default branch when unset.
+    pub default_base_branch: Option<String>,
+    /// `.env`-style file whose variables are set for every process run in the project's
+    /// worktrees. Relative paths are resolved against the repository root.
+    pub env_file: Option<String>,
+    /// Refuse to start processes when `env_file` is missing or unreadable, instead of
+    /// warning and running without it
+    pub env_file_required: bool,
+    /// Dev servers that can run alongside `dev_script`, each started and stopped by name
+    #[ts(type = "Array<NamedScript>")]
+    pub dev_scripts: sqlx::types::Json<Vec<NamedScript>>,
+    /// Share one worktree between all attempts of a task, resetting it to the new attempt's
+    /// base instead of creating a fresh one, so setup output such as installed dependencies
+    /// is kept. Attempts of the same task can then no longer run in parallel.
+    pub reuse_worktree: bool,
 
     #[ts(type = "Date")]
     pub created_at: DateTime<Utc>,
@@ -38,16 +74,58 @@ pub struct Project {
     pub updated_at: DateTime<Utc>,
 }
 
+/// A script identified by a name, e.g. one of several dev servers
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+pub struct NamedScript {
+    pub name: String,
+    pub script: String,
+}
+
+/// Error message if any dev server lacks a name or script, or shares its name with another
+pub fn invalid_dev_scripts_message(dev_scripts: &[NamedScript]) -> Option<String> {
+    for (i, dev) in dev_scripts.iter().enumerate() {
+        if dev.name.trim().is_empty() {
+            return Some("Dev server names cannot be empty".to_string());
+        }
+        if dev.script.trim().is_empty() {
+            return Some(format!("Dev server '{}' has no script", dev.name));
+        }
+        if dev_scripts[..i].iter().any(|other| other.name == dev.name) {
+            return Some(format!("Dev server name '{}' is used twice", dev.name));
+        }
+    }
+    None
+}
+
 #[derive(Debug, Deserialize, TS)]
 pub struct CreateProject {
     pub name: String,
     pub git_repo_path: String,
     pub use_existing_repo: bool,
+    /// Clone this URL into `git_repo_path` instead of initializing an empty repo. Only used
+    /// when `use_existing_repo` is false.
+    #[serde(default)]
+    pub git_url: Option<String>,
     pub setup_script: Option<String>,
     pub dev_script: Option<String>,
     pub cleanup_script: Option<String>,
     pub copy_files: Option<String>,
     pub worktree_dir: Option<String>,
+    pub push_remote: Option<String>,
+    pub pr_remote: Option<String>,
+    #[serde(default)]
+    pub always_run_cleanup: bool,
+    pub setup_cache_files: Option<String>,
+    pub setup_cache_artifacts: Option<String>,
+    pub script_language: Option<ScriptRequestLanguage>,
+    pub preview_url_pattern: Option<String>,
+    pub env_file: Option<String>,
+    #[serde(default)]
+    pub env_file_required: bool,
+    #[serde(default)]
+    pub dev_scripts: Vec<NamedScript>,
+    #[serde(default)]
+    pub reuse_worktree: bool,
 }
 
 #[derive(Debug, Deserialize, TS)]
@@ -59,6 +137,21 @@ pub struct UpdateProject {
     pub cleanup_script: Option<String>,
     pub copy_files: Option<String>,
     pub worktree_dir: Option<String>,
+    pub push_remote: Option<String>,
+    pub pr_remote: Option<String>,
+    /// Leaves the current setting unchanged when omitted
+    pub always_run_cleanup: Option<bool>,
+    pub setup_cache_files: Option<String>,
+    pub setup_cache_artifacts: Option<String>,
+    pub script_language: Option<ScriptRequestLanguage>,
+    pub preview_url_pattern: Option<String>,
+    pub env_file: Option<String>,
+    /// Leaves the current setting unchanged when omitted
+    pub env_file_required: Option<bool>,
+    /// Leaves the current dev servers unchanged when omitted
+    pub dev_scripts: Option<Vec<NamedScript>>,
+    /// Leaves the current setting unchanged when omitted
+    pub reuse_worktree: Option<bool>,
 }
 
 #[derive(Debug, Serialize, TS)]
@@ -76,6 +169,12 @@ pub enum SearchMatchType {
 }
 
 impl Project {
+    /// Interpreter for this project's scripts; `None` runs them in PowerShell on Windows and the
+    /// default shell elsewhere
+    pub fn script_language(&self) -> Option<ScriptRequestLanguage> {
+        self.script_language.clone()
+    }
+
     pub async fn count(pool: &SqlitePool) -> Result<i64, sqlx::Error> {
         sqlx::query_scalar!(r#"SELECT COUNT(*) as "count!: i64" FROM projects"#)
             .fetch_one(pool)
@@ -85,7 +184,7 @@ impl Project {
     pub async fn find_all(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
         sqlx::query_as!(
             Project,
-            r#"SELECT id as "id!: Uuid", name, git_repo_path, setup_script, dev_script, cleanup_script, copy_files, worktree_dir, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>" FROM projects ORDER BY created_at DESC"#
+            r#"SELECT id as "id!: Uuid", name, git_repo_path, setup_script, dev_script, cleanup_script, copy_files, worktree_dir, push_remote, pr_remote, always_run_cleanup as "always_run_cleanup!: bool", setup_cache_files, setup_cache_artifacts, script_language as "script_language: ScriptRequestLanguage", preview_url_pattern, default_base_branch, env_file, env_file_required as "env_file_required!: bool", dev_scripts as "dev_scripts!: sqlx::types::Json<Vec<NamedScript>>", reuse_worktree as "reuse_worktree!: bool", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>" FROM projects ORDER BY created_at DESC"#
         )
         .fetch_all(pool)
         .await
@@ -96,7 +195,7 @@ impl Project {
         sqlx::query_as!(
             Project,
             r#"
-            SELECT p.id as "id!: Uuid", p.name, p.git_repo_path, p.setup_script, p.dev_script, p.cleanup_script, p.copy_files, p.worktree_dir,
+            SELECT p.id as "id!: Uuid", p.name, p.git_repo_path, p.setup_script, p.dev_script, p.cleanup_script, p.copy_files, p.worktree_dir, p.push_remote, p.pr_remote, p.always_run_cleanup as "always_run_cleanup!: bool", p.setup_cache_files, p.setup_cache_artifacts, p.script_language as "script_language: ScriptRequestLanguage", p.preview_url_pattern, p.default_base_branch, p.env_file, p.env_file_required as "env_file_required!: bool", p.dev_scripts as "dev_scripts!: sqlx::types::Json<Vec<NamedScript>>", p.reuse_worktree as "reuse_worktree!: bool",
                    p.created_at as "created_at!: DateTime<Utc>", p.updated_at as "updated_at!: DateTime<Utc>"
             FROM projects p
             WHERE p.id IN (
@@ -116,7 +215,7 @@ impl Project {
     pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
         sqlx::query_as!(
             Project,
-            r#"SELECT id as "id!: Uuid", name, git_repo_path, setup_script, dev_script, cleanup_script, copy_files, worktree_dir, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>" FROM projects WHERE id = $1"#,
+            r#"SELECT id as "id!: Uuid", name, git_repo_path, setup_script, dev_script, cleanup_script, copy_files, worktree_dir, push_remote, pr_remote, always_run_cleanup as "always_run_cleanup!: bool", setup_cache_files, setup_cache_artifacts, script_language as "script_language: ScriptRequestLanguage", preview_url_pattern, default_base_branch, env_file, env_file_required as "env_file_required!: bool", dev_scripts as "dev_scripts!: sqlx::types::Json<Vec<NamedScript>>", reuse_worktree as "reuse_worktree!: bool", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>" FROM projects WHERE id = $1"#,
             id
         )
         .fetch_optional(pool)
@@ -129,7 +228,7 @@ impl Project {
     ) -> Result<Option<Self>, sqlx::Error> {
         sqlx::query_as!(
             Project,
-            r#"SELECT id as "id!: Uuid", name, git_repo_path, setup_script, dev_script, cleanup_script, copy_files, worktree_dir, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>" FROM projects WHERE git_repo_path = $1"#,
+            r#"SELECT id as "id!: Uuid", name, git_repo_path, setup_script, dev_script, cleanup_script, copy_files, worktree_dir, push_remote, pr_remote, always_run_cleanup as "always_run_cleanup!: bool", setup_cache_files, setup_cache_artifacts, script_language as "script_language: ScriptRequestLanguage", preview_url_pattern, default_base_branch, env_file, env_file_required as "env_file_required!: bool", dev_scripts as "dev_scripts!: sqlx::types::Json<Vec<NamedScript>>", reuse_worktree as "reuse_worktree!: bool", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>" FROM projects WHERE git_repo_path = $1"#,
             git_repo_path
         )
         .fetch_optional(pool)
@@ -143,7 +242,7 @@ impl Project {
     ) -> Result<Option<Self>, sqlx::Error> {
         sqlx::query_as!(
             Project,
-            r#"SELECT id as "id!: Uuid", name, git_repo_path, setup_script, dev_script, cleanup_script, copy_files, worktree_dir, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>" FROM projects WHERE git_repo_path = $1 AND id != $2"#,
+            r#"SELECT id as "id!: Uuid", name, git_repo_path, setup_script, dev_script, cleanup_script, copy_files, worktree_dir, push_remote, pr_remote, always_run_cleanup as "always_run_cleanup!: bool", setup_cache_files, setup_cache_artifacts, script_language as "script_language: ScriptRequestLanguage", preview_url_pattern, default_base_branch, env_file, env_file_required as "env_file_required!: bool", dev_scripts as "dev_scripts!: sqlx::types::Json<Vec<NamedScript>>", reuse_worktree as "reuse_worktree!: bool", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>" FROM projects WHERE git_repo_path = $1 AND id != $2"#,
             git_repo_path,
             exclude_id
         )
@@ -156,9 +255,10 @@ impl Project {
         data: &CreateProject,
         project_id: Uuid,
     ) -> Result<Self, sqlx::Error> {
+        let dev_scripts = sqlx::types::Json(&data.dev_scripts);
         sqlx::query_as!(
             Project,
-            r#"INSERT INTO projects (id, name, git_repo_path, setup_script, dev_script, cleanup_script, copy_files, worktree_dir) VALUES ($1, $2, $3, $4, $5, $6, $7, $8) RETURNING id as "id!: Uuid", name, git_repo_path, setup_script, dev_script, cleanup_script, copy_files, worktree_dir, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            r#"INSERT INTO projects (id, name, git_repo_path, setup_script, dev_script, cleanup_script, copy_files, worktree_dir, push_remote, pr_remote, always_run_cleanup, setup_cache_files, setup_cache_artifacts, script_language, preview_url_pattern, env_file, env_file_required, dev_scripts, reuse_worktree) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19) RETURNING id as "id!: Uuid", name, git_repo_path, setup_script, dev_script, cleanup_script, copy_files, worktree_dir, push_remote, pr_remote, always_run_cleanup as "always_run_cleanup!: bool", setup_cache_files, setup_cache_artifacts, script_language as "script_language: ScriptRequestLanguage", preview_url_pattern, default_base_branch, env_file, env_file_required as "env_file_required!: bool", dev_scripts as "dev_scripts!: sqlx::types::Json<Vec<NamedScript>>", reuse_worktree as "reuse_worktree!: bool", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
             project_id,
             data.name,
             data.git_repo_path,
@@ -166,7 +266,18 @@ impl Project {
             data.dev_script,
             data.cleanup_script,
             data.copy_files,
-            data.worktree_dir
+            data.worktree_dir,
+            data.push_remote,
+            data.pr_remote,
+            data.always_run_cleanup,
+            data.setup_cache_files,
+            data.setup_cache_artifacts,
+            data.script_language,
+            data.preview_url_pattern,
+            data.env_file,
+            data.env_file_required,
+            dev_scripts,
+            data.reuse_worktree
         )
         .fetch_one(pool)
         .await
@@ -183,10 +294,22 @@ impl Project {
         cleanup_script: Option<String>,
         copy_files: Option<String>,
         worktree_dir: Option<String>,
+        push_remote: Option<String>,
+        pr_remote: Option<String>,
+        always_run_cleanup: bool,
+        setup_cache_files: Option<String>,
+        setup_cache_artifacts: Option<String>,
+        script_language: Option<ScriptRequestLanguage>,
+        preview_url_pattern: Option<String>,
+        env_file: Option<String>,
+        env_file_required: bool,
+        dev_scripts: &[NamedScript],
+        reuse_worktree: bool,
     ) -> Result<Self, sqlx::Error> {
+        let dev_scripts = sqlx::types::Json(dev_scripts);
         sqlx::query_as!(
             Project,
-            r#"UPDATE projects SET name = $2, git_repo_path = $3, setup_script = $4, dev_script = $5, cleanup_script = $6, copy_files = $7, worktree_dir = $8 WHERE id = $1 RETURNING id as "id!: Uuid", name, git_repo_path, setup_script, dev_script, cleanup_script, copy_files, worktree_dir, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            r#"UPDATE projects SET name = $2, git_repo_path = $3, setup_script = $4, dev_script = $5, cleanup_script = $6, copy_files = $7, worktree_dir = $8, push_remote = $9, pr_remote = $10, always_run_cleanup = $11, setup_cache_files = $12, setup_cache_artifacts = $13, script_language = $14, preview_url_pattern = $15, env_file = $16, env_file_required = $17, dev_scripts = $18, reuse_worktree = $19 WHERE id = $1 RETURNING id as "id!: Uuid", name, git_repo_path, setup_script, dev_script, cleanup_script, copy_files, worktree_dir, push_remote, pr_remote, always_run_cleanup as "always_run_cleanup!: bool", setup_cache_files, setup_cache_artifacts, script_language as "script_language: ScriptRequestLanguage", preview_url_pattern, default_base_branch, env_file, env_file_required as "env_file_required!: bool", dev_scripts as "dev_scripts!: sqlx::types::Json<Vec<NamedScript>>", reuse_worktree as "reuse_worktree!: bool", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
             id,
             name,
             git_repo_path,
@@ -194,12 +317,38 @@ impl Project {
             dev_script,
             cleanup_script,
             copy_files,
-            worktree_dir
+            worktree_dir,
+            push_remote,
+            pr_remote,
+            always_run_cleanup,
+            setup_cache_files,
+            setup_cache_artifacts,
+            script_language,
+            preview_url_pattern,
+            env_file,
+            env_file_required,
+            dev_scripts,
+            reuse_worktree
         )
         .fetch_one(pool)
         .await
     }
 
+    pub async fn update_default_base_branch(
+        pool: &SqlitePool,
+        id: Uuid,
+        default_base_branch: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE projects SET default_base_branch = $2 WHERE id = $1",
+            id,
+            default_base_branch
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
     pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
         let result = sqlx::query!("DELETE FROM projects WHERE id = $1", id)
             .execute(pool)
@@ -221,4 +370,16 @@ impl Project {
 
         Ok(result.count > 0)
     }
+
+    /// The dev server script called `name`, or `dev_script` when no name is given
+    pub fn dev_script_named(&self, name: Option<&str>) -> Option<&str> {
+        match name {
+            Some(name) => self
+                .dev_scripts
+                .iter()
+                .find(|dev| dev.name == name)
+                .map(|dev| dev.script.as_str()),
+            None => self.dev_script.as_deref(),
+        }
+    }
 }
@@ -0,0 +1,122 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// A reusable prompt fragment, pulled into prompts with `{{snippet:name}}`
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct PromptSnippet {
+    pub id: Uuid,
+    /// `None` for global snippets, available in every project
+    pub project_id: Option<Uuid>,
+    pub name: String,
+    pub content: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct CreatePromptSnippet {
+    /// Omit to create a global snippet
+    pub project_id: Option<Uuid>,
+    pub name: String,
+    pub content: String,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct UpdatePromptSnippet {
+    pub name: Option<String>,
+    pub content: Option<String>,
+}
+
+/// Letters, digits, `-`, `_` and `.`, so a name can always be written inside `{{snippet:...}}`
+pub fn is_valid_snippet_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+impl PromptSnippet {
+    /// Global snippets, plus the project's own when `project_id` is given
+    pub async fn find_for_project(
+        pool: &SqlitePool,
+        project_id: Option<Uuid>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            PromptSnippet,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id: Uuid", name, content, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM prompt_snippets
+               WHERE project_id IS NULL OR project_id = $1
+               ORDER BY name ASC, project_id IS NULL ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            PromptSnippet,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id: Uuid", name, content, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM prompt_snippets
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        data: &CreatePromptSnippet,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            PromptSnippet,
+            r#"INSERT INTO prompt_snippets (id, project_id, name, content)
+               VALUES ($1, $2, $3, $4)
+               RETURNING id as "id!: Uuid", project_id as "project_id: Uuid", name, content, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            data.project_id,
+            data.name,
+            data.content
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn update(
+        pool: &SqlitePool,
+        id: Uuid,
+        data: &UpdatePromptSnippet,
+    ) -> Result<Self, sqlx::Error> {
+        let existing = Self::find_by_id(pool, id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)?;
+
+        let name = data.name.as_ref().unwrap_or(&existing.name);
+        let content = data.content.as_ref().unwrap_or(&existing.content);
+
+        sqlx::query_as!(
+            PromptSnippet,
+            r#"UPDATE prompt_snippets
+               SET name = $2, content = $3, updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid", project_id as "project_id: Uuid", name, content, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            name,
+            content
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM prompt_snippets WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -10,6 +10,8 @@ pub struct StationExecution {
     pub workflow_execution_id: Uuid,
     pub station_id: Uuid,
     pub execution_process_id: Option<Uuid>,
+    /// Transition taken from the previous station into this one, `None` for the first station
+    pub transition_id: Option<Uuid>,
     pub status: String, // 'pending', 'running', 'completed', 'failed', 'skipped'
     pub output_data: Option<String>, // JSON data for station output_context_keys
     pub started_at: Option<DateTime<Utc>>,
@@ -24,6 +26,7 @@ pub struct CreateStationExecution {
     pub station_id: Uuid,
     pub status: String,
     pub execution_process_id: Option<Uuid>,
+    pub transition_id: Option<Uuid>,
 }
 
 #[derive(Debug, Deserialize, TS)]
@@ -48,6 +51,7 @@ impl StationExecution {
                 workflow_execution_id as "workflow_execution_id!: Uuid",
                 station_id as "station_id!: Uuid",
                 execution_process_id as "execution_process_id: Uuid",
+                transition_id as "transition_id: Uuid",
                 status,
                 output_data,
                 started_at as "started_at: DateTime<Utc>",
@@ -75,6 +79,7 @@ impl StationExecution {
                 workflow_execution_id as "workflow_execution_id!: Uuid",
                 station_id as "station_id!: Uuid",
                 execution_process_id as "execution_process_id: Uuid",
+                transition_id as "transition_id: Uuid",
                 status,
                 output_data,
                 started_at as "started_at: DateTime<Utc>",
@@ -102,6 +107,7 @@ impl StationExecution {
                 workflow_execution_id as "workflow_execution_id!: Uuid",
                 station_id as "station_id!: Uuid",
                 execution_process_id as "execution_process_id: Uuid",
+                transition_id as "transition_id: Uuid",
                 status,
                 output_data,
                 started_at as "started_at: DateTime<Utc>",
@@ -126,6 +132,7 @@ impl StationExecution {
                 workflow_execution_id as "workflow_execution_id!: Uuid",
                 station_id as "station_id!: Uuid",
                 execution_process_id as "execution_process_id: Uuid",
+                transition_id as "transition_id: Uuid",
                 status,
                 output_data,
                 started_at as "started_at: DateTime<Utc>",
@@ -147,13 +154,14 @@ impl StationExecution {
     ) -> Result<Self, sqlx::Error> {
         sqlx::query_as!(
             StationExecution,
-            r#"INSERT INTO station_executions (id, workflow_execution_id, station_id, execution_process_id, status)
-               VALUES ($1, $2, $3, $4, $5)
+            r#"INSERT INTO station_executions (id, workflow_execution_id, station_id, execution_process_id, status, transition_id)
+               VALUES ($1, $2, $3, $4, $5, $6)
                RETURNING
                 id as "id!: Uuid",
                 workflow_execution_id as "workflow_execution_id!: Uuid",
                 station_id as "station_id!: Uuid",
                 execution_process_id as "execution_process_id: Uuid",
+                transition_id as "transition_id: Uuid",
                 status,
                 output_data,
                 started_at as "started_at: DateTime<Utc>",
@@ -164,7 +172,8 @@ impl StationExecution {
             data.workflow_execution_id,
             data.station_id,
             data.execution_process_id,
-            data.status
+            data.status,
+            data.transition_id
         )
         .fetch_one(pool)
         .await
@@ -200,6 +209,7 @@ impl StationExecution {
                 workflow_execution_id as "workflow_execution_id!: Uuid",
                 station_id as "station_id!: Uuid",
                 execution_process_id as "execution_process_id: Uuid",
+                transition_id as "transition_id: Uuid",
                 status,
                 output_data,
                 started_at as "started_at: DateTime<Utc>",
@@ -1,11 +1,19 @@
+use std::collections::HashSet;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::{Executor, FromRow, Sqlite, SqlitePool, Type};
 use strum_macros::{Display, EnumString};
 use ts_rs::TS;
+use utils::pagination::{Page, PageQuery};
 use uuid::Uuid;
 
-use super::{project::Project, task_attempt::TaskAttempt};
+use super::{
+    label::{Label, LabelFilter},
+    project::Project,
+    task_attempt::TaskAttempt,
+    task_event::{TaskEvent, TaskEventType},
+};
 
 #[derive(Debug, Clone, Type, Serialize, Deserialize, PartialEq, TS, EnumString, Display)]
 #[sqlx(type_name = "task_status", rename_all = "lowercase")]
@@ -30,6 +38,11 @@ pub struct Task {
     pub agent_id: Option<Uuid>, // Foreign key to Agent
     pub workflow_id: Option<Uuid>, // Foreign key to Workflow
     pub current_station_id: Option<Uuid>, // Foreign key to WorkflowStation
+    pub parent_task_id: Option<Uuid>, // Foreign key to the Task this was split from
+    pub blocked_on_subtasks: bool,
+    /// Queued attempts of higher priority tasks start first. Defaults to 0, so equal
+    /// priorities keep first-in, first-out order.
+    pub priority: i32,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -42,7 +55,10 @@ pub struct TaskWithAttemptStatus {
     pub has_in_progress_attempt: bool,
     pub has_merged_attempt: bool,
     pub last_attempt_failed: bool,
+    /// The latest attempt's agent ended its turn by asking the user something
+    pub needs_input: bool,
     pub executor: String,
+    pub labels: Vec<Label>,
 }
 
 impl std::ops::Deref for TaskWithAttemptStatus {
@@ -63,6 +79,7 @@ pub struct TaskRelationships {
     pub parent_task: Option<Task>,    // The task that owns this attempt
     pub current_attempt: TaskAttempt, // The attempt we're viewing
     pub children: Vec<Task>,          // Tasks created by this attempt
+    pub subtasks: Vec<Task>,          // Tasks the current task was split into
 }
 
 #[derive(Debug, Serialize, Deserialize, TS)]
@@ -123,6 +140,58 @@ impl Task {
         pool: &SqlitePool,
         project_id: Uuid,
     ) -> Result<Vec<TaskWithAttemptStatus>, sqlx::Error> {
+        // A negative LIMIT is unbounded in SQLite
+        Self::fetch_with_attempt_status(pool, project_id, &LabelFilter::default(), -1, 0).await
+    }
+
+    /// One page of a project's tasks carrying the filtered labels, newest first with the id as a
+    /// tie-breaker so pages stay stable
+    pub async fn fetch_page_with_attempt_status(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        label_filter: &LabelFilter,
+        page: &PageQuery,
+    ) -> Result<Page<TaskWithAttemptStatus>, sqlx::Error> {
+        let tasks = Self::fetch_with_attempt_status(
+            pool,
+            project_id,
+            label_filter,
+            i64::from(page.limit()),
+            i64::from(page.offset()),
+        )
+        .await?;
+
+        let label_ids = label_filter.label_ids_json();
+        let required_matches = label_filter.required_matches();
+        let total = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) AS "count!: i64"
+               FROM tasks t
+               WHERE t.project_id = $1
+                 AND (
+                   SELECT COUNT(*)
+                     FROM task_labels tl
+                    WHERE tl.task_id = t.id
+                      AND lower(hex(tl.label_id)) IN (SELECT value FROM json_each($2))
+                 ) >= $3"#,
+            project_id,
+            label_ids,
+            required_matches
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(Page::new(tasks, total as u32, page))
+    }
+
+    async fn fetch_with_attempt_status(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        label_filter: &LabelFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<TaskWithAttemptStatus>, sqlx::Error> {
+        let label_ids = label_filter.label_ids_json();
+        let required_matches = label_filter.required_matches();
         let records = sqlx::query!(
             r#"SELECT
   t.id                            AS "id!: Uuid",
@@ -134,6 +203,9 @@ impl Task {
   t.agent_id                      AS "agent_id: Uuid",
   t.workflow_id                   AS "workflow_id: Uuid",
   t.current_station_id            AS "current_station_id: Uuid",
+  t.parent_task_id                AS "parent_task_id: Uuid",
+  t.blocked_on_subtasks           AS "blocked_on_subtasks!: bool",
+  t.priority                      AS "priority!: i32",
   t.created_at                    AS "created_at!: DateTime<Utc>",
   t.updated_at                    AS "updated_at!: DateTime<Utc>",
 
@@ -165,16 +237,37 @@ impl Task {
       WHERE ta.task_id = t.id
      ORDER BY ta.created_at DESC
       LIMIT 1
-    )                               AS "executor!: String"
+    )                               AS "executor!: String",
+
+  ( SELECT ta.needs_input
+      FROM task_attempts ta
+      WHERE ta.task_id = t.id
+     ORDER BY ta.created_at DESC
+      LIMIT 1
+    )                               AS "needs_input: bool"
 
 FROM tasks t
 WHERE t.project_id = $1
-ORDER BY t.created_at DESC"#,
-            project_id
+  AND (
+    SELECT COUNT(*)
+      FROM task_labels tl
+     WHERE tl.task_id = t.id
+       AND lower(hex(tl.label_id)) IN (SELECT value FROM json_each($2))
+  ) >= $3
+ORDER BY t.created_at DESC, t.id DESC
+LIMIT $4 OFFSET $5"#,
+            project_id,
+            label_ids,
+            required_matches,
+            limit,
+            offset
         )
         .fetch_all(pool)
         .await?;
 
+        let mut labels_by_task =
+            Label::find_by_project_id_grouped_by_task(pool, project_id).await?;
+
         let tasks = records
             .into_iter()
             .map(|rec| TaskWithAttemptStatus {
@@ -188,13 +281,18 @@ ORDER BY t.created_at DESC"#,
                     agent_id: rec.agent_id,
                     workflow_id: rec.workflow_id,
                     current_station_id: rec.current_station_id,
+                    parent_task_id: rec.parent_task_id,
+                    blocked_on_subtasks: rec.blocked_on_subtasks,
+                    priority: rec.priority,
                     created_at: rec.created_at,
                     updated_at: rec.updated_at,
                 },
                 has_in_progress_attempt: rec.has_in_progress_attempt != 0,
                 has_merged_attempt: false, // TODO use merges table
                 last_attempt_failed: rec.last_attempt_failed != 0,
+                needs_input: rec.needs_input.unwrap_or(false),
                 executor: rec.executor,
+                labels: labels_by_task.remove(&rec.id).unwrap_or_default(),
             })
             .collect();
 
@@ -204,7 +302,7 @@ ORDER BY t.created_at DESC"#,
     pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
         sqlx::query_as!(
             Task,
-            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_task_attempt as "parent_task_attempt: Uuid", agent_id as "agent_id: Uuid", workflow_id as "workflow_id: Uuid", current_station_id as "current_station_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_task_attempt as "parent_task_attempt: Uuid", agent_id as "agent_id: Uuid", workflow_id as "workflow_id: Uuid", current_station_id as "current_station_id: Uuid", parent_task_id as "parent_task_id: Uuid", blocked_on_subtasks as "blocked_on_subtasks!: bool", priority as "priority!: i32", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
                FROM tasks
                WHERE id = $1"#,
             id
@@ -216,7 +314,7 @@ ORDER BY t.created_at DESC"#,
     pub async fn find_by_rowid(pool: &SqlitePool, rowid: i64) -> Result<Option<Self>, sqlx::Error> {
         sqlx::query_as!(
             Task,
-            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_task_attempt as "parent_task_attempt: Uuid", agent_id as "agent_id: Uuid", workflow_id as "workflow_id: Uuid", current_station_id as "current_station_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_task_attempt as "parent_task_attempt: Uuid", agent_id as "agent_id: Uuid", workflow_id as "workflow_id: Uuid", current_station_id as "current_station_id: Uuid", parent_task_id as "parent_task_id: Uuid", blocked_on_subtasks as "blocked_on_subtasks!: bool", priority as "priority!: i32", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
                FROM tasks
                WHERE rowid = $1"#,
             rowid
@@ -232,7 +330,7 @@ ORDER BY t.created_at DESC"#,
     ) -> Result<Option<Self>, sqlx::Error> {
         sqlx::query_as!(
             Task,
-            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_task_attempt as "parent_task_attempt: Uuid", agent_id as "agent_id: Uuid", workflow_id as "workflow_id: Uuid", current_station_id as "current_station_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_task_attempt as "parent_task_attempt: Uuid", agent_id as "agent_id: Uuid", workflow_id as "workflow_id: Uuid", current_station_id as "current_station_id: Uuid", parent_task_id as "parent_task_id: Uuid", blocked_on_subtasks as "blocked_on_subtasks!: bool", priority as "priority!: i32", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
                FROM tasks
                WHERE id = $1 AND project_id = $2"#,
             id,
@@ -247,11 +345,12 @@ ORDER BY t.created_at DESC"#,
         data: &CreateTask,
         task_id: Uuid,
     ) -> Result<Self, sqlx::Error> {
-        sqlx::query_as!(
+        let mut tx = pool.begin().await?;
+        let task = sqlx::query_as!(
             Task,
             r#"INSERT INTO tasks (id, project_id, title, description, status, parent_task_attempt, agent_id, workflow_id)
                VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
-               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_task_attempt as "parent_task_attempt: Uuid", agent_id as "agent_id: Uuid", workflow_id as "workflow_id: Uuid", current_station_id as "current_station_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_task_attempt as "parent_task_attempt: Uuid", agent_id as "agent_id: Uuid", workflow_id as "workflow_id: Uuid", current_station_id as "current_station_id: Uuid", parent_task_id as "parent_task_id: Uuid", blocked_on_subtasks as "blocked_on_subtasks!: bool", priority as "priority!: i32", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
             task_id,
             data.project_id,
             data.title,
@@ -261,8 +360,12 @@ ORDER BY t.created_at DESC"#,
             data.agent_id,
             data.workflow_id
         )
-        .fetch_one(pool)
-        .await
+        .fetch_one(&mut *tx)
+        .await?;
+
+        Self::record_created(&mut *tx, &task).await?;
+        tx.commit().await?;
+        Ok(task)
     }
 
     pub async fn update(
@@ -277,12 +380,14 @@ ORDER BY t.created_at DESC"#,
         workflow_id: Option<Uuid>,
         current_station_id: Option<Uuid>,
     ) -> Result<Self, sqlx::Error> {
-        sqlx::query_as!(
+        let previous_status = Self::find_by_id(pool, id).await?.map(|task| task.status);
+        let mut tx = pool.begin().await?;
+        let task = sqlx::query_as!(
             Task,
             r#"UPDATE tasks
                SET title = $3, description = $4, status = $5, parent_task_attempt = $6, agent_id = $7, workflow_id = $8, current_station_id = $9
                WHERE id = $1 AND project_id = $2
-               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_task_attempt as "parent_task_attempt: Uuid", agent_id as "agent_id: Uuid", workflow_id as "workflow_id: Uuid", current_station_id as "current_station_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_task_attempt as "parent_task_attempt: Uuid", agent_id as "agent_id: Uuid", workflow_id as "workflow_id: Uuid", current_station_id as "current_station_id: Uuid", parent_task_id as "parent_task_id: Uuid", blocked_on_subtasks as "blocked_on_subtasks!: bool", priority as "priority!: i32", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
             id,
             project_id,
             title,
@@ -293,8 +398,17 @@ ORDER BY t.created_at DESC"#,
             workflow_id,
             current_station_id
         )
-        .fetch_one(pool)
-        .await
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if let Some(previous_status) = previous_status {
+            Self::record_status_change(&mut *tx, id, previous_status, task.status.clone()).await?;
+        }
+        tx.commit().await?;
+        if let Some(parent_task_id) = task.parent_task_id {
+            Self::unblock_if_subtasks_complete(pool, parent_task_id).await?;
+        }
+        Ok(task)
     }
 
     pub async fn update_status(
@@ -302,16 +416,202 @@ ORDER BY t.created_at DESC"#,
         id: Uuid,
         status: TaskStatus,
     ) -> Result<(), sqlx::Error> {
+        let Some(previous) = Self::find_by_id(pool, id).await? else {
+            return Ok(());
+        };
+        let mut tx = pool.begin().await?;
         sqlx::query!(
             "UPDATE tasks SET status = $2, updated_at = CURRENT_TIMESTAMP WHERE id = $1",
             id,
             status
         )
+        .execute(&mut *tx)
+        .await?;
+
+        Self::record_status_change(&mut *tx, id, previous.status, status).await?;
+        tx.commit().await?;
+        if let Some(parent_task_id) = previous.parent_task_id {
+            Self::unblock_if_subtasks_complete(pool, parent_task_id).await?;
+        }
+        Ok(())
+    }
+
+    async fn record_created<'e, E>(executor: E, task: &Task) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
+        TaskEvent::record(
+            executor,
+            task.id,
+            None,
+            TaskEventType::Created,
+            None,
+            Some(task.status.clone()),
+            None,
+        )
+        .await
+    }
+
+    async fn record_status_change<'e, E>(
+        executor: E,
+        id: Uuid,
+        from: TaskStatus,
+        to: TaskStatus,
+    ) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
+        if from == to {
+            return Ok(());
+        }
+        TaskEvent::record(
+            executor,
+            id,
+            None,
+            TaskEventType::StatusChanged,
+            Some(from),
+            Some(to),
+            None,
+        )
+        .await
+    }
+
+    /// Create a task split out of `parent`, optionally also linked to the parent's attempt
+    pub async fn create_subtask(
+        pool: &SqlitePool,
+        parent: &Task,
+        title: &str,
+        description: Option<&str>,
+        parent_task_attempt: Option<Uuid>,
+        task_id: Uuid,
+    ) -> Result<Self, sqlx::Error> {
+        let mut tx = pool.begin().await?;
+        let task = sqlx::query_as!(
+            Task,
+            r#"INSERT INTO tasks (id, project_id, title, description, status, parent_task_attempt, parent_task_id)
+               VALUES ($1, $2, $3, $4, $5, $6, $7)
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_task_attempt as "parent_task_attempt: Uuid", agent_id as "agent_id: Uuid", workflow_id as "workflow_id: Uuid", current_station_id as "current_station_id: Uuid", parent_task_id as "parent_task_id: Uuid", blocked_on_subtasks as "blocked_on_subtasks!: bool", priority as "priority!: i32", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            task_id,
+            parent.project_id,
+            title,
+            description,
+            TaskStatus::Todo as TaskStatus,
+            parent_task_attempt,
+            parent.id
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        Self::record_created(&mut *tx, &task).await?;
+        tx.commit().await?;
+        Ok(task)
+    }
+
+    pub async fn set_parent_task(
+        pool: &SqlitePool,
+        id: Uuid,
+        parent_task_id: Option<Uuid>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE tasks SET parent_task_id = $2, updated_at = CURRENT_TIMESTAMP WHERE id = $1",
+            id,
+            parent_task_id
+        )
         .execute(pool)
         .await?;
         Ok(())
     }
 
+    pub async fn set_priority(
+        pool: &SqlitePool,
+        id: Uuid,
+        priority: i32,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE tasks SET priority = $2, updated_at = CURRENT_TIMESTAMP WHERE id = $1",
+            id,
+            priority
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn set_blocked_on_subtasks(
+        pool: &SqlitePool,
+        id: Uuid,
+        blocked: bool,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE tasks SET blocked_on_subtasks = $2, updated_at = CURRENT_TIMESTAMP WHERE id = $1",
+            id,
+            blocked
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Clear `blocked_on_subtasks` once every subtask is done or cancelled.
+    /// Returns whether the task was unblocked.
+    pub async fn unblock_if_subtasks_complete(
+        pool: &SqlitePool,
+        id: Uuid,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"UPDATE tasks
+               SET blocked_on_subtasks = 0, updated_at = CURRENT_TIMESTAMP
+               WHERE id = $1
+                 AND blocked_on_subtasks = 1
+                 AND NOT EXISTS (
+                   SELECT 1 FROM tasks c
+                    WHERE c.parent_task_id = $1
+                      AND c.status NOT IN ('done', 'cancelled')
+                 )"#,
+            id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Whether making `child_id` a subtask of `parent_id` would loop the hierarchy, i.e.
+    /// `child_id` is `parent_id` itself or one of its ancestors
+    pub async fn would_create_cycle(
+        pool: &SqlitePool,
+        parent_id: Uuid,
+        child_id: Uuid,
+    ) -> Result<bool, sqlx::Error> {
+        let mut visited = HashSet::new();
+        let mut current = Some(parent_id);
+        while let Some(id) = current {
+            // A repeat means the existing hierarchy already loops
+            if id == child_id || !visited.insert(id) {
+                return Ok(true);
+            }
+            current = Self::find_by_id(pool, id)
+                .await?
+                .and_then(|task| task.parent_task_id);
+        }
+        Ok(false)
+    }
+
+    pub async fn find_subtasks(
+        pool: &SqlitePool,
+        parent_task_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Task,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_task_attempt as "parent_task_attempt: Uuid", agent_id as "agent_id: Uuid", workflow_id as "workflow_id: Uuid", current_station_id as "current_station_id: Uuid", parent_task_id as "parent_task_id: Uuid", blocked_on_subtasks as "blocked_on_subtasks!: bool", priority as "priority!: i32", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM tasks
+               WHERE parent_task_id = $1
+               ORDER BY created_at ASC"#,
+            parent_task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
     /// Nullify parent_task_attempt for all tasks that reference the given attempt ID
     /// This breaks parent-child relationships before deleting a parent task
     pub async fn nullify_children_by_attempt_id<'e, E>(
@@ -362,7 +662,7 @@ ORDER BY t.created_at DESC"#,
         // Find only child tasks that have this attempt as their parent
         sqlx::query_as!(
             Task,
-            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_task_attempt as "parent_task_attempt: Uuid", agent_id as "agent_id: Uuid", workflow_id as "workflow_id: Uuid", current_station_id as "current_station_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_task_attempt as "parent_task_attempt: Uuid", agent_id as "agent_id: Uuid", workflow_id as "workflow_id: Uuid", current_station_id as "current_station_id: Uuid", parent_task_id as "parent_task_id: Uuid", blocked_on_subtasks as "blocked_on_subtasks!: bool", priority as "priority!: i32", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
                FROM tasks
                WHERE parent_task_attempt = $1
                ORDER BY created_at DESC"#,
@@ -391,6 +691,9 @@ ORDER BY t.created_at DESC"#,
             } else {
                 None
             }
+        } else if let Some(parent_task_id) = current_task.parent_task_id {
+            // Split from another task without going through one of its attempts
+            Self::find_by_id(pool, parent_task_id).await?
         } else {
             None
         };
@@ -398,10 +701,14 @@ ORDER BY t.created_at DESC"#,
         // 3. Get children tasks (created by this attempt)
         let children = Self::find_children_by_attempt_id(pool, task_attempt.id).await?;
 
+        // 4. Get subtasks the current task was split into
+        let subtasks = Self::find_subtasks(pool, current_task.id).await?;
+
         Ok(TaskRelationships {
             parent_task,
             current_attempt: task_attempt.clone(),
             children,
+            subtasks,
         })
     }
 }
@@ -4,9 +4,14 @@ use serde::{Deserialize, Serialize};
 use sqlx::{FromRow, SqlitePool, Type};
 use thiserror::Error;
 use ts_rs::TS;
+use utils::pagination::{Page, PageQuery};
 use uuid::Uuid;
 
-use super::{project::Project, task::Task};
+use super::{
+    project::Project,
+    task::Task,
+    task_event::{TaskEvent, TaskEventType},
+};
 
 #[derive(Debug, Error)]
 pub enum TaskAttemptError {
@@ -45,6 +50,10 @@ pub struct TaskAttempt {
     // "GEMINI", etc.)
     pub worktree_deleted: bool, // Flag indicating if worktree has been cleaned up
     pub setup_completed_at: Option<DateTime<Utc>>, // When setup script was last completed
+    pub reviewed_commit: Option<String>, // Commit the user last reviewed the attempt at
+    pub notes: Option<String>,  // Free-text markdown notes from reviewers
+    pub pinned: bool,           // Marked by the user as the task's canonical attempt
+    pub needs_input: bool,      // The coding agent stopped to ask the user a question
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -91,12 +100,14 @@ impl TaskAttempt {
         Task::find_by_id(pool, self.task_id).await
     }
 
-    /// Fetch all task attempts, optionally filtered by task_id. Newest first.
+    /// Fetch all task attempts, optionally filtered by task_id. Newest first, with pinned
+    /// attempts ahead of the rest when `pinned_first` is set.
     pub async fn fetch_all(
         pool: &SqlitePool,
         task_id: Option<Uuid>,
+        pinned_first: bool,
     ) -> Result<Vec<Self>, TaskAttemptError> {
-        let attempts = match task_id {
+        let mut attempts = match task_id {
             Some(tid) => sqlx::query_as!(
                 TaskAttempt,
                 r#"SELECT id AS "id!: Uuid",
@@ -107,6 +118,10 @@ impl TaskAttempt {
                               executor AS "executor!",
                               worktree_deleted AS "worktree_deleted!: bool",
                               setup_completed_at AS "setup_completed_at: DateTime<Utc>",
+                              reviewed_commit,
+                              notes,
+                              pinned AS "pinned!: bool",
+                              needs_input AS "needs_input!: bool",
                               created_at AS "created_at!: DateTime<Utc>",
                               updated_at AS "updated_at!: DateTime<Utc>"
                        FROM task_attempts
@@ -127,6 +142,10 @@ impl TaskAttempt {
                               executor AS "executor!",
                               worktree_deleted AS "worktree_deleted!: bool",
                               setup_completed_at AS "setup_completed_at: DateTime<Utc>",
+                              reviewed_commit,
+                              notes,
+                              pinned AS "pinned!: bool",
+                              needs_input AS "needs_input!: bool",
                               created_at AS "created_at!: DateTime<Utc>",
                               updated_at AS "updated_at!: DateTime<Utc>"
                        FROM task_attempts
@@ -137,9 +156,101 @@ impl TaskAttempt {
             .map_err(TaskAttemptError::Database)?,
         };
 
+        if pinned_first {
+            // Stable sort keeps newest-first order within pinned and unpinned attempts
+            attempts.sort_by_key(|attempt| !attempt.pinned);
+        }
+
         Ok(attempts)
     }
 
+    /// One page of [`Self::fetch_all`], ordered by creation time with the id as a tie-breaker so
+    /// pages stay stable
+    pub async fn fetch_page(
+        pool: &SqlitePool,
+        task_id: Option<Uuid>,
+        pinned_first: bool,
+        page: &PageQuery,
+    ) -> Result<Page<Self>, TaskAttemptError> {
+        let (limit, offset) = (page.limit(), page.offset());
+        let (attempts, total) = match task_id {
+            Some(tid) => {
+                let attempts = sqlx::query_as!(
+                    TaskAttempt,
+                    r#"SELECT id AS "id!: Uuid",
+                                  task_id AS "task_id!: Uuid",
+                                  container_ref,
+                                  branch,
+                                  target_branch,
+                                  executor AS "executor!",
+                                  worktree_deleted AS "worktree_deleted!: bool",
+                                  setup_completed_at AS "setup_completed_at: DateTime<Utc>",
+                                  reviewed_commit,
+                                  notes,
+                                  pinned AS "pinned!: bool",
+                                  needs_input AS "needs_input!: bool",
+                                  created_at AS "created_at!: DateTime<Utc>",
+                                  updated_at AS "updated_at!: DateTime<Utc>"
+                           FROM task_attempts
+                           WHERE task_id = $1
+                           ORDER BY CASE WHEN $2 THEN pinned ELSE 0 END DESC,
+                                    created_at DESC,
+                                    id DESC
+                           LIMIT $3 OFFSET $4"#,
+                    tid,
+                    pinned_first,
+                    limit,
+                    offset
+                )
+                .fetch_all(pool)
+                .await?;
+                let total = sqlx::query_scalar!(
+                    r#"SELECT COUNT(*) AS "count!: i64" FROM task_attempts WHERE task_id = $1"#,
+                    tid
+                )
+                .fetch_one(pool)
+                .await?;
+                (attempts, total)
+            }
+            None => {
+                let attempts = sqlx::query_as!(
+                    TaskAttempt,
+                    r#"SELECT id AS "id!: Uuid",
+                                  task_id AS "task_id!: Uuid",
+                                  container_ref,
+                                  branch,
+                                  target_branch,
+                                  executor AS "executor!",
+                                  worktree_deleted AS "worktree_deleted!: bool",
+                                  setup_completed_at AS "setup_completed_at: DateTime<Utc>",
+                                  reviewed_commit,
+                                  notes,
+                                  pinned AS "pinned!: bool",
+                                  needs_input AS "needs_input!: bool",
+                                  created_at AS "created_at!: DateTime<Utc>",
+                                  updated_at AS "updated_at!: DateTime<Utc>"
+                           FROM task_attempts
+                           ORDER BY CASE WHEN $1 THEN pinned ELSE 0 END DESC,
+                                    created_at DESC,
+                                    id DESC
+                           LIMIT $2 OFFSET $3"#,
+                    pinned_first,
+                    limit,
+                    offset
+                )
+                .fetch_all(pool)
+                .await?;
+                let total =
+                    sqlx::query_scalar!(r#"SELECT COUNT(*) AS "count!: i64" FROM task_attempts"#)
+                        .fetch_one(pool)
+                        .await?;
+                (attempts, total)
+            }
+        };
+
+        Ok(Page::new(attempts, total as u32, page))
+    }
+
     /// Load task attempt with full validation - ensures task_attempt belongs to task and task belongs to project
     pub async fn load_context(
         pool: &SqlitePool,
@@ -158,6 +269,10 @@ impl TaskAttempt {
                        ta.executor AS "executor!",
                        ta.worktree_deleted  AS "worktree_deleted!: bool",
                        ta.setup_completed_at AS "setup_completed_at: DateTime<Utc>",
+                       ta.reviewed_commit,
+                       ta.notes,
+                       ta.pinned AS "pinned!: bool",
+                       ta.needs_input AS "needs_input!: bool",
                        ta.created_at        AS "created_at!: DateTime<Utc>",
                        ta.updated_at        AS "updated_at!: DateTime<Utc>"
                FROM    task_attempts ta
@@ -231,6 +346,10 @@ impl TaskAttempt {
                        executor AS "executor!",
                        worktree_deleted  AS "worktree_deleted!: bool",
                        setup_completed_at AS "setup_completed_at: DateTime<Utc>",
+                       reviewed_commit,
+                       notes,
+                       pinned AS "pinned!: bool",
+                       needs_input AS "needs_input!: bool",
                        created_at        AS "created_at!: DateTime<Utc>",
                        updated_at        AS "updated_at!: DateTime<Utc>"
                FROM    task_attempts
@@ -252,6 +371,10 @@ impl TaskAttempt {
                        executor AS "executor!",
                        worktree_deleted  AS "worktree_deleted!: bool",
                        setup_completed_at AS "setup_completed_at: DateTime<Utc>",
+                       reviewed_commit,
+                       notes,
+                       pinned AS "pinned!: bool",
+                       needs_input AS "needs_input!: bool",
                        created_at        AS "created_at!: DateTime<Utc>",
                        updated_at        AS "updated_at!: DateTime<Utc>"
                FROM    task_attempts
@@ -313,6 +436,44 @@ impl TaskAttempt {
         Ok(result.exists)
     }
 
+    /// Whether an attempt other than `attempt_id` still uses the worktree at `container_ref`,
+    /// as when a project reuses one worktree for every attempt of a task
+    pub async fn container_ref_shared(
+        pool: &SqlitePool,
+        container_ref: &str,
+        attempt_id: Uuid,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"SELECT EXISTS(SELECT 1 FROM task_attempts WHERE container_ref = $1 AND id != $2 AND worktree_deleted = FALSE) as "shared!: bool""#,
+            container_ref,
+            attempt_id
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(result.shared)
+    }
+
+    /// Worktree of the task's most recent other attempt that still has one, for projects that
+    /// reuse a single worktree per task
+    pub async fn find_reusable_container_ref(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        attempt_id: Uuid,
+    ) -> Result<Option<String>, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT container_ref as "container_ref!"
+               FROM task_attempts
+               WHERE task_id = $1 AND id != $2 AND container_ref IS NOT NULL AND worktree_deleted = FALSE
+               ORDER BY created_at DESC
+               LIMIT 1"#,
+            task_id,
+            attempt_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
     /// Find task attempts that are expired (72+ hours since last activity) and eligible for worktree cleanup
     /// Activity includes: execution completion, task attempt updates (including worktree recreation),
     /// and any attempts that are currently in progress
@@ -370,11 +531,12 @@ impl TaskAttempt {
     ) -> Result<Self, TaskAttemptError> {
         // let prefixed_id = format!("vibe-kanban-{}", attempt_id);
         // Insert the record into the database
-        Ok(sqlx::query_as!(
+        let mut tx = pool.begin().await?;
+        let attempt = sqlx::query_as!(
             TaskAttempt,
             r#"INSERT INTO task_attempts (id, task_id, container_ref, branch, target_branch, executor, worktree_deleted, setup_completed_at)
                VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
-               RETURNING id as "id!: Uuid", task_id as "task_id!: Uuid", container_ref, branch, target_branch, executor as "executor!",  worktree_deleted as "worktree_deleted!: bool", setup_completed_at as "setup_completed_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+               RETURNING id as "id!: Uuid", task_id as "task_id!: Uuid", container_ref, branch, target_branch, executor as "executor!",  worktree_deleted as "worktree_deleted!: bool", setup_completed_at as "setup_completed_at: DateTime<Utc>", reviewed_commit, notes, pinned as "pinned!: bool", needs_input as "needs_input!: bool", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
             id,
             task_id,
             Option::<String>::None, // Container isn't known yet
@@ -384,8 +546,87 @@ impl TaskAttempt {
             false, // worktree_deleted is false during creation
             Option::<DateTime<Utc>>::None // setup_completed_at is None during creation
         )
-        .fetch_one(pool)
-        .await?)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        TaskEvent::record(
+            &mut *tx,
+            task_id,
+            Some(id),
+            TaskEventType::AttemptCreated,
+            None,
+            None,
+            Some(&data.base_branch),
+        )
+        .await?;
+        tx.commit().await?;
+        Ok(attempt)
+    }
+
+    /// Mark the attempt as reviewed up to `commit`, or clear the marker
+    pub async fn update_reviewed_commit(
+        pool: &SqlitePool,
+        attempt_id: Uuid,
+        commit: Option<&str>,
+    ) -> Result<(), TaskAttemptError> {
+        sqlx::query!(
+            "UPDATE task_attempts SET reviewed_commit = $1, updated_at = datetime('now') WHERE id = $2",
+            commit,
+            attempt_id,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn update_pinned(
+        pool: &SqlitePool,
+        attempt_id: Uuid,
+        pinned: bool,
+    ) -> Result<(), TaskAttemptError> {
+        sqlx::query!(
+            "UPDATE task_attempts SET pinned = $1, updated_at = datetime('now') WHERE id = $2",
+            pinned,
+            attempt_id,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn update_needs_input(
+        pool: &SqlitePool,
+        attempt_id: Uuid,
+        needs_input: bool,
+    ) -> Result<(), TaskAttemptError> {
+        sqlx::query!(
+            "UPDATE task_attempts SET needs_input = $1, updated_at = datetime('now') WHERE id = $2",
+            needs_input,
+            attempt_id,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Replace the attempt's reviewer notes; `None` clears them
+    pub async fn update_notes(
+        pool: &SqlitePool,
+        attempt_id: Uuid,
+        notes: Option<&str>,
+    ) -> Result<(), TaskAttemptError> {
+        sqlx::query!(
+            "UPDATE task_attempts SET notes = $1, updated_at = datetime('now') WHERE id = $2",
+            notes,
+            attempt_id,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
     }
 
     pub async fn update_target_branch(
@@ -423,4 +664,11 @@ impl TaskAttempt {
 
         Ok((result.attempt_id, result.task_id, result.project_id))
     }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM task_attempts WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
 }
@@ -0,0 +1,126 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Executor, FromRow, Sqlite, SqlitePool, Type};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::{merge::MergeStatus, task::TaskStatus};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Type, Serialize, Deserialize, TS)]
+#[sqlx(type_name = "TEXT", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum TaskEventType {
+    Created,
+    StatusChanged,
+    AttemptCreated,
+    /// An attempt's branch was merged directly into its target branch
+    Merged,
+    PrCreated,
+    PrMerged,
+    PrClosed,
+    /// An attempt was stopped for exceeding the configured maximum duration
+    TimedOut,
+    /// A dev server started listening; `details` holds its preview URL
+    PreviewAvailable,
+}
+
+/// One entry in a task's activity timeline
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct TaskEvent {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub task_attempt_id: Option<Uuid>,
+    pub event_type: TaskEventType,
+    /// Set for `status_changed` events
+    pub from_status: Option<TaskStatus>,
+    /// The new status for `status_changed` events, the initial one for `created`
+    pub to_status: Option<TaskStatus>,
+    /// Event-specific context: the base branch of a new attempt, the target branch of a merge,
+    /// or the PR URL
+    pub details: Option<String>,
+    /// Who triggered the event, when known. Local deployments have a single user and leave
+    /// it unset.
+    pub actor: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl TaskEvent {
+    /// Activity for a task, oldest first
+    pub async fn find_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskEvent,
+            r#"SELECT id as "id!: Uuid", task_id as "task_id!: Uuid", task_attempt_id as "task_attempt_id: Uuid", event_type as "event_type!: TaskEventType", from_status as "from_status: TaskStatus", to_status as "to_status: TaskStatus", details, actor, created_at as "created_at!: DateTime<Utc>"
+               FROM task_events
+               WHERE task_id = $1
+               ORDER BY created_at ASC, rowid ASC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn record<'e, E>(
+        executor: E,
+        task_id: Uuid,
+        task_attempt_id: Option<Uuid>,
+        event_type: TaskEventType,
+        from_status: Option<TaskStatus>,
+        to_status: Option<TaskStatus>,
+        details: Option<&str>,
+    ) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
+        let id = Uuid::new_v4();
+        sqlx::query!(
+            r#"INSERT INTO task_events (id, task_id, task_attempt_id, event_type, from_status, to_status, details)
+               VALUES ($1, $2, $3, $4, $5, $6, $7)"#,
+            id,
+            task_id,
+            task_attempt_id,
+            event_type,
+            from_status,
+            to_status,
+            details
+        )
+        .execute(executor)
+        .await?;
+        Ok(())
+    }
+
+    /// Record a PR leaving the `open` state, attributed to the task of the attempt that opened
+    /// it. Does nothing if the PR is already in `pr_status`, so repeated syncs don't duplicate
+    /// entries.
+    pub async fn record_pr_status_change<'e, E>(
+        executor: E,
+        merge_id: Uuid,
+        pr_status: &MergeStatus,
+    ) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
+        let event_type = match pr_status {
+            MergeStatus::Merged => TaskEventType::PrMerged,
+            MergeStatus::Closed => TaskEventType::PrClosed,
+            MergeStatus::Open | MergeStatus::Unknown => return Ok(()),
+        };
+        let id = Uuid::new_v4();
+        sqlx::query!(
+            r#"INSERT INTO task_events (id, task_id, task_attempt_id, event_type, details)
+               SELECT $1, ta.task_id, m.task_attempt_id, $2, m.pr_url
+               FROM merges m
+               JOIN task_attempts ta ON ta.id = m.task_attempt_id
+               WHERE m.id = $3 AND m.pr_status IS NOT $4"#,
+            id,
+            event_type,
+            merge_id,
+            pr_status
+        )
+        .execute(executor)
+        .await?;
+        Ok(())
+    }
+}
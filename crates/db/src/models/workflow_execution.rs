@@ -11,7 +11,7 @@ pub struct WorkflowExecution {
     pub task_id: Uuid,
     pub task_attempt_id: Option<Uuid>,
     pub current_station_id: Option<Uuid>,
-    pub status: String, // 'pending', 'running', 'completed', 'failed', 'cancelled'
+    pub status: String, // 'pending', 'running', 'paused', 'completed', 'failed', 'cancelled'
     pub started_at: Option<DateTime<Utc>>,
     pub completed_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
@@ -117,6 +117,39 @@ impl WorkflowExecution {
         .await
     }
 
+    /// The pending, running or paused execution of a workflow on any of a task's attempts.
+    /// At most one exists, enforced by `idx_workflow_executions_active_task`.
+    pub async fn find_active_by_task(
+        pool: &SqlitePool,
+        workflow_id: Uuid,
+        task_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            WorkflowExecution,
+            r#"SELECT
+                id as "id!: Uuid",
+                workflow_id as "workflow_id!: Uuid",
+                task_id as "task_id!: Uuid",
+                task_attempt_id as "task_attempt_id: Uuid",
+                current_station_id as "current_station_id: Uuid",
+                status,
+                started_at as "started_at: DateTime<Utc>",
+                completed_at as "completed_at: DateTime<Utc>",
+                created_at as "created_at!: DateTime<Utc>",
+                updated_at as "updated_at!: DateTime<Utc>"
+               FROM workflow_executions
+               WHERE workflow_id = $1
+                 AND task_id = $2
+                 AND status IN ('pending', 'running', 'paused')
+               ORDER BY created_at DESC
+               LIMIT 1"#,
+            workflow_id,
+            task_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
     pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
         sqlx::query_as!(
             WorkflowExecution,
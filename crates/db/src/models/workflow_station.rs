@@ -1,5 +1,5 @@
 use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use sqlx::{Executor, FromRow, Sqlite, SqlitePool};
 use ts_rs::TS;
 use uuid::Uuid;
@@ -16,6 +16,7 @@ pub struct WorkflowStation {
     pub agent_id: Option<Uuid>, // Phase 1.1: One agent per station
     pub station_prompt: Option<String>, // Phase 1.1: Instructions for this station's agent
     pub output_context_keys: Option<String>, // JSON array: ["design_doc", "api_spec"]
+    pub output_schema: Option<String>, // JSON Schema the extracted output must satisfy
     pub is_terminator: bool, // Phase 3.6: Marks final stations that trigger workflow completion
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
@@ -32,6 +33,7 @@ pub struct CreateWorkflowStation {
     pub agent_id: Option<Uuid>,
     pub station_prompt: Option<String>,
     pub output_context_keys: Option<String>,
+    pub output_schema: Option<String>,
     pub is_terminator: Option<bool>,
 }
 
@@ -45,9 +47,22 @@ pub struct UpdateWorkflowStation {
     pub agent_id: Option<Uuid>,
     pub station_prompt: Option<String>,
     pub output_context_keys: Option<String>,
+    /// Omit to keep the current schema; `null` removes it
+    #[serde(default, deserialize_with = "deserialize_present")]
+    #[ts(optional)]
+    pub output_schema: Option<Option<String>>,
     pub is_terminator: Option<bool>,
 }
 
+/// Deserialize a field that is present in the input as `Some`, even when its value is `null`,
+/// so an omitted field (`None`) can be told apart from an explicit `null` (`Some(None)`)
+fn deserialize_present<'de, D>(deserializer: D) -> Result<Option<Option<String>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<String>::deserialize(deserializer).map(Some)
+}
+
 impl WorkflowStation {
     pub async fn find_by_workflow_id(
         pool: &SqlitePool,
@@ -66,6 +81,7 @@ impl WorkflowStation {
                 agent_id as "agent_id: Uuid",
                 station_prompt,
                 output_context_keys,
+                output_schema,
                 is_terminator as "is_terminator!: bool",
                 created_at as "created_at!: DateTime<Utc>",
                 updated_at as "updated_at!: DateTime<Utc>"
@@ -92,6 +108,7 @@ impl WorkflowStation {
                 agent_id as "agent_id: Uuid",
                 station_prompt,
                 output_context_keys,
+                output_schema,
                 is_terminator as "is_terminator!: bool",
                 created_at as "created_at!: DateTime<Utc>",
                 updated_at as "updated_at!: DateTime<Utc>"
@@ -114,8 +131,8 @@ impl WorkflowStation {
 
         sqlx::query_as!(
             WorkflowStation,
-            r#"INSERT INTO workflow_stations (id, workflow_id, name, position, description, x_position, y_position, agent_id, station_prompt, output_context_keys, is_terminator)
-               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            r#"INSERT INTO workflow_stations (id, workflow_id, name, position, description, x_position, y_position, agent_id, station_prompt, output_context_keys, output_schema, is_terminator)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
                RETURNING
                 id as "id!: Uuid",
                 workflow_id as "workflow_id!: Uuid",
@@ -127,6 +144,7 @@ impl WorkflowStation {
                 agent_id as "agent_id: Uuid",
                 station_prompt,
                 output_context_keys,
+                output_schema,
                 is_terminator as "is_terminator!: bool",
                 created_at as "created_at!: DateTime<Utc>",
                 updated_at as "updated_at!: DateTime<Utc>""#,
@@ -140,6 +158,7 @@ impl WorkflowStation {
             data.agent_id,
             data.station_prompt,
             data.output_context_keys,
+            data.output_schema,
             is_terminator
         )
         .fetch_one(pool)
@@ -164,12 +183,13 @@ impl WorkflowStation {
         let agent_id = data.agent_id.or(existing.agent_id);
         let station_prompt = data.station_prompt.or(existing.station_prompt);
         let output_context_keys = data.output_context_keys.or(existing.output_context_keys);
+        let output_schema = data.output_schema.unwrap_or(existing.output_schema);
         let is_terminator = data.is_terminator.unwrap_or(existing.is_terminator);
 
         sqlx::query_as!(
             WorkflowStation,
             r#"UPDATE workflow_stations
-               SET name = $2, position = $3, description = $4, x_position = $5, y_position = $6, agent_id = $7, station_prompt = $8, output_context_keys = $9, is_terminator = $10, updated_at = CURRENT_TIMESTAMP
+               SET name = $2, position = $3, description = $4, x_position = $5, y_position = $6, agent_id = $7, station_prompt = $8, output_context_keys = $9, output_schema = $10, is_terminator = $11, updated_at = CURRENT_TIMESTAMP
                WHERE id = $1
                RETURNING
                 id as "id!: Uuid",
@@ -182,6 +202,7 @@ impl WorkflowStation {
                 agent_id as "agent_id: Uuid",
                 station_prompt,
                 output_context_keys,
+                output_schema,
                 is_terminator as "is_terminator!: bool",
                 created_at as "created_at!: DateTime<Utc>",
                 updated_at as "updated_at!: DateTime<Utc>""#,
@@ -194,6 +215,7 @@ impl WorkflowStation {
             agent_id,
             station_prompt,
             output_context_keys,
+            output_schema,
             is_terminator
         )
         .fetch_one(pool)
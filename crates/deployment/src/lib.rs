@@ -1,4 +1,8 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use anyhow::Error as AnyhowError;
 use async_trait::async_trait;
@@ -6,10 +10,11 @@ use axum::response::sse::Event;
 use db::{
     DBService,
     models::{
+        attempt_queue::QueuedAttempt,
         execution_process::{ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus},
         project::{CreateProject, Project},
         task::{Task, TaskStatus},
-        task_attempt::{TaskAttempt, TaskAttemptError},
+        task_attempt::{CreateTaskAttempt, TaskAttempt, TaskAttemptError},
     },
 };
 use executors::executors::ExecutorError;
@@ -28,6 +33,7 @@ use services::services::{
     filesystem::{FilesystemError, FilesystemService},
     filesystem_watcher::FilesystemWatcherError,
     git::{GitService, GitServiceError},
+    github_cache::GitHubMetadataCache,
     image::{ImageError, ImageService},
     pr_monitor::PrMonitorService,
     worktree_manager::WorktreeError,
@@ -37,6 +43,29 @@ use thiserror::Error;
 use tokio::sync::RwLock;
 use utils::{msg_store::MsgStore, sentry as sentry_utils};
 
+/// How often the attempt queue is checked for free execution slots
+const ATTEMPT_QUEUE_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How long a queued attempt that failed to start waits before it is tried again
+const ATTEMPT_QUEUE_RETRY_DELAY: Duration = Duration::from_secs(60);
+
+/// Failed starts after which a queued attempt is dropped from the queue
+const ATTEMPT_QUEUE_MAX_START_FAILURES: u32 = 3;
+
+#[derive(Debug, Clone, Copy)]
+struct QueuedAttemptFailures {
+    count: u32,
+    last_failed_at: Instant,
+}
+
+/// Scheduler state of the attempt queue. The lock is held while draining the queue so
+/// concurrent drains can't both fill the last slot; it guards the entries that failed to
+/// start, keyed by queue entry id.
+#[derive(Clone, Default)]
+pub struct AttemptQueueState {
+    failures: Arc<tokio::sync::Mutex<HashMap<Uuid, QueuedAttemptFailures>>>,
+}
+
 #[derive(Debug, Error)]
 pub enum DeploymentError {
     #[error(transparent)]
@@ -101,10 +130,14 @@ pub trait Deployment: Clone + Send + Sync + 'static {
 
     fn file_search_cache(&self) -> &Arc<FileSearchCache>;
 
+    fn github_cache(&self) -> &GitHubMetadataCache;
+
     fn approvals(&self) -> &Approvals;
 
     fn drafts(&self) -> &DraftsService;
 
+    fn attempt_queue(&self) -> &AttemptQueueState;
+
     async fn update_sentry_scope(&self) -> Result<(), DeploymentError> {
         let user_id = self.user_id();
         let config = self.config().read().await;
@@ -128,6 +161,190 @@ pub trait Deployment: Clone + Send + Sync + 'static {
         PrMonitorService::spawn(db, config, analytics).await
     }
 
+    async fn spawn_attempt_queue_scheduler(&self) -> tokio::task::JoinHandle<()> {
+        let deployment = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(ATTEMPT_QUEUE_POLL_INTERVAL);
+            loop {
+                interval.tick().await;
+                if let Err(e) = deployment.start_queued_attempts().await {
+                    tracing::error!("Failed to start queued attempts: {}", e);
+                }
+            }
+        })
+    }
+
+    /// Start queued attempts in queue order while `max_concurrent_attempts` leaves room.
+    /// An entry leaves the queue once its attempt has started; one that fails to start stays
+    /// queued and is retried after `ATTEMPT_QUEUE_RETRY_DELAY`, until it has failed
+    /// `ATTEMPT_QUEUE_MAX_START_FAILURES` times and is dropped.
+    async fn start_queued_attempts(&self) -> Result<(), DeploymentError> {
+        let mut failures = self.attempt_queue().failures.lock().await;
+        let pool = &self.db().pool;
+        loop {
+            let limit = self.config().read().await.max_concurrent_attempts;
+            if limit > 0
+                && ExecutionProcess::count_running_attempts(pool).await? >= i64::from(limit)
+            {
+                return Ok(());
+            }
+            let entries = QueuedAttempt::find_all(pool).await?;
+            // Forget entries that left the queue some other way
+            failures.retain(|id, _| entries.iter().any(|entry| entry.id == *id));
+            let Some(entry) = entries.into_iter().find(|entry| {
+                failures.get(&entry.id).is_none_or(|failed| {
+                    failed.last_failed_at.elapsed() >= ATTEMPT_QUEUE_RETRY_DELAY
+                })
+            }) else {
+                return Ok(());
+            };
+
+            match self.start_queued_attempt(&entry).await {
+                // Waiting on subtasks isn't a failure; look again after the retry delay
+                Ok(None) => {
+                    let count = failures.get(&entry.id).map_or(0, |failed| failed.count);
+                    failures.insert(
+                        entry.id,
+                        QueuedAttemptFailures {
+                            count,
+                            last_failed_at: Instant::now(),
+                        },
+                    );
+                }
+                Ok(Some(task_attempt)) => {
+                    tracing::info!(
+                        "Started queued attempt {} for task {}",
+                        task_attempt.id,
+                        entry.task_id
+                    );
+                    failures.remove(&entry.id);
+                    QueuedAttempt::delete(pool, entry.id).await?;
+                    if let Err(e) = self.events().push_attempt_queue_update().await {
+                        tracing::error!("Failed to push attempt queue update: {}", e);
+                    }
+                }
+                Err(e) => {
+                    let count = failures.get(&entry.id).map_or(0, |failed| failed.count) + 1;
+                    if count < ATTEMPT_QUEUE_MAX_START_FAILURES {
+                        tracing::error!(
+                            "Failed to start queued attempt for task {} ({} of {} tries): {}",
+                            entry.task_id,
+                            count,
+                            ATTEMPT_QUEUE_MAX_START_FAILURES,
+                            e
+                        );
+                        failures.insert(
+                            entry.id,
+                            QueuedAttemptFailures {
+                                count,
+                                last_failed_at: Instant::now(),
+                            },
+                        );
+                        continue;
+                    }
+                    tracing::error!(
+                        "Dropping queued attempt for task {} after {} failed starts: {}",
+                        entry.task_id,
+                        count,
+                        e
+                    );
+                    failures.remove(&entry.id);
+                    QueuedAttempt::delete(pool, entry.id).await?;
+                    if let Err(e) = self.events().push_attempt_queue_update().await {
+                        tracing::error!("Failed to push attempt queue update: {}", e);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Create and start the attempt for a queue entry. None while the task waits on its
+    /// subtasks.
+    async fn start_queued_attempt(
+        &self,
+        entry: &QueuedAttempt,
+    ) -> Result<Option<TaskAttempt>, DeploymentError> {
+        let pool = &self.db().pool;
+        let task = Task::find_by_id(pool, entry.task_id)
+            .await?
+            .ok_or(SqlxError::RowNotFound)?;
+        if task.blocked_on_subtasks {
+            tracing::debug!("Queued task {} is waiting on its subtasks", task.id);
+            return Ok(None);
+        }
+
+        self.image()
+            .resolve_prompt_images(task.id, &task.to_prompt(), &[])
+            .await?;
+
+        let executor_profile_id = entry.executor_profile_id.0.clone();
+        let attempt_id = Uuid::new_v4();
+        let branch = self
+            .container()
+            .git_branch_from_task_attempt(&attempt_id, &task.id, &task.title)
+            .await;
+        let task_attempt = TaskAttempt::create(
+            pool,
+            &CreateTaskAttempt {
+                executor: executor_profile_id.executor,
+                base_branch: entry.base_branch.clone(),
+                branch,
+            },
+            attempt_id,
+            task.id,
+        )
+        .await?;
+
+        if let Err(e) = self
+            .container()
+            .start_attempt(
+                &task_attempt,
+                executor_profile_id.clone(),
+                entry.force_setup,
+            )
+            .await
+        {
+            // Each retry creates a fresh attempt; don't leave this one and its worktree behind
+            self.discard_unstarted_attempt(task_attempt.id).await;
+            return Err(e.into());
+        }
+
+        self.track_if_analytics_allowed(
+            "task_attempt_started",
+            serde_json::json!({
+                "task_id": task_attempt.task_id.to_string(),
+                "variant": &executor_profile_id.variant,
+                "executor": &executor_profile_id.executor,
+                "attempt_id": task_attempt.id.to_string(),
+                "queued": true,
+            }),
+        )
+        .await;
+
+        Ok(Some(task_attempt))
+    }
+
+    /// Remove an attempt that never got going, along with its worktree
+    async fn discard_unstarted_attempt(&self, attempt_id: Uuid) {
+        let pool = &self.db().pool;
+        match TaskAttempt::find_by_id(pool, attempt_id).await {
+            Ok(Some(task_attempt)) => {
+                if let Err(e) = self.container().delete(&task_attempt).await {
+                    tracing::warn!(
+                        "Failed to clean up worktree of unstarted attempt {}: {}",
+                        attempt_id,
+                        e
+                    );
+                }
+            }
+            Ok(None) => return,
+            Err(e) => tracing::warn!("Failed to load unstarted attempt {}: {}", attempt_id, e),
+        }
+        if let Err(e) = TaskAttempt::delete(pool, attempt_id).await {
+            tracing::error!("Failed to delete unstarted attempt {}: {}", attempt_id, e);
+        }
+    }
+
     async fn track_if_analytics_allowed(&self, event_name: &str, properties: Value) {
         let analytics_enabled = self.config().read().await.analytics_enabled;
         // Only skip tracking if user explicitly opted out (Some(false))
@@ -139,6 +356,40 @@ pub trait Deployment: Clone + Send + Sync + 'static {
         }
     }
 
+    /// Record the repo's default branch as a freshly created project's default base branch.
+    /// Empty repos and detection failures leave the project unchanged.
+    async fn detect_default_base_branch(&self, mut project: Project) -> Project {
+        let branch = match self.git().get_default_branch(&project.git_repo_path) {
+            Ok(Some(branch)) => branch,
+            Ok(None) => {
+                tracing::debug!(
+                    "Project {} has an empty repository; no default base branch",
+                    project.id
+                );
+                return project;
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "Failed to detect default branch for project {}: {}",
+                    project.id,
+                    e
+                );
+                return project;
+            }
+        };
+
+        match Project::update_default_base_branch(&self.db().pool, project.id, Some(&branch)).await
+        {
+            Ok(()) => project.default_base_branch = Some(branch),
+            Err(e) => tracing::warn!(
+                "Failed to store default base branch for project {}: {}",
+                project.id,
+                e
+            ),
+        }
+        project
+    }
+
     /// Cleanup executions marked as running in the db, call at startup
     async fn cleanup_orphan_executions(&self) -> Result<(), DeploymentError> {
         let running_processes = ExecutionProcess::find_running(&self.db().pool).await?;
@@ -275,15 +526,27 @@ pub trait Deployment: Clone + Send + Sync + 'static {
                         name: project_name,
                         git_repo_path: repo.path.to_string_lossy().to_string(),
                         use_existing_repo: true,
+                        git_url: None,
                         setup_script: None,
                         dev_script: None,
                         cleanup_script: None,
                         copy_files: None,
                         worktree_dir: None,
+                        push_remote: None,
+                        pr_remote: None,
+                        always_run_cleanup: false,
+                        setup_cache_files: None,
+                        setup_cache_artifacts: None,
+                        script_language: None,
+                        preview_url_pattern: None,
+                        env_file: None,
+                        env_file_required: false,
+                        dev_scripts: Vec::new(),
+                        reuse_worktree: false,
                     };
-                    // Ensure existing repo has a main branch if it's empty
-                    if let Err(e) = self.git().ensure_main_branch_exists(&repo.path) {
-                        tracing::error!("Failed to ensure main branch exists: {}", e);
+                    // Ensure existing repo has a branch to work from if it's empty
+                    if let Err(e) = self.git().ensure_default_branch_exists(&repo.path) {
+                        tracing::error!("Failed to ensure default branch exists: {}", e);
                         continue;
                     }
 
@@ -291,6 +554,7 @@ pub trait Deployment: Clone + Send + Sync + 'static {
                     let project_id = Uuid::new_v4();
                     match Project::create(&self.db().pool, &create_data, project_id).await {
                         Ok(project) => {
+                            let project = self.detect_default_base_branch(project).await;
                             tracing::info!(
                                 "Auto-created project '{}' from {}",
                                 create_data.name,
@@ -3,19 +3,46 @@ use std::{path::Path, sync::Arc};
 use async_trait::async_trait;
 use command_group::AsyncCommandGroup;
 use serde::{Deserialize, Serialize};
+use sqlx::Type;
 use tokio::process::Command;
 use ts_rs::TS;
-use workspace_utils::shell::get_shell_command;
+use workspace_utils::shell::{get_shell_command, resolve_executable_path};
 
 use crate::{
     actions::Executable,
     approvals::ExecutorApprovalService,
+    command::apply_execution_env,
     executors::{ExecutorError, SpawnedChild},
 };
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS, Type)]
+#[sqlx(type_name = "TEXT")]
 pub enum ScriptRequestLanguage {
     Bash,
+    Sh,
+    PowerShell,
+    Node,
+}
+
+impl ScriptRequestLanguage {
+    /// Interpreter program and the arguments that make it run the following argument as a
+    /// script
+    pub fn interpreter(&self) -> (&'static str, &'static [&'static str]) {
+        match self {
+            Self::Bash => ("bash", &["-c"]),
+            Self::Sh => ("sh", &["-c"]),
+            Self::PowerShell if cfg!(windows) => {
+                ("powershell", &["-NoProfile", "-NonInteractive", "-Command"])
+            }
+            Self::PowerShell => ("pwsh", &["-NoProfile", "-NonInteractive", "-Command"]),
+            Self::Node => ("node", &["-e"]),
+        }
+    }
+
+    /// Whether the interpreter can be found on PATH
+    pub fn is_available(&self) -> bool {
+        resolve_executable_path(self.interpreter().0).is_some()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
@@ -28,8 +55,41 @@ pub enum ScriptContext {
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
 pub struct ScriptRequest {
     pub script: String,
-    pub language: ScriptRequestLanguage,
+    /// Interpreter for the script; `None` runs it in PowerShell on Windows and the default shell
+    /// elsewhere
+    #[serde(default)]
+    pub language: Option<ScriptRequestLanguage>,
     pub context: ScriptContext,
+    /// Setup cache key of the worktree; the script's artifacts are cached under it on success
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub cache_key: Option<String>,
+    /// Which of the project's dev servers this is; unset for the main `dev_script`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub name: Option<String>,
+}
+
+impl ScriptRequest {
+    /// Interpreter program and arguments for the script: the language's interpreter, or when no
+    /// language is set PowerShell on Windows, where bash usually isn't installed, and the
+    /// default shell (see [`get_shell_command`]) elsewhere
+    pub fn interpreter(&self) -> (&'static str, Vec<&'static str>) {
+        match &self.language {
+            Some(language) => {
+                let (program, args) = language.interpreter();
+                (program, args.to_vec())
+            }
+            None if cfg!(windows) => {
+                let (program, args) = ScriptRequestLanguage::PowerShell.interpreter();
+                (program, args.to_vec())
+            }
+            None => {
+                let (shell_cmd, shell_arg) = get_shell_command();
+                (shell_cmd, vec![shell_arg])
+            }
+        }
+    }
 }
 
 #[async_trait]
@@ -39,19 +99,94 @@ impl Executable for ScriptRequest {
         current_dir: &Path,
         _approvals: Arc<dyn ExecutorApprovalService>,
     ) -> Result<SpawnedChild, ExecutorError> {
-        let (shell_cmd, shell_arg) = get_shell_command();
-        let mut command = Command::new(shell_cmd);
+        let (program, args) = self.interpreter();
+        let mut command = match resolve_executable_path(program) {
+            Some(path) => {
+                let mut command = Command::new(path);
+                command.args(args);
+                command
+            }
+            // Scripts saved as Bash before the language setting ran in the default shell
+            None if self.language == Some(ScriptRequestLanguage::Bash) => {
+                let (shell_cmd, shell_arg) = get_shell_command();
+                let mut command = Command::new(shell_cmd);
+                command.arg(shell_arg);
+                command
+            }
+            None => {
+                return Err(ExecutorError::Io(std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    format!("Script interpreter '{program}' not found on PATH"),
+                )));
+            }
+        };
         command
             .kill_on_drop(true)
             .stdin(std::process::Stdio::null())
             .stdout(std::process::Stdio::piped())
             .stderr(std::process::Stdio::piped())
-            .arg(shell_arg)
             .arg(&self.script)
             .current_dir(current_dir);
+        apply_execution_env(&mut command);
 
         let child = command.group_spawn()?;
 
         Ok(child.into())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn script_request(language: Option<ScriptRequestLanguage>) -> ScriptRequest {
+        ScriptRequest {
+            script: "echo hi".to_string(),
+            language,
+            context: ScriptContext::SetupScript,
+            cache_key: None,
+            name: None,
+        }
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn unset_language_runs_in_the_default_shell() {
+        let (shell_cmd, shell_arg) = get_shell_command();
+        assert_eq!(
+            script_request(None).interpreter(),
+            (shell_cmd, vec![shell_arg])
+        );
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn unset_language_runs_in_powershell_on_windows() {
+        assert_eq!(
+            script_request(None).interpreter(),
+            (
+                "powershell",
+                vec!["-NoProfile", "-NonInteractive", "-Command"]
+            )
+        );
+    }
+
+    #[test]
+    fn set_language_runs_its_interpreter() {
+        assert_eq!(
+            script_request(Some(ScriptRequestLanguage::Bash)).interpreter(),
+            ("bash", vec!["-c"])
+        );
+        assert_eq!(
+            script_request(Some(ScriptRequestLanguage::Node)).interpreter(),
+            ("node", vec!["-e"])
+        );
+    }
+
+    #[test]
+    fn language_defaults_to_unset_when_missing() {
+        let request: ScriptRequest =
+            serde_json::from_str(r#"{"script":"echo hi","context":"SetupScript"}"#).unwrap();
+        assert_eq!(request.language, None);
+    }
+}
@@ -1,7 +1,24 @@
+use std::{collections::HashMap, sync::RwLock};
+
+use lazy_static::lazy_static;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 
+use crate::executors::BaseCodingAgent;
+
+lazy_static! {
+    /// Extra CLI arguments per executor from the user config, see [`set_user_extra_args`]
+    static ref USER_EXTRA_ARGS: RwLock<HashMap<BaseCodingAgent, Vec<String>>> =
+        RwLock::new(HashMap::new());
+}
+
+tokio::task_local! {
+    /// Variables from the project's env file for the process being spawned, see
+    /// [`with_execution_env`]
+    static EXECUTION_ENV: HashMap<String, String>;
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS, JsonSchema, Default)]
 pub struct CmdOverrides {
     #[schemars(
@@ -90,3 +107,91 @@ pub fn apply_overrides(builder: CommandBuilder, overrides: &CmdOverrides) -> Com
         builder
     }
 }
+
+/// Spawn executors inside `fut` with `env` set on their commands via [`apply_execution_env`]
+pub async fn with_execution_env<F: Future>(env: HashMap<String, String>, fut: F) -> F::Output {
+    EXECUTION_ENV.scope(env, fut).await
+}
+
+/// Set the current [`with_execution_env`] variables on an executor command. Call it before the
+/// executor's own `env` calls so those take precedence.
+pub fn apply_execution_env(command: &mut tokio::process::Command) {
+    let _ = EXECUTION_ENV.try_with(|env| {
+        command.envs(env);
+    });
+}
+
+/// Replace the user-configured extra arguments appended to every executor command
+pub fn set_user_extra_args(args: HashMap<BaseCodingAgent, Vec<String>>) {
+    *USER_EXTRA_ARGS.write().unwrap() = args;
+}
+
+/// Check that an extra argument survives `get_shell_command` unquoted on every platform.
+/// Only ASCII alphanumerics and `-_=.,:/@+` are allowed; a leading `=` is rejected because
+/// zsh expands `=cmd` to a path.
+pub fn validate_extra_arg(arg: &str) -> Result<(), String> {
+    if arg.is_empty() {
+        return Err("arguments must not be empty".to_string());
+    }
+    if arg.starts_with('=') {
+        return Err(format!("'{arg}' must not start with '='"));
+    }
+    if let Some(c) = arg
+        .chars()
+        .find(|c| !c.is_ascii_alphanumeric() && !"-_=.,:/@+".contains(*c))
+    {
+        return Err(format!("'{arg}' contains unsupported character {c:?}"));
+    }
+    Ok(())
+}
+
+/// Append the user's extra arguments for `agent`. They go after variant-derived flags and
+/// profile `additional_params`, so for CLIs where the last flag wins the user config wins.
+pub fn apply_user_extra_args(builder: CommandBuilder, agent: BaseCodingAgent) -> CommandBuilder {
+    let extra: Vec<String> = USER_EXTRA_ARGS
+        .read()
+        .unwrap()
+        .get(&agent)
+        .into_iter()
+        .flatten()
+        .filter(|arg| match validate_extra_arg(arg) {
+            Ok(()) => true,
+            Err(e) => {
+                tracing::warn!("Ignoring extra {} argument: {}", agent, e);
+                false
+            }
+        })
+        .cloned()
+        .collect();
+    if extra.is_empty() {
+        builder
+    } else {
+        builder.extend_params(extra)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_extra_arg_allows_plain_flags() {
+        for arg in [
+            "--dangerously-skip-permissions",
+            "--model=gpt-5",
+            "-c",
+            "a/b:c@d+e,f",
+        ] {
+            assert!(validate_extra_arg(arg).is_ok(), "{arg}");
+        }
+    }
+
+    #[test]
+    fn validate_extra_arg_rejects_shell_metacharacters() {
+        for arg in [
+            "", "=ls", "a b", "a;b", "$(id)", "`id`", "a|b", "a&b", "'x'", "a\nb",
+        ] {
+            assert!(validate_extra_arg(arg).is_err(), "{arg}");
+        }
+    }
+}
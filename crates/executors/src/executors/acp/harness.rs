@@ -17,7 +17,10 @@ use tracing::error;
 use workspace_utils::{shell::get_shell_command, stream_lines::LinesStreamExt};
 
 use super::{AcpClient, SessionManager};
-use crate::executors::{ExecutorError, SpawnedChild, acp::AcpEvent};
+use crate::{
+    command::apply_execution_env,
+    executors::{ExecutorError, SpawnedChild, acp::AcpEvent},
+};
 
 /// Reusable harness for ACP-based conns (Gemini, Qwen, etc.)
 pub struct AcpAgentHarness {
@@ -54,6 +57,7 @@ impl AcpAgentHarness {
     ) -> Result<SpawnedChild, ExecutorError> {
         let (shell_cmd, shell_arg) = get_shell_command();
         let mut command = Command::new(shell_cmd);
+        apply_execution_env(&mut command);
         command
             .kill_on_drop(true)
             .stdin(Stdio::piped())
@@ -92,6 +96,7 @@ impl AcpAgentHarness {
     ) -> Result<SpawnedChild, ExecutorError> {
         let (shell_cmd, shell_arg) = get_shell_command();
         let mut command = Command::new(shell_cmd);
+        apply_execution_env(&mut command);
         command
             .kill_on_drop(true)
             .stdin(Stdio::piped())
@@ -293,27 +293,27 @@ pub fn normalize_logs(msg_store: Arc<MsgStore>, worktree_path: &Path) {
                                 let mut exit_status = out
                                     .exit_code
                                     .map(|code| crate::logs::CommandExitStatus::ExitCode { code });
-                                let output = out.stdout.or(out.stderr).unwrap_or_default();
+                                let output = [out.stdout, out.stderr]
+                                    .into_iter()
+                                    .flatten()
+                                    .filter(|s| !s.trim().is_empty())
+                                    .collect::<Vec<_>>()
+                                    .join("\n");
                                 if exit_status.is_none() && completed {
                                     exit_status = Some(crate::logs::CommandExitStatus::Success {
                                         success: true,
                                     });
                                 }
-                                crate::logs::CommandRunResult {
-                                    exit_status,
-                                    output: Some(output),
-                                }
+                                crate::logs::CommandRunResult::new(exit_status, Some(output))
                             })
                     } else {
                         None
                     };
                     if result.is_none() && completed {
-                        result = Some(crate::logs::CommandRunResult {
-                            exit_status: Some(crate::logs::CommandExitStatus::Success {
-                                success: true,
-                            }),
-                            output: None,
-                        });
+                        result = Some(crate::logs::CommandRunResult::new(
+                            Some(crate::logs::CommandExitStatus::Success { success: true }),
+                            None,
+                        ));
                     }
                     ActionType::CommandRun { command, result }
                 }
@@ -9,9 +9,11 @@ use ts_rs::TS;
 use workspace_utils::{msg_store::MsgStore, shell::get_shell_command};
 
 use crate::{
-    command::{CmdOverrides, CommandBuilder, apply_overrides},
+    command::{
+        CmdOverrides, CommandBuilder, apply_execution_env, apply_overrides, apply_user_extra_args,
+    },
     executors::{
-        AppendPrompt, ExecutorError, SpawnedChild, StandardCodingAgentExecutor,
+        BaseCodingAgent, ExecutorError, PromptTemplate, SpawnedChild, StandardCodingAgentExecutor,
         claude::{ClaudeLogProcessor, HistoryStrategy},
     },
     logs::{stderr_processor::normalize_stderr_logs, utils::EntryIndexProvider},
@@ -19,8 +21,8 @@ use crate::{
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS, JsonSchema)]
 pub struct Amp {
-    #[serde(default)]
-    pub append_prompt: AppendPrompt,
+    #[serde(default, alias = "append_prompt")]
+    pub prompt_template: PromptTemplate,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     #[schemars(
         title = "Dangerously Allow All",
@@ -38,7 +40,7 @@ impl Amp {
         if self.dangerously_allow_all.unwrap_or(false) {
             builder = builder.extend_params(["--dangerously-allow-all"]);
         }
-        apply_overrides(builder, &self.cmd)
+        apply_user_extra_args(apply_overrides(builder, &self.cmd), BaseCodingAgent::Amp)
     }
 }
 
@@ -48,9 +50,10 @@ impl StandardCodingAgentExecutor for Amp {
         let (shell_cmd, shell_arg) = get_shell_command();
         let amp_command = self.build_command_builder().build_initial();
 
-        let combined_prompt = self.append_prompt.combine_prompt(prompt);
+        let combined_prompt = self.prompt_template.combine_prompt(prompt);
 
         let mut command = Command::new(shell_cmd);
+        apply_execution_env(&mut command);
         command
             .kill_on_drop(true)
             .stdin(Stdio::piped())
@@ -86,7 +89,9 @@ impl StandardCodingAgentExecutor for Amp {
             "fork".to_string(),
             session_id.to_string(),
         ]);
-        let fork_output = Command::new(shell_cmd)
+        let mut fork = Command::new(shell_cmd);
+        apply_execution_env(&mut fork);
+        let fork_output = fork
             .kill_on_drop(true)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
@@ -118,9 +123,10 @@ impl StandardCodingAgentExecutor for Amp {
             new_thread_id.clone(),
         ]);
 
-        let combined_prompt = self.append_prompt.combine_prompt(prompt);
+        let combined_prompt = self.prompt_template.combine_prompt(prompt);
 
         let mut command = Command::new(shell_cmd);
+        apply_execution_env(&mut command);
         command
             .kill_on_drop(true)
             .stdin(Stdio::piped())
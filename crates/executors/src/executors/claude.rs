@@ -24,15 +24,17 @@ use workspace_utils::{
 use self::{client::ClaudeAgentClient, protocol::ProtocolPeer, types::PermissionMode};
 use crate::{
     approvals::ExecutorApprovalService,
-    command::{CmdOverrides, CommandBuilder, apply_overrides},
+    command::{
+        CmdOverrides, CommandBuilder, apply_execution_env, apply_overrides, apply_user_extra_args,
+    },
     executors::{
-        AppendPrompt, ExecutorError, SpawnedChild, StandardCodingAgentExecutor,
+        BaseCodingAgent, ExecutorError, PromptTemplate, SpawnedChild, StandardCodingAgentExecutor,
         codex::client::LogWriter,
     },
     logs::{
         ActionType, FileChange, NormalizedEntry, NormalizedEntryType, TodoItem, ToolStatus,
         stderr_processor::normalize_stderr_logs,
-        utils::{EntryIndexProvider, patch::ConversationPatch},
+        utils::{EntryIndexProvider, patch::ConversationPatch, plan::parse_plan_steps},
     },
     stdout_dup::create_stdout_pipe_writer,
 };
@@ -50,8 +52,8 @@ use derivative::Derivative;
 #[derive(Derivative, Clone, Serialize, Deserialize, TS, JsonSchema)]
 #[derivative(Debug, PartialEq)]
 pub struct ClaudeCode {
-    #[serde(default)]
-    pub append_prompt: AppendPrompt,
+    #[serde(default, alias = "append_prompt")]
+    pub prompt_template: PromptTemplate,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub claude_code_router: Option<bool>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -110,7 +112,10 @@ impl ClaudeCode {
             "--include-partial-messages",
         ]);
 
-        apply_overrides(builder, &self.cmd)
+        apply_user_extra_args(
+            apply_overrides(builder, &self.cmd),
+            BaseCodingAgent::ClaudeCode,
+        )
     }
 
     pub fn permission_mode(&self) -> PermissionMode {
@@ -204,9 +209,10 @@ impl ClaudeCode {
         base_command: String,
     ) -> Result<SpawnedChild, ExecutorError> {
         let (shell_cmd, shell_arg) = get_shell_command();
-        let combined_prompt = self.append_prompt.combine_prompt(prompt);
+        let combined_prompt = self.prompt_template.combine_prompt(prompt);
 
         let mut command = Command::new(shell_cmd);
+        apply_execution_env(&mut command);
         command
             .kill_on_drop(true)
             .stdin(Stdio::piped())
@@ -619,9 +625,10 @@ impl ClaudeLogProcessor {
                     description: task_description,
                 }
             }
-            ClaudeToolData::ExitPlanMode { plan } => {
-                ActionType::PlanPresentation { plan: plan.clone() }
-            }
+            ClaudeToolData::ExitPlanMode { plan } => ActionType::PlanPresentation {
+                plan: plan.clone(),
+                plan_steps: parse_plan_steps(plan),
+            },
             ClaudeToolData::NotebookEdit { .. } => ActionType::Tool {
                 tool_name: "NotebookEdit".to_string(),
                 arguments: Some(serde_json::to_value(tool_data).unwrap_or(serde_json::Value::Null)),
@@ -896,21 +903,21 @@ impl ClaudeLogProcessor {
                             let result = if let Ok(result) =
                                 serde_json::from_str::<AmpBashResult>(&content_str)
                             {
-                                Some(crate::logs::CommandRunResult {
-                                    exit_status: Some(crate::logs::CommandExitStatus::ExitCode {
+                                Some(crate::logs::CommandRunResult::new(
+                                    Some(crate::logs::CommandExitStatus::ExitCode {
                                         code: result.exit_code,
                                     }),
-                                    output: Some(result.output),
-                                })
+                                    Some(result.output),
+                                ))
                             } else {
-                                Some(crate::logs::CommandRunResult {
-                                    exit_status: (*is_error).map(|is_error| {
+                                Some(crate::logs::CommandRunResult::new(
+                                    (*is_error).map(|is_error| {
                                         crate::logs::CommandExitStatus::Success {
                                             success: !is_error,
                                         }
                                     }),
-                                    output: Some(content_str),
-                                })
+                                    Some(content_str),
+                                ))
                             };
 
                             let status = if is_error.unwrap_or(false) {
@@ -1099,6 +1106,7 @@ impl ClaudeLogProcessor {
                 let entry_opt = match approval_status {
                     ApprovalStatus::Pending => None,
                     ApprovalStatus::Approved => None,
+                    ApprovalStatus::Cancelled => None,
                     ApprovalStatus::Denied { reason } => Some(NormalizedEntry {
                         timestamp: None,
                         entry_type: NormalizedEntryType::UserFeedback {
@@ -1175,7 +1183,7 @@ impl ClaudeLogProcessor {
                 }
                 _ => tool_data.get_name().to_string(),
             },
-            ActionType::PlanPresentation { plan } => plan.clone(),
+            ActionType::PlanPresentation { plan, .. } => plan.clone(),
             ActionType::TodoManagement { .. } => "TODO list updated".to_string(),
             ActionType::Other { description: _ } => match tool_data {
                 ClaudeToolData::LS { path } => {
@@ -1964,7 +1972,7 @@ mod tests {
             plan: None,
             approvals: None,
             model: None,
-            append_prompt: AppendPrompt::default(),
+            prompt_template: PromptTemplate::default(),
             dangerously_skip_permissions: None,
             cmd: crate::command::CmdOverrides {
                 base_command_override: None,
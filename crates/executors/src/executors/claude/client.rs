@@ -116,6 +116,10 @@ impl ClaudeAgentClient {
                         message: "Approval still pending (unexpected)".to_string(),
                         interrupt: Some(false),
                     }),
+                    ApprovalStatus::Cancelled => Ok(PermissionResult::Deny {
+                        message: "Execution was stopped".to_string(),
+                        interrupt: Some(true),
+                    }),
                 }
             }
             Err(e) => {
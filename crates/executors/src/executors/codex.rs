@@ -31,9 +31,11 @@ use self::{
 };
 use crate::{
     approvals::ExecutorApprovalService,
-    command::{CmdOverrides, CommandBuilder, apply_overrides},
+    command::{
+        CmdOverrides, CommandBuilder, apply_execution_env, apply_overrides, apply_user_extra_args,
+    },
     executors::{
-        AppendPrompt, ExecutorError, SpawnedChild, StandardCodingAgentExecutor,
+        BaseCodingAgent, ExecutorError, PromptTemplate, SpawnedChild, StandardCodingAgentExecutor,
         codex::{jsonrpc::ExitSignalSender, normalize_logs::Error},
     },
     stdout_dup::create_stdout_pipe_writer,
@@ -102,8 +104,8 @@ pub enum ReasoningSummaryFormat {
 #[derive(Derivative, Clone, Serialize, Deserialize, TS, JsonSchema)]
 #[derivative(Debug, PartialEq)]
 pub struct Codex {
-    #[serde(default)]
-    pub append_prompt: AppendPrompt,
+    #[serde(default, alias = "append_prompt")]
+    pub prompt_template: PromptTemplate,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub sandbox: Option<SandboxMode>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -174,7 +176,7 @@ impl Codex {
             builder = builder.extend_params(["--oss"]);
         }
 
-        apply_overrides(builder, &self.cmd)
+        apply_user_extra_args(apply_overrides(builder, &self.cmd), BaseCodingAgent::Codex)
     }
 
     fn build_new_conversation_params(&self, cwd: &Path) -> NewConversationParams {
@@ -250,10 +252,11 @@ impl Codex {
         command: String,
         resume_session: Option<&str>,
     ) -> Result<SpawnedChild, ExecutorError> {
-        let combined_prompt = self.append_prompt.combine_prompt(prompt);
+        let combined_prompt = self.prompt_template.combine_prompt(prompt);
         let (shell_cmd, shell_arg) = get_shell_command();
 
         let mut process = Command::new(shell_cmd);
+        apply_execution_env(&mut process);
         process
             .kill_on_drop(true)
             .stdin(std::process::Stdio::piped())
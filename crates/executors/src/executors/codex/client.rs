@@ -282,6 +282,7 @@ impl AppServerClient {
             }
             ApprovalStatus::TimedOut => (ReviewDecision::Denied, None),
             ApprovalStatus::Pending => (ReviewDecision::Denied, None),
+            ApprovalStatus::Cancelled => (ReviewDecision::Abort, None),
         };
         Ok(outcome)
     }
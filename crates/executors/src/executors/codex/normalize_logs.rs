@@ -37,8 +37,9 @@ use crate::{
     approvals::ToolCallMetadata,
     executors::codex::session::SessionHandler,
     logs::{
-        ActionType, CommandExitStatus, CommandRunResult, FileChange, NormalizedEntry,
-        NormalizedEntryType, TodoItem, ToolResult, ToolResultValueType, ToolStatus,
+        ActionType, CommandExitStatus, CommandRunResult, FileChange, MAX_COMMAND_OUTPUT_BYTES,
+        NormalizedEntry, NormalizedEntryType, TodoItem, ToolResult, ToolResultValueType,
+        ToolStatus,
         stderr_processor::normalize_stderr_logs,
         utils::{ConversationPatch, EntryIndexProvider},
     },
@@ -87,16 +88,15 @@ impl ToNormalizedEntry for CommandState {
                 tool_name: "bash".to_string(),
                 action_type: ActionType::CommandRun {
                     command: self.command.clone(),
-                    result: Some(CommandRunResult {
-                        exit_status: self
-                            .exit_code
+                    result: Some(CommandRunResult::new(
+                        self.exit_code
                             .map(|code| CommandExitStatus::ExitCode { code }),
-                        output: if self.formatted_output.is_some() {
+                        if self.formatted_output.is_some() {
                             self.formatted_output.clone()
                         } else {
                             build_command_output(Some(&self.stdout), Some(&self.stderr))
                         },
-                    }),
+                    )),
                 },
                 status: self.status.clone(),
             },
@@ -610,8 +610,12 @@ pub fn normalize_logs(msg_store: Arc<MsgStore>, worktree_path: &Path) {
                             continue;
                         }
                         match stream {
-                            ExecOutputStream::Stdout => command_state.stdout.push_str(&chunk),
-                            ExecOutputStream::Stderr => command_state.stderr.push_str(&chunk),
+                            ExecOutputStream::Stdout => {
+                                push_output_tail(&mut command_state.stdout, &chunk)
+                            }
+                            ExecOutputStream::Stderr => {
+                                push_output_tail(&mut command_state.stderr, &chunk)
+                            }
                         }
                         let Some(index) = command_state.index else {
                             tracing::error!("missing entry index for existing command state");
@@ -1029,6 +1033,20 @@ fn handle_model_params(
     );
 }
 
+/// Streamed output is only ever rendered through `CommandRunResult::new`, which keeps the
+/// tail, so drop the head early instead of buffering everything a noisy command prints.
+/// Slightly more than the cap is kept so the rendered entry still notes the truncation.
+fn push_output_tail(buf: &mut String, chunk: &str) {
+    buf.push_str(chunk);
+    if buf.len() > MAX_COMMAND_OUTPUT_BYTES * 2 {
+        let mut start = buf.len() - MAX_COMMAND_OUTPUT_BYTES - 1;
+        while !buf.is_char_boundary(start) {
+            start -= 1;
+        }
+        buf.drain(..start);
+    }
+}
+
 fn build_command_output(stdout: Option<&str>, stderr: Option<&str>) -> Option<String> {
     let mut sections = Vec::new();
     if let Some(out) = stdout {
@@ -1134,6 +1152,7 @@ impl ToNormalizedEntryOpt for Approval {
         match approval_status {
             ApprovalStatus::Pending => None,
             ApprovalStatus::Approved => None,
+            ApprovalStatus::Cancelled => None,
             ApprovalStatus::Denied { reason } => Some(NormalizedEntry {
                 timestamp: None,
                 entry_type: NormalizedEntryType::UserFeedback {
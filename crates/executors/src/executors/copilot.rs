@@ -23,8 +23,12 @@ use workspace_utils::{
 };
 
 use crate::{
-    command::{CmdOverrides, CommandBuilder, apply_overrides},
-    executors::{AppendPrompt, ExecutorError, SpawnedChild, StandardCodingAgentExecutor},
+    command::{
+        CmdOverrides, CommandBuilder, apply_execution_env, apply_overrides, apply_user_extra_args,
+    },
+    executors::{
+        BaseCodingAgent, ExecutorError, PromptTemplate, SpawnedChild, StandardCodingAgentExecutor,
+    },
     logs::{
         NormalizedEntry, NormalizedEntryType, plain_text_processor::PlainTextLogProcessor,
         stderr_processor::normalize_stderr_logs, utils::EntryIndexProvider,
@@ -34,8 +38,8 @@ use crate::{
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS, JsonSchema)]
 pub struct Copilot {
-    #[serde(default)]
-    pub append_prompt: AppendPrompt,
+    #[serde(default, alias = "append_prompt")]
+    pub prompt_template: PromptTemplate,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub model: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -90,7 +94,10 @@ impl Copilot {
             }
         }
 
-        apply_overrides(builder, &self.cmd)
+        apply_user_extra_args(
+            apply_overrides(builder, &self.cmd),
+            BaseCodingAgent::Copilot,
+        )
     }
 }
 
@@ -103,9 +110,10 @@ impl StandardCodingAgentExecutor for Copilot {
             .build_command_builder(&log_dir.to_string_lossy())
             .build_initial();
 
-        let combined_prompt = self.append_prompt.combine_prompt(prompt);
+        let combined_prompt = self.prompt_template.combine_prompt(prompt);
 
         let mut command = Command::new(shell_cmd);
+        apply_execution_env(&mut command);
         command
             .kill_on_drop(true)
             .stdin(Stdio::piped())
@@ -142,9 +150,10 @@ impl StandardCodingAgentExecutor for Copilot {
             .build_command_builder(&log_dir.to_string_lossy())
             .build_follow_up(&["--resume".to_string(), session_id.to_string()]);
 
-        let combined_prompt = self.append_prompt.combine_prompt(prompt);
+        let combined_prompt = self.prompt_template.combine_prompt(prompt);
 
         let mut command = Command::new(shell_cmd);
+        apply_execution_env(&mut command);
 
         command
             .kill_on_drop(true)
@@ -19,8 +19,12 @@ use workspace_utils::{
 };
 
 use crate::{
-    command::{CmdOverrides, CommandBuilder, apply_overrides},
-    executors::{AppendPrompt, ExecutorError, SpawnedChild, StandardCodingAgentExecutor},
+    command::{
+        CmdOverrides, CommandBuilder, apply_execution_env, apply_overrides, apply_user_extra_args,
+    },
+    executors::{
+        BaseCodingAgent, ExecutorError, PromptTemplate, SpawnedChild, StandardCodingAgentExecutor,
+    },
     logs::{
         ActionType, FileChange, NormalizedEntry, NormalizedEntryType, TodoItem, ToolStatus,
         plain_text_processor::PlainTextLogProcessor,
@@ -33,8 +37,8 @@ mod mcp;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS, JsonSchema)]
 pub struct CursorAgent {
-    #[serde(default)]
-    pub append_prompt: AppendPrompt,
+    #[serde(default, alias = "append_prompt")]
+    pub prompt_template: PromptTemplate,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     #[schemars(description = "Force allow commands unless explicitly denied")]
     pub force: Option<bool>,
@@ -58,7 +62,10 @@ impl CursorAgent {
             builder = builder.extend_params(["--model", model]);
         }
 
-        apply_overrides(builder, &self.cmd)
+        apply_user_extra_args(
+            apply_overrides(builder, &self.cmd),
+            BaseCodingAgent::CursorAgent,
+        )
     }
 }
 
@@ -70,9 +77,10 @@ impl StandardCodingAgentExecutor for CursorAgent {
         let (shell_cmd, shell_arg) = get_shell_command();
         let agent_cmd = self.build_command_builder().build_initial();
 
-        let combined_prompt = self.append_prompt.combine_prompt(prompt);
+        let combined_prompt = self.prompt_template.combine_prompt(prompt);
 
         let mut command = Command::new(shell_cmd);
+        apply_execution_env(&mut command);
         command
             .kill_on_drop(true)
             .stdin(Stdio::piped())
@@ -105,9 +113,10 @@ impl StandardCodingAgentExecutor for CursorAgent {
             .build_command_builder()
             .build_follow_up(&["--resume".to_string(), session_id.to_string()]);
 
-        let combined_prompt = self.append_prompt.combine_prompt(prompt);
+        let combined_prompt = self.prompt_template.combine_prompt(prompt);
 
         let mut command = Command::new(shell_cmd);
+        apply_execution_env(&mut command);
         command
             .kill_on_drop(true)
             .stdin(Stdio::piped())
@@ -362,10 +371,10 @@ impl StandardCodingAgentExecutor for CursorAgent {
                                     .map(|code| crate::logs::CommandExitStatus::ExitCode { code });
                                 new_action = ActionType::CommandRun {
                                     command: args.command.clone(),
-                                    result: Some(crate::logs::CommandRunResult {
+                                    result: Some(crate::logs::CommandRunResult::new(
                                         exit_status,
                                         output,
-                                    }),
+                                    )),
                                 };
                             } else if let CursorToolCall::Mcp { args, result } = &tool_call {
                                 // Extract a human-readable text from content array using typed deserialization
@@ -1184,7 +1193,7 @@ mod tests {
         // Avoid relying on feature flag in tests; construct with a dummy command
         let executor = CursorAgent {
             // No command field needed anymore
-            append_prompt: AppendPrompt::default(),
+            prompt_template: PromptTemplate::default(),
             force: None,
             model: None,
             cmd: Default::default(),
@@ -1223,6 +1232,100 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_cursor_fixture_tool_actions() {
+        let executor = CursorAgent {
+            prompt_template: PromptTemplate::default(),
+            force: None,
+            model: None,
+            cmd: Default::default(),
+        };
+        let msg_store = Arc::new(MsgStore::new());
+        let current_dir = std::path::PathBuf::from("/tmp/cursor-fixture");
+        for line in include_str!("../../tests/fixtures/cursor_tool_calls.jsonl").lines() {
+            msg_store.push_stdout(format!("{line}\n"));
+        }
+        msg_store.push_finished();
+
+        executor.normalize_logs(msg_store.clone(), &current_dir);
+        tokio::time::sleep(tokio::time::Duration::from_millis(150)).await;
+
+        // Keep the latest version of each entry, as completed tool calls replace started ones
+        let mut entries = std::collections::BTreeMap::new();
+        for msg in msg_store.get_history() {
+            if let workspace_utils::log_msg::LogMsg::JsonPatch(patch) = msg
+                && let Some((index, entry)) =
+                    crate::logs::utils::patch::extract_normalized_entry_from_patch(&patch)
+            {
+                entries.insert(index, entry);
+            }
+        }
+        let tools: Vec<(String, ActionType, ToolStatus)> = entries
+            .into_values()
+            .filter_map(|entry| match entry.entry_type {
+                NormalizedEntryType::ToolUse {
+                    tool_name,
+                    action_type,
+                    status,
+                } => Some((tool_name, action_type, status)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(tools.len(), 6);
+
+        assert!(matches!(
+            &tools[0],
+            (_, ActionType::FileRead { path }, ToolStatus::Success) if path == "src/main.rs"
+        ));
+        match &tools[1].1 {
+            ActionType::FileEdit { path, changes } => {
+                assert_eq!(path, "src/main.rs");
+                assert!(matches!(
+                    changes.as_slice(),
+                    [FileChange::Edit { unified_diff, .. }] if unified_diff.contains("+    println!(\"hello\");")
+                ));
+            }
+            other => panic!("Expected FileEdit, got {other:?}"),
+        }
+        match &tools[2].1 {
+            ActionType::CommandRun {
+                command,
+                result: Some(result),
+            } => {
+                assert_eq!(command, "cargo run");
+                assert_eq!(result.output.as_deref(), Some("hello\n"));
+                assert!(matches!(
+                    result.exit_status,
+                    Some(crate::logs::CommandExitStatus::ExitCode { code: 0 })
+                ));
+            }
+            other => panic!("Expected CommandRun with result, got {other:?}"),
+        }
+        assert!(matches!(
+            &tools[3],
+            (_, ActionType::Search { query }, ToolStatus::Created) if query == "println"
+        ));
+        assert!(matches!(
+            &tools[4].1,
+            ActionType::FileEdit { path, changes } if path == "old.txt"
+                && matches!(changes.as_slice(), [FileChange::Delete])
+        ));
+        match &tools[5] {
+            (
+                tool_name,
+                ActionType::Tool {
+                    result: Some(result),
+                    ..
+                },
+                ToolStatus::Success,
+            ) => {
+                assert_eq!(tool_name, "mcp:vibe_kanban:list_tasks");
+                assert_eq!(result.value, serde_json::json!("[]"));
+            }
+            other => panic!("Expected MCP tool with result, got {other:?}"),
+        }
+    }
+
     #[test]
     fn test_session_id_extraction_from_system_line() {
         // Ensure we can parse and find session_id from a system JSON line
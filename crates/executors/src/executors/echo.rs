@@ -0,0 +1,229 @@
+use std::{collections::HashMap, path::Path, process::Stdio, sync::Arc};
+
+use async_trait::async_trait;
+use command_group::AsyncCommandGroup;
+use futures::StreamExt;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tokio::{io::AsyncWriteExt, process::Command};
+use ts_rs::TS;
+use uuid::Uuid;
+use workspace_utils::{msg_store::MsgStore, shell::get_shell_command};
+
+use crate::{
+    command::apply_execution_env,
+    executors::{ExecutorError, PromptTemplate, SpawnedChild, StandardCodingAgentExecutor},
+    logs::{
+        NormalizedEntry, NormalizedEntryType,
+        utils::{ConversationPatch, EntryIndexProvider},
+    },
+};
+
+const DEFAULT_RESPONSE: &str = "Echo executor finished without making changes.";
+/// Heading the workflow orchestrator uses to list a station's `output_context_keys`
+const EXPECTED_OUTPUTS_HEADING: &str = "## Expected Outputs";
+
+/// Deterministic executor that prints a canned response instead of calling a model.
+/// Intended for developing and testing workflows.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS, JsonSchema)]
+pub struct Echo {
+    #[serde(default, alias = "append_prompt")]
+    pub prompt_template: PromptTemplate,
+    /// Response printed as the assistant message
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response: Option<String>,
+    /// Values for the JSON output block; requested keys missing here are echoed back as
+    /// `"echo:<key>"`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub outputs: Option<HashMap<String, String>>,
+}
+
+impl Echo {
+    const SESSION_PREFIX: &'static str = "[echo-session] ";
+
+    /// The canned response, followed by a JSON block with every output key the prompt asks for
+    pub fn render_response(&self, prompt: &str) -> String {
+        let mut response = self
+            .response
+            .clone()
+            .unwrap_or_else(|| DEFAULT_RESPONSE.to_string());
+
+        let keys = requested_output_keys(prompt);
+        if !keys.is_empty() {
+            let outputs: serde_json::Map<String, serde_json::Value> = keys
+                .into_iter()
+                .map(|key| {
+                    let value = self
+                        .outputs
+                        .as_ref()
+                        .and_then(|outputs| outputs.get(&key).cloned())
+                        .unwrap_or_else(|| format!("echo:{key}"));
+                    (key, serde_json::Value::String(value))
+                })
+                .collect();
+            let json = serde_json::to_string_pretty(&outputs).unwrap_or_default();
+            response.push_str(&format!("\n\n```json\n{json}\n```"));
+        }
+        response
+    }
+
+    async fn spawn_echo(
+        &self,
+        current_dir: &Path,
+        prompt: &str,
+        session_id: &str,
+    ) -> Result<SpawnedChild, ExecutorError> {
+        let (shell_cmd, shell_arg) = get_shell_command();
+        let echo_command = if cfg!(windows) { "more" } else { "cat" };
+        let combined_prompt = self.prompt_template.combine_prompt(prompt);
+        let output = format!(
+            "{}{session_id}\n{}\n",
+            Self::SESSION_PREFIX,
+            self.render_response(&combined_prompt)
+        );
+
+        let mut command = Command::new(shell_cmd);
+        apply_execution_env(&mut command);
+        command
+            .kill_on_drop(true)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .current_dir(current_dir)
+            .arg(shell_arg)
+            .arg(echo_command);
+
+        let mut child = command.group_spawn()?;
+
+        if let Some(mut stdin) = child.inner().stdin.take() {
+            stdin.write_all(output.as_bytes()).await?;
+            stdin.shutdown().await?;
+        }
+
+        Ok(child.into())
+    }
+}
+
+/// Keys listed as `- key` under the "Expected Outputs" heading of a workflow prompt
+fn requested_output_keys(prompt: &str) -> Vec<String> {
+    let Some((_, section)) = prompt.split_once(EXPECTED_OUTPUTS_HEADING) else {
+        return vec![];
+    };
+    section
+        .lines()
+        .skip(1)
+        .take_while(|line| !line.starts_with('#') && !line.starts_with("```"))
+        .filter_map(|line| line.trim().strip_prefix("- "))
+        .map(|key| key.trim().to_string())
+        .filter(|key| !key.is_empty())
+        .collect()
+}
+
+#[async_trait]
+impl StandardCodingAgentExecutor for Echo {
+    async fn spawn(&self, current_dir: &Path, prompt: &str) -> Result<SpawnedChild, ExecutorError> {
+        let session_id = Uuid::new_v4().to_string();
+        self.spawn_echo(current_dir, prompt, &session_id).await
+    }
+
+    async fn spawn_follow_up(
+        &self,
+        current_dir: &Path,
+        prompt: &str,
+        session_id: &str,
+    ) -> Result<SpawnedChild, ExecutorError> {
+        self.spawn_echo(current_dir, prompt, session_id).await
+    }
+
+    /// Emits the whole response as a single assistant message so that JSON output blocks
+    /// are never split across entries.
+    fn normalize_logs(&self, msg_store: Arc<MsgStore>, _worktree_path: &Path) {
+        let entry_index_provider = EntryIndexProvider::start_from(&msg_store);
+
+        tokio::spawn(async move {
+            let mut lines = msg_store.stdout_lines_stream();
+            let mut entry_index: Option<usize> = None;
+            let mut content = String::new();
+
+            while let Some(Ok(line)) = lines.next().await {
+                if let Some(session_id) = line.strip_prefix(Self::SESSION_PREFIX) {
+                    msg_store.push_session_id(session_id.trim().to_string());
+                    continue;
+                }
+                if !content.is_empty() {
+                    content.push('\n');
+                }
+                content.push_str(&line);
+
+                let entry = NormalizedEntry {
+                    timestamp: None,
+                    entry_type: NormalizedEntryType::AssistantMessage,
+                    content: content.trim_end().to_string(),
+                    metadata: None,
+                };
+                let patch = match entry_index {
+                    Some(index) => ConversationPatch::replace(index, entry),
+                    None => {
+                        let index = entry_index_provider.next();
+                        entry_index = Some(index);
+                        ConversationPatch::add_normalized_entry(index, entry)
+                    }
+                };
+                msg_store.push_patch(patch);
+            }
+        });
+    }
+
+    fn default_mcp_config_path(&self) -> Option<std::path::PathBuf> {
+        None
+    }
+
+    /// Needs no installation, but is never recommended; see
+    /// `ExecutorConfigs::get_recommended_executor_profile`
+    async fn check_availability(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn echo(response: Option<&str>, outputs: &[(&str, &str)]) -> Echo {
+        Echo {
+            prompt_template: PromptTemplate::default(),
+            response: response.map(str::to_string),
+            outputs: Some(
+                outputs
+                    .iter()
+                    .map(|(k, v)| (k.to_string(), v.to_string()))
+                    .collect(),
+            ),
+        }
+    }
+
+    #[test]
+    fn render_response_without_expected_outputs_is_plain() {
+        let executor = echo(Some("All done."), &[]);
+        assert_eq!(executor.render_response("Fix the bug"), "All done.");
+    }
+
+    #[test]
+    fn render_response_answers_requested_output_keys() {
+        let prompt = "Do the work\n\n## Expected Outputs\nPlease provide the following outputs in a JSON code block (```json ... ```):\n- summary\n- pr_url\n\nExample format:\n```json\n{\n  \"summary\": \"your_value_here\"\n}\n```\n";
+        let executor = echo(Some("Done."), &[("summary", "Implemented the fix")]);
+        let response = executor.render_response(prompt);
+
+        let json = response
+            .split_once("```json\n")
+            .and_then(|(_, rest)| rest.split_once("\n```"))
+            .map(|(json, _)| json)
+            .unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(json).unwrap();
+        assert!(response.starts_with("Done."));
+        assert_eq!(
+            parsed,
+            serde_json::json!({ "summary": "Implemented the fix", "pr_url": "echo:pr_url" })
+        );
+    }
+}
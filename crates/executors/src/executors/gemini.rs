@@ -8,8 +8,10 @@ use workspace_utils::msg_store::MsgStore;
 
 pub use super::acp::AcpAgentHarness;
 use crate::{
-    command::{CmdOverrides, CommandBuilder, apply_overrides},
-    executors::{AppendPrompt, ExecutorError, SpawnedChild, StandardCodingAgentExecutor},
+    command::{CmdOverrides, CommandBuilder, apply_overrides, apply_user_extra_args},
+    executors::{
+        BaseCodingAgent, ExecutorError, PromptTemplate, SpawnedChild, StandardCodingAgentExecutor,
+    },
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS, JsonSchema)]
@@ -37,8 +39,8 @@ impl GeminiModel {
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS, JsonSchema)]
 pub struct Gemini {
-    #[serde(default)]
-    pub append_prompt: AppendPrompt,
+    #[serde(default, alias = "append_prompt")]
+    pub prompt_template: PromptTemplate,
     pub model: GeminiModel,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub yolo: Option<bool>,
@@ -56,7 +58,7 @@ impl Gemini {
 
         builder = builder.extend_params(["--experimental-acp"]);
 
-        apply_overrides(builder, &self.cmd)
+        apply_user_extra_args(apply_overrides(builder, &self.cmd), BaseCodingAgent::Gemini)
     }
 }
 
@@ -64,7 +66,7 @@ impl Gemini {
 impl StandardCodingAgentExecutor for Gemini {
     async fn spawn(&self, current_dir: &Path, prompt: &str) -> Result<SpawnedChild, ExecutorError> {
         let harness = AcpAgentHarness::new();
-        let combined_prompt = self.append_prompt.combine_prompt(prompt);
+        let combined_prompt = self.prompt_template.combine_prompt(prompt);
         let gemini_command = self.build_command_builder().build_initial();
         harness
             .spawn_with_command(current_dir, combined_prompt, gemini_command)
@@ -78,7 +80,7 @@ impl StandardCodingAgentExecutor for Gemini {
         session_id: &str,
     ) -> Result<SpawnedChild, ExecutorError> {
         let harness = AcpAgentHarness::new();
-        let combined_prompt = self.append_prompt.combine_prompt(prompt);
+        let combined_prompt = self.prompt_template.combine_prompt(prompt);
         let gemini_command = self.build_command_builder().build_follow_up(&[]);
         harness
             .spawn_follow_up_with_command(current_dir, combined_prompt, session_id, gemini_command)
@@ -16,7 +16,7 @@ use crate::{
     approvals::ExecutorApprovalService,
     executors::{
         amp::Amp, claude::ClaudeCode, codex::Codex, copilot::Copilot, cursor::CursorAgent,
-        gemini::Gemini, opencode::Opencode, qwen::QwenCode,
+        echo::Echo, gemini::Gemini, opencode::Opencode, qwen::QwenCode,
     },
     mcp_config::McpConfig,
 };
@@ -27,6 +27,7 @@ pub mod claude;
 pub mod codex;
 pub mod copilot;
 pub mod cursor;
+pub mod echo;
 pub mod gemini;
 pub mod opencode;
 pub mod qwen;
@@ -84,6 +85,8 @@ pub enum CodingAgent {
     CursorAgent,
     QwenCode,
     Copilot,
+    /// Canned responses for testing workflows; never recommended to users
+    Echo,
 }
 
 impl CodingAgent {
@@ -136,7 +139,7 @@ impl CodingAgent {
             Self::Codex(_) => vec![BaseAgentCapability::SessionFork],
             Self::Gemini(_) => vec![BaseAgentCapability::SessionFork],
             Self::QwenCode(_) => vec![BaseAgentCapability::SessionFork],
-            Self::Opencode(_) | Self::CursorAgent(_) | Self::Copilot(_) => vec![],
+            Self::Opencode(_) | Self::CursorAgent(_) | Self::Copilot(_) | Self::Echo(_) => vec![],
         }
     }
 }
@@ -185,33 +188,74 @@ impl From<AsyncGroupChild> for SpawnedChild {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS, JsonSchema)]
-#[serde(transparent)]
+/// Placeholder in a [`PromptTemplate`] marking where the prompt goes
+pub const PROMPT_PLACEHOLDER: &str = "{{prompt}}";
+
+/// Text wrapped around every prompt sent to an executor. The prompt goes between `prefix` and
+/// `suffix`, or in place of `{{prompt}}` when either of them contains it.
+#[derive(Debug, Clone, Default, Serialize, PartialEq, TS, JsonSchema)]
 #[schemars(
-    title = "Append Prompt",
-    description = "Extra text appended to the prompt",
-    extend("format" = "textarea")
+    title = "Prompt Template",
+    description = "Extra text around the prompt; use {{prompt}} to place the prompt yourself"
 )]
-#[derive(Default)]
-pub struct AppendPrompt(pub Option<String>);
+pub struct PromptTemplate {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(extend("format" = "textarea"))]
+    pub prefix: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(extend("format" = "textarea"))]
+    pub suffix: Option<String>,
+}
 
-impl AppendPrompt {
-    pub fn get(&self) -> Option<String> {
-        self.0.clone()
+impl PromptTemplate {
+    /// A template that only appends text, as the former `append_prompt` setting did
+    pub fn suffix_only(suffix: Option<String>) -> Self {
+        Self {
+            prefix: None,
+            suffix,
+        }
     }
 
     pub fn combine_prompt(&self, prompt: &str) -> String {
-        match self {
-            AppendPrompt(Some(value)) => format!("{prompt}{value}"),
-            AppendPrompt(None) => prompt.to_string(),
+        let prefix = self.prefix.as_deref().unwrap_or_default();
+        let suffix = self.suffix.as_deref().unwrap_or_default();
+        let template = format!("{prefix}{suffix}");
+        if template.contains(PROMPT_PLACEHOLDER) {
+            template.replace(PROMPT_PLACEHOLDER, prompt)
+        } else {
+            format!("{prefix}{prompt}{suffix}")
         }
     }
 }
 
+impl<'de> Deserialize<'de> for PromptTemplate {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            /// Profiles written before templates existed store the appended text directly
+            AppendPrompt(Option<String>),
+            Template {
+                #[serde(default)]
+                prefix: Option<String>,
+                #[serde(default)]
+                suffix: Option<String>,
+            },
+        }
+
+        Ok(match Repr::deserialize(deserializer)? {
+            Repr::AppendPrompt(suffix) => Self::suffix_only(suffix),
+            Repr::Template { prefix, suffix } => Self { prefix, suffix },
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::str::FromStr;
 
+    use strum::VariantNames;
+
     use super::*;
 
     #[test]
@@ -239,4 +283,52 @@ mod tests {
         assert!(result.is_ok(), "CURSOR should deserialize via serde");
         assert_eq!(result.unwrap(), BaseCodingAgent::CursorAgent);
     }
+
+    #[test]
+    fn prompt_template_wraps_prompt() {
+        let template = PromptTemplate {
+            prefix: Some("Follow the style guide.\n".to_string()),
+            suffix: Some("\nRun the tests.".to_string()),
+        };
+        assert_eq!(
+            template.combine_prompt("Fix the bug"),
+            "Follow the style guide.\nFix the bug\nRun the tests."
+        );
+
+        let template = PromptTemplate {
+            prefix: Some("<task>{{prompt}}</task>".to_string()),
+            suffix: None,
+        };
+        assert_eq!(template.combine_prompt("Fix it"), "<task>Fix it</task>");
+        assert_eq!(PromptTemplate::default().combine_prompt("Fix it"), "Fix it");
+    }
+
+    #[test]
+    fn legacy_append_prompt_is_a_suffix() {
+        let agent: ClaudeCode = serde_json::from_str(r#"{"append_prompt": " Be brief."}"#).unwrap();
+        assert_eq!(
+            agent.prompt_template,
+            PromptTemplate::suffix_only(Some(" Be brief.".to_string()))
+        );
+
+        let agent: ClaudeCode =
+            serde_json::from_str(r#"{"prompt_template": {"prefix": "Hi. "}}"#).unwrap();
+        assert_eq!(agent.prompt_template.combine_prompt("Go"), "Hi. Go");
+    }
+
+    #[test]
+    fn every_agent_has_a_default_profile() {
+        let profiles = crate::profile::ExecutorConfigs::from_defaults();
+        for name in CodingAgent::VARIANTS {
+            let agent = BaseCodingAgent::from_str(name).unwrap();
+            assert!(
+                profiles
+                    .executors
+                    .get(&agent)
+                    .and_then(|config| config.get_default())
+                    .is_some(),
+                "{name} has no DEFAULT profile"
+            );
+        }
+    }
 }
@@ -19,14 +19,16 @@ use ts_rs::TS;
 use workspace_utils::{msg_store::MsgStore, path::make_path_relative, shell::get_shell_command};
 
 use crate::{
-    command::{CmdOverrides, CommandBuilder, apply_overrides},
+    command::{
+        CmdOverrides, CommandBuilder, apply_execution_env, apply_overrides, apply_user_extra_args,
+    },
     executors::{
-        AppendPrompt, ExecutorError, SpawnedChild, StandardCodingAgentExecutor,
+        BaseCodingAgent, ExecutorError, PromptTemplate, SpawnedChild, StandardCodingAgentExecutor,
         opencode::share_bridge::Bridge as ShareBridge,
     },
     logs::{
-        ActionType, FileChange, NormalizedEntry, NormalizedEntryType, TodoItem, ToolStatus,
-        utils::EntryIndexProvider,
+        ActionType, FileChange, LogChannel, NormalizedEntry, NormalizedEntryType, TodoItem,
+        ToolStatus, utils::EntryIndexProvider,
     },
     stdout_dup,
 };
@@ -96,8 +98,8 @@ struct OcToolState {
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS, JsonSchema)]
 pub struct Opencode {
-    #[serde(default)]
-    pub append_prompt: AppendPrompt,
+    #[serde(default, alias = "append_prompt")]
+    pub prompt_template: PromptTemplate,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub model: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -122,7 +124,10 @@ impl Opencode {
             builder = builder.extend_params(["--agent", agent]);
         }
 
-        apply_overrides(builder, &self.cmd)
+        apply_user_extra_args(
+            apply_overrides(builder, &self.cmd),
+            BaseCodingAgent::Opencode,
+        )
     }
 }
 
@@ -134,9 +139,10 @@ impl StandardCodingAgentExecutor for Opencode {
         let (shell_cmd, shell_arg) = get_shell_command();
         let opencode_command = self.build_command_builder().build_initial();
 
-        let combined_prompt = self.append_prompt.combine_prompt(prompt);
+        let combined_prompt = self.prompt_template.combine_prompt(prompt);
 
         let mut command = Command::new(shell_cmd);
+        apply_execution_env(&mut command);
         command
             .kill_on_drop(true)
             .stdin(Stdio::piped())
@@ -201,9 +207,10 @@ impl StandardCodingAgentExecutor for Opencode {
             .build_command_builder()
             .build_follow_up(&["--session".to_string(), session_id.to_string()]);
 
-        let combined_prompt = self.append_prompt.combine_prompt(prompt);
+        let combined_prompt = self.prompt_template.combine_prompt(prompt);
 
         let mut command = Command::new(shell_cmd);
+        apply_execution_env(&mut command);
         command
             .kill_on_drop(true)
             .stdin(Stdio::piped())
@@ -328,7 +335,8 @@ impl Opencode {
                     entry_type: NormalizedEntryType::ErrorMessage,
                     content: line.clone(),
                     metadata: None,
-                };
+                }
+                .with_channel(LogChannel::Stderr);
 
                 // Create a patch for this single entry
                 let patch = crate::logs::utils::ConversationPatch::add_normalized_entry(
@@ -772,10 +780,10 @@ impl Opencode {
                     let resolved_action_type = match action_type {
                         Some(mut at) => match (&mut at, &result) {
                             (ActionType::CommandRun { result: r, .. }, Some(res)) => {
-                                *r = Some(crate::logs::CommandRunResult {
-                                    exit_status: exit_status.clone(),
-                                    output: res.value.as_str().map(|s| s.to_owned()),
-                                });
+                                *r = Some(crate::logs::CommandRunResult::new(
+                                    exit_status.clone(),
+                                    res.value.as_str().map(|s| s.to_owned()),
+                                ));
                                 at
                             }
                             _ => at,
@@ -963,3 +971,104 @@ impl LogUtils {
         line.starts_with("!  ")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use workspace_utils::log_msg::LogMsg;
+
+    use super::*;
+    use crate::logs::utils::patch::extract_normalized_entry_from_patch;
+
+    #[tokio::test]
+    async fn test_share_event_fixture_tool_actions() {
+        let lines: Vec<String> = include_str!("../../tests/fixtures/opencode_share_events.jsonl")
+            .lines()
+            .filter_map(|line| line.strip_prefix(Opencode::SHARE_PREFIX))
+            .map(str::to_string)
+            .collect();
+        let msg_store = Arc::new(MsgStore::new());
+        Opencode::process_share_events(
+            futures::stream::iter(lines).boxed(),
+            PathBuf::from("/tmp/oc-fixture"),
+            EntryIndexProvider::test_new(),
+            msg_store.clone(),
+        )
+        .await;
+
+        // Keep the latest version of each entry, as tool parts are updated in place
+        let mut entries = BTreeMap::new();
+        for msg in msg_store.get_history() {
+            if let LogMsg::JsonPatch(patch) = msg
+                && let Some((index, entry)) = extract_normalized_entry_from_patch(&patch)
+            {
+                entries.insert(index, entry);
+            }
+        }
+        let entries: Vec<NormalizedEntry> = entries.into_values().collect();
+        assert!(matches!(
+            entries[0].entry_type,
+            NormalizedEntryType::AssistantMessage
+        ));
+        assert_eq!(entries[0].content, "Reading the library first.");
+
+        let actions: Vec<(String, ActionType)> = entries
+            .into_iter()
+            .filter_map(|entry| match entry.entry_type {
+                NormalizedEntryType::ToolUse {
+                    tool_name,
+                    action_type,
+                    ..
+                } => Some((tool_name, action_type)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(actions.len(), 6);
+
+        assert!(matches!(
+            &actions[0].1,
+            ActionType::FileRead { path } if path == "src/lib.rs"
+        ));
+        match &actions[1].1 {
+            ActionType::FileEdit { path, changes } => {
+                assert_eq!(path, "src/lib.rs");
+                assert!(matches!(
+                    changes.as_slice(),
+                    [FileChange::Edit { unified_diff, .. }] if unified_diff.contains("+pub fn add(a: i32, b: i32)")
+                ));
+            }
+            other => panic!("Expected FileEdit, got {other:?}"),
+        }
+        match &actions[2].1 {
+            ActionType::CommandRun {
+                command,
+                result: Some(result),
+            } => {
+                assert_eq!(command, "cargo test");
+                assert_eq!(
+                    result.output.as_deref(),
+                    Some("test result: ok. 1 passed\n")
+                );
+                assert!(matches!(
+                    result.exit_status,
+                    Some(crate::logs::CommandExitStatus::ExitCode { code: 0 })
+                ));
+            }
+            other => panic!("Expected CommandRun with result, got {other:?}"),
+        }
+        assert!(matches!(
+            &actions[3].1,
+            ActionType::Search { query } if query == "fn add"
+        ));
+        assert!(matches!(
+            &actions[4].1,
+            ActionType::WebFetch { url } if url == "https://doc.rust-lang.org/std/"
+        ));
+        // Tools without a dedicated action fall back to a generic tool call
+        assert!(matches!(
+            &actions[5],
+            (tool_name, ActionType::Tool { result: Some(_), .. }) if tool_name == "list"
+        ));
+    }
+}
@@ -7,17 +7,17 @@ use ts_rs::TS;
 use workspace_utils::msg_store::MsgStore;
 
 use crate::{
-    command::{CmdOverrides, CommandBuilder, apply_overrides},
+    command::{CmdOverrides, CommandBuilder, apply_overrides, apply_user_extra_args},
     executors::{
-        AppendPrompt, ExecutorError, SpawnedChild, StandardCodingAgentExecutor,
+        BaseCodingAgent, ExecutorError, PromptTemplate, SpawnedChild, StandardCodingAgentExecutor,
         gemini::AcpAgentHarness,
     },
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS, JsonSchema)]
 pub struct QwenCode {
-    #[serde(default)]
-    pub append_prompt: AppendPrompt,
+    #[serde(default, alias = "append_prompt")]
+    pub prompt_template: PromptTemplate,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub yolo: Option<bool>,
     #[serde(flatten)]
@@ -32,7 +32,10 @@ impl QwenCode {
             builder = builder.extend_params(["--yolo"]);
         }
         builder = builder.extend_params(["--experimental-acp"]);
-        apply_overrides(builder, &self.cmd)
+        apply_user_extra_args(
+            apply_overrides(builder, &self.cmd),
+            BaseCodingAgent::QwenCode,
+        )
     }
 }
 
@@ -40,7 +43,7 @@ impl QwenCode {
 impl StandardCodingAgentExecutor for QwenCode {
     async fn spawn(&self, current_dir: &Path, prompt: &str) -> Result<SpawnedChild, ExecutorError> {
         let qwen_command = self.build_command_builder().build_initial();
-        let combined_prompt = self.append_prompt.combine_prompt(prompt);
+        let combined_prompt = self.prompt_template.combine_prompt(prompt);
         let harness = AcpAgentHarness::with_session_namespace("qwen_sessions");
         harness
             .spawn_with_command(current_dir, combined_prompt, qwen_command)
@@ -54,7 +57,7 @@ impl StandardCodingAgentExecutor for QwenCode {
         session_id: &str,
     ) -> Result<SpawnedChild, ExecutorError> {
         let qwen_command = self.build_command_builder().build_follow_up(&[]);
-        let combined_prompt = self.append_prompt.combine_prompt(prompt);
+        let combined_prompt = self.prompt_template.combine_prompt(prompt);
         let harness = AcpAgentHarness::with_session_namespace("qwen_sessions");
         harness
             .spawn_follow_up_with_command(current_dir, combined_prompt, session_id, qwen_command)
@@ -38,6 +38,33 @@ pub struct CommandRunResult {
     pub output: Option<String>,
 }
 
+/// Output kept on a `CommandRun` entry; anything longer keeps only its tail
+pub const MAX_COMMAND_OUTPUT_BYTES: usize = 64 * 1024;
+
+pub const COMMAND_OUTPUT_TRUNCATED_NOTE: &str = "[... earlier output truncated ...]";
+
+impl CommandRunResult {
+    pub fn new(exit_status: Option<CommandExitStatus>, output: Option<String>) -> Self {
+        Self {
+            exit_status,
+            output: output.map(cap_command_output),
+        }
+    }
+}
+
+/// Keep the last `MAX_COMMAND_OUTPUT_BYTES` of command output, where errors and summaries
+/// usually are, behind a note that the beginning was cut.
+pub fn cap_command_output(output: String) -> String {
+    if output.len() <= MAX_COMMAND_OUTPUT_BYTES {
+        return output;
+    }
+    let mut start = output.len() - MAX_COMMAND_OUTPUT_BYTES;
+    while !output.is_char_boundary(start) {
+        start += 1;
+    }
+    format!("{COMMAND_OUTPUT_TRUNCATED_NOTE}\n{}", &output[start..])
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 pub struct NormalizedConversation {
     pub entries: Vec<NormalizedEntry>,
@@ -71,16 +98,79 @@ pub enum NormalizedEntryType {
     },
 }
 
+/// The process output stream an entry was normalized from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum LogChannel {
+    Stdout,
+    Stderr,
+}
+
+impl LogChannel {
+    /// The channel recorded under `channel` in an entry's metadata. Entries without one
+    /// were normalized from stdout.
+    pub fn from_metadata(metadata: Option<&serde_json::Value>) -> Self {
+        metadata
+            .and_then(|metadata| metadata.get("channel"))
+            .and_then(|channel| serde_json::from_value(channel.clone()).ok())
+            .unwrap_or(LogChannel::Stdout)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 pub struct NormalizedEntry {
     pub timestamp: Option<String>,
     pub entry_type: NormalizedEntryType,
     pub content: String,
-    #[ts(skip)]
+    /// Executor-specific details; `channel` records the [`LogChannel`] the entry came from
+    #[ts(optional)]
     pub metadata: Option<serde_json::Value>,
 }
 
 impl NormalizedEntry {
+    pub fn channel(&self) -> LogChannel {
+        LogChannel::from_metadata(self.metadata.as_ref())
+    }
+
+    /// Record the channel the entry was normalized from, keeping any other metadata
+    pub fn with_channel(mut self, channel: LogChannel) -> Self {
+        self.insert_metadata("channel", serde_json::to_value(channel).unwrap());
+        self
+    }
+
+    /// Whether this assistant message asks the user something, as flagged by
+    /// [`Self::with_awaiting_input_flag`]
+    pub fn awaits_input(&self) -> bool {
+        self.metadata
+            .as_ref()
+            .and_then(|metadata| metadata.get("awaits_input"))
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false)
+    }
+
+    /// Flag an assistant message that asks the user something, see
+    /// [`utils::awaits_user_input`]. Messages are re-flagged as they stream in, so the flag of
+    /// the final one tells whether the turn ended waiting on the user.
+    pub fn with_awaiting_input_flag(mut self) -> Self {
+        if matches!(self.entry_type, NormalizedEntryType::AssistantMessage) {
+            let awaits_input = utils::awaits_user_input(&self.content);
+            if awaits_input || self.awaits_input() {
+                self.insert_metadata("awaits_input", serde_json::Value::Bool(awaits_input));
+            }
+        }
+        self
+    }
+
+    fn insert_metadata(&mut self, key: &str, value: serde_json::Value) {
+        match &mut self.metadata {
+            Some(serde_json::Value::Object(metadata)) => {
+                metadata.insert(key.to_string(), value);
+            }
+            _ => self.metadata = Some(serde_json::json!({ key: value })),
+        }
+    }
+
     pub fn with_tool_status(&self, status: ToolStatus) -> Option<Self> {
         if let NormalizedEntryType::ToolUse {
             tool_name,
@@ -129,6 +219,9 @@ impl ToolStatus {
                 reason: reason.clone(),
             }),
             ApprovalStatus::TimedOut => Some(ToolStatus::TimedOut),
+            ApprovalStatus::Cancelled => Some(ToolStatus::Denied {
+                reason: Some("Execution was stopped".to_string()),
+            }),
             ApprovalStatus::Pending => None, // this should not happen
         }
     }
@@ -143,6 +236,17 @@ pub struct TodoItem {
     pub priority: Option<String>,
 }
 
+/// One item of a presented plan, see [`utils::plan::parse_plan_steps`]
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct PlanStep {
+    pub text: String,
+    /// The agent marked the step as completed (`- [x]`)
+    pub done: bool,
+    /// Nesting depth, 0 for top-level steps
+    pub level: usize,
+}
+
 /// Types of tool actions that can be performed
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[ts(export)]
@@ -179,6 +283,9 @@ pub enum ActionType {
     },
     PlanPresentation {
         plan: String,
+        /// Steps parsed from `plan`; a single step with the raw text for free-form plans
+        #[serde(default)]
+        plan_steps: Vec<PlanStep>,
     },
     TodoManagement {
         todos: Vec<TodoItem>,
@@ -206,3 +313,41 @@ pub enum FileChange {
         has_line_numbers: bool,
     },
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn command_output_keeps_tail_within_cap() {
+        let short = CommandRunResult::new(None, Some("ok".to_string()));
+        assert_eq!(short.output.as_deref(), Some("ok"));
+
+        // Multi-byte characters straddle the cut point
+        let long = format!("{}END", "é".repeat(MAX_COMMAND_OUTPUT_BYTES));
+        let capped = CommandRunResult::new(None, Some(long)).output.unwrap();
+        assert!(capped.starts_with(COMMAND_OUTPUT_TRUNCATED_NOTE));
+        assert!(capped.ends_with("END"));
+        assert!(capped.len() <= MAX_COMMAND_OUTPUT_BYTES + COMMAND_OUTPUT_TRUNCATED_NOTE.len() + 1);
+    }
+
+    #[test]
+    fn channel_round_trips_through_metadata() {
+        let entry = NormalizedEntry {
+            timestamp: None,
+            entry_type: NormalizedEntryType::ErrorMessage,
+            content: "boom".to_string(),
+            metadata: Some(serde_json::json!({ "session_id": "abc" })),
+        };
+        assert_eq!(entry.channel(), LogChannel::Stdout);
+
+        let entry = entry.with_channel(LogChannel::Stderr);
+        assert_eq!(entry.channel(), LogChannel::Stderr);
+        assert_eq!(entry.metadata.as_ref().unwrap()["session_id"], "abc");
+
+        // Survives the serialization used for patches and stored logs
+        let entry: NormalizedEntry =
+            serde_json::from_value(serde_json::to_value(&entry).unwrap()).unwrap();
+        assert_eq!(entry.channel(), LogChannel::Stderr);
+    }
+}
@@ -1,7 +1,8 @@
 //! Standard stderr log processor for executors
 //!
 //! Uses `PlainTextLogProcessor` with a 2-second `latency_threshold` to split stderr streams into entries.
-//! Each entry is normalized as `ErrorMessage` and emitted as JSON patches to the message store.
+//! Each entry is normalized as `ErrorMessage`, tagged with the stderr [`LogChannel`] and emitted as
+//! JSON patches to the message store.
 //!
 //! Example:
 //! ```rust,ignore
@@ -13,14 +14,17 @@ use std::{sync::Arc, time::Duration};
 use futures::StreamExt;
 use workspace_utils::msg_store::MsgStore;
 
-use super::{NormalizedEntry, NormalizedEntryType, plain_text_processor::PlainTextLogProcessor};
+use super::{
+    LogChannel, NormalizedEntry, NormalizedEntryType, plain_text_processor::PlainTextLogProcessor,
+};
 use crate::logs::utils::EntryIndexProvider;
 
 /// Standard stderr log normalizer that uses PlainTextLogProcessor to stream error logs.
 ///
 /// Splits stderr output into discrete entries based on a latency threshold (2s) to group
 /// related lines into a single error entry. Each entry is normalized as an `ErrorMessage`
-/// and emitted as JSON patches for downstream consumption (e.g., UI or log aggregation).
+/// tagged with the stderr channel and emitted as JSON patches for downstream consumption
+/// (e.g., UI or log aggregation).
 ///
 /// # Options
 /// - `latency_threshold`: 2 seconds to separate error messages based on time gaps.
@@ -39,11 +43,14 @@ pub fn normalize_stderr_logs(msg_store: Arc<MsgStore>, entry_index_provider: Ent
 
         // Create a processor with time-based emission for stderr
         let mut processor = PlainTextLogProcessor::builder()
-            .normalized_entry_producer(Box::new(|content: String| NormalizedEntry {
-                timestamp: None,
-                entry_type: NormalizedEntryType::ErrorMessage,
-                content: strip_ansi_escapes::strip_str(&content),
-                metadata: None,
+            .normalized_entry_producer(Box::new(|content: String| {
+                NormalizedEntry {
+                    timestamp: None,
+                    entry_type: NormalizedEntryType::ErrorMessage,
+                    content: strip_ansi_escapes::strip_str(&content),
+                    metadata: None,
+                }
+                .with_channel(LogChannel::Stderr)
             }))
             .time_gap(Duration::from_secs(2)) // Break messages if they are 2 seconds apart
             .index_provider(entry_index_provider)
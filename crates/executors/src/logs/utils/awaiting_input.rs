@@ -0,0 +1,52 @@
+/// Phrases an agent (or its system prompt) uses to say explicitly that it is blocked on the
+/// user. Matched case-insensitively anywhere in the final message.
+const AWAITING_INPUT_MARKERS: &[&str] = &[
+    "[awaiting input]",
+    "awaiting your input",
+    "awaiting your reply",
+    "waiting for your input",
+    "waiting for your reply",
+    "waiting for your response",
+];
+
+/// Heuristic for "the agent asked a question and stopped": the final assistant message either
+/// ends in a question or contains an explicit awaiting-input marker. Trailing markdown emphasis
+/// and closing quotes or brackets are ignored, so `**Should I continue?**` counts.
+pub fn awaits_user_input(final_message: &str) -> bool {
+    let lowercase = final_message.to_lowercase();
+    if AWAITING_INPUT_MARKERS
+        .iter()
+        .any(|marker| lowercase.contains(marker))
+    {
+        return true;
+    }
+
+    final_message
+        .lines()
+        .map(str::trim)
+        .rfind(|line| !line.is_empty())
+        .map(|line| line.trim_end_matches(['*', '_', '`', '"', '\'', ')', ']', ' ']))
+        .is_some_and(|line| line.ends_with('?'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_trailing_questions_and_markers() {
+        assert!(awaits_user_input(
+            "I found two configs.\n\nWhich one should I update?\n"
+        ));
+        assert!(awaits_user_input("**Should I also migrate the tests?**"));
+        assert!(awaits_user_input(
+            "Stopping here until you confirm. [Awaiting input]\n\nThanks."
+        ));
+
+        assert!(!awaits_user_input(
+            "Done. Why did it fail? A missing import."
+        ));
+        assert!(!awaits_user_input("```rust\nlet x = a?;\n```"));
+        assert!(!awaits_user_input(""));
+    }
+}
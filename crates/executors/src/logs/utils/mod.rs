@@ -1,7 +1,11 @@
 //! Utility modules for executor framework
 
+pub mod awaiting_input;
 pub mod entry_index;
 pub mod patch;
+pub mod plan;
+pub mod redact;
 
+pub use awaiting_input::awaits_user_input;
 pub use entry_index::EntryIndexProvider;
-pub use patch::ConversationPatch;
+pub use patch::{ChannelPatchFilter, ConversationPatch};
@@ -1,10 +1,10 @@
 use json_patch::Patch;
 use serde::{Deserialize, Serialize};
-use serde_json::{from_value, json, to_value};
+use serde_json::{Value, from_value, json, to_value};
 use ts_rs::TS;
 use workspace_utils::diff::Diff;
 
-use crate::logs::NormalizedEntry;
+use crate::logs::{LogChannel, NormalizedEntry, utils::redact::redact_json_strings};
 
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, TS)]
 #[serde(rename_all = "lowercase")]
@@ -35,19 +35,29 @@ pub fn escape_json_pointer_segment(s: &str) -> String {
     s.replace('~', "~0").replace('/', "~1")
 }
 
+/// Entries shown in the conversation are scrubbed of secrets before they are streamed or
+/// stored, see [`crate::logs::utils::redact`]
+fn redacted_patch(patch_entry: PatchEntry) -> Patch {
+    let mut value = json!([patch_entry]);
+    redact_json_strings(&mut value);
+    from_value(value).unwrap()
+}
+
 /// Helper functions to create JSON patches for conversation entries
 pub struct ConversationPatch;
 
 impl ConversationPatch {
-    /// Create an ADD patch for a new conversation entry at the given index
+    /// Create an ADD patch for a new conversation entry at the given index. Assistant messages
+    /// asking the user something are flagged on the way, see
+    /// [`NormalizedEntry::with_awaiting_input_flag`].
     pub fn add_normalized_entry(entry_index: usize, entry: NormalizedEntry) -> Patch {
         let patch_entry = PatchEntry {
             op: PatchOperation::Add,
             path: format!("/entries/{entry_index}"),
-            value: PatchType::NormalizedEntry(entry),
+            value: PatchType::NormalizedEntry(entry.with_awaiting_input_flag()),
         };
 
-        from_value(json!([patch_entry])).unwrap()
+        redacted_patch(patch_entry)
     }
 
     /// Create an ADD patch for a new string at the given index
@@ -58,7 +68,7 @@ impl ConversationPatch {
             value: PatchType::Stdout(entry),
         };
 
-        from_value(json!([patch_entry])).unwrap()
+        redacted_patch(patch_entry)
     }
 
     /// Create an ADD patch for a new string at the given index
@@ -69,7 +79,7 @@ impl ConversationPatch {
             value: PatchType::Stderr(entry),
         };
 
-        from_value(json!([patch_entry])).unwrap()
+        redacted_patch(patch_entry)
     }
 
     /// Create an ADD patch for a new diff at the given index
@@ -108,10 +118,10 @@ impl ConversationPatch {
         let patch_entry = PatchEntry {
             op: PatchOperation::Replace,
             path: format!("/entries/{entry_index}"),
-            value: PatchType::NormalizedEntry(entry),
+            value: PatchType::NormalizedEntry(entry.with_awaiting_input_flag()),
         };
 
-        from_value(json!([patch_entry])).unwrap()
+        redacted_patch(patch_entry)
     }
 
     pub fn remove(entry_index: usize) -> Patch {
@@ -139,3 +149,223 @@ pub fn extract_normalized_entry_from_patch(patch: &Patch) -> Option<(usize, Norm
             .map(|entry| (entry_index, entry))
     })
 }
+
+/// Narrows a conversation patch stream to the entries of one [`LogChannel`]
+///
+/// Entry indices are rewritten so the filtered patches apply to a list holding only the kept
+/// entries. Raw `STDERR` entries belong to the stderr channel, normalized entries to the channel
+/// in their metadata, and everything else to stdout. A replace that moves an entry between
+/// channels becomes an add or a remove in the filtered stream.
+pub struct ChannelPatchFilter {
+    channel: LogChannel,
+    /// For every entry of the unfiltered list, whether it belongs to `channel`
+    kept: Vec<bool>,
+}
+
+impl ChannelPatchFilter {
+    pub fn new(channel: LogChannel) -> Self {
+        Self {
+            channel,
+            kept: Vec::new(),
+        }
+    }
+
+    /// The operations of `patch` that touch kept entries, or `None` if there are none
+    pub fn filter(&mut self, patch: &Patch) -> Option<Patch> {
+        let value = to_value(patch).ok()?;
+        let mut filtered = Vec::new();
+        for op in value.as_array()? {
+            let Some(index) = op
+                .get("path")
+                .and_then(Value::as_str)
+                .and_then(|path| path.strip_prefix("/entries/"))
+                .and_then(|index| index.parse::<usize>().ok())
+            else {
+                continue;
+            };
+            let filtered_index = self.kept[..index.min(self.kept.len())]
+                .iter()
+                .filter(|kept| **kept)
+                .count();
+            let channel = op.get("value").map(patch_value_channel);
+            let mut filtered_op = op.clone();
+            match op.get("op").and_then(Value::as_str) {
+                Some("add") => {
+                    let keep = channel == Some(self.channel);
+                    self.kept.insert(index.min(self.kept.len()), keep);
+                    if !keep {
+                        continue;
+                    }
+                }
+                Some("replace") if index < self.kept.len() => {
+                    // The new value decides whether the entry is in the filtered list
+                    let was_kept = self.kept[index];
+                    let keep = channel == Some(self.channel);
+                    self.kept[index] = keep;
+                    match (was_kept, keep) {
+                        (true, true) => {}
+                        (false, true) => filtered_op["op"] = json!("add"),
+                        (true, false) => filtered_op = json!({ "op": "remove" }),
+                        (false, false) => continue,
+                    }
+                }
+                Some("remove") if index < self.kept.len() => {
+                    if !self.kept.remove(index) {
+                        continue;
+                    }
+                }
+                _ => continue,
+            }
+            filtered_op["path"] = json!(format!("/entries/{filtered_index}"));
+            filtered.push(filtered_op);
+        }
+        if filtered.is_empty() {
+            return None;
+        }
+        from_value(Value::Array(filtered)).ok()
+    }
+}
+
+fn patch_value_channel(value: &Value) -> LogChannel {
+    match value.get("type").and_then(Value::as_str) {
+        Some("STDERR") => LogChannel::Stderr,
+        Some("NORMALIZED_ENTRY") => LogChannel::from_metadata(value.pointer("/content/metadata")),
+        _ => LogChannel::Stdout,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logs::NormalizedEntryType;
+
+    fn entry(content: &str, channel: LogChannel) -> NormalizedEntry {
+        NormalizedEntry {
+            timestamp: None,
+            entry_type: NormalizedEntryType::ErrorMessage,
+            content: content.to_string(),
+            metadata: None,
+        }
+        .with_channel(channel)
+    }
+
+    fn paths(patch: &Patch) -> Vec<String> {
+        to_value(patch)
+            .unwrap()
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|op| {
+                format!(
+                    "{} {}",
+                    op["op"].as_str().unwrap(),
+                    op["path"].as_str().unwrap()
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn stderr_filter_keeps_indices_contiguous() {
+        let mut filter = ChannelPatchFilter::new(LogChannel::Stderr);
+
+        let out = ConversationPatch::add_normalized_entry(0, entry("hi", LogChannel::Stdout));
+        assert!(filter.filter(&out).is_none());
+
+        let err = ConversationPatch::add_normalized_entry(1, entry("boom", LogChannel::Stderr));
+        assert_eq!(paths(&filter.filter(&err).unwrap()), ["add /entries/0"]);
+
+        let raw = ConversationPatch::add_stderr(2, "raw".to_string());
+        assert_eq!(paths(&filter.filter(&raw).unwrap()), ["add /entries/1"]);
+
+        let replace = ConversationPatch::replace(2, entry("raw!", LogChannel::Stderr));
+        assert_eq!(
+            paths(&filter.filter(&replace).unwrap()),
+            ["replace /entries/1"]
+        );
+
+        // Removing a dropped entry shifts nothing in the filtered list
+        assert!(filter.filter(&ConversationPatch::remove(0)).is_none());
+        assert_eq!(
+            paths(&filter.filter(&ConversationPatch::remove(0)).unwrap()),
+            ["remove /entries/0"]
+        );
+
+        let replace = ConversationPatch::replace(0, entry("x", LogChannel::Stderr));
+        assert_eq!(
+            paths(&filter.filter(&replace).unwrap()),
+            ["replace /entries/0"]
+        );
+    }
+
+    #[test]
+    fn replace_that_changes_channel_adds_or_removes() {
+        let mut filter = ChannelPatchFilter::new(LogChannel::Stderr);
+        let out = ConversationPatch::add_normalized_entry(0, entry("a", LogChannel::Stdout));
+        assert!(filter.filter(&out).is_none());
+        let err = ConversationPatch::add_normalized_entry(1, entry("b", LogChannel::Stderr));
+        assert_eq!(paths(&filter.filter(&err).unwrap()), ["add /entries/0"]);
+
+        // Entry 0 moves to stderr, so it appears ahead of entry 1
+        let to_stderr = ConversationPatch::replace(0, entry("a", LogChannel::Stderr));
+        assert_eq!(
+            paths(&filter.filter(&to_stderr).unwrap()),
+            ["add /entries/0"]
+        );
+
+        // Entry 1 moves to stdout, so it leaves the filtered list
+        let to_stdout = ConversationPatch::replace(1, entry("b", LogChannel::Stdout));
+        assert_eq!(
+            paths(&filter.filter(&to_stdout).unwrap()),
+            ["remove /entries/1"]
+        );
+
+        let unchanged = ConversationPatch::replace(1, entry("b!", LogChannel::Stdout));
+        assert!(filter.filter(&unchanged).is_none());
+    }
+
+    #[test]
+    fn stdout_filter_keeps_untagged_entries() {
+        let mut filter = ChannelPatchFilter::new(LogChannel::Stdout);
+        let untagged = NormalizedEntry {
+            timestamp: None,
+            entry_type: NormalizedEntryType::AssistantMessage,
+            content: "done".to_string(),
+            metadata: Some(json!({ "session_id": "abc" })),
+        };
+        let raw = ConversationPatch::add_stderr(0, "raw".to_string());
+        assert!(filter.filter(&raw).is_none());
+
+        let untagged = ConversationPatch::add_normalized_entry(1, untagged);
+        assert_eq!(
+            paths(&filter.filter(&untagged).unwrap()),
+            ["add /entries/0"]
+        );
+    }
+
+    #[test]
+    fn assistant_messages_asking_the_user_are_flagged() {
+        let asking = NormalizedEntry {
+            timestamp: None,
+            entry_type: NormalizedEntryType::AssistantMessage,
+            content: "Which config should I update?".to_string(),
+            metadata: None,
+        };
+        let patch = ConversationPatch::add_normalized_entry(0, asking);
+        let (_, flagged) = extract_normalized_entry_from_patch(&patch).unwrap();
+        assert!(flagged.awaits_input());
+
+        // The message streamed on past its question
+        let answered = NormalizedEntry {
+            content: "Which config should I update? Both, so I did.".to_string(),
+            ..flagged
+        };
+        let patch = ConversationPatch::replace(0, answered);
+        let (_, cleared) = extract_normalized_entry_from_patch(&patch).unwrap();
+        assert!(!cleared.awaits_input());
+
+        let error = ConversationPatch::add_normalized_entry(1, entry("Why?", LogChannel::Stdout));
+        let (_, error) = extract_normalized_entry_from_patch(&error).unwrap();
+        assert!(!error.awaits_input());
+    }
+}
@@ -0,0 +1,130 @@
+//! Split free-form plan markdown into checklist steps
+
+use crate::logs::PlanStep;
+
+/// Parse the numbered and bulleted items of a plan into steps. Markdown task boxes
+/// (`- [x]`) mark a step done; text outside list items and inside code fences is ignored.
+/// A plan without any list items becomes a single step holding the raw text.
+pub fn parse_plan_steps(plan: &str) -> Vec<PlanStep> {
+    let mut steps = Vec::new();
+    let mut in_code_fence = false;
+
+    for line in plan.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code_fence = !in_code_fence;
+            continue;
+        }
+        if in_code_fence {
+            continue;
+        }
+        let Some(item) = strip_list_marker(trimmed) else {
+            continue;
+        };
+        let (done, text) = strip_task_box(item);
+        let text = text.trim();
+        if text.is_empty() {
+            continue;
+        }
+        steps.push(PlanStep {
+            text: text.to_string(),
+            done,
+            level: indent_width(line) / 2,
+        });
+    }
+
+    // Nesting is relative to the shallowest item, so a uniformly indented list is top-level
+    if let Some(min_level) = steps.iter().map(|step| step.level).min() {
+        for step in &mut steps {
+            step.level -= min_level;
+        }
+    }
+
+    if steps.is_empty() && !plan.trim().is_empty() {
+        steps.push(PlanStep {
+            text: plan.trim().to_string(),
+            done: false,
+            level: 0,
+        });
+    }
+    steps
+}
+
+/// Text after a `-`, `*`, `+`, `1.` or `1)` list marker
+fn strip_list_marker(line: &str) -> Option<&str> {
+    if let Some(rest) = line
+        .strip_prefix("- ")
+        .or_else(|| line.strip_prefix("* "))
+        .or_else(|| line.strip_prefix("+ "))
+    {
+        return Some(rest);
+    }
+    let digits = line.len() - line.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    if digits == 0 || digits > 3 {
+        return None;
+    }
+    line[digits..]
+        .strip_prefix(". ")
+        .or_else(|| line[digits..].strip_prefix(") "))
+}
+
+fn strip_task_box(item: &str) -> (bool, &str) {
+    if let Some(rest) = item
+        .strip_prefix("[x]")
+        .or_else(|| item.strip_prefix("[X]"))
+    {
+        (true, rest)
+    } else if let Some(rest) = item.strip_prefix("[ ]") {
+        (false, rest)
+    } else {
+        (false, item)
+    }
+}
+
+fn indent_width(line: &str) -> usize {
+    line.chars()
+        .take_while(|c| c.is_whitespace())
+        .map(|c| if c == '\t' { 4 } else { 1 })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_numbered_steps_with_nested_bullets() {
+        let plan = "## Plan\n\nIntro text.\n\n1. Add the model\n   - write migration\n   - update queries\n2) Wire the route\n\n```rust\n- not a step\n```\n";
+        let steps = parse_plan_steps(plan);
+        let summary: Vec<_> = steps
+            .iter()
+            .map(|s| (s.text.as_str(), s.level, s.done))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("Add the model", 0, false),
+                ("write migration", 1, false),
+                ("update queries", 1, false),
+                ("Wire the route", 0, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn task_boxes_mark_steps_done() {
+        let steps = parse_plan_steps("- [x] Read the code\n- [ ] Fix the bug\n* [X] Test");
+        let done: Vec<_> = steps.iter().map(|s| s.done).collect();
+        assert_eq!(done, vec![true, false, true]);
+        assert_eq!(steps[1].text, "Fix the bug");
+    }
+
+    #[test]
+    fn free_form_plan_falls_back_to_single_step() {
+        let steps = parse_plan_steps("  Refactor the parser, then add tests.  \n");
+        assert_eq!(steps.len(), 1);
+        assert_eq!(steps[0].text, "Refactor the parser, then add tests.");
+        assert!(!steps[0].done);
+        assert!(parse_plan_steps("   ").is_empty());
+    }
+}
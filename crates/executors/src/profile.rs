@@ -224,7 +224,15 @@ impl ExecutorConfigs {
             Ok(mut user_overrides) => {
                 tracing::info!("Loaded user profile overrides from profiles.json");
                 user_overrides.canonicalise();
-                Self::merge_with_defaults(defaults, user_overrides)
+                let merged = Self::merge_with_defaults(defaults, user_overrides);
+                // Rewrite profiles saved before `append_prompt` became `prompt_template`
+                if content.contains("\"append_prompt\"") {
+                    match merged.save_overrides() {
+                        Ok(()) => tracing::info!("Migrated append_prompt in profiles.json"),
+                        Err(e) => tracing::warn!("Failed to migrate profiles.json: {}", e),
+                    }
+                }
+                merged
             }
             Err(e) => {
                 tracing::error!(
@@ -277,6 +285,7 @@ impl ExecutorConfigs {
                 }
             }
         }
+        defaults.hide_test_agents();
         defaults
     }
 
@@ -377,10 +386,20 @@ impl ExecutorConfigs {
 
     /// Load from the new v3 defaults
     pub fn from_defaults() -> Self {
-        serde_json::from_str(DEFAULT_PROFILES_JSON).unwrap_or_else(|e| {
+        let mut defaults: Self = serde_json::from_str(DEFAULT_PROFILES_JSON).unwrap_or_else(|e| {
             tracing::error!("Failed to parse embedded default_profiles.json: {}", e);
             panic!("Default profiles v3 JSON is invalid")
-        })
+        });
+        defaults.hide_test_agents();
+        defaults
+    }
+
+    /// Drop the Echo agent outside dev and test builds, where it is only useful for
+    /// exercising workflows
+    fn hide_test_agents(&mut self) {
+        if !cfg!(debug_assertions) {
+            self.executors.remove(&BaseCodingAgent::Echo);
+        }
     }
 
     pub fn get_coding_agent(&self, executor_profile_id: &ExecutorProfileId) -> Option<CodingAgent> {
@@ -414,6 +433,10 @@ impl ExecutorConfigs {
         &self,
     ) -> Result<ExecutorProfileId, ProfileError> {
         for &base_agent in self.executors.keys() {
+            // Always available, but it only echoes canned responses
+            if base_agent == BaseCodingAgent::Echo {
+                continue;
+            }
             let profile_id = ExecutorProfileId::new(base_agent);
             if let Some(coding_agent) = self.get_coding_agent(&profile_id)
                 && coding_agent.check_availability().await
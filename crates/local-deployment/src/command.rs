@@ -1,6 +1,8 @@
+use chrono::{DateTime, Utc};
 use command_group::AsyncGroupChild;
 #[cfg(unix)]
 use nix::{
+    errno::Errno,
     sys::signal::{Signal, killpg},
     unistd::{Pid, getpgid},
 };
@@ -41,3 +43,183 @@ pub async fn kill_process_group(child: &mut AsyncGroupChild) -> Result<(), Conta
     let _ = child.wait().await;
     Ok(())
 }
+
+/// How far the start time of a process may be from the `started_at` recorded for its execution
+/// process for the pid to still be trusted as the one that was spawned
+const ORPHAN_START_TOLERANCE_SECS: i64 = 30;
+
+/// How long an orphaned process group gets to exit after each signal
+#[cfg(unix)]
+const ORPHAN_KILL_GRACE: Duration = Duration::from_secs(2);
+
+/// Kill a process group left over from a previous server run, given the pid of its leader and
+/// when it was spawned. Returns `false` without signalling anything if that process is no
+/// longer running: the machine has rebooted since, or the pid now belongs to a process that
+/// started at a different time.
+pub async fn kill_orphaned_process_group(
+    pid: u32,
+    spawned_at: DateTime<Utc>,
+) -> Result<bool, ContainerError> {
+    if system_boot_time()
+        .await
+        .is_some_and(|boot| spawned_at < boot)
+    {
+        return Ok(false);
+    }
+    match process_start_time(pid).await {
+        Some(started)
+            if (started - spawned_at).num_seconds().abs() <= ORPHAN_START_TOLERANCE_SECS => {}
+        _ => return Ok(false),
+    }
+
+    #[cfg(unix)]
+    {
+        let pid = Pid::from_raw(pid as i32);
+        // Still leading its own process group, so it is safe to signal the group
+        if getpgid(Some(pid)) != Ok(pid) {
+            return Ok(false);
+        }
+
+        for sig in [Signal::SIGTERM, Signal::SIGKILL] {
+            match killpg(pid, sig) {
+                Ok(()) => {}
+                Err(Errno::ESRCH) => break,
+                Err(e) => tracing::warn!(
+                    "Failed to send signal {:?} to process group {}: {}",
+                    sig,
+                    pid,
+                    e
+                ),
+            }
+            let deadline = tokio::time::Instant::now() + ORPHAN_KILL_GRACE;
+            while killpg(pid, None).is_ok() && tokio::time::Instant::now() < deadline {
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+            if killpg(pid, None).is_err() {
+                break;
+            }
+        }
+        Ok(true)
+    }
+
+    #[cfg(windows)]
+    {
+        let status = tokio::process::Command::new("taskkill")
+            .args(["/PID", &pid.to_string(), "/T", "/F"])
+            .status()
+            .await
+            .map_err(ContainerError::KillFailed)?;
+        Ok(status.success())
+    }
+}
+
+/// When the system was last booted, if it can be determined
+async fn system_boot_time() -> Option<DateTime<Utc>> {
+    #[cfg(target_os = "linux")]
+    {
+        let stat = tokio::fs::read_to_string("/proc/stat").await.ok()?;
+        let secs = stat
+            .lines()
+            .find_map(|line| line.strip_prefix("btime "))?
+            .trim()
+            .parse()
+            .ok()?;
+        DateTime::from_timestamp(secs, 0)
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        // e.g. `{ sec = 1760000000, usec = 0 } Thu Oct  9 08:53:20 2025`
+        let output = tokio::process::Command::new("sysctl")
+            .args(["-n", "kern.boottime"])
+            .output()
+            .await
+            .ok()?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let secs = stdout
+            .split("sec = ")
+            .nth(1)?
+            .split(',')
+            .next()?
+            .trim()
+            .parse()
+            .ok()?;
+        DateTime::from_timestamp(secs, 0)
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "macos")))]
+    {
+        None
+    }
+}
+
+/// When the process with `pid` started, or `None` if no such process is running
+async fn process_start_time(pid: u32) -> Option<DateTime<Utc>> {
+    #[cfg(unix)]
+    {
+        let output = tokio::process::Command::new("ps")
+            .args(["-o", "etime=", "-p", &pid.to_string()])
+            .output()
+            .await
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let elapsed = parse_elapsed_time(String::from_utf8_lossy(&output.stdout).trim())?;
+        Some(Utc::now() - chrono::Duration::seconds(elapsed))
+    }
+
+    #[cfg(windows)]
+    {
+        let script = format!(
+            "(Get-Process -Id {pid} -ErrorAction Stop).StartTime.ToUniversalTime().ToString('o')"
+        );
+        let output = tokio::process::Command::new("powershell")
+            .args(["-NoProfile", "-Command", &script])
+            .output()
+            .await
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        DateTime::parse_from_rfc3339(String::from_utf8_lossy(&output.stdout).trim())
+            .ok()
+            .map(|started| started.with_timezone(&Utc))
+    }
+}
+
+/// Seconds in a `ps` elapsed time, formatted `[[dd-]hh:]mm:ss`
+#[cfg(unix)]
+fn parse_elapsed_time(elapsed: &str) -> Option<i64> {
+    let (days, clock) = match elapsed.split_once('-') {
+        Some((days, clock)) => (days.parse::<i64>().ok()?, clock),
+        None => (0, elapsed),
+    };
+    let mut secs = 0;
+    let mut fields = 0;
+    for field in clock.split(':') {
+        secs = secs * 60 + field.parse::<i64>().ok()?;
+        fields += 1;
+    }
+    if !(2..=3).contains(&fields) {
+        return None;
+    }
+    Some(days * 86_400 + secs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn parses_ps_elapsed_times() {
+        assert_eq!(parse_elapsed_time("00:07"), Some(7));
+        assert_eq!(parse_elapsed_time("12:34"), Some(754));
+        assert_eq!(parse_elapsed_time("01:02:03"), Some(3723));
+        assert_eq!(parse_elapsed_time("2-01:02:03"), Some(2 * 86_400 + 3723));
+        assert_eq!(parse_elapsed_time("7"), None);
+        assert_eq!(parse_elapsed_time(""), None);
+        assert_eq!(parse_elapsed_time("1:2:3:4"), None);
+    }
+}
@@ -0,0 +1,226 @@
+use std::{path::Path, sync::Arc, time::Duration};
+
+use executors::{
+    executors::{CodingAgent, StandardCodingAgentExecutor},
+    logs::{NormalizedEntryType, utils::patch::extract_normalized_entry_from_patch},
+    profile::{ExecutorConfigs, ExecutorProfileId},
+};
+use futures::{FutureExt, StreamExt, TryStreamExt, stream::select};
+use tokio_util::io::ReaderStream;
+use utils::{log_msg::LogMsg, msg_store::MsgStore};
+use uuid::Uuid;
+
+use crate::{command, container::truncate_to_char_boundary};
+
+/// Maximum diff bytes fed to the summarizer; larger diffs are truncated
+pub(crate) const MAX_DIFF_BYTES: usize = 32 * 1024;
+/// How long to wait for the log normalizer to emit the final assistant message after exit
+const NORMALIZE_GRACE_PERIOD: Duration = Duration::from_secs(2);
+
+/// `diff` cut to `MAX_DIFF_BYTES`, with a note for the agent when it was cut
+fn bounded_diff(diff: &str) -> (&str, &'static str) {
+    if diff.len() > MAX_DIFF_BYTES {
+        (
+            truncate_to_char_boundary(diff, MAX_DIFF_BYTES),
+            "\n(The diff was truncated; summarize the visible changes.)",
+        )
+    } else {
+        (diff, "")
+    }
+}
+
+fn build_prompt(diff: &str) -> String {
+    let (diff, truncated_note) = bounded_diff(diff);
+    format!(
+        "Write a git commit message for the diff below using the Conventional Commits format \
+         (e.g. `feat: ...`, `fix: ...`, `refactor: ...`). Reply with the commit message only: \
+         a subject line of at most 72 characters, optionally followed by a blank line and a short \
+         body. Do not use any tools and do not modify any files.{truncated_note}\n\n```diff\n{diff}\n```"
+    )
+}
+
+fn build_summary_prompt(diff: &str) -> String {
+    let (diff, truncated_note) = bounded_diff(diff);
+    format!(
+        "Summarize the diff below for a code reviewer in a few short paragraphs or bullet \
+         points: what changed, and why it was likely changed. Mention anything risky or \
+         surprising. Reply with the summary only, in Markdown. Do not use any tools and do not \
+         modify any files.{truncated_note}\n\n```diff\n{diff}\n```"
+    )
+}
+
+/// Strip code fences and surrounding whitespace the agent may wrap its answer in
+fn clean_message(raw: &str) -> Option<String> {
+    let message = raw
+        .trim()
+        .trim_start_matches("```text")
+        .trim_start_matches("```")
+        .trim_end_matches("```")
+        .trim();
+    if message.is_empty() {
+        None
+    } else {
+        Some(message.to_string())
+    }
+}
+
+fn last_assistant_message(store: &MsgStore) -> Option<String> {
+    store.get_history().iter().rev().find_map(|msg| match msg {
+        LogMsg::JsonPatch(patch) => extract_normalized_entry_from_patch(patch)
+            .filter(|(_, entry)| matches!(entry.entry_type, NormalizedEntryType::AssistantMessage))
+            .map(|(_, entry)| entry.content),
+        _ => None,
+    })
+}
+
+/// Ask the coding agent for `executor_profile_id` to summarize `diff` into a commit message.
+/// The agent runs in a scratch directory so it cannot touch the worktree. Returns None on any
+/// failure or when the agent does not answer within `timeout`.
+pub async fn generate_commit_message(
+    executor_profile_id: &ExecutorProfileId,
+    diff: &str,
+    timeout: Duration,
+) -> Option<String> {
+    if diff.trim().is_empty() {
+        return None;
+    }
+
+    match run_in_scratch_dir(executor_profile_id, &build_prompt(diff), timeout).await {
+        Ok(message) => clean_message(&message),
+        Err(e) => {
+            tracing::warn!("Commit message generation failed: {}", e);
+            None
+        }
+    }
+}
+
+/// Ask the coding agent for `executor_profile_id` to explain `diff` for a reviewer. Like
+/// commit messages, the agent runs in a scratch directory. Errors describe why no summary
+/// was produced; a partial answer is never returned.
+pub async fn generate_diff_summary(
+    executor_profile_id: &ExecutorProfileId,
+    diff: &str,
+    timeout: Duration,
+) -> Result<String, String> {
+    if diff.trim().is_empty() {
+        return Err("the attempt has no changes to summarize".to_string());
+    }
+    let summary =
+        run_in_scratch_dir(executor_profile_id, &build_summary_prompt(diff), timeout).await?;
+    clean_message(&summary).ok_or_else(|| "the agent returned an empty summary".to_string())
+}
+
+async fn run_in_scratch_dir(
+    executor_profile_id: &ExecutorProfileId,
+    prompt: &str,
+    timeout: Duration,
+) -> Result<String, String> {
+    let agent = ExecutorConfigs::get_cached()
+        .get_coding_agent(executor_profile_id)
+        .ok_or_else(|| format!("no coding agent configured for {executor_profile_id}"))?;
+    let scratch_dir = std::env::temp_dir().join(format!("vk-agent-prompt-{}", Uuid::new_v4()));
+    tokio::fs::create_dir_all(&scratch_dir)
+        .await
+        .map_err(|e| format!("failed to create scratch dir: {e}"))?;
+
+    let answer = run_agent(&agent, &scratch_dir, prompt, timeout).await;
+
+    if let Err(e) = tokio::fs::remove_dir_all(&scratch_dir).await {
+        tracing::debug!("Failed to remove agent scratch dir: {}", e);
+    }
+    answer
+}
+
+async fn run_agent(
+    agent: &CodingAgent,
+    scratch_dir: &Path,
+    prompt: &str,
+    timeout: Duration,
+) -> Result<String, String> {
+    let mut spawned = agent
+        .spawn(scratch_dir, prompt)
+        .await
+        .map_err(|e| format!("failed to spawn agent: {e}"))?;
+
+    let store = Arc::new(MsgStore::new());
+    let (Some(out), Some(err)) = (
+        spawned.child.inner().stdout.take(),
+        spawned.child.inner().stderr.take(),
+    ) else {
+        return Err("agent process has no output pipes".to_string());
+    };
+    let out = ReaderStream::new(out)
+        .map_ok(|chunk| LogMsg::Stdout(String::from_utf8_lossy(&chunk).into_owned()));
+    let err = ReaderStream::new(err)
+        .map_ok(|chunk| LogMsg::Stderr(String::from_utf8_lossy(&chunk).into_owned()));
+    let forwarder = store.clone().spawn_forwarder(select(out, err));
+    agent.normalize_logs(store.clone(), scratch_dir);
+
+    let exit_signal = spawned
+        .exit_signal
+        .take()
+        .map(|rx| rx.map(|_| ()).boxed())
+        .unwrap_or_else(|| std::future::pending::<()>().boxed());
+
+    let (outcome, exited) = tokio::select! {
+        status = spawned.child.wait() => match status {
+            Ok(s) if s.success() => (Ok(()), true),
+            Ok(s) => (Err(format!("agent exited with {s}")), true),
+            Err(e) => (Err(format!("failed to wait for agent: {e}")), true),
+        },
+        _ = exit_signal => (Ok(()), false),
+        _ = tokio::time::sleep(timeout) => {
+            (Err(format!("agent did not finish within {}s", timeout.as_secs())), false)
+        }
+    };
+
+    if !exited && let Err(e) = command::kill_process_group(&mut spawned.child).await {
+        tracing::warn!("Failed to kill prompt agent: {}", e);
+    }
+    if let Err(e) = outcome {
+        forwarder.abort();
+        return Err(e);
+    }
+
+    let _ = tokio::time::timeout(NORMALIZE_GRACE_PERIOD, forwarder).await;
+    store.push_finished();
+
+    // Normalization runs asynchronously; give it a moment to emit the final message
+    let deadline = tokio::time::Instant::now() + NORMALIZE_GRACE_PERIOD;
+    loop {
+        if let Some(message) = last_assistant_message(&store) {
+            return Ok(message);
+        }
+        if tokio::time::Instant::now() >= deadline {
+            return Err("agent finished without an answer".to_string());
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_prompt_bounds_diff_size() {
+        let diff = "+x\n".repeat(MAX_DIFF_BYTES);
+        let prompt = build_prompt(&diff);
+        assert!(prompt.len() < MAX_DIFF_BYTES + 1024);
+        assert!(prompt.contains("truncated"));
+
+        let summary_prompt = build_summary_prompt(&diff);
+        assert!(summary_prompt.len() < MAX_DIFF_BYTES + 1024);
+        assert!(summary_prompt.contains("truncated"));
+        assert!(!build_summary_prompt("+x\n").contains("truncated"));
+    }
+
+    #[test]
+    fn clean_message_strips_fences() {
+        assert_eq!(
+            clean_message("```\nfeat: add thing\n```").as_deref(),
+            Some("feat: add thing")
+        );
+        assert_eq!(clean_message("  \n"), None);
+    }
+}
@@ -1,14 +1,19 @@
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet, hash_map::Entry},
+    hash::{DefaultHasher, Hash, Hasher},
     io,
     path::{Path, PathBuf},
-    sync::{Arc, atomic::AtomicUsize},
+    sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+    },
     time::Duration,
 };
 
 use anyhow::anyhow;
 use async_trait::async_trait;
-use command_group::AsyncGroupChild;
+use chrono::{DateTime, Utc};
+use command_group::{AsyncCommandGroup, AsyncGroupChild};
 use db::{
     DBService,
     models::{
@@ -22,20 +27,23 @@ use db::{
         project::Project,
         task::{Task, TaskStatus},
         task_attempt::TaskAttempt,
+        task_event::{TaskEvent, TaskEventType},
     },
 };
 use deployment::DeploymentError;
 use executors::{
-    actions::{Executable, ExecutorAction},
+    actions::{Executable, ExecutorAction, ExecutorActionType, script::ScriptRequest},
     approvals::{ExecutorApprovalService, NoopExecutorApprovalService},
+    command::with_execution_env,
     executors::BaseCodingAgent,
     logs::{
-        NormalizedEntryType,
+        NormalizedEntry, NormalizedEntryType,
         utils::{
             ConversationPatch,
             patch::{escape_json_pointer_segment, extract_normalized_entry_from_patch},
         },
     },
+    profile::ExecutorProfileId,
 };
 use futures::{FutureExt, StreamExt, TryStreamExt, stream::select};
 use serde_json::json;
@@ -43,45 +51,109 @@ use services::services::{
     analytics::AnalyticsContext,
     approvals::{Approvals, executor_approvals::ExecutorApprovalBridge},
     config::Config,
-    container::{ContainerError, ContainerRef, ContainerService},
+    container::{
+        ContainerError, ContainerRef, ContainerService, ExecCommandHandle, StoppedDevServer,
+    },
     diff_stream::{self, DiffStreamHandle},
-    git::{Commit, DiffTarget, GitService},
+    events::{AttemptProgress, AttemptProgressStage, attempt_progress_patch},
+    file_review::FileReviewService,
+    git::{Commit, CommitOptions, DiffContentOptions, DiffTarget, GitService},
     image::ImageService,
     notification::NotificationService,
+    prompt_snippets::PromptSnippetService,
+    repo_config::RepoConfigService,
+    setup_cache::SetupCacheService,
     worktree_manager::WorktreeManager,
 };
-use tokio::{sync::RwLock, task::JoinHandle};
+use tokio::{
+    sync::{Mutex, RwLock},
+    task::JoinHandle,
+};
 use tokio_util::io::ReaderStream;
 use utils::{
+    diff::{Diff, create_unified_diff},
+    env_file::parse_env_file,
+    git::{DEFAULT_BRANCH_TEMPLATE, is_valid_branch_template, render_branch_template},
     log_msg::LogMsg,
     msg_store::MsgStore,
+    path::expand_tilde,
+    shell::get_shell_command,
     text::{git_branch_id, short_uuid},
 };
 use uuid::Uuid;
 
-use crate::command;
+use crate::{command, commit_message};
+
+/// Ad-hoc commands are killed after this long; they are meant for quick checks like
+/// `git status`, not long-running processes, and the request waits for them
+const EXEC_COMMAND_TIMEOUT: Duration = Duration::from_secs(60);
+/// How long a finished command's remaining output may take to reach its `MsgStore`; a
+/// background process it started can keep the pipes open indefinitely
+const EXEC_COMMAND_OUTPUT_GRACE: Duration = Duration::from_secs(2);
+/// How long the agent may take to summarize an attempt's diff
+const DIFF_SUMMARY_TIMEOUT: Duration = Duration::from_secs(180);
+/// Upper bound on commit message generation, whatever the config says; the exit monitor
+/// waits on it before the next queued process can start
+const MAX_COMMIT_MESSAGE_TIMEOUT: Duration = Duration::from_secs(20);
+/// Summaries kept in memory; an arbitrary entry is evicted when full
+const DIFF_SUMMARY_CACHE_CAPACITY: usize = 64;
+
+/// Summarizing profile, head commit and a hash of the rendered diff, so switching agents or
+/// making uncommitted edits misses the cache
+type DiffSummaryKey = (ExecutorProfileId, String, u64);
 
 #[derive(Clone)]
 pub struct LocalContainerService {
     db: DBService,
     child_store: Arc<RwLock<HashMap<Uuid, Arc<RwLock<AsyncGroupChild>>>>>,
     msg_stores: Arc<RwLock<HashMap<Uuid, Arc<MsgStore>>>>,
+    events_msg_store: Arc<MsgStore>,
     config: Arc<RwLock<Config>>,
     git: GitService,
     image_service: ImageService,
     analytics: Option<AnalyticsContext>,
     approvals: Approvals,
+    repo_config: RepoConfigService,
+    shutting_down: Arc<AtomicBool>,
+    diff_summaries: Arc<RwLock<HashMap<DiffSummaryKey, String>>>,
+    /// When each attempt's current run of setup, coding agent and cleanup processes started
+    attempt_runs: Arc<RwLock<HashMap<Uuid, DateTime<Utc>>>>,
+    /// Attempts stopped for running too long, whose queued follow-ups must not start
+    timed_out_attempts: Arc<RwLock<HashSet<Uuid>>>,
+    /// Held while committing on behalf of an agent, so a shutdown checkpoint and an exit
+    /// monitor never commit at the same time
+    commit_lock: Arc<Mutex<()>>,
+}
+
+/// Wait up to `grace_period` for every child to leave `child_store`, which exit monitors do
+/// once a process's result is committed and recorded. Returns whether they all did.
+async fn wait_for_children<T>(
+    child_store: &RwLock<HashMap<Uuid, T>>,
+    grace_period: Duration,
+) -> bool {
+    let deadline = tokio::time::Instant::now() + grace_period;
+    loop {
+        if child_store.read().await.is_empty() {
+            return true;
+        }
+        if tokio::time::Instant::now() >= deadline {
+            return false;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
 }
 
 impl LocalContainerService {
     pub fn new(
         db: DBService,
         msg_stores: Arc<RwLock<HashMap<Uuid, Arc<MsgStore>>>>,
+        events_msg_store: Arc<MsgStore>,
         config: Arc<RwLock<Config>>,
         git: GitService,
         image_service: ImageService,
         analytics: Option<AnalyticsContext>,
         approvals: Approvals,
+        repo_config: RepoConfigService,
     ) -> Self {
         let child_store = Arc::new(RwLock::new(HashMap::new()));
 
@@ -89,11 +161,18 @@ impl LocalContainerService {
             db,
             child_store,
             msg_stores,
+            events_msg_store,
             config,
             git,
             image_service,
             analytics,
             approvals,
+            repo_config,
+            shutting_down: Arc::new(AtomicBool::new(false)),
+            diff_summaries: Arc::new(RwLock::new(HashMap::new())),
+            attempt_runs: Arc::new(RwLock::new(HashMap::new())),
+            timed_out_attempts: Arc::new(RwLock::new(HashSet::new())),
+            commit_lock: Arc::new(Mutex::new(())),
         }
     }
 
@@ -114,7 +193,7 @@ impl LocalContainerService {
 
     /// A context is finalized when
     /// - The next action is None (no follow-up actions)
-    /// - The run reason is not DevServer
+    /// - The run reason is not DevServer or a standalone SetupScript re-run
     fn should_finalize(ctx: &ExecutionContext) -> bool {
         ctx.execution_process
             .executor_action()
@@ -123,17 +202,325 @@ impl LocalContainerService {
             .is_none()
             && (!matches!(
                 ctx.execution_process.run_reason,
-                ExecutionProcessRunReason::DevServer
+                ExecutionProcessRunReason::DevServer | ExecutionProcessRunReason::SetupScript
             ))
     }
 
     /// Finalize task execution by updating status to InReview and sending notifications
     async fn finalize_task(db: &DBService, config: &Arc<RwLock<Config>>, ctx: &ExecutionContext) {
+        if config.read().await.squash_on_finalize {
+            Self::squash_attempt_commits(db, ctx).await;
+        }
         if let Err(e) = Task::update_status(&db.pool, ctx.task.id, TaskStatus::InReview).await {
             tracing::error!("Failed to update task status to InReview: {e}");
         }
+
         let notify_cfg = config.read().await.notifications.clone();
-        NotificationService::notify_execution_halted(notify_cfg, ctx).await;
+        if ctx.task_attempt.needs_input {
+            NotificationService::notify_awaiting_input(notify_cfg, ctx).await;
+        } else {
+            NotificationService::notify_execution_halted(notify_cfg, ctx).await;
+        }
+    }
+
+    /// Final message of the attempt's latest coding agent run, if it recorded one
+    async fn latest_agent_summary(db: &DBService, attempt_id: Uuid) -> Option<String> {
+        let process = ExecutionProcess::find_latest_by_task_attempt_and_run_reason(
+            &db.pool,
+            attempt_id,
+            &ExecutionProcessRunReason::CodingAgent,
+        )
+        .await
+        .ok()
+        .flatten()?;
+        ExecutorSession::find_by_execution_process_id(&db.pool, process.id)
+            .await
+            .ok()
+            .flatten()
+            .and_then(|session| session.summary)
+    }
+
+    /// Squash the attempt's commits into one with the coding agent's summary as the message.
+    /// Only runs after a successful run, and skips branches with a single commit or that have
+    /// been pushed. The original commits stay on the branch until the squashed commit is
+    /// confirmed to hold the same tree; otherwise the branch is put back where it was.
+    async fn squash_attempt_commits(db: &DBService, ctx: &ExecutionContext) {
+        if ctx.execution_process.status != ExecutionProcessStatus::Completed {
+            return;
+        }
+        let attempt = &ctx.task_attempt;
+        let Some(worktree_path) = attempt.container_ref.as_deref().map(PathBuf::from) else {
+            return;
+        };
+        let Ok(Some(project)) = Project::find_by_id(&db.pool, ctx.task.project_id).await else {
+            return;
+        };
+        let git = GitService::new();
+
+        match git.is_branch_pushed(&project.git_repo_path, &attempt.branch) {
+            Ok(false) => {}
+            Ok(true) => {
+                tracing::debug!(
+                    "Not squashing attempt {}: branch '{}' has been pushed",
+                    attempt.id,
+                    attempt.branch
+                );
+                return;
+            }
+            Err(e) => {
+                tracing::warn!("Not squashing attempt {}: {}", attempt.id, e);
+                return;
+            }
+        }
+        let oids = match git
+            .get_base_commit(
+                &project.git_repo_path,
+                &attempt.branch,
+                &attempt.target_branch,
+            )
+            .and_then(|base| git.commits_since(&worktree_path, &base))
+        {
+            Ok(oids) if oids.len() > 1 => oids,
+            Ok(_) => return,
+            Err(e) => {
+                tracing::warn!("Not squashing attempt {}: {}", attempt.id, e);
+                return;
+            }
+        };
+
+        let message = Self::latest_agent_summary(db, attempt.id)
+            .await
+            .filter(|summary| !summary.trim().is_empty())
+            .unwrap_or_else(|| ctx.task.title.clone());
+
+        let original_head = &oids[0];
+        let confirmed = match git.squash_commits(&worktree_path, &oids, &message) {
+            Ok(new_head) => {
+                let same_tree = git
+                    .commit_tree_oid(&worktree_path, &new_head)
+                    .ok()
+                    .zip(git.commit_tree_oid(&worktree_path, original_head).ok())
+                    .is_some_and(|(squashed, original)| squashed == original);
+                if !same_tree {
+                    tracing::warn!(
+                        "Squashed commit {} of attempt {} does not match {}",
+                        new_head,
+                        attempt.id,
+                        original_head
+                    );
+                }
+                same_tree
+            }
+            Err(e) => {
+                tracing::warn!("Failed to squash commits of attempt {}: {}", attempt.id, e);
+                if git.is_rebase_in_progress(&worktree_path).unwrap_or(false)
+                    && let Err(e) = git.abort_rebase(&worktree_path)
+                {
+                    tracing::error!("Failed to abort squash of attempt {}: {}", attempt.id, e);
+                }
+                false
+            }
+        };
+
+        if confirmed {
+            tracing::info!(
+                "Squashed {} commits of attempt {} (previous head {})",
+                oids.len(),
+                attempt.id,
+                original_head
+            );
+        } else if git
+            .get_head_info(&worktree_path)
+            .is_ok_and(|head| &head.oid != original_head)
+            && let Err(e) = git.reset_worktree_to_commit(&worktree_path, original_head, true)
+        {
+            tracing::error!(
+                "Failed to restore attempt {} to {}: {}",
+                attempt.id,
+                original_head,
+                e
+            );
+        }
+    }
+
+    /// Number of consecutive failed runs of this coding agent request, including this one.
+    /// Runs of a different request (e.g. a new follow-up) break the streak.
+    async fn failed_turn_streak(db: &DBService, ctx: &ExecutionContext) -> usize {
+        let Ok(action) = ctx.execution_process.executor_action() else {
+            return 0;
+        };
+        let Ok(processes) =
+            ExecutionProcess::find_by_task_attempt_id(&db.pool, ctx.task_attempt.id, false).await
+        else {
+            return 0;
+        };
+
+        processes
+            .iter()
+            .rev()
+            .filter(|p| p.run_reason == ExecutionProcessRunReason::CodingAgent)
+            .take_while(|p| {
+                p.status == ExecutionProcessStatus::Failed
+                    && p.executor_action()
+                        .is_ok_and(|other| other.typ() == action.typ())
+            })
+            .count()
+    }
+
+    /// Re-run a coding agent turn that crashed, if `auto_retry_failed_turns` allows another
+    /// attempt. The retry is started as a new execution process after an exponential backoff
+    /// (5s, 10s, 20s, ... capped at a minute). Returns whether a retry was scheduled, in which
+    /// case the caller must not finalize the task.
+    async fn schedule_failed_turn_retry(&self, ctx: &ExecutionContext) -> bool {
+        let max_retries = self.config.read().await.auto_retry_failed_turns as usize;
+        if max_retries == 0 || self.shutting_down.load(Ordering::SeqCst) {
+            return false;
+        }
+
+        // A worktree stuck mid-rebase/merge fails the same way every time
+        let worktree_dir = self.task_attempt_to_current_dir(&ctx.task_attempt);
+        if matches!(self.git().detect_conflict_op(&worktree_dir), Ok(Some(_))) {
+            return false;
+        }
+
+        let retries_done = Self::failed_turn_streak(&self.db, ctx)
+            .await
+            .saturating_sub(1);
+        if retries_done >= max_retries {
+            return false;
+        }
+        let Ok(action) = ctx.execution_process.executor_action().cloned() else {
+            return false;
+        };
+
+        let delay = Duration::from_secs((5u64 << retries_done.min(4)).min(60));
+        tracing::warn!(
+            "Coding agent turn {} for attempt {} failed; retrying in {}s ({}/{})",
+            ctx.execution_process.id,
+            ctx.task_attempt.id,
+            delay.as_secs(),
+            retries_done + 1,
+            max_retries
+        );
+
+        let container = self.clone();
+        let ctx = ctx.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(delay).await;
+
+            // Leave it alone if the user started something else in the meantime
+            let superseded = ExecutionProcess::find_latest_by_task_attempt_and_run_reason(
+                &container.db.pool,
+                ctx.task_attempt.id,
+                &ExecutionProcessRunReason::CodingAgent,
+            )
+            .await
+            .ok()
+            .flatten()
+            .is_none_or(|latest| latest.id != ctx.execution_process.id);
+            if superseded || container.shutting_down.load(Ordering::SeqCst) {
+                return;
+            }
+
+            if let Err(e) = container
+                .start_execution(
+                    &ctx.task_attempt,
+                    &action,
+                    &ExecutionProcessRunReason::CodingAgent,
+                )
+                .await
+            {
+                tracing::error!(
+                    "Failed to retry coding agent turn for attempt {}: {}",
+                    ctx.task_attempt.id,
+                    e
+                );
+                Self::finalize_task(&container.db, &container.config, &ctx).await;
+            }
+        });
+        true
+    }
+
+    /// Variables from the project's env file, set on every process spawned in the attempt's
+    /// worktree. A missing or malformed file is skipped with a warning unless the project
+    /// marks it required. Only the path and line numbers are logged, never values.
+    async fn load_project_env(
+        &self,
+        task_attempt: &TaskAttempt,
+    ) -> Result<HashMap<String, String>, ContainerError> {
+        let task = task_attempt
+            .parent_task(&self.db.pool)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)?;
+        let project = task
+            .parent_project(&self.db.pool)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)?;
+        let Some(env_file) = project.env_file.as_deref().filter(|f| !f.trim().is_empty()) else {
+            return Ok(HashMap::new());
+        };
+
+        let path = project.git_repo_path.join(expand_tilde(env_file.trim()));
+        let parsed = match tokio::fs::read_to_string(&path).await {
+            Ok(contents) => parse_env_file(&contents).map_err(|e| e.to_string()),
+            Err(e) => Err(e.to_string()),
+        };
+        match parsed {
+            Ok(vars) => Ok(vars),
+            Err(e) if project.env_file_required => Err(ContainerError::Other(anyhow!(
+                "Failed to load env file {}: {}",
+                path.display(),
+                e
+            ))),
+            Err(e) => {
+                tracing::warn!(
+                    "Ignoring env file {} for project {}: {}",
+                    path.display(),
+                    project.id,
+                    e
+                );
+                Ok(HashMap::new())
+            }
+        }
+    }
+
+    /// Cache the artifacts of a successful setup script run under the key it was started with
+    async fn store_setup_cache(db: &DBService, ctx: &ExecutionContext) {
+        let Ok(action) = ctx.execution_process.executor_action() else {
+            return;
+        };
+        let ExecutorActionType::ScriptRequest(ScriptRequest {
+            cache_key: Some(key),
+            ..
+        }) = action.typ()
+        else {
+            return;
+        };
+        let (Some(worktree), Ok(Some(project))) = (
+            ctx.task_attempt.container_ref.clone(),
+            Project::find_by_id(&db.pool, ctx.task.project_id).await,
+        ) else {
+            return;
+        };
+
+        let key = key.clone();
+        let artifacts = SetupCacheService::parse_list(project.setup_cache_artifacts.as_deref());
+        let result = tokio::task::spawn_blocking(move || {
+            SetupCacheService::new().store(project.id, &key, Path::new(&worktree), &artifacts)
+        })
+        .await;
+        match result {
+            Ok(Ok(())) => tracing::debug!(
+                "Stored setup cache for task attempt {}",
+                ctx.task_attempt.id
+            ),
+            Ok(Err(e)) => tracing::warn!(
+                "Failed to store setup cache for task attempt {}: {}",
+                ctx.task_attempt.id,
+                e
+            ),
+            Err(e) => tracing::warn!("Setup cache task panicked: {}", e),
+        }
     }
 
     /// Defensively check for externally deleted worktrees and mark them as deleted in the database
@@ -236,7 +623,16 @@ impl LocalContainerService {
         worktree_path: PathBuf,
         git_repo_path: PathBuf,
     ) -> Result<(), DeploymentError> {
-        WorktreeManager::cleanup_worktree(&worktree_path, Some(&git_repo_path)).await?;
+        // A worktree shared by a task's attempts is removed with the last attempt using it
+        if !TaskAttempt::container_ref_shared(
+            &db.pool,
+            &worktree_path.to_string_lossy(),
+            attempt_id,
+        )
+        .await?
+        {
+            WorktreeManager::cleanup_worktree(&worktree_path, Some(&git_repo_path)).await?;
+        }
         // Mark worktree as deleted in database after successful cleanup
         TaskAttempt::mark_worktree_deleted(&db.pool, attempt_id).await?;
         tracing::info!("Successfully marked worktree as deleted for attempt {attempt_id}",);
@@ -268,8 +664,104 @@ impl LocalContainerService {
         Ok(())
     }
 
+    /// Forget when an attempt's run started once none of its setup, coding agent or cleanup
+    /// processes are running any more
+    async fn clear_idle_attempt_run(&self, task_attempt_id: Uuid) {
+        let still_running =
+            ExecutionProcess::find_by_task_attempt_id(&self.db.pool, task_attempt_id, false)
+                .await
+                .is_ok_and(|processes| {
+                    processes.iter().any(|p| {
+                        p.status == ExecutionProcessStatus::Running
+                            && p.run_reason != ExecutionProcessRunReason::DevServer
+                    })
+                });
+        if !still_running {
+            self.attempt_runs.write().await.remove(&task_attempt_id);
+        }
+    }
+
+    /// Stop attempts whose current run has lasted longer than `max_attempt_duration_mins`.
+    /// Stopping hands the task back for review and the exit monitor finalizes it as usual, but
+    /// without starting a queued follow-up. Dev servers alone don't keep a run going.
+    async fn stop_overdue_attempts(&self) {
+        let max_mins = self.config.read().await.max_attempt_duration_mins;
+        if max_mins == 0 {
+            return;
+        }
+        let cutoff = Utc::now() - chrono::Duration::minutes(i64::from(max_mins));
+        let overdue: Vec<Uuid> = self
+            .attempt_runs
+            .read()
+            .await
+            .iter()
+            .filter(|(_, started_at)| **started_at < cutoff)
+            .map(|(attempt_id, _)| *attempt_id)
+            .collect();
+
+        for attempt_id in overdue {
+            self.attempt_runs.write().await.remove(&attempt_id);
+
+            let running =
+                match ExecutionProcess::find_by_task_attempt_id(&self.db.pool, attempt_id, false)
+                    .await
+                {
+                    Ok(processes) => processes.into_iter().find(|p| {
+                        p.status == ExecutionProcessStatus::Running
+                            && p.run_reason != ExecutionProcessRunReason::DevServer
+                    }),
+                    Err(e) => {
+                        tracing::error!("Failed to load processes for attempt {attempt_id}: {e}");
+                        continue;
+                    }
+                };
+            let Some(process) = running else {
+                continue;
+            };
+            let Ok(ctx) = ExecutionProcess::load_context(&self.db.pool, process.id).await else {
+                continue;
+            };
+
+            tracing::warn!(
+                "Stopping task attempt {} after running for more than {} minutes",
+                attempt_id,
+                max_mins
+            );
+            self.timed_out_attempts.write().await.insert(attempt_id);
+            self.try_stop(&ctx.task_attempt).await;
+            if let Err(e) = TaskEvent::record(
+                &self.db.pool,
+                ctx.task.id,
+                Some(attempt_id),
+                TaskEventType::TimedOut,
+                None,
+                None,
+                Some(&format!(
+                    "Stopped after running for more than {max_mins} minutes"
+                )),
+            )
+            .await
+            {
+                tracing::error!("Failed to record timeout for attempt {attempt_id}: {e}");
+            }
+        }
+    }
+
+    /// Check for attempts that have run too long every minute
+    pub fn spawn_overdue_attempt_monitor(&self) {
+        let container = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(60));
+            loop {
+                interval.tick().await;
+                container.stop_overdue_attempts().await;
+            }
+        });
+    }
+
     pub async fn spawn_worktree_cleanup(&self) {
         let db = self.db.clone();
+        let container = self.clone();
         let mut cleanup_interval = tokio::time::interval(tokio::time::Duration::from_secs(1800)); // 30 minutes
         self.cleanup_orphaned_worktrees().await;
         tokio::spawn(async move {
@@ -348,14 +840,24 @@ impl LocalContainerService {
                 Err(_) => (None, ExecutionProcessStatus::Failed),
             };
 
-            if !ExecutionProcess::was_stopped(&db.pool, exec_id).await
+            let was_stopped = ExecutionProcess::was_stopped(&db.pool, exec_id).await;
+            if !was_stopped
                 && let Err(e) =
                     ExecutionProcess::update_completion(&db.pool, exec_id, status, exit_code).await
             {
                 tracing::error!("Failed to update execution process completion: {}", e);
             }
 
-            if let Ok(ctx) = ExecutionProcess::load_context(&db.pool, exec_id).await {
+            let mut retrying = false;
+
+            if let Ok(mut ctx) = ExecutionProcess::load_context(&db.pool, exec_id).await {
+                if matches!(
+                    ctx.execution_process.run_reason,
+                    ExecutionProcessRunReason::CodingAgent
+                ) {
+                    ctx.task_attempt.needs_input = container.update_needs_input(&ctx).await;
+                }
+
                 // Update executor session summary if available
                 if let Err(e) = container.update_executor_session_summary(&exec_id).await {
                     tracing::warn!("Failed to update executor session summary: {}", e);
@@ -366,6 +868,23 @@ impl LocalContainerService {
                     ExecutionProcessStatus::Completed
                 ) && exit_code == Some(0);
 
+                // Setup is over either way; the agent's own process takes it from here
+                if matches!(
+                    ctx.execution_process.run_reason,
+                    ExecutionProcessRunReason::SetupScript
+                ) {
+                    container.report_attempt_progress(&ctx.task_attempt, None);
+                }
+
+                // Crashed agent turns may be re-run instead of handing the attempt back
+                retrying = !was_stopped
+                    && matches!(
+                        ctx.execution_process.run_reason,
+                        ExecutionProcessRunReason::CodingAgent
+                    )
+                    && matches!(ctx.execution_process.status, ExecutionProcessStatus::Failed)
+                    && container.schedule_failed_turn_retry(&ctx).await;
+
                 let cleanup_done = matches!(
                     ctx.execution_process.run_reason,
                     ExecutionProcessRunReason::CleanupScript
@@ -389,11 +908,27 @@ impl LocalContainerService {
                         ctx.execution_process.run_reason,
                         ExecutionProcessRunReason::CodingAgent
                     ) {
+                        // Projects can opt into running cleanup regardless, e.g. to tear down
+                        // external resources
                         changes_committed
+                            || Project::find_by_id(&db.pool, ctx.task.project_id)
+                                .await
+                                .ok()
+                                .flatten()
+                                .is_some_and(|project| project.always_run_cleanup)
                     } else {
                         true
                     };
 
+                    if success
+                        && matches!(
+                            ctx.execution_process.run_reason,
+                            ExecutionProcessRunReason::SetupScript
+                        )
+                    {
+                        Self::store_setup_cache(&db, &ctx).await;
+                    }
+
                     if should_start_next {
                         // If the process exited successfully, start the next action
                         if let Err(e) = container.try_start_next_action(&ctx).await {
@@ -410,10 +945,19 @@ impl LocalContainerService {
                     }
                 }
 
-                if Self::should_finalize(&ctx) {
+                container.refresh_file_reviews(&ctx).await;
+
+                if !retrying && Self::should_finalize(&ctx) {
                     Self::finalize_task(&db, &config, &ctx).await;
-                    // After finalization, check if a queued follow-up exists and start it
-                    if let Err(e) = container.try_consume_queued_followup(&ctx).await {
+                    // After finalization, check if a queued follow-up exists and start it,
+                    // unless the attempt was stopped for running too long
+                    let timed_out = container
+                        .timed_out_attempts
+                        .write()
+                        .await
+                        .remove(&ctx.task_attempt.id);
+                    if !timed_out && let Err(e) = container.try_consume_queued_followup(&ctx).await
+                    {
                         tracing::error!(
                             "Failed to start queued follow-up for attempt {}: {}",
                             ctx.task_attempt.id,
@@ -441,53 +985,82 @@ impl LocalContainerService {
             }
 
             // Now that commit/next-action/finalization steps for this process are complete,
-            // check if this execution is part of a workflow and trigger workflow progression
-            if let Ok(ctx) = ExecutionProcess::load_context(&db.pool, exec_id).await {
+            // check if this execution is part of a workflow and trigger workflow progression.
+            // A retried turn reports to the workflow when the retry finishes.
+            if !retrying && let Ok(ctx) = ExecutionProcess::load_context(&db.pool, exec_id).await {
                 let success = matches!(
                     ctx.execution_process.status,
                     ExecutionProcessStatus::Completed
                 ) && exit_code == Some(0);
 
                 // Create workflow orchestrator and check if this execution is part of a workflow
-                let workflow_orchestrator = services::services::workflow_orchestrator::WorkflowOrchestrator::new(db.clone());
+                let workflow_orchestrator =
+                    services::services::workflow_orchestrator::WorkflowOrchestrator::new(
+                        db.clone(),
+                    );
 
-                if let Ok(Some((station_execution, _))) = workflow_orchestrator
+                match workflow_orchestrator
                     .check_execution_for_workflow(exec_id, success)
                     .await
                 {
-                    tracing::info!(
-                        "Execution {} is part of workflow, triggering station completion",
-                        exec_id
-                    );
+                    Ok(Some((station_execution, success))) => {
+                        tracing::info!(
+                            "Execution {} is part of workflow, triggering station completion",
+                            exec_id
+                        );
 
-                    // Extract output data from agent response (Phase 4.10)
-                    let output_data = container.extract_workflow_output_data(&exec_id, &station_execution).await;
+                        // Extract output data from the agent response using the station's output_context_keys
+                        let output_data = container
+                            .extract_workflow_output_data(&exec_id, &station_execution)
+                            .await;
+
+                        if let Some(ref data) = output_data {
+                            tracing::debug!(
+                                "Extracted output data for station execution {}: {}",
+                                station_execution.id,
+                                data
+                            );
+                        } else {
+                            tracing::debug!(
+                                "No output data extracted for station execution {}",
+                                station_execution.id
+                            );
+                        }
 
-                    if let Some(ref data) = output_data {
-                        tracing::debug!(
-                            "Extracted output data for station execution {}: {}",
-                            station_execution.id,
-                            data
-                        );
-                    } else {
-                        tracing::debug!(
-                            "No output data extracted for station execution {}",
-                            station_execution.id
-                        );
+                        // Complete the station and advance the workflow to the next station
+                        if let Err(e) = workflow_orchestrator
+                            .handle_station_completion(
+                                &container,
+                                station_execution.id,
+                                success,
+                                output_data,
+                            )
+                            .await
+                        {
+                            tracing::error!(
+                                "Failed to handle workflow station completion for station execution {}: {}",
+                                station_execution.id,
+                                e
+                            );
+                        }
                     }
-
-                    // Trigger workflow progression (this will handle station completion and advance workflow)
-                    if let Err(e) = workflow_orchestrator
-                        .trigger_workflow_progression(&container, exec_id, success, output_data)
-                        .await
-                    {
-                        tracing::error!("Failed to handle workflow station completion: {}", e);
+                    Ok(None) => {}
+                    Err(e) => {
+                        tracing::error!(
+                            "Failed to check workflow membership for execution {}: {}",
+                            exec_id,
+                            e
+                        );
                     }
                 }
             }
 
             // Capture the HEAD OID as the definitive "after" state (best-effort).
             if let Ok(ctx) = ExecutionProcess::load_context(&db.pool, exec_id).await {
+                // Next actions and queued follow-ups have started by now, so the run is only
+                // over if nothing else is running
+                container.clear_idle_attempt_run(ctx.task_attempt.id).await;
+
                 let worktree_dir = container.task_attempt_to_current_dir(&ctx.task_attempt);
                 if let Ok(head) = container.git().get_head_info(&worktree_dir)
                     && let Err(e) =
@@ -554,12 +1127,151 @@ impl LocalContainerService {
         rx
     }
 
-    pub fn dir_name_from_task_attempt(attempt_id: &Uuid, task_title: &str) -> String {
-        let task_title_id = git_branch_id(task_title);
-        format!("{}-{}", short_uuid(attempt_id), task_title_id)
+    /// Worktree directory name: the branch template rendered without the prefix, flattened
+    /// to a single path component.
+    pub fn dir_name_from_task_attempt(
+        branch_template: &str,
+        attempt_id: &Uuid,
+        task_id: &Uuid,
+        task_title: &str,
+    ) -> String {
+        let template = if is_valid_branch_template(branch_template) {
+            branch_template
+        } else {
+            DEFAULT_BRANCH_TEMPLATE
+        };
+        render_branch_template(
+            template,
+            "",
+            &short_uuid(attempt_id),
+            &short_uuid(task_id),
+            &git_branch_id(task_title),
+        )
+        .replace('/', "-")
+    }
+
+    /// Mark a dev server whose child handle was lost with a previous server run killed, and
+    /// kill it in the background using the pid recorded when it was spawned
+    async fn kill_orphaned_dev_server(
+        &self,
+        execution_process: &ExecutionProcess,
+    ) -> Result<(), ContainerError> {
+        let pid = ExecutionProcess::find_pid(&self.db.pool, execution_process.id).await?;
+        ExecutionProcess::update_completion(
+            &self.db.pool,
+            execution_process.id,
+            ExecutionProcessStatus::Killed,
+            None,
+        )
+        .await?;
+
+        if let Some(pid) = pid {
+            let id = execution_process.id;
+            let spawned_at = execution_process.started_at;
+            tokio::spawn(async move {
+                match command::kill_orphaned_process_group(pid as u32, spawned_at).await {
+                    Ok(true) => tracing::debug!("Killed orphaned dev server {} (pid {})", id, pid),
+                    Ok(false) => {}
+                    Err(e) => tracing::warn!("Failed to kill orphaned dev server {}: {}", id, e),
+                }
+            });
+        }
+        Ok(())
+    }
+
+    /// Commit all changes in `worktree` on behalf of an agent or script, honouring the
+    /// `skip_agent_commit_hooks` setting. Returns whether anything was committed.
+    async fn commit_agent_changes(
+        &self,
+        worktree: &Path,
+        message: &str,
+    ) -> Result<bool, ContainerError> {
+        let skip_hooks = self.config.read().await.skip_agent_commit_hooks;
+        let _commit_guard = self.commit_lock.lock().await;
+        self.git()
+            .commit_with_options(worktree, message, CommitOptions { skip_hooks })
+            .map_err(|e| {
+                if !skip_hooks {
+                    tracing::warn!(
+                        "Agent commit in {:?} failed; if a git hook rejected it, enable \
+                         skipping git hooks for agent commits in settings",
+                        worktree
+                    );
+                }
+                e.into()
+            })
+    }
+
+    /// Reset the reviews of files a finished process changed, whether it committed them or
+    /// left them uncommitted in the worktree
+    async fn refresh_file_reviews(&self, ctx: &ExecutionContext) {
+        let worktree = self.task_attempt_to_current_dir(&ctx.task_attempt);
+        if let Err(e) = FileReviewService::refresh(
+            &self.db.pool,
+            self.git(),
+            &self.events_msg_store,
+            &ctx.task_attempt,
+            &worktree,
+        )
+        .await
+        {
+            tracing::warn!(
+                "Failed to refresh file reviews of attempt {}: {}",
+                ctx.task_attempt.id,
+                e
+            );
+        }
+    }
+
+    /// Commit whatever an interrupted coding agent or cleanup script left in the worktree, so
+    /// the work survives on the attempt branch, and record the resulting head commit
+    async fn checkpoint_interrupted(&self, ctx: &ExecutionContext) -> Result<(), ContainerError> {
+        let label = match ctx.execution_process.run_reason {
+            ExecutionProcessRunReason::CodingAgent => "coding agent",
+            ExecutionProcessRunReason::CleanupScript => "cleanup script",
+            _ => return Ok(()),
+        };
+        let worktree = self.task_attempt_to_current_dir(&ctx.task_attempt);
+        let message = format!(
+            "Work in progress from interrupted {label} for task attempt {}",
+            ctx.task_attempt.id
+        );
+        self.commit_agent_changes(&worktree, &message).await?;
+
+        let head = self.git().get_head_info(&worktree)?;
+        ExecutionProcess::update_after_head_commit(
+            &self.db.pool,
+            ctx.execution_process.id,
+            &head.oid,
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Contexts of the execution processes whose child is still in the child store. Commands
+    /// started with `exec_command` have no execution process and are skipped.
+    async fn running_execution_contexts(&self) -> Vec<ExecutionContext> {
+        let running: Vec<Uuid> = self.child_store.read().await.keys().copied().collect();
+        let mut contexts = Vec::with_capacity(running.len());
+        for exec_id in running {
+            match ExecutionProcess::load_context(&self.db.pool, exec_id).await {
+                Ok(ctx) => contexts.push(ctx),
+                Err(sqlx::Error::RowNotFound) => {}
+                Err(e) => {
+                    tracing::error!("Failed to load execution process {}: {}", exec_id, e)
+                }
+            }
+        }
+        contexts
     }
 
-    async fn track_child_msgs_in_store(&self, id: Uuid, child: &mut AsyncGroupChild) {
+    /// Forward the child's stdout and stderr into a new `MsgStore` under `id`. The returned
+    /// handle completes once both pipes are closed and all output is in the store.
+    async fn track_child_msgs_in_store(
+        &self,
+        id: Uuid,
+        child: &mut AsyncGroupChild,
+    ) -> JoinHandle<()> {
         let store = Arc::new(MsgStore::new());
 
         let out = child.inner().stdout.take().expect("no stdout");
@@ -578,10 +1290,11 @@ impl LocalContainerService {
         // Merge and forward into the store
         let merged = select(out, err); // Stream<Item = Result<LogMsg, io::Error>>
         let debounced = utils::stream_ext::debounce_logs(merged);
-        store.clone().spawn_forwarder(debounced);
+        let forwarder = store.clone().spawn_forwarder(debounced);
 
         let mut map = self.msg_stores().write().await;
         map.insert(id, store);
+        forwarder
     }
 
     /// Get the worktree path for a task attempt
@@ -619,19 +1332,15 @@ impl LocalContainerService {
     }
 
     /// Create a diff log stream for merged attempts (never changes) for WebSocket
-    fn create_merged_diff_stream(
+    /// One-off diff stream of committed changes, e.g. a merge commit or the branch of an attempt
+    /// whose shared worktree is on another attempt's branch
+    fn create_static_diff_stream(
         &self,
-        project_repo_path: &Path,
-        merge_commit_id: &str,
+        target: DiffTarget,
         stats_only: bool,
+        options: DiffContentOptions,
     ) -> Result<DiffStreamHandle, ContainerError> {
-        let diffs = self.git().get_diffs(
-            DiffTarget::Commit {
-                repo_path: project_repo_path,
-                commit_sha: merge_commit_id,
-            },
-            None,
-        )?;
+        let diffs = self.git().get_diffs_with_options(target, None, options)?;
 
         let cum = Arc::new(AtomicUsize::new(0));
         let diffs: Vec<_> = diffs
@@ -663,12 +1372,14 @@ impl LocalContainerService {
         worktree_path: &Path,
         base_commit: &Commit,
         stats_only: bool,
+        options: DiffContentOptions,
     ) -> Result<DiffStreamHandle, ContainerError> {
         diff_stream::create(
             self.git().clone(),
             worktree_path.to_path_buf(),
             base_commit.clone(),
             stats_only,
+            options,
         )
         .await
         .map_err(|e| ContainerError::Other(anyhow!("{e}")))
@@ -702,10 +1413,18 @@ impl ContainerService for LocalContainerService {
         &self.git
     }
 
+    fn repo_config(&self) -> &RepoConfigService {
+        &self.repo_config
+    }
+
     async fn git_branch_prefix(&self) -> String {
         self.config.read().await.git_branch_prefix.clone()
     }
 
+    async fn git_branch_template(&self) -> String {
+        self.config.read().await.branch_template.clone()
+    }
+
     fn task_attempt_to_current_dir(&self, task_attempt: &TaskAttempt) -> PathBuf {
         PathBuf::from(task_attempt.container_ref.clone().unwrap_or_default())
     }
@@ -720,21 +1439,70 @@ impl ContainerService for LocalContainerService {
             .parent_project(&self.db.pool)
             .await?
             .ok_or(sqlx::Error::RowNotFound)?;
+        let project = self.repo_config.effective(project).await;
+
+        let shared_worktree = if project.reuse_worktree {
+            TaskAttempt::find_reusable_container_ref(&self.db.pool, task.id, task_attempt.id)
+                .await?
+                .map(PathBuf::from)
+        } else {
+            None
+        };
+        let worktree_path = match &shared_worktree {
+            Some(path) => path.clone(),
+            None => {
+                let branch_template = self.config.read().await.branch_template.clone();
+                let worktree_dir_name = LocalContainerService::dir_name_from_task_attempt(
+                    &branch_template,
+                    &task_attempt.id,
+                    &task.id,
+                    &task.title,
+                );
+                WorktreeManager::get_worktree_base_dir(project.worktree_dir.as_deref())
+                    .join(&worktree_dir_name)
+            }
+        };
 
-        let worktree_dir_name =
-            LocalContainerService::dir_name_from_task_attempt(&task_attempt.id, &task.title);
-        let worktree_path = WorktreeManager::get_worktree_base_dir(
-            project.worktree_dir.as_deref()
-        ).join(&worktree_dir_name);
+        self.report_attempt_progress(task_attempt, Some(AttemptProgressStage::CreatingWorktree));
+        let created: Result<(), ContainerError> = async {
+            match &shared_worktree {
+                // Put the task's worktree on a fresh branch off the base, keeping ignored files
+                Some(_) => {
+                    self.ensure_shared_worktree_idle(task_attempt, &worktree_path)
+                        .await?;
+                    WorktreeManager::create_branch(
+                        &project.git_repo_path,
+                        &task_attempt.branch,
+                        &task_attempt.target_branch,
+                    )
+                    .await?;
+                    WorktreeManager::switch_shared_worktree(
+                        &project.git_repo_path,
+                        &task_attempt.branch,
+                        &worktree_path,
+                    )
+                    .await?;
+                }
+                None => {
+                    WorktreeManager::create_worktree(
+                        &project.git_repo_path,
+                        &task_attempt.branch,
+                        &worktree_path,
+                        &task_attempt.target_branch,
+                        true, // create new branch
+                    )
+                    .await?;
+                }
+            }
+            Ok(())
+        }
+        .await;
+        if let Err(e) = created {
+            self.report_attempt_progress(task_attempt, None);
+            return Err(e);
+        }
 
-        WorktreeManager::create_worktree(
-            &project.git_repo_path,
-            &task_attempt.branch,
-            &worktree_path,
-            &task_attempt.target_branch,
-            true, // create new branch
-        )
-        .await?;
+        self.report_attempt_progress(task_attempt, Some(AttemptProgressStage::CopyingFiles));
 
         // Copy files specified in the project's copy_files field
         if let Some(copy_files) = &project.copy_files
@@ -757,16 +1525,61 @@ impl ContainerService for LocalContainerService {
         }
 
         // Update both container_ref and branch in the database
-        TaskAttempt::update_container_ref(
+        if let Err(e) = TaskAttempt::update_container_ref(
             &self.db.pool,
             task_attempt.id,
             &worktree_path.to_string_lossy(),
         )
-        .await?;
+        .await
+        {
+            self.report_attempt_progress(task_attempt, None);
+            return Err(e.into());
+        }
 
         Ok(worktree_path.to_string_lossy().to_string())
     }
 
+    fn report_attempt_progress(
+        &self,
+        task_attempt: &TaskAttempt,
+        stage: Option<AttemptProgressStage>,
+    ) {
+        let patch = match stage {
+            Some(stage) => attempt_progress_patch::set(&AttemptProgress {
+                task_attempt_id: task_attempt.id,
+                task_id: task_attempt.task_id,
+                stage,
+            }),
+            None => attempt_progress_patch::clear(task_attempt.id),
+        };
+        self.events_msg_store.push_patch(patch);
+    }
+
+    /// Refuse to move a worktree shared by the task's attempts to `task_attempt`'s branch while
+    /// a process of another attempt is running in it
+    async fn ensure_shared_worktree_idle(
+        &self,
+        task_attempt: &TaskAttempt,
+        worktree_path: &Path,
+    ) -> Result<(), ContainerError> {
+        let worktree = worktree_path.to_string_lossy();
+        for process in ExecutionProcess::find_running(&self.db.pool).await? {
+            if process.task_attempt_id == task_attempt.id {
+                continue;
+            }
+            if let Some(other) =
+                TaskAttempt::find_by_id(&self.db.pool, process.task_attempt_id).await?
+                && other.container_ref.as_deref() == Some(worktree.as_ref())
+            {
+                return Err(ContainerError::Other(anyhow!(
+                    "Attempt {} is still running in the task's shared worktree; attempts of a task can't run in parallel while the project reuses worktrees",
+                    other.id
+                )));
+            }
+        }
+        Ok(())
+    }
+
     async fn delete_inner(&self, task_attempt: &TaskAttempt) -> Result<(), ContainerError> {
         // cleanup the container, here that means deleting the worktree
         let task = task_attempt
@@ -781,6 +1594,17 @@ impl ContainerService for LocalContainerService {
                 None
             }
         };
+        if let Some(container_ref) = &task_attempt.container_ref
+            && TaskAttempt::container_ref_shared(&self.db.pool, container_ref, task_attempt.id)
+                .await?
+        {
+            tracing::debug!(
+                "Keeping worktree {} of task attempt {}: other attempts of the task still use it",
+                container_ref,
+                task_attempt.id
+            );
+            return Ok(());
+        }
         WorktreeManager::cleanup_worktree(
             &PathBuf::from(task_attempt.container_ref.clone().unwrap_or_default()),
             git_repo_path.as_deref(),
@@ -816,6 +1640,8 @@ impl ContainerService for LocalContainerService {
         })?;
         let worktree_path = PathBuf::from(container_ref);
 
+        // A worktree shared by the task's attempts may be on another attempt's branch; it is
+        // only switched when an execution starts (see `checkout_attempt_branch`)
         WorktreeManager::ensure_worktree_exists(
             &project.git_repo_path,
             &task_attempt.branch,
@@ -826,6 +1652,44 @@ impl ContainerService for LocalContainerService {
         Ok(container_ref.to_string())
     }
 
+    async fn checkout_attempt_branch(
+        &self,
+        task_attempt: &TaskAttempt,
+    ) -> Result<(), ContainerError> {
+        let Some(container_ref) = &task_attempt.container_ref else {
+            return Ok(());
+        };
+        if !TaskAttempt::container_ref_shared(&self.db.pool, container_ref, task_attempt.id).await?
+        {
+            return Ok(());
+        }
+        let worktree_path = PathBuf::from(container_ref);
+        let on_other_branch = self
+            .git()
+            .get_head_info(&worktree_path)
+            .is_ok_and(|head| head.branch != task_attempt.branch);
+        if !on_other_branch {
+            return Ok(());
+        }
+        self.ensure_shared_worktree_idle(task_attempt, &worktree_path)
+            .await?;
+
+        let project = task_attempt
+            .parent_task(&self.db.pool)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)?
+            .parent_project(&self.db.pool)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)?;
+        WorktreeManager::switch_shared_worktree(
+            &project.git_repo_path,
+            &task_attempt.branch,
+            &worktree_path,
+        )
+        .await?;
+        Ok(())
+    }
+
     async fn is_container_clean(&self, task_attempt: &TaskAttempt) -> Result<bool, ContainerError> {
         if let Some(container_ref) = &task_attempt.container_ref {
             // If container_ref is set, check if the worktree exists
@@ -846,6 +1710,24 @@ impl ContainerService for LocalContainerService {
         execution_process: &ExecutionProcess,
         executor_action: &ExecutorAction,
     ) -> Result<(), ContainerError> {
+        if self.shutting_down.load(Ordering::SeqCst) {
+            return Err(ContainerError::Other(anyhow!(
+                "Server is shutting down, not starting new executions"
+            )));
+        }
+
+        if execution_process.run_reason != ExecutionProcessRunReason::DevServer {
+            let mut attempt_runs = self.attempt_runs.write().await;
+            if let Entry::Vacant(run) = attempt_runs.entry(task_attempt.id) {
+                // A new run; an earlier timeout no longer holds back queued follow-ups
+                self.timed_out_attempts
+                    .write()
+                    .await
+                    .remove(&task_attempt.id);
+                run.insert(Utc::now());
+            }
+        }
+
         // Get the worktree path
         let container_ref = task_attempt
             .container_ref
@@ -867,10 +1749,22 @@ impl ContainerService for LocalContainerService {
                 _ => Arc::new(NoopExecutorApprovalService {}),
             };
 
+        let env = self.load_project_env(task_attempt).await?;
+
         // Create the child and stream, add to execution tracker
-        let mut spawned = executor_action
-            .spawn(&current_dir, approvals_service)
-            .await?;
+        let mut spawned =
+            with_execution_env(env, executor_action.spawn(&current_dir, approvals_service)).await?;
+
+        if let Some(pid) = spawned.child.inner().id()
+            && let Err(e) =
+                ExecutionProcess::update_pid(&self.db.pool, execution_process.id, pid as i64).await
+        {
+            tracing::warn!(
+                "Failed to record pid for execution process {}: {}",
+                execution_process.id,
+                e
+            );
+        }
 
         self.track_child_msgs_in_store(execution_process.id, &mut spawned.child)
             .await;
@@ -904,6 +1798,11 @@ impl ContainerService for LocalContainerService {
         ExecutionProcess::update_completion(&self.db.pool, execution_process.id, status, exit_code)
             .await?;
 
+        // Resolve approvals the process is blocked on before its msg store goes away
+        self.approvals
+            .cancel_for_execution(execution_process.id)
+            .await;
+
         // Kill the child process and remove from the store
         {
             let mut child_guard = child.write().await;
@@ -956,12 +1855,272 @@ impl ContainerService for LocalContainerService {
         Ok(())
     }
 
+    async fn shutdown(&self, grace_period: Duration) {
+        self.shutting_down.store(true, Ordering::SeqCst);
+
+        // Dev servers never finish on their own, so they are stopped right away
+        for ctx in self.running_execution_contexts().await {
+            if matches!(
+                ctx.execution_process.run_reason,
+                ExecutionProcessRunReason::DevServer
+            ) && let Err(e) = self
+                .stop_execution(&ctx.execution_process, ExecutionProcessStatus::Killed)
+                .await
+            {
+                tracing::error!(
+                    "Failed to stop dev server {} on shutdown: {}",
+                    ctx.execution_process.id,
+                    e
+                );
+            }
+        }
+
+        if wait_for_children(&self.child_store, grace_period).await {
+            return;
+        }
+
+        for ctx in self.running_execution_contexts().await {
+            let exec_id = ctx.execution_process.id;
+            // Commit the work in progress while the process still holds it
+            if let Err(e) = self.checkpoint_interrupted(&ctx).await {
+                tracing::warn!(
+                    "Failed to checkpoint execution process {} on shutdown: {}",
+                    exec_id,
+                    e
+                );
+            }
+            if let Err(e) = self
+                .stop_execution(&ctx.execution_process, ExecutionProcessStatus::Killed)
+                .await
+            {
+                tracing::error!(
+                    "Failed to stop execution process {} on shutdown: {}",
+                    exec_id,
+                    e
+                );
+                continue;
+            }
+            tracing::info!("Stopped execution process {} on shutdown", exec_id);
+        }
+
+        // Let log streams still attached to an unfinished process end cleanly
+        for (_, msg_store) in self.msg_stores.write().await.drain() {
+            msg_store.push_finished();
+        }
+    }
+
+    async fn exec_command(
+        &self,
+        task_attempt: &TaskAttempt,
+        command: &str,
+    ) -> Result<ExecCommandHandle, ContainerError> {
+        if self.shutting_down.load(Ordering::SeqCst) {
+            return Err(ContainerError::Other(anyhow!(
+                "Server is shutting down, not starting new commands"
+            )));
+        }
+
+        let current_dir = PathBuf::from(self.ensure_container_exists(task_attempt).await?);
+        self.checkout_attempt_branch(task_attempt).await?;
+        let env = self.load_project_env(task_attempt).await?;
+
+        let (shell_cmd, shell_arg) = get_shell_command();
+        let mut cmd = tokio::process::Command::new(shell_cmd);
+        cmd.kill_on_drop(true)
+            .stdin(std::process::Stdio::null())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .arg(shell_arg)
+            .arg(command)
+            .current_dir(&current_dir)
+            .envs(env);
+        let mut child = cmd.group_spawn()?;
+
+        // Same child/msg store bookkeeping as executions, keyed by an id with no DB record
+        let id = Uuid::new_v4();
+        let forwarder = self.track_child_msgs_in_store(id, &mut child).await;
+        let msg_store = self
+            .get_msg_store_by_id(&id)
+            .await
+            .ok_or_else(|| ContainerError::Other(anyhow!("MsgStore missing for command")))?;
+        self.add_child_to_store(id, child).await;
+
+        let exit_rx = self.spawn_os_exit_watcher(id);
+        let child_store = self.child_store.clone();
+        let msg_stores = self.msg_stores.clone();
+        let exit_code = tokio::spawn(async move {
+            let exit_code = match tokio::time::timeout(EXEC_COMMAND_TIMEOUT, exit_rx).await {
+                Ok(Ok(Ok(status))) => status.code(),
+                Ok(_) => None,
+                Err(_) => {
+                    if let Some(child_lock) = child_store.read().await.get(&id).cloned()
+                        && let Err(e) =
+                            command::kill_process_group(&mut *child_lock.write().await).await
+                    {
+                        tracing::error!("Failed to kill timed out command {}: {}", id, e);
+                    }
+                    if let Some(msg_store) = msg_stores.read().await.get(&id) {
+                        msg_store.push_stderr(format!(
+                            "Command killed after {}s",
+                            EXEC_COMMAND_TIMEOUT.as_secs()
+                        ));
+                    }
+                    None
+                }
+            };
+
+            // Output still in flight would otherwise land after `Finished`
+            if tokio::time::timeout(EXEC_COMMAND_OUTPUT_GRACE, forwarder)
+                .await
+                .is_err()
+            {
+                tracing::debug!("Output of command {} still open after it exited", id);
+            }
+            if let Some(msg_store) = msg_stores.write().await.remove(&id) {
+                msg_store.push_finished();
+            }
+            child_store.write().await.remove(&id);
+            exit_code
+        });
+
+        Ok(ExecCommandHandle {
+            id,
+            msg_store,
+            exit_code,
+        })
+    }
+
+    async fn summarize_diff(
+        &self,
+        task_attempt: &TaskAttempt,
+        executor_profile_id: &ExecutorProfileId,
+    ) -> Result<String, ContainerError> {
+        let project_repo_path = self.get_project_repo_path(task_attempt).await?;
+        let worktree_path = PathBuf::from(self.ensure_container_exists(task_attempt).await?);
+        let base_commit = self.git().get_base_commit(
+            &project_repo_path,
+            &task_attempt.branch,
+            &task_attempt.target_branch,
+        )?;
+        let (diffs, head_oid) = if self
+            .worktree_on_attempt_branch(task_attempt, &worktree_path)
+            .await?
+        {
+            let diffs = self.git().get_diffs(
+                DiffTarget::Worktree {
+                    worktree_path: &worktree_path,
+                    base_commit: &base_commit,
+                },
+                None,
+            )?;
+            (diffs, self.git().get_head_info(&worktree_path)?.oid)
+        } else {
+            let diffs = self.git().get_diffs(
+                DiffTarget::Branch {
+                    repo_path: &project_repo_path,
+                    branch_name: &task_attempt.branch,
+                    base_branch: &task_attempt.target_branch,
+                },
+                None,
+            )?;
+            let head_oid = self
+                .git()
+                .get_branch_oid(&project_repo_path, &task_attempt.branch)?;
+            (diffs, head_oid)
+        };
+        let diff = Self::render_bounded_diff(&diffs, commit_message::MAX_DIFF_BYTES);
+
+        let mut hasher = DefaultHasher::new();
+        diff.hash(&mut hasher);
+        let key = (executor_profile_id.clone(), head_oid, hasher.finish());
+        if let Some(summary) = self.diff_summaries.read().await.get(&key) {
+            return Ok(summary.clone());
+        }
+
+        let summary =
+            commit_message::generate_diff_summary(executor_profile_id, &diff, DIFF_SUMMARY_TIMEOUT)
+                .await
+                .map_err(|e| ContainerError::Other(anyhow!("Failed to summarize diff: {e}")))?;
+
+        let mut cache = self.diff_summaries.write().await;
+        if cache.len() >= DIFF_SUMMARY_CACHE_CAPACITY
+            && let Some(evict) = cache.keys().next().cloned()
+        {
+            cache.remove(&evict);
+        }
+        cache.insert(key, summary.clone());
+        Ok(summary)
+    }
+
+    async fn stop_dev_servers_except(
+        &self,
+        keep_project_ids: &[Uuid],
+    ) -> Result<Vec<StoppedDevServer>, ContainerError> {
+        let mut stopped = Vec::new();
+        for project in Project::find_all(&self.db.pool).await? {
+            if keep_project_ids.contains(&project.id) {
+                continue;
+            }
+            for process in
+                ExecutionProcess::find_running_dev_servers_by_project(&self.db.pool, project.id)
+                    .await?
+            {
+                let orphaned = self.get_child_from_store(&process.id).await.is_none();
+                let result = if orphaned {
+                    self.kill_orphaned_dev_server(&process).await
+                } else {
+                    self.stop_execution(&process, ExecutionProcessStatus::Killed)
+                        .await
+                };
+                if let Err(e) = result {
+                    tracing::error!("Failed to stop dev server {}: {}", process.id, e);
+                    continue;
+                }
+
+                tracing::info!(
+                    "Stopped {}dev server {} of project {}",
+                    if orphaned { "orphaned " } else { "" },
+                    process.id,
+                    project.id
+                );
+                stopped.push(StoppedDevServer {
+                    execution_process_id: process.id,
+                    task_attempt_id: process.task_attempt_id,
+                    project_id: project.id,
+                    orphaned,
+                });
+            }
+        }
+        Ok(stopped)
+    }
+
     async fn stream_diff(
         &self,
         task_attempt: &TaskAttempt,
         stats_only: bool,
+        since_reviewed: bool,
+        options: DiffContentOptions,
     ) -> Result<futures::stream::BoxStream<'static, Result<LogMsg, std::io::Error>>, ContainerError>
     {
+        if since_reviewed && let Some(reviewed_commit) = &task_attempt.reviewed_commit {
+            let container_ref = self.ensure_container_exists(task_attempt).await?;
+            let worktree_path = PathBuf::from(container_ref);
+            if !self
+                .worktree_on_attempt_branch(task_attempt, &worktree_path)
+                .await?
+            {
+                return Err(ContainerError::Other(anyhow!(
+                    "The attempt's branch is not checked out in its shared worktree, so changes \
+                     since the review can't be shown"
+                )));
+            }
+            let reviewed_commit = self.git().find_commit(&worktree_path, reviewed_commit)?;
+            let wrapper = self
+                .create_live_diff_stream(&worktree_path, &reviewed_commit, stats_only, options)
+                .await?;
+            return Ok(Box::pin(wrapper));
+        }
+
         let project_repo_path = self.get_project_repo_path(task_attempt).await?;
         let latest_merge =
             Merge::find_latest_by_task_attempt_id(&self.db.pool, task_attempt.id).await?;
@@ -981,13 +2140,34 @@ impl ContainerService for LocalContainerService {
             && self.is_container_clean(task_attempt).await?
             && !is_ahead
         {
-            let wrapper =
-                self.create_merged_diff_stream(&project_repo_path, &commit, stats_only)?;
+            let wrapper = self.create_static_diff_stream(
+                DiffTarget::Commit {
+                    repo_path: &project_repo_path,
+                    commit_sha: &commit,
+                },
+                stats_only,
+                options,
+            )?;
             return Ok(Box::pin(wrapper));
         }
 
         let container_ref = self.ensure_container_exists(task_attempt).await?;
         let worktree_path = PathBuf::from(container_ref);
+        if !self
+            .worktree_on_attempt_branch(task_attempt, &worktree_path)
+            .await?
+        {
+            let wrapper = self.create_static_diff_stream(
+                DiffTarget::Branch {
+                    repo_path: &project_repo_path,
+                    branch_name: &task_attempt.branch,
+                    base_branch: &task_attempt.target_branch,
+                },
+                stats_only,
+                options,
+            )?;
+            return Ok(Box::pin(wrapper));
+        }
         let base_commit = self.git().get_base_commit(
             &project_repo_path,
             &task_attempt.branch,
@@ -995,7 +2175,7 @@ impl ContainerService for LocalContainerService {
         )?;
 
         let wrapper = self
-            .create_live_diff_stream(&worktree_path, &base_commit, stats_only)
+            .create_live_diff_stream(&worktree_path, &base_commit, stats_only, options)
             .await?;
         Ok(Box::pin(wrapper))
     }
@@ -1010,36 +2190,41 @@ impl ContainerService for LocalContainerService {
 
         let message = match ctx.execution_process.run_reason {
             ExecutionProcessRunReason::CodingAgent => {
-                // Try to retrieve the task summary from the executor session
-                // otherwise fallback to default message
-                match ExecutorSession::find_by_execution_process_id(
+                // Try to retrieve the task summary from the executor session, then an
+                // agent-generated message (if enabled), otherwise fallback to default message
+                let summary = match ExecutorSession::find_by_execution_process_id(
                     &self.db().pool,
                     ctx.execution_process.id,
                 )
                 .await
                 {
-                    Ok(Some(session)) if session.summary.is_some() => session.summary.unwrap(),
-                    Ok(_) => {
-                        tracing::debug!(
-                            "No summary found for execution process {}, using default message",
-                            ctx.execution_process.id
-                        );
-                        format!(
-                            "Commit changes from coding agent for task attempt {}",
-                            ctx.task_attempt.id
-                        )
-                    }
+                    Ok(Some(session)) => session.summary,
+                    Ok(None) => None,
                     Err(e) => {
                         tracing::debug!(
                             "Failed to retrieve summary for execution process {}: {}",
                             ctx.execution_process.id,
                             e
                         );
-                        format!(
-                            "Commit changes from coding agent for task attempt {}",
-                            ctx.task_attempt.id
-                        )
+                        None
                     }
+                };
+
+                match summary {
+                    Some(summary) => summary,
+                    None => match self.generate_commit_message(ctx).await {
+                        Some(message) => message,
+                        None => {
+                            tracing::debug!(
+                                "No summary found for execution process {}, using default message",
+                                ctx.execution_process.id
+                            );
+                            format!(
+                                "Commit changes from coding agent for task attempt {}",
+                                ctx.task_attempt.id
+                            )
+                        }
+                    },
                 }
             }
             ExecutionProcessRunReason::CleanupScript => {
@@ -1064,7 +2249,9 @@ impl ContainerService for LocalContainerService {
             message
         );
 
-        let changes_committed = self.git().commit(Path::new(container_ref), &message)?;
+        let changes_committed = self
+            .commit_agent_changes(Path::new(container_ref), &message)
+            .await?;
         Ok(changes_committed)
     }
 
@@ -1115,6 +2302,18 @@ impl ContainerService for LocalContainerService {
 impl LocalContainerService {
     /// Extract the last assistant message from the MsgStore history
     fn extract_last_assistant_message(&self, exec_id: &Uuid) -> Option<String> {
+        let entry = self.last_assistant_entry(exec_id)?;
+        let content = entry.content.trim();
+        const MAX_SUMMARY_LENGTH: usize = 4096;
+        if content.len() > MAX_SUMMARY_LENGTH {
+            let truncated = truncate_to_char_boundary(content, MAX_SUMMARY_LENGTH);
+            return Some(format!("{truncated}..."));
+        }
+        Some(content.to_string())
+    }
+
+    /// The last non-empty assistant message the execution's normalizer produced
+    fn last_assistant_entry(&self, exec_id: &Uuid) -> Option<NormalizedEntry> {
         // Get the MsgStore for this execution
         let msg_stores = self.msg_stores.try_read().ok()?;
         let msg_store = msg_stores.get(exec_id)?;
@@ -1122,26 +2321,38 @@ impl LocalContainerService {
         // Get the history and scan in reverse for the last assistant message
         let history = msg_store.get_history();
 
-        for msg in history.iter().rev() {
-            if let LogMsg::JsonPatch(patch) = msg {
-                // Try to extract a NormalizedEntry from the patch
-                if let Some((_, entry)) = extract_normalized_entry_from_patch(patch)
-                    && matches!(entry.entry_type, NormalizedEntryType::AssistantMessage)
-                {
-                    let content = entry.content.trim();
-                    if !content.is_empty() {
-                        const MAX_SUMMARY_LENGTH: usize = 4096;
-                        if content.len() > MAX_SUMMARY_LENGTH {
-                            let truncated = truncate_to_char_boundary(content, MAX_SUMMARY_LENGTH);
-                            return Some(format!("{truncated}..."));
-                        }
-                        return Some(content.to_string());
-                    }
-                }
-            }
-        }
+        history.iter().rev().find_map(|msg| {
+            let LogMsg::JsonPatch(patch) = msg else {
+                return None;
+            };
+            extract_normalized_entry_from_patch(patch)
+                .map(|(_, entry)| entry)
+                .filter(|entry| {
+                    matches!(entry.entry_type, NormalizedEntryType::AssistantMessage)
+                        && !entry.content.trim().is_empty()
+                })
+        })
+    }
 
-        None
+    /// Flag the attempt when its agent's turn completed on a message asking the user something,
+    /// as flagged by the executor's log normalizer, and clear the flag otherwise
+    async fn update_needs_input(&self, ctx: &ExecutionContext) -> bool {
+        let needs_input = ctx.execution_process.status == ExecutionProcessStatus::Completed
+            && self
+                .last_assistant_entry(&ctx.execution_process.id)
+                .is_some_and(|entry| entry.awaits_input());
+        if needs_input != ctx.task_attempt.needs_input
+            && let Err(e) =
+                TaskAttempt::update_needs_input(&self.db.pool, ctx.task_attempt.id, needs_input)
+                    .await
+        {
+            tracing::error!(
+                "Failed to update needs_input for attempt {}: {}",
+                ctx.task_attempt.id,
+                e
+            );
+        }
+        needs_input
     }
 
     /// Extract workflow output data from agent execution logs
@@ -1247,6 +2458,73 @@ impl LocalContainerService {
         }
     }
 
+    /// Ask the attempt's coding agent to summarize the uncommitted diff into a commit message.
+    /// Returns None when disabled in config or when generation fails or times out.
+    async fn generate_commit_message(&self, ctx: &ExecutionContext) -> Option<String> {
+        let (enabled, timeout_secs) = {
+            let config = self.config.read().await;
+            (
+                config.generate_commit_messages,
+                config.commit_message_timeout_secs,
+            )
+        };
+        if !enabled {
+            return None;
+        }
+
+        let executor_profile_id = match ctx.execution_process.executor_action().ok()?.typ() {
+            ExecutorActionType::CodingAgentInitialRequest(request) => {
+                request.executor_profile_id.clone()
+            }
+            ExecutorActionType::CodingAgentFollowUpRequest(request) => {
+                request.executor_profile_id.clone()
+            }
+            ExecutorActionType::ScriptRequest(_) => return None,
+        };
+
+        let worktree_path = self.task_attempt_to_current_dir(&ctx.task_attempt);
+        let diff = match self.git().get_uncommitted_patch(&worktree_path) {
+            Ok(diff) => diff,
+            Err(e) => {
+                tracing::warn!(
+                    "Failed to read diff for commit message generation ({}): {}",
+                    ctx.task_attempt.id,
+                    e
+                );
+                return None;
+            }
+        };
+
+        commit_message::generate_commit_message(
+            &executor_profile_id,
+            &diff,
+            Duration::from_secs(timeout_secs.into()).min(MAX_COMMIT_MESSAGE_TIMEOUT),
+        )
+        .await
+    }
+
+    /// Render `diffs` as a unified diff, stopping once `max_bytes` is exceeded so huge
+    /// attempts are not rendered in full only to be truncated by the prompt builder
+    fn render_bounded_diff(diffs: &[Diff], max_bytes: usize) -> String {
+        let mut out = String::new();
+        for diff in diffs {
+            if out.len() > max_bytes {
+                break;
+            }
+            let path = GitService::diff_path(diff);
+            if diff.content_omitted {
+                out.push_str(&format!("--- a/{path}\n+++ b/{path}\n(contents omitted)\n"));
+                continue;
+            }
+            out.push_str(&create_unified_diff(
+                &path,
+                diff.old_content.as_deref().unwrap_or_default(),
+                diff.new_content.as_deref().unwrap_or_default(),
+            ));
+        }
+        out
+    }
+
     /// Update the executor session summary with the final assistant message
     async fn update_executor_session_summary(&self, exec_id: &Uuid) -> Result<(), anyhow::Error> {
         // Check if there's an executor session for this execution process
@@ -1347,7 +2625,6 @@ impl LocalContainerService {
             return Ok(());
         };
 
-        use executors::actions::ExecutorActionType;
         let initial_executor_profile_id = match &latest.executor_action()?.typ {
             ExecutorActionType::CodingAgentInitialRequest(req) => req.executor_profile_id.clone(),
             ExecutorActionType::CodingAgentFollowUpRequest(req) => req.executor_profile_id.clone(),
@@ -1366,14 +2643,15 @@ impl LocalContainerService {
         };
 
         // Prepare cleanup action
-        let cleanup_action = ctx
-            .task
-            .parent_project(&self.db.pool)
-            .await?
-            .and_then(|project| self.cleanup_action(project.cleanup_script));
+        let cleanup_action = match ctx.task.parent_project(&self.db.pool).await? {
+            Some(project) => self.cleanup_action(&project).await,
+            None => None,
+        };
+
+        let mut prompt =
+            PromptSnippetService::expand(&self.db.pool, ctx.task.project_id, &draft.prompt).await?;
 
         // Handle images: associate, copy to worktree, canonicalize prompt
-        let mut prompt = draft.prompt.clone();
         if let Some(image_ids) = &draft.image_ids {
             // Associate to task
             let _ = TaskImage::associate_many_dedup(&self.db.pool, ctx.task.id, image_ids).await;
@@ -1420,7 +2698,7 @@ impl LocalContainerService {
     }
 }
 
-fn truncate_to_char_boundary(content: &str, max_len: usize) -> &str {
+pub(crate) fn truncate_to_char_boundary(content: &str, max_len: usize) -> &str {
     if content.len() <= max_len {
         return content;
     }
@@ -1439,6 +2717,30 @@ fn truncate_to_char_boundary(content: &str, max_len: usize) -> &str {
 
 #[cfg(test)]
 mod tests {
+    use std::{collections::HashMap, sync::Arc, time::Instant};
+
+    use tokio::{sync::RwLock, time::Duration};
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn wait_for_children_returns_once_children_exit() {
+        use super::wait_for_children;
+
+        let empty: RwLock<HashMap<Uuid, ()>> = RwLock::new(HashMap::new());
+        assert!(wait_for_children(&empty, Duration::ZERO).await);
+
+        let child_store = Arc::new(RwLock::new(HashMap::from([(Uuid::new_v4(), ())])));
+        assert!(!wait_for_children(&child_store, Duration::from_millis(150)).await);
+
+        let exit_monitor = child_store.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            exit_monitor.write().await.clear();
+        });
+        let started = Instant::now();
+        assert!(wait_for_children(&child_store, Duration::from_secs(30)).await);
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
 
     #[test]
     fn test_truncate_to_char_boundary() {
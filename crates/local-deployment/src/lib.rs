@@ -2,8 +2,11 @@ use std::{collections::HashMap, sync::Arc};
 
 use async_trait::async_trait;
 use db::DBService;
-use deployment::{Deployment, DeploymentError};
-use executors::profile::ExecutorConfigs;
+use deployment::{AttemptQueueState, Deployment, DeploymentError};
+use executors::{
+    command::set_user_extra_args, logs::utils::redact::set_user_redaction_patterns,
+    profile::ExecutorConfigs,
+};
 use services::services::{
     analytics::{AnalyticsConfig, AnalyticsContext, AnalyticsService, generate_user_id},
     approvals::Approvals,
@@ -14,15 +17,21 @@ use services::services::{
     events::EventService,
     file_search_cache::FileSearchCache,
     filesystem::FilesystemService,
-    git::GitService,
-    image::ImageService,
+    git::{GitService, set_fetch_settings},
+    github_cache::GitHubMetadataCache,
+    image::{ImageService, OrphanCleanupOptions},
+    repo_config::RepoConfigService,
 };
 use tokio::sync::RwLock;
-use utils::{assets::config_path, msg_store::MsgStore};
+use utils::{
+    assets::config_path,
+    msg_store::{MsgStore, PatchRetention},
+};
 use uuid::Uuid;
 
 use crate::container::LocalContainerService;
 mod command;
+mod commit_message;
 pub mod container;
 
 #[derive(Clone)]
@@ -39,8 +48,10 @@ pub struct LocalDeployment {
     filesystem: FilesystemService,
     events: EventService,
     file_search_cache: Arc<FileSearchCache>,
+    github_cache: GitHubMetadataCache,
     approvals: Approvals,
     drafts: DraftsService,
+    attempt_queue: AttemptQueueState,
 }
 
 #[async_trait]
@@ -69,6 +80,9 @@ impl Deployment for LocalDeployment {
 
         // Always save config (may have been migrated or version updated)
         save_config_to_file(&raw_config, &config_path()).await?;
+        set_user_extra_args(raw_config.executor_extra_args.clone());
+        set_user_redaction_patterns(&raw_config.log_redaction_patterns);
+        set_fetch_settings(raw_config.fetch_settings());
 
         let config = Arc::new(RwLock::new(raw_config));
         let user_id = generate_user_id();
@@ -77,9 +91,10 @@ impl Deployment for LocalDeployment {
         let msg_stores = Arc::new(RwLock::new(HashMap::new()));
         let auth = AuthService::new();
         let filesystem = FilesystemService::new();
+        let repo_config = RepoConfigService::new(config.clone());
 
         // Create shared components for EventService
-        let events_msg_store = Arc::new(MsgStore::new());
+        let events_msg_store = Arc::new(MsgStore::with_patch_retention(PatchRetention::from_env()));
         let events_entry_count = Arc::new(RwLock::new(0));
 
         // Create DB with event hooks
@@ -95,9 +110,10 @@ impl Deployment for LocalDeployment {
         let image = ImageService::new(db.clone().pool)?;
         {
             let image_service = image.clone();
+            let cleanup_options = OrphanCleanupOptions::from_config(&*config.read().await);
             tokio::spawn(async move {
                 tracing::info!("Starting orphaned image cleanup...");
-                if let Err(e) = image_service.delete_orphaned_images().await {
+                if let Err(e) = image_service.delete_orphaned_images(cleanup_options).await {
                     tracing::error!("Failed to clean up orphaned images: {}", e);
                 }
             });
@@ -114,17 +130,21 @@ impl Deployment for LocalDeployment {
         let container = LocalContainerService::new(
             db.clone(),
             msg_stores.clone(),
+            events_msg_store.clone(),
             config.clone(),
             git.clone(),
             image.clone(),
             analytics_ctx,
             approvals.clone(),
+            repo_config,
         );
         container.spawn_worktree_cleanup().await;
+        container.spawn_overdue_attempt_monitor();
 
         let events = EventService::new(db.clone(), events_msg_store, events_entry_count);
         let drafts = DraftsService::new(db.clone(), image.clone());
         let file_search_cache = Arc::new(FileSearchCache::new());
+        let github_cache = GitHubMetadataCache::new();
 
         Ok(Self {
             config,
@@ -139,8 +159,10 @@ impl Deployment for LocalDeployment {
             filesystem,
             events,
             file_search_cache,
+            github_cache,
             approvals,
             drafts,
+            attempt_queue: AttemptQueueState::default(),
         })
     }
 
@@ -195,6 +217,10 @@ impl Deployment for LocalDeployment {
         &self.file_search_cache
     }
 
+    fn github_cache(&self) -> &GitHubMetadataCache {
+        &self.github_cache
+    }
+
     fn approvals(&self) -> &Approvals {
         &self.approvals
     }
@@ -202,4 +228,8 @@ impl Deployment for LocalDeployment {
     fn drafts(&self) -> &DraftsService {
         &self.drafts
     }
+
+    fn attempt_queue(&self) -> &AttemptQueueState {
+        &self.attempt_queue
+    }
 }
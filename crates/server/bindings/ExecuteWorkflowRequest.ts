@@ -1,4 +1,9 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 import type { ExecutorProfileId } from "./ExecutorProfileId";
 
-export type ExecuteWorkflowRequest = { task_id: string, base_branch: string, executor_profile_id: ExecutorProfileId | null, };
+export type ExecuteWorkflowRequest = { task_id: string, base_branch: string, executor_profile_id: ExecutorProfileId | null, 
+/**
+ * Cancel the workflow's active execution on this task and start a fresh one, instead of
+ * returning the active execution
+ */
+force_restart: boolean | null, };
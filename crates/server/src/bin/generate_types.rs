@@ -15,6 +15,12 @@ fn generate_types_content() -> String {
         db::models::project::Project::decl(),
         db::models::project::CreateProject::decl(),
         db::models::project::UpdateProject::decl(),
+        db::models::project::NamedScript::decl(),
+        server::routes::projects::SetDefaultBaseBranchRequest::decl(),
+        server::routes::projects::RunningDevServer::decl(),
+        server::routes::projects::TrustRepoConfigRequest::decl(),
+        services::services::repo_config::RepoProjectConfig::decl(),
+        services::services::repo_config::RepoConfigStatus::decl(),
         db::models::project::SearchResult::decl(),
         db::models::project::SearchMatchType::decl(),
         executors::actions::ExecutorAction::decl(),
@@ -29,29 +35,60 @@ fn generate_types_content() -> String {
         db::models::tag::CreateTag::decl(),
         db::models::tag::UpdateTag::decl(),
         server::routes::tags::TagSearchParams::decl(),
+        db::models::label::Label::decl(),
+        db::models::label::CreateLabel::decl(),
+        db::models::label::UpdateLabel::decl(),
+        db::models::label::LabelMatch::decl(),
+        db::models::prompt_snippet::PromptSnippet::decl(),
+        db::models::prompt_snippet::CreatePromptSnippet::decl(),
+        db::models::prompt_snippet::UpdatePromptSnippet::decl(),
+        db::models::attempt_kv::AttemptKvEntry::decl(),
         db::models::task::TaskStatus::decl(),
         db::models::task::Task::decl(),
         db::models::task::TaskWithAttemptStatus::decl(),
         db::models::task::TaskRelationships::decl(),
         db::models::task::CreateTask::decl(),
         db::models::task::UpdateTask::decl(),
+        db::models::task_event::TaskEventType::decl(),
+        db::models::task_event::TaskEvent::decl(),
+        db::models::attempt_queue::QueuedAttempt::decl(),
         db::models::image::Image::decl(),
         db::models::image::CreateImage::decl(),
         utils::response::ApiResponse::<()>::decl(),
+        utils::response::ApiErrorCode::decl(),
+        utils::pagination::Page::<()>::decl(),
+        server::routes::batch::BatchSubRequest::decl(),
+        server::routes::batch::BatchSubResponse::decl(),
         server::routes::config::UserSystemInfo::decl(),
         server::routes::config::Environment::decl(),
         server::routes::config::McpServerQuery::decl(),
         server::routes::config::UpdateMcpServersBody::decl(),
         server::routes::config::GetMcpServerResponse::decl(),
+        server::routes::config::ExecutorCapabilities::decl(),
+        server::routes::execution_processes::RunningProcessInfo::decl(),
+        server::routes::execution_processes::StopRunningProcessesRequest::decl(),
         server::routes::task_attempts::CreateFollowUpAttempt::decl(),
+        services::services::container::StoppedDevServer::decl(),
         services::services::drafts::DraftResponse::decl(),
         services::services::drafts::UpdateFollowUpDraftRequest::decl(),
         services::services::drafts::UpdateRetryFollowUpDraftRequest::decl(),
+        server::routes::task_attempts::MarkReviewedRequest::decl(),
+        server::routes::task_attempts::UpdateAttemptNotesRequest::decl(),
         server::routes::task_attempts::ChangeTargetBranchRequest::decl(),
         server::routes::task_attempts::ChangeTargetBranchResponse::decl(),
+        server::routes::task_attempts::file_reviews::FileReviewStatus::decl(),
+        server::routes::task_attempts::file_reviews::SetFileReviewedRequest::decl(),
+        server::routes::task_attempts::kv::SetKvEntryRequest::decl(),
         server::routes::tasks::CreateAndStartTaskRequest::decl(),
+        server::routes::attempt_queue::MoveQueuedAttemptRequest::decl(),
+        server::routes::tasks::SubtaskSpec::decl(),
+        server::routes::tasks::SplitTaskRequest::decl(),
+        server::routes::tasks::SplitTaskResponse::decl(),
+        server::routes::tasks::TaskLabelRequest::decl(),
+        server::routes::tasks::TaskPriority::decl(),
         server::routes::task_attempts::CreateGitHubPrRequest::decl(),
         server::routes::images::ImageResponse::decl(),
+        services::services::image::OrphanCleanupReport::decl(),
         services::services::github_service::GitHubServiceError::decl(),
         services::services::config::Config::decl(),
         services::services::config::NotificationConfig::decl(),
@@ -66,6 +103,11 @@ fn generate_types_content() -> String {
         server::routes::auth::DevicePollStatus::decl(),
         server::routes::auth::CheckTokenResponse::decl(),
         services::services::git::GitBranch::decl(),
+        services::services::git::BlameLine::decl(),
+        services::services::git::ConflictResolution::decl(),
+        services::services::git::ConflictHunk::decl(),
+        services::services::git::FileConflict::decl(),
+        services::services::git::MergePreview::decl(),
         utils::diff::Diff::decl(),
         utils::diff::DiffChangeKind::decl(),
         services::services::github_service::RepositoryInfo::decl(),
@@ -85,22 +127,39 @@ fn generate_types_content() -> String {
         executors::executors::codex::ReasoningSummaryFormat::decl(),
         executors::executors::cursor::CursorAgent::decl(),
         executors::executors::copilot::Copilot::decl(),
+        executors::executors::echo::Echo::decl(),
         executors::executors::opencode::Opencode::decl(),
         executors::executors::qwen::QwenCode::decl(),
-        executors::executors::AppendPrompt::decl(),
+        executors::executors::PromptTemplate::decl(),
         executors::actions::coding_agent_initial::CodingAgentInitialRequest::decl(),
         executors::actions::coding_agent_follow_up::CodingAgentFollowUpRequest::decl(),
         server::routes::task_attempts::CreateTaskAttemptBody::decl(),
         server::routes::task_attempts::RebaseTaskAttemptRequest::decl(),
+        server::routes::task_attempts::ReorderCommitsRequest::decl(),
+        server::routes::task_attempts::SquashCommitsRequest::decl(),
+        server::routes::task_attempts::ResolveConflictRequest::decl(),
+        server::routes::task_attempts::RemainingConflictsResponse::decl(),
         server::routes::task_attempts::GitOperationError::decl(),
         server::routes::task_attempts::ReplaceProcessRequest::decl(),
+        server::routes::task_attempts::FollowUpFromCommitRequest::decl(),
+        server::routes::task_attempts::FollowUpFromCommitResult::decl(),
+        server::routes::task_attempts::ResetAttemptRequest::decl(),
+        server::routes::task_attempts::ResetAttemptResult::decl(),
+        server::routes::task_attempts::RecoverAttemptRequest::decl(),
+        server::routes::task_attempts::RecoverAttemptResult::decl(),
+        server::routes::task_attempts::ExecCommandRequest::decl(),
+        server::routes::task_attempts::ExecCommandResult::decl(),
+        server::routes::task_attempts::MergeResult::decl(),
+        server::routes::task_attempts::DiffSummaryResult::decl(),
         server::routes::task_attempts::CommitInfo::decl(),
+        server::routes::task_attempts::CommitExecutionProcess::decl(),
         server::routes::task_attempts::BranchStatus::decl(),
         services::services::git::ConflictOp::decl(),
         db::models::task_attempt::TaskAttempt::decl(),
         db::models::execution_process::ExecutionProcess::decl(),
         db::models::execution_process::ExecutionProcessStatus::decl(),
         db::models::execution_process::ExecutionProcessRunReason::decl(),
+        db::models::execution_process::RunningExecutionProcess::decl(),
         db::models::merge::Merge::decl(),
         db::models::merge::DirectMerge::decl(),
         db::models::merge::PrMerge::decl(),
@@ -108,6 +167,11 @@ fn generate_types_content() -> String {
         db::models::merge::PullRequestInfo::decl(),
         db::models::draft::Draft::decl(),
         db::models::draft::DraftType::decl(),
+        services::services::events::AttemptProgressStage::decl(),
+        services::services::events::AttemptProgress::decl(),
+        services::services::events::AttemptGitOperation::decl(),
+        services::services::events::AttemptGitUpdate::decl(),
+        services::services::events::AttemptFileReviewUpdate::decl(),
         db::models::agent::ContextFile::decl(),
         db::models::agent::Agent::decl(),
         db::models::agent::CreateAgent::decl(),
@@ -124,6 +188,8 @@ fn generate_types_content() -> String {
         db::models::station_transition::StationTransition::decl(),
         db::models::station_transition::CreateStationTransition::decl(),
         db::models::station_transition::UpdateStationTransition::decl(),
+        server::routes::workflows::DryRunTransitionConditionRequest::decl(),
+        server::routes::workflows::DryRunTransitionConditionResponse::decl(),
         db::models::task_station_execution::TaskStationExecution::decl(),
         db::models::task_station_execution::CreateTaskStationExecution::decl(),
         db::models::task_station_execution::UpdateTaskStationExecution::decl(),
@@ -137,17 +203,21 @@ fn generate_types_content() -> String {
         server::routes::workflows::workflow_executions::ExecuteWorkflowResponse::decl(),
         server::routes::workflows::workflow_executions::WorkflowExecutionDetailsResponse::decl(),
         server::routes::workflows::workflow_executions::StationExecutionSummary::decl(),
+        server::routes::workflows::workflow_executions::WorkflowExecutionHistoryResponse::decl(),
+        server::routes::workflows::workflow_executions::StationTimelineEntry::decl(),
         server::routes::workflows::workflow_executions::CancelWorkflowExecutionRequest::decl(),
         server::routes::workflows::workflow_executions::CancelWorkflowExecutionResponse::decl(),
         server::routes::workflows::workflow_executions::RetryStationRequest::decl(),
         server::routes::workflows::workflow_executions::RetryStationResponse::decl(),
         executors::logs::CommandExitStatus::decl(),
         executors::logs::CommandRunResult::decl(),
+        executors::logs::LogChannel::decl(),
         executors::logs::NormalizedEntry::decl(),
         executors::logs::NormalizedEntryType::decl(),
         executors::logs::FileChange::decl(),
         executors::logs::ActionType::decl(),
         executors::logs::TodoItem::decl(),
+        executors::logs::PlanStep::decl(),
         executors::logs::ToolResult::decl(),
         executors::logs::ToolResultValueType::decl(),
         executors::logs::ToolStatus::decl(),
@@ -228,6 +298,10 @@ fn generate_schemas() -> Result<HashMap<&'static str, String>, serde_json::Error
             "copilot",
             generate_json_schema::<executors::executors::copilot::Copilot>()?,
         ),
+        (
+            "echo",
+            generate_json_schema::<executors::executors::echo::Echo>()?,
+        ),
     ]);
     println!(
         "✅ JSON schemas generated. {} schemas created.",
@@ -12,11 +12,14 @@ use executors::executors::ExecutorError;
 use git2::Error as Git2Error;
 use services::services::{
     auth::AuthError, config::ConfigError, container::ContainerError, drafts::DraftsServiceError,
-    git::GitServiceError, github_service::GitHubServiceError, image::ImageError,
+    file_review::FileReviewError, git::GitServiceError, github_service::GitHubServiceError,
+    github_webhook::GitHubWebhookError, image::ImageError, prompt_snippets::PromptSnippetError,
     worktree_manager::WorktreeError,
 };
 use thiserror::Error;
-use utils::response::ApiResponse;
+use utils::response::{ApiErrorCode, ApiResponse};
+
+use crate::middleware::current_request_id;
 
 #[derive(Debug, Error, ts_rs::TS)]
 #[ts(type = "string")]
@@ -32,6 +35,8 @@ pub enum ApiError {
     #[error(transparent)]
     GitHubService(#[from] GitHubServiceError),
     #[error(transparent)]
+    GitHubWebhook(#[from] GitHubWebhookError),
+    #[error(transparent)]
     Auth(#[from] AuthError),
     #[error(transparent)]
     Deployment(#[from] DeploymentError),
@@ -49,6 +54,8 @@ pub enum ApiError {
     Image(#[from] ImageError),
     #[error(transparent)]
     Drafts(#[from] DraftsServiceError),
+    #[error(transparent)]
+    PromptSnippet(#[from] PromptSnippetError),
     #[error("Multipart error: {0}")]
     Multipart(#[from] MultipartError),
     #[error("IO error: {0}")]
@@ -65,6 +72,86 @@ impl From<Git2Error> for ApiError {
     }
 }
 
+impl From<FileReviewError> for ApiError {
+    fn from(err: FileReviewError) -> Self {
+        match err {
+            FileReviewError::Database(e) => ApiError::Database(e),
+            FileReviewError::GitService(e) => ApiError::GitService(e),
+        }
+    }
+}
+
+impl ApiError {
+    /// The stable code clients can branch on, independent of the display message.
+    pub fn error_code(&self) -> ApiErrorCode {
+        use services::services::git::GitServiceError;
+
+        match self {
+            ApiError::ExecutionProcess(ExecutionProcessError::ExecutionProcessNotFound) => {
+                ApiErrorCode::NotFound
+            }
+            ApiError::GitService(git_err) => match git_err {
+                GitServiceError::MergeConflicts(_) => ApiErrorCode::MergeConflict,
+                GitServiceError::RebaseInProgress => ApiErrorCode::RebaseInProgress,
+                GitServiceError::UnresolvedConflictMarkers(_) => {
+                    ApiErrorCode::UnresolvedConflictMarkers
+                }
+                GitServiceError::WorktreeDirty(_, _) => ApiErrorCode::WorktreeDirty,
+                GitServiceError::BranchesDiverged(_) => ApiErrorCode::BranchesDiverged,
+                GitServiceError::BranchNotFound(_) => ApiErrorCode::BranchNotFound,
+                GitServiceError::RemoteNotFound(_) => ApiErrorCode::RemoteNotFound,
+                GitServiceError::TokenUnavailable => ApiErrorCode::TokenUnavailable,
+                GitServiceError::FileNotBlameable(_, _)
+                | GitServiceError::InvalidCommitSelection(_)
+                | GitServiceError::FileNotConflicted(_) => ApiErrorCode::ValidationFailed,
+                _ => ApiErrorCode::InternalError,
+            },
+            ApiError::GitHubService(gh_err) => match gh_err {
+                GitHubServiceError::TokenInvalid => ApiErrorCode::TokenInvalid,
+                GitHubServiceError::InsufficientPermissions => {
+                    ApiErrorCode::InsufficientPermissions
+                }
+                GitHubServiceError::RepoNotFoundOrNoAccess => ApiErrorCode::RepoNotFoundOrNoAccess,
+                GitHubServiceError::RateLimited => ApiErrorCode::RateLimited,
+                _ => ApiErrorCode::InternalError,
+            },
+            ApiError::GitHubWebhook(webhook_err) => match webhook_err {
+                GitHubWebhookError::NotConfigured => ApiErrorCode::WebhookNotConfigured,
+                GitHubWebhookError::InvalidSignature => ApiErrorCode::InvalidSignature,
+                GitHubWebhookError::InvalidPayload(_) => ApiErrorCode::ValidationFailed,
+                _ => ApiErrorCode::InternalError,
+            },
+            ApiError::Auth(auth_err) => match auth_err {
+                AuthError::DeviceFlowNotStarted => ApiErrorCode::NoSession,
+                AuthError::Pending(_) => ApiErrorCode::AuthPending,
+                _ => ApiErrorCode::InternalError,
+            },
+            ApiError::Image(img_err) => match img_err {
+                ImageError::InvalidFormat => ApiErrorCode::InvalidImageFormat,
+                ImageError::TooLarge(_, _) => ApiErrorCode::ImageTooLarge,
+                ImageError::NotFound => ApiErrorCode::NotFound,
+                ImageError::MissingReferences(_) => ApiErrorCode::ValidationFailed,
+                _ => ApiErrorCode::InternalError,
+            },
+            ApiError::Drafts(DraftsServiceError::Conflict(_)) | ApiError::Conflict(_) => {
+                ApiErrorCode::Conflict
+            }
+            ApiError::Drafts(DraftsServiceError::Image(ImageError::MissingReferences(_))) => {
+                ApiErrorCode::ValidationFailed
+            }
+            ApiError::Drafts(DraftsServiceError::PromptSnippet(
+                PromptSnippetError::UnknownSnippets(_),
+            )) => ApiErrorCode::ValidationFailed,
+            ApiError::PromptSnippet(PromptSnippetError::UnknownSnippets(_)) => {
+                ApiErrorCode::ValidationFailed
+            }
+            ApiError::Multipart(_) => ApiErrorCode::UploadFailed,
+            ApiError::Validation(_) => ApiErrorCode::ValidationFailed,
+            _ => ApiErrorCode::InternalError,
+        }
+    }
+}
+
 impl IntoResponse for ApiError {
     fn into_response(self) -> Response {
         let (status_code, error_type) = match &self {
@@ -81,12 +168,32 @@ impl IntoResponse for ApiError {
                 services::services::git::GitServiceError::MergeConflicts(_) => {
                     (StatusCode::CONFLICT, "GitServiceError")
                 }
-                services::services::git::GitServiceError::RebaseInProgress => {
+                services::services::git::GitServiceError::RebaseInProgress
+                | services::services::git::GitServiceError::UnresolvedConflictMarkers(_) => {
                     (StatusCode::CONFLICT, "GitServiceError")
                 }
+                services::services::git::GitServiceError::FileNotBlameable(_, _)
+                | services::services::git::GitServiceError::InvalidCommitSelection(_)
+                | services::services::git::GitServiceError::FileNotConflicted(_)
+                | services::services::git::GitServiceError::RemoteNotFound(_) => {
+                    (StatusCode::BAD_REQUEST, "GitServiceError")
+                }
                 _ => (StatusCode::INTERNAL_SERVER_ERROR, "GitServiceError"),
             },
+            ApiError::GitHubService(GitHubServiceError::RateLimited) => {
+                (StatusCode::TOO_MANY_REQUESTS, "GitHubServiceError")
+            }
             ApiError::GitHubService(_) => (StatusCode::INTERNAL_SERVER_ERROR, "GitHubServiceError"),
+            ApiError::GitHubWebhook(webhook_err) => match webhook_err {
+                GitHubWebhookError::NotConfigured => (StatusCode::NOT_FOUND, "GitHubWebhookError"),
+                GitHubWebhookError::InvalidSignature => {
+                    (StatusCode::UNAUTHORIZED, "GitHubWebhookError")
+                }
+                GitHubWebhookError::InvalidPayload(_) => {
+                    (StatusCode::BAD_REQUEST, "GitHubWebhookError")
+                }
+                _ => (StatusCode::INTERNAL_SERVER_ERROR, "GitHubWebhookError"),
+            },
             ApiError::Auth(_) => (StatusCode::INTERNAL_SERVER_ERROR, "AuthError"),
             ApiError::Deployment(_) => (StatusCode::INTERNAL_SERVER_ERROR, "DeploymentError"),
             ApiError::Container(_) => (StatusCode::INTERNAL_SERVER_ERROR, "ContainerError"),
@@ -98,6 +205,7 @@ impl IntoResponse for ApiError {
                 ImageError::InvalidFormat => (StatusCode::BAD_REQUEST, "InvalidImageFormat"),
                 ImageError::TooLarge(_, _) => (StatusCode::PAYLOAD_TOO_LARGE, "ImageTooLarge"),
                 ImageError::NotFound => (StatusCode::NOT_FOUND, "ImageNotFound"),
+                ImageError::MissingReferences(_) => (StatusCode::BAD_REQUEST, "MissingImages"),
                 _ => (StatusCode::INTERNAL_SERVER_ERROR, "ImageError"),
             },
             ApiError::Drafts(drafts_err) => match drafts_err {
@@ -108,11 +216,24 @@ impl IntoResponse for ApiError {
                 DraftsServiceError::Container(_) => {
                     (StatusCode::INTERNAL_SERVER_ERROR, "ContainerError")
                 }
+                DraftsServiceError::Image(ImageError::MissingReferences(_)) => {
+                    (StatusCode::BAD_REQUEST, "MissingImages")
+                }
                 DraftsServiceError::Image(_) => (StatusCode::INTERNAL_SERVER_ERROR, "ImageError"),
                 DraftsServiceError::ExecutionProcess(_) => {
                     (StatusCode::INTERNAL_SERVER_ERROR, "ExecutionProcessError")
                 }
+                DraftsServiceError::PromptSnippet(PromptSnippetError::UnknownSnippets(_)) => {
+                    (StatusCode::BAD_REQUEST, "UnknownPromptSnippets")
+                }
+                DraftsServiceError::PromptSnippet(_) => {
+                    (StatusCode::INTERNAL_SERVER_ERROR, "PromptSnippetError")
+                }
             },
+            ApiError::PromptSnippet(PromptSnippetError::UnknownSnippets(_)) => {
+                (StatusCode::BAD_REQUEST, "UnknownPromptSnippets")
+            }
+            ApiError::PromptSnippet(_) => (StatusCode::INTERNAL_SERVER_ERROR, "PromptSnippetError"),
             ApiError::Io(_) => (StatusCode::INTERNAL_SERVER_ERROR, "IoError"),
             ApiError::Multipart(_) => (StatusCode::BAD_REQUEST, "MultipartError"),
             ApiError::Conflict(_) => (StatusCode::CONFLICT, "ConflictError"),
@@ -128,6 +249,7 @@ impl IntoResponse for ApiError {
                     *max as f64 / 1_048_576.0
                 ),
                 ImageError::NotFound => "Image not found.".to_string(),
+                ImageError::MissingReferences(_) => img_err.to_string(),
                 _ => {
                     "Failed to process image. Please try again.".to_string()
                 }
@@ -137,8 +259,16 @@ impl IntoResponse for ApiError {
                 services::services::git::GitServiceError::RebaseInProgress => {
                     "A rebase is already in progress. Resolve conflicts or abort the rebase, then retry.".to_string()
                 }
+                services::services::git::GitServiceError::FileNotBlameable(_, _)
+                | services::services::git::GitServiceError::InvalidCommitSelection(_)
+                | services::services::git::GitServiceError::FileNotConflicted(_)
+                | services::services::git::GitServiceError::UnresolvedConflictMarkers(_)
+                | services::services::git::GitServiceError::RemoteNotFound(_) => {
+                    git_err.to_string()
+                }
                 _ => format!("{}: {}", error_type, self),
             },
+            ApiError::PromptSnippet(err @ PromptSnippetError::UnknownSnippets(_)) => err.to_string(),
             ApiError::Multipart(_) => "Failed to upload file. Please ensure the file is valid and try again.".to_string(),
             ApiError::Conflict(msg) => msg.clone(),
             ApiError::Validation(msg) => msg.clone(),
@@ -146,14 +276,26 @@ impl IntoResponse for ApiError {
                 DraftsServiceError::Conflict(msg) => msg.clone(),
                 DraftsServiceError::Database(_) => format!("{}: {}", error_type, drafts_err),
                 DraftsServiceError::Container(_) => format!("{}: {}", error_type, drafts_err),
+                DraftsServiceError::Image(ImageError::MissingReferences(_)) => {
+                    drafts_err.to_string()
+                }
                 DraftsServiceError::Image(_) => format!("{}: {}", error_type, drafts_err),
                 DraftsServiceError::ExecutionProcess(_) => {
                     format!("{}: {}", error_type, drafts_err)
                 }
+                DraftsServiceError::PromptSnippet(PromptSnippetError::UnknownSnippets(_)) => {
+                    drafts_err.to_string()
+                }
+                DraftsServiceError::PromptSnippet(_) => format!("{}: {}", error_type, drafts_err),
             },
             _ => format!("{}: {}", error_type, self),
         };
-        let response = ApiResponse::<()>::error(&error_message);
+        if status_code.is_server_error() {
+            tracing::error!("{}", error_message);
+        }
+        // Errors carry the request id so a report can be matched to the logs
+        let response = ApiResponse::<()>::error_with_code(&error_message, self.error_code())
+            .with_request_id(current_request_id());
         (status_code, Json(response)).into_response()
     }
 }
@@ -1,6 +1,9 @@
+use std::time::Duration;
+
 use anyhow::{self, Error as AnyhowError};
 use deployment::{Deployment, DeploymentError};
 use server::{DeploymentImpl, routes};
+use services::services::container::ContainerService;
 use sqlx::Error as SqlxError;
 use strip_ansi_escapes::strip;
 use thiserror::Error;
@@ -34,6 +37,8 @@ async fn main() -> Result<(), VibeKanbanError> {
         level = log_level
     );
     let env_filter = EnvFilter::try_new(filter_string).expect("Failed to create tracing filter");
+    // The fmt layer prints span fields, so lines logged while handling an API request are
+    // prefixed with its `request{request_id=..}` span (see `middleware::request_id`)
     tracing_subscriber::registry()
         .with(tracing_subscriber::fmt::layer().with_filter(env_filter))
         .with(sentry_layer())
@@ -46,9 +51,19 @@ async fn main() -> Result<(), VibeKanbanError> {
 
     let deployment = DeploymentImpl::new().await?;
     deployment.update_sentry_scope().await?;
+    // Mark dev servers left over from a previous run killed before their rows are marked
+    // failed; their processes are killed in the background
+    match deployment.container().stop_dev_servers_except(&[]).await {
+        Ok(stopped) if !stopped.is_empty() => {
+            tracing::info!("Stopped {} orphaned dev server(s)", stopped.len());
+        }
+        Ok(_) => {}
+        Err(e) => tracing::warn!("Failed to stop orphaned dev servers: {}", e),
+    }
     deployment.cleanup_orphan_executions().await?;
     deployment.backfill_before_head_commits().await?;
     deployment.spawn_pr_monitor_service().await;
+    deployment.spawn_attempt_queue_scheduler().await;
     deployment
         .track_if_analytics_allowed("session_start", serde_json::json!({}))
         .await;
@@ -65,7 +80,7 @@ async fn main() -> Result<(), VibeKanbanError> {
         }
     });
 
-    let app_router = routes::router(deployment);
+    let app_router = routes::router(deployment.clone());
 
     let port = std::env::var("BACKEND_PORT")
         .or_else(|_| std::env::var("PORT"))
@@ -105,6 +120,57 @@ async fn main() -> Result<(), VibeKanbanError> {
         });
     }
 
-    axum::serve(listener, app_router).await?;
+    // Stop accepting connections on shutdown without waiting for open event streams to close
+    tokio::select! {
+        result = axum::serve(listener, app_router) => result?,
+        _ = shutdown_signal() => {}
+    }
+
+    let grace_period = shutdown_grace_period();
+    tracing::info!(
+        "Shutting down, waiting up to {:?} for running executions",
+        grace_period
+    );
+    deployment.container().shutdown(grace_period).await;
     Ok(())
 }
+
+/// How long running executions get to finish on their own at shutdown before they are
+/// checkpointed and killed. Set with `SHUTDOWN_GRACE_PERIOD_SECS`, defaults to 5 seconds.
+fn shutdown_grace_period() -> Duration {
+    std::env::var("SHUTDOWN_GRACE_PERIOD_SECS")
+        .ok()
+        .and_then(|s| s.trim().parse::<u64>().ok())
+        .map(Duration::from_secs)
+        .unwrap_or(Duration::from_secs(5))
+}
+
+/// Resolves on Ctrl-C, or SIGTERM on unix
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            tracing::error!("Failed to listen for Ctrl-C: {}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(e) => {
+                tracing::error!("Failed to listen for SIGTERM: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
+}
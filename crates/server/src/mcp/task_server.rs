@@ -1,6 +1,7 @@
 use std::{future::Future, path::PathBuf, str::FromStr};
 
 use db::models::{
+    attempt_kv::AttemptKvEntry,
     project::Project,
     task::{CreateTask, Task, TaskStatus, TaskWithAttemptStatus, UpdateTask},
     task_attempt::TaskAttempt,
@@ -16,9 +17,10 @@ use rmcp::{
 };
 use serde::{Deserialize, Serialize, de::DeserializeOwned};
 use serde_json;
+use utils::pagination::{MAX_PAGE_SIZE, Page};
 use uuid::Uuid;
 
-use crate::routes::task_attempts::CreateTaskAttemptBody;
+use crate::routes::task_attempts::{CreateTaskAttemptBody, kv::SetKvEntryRequest};
 
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
 pub struct CreateTaskRequest {
@@ -208,8 +210,10 @@ pub struct StartTaskAttemptRequest {
     pub executor: String,
     #[schemars(description = "Optional executor variant, if needed")]
     pub variant: Option<String>,
-    #[schemars(description = "The base branch to use for the attempt")]
-    pub base_branch: String,
+    #[schemars(
+        description = "The base branch to use for the attempt. Defaults to the project's default base branch"
+    )]
+    pub base_branch: Option<String>,
 }
 
 #[derive(Debug, Serialize, schemars::JsonSchema)]
@@ -234,6 +238,59 @@ pub struct GetTaskResponse {
     pub task: TaskDetails,
 }
 
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct GetAttemptStateRequest {
+    #[schemars(description = "The ID of the task attempt whose state to read")]
+    pub attempt_id: Uuid,
+    #[schemars(description = "Key to read. Omit to list every key stored for the attempt")]
+    pub key: Option<String>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct SetAttemptStateRequest {
+    #[schemars(description = "The ID of the task attempt to store the value for")]
+    pub attempt_id: Uuid,
+    #[schemars(description = "Key to store the value under (up to 128 characters)")]
+    pub key: String,
+    #[schemars(description = "Any JSON value, up to 16 KiB once serialized")]
+    pub value: serde_json::Value,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct DeleteAttemptStateRequest {
+    #[schemars(description = "The ID of the task attempt to remove the key from")]
+    pub attempt_id: Uuid,
+    #[schemars(description = "Key to remove")]
+    pub key: String,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct AttemptStateEntry {
+    pub key: String,
+    pub value: serde_json::Value,
+}
+
+impl From<AttemptKvEntry> for AttemptStateEntry {
+    fn from(entry: AttemptKvEntry) -> Self {
+        Self {
+            key: entry.key,
+            value: entry.value.0,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct GetAttemptStateResponse {
+    pub entries: Vec<AttemptStateEntry>,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct DeleteAttemptStateResponse {
+    pub key: String,
+    #[schemars(description = "False when the key wasn't set")]
+    pub deleted: bool,
+}
+
 #[derive(Debug, Clone)]
 pub struct TaskServer {
     client: reqwest::Client,
@@ -401,22 +458,30 @@ impl TaskServer {
             None
         };
 
-        let url = self.url(&format!("/api/tasks?project_id={}", project_id));
-        let all_tasks: Vec<TaskWithAttemptStatus> =
-            match self.send_json(self.client.get(&url)).await {
-                Ok(t) => t,
-                Err(e) => return Ok(e),
-            };
-
+        // Follow the listing's pages until enough tasks match the status filter
         let task_limit = limit.unwrap_or(50).max(0) as usize;
-        let filtered = all_tasks.into_iter().filter(|t| {
-            if let Some(ref want) = status_filter {
-                &t.status == want
-            } else {
-                true
-            }
-        });
-        let limited: Vec<TaskWithAttemptStatus> = filtered.take(task_limit).collect();
+        let mut limited: Vec<TaskWithAttemptStatus> = Vec::new();
+        let mut offset = Some(0);
+        while let Some(page_offset) = offset
+            && limited.len() < task_limit
+        {
+            let url = self.url(&format!(
+                "/api/tasks?project_id={}&limit={}&offset={}",
+                project_id, MAX_PAGE_SIZE, page_offset
+            ));
+            let page: Page<TaskWithAttemptStatus> =
+                match self.send_json(self.client.get(&url)).await {
+                    Ok(page) => page,
+                    Err(e) => return Ok(e),
+                };
+            offset = page.next_offset;
+            limited.extend(
+                page.items
+                    .into_iter()
+                    .filter(|t| status_filter.as_ref().is_none_or(|want| &t.status == want))
+                    .take(task_limit - limited.len()),
+            );
+        }
 
         let task_summaries: Vec<TaskSummary> = limited
             .into_iter()
@@ -446,10 +511,9 @@ impl TaskServer {
             base_branch,
         }): Parameters<StartTaskAttemptRequest>,
     ) -> Result<CallToolResult, ErrorData> {
-        let base_branch = base_branch.trim().to_string();
-        if base_branch.is_empty() {
-            return Self::err("Base branch must not be empty.".to_string(), None::<String>);
-        }
+        let base_branch = base_branch
+            .map(|b| b.trim().to_string())
+            .filter(|b| !b.is_empty());
 
         let executor_trimmed = executor.trim();
         if executor_trimmed.is_empty() {
@@ -485,6 +549,7 @@ impl TaskServer {
             task_id,
             executor_profile_id,
             base_branch,
+            force_setup: None,
         };
 
         let url = self.url("/api/task-attempts");
@@ -589,6 +654,73 @@ impl TaskServer {
 
         TaskServer::success(&response)
     }
+
+    #[tool(
+        description = "Read scratch state stored for a task attempt: small JSON values such as counters or flags that persist across turns. Pass `key` to read one value, or omit it to list them all. `attempt_id` is required!"
+    )]
+    async fn get_attempt_state(
+        &self,
+        Parameters(GetAttemptStateRequest { attempt_id, key }): Parameters<GetAttemptStateRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let url = self.url(&format!("/api/task-attempts/{}/kv", attempt_id));
+        let mut request = self.client.get(&url);
+        if let Some(key) = &key {
+            request = request.query(&[("key", key)]);
+        }
+        let entries: Vec<AttemptKvEntry> = match self.send_json(request).await {
+            Ok(entries) => entries,
+            Err(e) => return Ok(e),
+        };
+
+        let response = GetAttemptStateResponse {
+            entries: entries.into_iter().map(AttemptStateEntry::from).collect(),
+        };
+        TaskServer::success(&response)
+    }
+
+    #[tool(
+        description = "Store a JSON value under a key in a task attempt's scratch state, replacing any previous value. Use it for small state that must survive across turns and doesn't belong in a file; an attempt holds at most 256 keys. `attempt_id`, `key` and `value` are required!"
+    )]
+    async fn set_attempt_state(
+        &self,
+        Parameters(SetAttemptStateRequest {
+            attempt_id,
+            key,
+            value,
+        }): Parameters<SetAttemptStateRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let payload = SetKvEntryRequest { key, value };
+        let url = self.url(&format!("/api/task-attempts/{}/kv", attempt_id));
+        let entry: AttemptKvEntry = match self.send_json(self.client.put(&url).json(&payload)).await
+        {
+            Ok(entry) => entry,
+            Err(e) => return Ok(e),
+        };
+
+        TaskServer::success(&AttemptStateEntry::from(entry))
+    }
+
+    #[tool(
+        description = "Remove a key from a task attempt's scratch state. `attempt_id` and `key` are required!"
+    )]
+    async fn delete_attempt_state(
+        &self,
+        Parameters(DeleteAttemptStateRequest { attempt_id, key }): Parameters<
+            DeleteAttemptStateRequest,
+        >,
+    ) -> Result<CallToolResult, ErrorData> {
+        let url = self.url(&format!("/api/task-attempts/{}/kv", attempt_id));
+        let deleted: bool = match self
+            .send_json(self.client.delete(&url).query(&[("key", &key)]))
+            .await
+        {
+            Ok(deleted) => deleted,
+            Err(e) => return Ok(e),
+        };
+
+        let response = DeleteAttemptStateResponse { key, deleted };
+        TaskServer::success(&response)
+    }
 }
 
 #[tool_handler]
@@ -603,7 +735,7 @@ impl ServerHandler for TaskServer {
                 name: "vibe-kanban".to_string(),
                 version: "1.0.0".to_string(),
             },
-            instructions: Some("A task and project management server. If you need to create or update tickets or tasks then use these tools. Most of them absolutely require that you pass the `project_id` of the project that you are currently working on. This should be provided to you. Call `list_tasks` to fetch the `task_ids` of all the tasks in a project`. TOOLS: 'list_projects', 'list_tasks', 'create_task', 'start_task_attempt', 'get_task', 'update_task', 'delete_task'. Make sure to pass `project_id` or `task_id` where required. You can use list tools to get the available ids.".to_string()),
+            instructions: Some("A task and project management server. If you need to create or update tickets or tasks then use these tools. Most of them absolutely require that you pass the `project_id` of the project that you are currently working on. This should be provided to you. Call `list_tasks` to fetch the `task_ids` of all the tasks in a project`. TOOLS: 'list_projects', 'list_tasks', 'create_task', 'start_task_attempt', 'get_task', 'update_task', 'delete_task', 'get_attempt_state', 'set_attempt_state', 'delete_attempt_state'. Make sure to pass `project_id` or `task_id` where required. You can use list tools to get the available ids.".to_string()),
         }
     }
 }
@@ -1,3 +1,5 @@
 pub mod model_loaders;
+pub mod request_id;
 
 pub use model_loaders::*;
+pub use request_id::*;
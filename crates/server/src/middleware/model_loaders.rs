@@ -5,8 +5,9 @@ use axum::{
     response::Response,
 };
 use db::models::{
-    agent::Agent, execution_process::ExecutionProcess, project::Project, tag::Tag, task::Task,
-    task_attempt::TaskAttempt, workflow::Workflow, workflow_station::WorkflowStation,
+    agent::Agent, execution_process::ExecutionProcess, label::Label, project::Project,
+    prompt_snippet::PromptSnippet, tag::Tag, task::Task, task_attempt::TaskAttempt,
+    workflow::Workflow, workflow_station::WorkflowStation,
     station_transition::StationTransition,
 };
 use deployment::Deployment;
@@ -205,6 +206,60 @@ pub async fn load_tag_middleware(
     Ok(next.run(request).await)
 }
 
+pub async fn load_label_middleware(
+    State(deployment): State<DeploymentImpl>,
+    Path(label_id): Path<Uuid>,
+    request: axum::extract::Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    // Load the label from the database
+    let label = match Label::find_by_id(&deployment.db().pool, label_id).await {
+        Ok(Some(label)) => label,
+        Ok(None) => {
+            tracing::warn!("Label {} not found", label_id);
+            return Err(StatusCode::NOT_FOUND);
+        }
+        Err(e) => {
+            tracing::error!("Failed to fetch label {}: {}", label_id, e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+
+    // Insert the label as an extension
+    let mut request = request;
+    request.extensions_mut().insert(label);
+
+    // Continue with the next middleware/handler
+    Ok(next.run(request).await)
+}
+
+pub async fn load_prompt_snippet_middleware(
+    State(deployment): State<DeploymentImpl>,
+    Path(snippet_id): Path<Uuid>,
+    request: axum::extract::Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    // Load the prompt snippet from the database
+    let snippet = match PromptSnippet::find_by_id(&deployment.db().pool, snippet_id).await {
+        Ok(Some(snippet)) => snippet,
+        Ok(None) => {
+            tracing::warn!("PromptSnippet {} not found", snippet_id);
+            return Err(StatusCode::NOT_FOUND);
+        }
+        Err(e) => {
+            tracing::error!("Failed to fetch prompt snippet {}: {}", snippet_id, e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+
+    // Insert the prompt snippet as an extension
+    let mut request = request;
+    request.extensions_mut().insert(snippet);
+
+    // Continue with the next middleware/handler
+    Ok(next.run(request).await)
+}
+
 pub async fn load_agent_middleware(
     State(deployment): State<DeploymentImpl>,
     Path(agent_id): Path<Uuid>,
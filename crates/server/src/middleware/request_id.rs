@@ -0,0 +1,89 @@
+use axum::{
+    extract::Request,
+    http::{HeaderName, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use tracing::Instrument;
+use uuid::Uuid;
+
+pub static REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+
+/// Inbound ids longer than this are replaced rather than echoed into logs
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+tokio::task_local! {
+    static REQUEST_ID: String;
+}
+
+/// The id of the request currently being handled, if called from within one.
+pub fn current_request_id() -> Option<String> {
+    REQUEST_ID.try_with(|id| id.clone()).ok()
+}
+
+/// Tag every request with an id, reusing a well-formed inbound `X-Request-Id` header.
+///
+/// The id is recorded on a `request` span so every log line emitted while handling the
+/// request carries it, is available to error responses via [`current_request_id`], and is
+/// echoed back in the `X-Request-Id` response header.
+pub async fn request_id_middleware(request: Request, next: Next) -> Response {
+    let request_id = request
+        .headers()
+        .get(&REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(str::trim)
+        .filter(|id| is_valid_request_id(id))
+        .map(str::to_string)
+        .unwrap_or_else(|| Uuid::new_v4().to_string());
+
+    let span = tracing::info_span!(
+        "request",
+        request_id = %request_id,
+        method = %request.method(),
+        path = %request.uri().path(),
+    );
+
+    let mut response = REQUEST_ID
+        .scope(request_id.clone(), next.run(request))
+        .instrument(span)
+        .await;
+
+    if let Ok(value) = HeaderValue::from_str(&request_id) {
+        response
+            .headers_mut()
+            .insert(REQUEST_ID_HEADER.clone(), value);
+    }
+    response
+}
+
+fn is_valid_request_id(id: &str) -> bool {
+    !id.is_empty()
+        && id.len() <= MAX_REQUEST_ID_LEN
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | ':'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_inbound_request_ids() {
+        assert!(is_valid_request_id("3f1c2a9e-7b1d-4c55-9a0e-1d2b3c4d5e6f"));
+        assert!(is_valid_request_id("req_01HZX.trace:1"));
+        assert!(!is_valid_request_id(""));
+        assert!(!is_valid_request_id("has spaces"));
+        assert!(!is_valid_request_id("line\nbreak"));
+        assert!(!is_valid_request_id(&"a".repeat(MAX_REQUEST_ID_LEN + 1)));
+    }
+
+    #[tokio::test]
+    async fn request_id_is_scoped_to_the_request() {
+        assert_eq!(current_request_id(), None);
+        let id = REQUEST_ID
+            .scope("abc".to_string(), async { current_request_id() })
+            .await;
+        assert_eq!(id.as_deref(), Some("abc"));
+    }
+}
@@ -7,11 +7,22 @@ use axum::{
 };
 use db::models::agent::{Agent, CreateAgent, UpdateAgent};
 use deployment::Deployment;
+use services::services::workflow_orchestrator::WorkflowOrchestrator;
 use utils::response::ApiResponse;
 use uuid::Uuid;
 
 use crate::{DeploymentImpl, error::ApiError, middleware::load_agent_middleware};
 
+/// Reject an executor that stations could not run the agent with
+fn validate_executor(executor: &str) -> Result<(), ApiError> {
+    if WorkflowOrchestrator::parse_executor_profile(executor).is_err() {
+        return Err(ApiError::Validation(format!(
+            "Unknown executor '{executor}'; use a coding agent name such as CLAUDE_CODE"
+        )));
+    }
+    Ok(())
+}
+
 pub async fn get_agents(
     State(deployment): State<DeploymentImpl>,
 ) -> Result<ResponseJson<ApiResponse<Vec<Agent>>>, ApiError> {
@@ -29,6 +40,10 @@ pub async fn create_agent(
     State(deployment): State<DeploymentImpl>,
     Json(payload): Json<CreateAgent>,
 ) -> Result<ResponseJson<ApiResponse<Agent>>, ApiError> {
+    if let Some(executor) = &payload.executor {
+        validate_executor(executor)?;
+    }
+
     let agent_id = Uuid::new_v4();
     let agent = Agent::create(&deployment.db().pool, payload, agent_id).await?;
 
@@ -51,6 +66,10 @@ pub async fn update_agent(
     State(deployment): State<DeploymentImpl>,
     Json(payload): Json<UpdateAgent>,
 ) -> Result<ResponseJson<ApiResponse<Agent>>, ApiError> {
+    if let Some(executor) = &payload.executor {
+        validate_executor(executor)?;
+    }
+
     let updated_agent = Agent::update(&deployment.db().pool, agent.id, payload).await?;
 
     deployment
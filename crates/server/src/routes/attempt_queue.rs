@@ -0,0 +1,110 @@
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+    routing::{delete, get, post},
+};
+use db::models::attempt_queue::{CreateQueuedAttempt, QueuedAttempt};
+use deployment::Deployment;
+use serde::Deserialize;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    routes::task_attempts::{CreateTaskAttemptBody, validate_new_attempt},
+};
+
+#[derive(Debug, Deserialize, TS)]
+pub struct MoveQueuedAttemptRequest {
+    /// New place in the queue; 0 starts next. Values past the end move the entry last.
+    pub position: usize,
+}
+
+/// Publish the queue after a change and give the scheduler a chance to fill free slots
+/// without waiting for its next tick
+async fn queue_changed(deployment: &DeploymentImpl) {
+    if let Err(e) = deployment.events().push_attempt_queue_update().await {
+        tracing::error!("Failed to push attempt queue update: {}", e);
+    }
+    let deployment = deployment.clone();
+    tokio::spawn(async move {
+        if let Err(e) = deployment.start_queued_attempts().await {
+            tracing::error!("Failed to start queued attempts: {}", e);
+        }
+    });
+}
+
+pub async fn get_attempt_queue(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<QueuedAttempt>>>, ApiError> {
+    let queue = QueuedAttempt::find_all(&deployment.db().pool).await?;
+    Ok(ResponseJson(ApiResponse::success(queue)))
+}
+
+/// Queue an attempt to start once `max_concurrent_attempts` leaves room. The task attempt is
+/// only created when it starts.
+pub async fn enqueue_attempt(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateTaskAttemptBody>,
+) -> Result<ResponseJson<ApiResponse<QueuedAttempt>>, ApiError> {
+    let (task, base_branch) = validate_new_attempt(&deployment, &payload).await?;
+
+    let queued = QueuedAttempt::create(
+        &deployment.db().pool,
+        &CreateQueuedAttempt {
+            task_id: task.id,
+            executor_profile_id: payload.get_executor_profile_id(),
+            base_branch,
+            force_setup: payload.force_setup.unwrap_or(false),
+        },
+        Uuid::new_v4(),
+    )
+    .await?;
+
+    queue_changed(&deployment).await;
+    Ok(ResponseJson(ApiResponse::success(queued)))
+}
+
+/// Cancel a queued attempt before it starts
+pub async fn dequeue_attempt(
+    State(deployment): State<DeploymentImpl>,
+    Path(queued_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    if QueuedAttempt::delete(&deployment.db().pool, queued_id).await? == 0 {
+        return Err(ApiError::Conflict(
+            "This attempt is no longer queued; it may have already started".to_string(),
+        ));
+    }
+
+    queue_changed(&deployment).await;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+pub async fn move_queued_attempt(
+    State(deployment): State<DeploymentImpl>,
+    Path(queued_id): Path<Uuid>,
+    Json(payload): Json<MoveQueuedAttemptRequest>,
+) -> Result<ResponseJson<ApiResponse<Vec<QueuedAttempt>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    if !QueuedAttempt::move_to(pool, queued_id, payload.position).await? {
+        return Err(ApiError::Conflict(
+            "This attempt is no longer queued; it may have already started".to_string(),
+        ));
+    }
+
+    queue_changed(&deployment).await;
+    let queue = QueuedAttempt::find_all(pool).await?;
+    Ok(ResponseJson(ApiResponse::success(queue)))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    let inner = Router::new()
+        .route("/", get(get_attempt_queue).post(enqueue_attempt))
+        .route("/{queued_id}", delete(dequeue_attempt))
+        .route("/{queued_id}/move", post(move_queued_attempt));
+
+    Router::new().nest("/attempt-queue", inner)
+}
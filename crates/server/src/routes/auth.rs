@@ -12,7 +12,7 @@ use serde::{Deserialize, Serialize};
 use services::services::{
     auth::{AuthError, DeviceFlowStartResponse},
     config::save_config_to_file,
-    github_service::{GitHubService, GitHubServiceError},
+    github_service::GitHubServiceError,
 };
 use utils::response::ApiResponse;
 
@@ -82,6 +82,7 @@ async fn device_poll(
         config.github_login_acknowledged = true; // Also acknowledge the GitHub login step
         save_config_to_file(&config.clone(), &config_path).await?;
     }
+    deployment.github_cache().invalidate_tokens();
     let _ = deployment.update_sentry_scope().await;
     let props = serde_json::json!({
         "username": user_info.username,
@@ -105,8 +106,7 @@ async fn github_check_token(
             CheckTokenResponse::Invalid,
         )));
     };
-    let gh = GitHubService::new(&token)?;
-    match gh.check_token().await {
+    match deployment.github_cache().verify_token(&token, false).await {
         Ok(()) => Ok(ResponseJson(ApiResponse::success(
             CheckTokenResponse::Valid,
         ))),
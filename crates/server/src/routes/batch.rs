@@ -0,0 +1,263 @@
+use std::time::Duration;
+
+use axum::{
+    Json, Router,
+    body::{Body, to_bytes},
+    http::{Method, Request, StatusCode, header},
+    response::Json as ResponseJson,
+};
+use serde::{Deserialize, Serialize};
+use tower::ServiceExt;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+
+use crate::error::ApiError;
+
+/// Most sub-requests accepted in one batch
+pub const MAX_BATCH_REQUESTS: usize = 25;
+/// Largest sub-response body returned, in bytes
+pub const MAX_SUB_RESPONSE_BYTES: usize = 4 * 1024 * 1024;
+/// Longest a single sub-request may take, including reading its response body
+pub const SUB_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Deserialize, TS)]
+pub struct BatchSubRequest {
+    /// HTTP method, e.g. `GET`
+    pub method: String,
+    /// Path and query relative to `/api`, e.g. `/tasks/<id>`. A leading `/api` is accepted.
+    pub path: String,
+    /// JSON request body, if the route takes one
+    #[serde(default)]
+    #[ts(optional)]
+    pub body: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct BatchSubResponse {
+    pub status: u16,
+    /// The route's JSON response, a string if it wasn't JSON, or `null` if it was empty
+    pub body: serde_json::Value,
+}
+
+impl BatchSubResponse {
+    fn error(status: StatusCode, message: &str) -> Self {
+        Self {
+            status: status.as_u16(),
+            body: serde_json::to_value(ApiResponse::<()>::error(message)).unwrap_or_default(),
+        }
+    }
+}
+
+/// `POST /api/batch`: run several API calls in one round-trip, e.g. loading a task, its
+/// attempts and their branch status when a page opens.
+///
+/// Each sub-request is dispatched through `api`, the same router that serves normal requests,
+/// so it gets the same handlers, middleware and responses. Sub-requests run one at a time in
+/// the order given and a failing one doesn't affect the others; each gets its own status and
+/// body. The endpoint is meant for reads: writes are allowed but nothing is rolled back when a
+/// later sub-request fails.
+///
+/// At most [`MAX_BATCH_REQUESTS`] sub-requests are accepted. A sub-response body larger than
+/// [`MAX_SUB_RESPONSE_BYTES`], or a sub-request taking longer than [`SUB_REQUEST_TIMEOUT`], is
+/// replaced by an error. Streaming routes (server-sent events and websockets) and nested
+/// batches are rejected, as is any other response sent as `text/event-stream`.
+pub async fn run_batch(
+    api: Router,
+    Json(requests): Json<Vec<BatchSubRequest>>,
+) -> Result<ResponseJson<ApiResponse<Vec<BatchSubResponse>>>, ApiError> {
+    if requests.len() > MAX_BATCH_REQUESTS {
+        return Err(ApiError::Validation(format!(
+            "A batch may contain at most {MAX_BATCH_REQUESTS} requests, got {}",
+            requests.len()
+        )));
+    }
+
+    let mut responses = Vec::with_capacity(requests.len());
+    for request in requests {
+        responses.push(dispatch(&api, request).await);
+    }
+    Ok(ResponseJson(ApiResponse::success(responses)))
+}
+
+async fn dispatch(api: &Router, request: BatchSubRequest) -> BatchSubResponse {
+    let Ok(method) = Method::from_bytes(request.method.to_ascii_uppercase().as_bytes()) else {
+        return BatchSubResponse::error(
+            StatusCode::BAD_REQUEST,
+            &format!("Invalid method '{}'", request.method),
+        );
+    };
+    let path = request.path.strip_prefix("/api").unwrap_or(&request.path);
+    if !path.starts_with('/') {
+        return BatchSubResponse::error(
+            StatusCode::BAD_REQUEST,
+            &format!("Path '{}' must start with '/'", request.path),
+        );
+    }
+    let route = path.split('?').next().unwrap_or_default();
+    if is_streaming_route(route) {
+        return BatchSubResponse::error(
+            StatusCode::BAD_REQUEST,
+            &format!("'{route}' cannot be called from a batch"),
+        );
+    }
+
+    let mut builder = Request::builder().method(method).uri(path);
+    let body = match request.body {
+        Some(body) => {
+            builder = builder.header(header::CONTENT_TYPE, "application/json");
+            Body::from(body.to_string())
+        }
+        None => Body::empty(),
+    };
+    let sub_request = match builder.body(body) {
+        Ok(sub_request) => sub_request,
+        Err(e) => return BatchSubResponse::error(StatusCode::BAD_REQUEST, &e.to_string()),
+    };
+
+    match tokio::time::timeout(SUB_REQUEST_TIMEOUT, call(api, route, sub_request)).await {
+        Ok(response) => response,
+        Err(_) => BatchSubResponse::error(
+            StatusCode::GATEWAY_TIMEOUT,
+            &format!(
+                "'{route}' took longer than {}s",
+                SUB_REQUEST_TIMEOUT.as_secs()
+            ),
+        ),
+    }
+}
+
+/// Whether `route` streams its response or is the batch endpoint itself. The server-sent
+/// events and websocket routes live under `/events`, end in `/ws` or have a `stream` segment.
+fn is_streaming_route(route: &str) -> bool {
+    let route = route.trim_end_matches('/');
+    route == "/batch"
+        || route == "/events"
+        || route.starts_with("/events/")
+        || route.ends_with("/ws")
+        || route.split('/').any(|segment| segment == "stream")
+}
+
+async fn call(api: &Router, route: &str, sub_request: Request<Body>) -> BatchSubResponse {
+    let response = match api.clone().oneshot(sub_request).await {
+        Ok(response) => response,
+        Err(infallible) => match infallible {},
+    };
+    let status = response.status();
+    let is_event_stream = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("text/event-stream"));
+    if is_event_stream {
+        return BatchSubResponse::error(
+            StatusCode::BAD_REQUEST,
+            &format!("'{route}' streams its response and cannot be called from a batch"),
+        );
+    }
+    let bytes = match to_bytes(response.into_body(), MAX_SUB_RESPONSE_BYTES).await {
+        Ok(bytes) => bytes,
+        Err(_) => {
+            return BatchSubResponse::error(
+                StatusCode::PAYLOAD_TOO_LARGE,
+                &format!("Response is larger than {MAX_SUB_RESPONSE_BYTES} bytes"),
+            );
+        }
+    };
+    let body = if bytes.is_empty() {
+        serde_json::Value::Null
+    } else {
+        serde_json::from_slice(&bytes).unwrap_or_else(|_| {
+            serde_json::Value::String(String::from_utf8_lossy(&bytes).into_owned())
+        })
+    };
+    BatchSubResponse {
+        status: status.as_u16(),
+        body,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{
+        response::sse::{Event, Sse},
+        routing::{get, post},
+    };
+    use futures_util::stream;
+
+    use super::*;
+
+    fn sub_request(method: &str, path: &str, body: Option<serde_json::Value>) -> BatchSubRequest {
+        BatchSubRequest {
+            method: method.to_string(),
+            path: path.to_string(),
+            body,
+        }
+    }
+
+    #[tokio::test]
+    async fn failing_sub_requests_do_not_fail_the_batch() {
+        let api = Router::new()
+            .route(
+                "/ok",
+                get(|| async { Json(serde_json::json!({ "ok": true })) }),
+            )
+            .route(
+                "/echo",
+                post(|Json(body): Json<serde_json::Value>| async move { Json(body) }),
+            );
+
+        let ResponseJson(response) = run_batch(
+            api,
+            Json(vec![
+                sub_request("get", "/api/ok", None),
+                sub_request("GET", "/missing", None),
+                sub_request("POST", "/echo", Some(serde_json::json!([1, 2]))),
+                sub_request("GET", "/events", None),
+            ]),
+        )
+        .await
+        .unwrap();
+
+        let responses = response.into_data().unwrap();
+        let statuses: Vec<u16> = responses.iter().map(|r| r.status).collect();
+        assert_eq!(statuses, vec![200, 404, 200, 400]);
+        assert_eq!(responses[0].body, serde_json::json!({ "ok": true }));
+        assert_eq!(responses[2].body, serde_json::json!([1, 2]));
+    }
+
+    #[test]
+    fn streaming_routes_are_recognized() {
+        for route in [
+            "/batch",
+            "/events",
+            "/events/",
+            "/tasks/stream/ws",
+            "/execution-processes/1/raw-logs/ws",
+            "/projects/1/search/stream",
+        ] {
+            assert!(is_streaming_route(route), "{route}");
+        }
+        for route in ["/tasks", "/eventsx", "/projects/1/search"] {
+            assert!(!is_streaming_route(route), "{route}");
+        }
+    }
+
+    #[tokio::test]
+    async fn event_stream_responses_are_rejected() {
+        let api = Router::new().route(
+            "/feed",
+            get(|| async {
+                Sse::new(stream::iter(vec![Ok::<_, std::convert::Infallible>(
+                    Event::default().data("hi"),
+                )]))
+            }),
+        );
+
+        let ResponseJson(response) = run_batch(api, Json(vec![sub_request("GET", "/feed", None)]))
+            .await
+            .unwrap();
+
+        let responses = response.into_data().unwrap();
+        assert_eq!(responses[0].status, 400);
+    }
+}
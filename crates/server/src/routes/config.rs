@@ -10,13 +10,18 @@ use axum::{
 };
 use deployment::{Deployment, DeploymentError};
 use executors::{
+    command::{set_user_extra_args, validate_extra_arg},
     executors::{BaseAgentCapability, BaseCodingAgent, StandardCodingAgentExecutor},
+    logs::utils::redact::{set_user_redaction_patterns, validate_redaction_pattern},
     mcp_config::{McpConfig, read_agent_config, write_agent_config},
     profile::{ExecutorConfigs, ExecutorProfileId},
 };
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use services::services::config::{Config, ConfigError, SoundFile, save_config_to_file};
+use services::services::{
+    config::{Config, ConfigError, SoundFile, save_config_to_file},
+    git::set_fetch_settings,
+};
 use tokio::fs;
 use ts_rs::TS;
 use utils::{assets::config_path, response::ApiResponse};
@@ -30,6 +35,7 @@ pub fn router() -> Router<DeploymentImpl> {
         .route("/sounds/{sound}", get(get_sound))
         .route("/mcp-config", get(get_mcp_servers).post(update_mcp_servers))
         .route("/profiles", get(get_profiles).put(update_profiles))
+        .route("/executor-capabilities", get(get_executor_capabilities))
 }
 
 #[derive(Debug, Serialize, Deserialize, TS)]
@@ -109,6 +115,33 @@ async fn update_config(
         ));
     }
 
+    // Validate branch naming template
+    if !utils::git::is_valid_branch_template(&new_config.branch_template) {
+        return ResponseJson(ApiResponse::error(
+            "Invalid branch template. Use only {prefix}, {attempt_id}, {task_id} and {slug} placeholders and characters allowed in git branch names.",
+        ));
+    }
+
+    // Validate extra executor arguments; they are passed through the shell unquoted
+    for (agent, args) in &new_config.executor_extra_args {
+        if let Some(e) = args.iter().find_map(|arg| validate_extra_arg(arg).err()) {
+            return ResponseJson(ApiResponse::error(&format!(
+                "Invalid extra argument for {agent}: {e}"
+            )));
+        }
+    }
+
+    // Validate log redaction patterns
+    if let Some(e) = new_config
+        .log_redaction_patterns
+        .iter()
+        .find_map(|pattern| validate_redaction_pattern(pattern).err())
+    {
+        return ResponseJson(ApiResponse::error(&format!(
+            "Invalid log redaction pattern: {e}"
+        )));
+    }
+
     // Get old config state before updating
     let old_config = deployment.config().read().await.clone();
 
@@ -117,6 +150,9 @@ async fn update_config(
             let mut config = deployment.config().write().await;
             *config = new_config.clone();
             drop(config);
+            set_user_extra_args(new_config.executor_extra_args.clone());
+            set_user_redaction_patterns(&new_config.log_redaction_patterns);
+            set_fetch_settings(new_config.fetch_settings());
 
             // Track config events when fields transition from false → true and run side effects
             handle_config_events(&deployment, &old_config, &new_config).await;
@@ -202,6 +238,39 @@ async fn get_sound(Path(sound): Path<SoundFile>) -> Result<Response, ApiError> {
     Ok(response)
 }
 
+/// What a coding agent supports, so clients don't hardcode the feature matrix
+#[derive(Debug, Serialize, TS)]
+pub struct ExecutorCapabilities {
+    pub executor: BaseCodingAgent,
+    pub capabilities: Vec<BaseAgentCapability>,
+    pub supports_mcp: bool,
+    /// Configured variants, e.g. `DEFAULT` and `PLAN`
+    pub variants: Vec<String>,
+}
+
+/// Capabilities of every configured coding agent, taken from its default variant
+async fn get_executor_capabilities() -> ResponseJson<ApiResponse<Vec<ExecutorCapabilities>>> {
+    let profiles = ExecutorConfigs::get_cached();
+    let mut matrix: Vec<ExecutorCapabilities> = profiles
+        .executors
+        .iter()
+        .filter_map(|(executor, config)| {
+            let agent = config.get_default()?;
+            let mut variants: Vec<String> = config.configurations.keys().cloned().collect();
+            variants.sort();
+            Some(ExecutorCapabilities {
+                executor: *executor,
+                capabilities: agent.capabilities(),
+                supports_mcp: agent.supports_mcp(),
+                variants,
+            })
+        })
+        .collect();
+    matrix.sort_by_key(|entry| entry.executor.to_string());
+
+    ResponseJson(ApiResponse::success(matrix))
+}
+
 #[derive(TS, Debug, Deserialize)]
 pub struct McpServerQuery {
     executor: BaseCodingAgent,
@@ -1,6 +1,6 @@
 use anyhow;
 use axum::{
-    Extension, Router,
+    Extension, Json, Router,
     extract::{
         Path, Query, State,
         ws::{WebSocket, WebSocketUpgrade},
@@ -9,13 +9,19 @@ use axum::{
     response::{IntoResponse, Json as ResponseJson},
     routing::{get, post},
 };
-use db::models::execution_process::{
-    ExecutionProcess, ExecutionProcessError, ExecutionProcessStatus,
+use chrono::Utc;
+use db::models::{
+    execution_process::{
+        ExecutionProcess, ExecutionProcessError, ExecutionProcessStatus, RunningExecutionProcess,
+    },
+    task_attempt::TaskAttempt,
 };
 use deployment::Deployment;
+use executors::logs::{LogChannel, utils::ChannelPatchFilter};
 use futures_util::{SinkExt, StreamExt, TryStreamExt};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use services::services::container::ContainerService;
+use ts_rs::TS;
 use utils::{log_msg::LogMsg, response::ApiResponse};
 use uuid::Uuid;
 
@@ -29,6 +35,36 @@ pub struct ExecutionProcessQuery {
     pub show_soft_deleted: Option<bool>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct LogStreamQuery {
+    /// Only stream entries from this output channel
+    pub channel: Option<LogChannel>,
+}
+
+/// A process running anywhere on this server
+#[derive(Debug, Serialize, TS)]
+pub struct RunningProcessInfo {
+    #[serde(flatten)]
+    pub process: RunningExecutionProcess,
+    pub uptime_seconds: i64,
+}
+
+impl From<RunningExecutionProcess> for RunningProcessInfo {
+    fn from(process: RunningExecutionProcess) -> Self {
+        let uptime_seconds = (Utc::now() - process.started_at).num_seconds().max(0);
+        Self {
+            process,
+            uptime_seconds,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct StopRunningProcessesRequest {
+    /// Stop only this process; every running process is stopped when omitted
+    pub execution_process_id: Option<Uuid>,
+}
+
 pub async fn get_execution_processes(
     State(deployment): State<DeploymentImpl>,
     Query(query): Query<ExecutionProcessQuery>,
@@ -55,6 +91,7 @@ pub async fn stream_raw_logs_ws(
     ws: WebSocketUpgrade,
     State(deployment): State<DeploymentImpl>,
     Path(exec_id): Path<Uuid>,
+    Query(query): Query<LogStreamQuery>,
 ) -> Result<impl IntoResponse, ApiError> {
     // Check if the stream exists before upgrading the WebSocket
     let _stream = deployment
@@ -66,7 +103,7 @@ pub async fn stream_raw_logs_ws(
         })?;
 
     Ok(ws.on_upgrade(move |socket| async move {
-        if let Err(e) = handle_raw_logs_ws(socket, deployment, exec_id).await {
+        if let Err(e) = handle_raw_logs_ws(socket, deployment, exec_id, query.channel).await {
             tracing::warn!("raw logs WS closed: {}", e);
         }
     }))
@@ -76,6 +113,7 @@ async fn handle_raw_logs_ws(
     socket: WebSocket,
     deployment: DeploymentImpl,
     exec_id: Uuid,
+    channel: Option<LogChannel>,
 ) -> anyhow::Result<()> {
     use std::sync::{
         Arc,
@@ -93,23 +131,33 @@ async fn handle_raw_logs_ws(
         .ok_or_else(|| anyhow::anyhow!("Execution process not found"))?;
 
     let counter = Arc::new(AtomicUsize::new(0));
-    let mut stream = raw_stream.map_ok({
-        let counter = counter.clone();
-        move |m| match m {
-            LogMsg::Stdout(content) => {
-                let index = counter.fetch_add(1, Ordering::SeqCst);
-                let patch = ConversationPatch::add_stdout(index, content);
-                LogMsg::JsonPatch(patch).to_ws_message_unchecked()
-            }
-            LogMsg::Stderr(content) => {
-                let index = counter.fetch_add(1, Ordering::SeqCst);
-                let patch = ConversationPatch::add_stderr(index, content);
-                LogMsg::JsonPatch(patch).to_ws_message_unchecked()
+    let mut stream = raw_stream
+        .try_filter(move |m| {
+            // Drop the other channel before indexing so the client sees contiguous entries
+            let keep = !matches!(
+                (channel, m),
+                (Some(LogChannel::Stdout), LogMsg::Stderr(_))
+                    | (Some(LogChannel::Stderr), LogMsg::Stdout(_))
+            );
+            futures_util::future::ready(keep)
+        })
+        .map_ok({
+            let counter = counter.clone();
+            move |m| match m {
+                LogMsg::Stdout(content) => {
+                    let index = counter.fetch_add(1, Ordering::SeqCst);
+                    let patch = ConversationPatch::add_stdout(index, content);
+                    LogMsg::JsonPatch(patch).to_ws_message_unchecked()
+                }
+                LogMsg::Stderr(content) => {
+                    let index = counter.fetch_add(1, Ordering::SeqCst);
+                    let patch = ConversationPatch::add_stderr(index, content);
+                    LogMsg::JsonPatch(patch).to_ws_message_unchecked()
+                }
+                LogMsg::Finished => LogMsg::Finished.to_ws_message_unchecked(),
+                _ => unreachable!("Raw stream should only have Stdout/Stderr/Finished"),
             }
-            LogMsg::Finished => LogMsg::Finished.to_ws_message_unchecked(),
-            _ => unreachable!("Raw stream should only have Stdout/Stderr/Finished"),
-        }
-    });
+        });
 
     // Split socket into sender and receiver
     let (mut sender, mut receiver) = socket.split();
@@ -138,6 +186,7 @@ pub async fn stream_normalized_logs_ws(
     ws: WebSocketUpgrade,
     State(deployment): State<DeploymentImpl>,
     Path(exec_id): Path<Uuid>,
+    Query(query): Query<LogStreamQuery>,
 ) -> Result<impl IntoResponse, ApiError> {
     let stream = deployment
         .container()
@@ -151,7 +200,7 @@ pub async fn stream_normalized_logs_ws(
     let stream = stream.err_into::<anyhow::Error>().into_stream();
 
     Ok(ws.on_upgrade(move |socket| async move {
-        if let Err(e) = handle_normalized_logs_ws(socket, stream).await {
+        if let Err(e) = handle_normalized_logs_ws(socket, stream, query.channel).await {
             tracing::warn!("normalized logs WS closed: {}", e);
         }
     }))
@@ -160,8 +209,18 @@ pub async fn stream_normalized_logs_ws(
 async fn handle_normalized_logs_ws(
     socket: WebSocket,
     stream: impl futures_util::Stream<Item = anyhow::Result<LogMsg>> + Unpin + Send + 'static,
+    channel: Option<LogChannel>,
 ) -> anyhow::Result<()> {
-    let mut stream = stream.map_ok(|msg| msg.to_ws_message_unchecked());
+    let mut channel_filter = channel.map(ChannelPatchFilter::new);
+    let mut stream = stream.try_filter_map(move |msg| {
+        let msg = match (&mut channel_filter, msg) {
+            (Some(filter), LogMsg::JsonPatch(patch)) => {
+                filter.filter(&patch).map(LogMsg::JsonPatch)
+            }
+            (_, msg) => Some(msg),
+        };
+        futures_util::future::ready(Ok(msg.map(|msg| msg.to_ws_message_unchecked())))
+    });
     let (mut sender, mut receiver) = socket.split();
     tokio::spawn(async move { while let Some(Ok(_)) = receiver.next().await {} });
     while let Some(item) = stream.next().await {
@@ -192,6 +251,64 @@ pub async fn stop_execution_process(
     Ok(ResponseJson(ApiResponse::success(())))
 }
 
+/// List every running process across all projects, oldest first
+pub async fn get_running_processes(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<RunningProcessInfo>>>, ApiError> {
+    let running = ExecutionProcess::find_running_with_context(&deployment.db().pool).await?;
+
+    Ok(ResponseJson(ApiResponse::success(
+        running.into_iter().map(RunningProcessInfo::from).collect(),
+    )))
+}
+
+/// Stop one running process, or all of them, and return the processes that were stopped
+pub async fn stop_running_processes(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<StopRunningProcessesRequest>,
+) -> Result<ResponseJson<ApiResponse<Vec<RunningProcessInfo>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let mut running = ExecutionProcess::find_running_with_context(pool).await?;
+
+    if let Some(process_id) = payload.execution_process_id {
+        let process = ExecutionProcess::find_by_id(pool, process_id)
+            .await?
+            .ok_or(ApiError::ExecutionProcess(
+                ExecutionProcessError::ExecutionProcessNotFound,
+            ))?;
+        running.retain(|p| p.id == process_id);
+        if !running.is_empty() {
+            deployment
+                .container()
+                .stop_execution(&process, ExecutionProcessStatus::Killed)
+                .await?;
+        }
+    } else {
+        let mut attempt_ids: Vec<Uuid> = running.iter().map(|p| p.task_attempt_id).collect();
+        attempt_ids.sort();
+        attempt_ids.dedup();
+        for attempt_id in attempt_ids {
+            if let Some(attempt) = TaskAttempt::find_by_id(pool, attempt_id).await? {
+                deployment.container().try_stop(&attempt).await;
+            }
+        }
+    }
+
+    deployment
+        .track_if_analytics_allowed(
+            "running_processes_stopped",
+            serde_json::json!({
+                "stopped_count": running.len(),
+                "all": payload.execution_process_id.is_none(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(
+        running.into_iter().map(RunningProcessInfo::from).collect(),
+    )))
+}
+
 pub async fn stream_execution_processes_ws(
     ws: WebSocketUpgrade,
     State(deployment): State<DeploymentImpl>,
@@ -261,6 +378,8 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     let task_attempts_router = Router::new()
         .route("/", get(get_execution_processes))
         .route("/stream/ws", get(stream_execution_processes_ws))
+        .route("/running", get(get_running_processes))
+        .route("/running/stop", post(stop_running_processes))
         .nest("/{id}", task_attempt_id_router);
 
     Router::new().nest("/execution-processes", task_attempts_router)
@@ -0,0 +1,61 @@
+use axum::{
+    Router,
+    body::Bytes,
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    routing::post,
+};
+use deployment::Deployment;
+use services::services::{
+    analytics::AnalyticsContext,
+    github_webhook::{
+        EVENT_HEADER, GitHubWebhookError, GitHubWebhookService, SIGNATURE_HEADER, verify_signature,
+        webhook_secret,
+    },
+};
+
+use crate::{DeploymentImpl, error::ApiError};
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new().route("/github/webhook", post(receive_github_webhook))
+}
+
+/// POST /github/webhook
+/// Receives `pull_request` and `check_suite` deliveries signed with `GITHUB_WEBHOOK_SECRET`
+async fn receive_github_webhook(
+    State(deployment): State<DeploymentImpl>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<StatusCode, ApiError> {
+    let secret = webhook_secret().ok_or(GitHubWebhookError::NotConfigured)?;
+    let signature = headers
+        .get(SIGNATURE_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .ok_or(GitHubWebhookError::InvalidSignature)?;
+    if !verify_signature(&secret, &body, signature) {
+        return Err(GitHubWebhookError::InvalidSignature.into());
+    }
+    let event = headers
+        .get(EVENT_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+
+    let analytics = deployment
+        .analytics()
+        .as_ref()
+        .map(|analytics_service| AnalyticsContext {
+            user_id: deployment.user_id().to_string(),
+            analytics_service: analytics_service.clone(),
+        });
+    let service = GitHubWebhookService::new(
+        deployment.db().clone(),
+        deployment.config().clone(),
+        analytics,
+    );
+    let updated = service.handle_event(event, &body).await?;
+    if updated > 0 {
+        tracing::info!("GitHub '{}' webhook updated {} PR merge(s)", event, updated);
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
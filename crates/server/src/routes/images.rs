@@ -1,7 +1,7 @@
 use axum::{
     Router,
     body::Body,
-    extract::{DefaultBodyLimit, Multipart, Path, State},
+    extract::{DefaultBodyLimit, Multipart, Path, Query, State},
     http::{StatusCode, header},
     response::{Json as ResponseJson, Response},
     routing::{delete, get, post},
@@ -13,7 +13,7 @@ use db::models::{
 };
 use deployment::Deployment;
 use serde::{Deserialize, Serialize};
-use services::services::image::ImageError;
+use services::services::image::{ImageError, OrphanCleanupOptions, OrphanCleanupReport};
 use sqlx::Error as SqlxError;
 use tokio::fs::File;
 use tokio_util::io::ReaderStream;
@@ -170,12 +170,36 @@ pub async fn get_task_images(
     Ok(ResponseJson(ApiResponse::success(image_responses)))
 }
 
+#[derive(Debug, Deserialize)]
+pub struct OrphanCleanupQuery {
+    #[serde(default)]
+    pub dry_run: bool,
+    /// Overrides the configured per-run limit; 0 means no limit
+    pub limit: Option<usize>,
+}
+
+/// Run the orphaned image cleanup now, or with `dry_run` just report what it would delete
+pub async fn cleanup_orphaned_images(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<OrphanCleanupQuery>,
+) -> Result<ResponseJson<ApiResponse<OrphanCleanupReport>>, ApiError> {
+    let mut options = OrphanCleanupOptions::from_config(&*deployment.config().read().await);
+    options.dry_run = query.dry_run;
+    if let Some(limit) = query.limit {
+        options.limit = (limit > 0).then_some(limit);
+    }
+
+    let report = deployment.image().delete_orphaned_images(options).await?;
+    Ok(ResponseJson(ApiResponse::success(report)))
+}
+
 pub fn routes() -> Router<DeploymentImpl> {
     Router::new()
         .route(
             "/upload",
             post(upload_image).layer(DefaultBodyLimit::max(20 * 1024 * 1024)), // 20MB limit
         )
+        .route("/orphans/cleanup", post(cleanup_orphaned_images))
         .route("/{id}/file", get(serve_image))
         .route("/{id}", delete(delete_image))
         .route("/task/{task_id}", get(get_task_images))
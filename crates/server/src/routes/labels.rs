@@ -0,0 +1,141 @@
+use axum::{
+    Extension, Json, Router,
+    extract::{Query, State},
+    middleware::from_fn_with_state,
+    response::Json as ResponseJson,
+    routing::get,
+};
+use db::models::label::{CreateLabel, Label, UpdateLabel, is_valid_label_color};
+use deployment::Deployment;
+use serde::Deserialize;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, middleware::load_label_middleware};
+
+#[derive(Debug, Deserialize)]
+pub struct LabelQuery {
+    pub project_id: Uuid,
+}
+
+fn validate_label(name: Option<&str>, color: Option<&str>) -> Result<(), ApiError> {
+    if name.is_some_and(|name| name.trim().is_empty()) {
+        return Err(ApiError::Validation(
+            "Label name cannot be empty".to_string(),
+        ));
+    }
+    if let Some(color) = color
+        && !is_valid_label_color(color)
+    {
+        return Err(ApiError::Validation(format!(
+            "Invalid label color '{color}'; expected a hex color such as #ef4444"
+        )));
+    }
+    Ok(())
+}
+
+fn map_duplicate_name(err: sqlx::Error, name: &str) -> ApiError {
+    match &err {
+        sqlx::Error::Database(db_err) if db_err.is_unique_violation() => ApiError::Conflict(
+            format!("A label named '{name}' already exists in this project"),
+        ),
+        _ => ApiError::Database(err),
+    }
+}
+
+/// Re-publish the tasks carrying a label so live task lists pick up the change
+async fn push_label_task_updates(deployment: &DeploymentImpl, task_ids: &[Uuid]) {
+    for task_id in task_ids {
+        if let Err(e) = deployment.events().push_task_update(*task_id).await {
+            tracing::error!("Failed to push task update for {}: {}", task_id, e);
+        }
+    }
+}
+
+pub async fn get_labels(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<LabelQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<Label>>>, ApiError> {
+    let labels = Label::find_by_project_id(&deployment.db().pool, query.project_id).await?;
+    Ok(ResponseJson(ApiResponse::success(labels)))
+}
+
+pub async fn get_label(
+    Extension(label): Extension<Label>,
+) -> Result<ResponseJson<ApiResponse<Label>>, ApiError> {
+    Ok(Json(ApiResponse::success(label)))
+}
+
+pub async fn create_label(
+    State(deployment): State<DeploymentImpl>,
+    Json(mut payload): Json<CreateLabel>,
+) -> Result<ResponseJson<ApiResponse<Label>>, ApiError> {
+    payload.name = payload.name.trim().to_string();
+    validate_label(Some(&payload.name), payload.color.as_deref())?;
+
+    let label = Label::create(&deployment.db().pool, &payload)
+        .await
+        .map_err(|e| map_duplicate_name(e, &payload.name))?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "label_created",
+            serde_json::json!({
+                "label_id": label.id.to_string(),
+                "project_id": label.project_id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(label)))
+}
+
+pub async fn update_label(
+    Extension(label): Extension<Label>,
+    State(deployment): State<DeploymentImpl>,
+    Json(mut payload): Json<UpdateLabel>,
+) -> Result<ResponseJson<ApiResponse<Label>>, ApiError> {
+    payload.name = payload.name.map(|name| name.trim().to_string());
+    validate_label(payload.name.as_deref(), payload.color.as_deref())?;
+
+    let pool = &deployment.db().pool;
+    let updated_label = Label::update(pool, label.id, &payload)
+        .await
+        .map_err(|e| map_duplicate_name(e, payload.name.as_deref().unwrap_or(&label.name)))?;
+
+    let task_ids = Label::find_task_ids(pool, label.id).await?;
+    push_label_task_updates(&deployment, &task_ids).await;
+
+    Ok(ResponseJson(ApiResponse::success(updated_label)))
+}
+
+pub async fn delete_label(
+    Extension(label): Extension<Label>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let task_ids = Label::find_task_ids(pool, label.id).await?;
+
+    let rows_affected = Label::delete(pool, label.id).await?;
+    if rows_affected == 0 {
+        return Err(ApiError::Database(sqlx::Error::RowNotFound));
+    }
+
+    push_label_task_updates(&deployment, &task_ids).await;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let label_router = Router::new()
+        .route("/", get(get_label).put(update_label).delete(delete_label))
+        .layer(from_fn_with_state(
+            deployment.clone(),
+            load_label_middleware,
+        ));
+
+    let inner = Router::new()
+        .route("/", get(get_labels).post(create_label))
+        .nest("/{label_id}", label_router);
+
+    Router::new().nest("/labels", inner)
+}
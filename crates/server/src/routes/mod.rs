@@ -1,14 +1,16 @@
 use axum::{
-    Router,
-    middleware::from_fn_with_state,
-    routing::{IntoMakeService, get},
+    Json, Router,
+    middleware::{from_fn, from_fn_with_state},
+    routing::{IntoMakeService, get, post},
 };
 
-use crate::DeploymentImpl;
+use crate::{DeploymentImpl, middleware::request_id_middleware};
 
 pub mod agents;
 pub mod approvals;
+pub mod attempt_queue;
 pub mod auth;
+pub mod batch;
 pub mod config;
 pub mod containers;
 pub mod filesystem;
@@ -17,9 +19,12 @@ pub mod drafts;
 pub mod events;
 pub mod execution_processes;
 pub mod frontend;
+pub mod github_webhooks;
 pub mod health;
 pub mod images;
+pub mod labels;
 pub mod projects;
+pub mod prompt_snippets;
 pub mod tags;
 pub mod task_attempts;
 pub mod tasks;
@@ -35,24 +40,39 @@ pub fn router(deployment: DeploymentImpl) -> IntoMakeService<Router> {
         .merge(drafts::router(&deployment))
         .merge(tasks::router(&deployment))
         .merge(task_attempts::router(&deployment))
+        .merge(attempt_queue::router())
         .merge(execution_processes::router(&deployment))
         .merge(tags::router(&deployment))
+        .merge(labels::router(&deployment))
+        .merge(prompt_snippets::router(&deployment))
         .merge(agents::router(&deployment))
         .merge(workflows::router(&deployment))
         .merge(auth::router(&deployment))
+        .merge(github_webhooks::router())
         .merge(filesystem::router())
         .merge(events::router(&deployment))
         .merge(approvals::router())
         .nest("/images", images::routes())
+        .with_state(deployment.clone());
+    // Batched sub-requests are dispatched through the routes above, inside the batch
+    // request's request id and Sentry scope
+    let batch_routes = base_routes.clone();
+    let api_routes = base_routes
+        .route(
+            "/batch",
+            post(move |payload: Json<Vec<batch::BatchSubRequest>>| {
+                batch::run_batch(batch_routes.clone(), payload)
+            }),
+        )
         .layer(from_fn_with_state(
-            deployment.clone(),
+            deployment,
             auth::sentry_user_context_middleware,
         ))
-        .with_state(deployment);
+        .layer(from_fn(request_id_middleware));
 
     Router::new()
         .route("/", get(frontend::serve_frontend_root))
         .route("/{*path}", get(frontend::serve_frontend))
-        .nest("/api", base_routes)
+        .nest("/api", api_routes)
         .into_make_service()
 }
@@ -1,24 +1,40 @@
 use std::path::Path;
 
 use axum::{
-    Extension, Json, Router,
+    BoxError, Extension, Json, Router,
     extract::{Query, State},
     http::StatusCode,
     middleware::from_fn_with_state,
-    response::Json as ResponseJson,
-    routing::{get, post},
+    response::{
+        Json as ResponseJson, Sse,
+        sse::{Event, KeepAlive},
+    },
+    routing::{get, post, put},
 };
-use db::models::project::{
-    CreateProject, Project, ProjectError, SearchMatchType, SearchResult, UpdateProject,
+use db::models::{
+    execution_process::ExecutionProcess,
+    project::{
+        CreateProject, Project, ProjectError, SearchMatchType, SearchResult, UpdateProject,
+        invalid_dev_scripts_message,
+    },
 };
 use deployment::Deployment;
-use ignore::WalkBuilder;
+use executors::actions::script::ScriptRequestLanguage;
+use futures_util::{Stream, StreamExt, TryStreamExt, stream};
+use serde::{Deserialize, Serialize};
 use services::services::{
+    config::save_config_to_file,
+    container::{ContainerService, StoppedDevServer},
     file_ranker::FileRanker,
     file_search_cache::{CacheError, SearchMode, SearchQuery},
-    git::GitBranch,
+    filesystem::FilesystemService,
+    git::{GitBranch, GitService, GitServiceError},
+    git_cli::GitCliError,
+    repo_config::RepoConfigStatus,
 };
-use utils::{path::expand_tilde, response::ApiResponse};
+use tokio::sync::mpsc;
+use ts_rs::TS;
+use utils::{assets::config_path, path::expand_tilde, response::ApiResponse};
 use uuid::Uuid;
 
 use crate::{DeploymentImpl, error::ApiError, middleware::load_project_middleware};
@@ -32,10 +48,79 @@ pub async fn get_projects(
 
 pub async fn get_project(
     Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
 ) -> Result<ResponseJson<ApiResponse<Project>>, ApiError> {
+    // Opening a project starts following its repository config
+    if let Err(e) = deployment
+        .container()
+        .repo_config()
+        .watch(&project.git_repo_path)
+        .await
+    {
+        tracing::warn!(
+            "Failed to watch repository config for project {}: {}",
+            project.id,
+            e
+        );
+    }
     Ok(ResponseJson(ApiResponse::success(project)))
 }
 
+/// Settings the project's repository shares in `.vibe/config.toml`, and why the file is being
+/// ignored if it is invalid
+pub async fn get_project_repo_config(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<RepoConfigStatus>>, ApiError> {
+    let status = deployment
+        .container()
+        .repo_config()
+        .status(&project.git_repo_path)
+        .await;
+    Ok(ResponseJson(ApiResponse::success(status)))
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct TrustRepoConfigRequest {
+    /// Fingerprint of the config file the user reviewed, or `None` to stop trusting the
+    /// repository's scripts
+    pub fingerprint: Option<String>,
+}
+
+/// Let the scripts in the repository config run for the project, pinned to the version of
+/// the file the user reviewed
+pub async fn trust_project_repo_config(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<TrustRepoConfigRequest>,
+) -> Result<ResponseJson<ApiResponse<RepoConfigStatus>>, ApiError> {
+    let repo_config = deployment.container().repo_config();
+    let key = project.git_repo_path.to_string_lossy().into_owned();
+    if let Some(fingerprint) = &payload.fingerprint {
+        let status = repo_config.status(&project.git_repo_path).await;
+        if status.fingerprint.as_ref() != Some(fingerprint) {
+            return Err(ApiError::Validation(
+                "The repository config has changed; review it again before trusting its scripts"
+                    .to_string(),
+            ));
+        }
+    }
+
+    {
+        let mut config = deployment.config().write().await;
+        let mut updated = config.clone();
+        match payload.fingerprint {
+            Some(fingerprint) => updated.trusted_repo_scripts.insert(key, fingerprint),
+            None => updated.trusted_repo_scripts.remove(&key),
+        };
+        save_config_to_file(&updated, &config_path()).await?;
+        *config = updated;
+    }
+
+    let status = repo_config.status(&project.git_repo_path).await;
+    Ok(ResponseJson(ApiResponse::success(status)))
+}
+
 pub async fn get_project_branches(
     Extension(project): Extension<Project>,
     State(deployment): State<DeploymentImpl>,
@@ -44,10 +129,133 @@ pub async fn get_project_branches(
     Ok(ResponseJson(ApiResponse::success(branches)))
 }
 
+#[derive(Debug, Deserialize, TS)]
+pub struct SetDefaultBaseBranchRequest {
+    /// Branch new attempts are based on; `null` or blank clears it
+    pub default_base_branch: Option<String>,
+}
+
+pub async fn set_project_default_base_branch(
+    Extension(mut project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<SetDefaultBaseBranchRequest>,
+) -> Result<ResponseJson<ApiResponse<Project>>, ApiError> {
+    let branch = payload
+        .default_base_branch
+        .map(|b| b.trim().to_string())
+        .filter(|b| !b.is_empty());
+
+    if let Some(branch) = &branch
+        && !deployment
+            .git()
+            .check_branch_exists(&project.git_repo_path, branch)?
+    {
+        return Err(ApiError::Validation(format!(
+            "Branch '{branch}' does not exist in this repository"
+        )));
+    }
+
+    Project::update_default_base_branch(&deployment.db().pool, project.id, branch.as_deref())
+        .await?;
+    project.default_base_branch = branch;
+
+    Ok(ResponseJson(ApiResponse::success(project)))
+}
+
+/// Treat blank remote names as "use the default remote"
+fn normalize_remote(remote: Option<String>) -> Option<String> {
+    remote
+        .map(|r| r.trim().to_string())
+        .filter(|r| !r.is_empty())
+}
+
+/// Error message if a configured push or PR remote doesn't exist in the repository
+fn invalid_remote_message(
+    deployment: &DeploymentImpl,
+    repo_path: &Path,
+    push_remote: &Option<String>,
+    pr_remote: &Option<String>,
+) -> Option<String> {
+    [("Push", push_remote), ("PR", pr_remote)]
+        .into_iter()
+        .find_map(|(label, remote)| {
+            let remote = remote.as_deref()?;
+            deployment
+                .git()
+                .ensure_remote_exists(repo_path, remote)
+                .err()
+                .map(|e| format!("{label} remote is invalid: {e}"))
+        })
+}
+
+/// Error message if the chosen script language's interpreter isn't installed
+fn unavailable_script_language_message(
+    script_language: &Option<ScriptRequestLanguage>,
+) -> Option<String> {
+    let language = script_language.as_ref()?;
+    if language.is_available() {
+        return None;
+    }
+    Some(format!(
+        "Script interpreter '{}' was not found on PATH",
+        language.interpreter().0
+    ))
+}
+
+/// Error message if the project's preview URL pattern isn't a valid regex
+fn invalid_preview_url_pattern_message(preview_url_pattern: &Option<String>) -> Option<String> {
+    let pattern = preview_url_pattern.as_deref()?;
+    regex::Regex::new(pattern)
+        .err()
+        .map(|e| format!("Preview URL pattern is invalid: {e}"))
+}
+
 pub async fn create_project(
     State(deployment): State<DeploymentImpl>,
     Json(payload): Json<CreateProject>,
 ) -> Result<ResponseJson<ApiResponse<Project>>, ApiError> {
+    Ok(ResponseJson(
+        create_project_with_progress(&deployment, payload, None).await?,
+    ))
+}
+
+/// Create a project like `POST /projects`, streaming `git clone` progress lines as
+/// `progress` events when `git_url` is set. Ends with a `result` event carrying the same
+/// response body the non-streaming route returns.
+pub async fn create_project_stream(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateProject>,
+) -> Sse<impl Stream<Item = Result<Event, BoxError>>> {
+    let (progress_tx, progress_rx) = mpsc::unbounded_channel();
+    let created = tokio::spawn(async move {
+        create_project_with_progress(&deployment, payload, Some(progress_tx))
+            .await
+            .unwrap_or_else(|e| ApiResponse::error(&e.to_string()))
+    });
+
+    let progress = stream::unfold(progress_rx, |mut receiver| async move {
+        let line: String = receiver.recv().await?;
+        Some((
+            Ok::<_, axum::Error>(Event::default().event("progress").data(line)),
+            receiver,
+        ))
+    });
+    let result = stream::once(async move {
+        let response = created
+            .await
+            .unwrap_or_else(|e| ApiResponse::error(&format!("Project creation failed: {e}")));
+        Event::default().event("result").json_data(response)
+    });
+
+    Sse::new(progress.chain(result).map_err(|e| -> BoxError { e.into() }))
+        .keep_alive(KeepAlive::default())
+}
+
+async fn create_project_with_progress(
+    deployment: &DeploymentImpl,
+    payload: CreateProject,
+    progress: Option<mpsc::UnboundedSender<String>>,
+) -> Result<ApiResponse<Project>, ApiError> {
     let id = Uuid::new_v4();
     let CreateProject {
         name,
@@ -57,62 +265,93 @@ pub async fn create_project(
         cleanup_script,
         copy_files,
         worktree_dir,
+        push_remote,
+        pr_remote,
+        always_run_cleanup,
+        setup_cache_files,
+        setup_cache_artifacts,
+        script_language,
+        preview_url_pattern,
+        env_file,
+        env_file_required,
+        dev_scripts,
+        reuse_worktree,
         use_existing_repo,
+        git_url,
     } = payload;
+    let git_url = git_url
+        .map(|url| url.trim().to_string())
+        .filter(|url| !url.is_empty());
+    let cloned = !use_existing_repo && git_url.is_some();
+    let push_remote = normalize_remote(push_remote);
+    let pr_remote = normalize_remote(pr_remote);
     tracing::debug!("Creating project '{}'", name);
 
+    if let Some(message) = unavailable_script_language_message(&script_language) {
+        return Ok(ApiResponse::error(&message));
+    }
+
+    if let Some(message) = invalid_preview_url_pattern_message(&preview_url_pattern) {
+        return Ok(ApiResponse::error(&message));
+    }
+
+    if let Some(message) = invalid_dev_scripts_message(&dev_scripts) {
+        return Ok(ApiResponse::error(&message));
+    }
+
     // Validate worktree_dir if provided
     if let Some(ref dir) = worktree_dir {
         let path = expand_tilde(dir);
 
         // Validate path is not empty
         if path.as_os_str().is_empty() {
-            return Ok(ResponseJson(ApiResponse::error(
+            return Ok(ApiResponse::error(
                 "Worktree directory path cannot be empty",
-            )));
+            ));
         }
 
         // Create directory if it doesn't exist
         if !path.exists() {
             if let Err(e) = std::fs::create_dir_all(&path) {
                 tracing::error!("Failed to create worktree directory '{}': {}", dir, e);
-                return Ok(ResponseJson(ApiResponse::error(&format!(
+                return Ok(ApiResponse::error(&format!(
                     "Failed to create worktree directory: {}",
                     e
-                ))));
+                )));
             }
             tracing::info!("Created worktree directory: {}", path.display());
         }
 
         // Validate path is a directory
         if !path.is_dir() {
-            return Ok(ResponseJson(ApiResponse::error(
+            return Ok(ApiResponse::error(
                 "Worktree path exists but is not a directory",
-            )));
+            ));
         }
 
         // Test write permissions
         let test_file = path.join(".vibe-kanban-test");
         if let Err(e) = std::fs::write(&test_file, "") {
             tracing::error!("Worktree directory '{}' is not writable: {}", dir, e);
-            return Ok(ResponseJson(ApiResponse::error(&format!(
+            return Ok(ApiResponse::error(&format!(
                 "Worktree directory is not writable: {}",
                 e
-            ))));
+            )));
         }
         let _ = std::fs::remove_file(&test_file);
     }
 
     // Validate and setup git repository
     let path = std::path::absolute(expand_tilde(&git_repo_path))?;
+    let clone_dest_existed = path.exists();
     // Check if git repo path is already used by another project
     match Project::find_by_git_repo_path(&deployment.db().pool, path.to_string_lossy().as_ref())
         .await
     {
         Ok(Some(_)) => {
-            return Ok(ResponseJson(ApiResponse::error(
+            return Ok(ApiResponse::error(
                 "A project with this git repository path already exists",
-            )));
+            ));
         }
         Ok(None) => {
             // Path is available, continue
@@ -125,30 +364,63 @@ pub async fn create_project(
     if use_existing_repo {
         // For existing repos, validate that the path exists and is a git repository
         if !path.exists() {
-            return Ok(ResponseJson(ApiResponse::error(
-                "The specified path does not exist",
-            )));
+            return Ok(ApiResponse::error("The specified path does not exist"));
         }
 
         if !path.is_dir() {
-            return Ok(ResponseJson(ApiResponse::error(
-                "The specified path is not a directory",
-            )));
+            return Ok(ApiResponse::error("The specified path is not a directory"));
         }
 
         if !path.join(".git").exists() {
-            return Ok(ResponseJson(ApiResponse::error(
+            return Ok(ApiResponse::error(
                 "The specified directory is not a git repository",
-            )));
+            ));
         }
 
-        // Ensure existing repo has a main branch if it's empty
-        if let Err(e) = deployment.git().ensure_main_branch_exists(&path) {
-            tracing::error!("Failed to ensure main branch exists: {}", e);
-            return Ok(ResponseJson(ApiResponse::error(&format!(
-                "Failed to ensure main branch exists: {}",
+        // Ensure existing repo has a branch to work from if it's empty
+        if let Err(e) = deployment.git().ensure_default_branch_exists(&path) {
+            tracing::error!("Failed to ensure default branch exists: {}", e);
+            return Ok(ApiResponse::error(&format!(
+                "Failed to ensure default branch exists: {}",
                 e
-            ))));
+            )));
+        }
+    } else if let Some(git_url) = &git_url {
+        // Clone into a new or empty directory; git refuses anything else
+        let occupied = clone_dest_existed
+            && std::fs::read_dir(&path)
+                .map(|mut entries| entries.next().is_some())
+                .unwrap_or(true);
+        if occupied {
+            return Ok(ApiResponse::error(
+                "The clone destination already exists and is not empty",
+            ));
+        }
+
+        let github_token = deployment.config().read().await.github.token();
+        let git = deployment.git().clone();
+        let (url, dest) = (git_url.clone(), path.clone());
+        let result = tokio::task::spawn_blocking(move || {
+            git.clone_with_progress(&url, &dest, github_token.as_deref(), |line| {
+                tracing::debug!("git clone {}: {}", url, line);
+                if let Some(progress) = &progress {
+                    let _ = progress.send(line.to_string());
+                }
+            })
+        })
+        .await
+        .map_err(std::io::Error::other)?;
+
+        if let Err(e) = result {
+            tracing::error!("Failed to clone repository {}: {}", git_url, e);
+            let message = match e {
+                GitServiceError::GitCLI(GitCliError::AuthFailed(_)) => format!(
+                    "Authentication failed cloning {git_url}. For private GitHub repositories, \
+                     connect GitHub or add a personal access token in settings."
+                ),
+                e => format!("Failed to clone repository: {e}"),
+            };
+            return Ok(ApiResponse::error(&message));
         }
     } else {
         // For new repos, create directory and initialize git
@@ -158,10 +430,10 @@ pub async fn create_project(
             && let Err(e) = std::fs::create_dir_all(&path)
         {
             tracing::error!("Failed to create directory: {}", e);
-            return Ok(ResponseJson(ApiResponse::error(&format!(
+            return Ok(ApiResponse::error(&format!(
                 "Failed to create directory: {}",
                 e
-            ))));
+            )));
         }
 
         // Check if it's already a git repo, if not initialize it
@@ -169,30 +441,56 @@ pub async fn create_project(
             && let Err(e) = deployment.git().initialize_repo_with_main_branch(&path)
         {
             tracing::error!("Failed to initialize git repository: {}", e);
-            return Ok(ResponseJson(ApiResponse::error(&format!(
+            return Ok(ApiResponse::error(&format!(
                 "Failed to initialize git repository: {}",
                 e
-            ))));
+            )));
         }
     }
 
+    // Remotes can only be checked once the repository exists; don't keep a clone made for a
+    // project that is not created
+    let discard_clone = || {
+        if cloned {
+            GitService::remove_clone(&path, clone_dest_existed);
+        }
+    };
+    if let Some(message) = invalid_remote_message(deployment, &path, &push_remote, &pr_remote) {
+        discard_clone();
+        return Ok(ApiResponse::error(&message));
+    }
+
     match Project::create(
         &deployment.db().pool,
         &CreateProject {
             name,
             git_repo_path: path.to_string_lossy().to_string(),
             use_existing_repo,
+            git_url,
             setup_script,
             dev_script,
             cleanup_script,
             copy_files,
             worktree_dir,
+            push_remote,
+            pr_remote,
+            always_run_cleanup,
+            setup_cache_files,
+            setup_cache_artifacts,
+            script_language,
+            preview_url_pattern,
+            env_file,
+            env_file_required,
+            dev_scripts,
+            reuse_worktree,
         },
         id,
     )
     .await
     {
         Ok(project) => {
+            let project = deployment.detect_default_base_branch(project).await;
+
             // Track project creation event
             deployment
                 .track_if_analytics_allowed(
@@ -200,6 +498,7 @@ pub async fn create_project(
                     serde_json::json!({
                         "project_id": project.id.to_string(),
                         "use_existing_repo": use_existing_repo,
+                        "cloned": cloned,
                         "has_setup_script": project.setup_script.is_some(),
                         "has_dev_script": project.dev_script.is_some(),
                         "trigger": "manual",
@@ -207,9 +506,12 @@ pub async fn create_project(
                 )
                 .await;
 
-            Ok(ResponseJson(ApiResponse::success(project)))
+            Ok(ApiResponse::success(project))
+        }
+        Err(e) => {
+            discard_clone();
+            Err(ProjectError::CreateFailed(e.to_string()).into())
         }
-        Err(e) => Err(ProjectError::CreateFailed(e.to_string()).into()),
     }
 }
 
@@ -229,7 +531,20 @@ pub async fn update_project(
         cleanup_script,
         copy_files,
         worktree_dir,
+        push_remote,
+        pr_remote,
+        always_run_cleanup,
+        setup_cache_files,
+        setup_cache_artifacts,
+        script_language,
+        preview_url_pattern,
+        env_file,
+        env_file_required,
+        dev_scripts,
+        reuse_worktree,
     } = payload;
+    let push_remote = normalize_remote(push_remote);
+    let pr_remote = normalize_remote(pr_remote);
 
     // Validate worktree_dir if provided
     if let Some(ref dir) = worktree_dir {
@@ -273,6 +588,7 @@ pub async fn update_project(
         let _ = std::fs::remove_file(&test_file);
     }
 
+    let previous_repo_path = existing_project.git_repo_path.clone();
     // If git_repo_path is being changed, check if the new path is already used by another project
     let git_repo_path = if let Some(new_git_repo_path) = git_repo_path.map(|s| expand_tilde(&s))
         && new_git_repo_path != existing_project.git_repo_path
@@ -299,6 +615,25 @@ pub async fn update_project(
         existing_project.git_repo_path
     };
 
+    if let Some(message) =
+        invalid_remote_message(&deployment, &git_repo_path, &push_remote, &pr_remote)
+    {
+        return Ok(ResponseJson(ApiResponse::error(&message)));
+    }
+
+    if let Some(message) = unavailable_script_language_message(&script_language) {
+        return Ok(ResponseJson(ApiResponse::error(&message)));
+    }
+
+    if let Some(message) = invalid_preview_url_pattern_message(&preview_url_pattern) {
+        return Ok(ResponseJson(ApiResponse::error(&message)));
+    }
+
+    let dev_scripts = dev_scripts.unwrap_or(existing_project.dev_scripts.0);
+    if let Some(message) = invalid_dev_scripts_message(&dev_scripts) {
+        return Ok(ResponseJson(ApiResponse::error(&message)));
+    }
+
     match Project::update(
         &deployment.db().pool,
         existing_project.id,
@@ -309,10 +644,29 @@ pub async fn update_project(
         cleanup_script,
         copy_files,
         worktree_dir,
+        push_remote,
+        pr_remote,
+        always_run_cleanup.unwrap_or(existing_project.always_run_cleanup),
+        setup_cache_files,
+        setup_cache_artifacts,
+        script_language,
+        preview_url_pattern,
+        env_file,
+        env_file_required.unwrap_or(existing_project.env_file_required),
+        &dev_scripts,
+        reuse_worktree.unwrap_or(existing_project.reuse_worktree),
     )
     .await
     {
-        Ok(project) => Ok(ResponseJson(ApiResponse::success(project))),
+        Ok(project) => {
+            if project.git_repo_path != previous_repo_path {
+                deployment
+                    .container()
+                    .repo_config()
+                    .unwatch(&previous_repo_path);
+            }
+            Ok(ResponseJson(ApiResponse::success(project)))
+        }
         Err(e) => {
             tracing::error!("Failed to update project: {}", e);
             Err(StatusCode::INTERNAL_SERVER_ERROR)
@@ -329,6 +683,10 @@ pub async fn delete_project(
             if rows_affected == 0 {
                 Err(StatusCode::NOT_FOUND)
             } else {
+                deployment
+                    .container()
+                    .repo_config()
+                    .unwatch(&project.git_repo_path);
                 deployment
                     .track_if_analytics_allowed(
                         "project_deleted",
@@ -389,6 +747,66 @@ pub async fn open_project_in_editor(
     }
 }
 
+/// A dev server running in one of the project's attempts
+#[derive(Debug, Serialize, TS)]
+pub struct RunningDevServer {
+    pub execution_process_id: Uuid,
+    pub task_attempt_id: Uuid,
+    /// Name from the project's `dev_scripts`; `None` for the main `dev_script`
+    pub name: Option<String>,
+    pub preview_url: Option<String>,
+    /// Port of `preview_url`, once the dev server has reported it
+    pub port: Option<u16>,
+}
+
+/// List the project's running dev servers with their names and ports
+pub async fn get_running_dev_servers(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<RunningDevServer>>>, ApiError> {
+    let running =
+        ExecutionProcess::find_running_dev_servers_by_project(&deployment.db().pool, project.id)
+            .await?
+            .into_iter()
+            .map(|process| RunningDevServer {
+                execution_process_id: process.id,
+                task_attempt_id: process.task_attempt_id,
+                name: process.dev_server_name().map(str::to_string),
+                port: process
+                    .preview_url
+                    .as_deref()
+                    .and_then(|url| reqwest::Url::parse(url).ok())
+                    .and_then(|url| url.port_or_known_default()),
+                preview_url: process.preview_url,
+            })
+            .collect();
+    Ok(ResponseJson(ApiResponse::success(running)))
+}
+
+/// Stop dev servers of every other project, including ones left running by a previous server
+/// run, so they release their ports
+pub async fn stop_other_dev_servers(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<StoppedDevServer>>>, ApiError> {
+    let stopped = deployment
+        .container()
+        .stop_dev_servers_except(&[project.id])
+        .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "other_dev_servers_stopped",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "stopped_count": stopped.len(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(stopped)))
+}
+
 pub async fn search_project_files(
     State(deployment): State<DeploymentImpl>,
     Extension(project): Extension<Project>,
@@ -452,6 +870,60 @@ pub async fn search_project_files(
     }
 }
 
+/// Default and maximum number of matches sent by the streaming file search
+const STREAM_SEARCH_DEFAULT_LIMIT: usize = 200;
+const STREAM_SEARCH_MAX_LIMIT: usize = 2000;
+
+#[derive(Debug, Deserialize)]
+pub struct StreamSearchQuery {
+    pub q: String,
+    #[serde(default)]
+    pub mode: SearchMode,
+    pub limit: Option<usize>,
+}
+
+/// Stream file search matches as server-sent `result` events while the repository is walked,
+/// followed by a `done` event. Unlike `/search` the results are unranked; closing the
+/// connection stops the walk.
+pub async fn stream_project_file_search(
+    State(deployment): State<DeploymentImpl>,
+    Extension(project): Extension<Project>,
+    Query(search_query): Query<StreamSearchQuery>,
+) -> Result<Sse<impl Stream<Item = Result<Event, BoxError>>>, ApiError> {
+    let query = search_query.q.trim();
+    if query.is_empty() {
+        return Err(ApiError::Validation(
+            "Query parameter 'q' is required and cannot be empty".to_string(),
+        ));
+    }
+    if !project.git_repo_path.exists() {
+        return Err(ApiError::Validation(
+            "Repository path does not exist".to_string(),
+        ));
+    }
+    let limit = search_query
+        .limit
+        .unwrap_or(STREAM_SEARCH_DEFAULT_LIMIT)
+        .clamp(1, STREAM_SEARCH_MAX_LIMIT);
+
+    let receiver = deployment.filesystem().stream_search(
+        project.git_repo_path.clone(),
+        query,
+        search_query.mode,
+        limit,
+    );
+    let results = stream::unfold(receiver, |mut receiver| async move {
+        let result = receiver.recv().await?;
+        Some((Event::default().event("result").json_data(result), receiver))
+    });
+    let done = stream::once(async { Ok::<_, BoxError>(Event::default().event("done").data("")) });
+
+    Ok(
+        Sse::new(results.map_err(|e| -> BoxError { e.into() }).chain(done))
+            .keep_alive(KeepAlive::default()),
+    )
+}
+
 async fn search_files_in_repo(
     repo_path: &str,
     query: &str,
@@ -466,84 +938,12 @@ async fn search_files_in_repo(
     let mut results = Vec::new();
     let query_lower = query.to_lowercase();
 
-    // Configure walker based on mode
-    let walker = match mode {
-        SearchMode::Settings => {
-            // Settings mode: Include ignored files but exclude performance killers
-            WalkBuilder::new(repo_path)
-                .git_ignore(false) // Include ignored files like .env
-                .git_global(false)
-                .git_exclude(false)
-                .hidden(false)
-                .filter_entry(|entry| {
-                    let name = entry.file_name().to_string_lossy();
-                    // Always exclude .git directories and performance killers
-                    name != ".git"
-                        && name != "node_modules"
-                        && name != "target"
-                        && name != "dist"
-                        && name != "build"
-                })
-                .build()
-        }
-        SearchMode::TaskForm => {
-            // Task form mode: Respect gitignore (cleaner results)
-            WalkBuilder::new(repo_path)
-                .git_ignore(true) // Respect .gitignore
-                .git_global(true) // Respect global .gitignore
-                .git_exclude(true) // Respect .git/info/exclude
-                .hidden(false) // Still show hidden files like .env (if not gitignored)
-                .filter_entry(|entry| {
-                    let name = entry.file_name().to_string_lossy();
-                    name != ".git"
-                })
-                .build()
-        }
-    };
-
-    for result in walker {
+    for result in FilesystemService::search_walker(repo_path, &mode, || false) {
         let entry = result?;
-        let path = entry.path();
-
-        // Skip the root directory itself
-        if path == repo_path {
-            continue;
-        }
-
-        let relative_path = path.strip_prefix(repo_path)?;
-        let relative_path_str = relative_path.to_string_lossy().to_lowercase();
-
-        let file_name = path
-            .file_name()
-            .map(|name| name.to_string_lossy().to_lowercase())
-            .unwrap_or_default();
-
-        // Check for matches
-        if file_name.contains(&query_lower) {
-            results.push(SearchResult {
-                path: relative_path.to_string_lossy().to_string(),
-                is_file: path.is_file(),
-                match_type: SearchMatchType::FileName,
-            });
-        } else if relative_path_str.contains(&query_lower) {
-            // Check if it's a directory name match or full path match
-            let match_type = if path
-                .parent()
-                .and_then(|p| p.file_name())
-                .map(|name| name.to_string_lossy().to_lowercase())
-                .unwrap_or_default()
-                .contains(&query_lower)
-            {
-                SearchMatchType::DirectoryName
-            } else {
-                SearchMatchType::FullPath
-            };
-
-            results.push(SearchResult {
-                path: relative_path.to_string_lossy().to_string(),
-                is_file: path.is_file(),
-                match_type,
-            });
+        if let Some(result) =
+            FilesystemService::match_search_entry(repo_path, entry.path(), &query_lower)
+        {
+            results.push(result);
         }
     }
 
@@ -587,8 +987,14 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
             get(get_project).put(update_project).delete(delete_project),
         )
         .route("/branches", get(get_project_branches))
+        .route("/repo-config", get(get_project_repo_config))
+        .route("/repo-config/trust", put(trust_project_repo_config))
+        .route("/default-base-branch", put(set_project_default_base_branch))
         .route("/search", get(search_project_files))
+        .route("/search/stream", get(stream_project_file_search))
         .route("/open-editor", post(open_project_in_editor))
+        .route("/dev-servers", get(get_running_dev_servers))
+        .route("/stop-other-dev-servers", post(stop_other_dev_servers))
         .layer(from_fn_with_state(
             deployment.clone(),
             load_project_middleware,
@@ -596,6 +1002,7 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
 
     let projects_router = Router::new()
         .route("/", get(get_projects).post(create_project))
+        .route("/create/stream", post(create_project_stream))
         .nest("/{id}", project_id_router);
 
     Router::new().nest("/projects", projects_router)
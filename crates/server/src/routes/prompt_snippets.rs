@@ -0,0 +1,139 @@
+use axum::{
+    Extension, Json, Router,
+    extract::{Query, State},
+    middleware::from_fn_with_state,
+    response::Json as ResponseJson,
+    routing::get,
+};
+use db::models::{
+    project::Project,
+    prompt_snippet::{
+        CreatePromptSnippet, PromptSnippet, UpdatePromptSnippet, is_valid_snippet_name,
+    },
+};
+use deployment::Deployment;
+use serde::Deserialize;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, middleware::load_prompt_snippet_middleware};
+
+#[derive(Debug, Deserialize)]
+pub struct PromptSnippetQuery {
+    /// Include this project's snippets next to the global ones
+    pub project_id: Option<Uuid>,
+}
+
+fn validate_snippet_name(name: &str) -> Result<(), ApiError> {
+    if !is_valid_snippet_name(name) {
+        return Err(ApiError::Validation(format!(
+            "Invalid snippet name '{name}'; use letters, digits, '-', '_' and '.'"
+        )));
+    }
+    Ok(())
+}
+
+fn map_duplicate_name(err: sqlx::Error, name: &str) -> ApiError {
+    match &err {
+        sqlx::Error::Database(db_err) if db_err.is_unique_violation() => ApiError::Conflict(
+            format!("A snippet named '{name}' already exists in this scope"),
+        ),
+        _ => ApiError::Database(err),
+    }
+}
+
+pub async fn get_prompt_snippets(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<PromptSnippetQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<PromptSnippet>>>, ApiError> {
+    let snippets = PromptSnippet::find_for_project(&deployment.db().pool, query.project_id).await?;
+    Ok(ResponseJson(ApiResponse::success(snippets)))
+}
+
+pub async fn get_prompt_snippet(
+    Extension(snippet): Extension<PromptSnippet>,
+) -> Result<ResponseJson<ApiResponse<PromptSnippet>>, ApiError> {
+    Ok(Json(ApiResponse::success(snippet)))
+}
+
+pub async fn create_prompt_snippet(
+    State(deployment): State<DeploymentImpl>,
+    Json(mut payload): Json<CreatePromptSnippet>,
+) -> Result<ResponseJson<ApiResponse<PromptSnippet>>, ApiError> {
+    payload.name = payload.name.trim().to_string();
+    validate_snippet_name(&payload.name)?;
+
+    let pool = &deployment.db().pool;
+    if let Some(project_id) = payload.project_id
+        && Project::find_by_id(pool, project_id).await?.is_none()
+    {
+        return Err(ApiError::Validation(format!(
+            "Project {project_id} does not exist"
+        )));
+    }
+
+    let snippet = PromptSnippet::create(pool, &payload)
+        .await
+        .map_err(|e| map_duplicate_name(e, &payload.name))?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "prompt_snippet_created",
+            serde_json::json!({
+                "snippet_id": snippet.id.to_string(),
+                "global": snippet.project_id.is_none(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(snippet)))
+}
+
+pub async fn update_prompt_snippet(
+    Extension(snippet): Extension<PromptSnippet>,
+    State(deployment): State<DeploymentImpl>,
+    Json(mut payload): Json<UpdatePromptSnippet>,
+) -> Result<ResponseJson<ApiResponse<PromptSnippet>>, ApiError> {
+    payload.name = payload.name.map(|name| name.trim().to_string());
+    if let Some(name) = &payload.name {
+        validate_snippet_name(name)?;
+    }
+
+    let updated_snippet = PromptSnippet::update(&deployment.db().pool, snippet.id, &payload)
+        .await
+        .map_err(|e| map_duplicate_name(e, payload.name.as_deref().unwrap_or(&snippet.name)))?;
+
+    Ok(ResponseJson(ApiResponse::success(updated_snippet)))
+}
+
+pub async fn delete_prompt_snippet(
+    Extension(snippet): Extension<PromptSnippet>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let rows_affected = PromptSnippet::delete(&deployment.db().pool, snippet.id).await?;
+    if rows_affected == 0 {
+        Err(ApiError::Database(sqlx::Error::RowNotFound))
+    } else {
+        Ok(ResponseJson(ApiResponse::success(())))
+    }
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let snippet_router = Router::new()
+        .route(
+            "/",
+            get(get_prompt_snippet)
+                .put(update_prompt_snippet)
+                .delete(delete_prompt_snippet),
+        )
+        .layer(from_fn_with_state(
+            deployment.clone(),
+            load_prompt_snippet_middleware,
+        ));
+
+    let inner = Router::new()
+        .route("/", get(get_prompt_snippets).post(create_prompt_snippet))
+        .nest("/{snippet_id}", snippet_router);
+
+    Router::new().nest("/prompt-snippets", inner)
+}
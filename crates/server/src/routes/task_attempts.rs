@@ -1,4 +1,7 @@
 pub mod drafts;
+pub mod file_reviews;
+pub mod kv;
+pub mod summary;
 pub mod util;
 
 use axum::{
@@ -26,33 +29,47 @@ use executors::{
     actions::{
         ExecutorAction, ExecutorActionType,
         coding_agent_follow_up::CodingAgentFollowUpRequest,
-        script::{ScriptContext, ScriptRequest, ScriptRequestLanguage},
+        script::{ScriptContext, ScriptRequest},
     },
+    logs::cap_command_output,
     profile::ExecutorProfileId,
 };
 use git2::BranchType;
 use serde::{Deserialize, Serialize};
 use services::services::{
     container::ContainerService,
-    git::{ConflictOp, WorktreeResetOptions},
+    events::{AttemptGitOperation, AttemptGitUpdate},
+    git::{
+        BlameLine, ConflictOp, ConflictResolution, DiffContentOptions, DiffPathFilter,
+        FileConflict, GitService, GitServiceError, MergePreview, WorktreeResetOptions,
+    },
     github_service::{CreatePrRequest, GitHubService, GitHubServiceError},
+    prompt_snippets::PromptSnippetService,
 };
 use sqlx::Error as SqlxError;
 use ts_rs::TS;
-use utils::response::ApiResponse;
+use utils::{
+    log_msg::LogMsg,
+    pagination::{Page, PageQuery},
+    response::ApiResponse,
+};
 use uuid::Uuid;
 
 use crate::{
     DeploymentImpl,
     error::ApiError,
     middleware::load_task_attempt_middleware,
-    routes::task_attempts::util::{ensure_worktree_path, handle_images_for_prompt},
+    routes::task_attempts::util::{
+        ensure_worktree_path, expand_prompt_snippets, handle_images_for_prompt,
+    },
 };
 
 #[derive(Debug, Deserialize, Serialize, TS)]
 pub struct RebaseTaskAttemptRequest {
     pub old_base_branch: Option<String>,
     pub new_base_branch: Option<String>,
+    /// Rebase onto this commit instead of the tip of the new base branch
+    pub onto_commit: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, TS)]
@@ -86,6 +103,84 @@ pub struct ReplaceProcessResult {
     pub new_execution_id: Option<Uuid>,
 }
 
+#[derive(Debug, Deserialize, Serialize, TS)]
+pub struct FollowUpFromCommitRequest {
+    /// Commit on the attempt branch to continue from (full or abbreviated OID)
+    pub commit_oid: String,
+    /// Prompt for the follow-up started from that commit
+    pub prompt: String,
+    /// Optional variant override
+    pub variant: Option<String>,
+    /// If true, discard uncommitted changes instead of refusing to reset
+    pub force_when_dirty: Option<bool>,
+    /// If set, keep the current branch tip on a new branch with this name before resetting
+    pub backup_branch: Option<String>,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct FollowUpFromCommitResult {
+    pub previous_head_oid: String,
+    pub target_oid: String,
+    pub backup_branch: Option<String>,
+    pub new_execution_id: Uuid,
+}
+
+#[derive(Debug, Deserialize, Serialize, TS)]
+pub struct ResetAttemptRequest {
+    /// If true, discard uncommitted changes instead of refusing to reset
+    pub force_when_dirty: Option<bool>,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct ResetAttemptResult {
+    pub previous_head_oid: String,
+    pub base_oid: String,
+    pub dropped_count: i64,
+}
+
+#[derive(Debug, Deserialize, Serialize, TS)]
+pub struct RecoverAttemptRequest {
+    /// If true, discard uncommitted changes instead of refusing to reset
+    pub force_when_dirty: Option<bool>,
+    /// Send this follow-up once the worktree is restored, to let the agent continue
+    pub follow_up_prompt: Option<String>,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct RecoverAttemptResult {
+    pub previous_head_oid: String,
+    /// The last commit a process completed successfully on, or the crashed process's
+    /// starting commit when none did
+    pub restored_oid: String,
+    pub follow_up: Option<ExecutionProcess>,
+}
+
+#[derive(Debug, Deserialize, Serialize, TS)]
+pub struct ExecCommandRequest {
+    /// Shell command run in the attempt's worktree
+    pub command: String,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct ExecCommandResult {
+    /// None if the command was killed, e.g. after running too long
+    pub exit_code: Option<i32>,
+    /// Interleaved stdout and stderr, keeping only the tail of long output
+    pub output: String,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct MergeResult {
+    /// Whether the target branch was pushed after merging, see `auto_push_after_merge`
+    pub pushed: bool,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct DiffSummaryResult {
+    pub executor_profile_id: ExecutorProfileId,
+    pub summary: String,
+}
+
 #[derive(Debug, Deserialize, Serialize, TS)]
 pub struct CreateGitHubPrRequest {
     pub title: String,
@@ -103,20 +198,46 @@ pub struct FollowUpResponse {
 #[derive(Debug, Deserialize)]
 pub struct TaskAttemptQuery {
     pub task_id: Option<Uuid>,
+    /// List pinned attempts before the rest
+    #[serde(default)]
+    pub pinned_first: bool,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct DiffStreamQuery {
     #[serde(default)]
     pub stats_only: bool,
+    /// Hide whitespace-only changes
+    #[serde(default)]
+    pub ignore_whitespace: bool,
+    /// Only changes since the attempt's reviewed commit; the full diff when no marker is set
+    #[serde(default)]
+    pub since_reviewed: bool,
+    /// Comma-separated path globs; `!`-prefixed globs exclude (e.g. `src/**,!**/*.lock`)
+    pub path_filter: Option<String>,
+}
+
+fn parse_diff_path_filter(path_filter: &str) -> Result<Option<DiffPathFilter>, ApiError> {
+    let globs: Vec<&str> = path_filter
+        .split(',')
+        .map(str::trim)
+        .filter(|glob| !glob.is_empty())
+        .collect();
+    if globs.is_empty() {
+        return Ok(None);
+    }
+    DiffPathFilter::new(&globs)
+        .map(Some)
+        .map_err(|e| ApiError::Validation(format!("Invalid path filter: {e}")))
 }
 
 pub async fn get_task_attempts(
     State(deployment): State<DeploymentImpl>,
     Query(query): Query<TaskAttemptQuery>,
-) -> Result<ResponseJson<ApiResponse<Vec<TaskAttempt>>>, ApiError> {
+    Query(page): Query<PageQuery>,
+) -> Result<ResponseJson<ApiResponse<Page<TaskAttempt>>>, ApiError> {
     let pool = &deployment.db().pool;
-    let attempts = TaskAttempt::fetch_all(pool, query.task_id).await?;
+    let attempts = TaskAttempt::fetch_page(pool, query.task_id, query.pinned_first, &page).await?;
     Ok(ResponseJson(ApiResponse::success(attempts)))
 }
 
@@ -132,7 +253,15 @@ pub struct CreateTaskAttemptBody {
     pub task_id: Uuid,
     /// Executor profile specification
     pub executor_profile_id: ExecutorProfileId,
-    pub base_branch: String,
+    /// Branch to base the attempt on. Defaults to the project's default base branch, then to
+    /// the repository's default branch.
+    #[serde(default)]
+    #[ts(optional)]
+    pub base_branch: Option<String>,
+    /// Run the setup script even if the project's setup cache can be restored
+    #[serde(default)]
+    #[ts(optional)]
+    pub force_setup: Option<bool>,
 }
 
 impl CreateTaskAttemptBody {
@@ -142,27 +271,87 @@ impl CreateTaskAttemptBody {
     }
 }
 
+/// Check that `payload`'s task can get a new attempt and resolve its base branch: the payload's,
+/// then the project's default, then the repository's default branch. The task's prompt must
+/// only reference known snippets.
+pub(crate) async fn validate_new_attempt(
+    deployment: &DeploymentImpl,
+    payload: &CreateTaskAttemptBody,
+) -> Result<(Task, String), ApiError> {
+    let task = Task::find_by_id(&deployment.db().pool, payload.task_id)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    if task.blocked_on_subtasks {
+        return Err(ApiError::Conflict(
+            "This task is waiting on its subtasks. Finish or cancel them before starting it."
+                .to_string(),
+        ));
+    }
+    let project = task
+        .parent_project(&deployment.db().pool)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    PromptSnippetService::expand(&deployment.db().pool, project.id, &task.to_prompt()).await?;
+
+    let base_branch = match payload
+        .base_branch
+        .as_deref()
+        .map(str::trim)
+        .filter(|b| !b.is_empty())
+        .or(project.default_base_branch.as_deref())
+    {
+        Some(branch) => branch.to_string(),
+        None => deployment
+            .git()
+            .get_default_branch(&project.git_repo_path)?
+            .ok_or_else(|| {
+                ApiError::Validation(
+                    "The project's repository has no branches yet; make an initial commit first"
+                        .to_string(),
+                )
+            })?,
+    };
+
+    // Base branch may be local or remote (e.g. "origin/main"); reject unknown branches up front
+    match deployment
+        .git()
+        .find_branch_type(&project.git_repo_path, &base_branch)
+    {
+        Ok(_) => {}
+        Err(GitServiceError::BranchNotFound(branch)) => {
+            return Err(ApiError::Validation(format!(
+                "Base branch '{branch}' does not exist locally or on any remote"
+            )));
+        }
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok((task, base_branch))
+}
+
 #[axum::debug_handler]
 pub async fn create_task_attempt(
     State(deployment): State<DeploymentImpl>,
     Json(payload): Json<CreateTaskAttemptBody>,
 ) -> Result<ResponseJson<ApiResponse<TaskAttempt>>, ApiError> {
     let executor_profile_id = payload.get_executor_profile_id();
-    let task = Task::find_by_id(&deployment.db().pool, payload.task_id)
-        .await?
-        .ok_or(SqlxError::RowNotFound)?;
+    let (task, base_branch) = validate_new_attempt(&deployment, &payload).await?;
+    deployment
+        .image()
+        .resolve_prompt_images(task.id, &task.to_prompt(), &[])
+        .await?;
 
     let attempt_id = Uuid::new_v4();
     let git_branch_name = deployment
         .container()
-        .git_branch_from_task_attempt(&attempt_id, &task.title)
+        .git_branch_from_task_attempt(&attempt_id, &task.id, &task.title)
         .await;
 
     let task_attempt = TaskAttempt::create(
         &deployment.db().pool,
         &CreateTaskAttempt {
             executor: executor_profile_id.executor,
-            base_branch: payload.base_branch.clone(),
+            base_branch,
             branch: git_branch_name.clone(),
         },
         attempt_id,
@@ -172,7 +361,11 @@ pub async fn create_task_attempt(
 
     let execution_process = deployment
         .container()
-        .start_attempt(&task_attempt, executor_profile_id.clone())
+        .start_attempt(
+            &task_attempt,
+            executor_profile_id.clone(),
+            payload.force_setup.unwrap_or(false),
+        )
         .await?;
 
     deployment
@@ -189,9 +382,113 @@ pub async fn create_task_attempt(
 
     tracing::info!("Started execution process {}", execution_process.id);
 
+    if deployment.config().read().await.open_editor_on_start {
+        open_worktree_in_editor(&deployment, task_attempt.id).await;
+    }
+
     Ok(ResponseJson(ApiResponse::success(task_attempt)))
 }
 
+/// Open a just-started attempt's worktree in the configured editor. Failures are only logged
+/// since the attempt itself is already running.
+async fn open_worktree_in_editor(deployment: &DeploymentImpl, attempt_id: Uuid) {
+    let worktree = match TaskAttempt::find_by_id(&deployment.db().pool, attempt_id).await {
+        Ok(Some(TaskAttempt {
+            container_ref: Some(worktree),
+            ..
+        })) => worktree,
+        Ok(_) => {
+            tracing::warn!("No worktree to open in editor for attempt {}", attempt_id);
+            return;
+        }
+        Err(e) => {
+            tracing::warn!(
+                "Failed to load attempt {} to open editor: {}",
+                attempt_id,
+                e
+            );
+            return;
+        }
+    };
+
+    let editor_config = deployment.config().read().await.editor.clone();
+    if let Err(e) = editor_config.open_file(&worktree) {
+        tracing::warn!(
+            "Failed to open editor for attempt {} at {}: {}",
+            attempt_id,
+            worktree,
+            e
+        );
+    }
+}
+
+/// Create a new attempt with the same base branch and initial prompt/profile as an existing one.
+/// The clone gets its own branch and worktree.
+pub async fn clone_task_attempt(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<TaskAttempt>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let initial_request =
+        ExecutionProcess::initial_request_for_attempt(pool, task_attempt.id).await?;
+    let executor_profile_id = initial_request.executor_profile_id.clone();
+
+    let task = task_attempt
+        .parent_task(pool)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+
+    let attempt_id = Uuid::new_v4();
+    let git_branch_name = deployment
+        .container()
+        .git_branch_from_task_attempt(&attempt_id, &task.id, &task.title)
+        .await;
+
+    let cloned_attempt = TaskAttempt::create(
+        pool,
+        &CreateTaskAttempt {
+            executor: executor_profile_id.executor,
+            base_branch: task_attempt.target_branch.clone(),
+            branch: git_branch_name,
+        },
+        attempt_id,
+        task.id,
+    )
+    .await?;
+
+    let execution_process = deployment
+        .container()
+        .clone_attempt(&task_attempt, &cloned_attempt, initial_request)
+        .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_attempt_cloned",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "variant": &executor_profile_id.variant,
+                "executor": &executor_profile_id.executor,
+                "source_attempt_id": task_attempt.id.to_string(),
+                "attempt_id": cloned_attempt.id.to_string(),
+            }),
+        )
+        .await;
+
+    tracing::info!(
+        "Cloned task attempt {} into {}, started execution process {}",
+        task_attempt.id,
+        cloned_attempt.id,
+        execution_process.id
+    );
+
+    // Reload so the response includes the new container_ref
+    let cloned_attempt = TaskAttempt::find_by_id(pool, cloned_attempt.id)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+
+    Ok(ResponseJson(ApiResponse::success(cloned_attempt)))
+}
+
 #[derive(Debug, Deserialize, TS)]
 pub struct CreateFollowUpAttempt {
     pub prompt: String,
@@ -245,6 +542,17 @@ pub async fn follow_up(
         None
     };
 
+    // Reject unknown snippets and broken image references before a retry resets anything
+    let prompt = expand_prompt_snippets(&deployment, &task_attempt, &payload.prompt).await?;
+    let image_ids = deployment
+        .image()
+        .resolve_prompt_images(
+            task.id,
+            &prompt,
+            payload.image_ids.as_deref().unwrap_or_default(),
+        )
+        .await?;
+
     // If retry settings provided, perform replace-logic before proceeding
     if let Some(proc_id) = payload.retry_process_id {
         let pool = &deployment.db().pool;
@@ -310,11 +618,8 @@ pub async fn follow_up(
     )
     .await?;
 
-    let mut prompt = payload.prompt;
-    if let Some(image_ids) = &payload.image_ids {
-        prompt = handle_images_for_prompt(&deployment, &task_attempt, task.id, image_ids, &prompt)
-            .await?;
-    }
+    let mut prompt =
+        handle_images_for_prompt(&deployment, &task_attempt, &image_ids, &prompt).await?;
 
     // Apply agent configuration if present
     if let Some(agent) = &agent {
@@ -352,9 +657,7 @@ pub async fn follow_up(
         executor_profile_id.clone()
     };
 
-    let cleanup_action = deployment
-        .container()
-        .cleanup_action(project.cleanup_script);
+    let cleanup_action = deployment.container().cleanup_action(&project).await;
 
     let action_type = if let Some(session_id) = latest_session_id {
         ExecutorActionType::CodingAgentFollowUpRequest(CodingAgentFollowUpRequest {
@@ -417,6 +720,7 @@ pub async fn replace_process(
             "Process does not belong to this attempt".to_string(),
         )));
     }
+    let prompt = expand_prompt_snippets(&deployment, &task_attempt, &payload.prompt).await?;
 
     // Determine target reset OID: before the target process
     let mut target_before_oid = process.before_head_commit.clone();
@@ -484,7 +788,7 @@ pub async fn replace_process(
 
     let action = if let Some(session_id) = latest_session_id {
         let follow_up_request = CodingAgentFollowUpRequest {
-            prompt: payload.prompt.clone(),
+            prompt,
             session_id,
             executor_profile_id,
         };
@@ -497,7 +801,7 @@ pub async fn replace_process(
         ExecutorAction::new(
             ExecutorActionType::CodingAgentInitialRequest(
                 executors::actions::coding_agent_initial::CodingAgentInitialRequest {
-                    prompt: payload.prompt.clone(),
+                    prompt,
                     executor_profile_id,
                 },
             ),
@@ -523,81 +827,453 @@ pub async fn replace_process(
     })))
 }
 
+/// Reset the attempt branch to an earlier commit and continue the existing agent session
+/// from there. Unlike `replace_process`, no execution history is dropped.
 #[axum::debug_handler]
-pub async fn stream_task_attempt_diff_ws(
-    ws: WebSocketUpgrade,
-    Query(params): Query<DiffStreamQuery>,
+pub async fn follow_up_from_commit(
     Extension(task_attempt): Extension<TaskAttempt>,
     State(deployment): State<DeploymentImpl>,
-) -> impl IntoResponse {
-    let stats_only = params.stats_only;
-    ws.on_upgrade(move |socket| async move {
-        if let Err(e) =
-            handle_task_attempt_diff_ws(socket, deployment, task_attempt, stats_only).await
-        {
-            tracing::warn!("diff WS closed: {}", e);
-        }
-    })
-}
+    Json(payload): Json<FollowUpFromCommitRequest>,
+) -> Result<ResponseJson<ApiResponse<FollowUpFromCommitResult>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let force_when_dirty = payload.force_when_dirty.unwrap_or(false);
+    let prompt = expand_prompt_snippets(&deployment, &task_attempt, &payload.prompt).await?;
+    let wt_buf = ensure_worktree_path(&deployment, &task_attempt).await?;
+    let wt = wt_buf.as_path();
 
-async fn handle_task_attempt_diff_ws(
-    socket: WebSocket,
-    deployment: DeploymentImpl,
-    task_attempt: TaskAttempt,
-    stats_only: bool,
-) -> anyhow::Result<()> {
-    use futures_util::{SinkExt, StreamExt, TryStreamExt};
-    use utils::log_msg::LogMsg;
+    let previous_head_oid = deployment.git().get_head_info(wt)?.oid;
+    let target_oid = deployment
+        .git()
+        .resolve_commit_oid(wt, payload.commit_oid.trim())
+        .map_err(|_| {
+            GitServiceError::InvalidCommitSelection(format!(
+                "commit '{}' not found",
+                payload.commit_oid
+            ))
+        })?;
+    if !deployment
+        .git()
+        .is_ancestor_commit(wt, &target_oid, &previous_head_oid)?
+    {
+        return Err(GitServiceError::InvalidCommitSelection(format!(
+            "commit {target_oid} is not on branch {}",
+            task_attempt.branch
+        ))
+        .into());
+    }
 
-    let stream = deployment
-        .container()
-        .stream_diff(&task_attempt, stats_only)
-        .await?;
+    if !force_when_dirty && !deployment.git().is_worktree_clean(wt)? {
+        return Err(ApiError::Conflict(
+            "Worktree has uncommitted changes. Commit or discard them, or retry with force_when_dirty."
+                .to_string(),
+        ));
+    }
 
-    let mut stream = stream.map_ok(|msg: LogMsg| msg.to_ws_message_unchecked());
+    let backup_branch = payload
+        .backup_branch
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty());
+    if let Some(name) = &backup_branch {
+        if !git2::Branch::name_is_valid(name).unwrap_or(false) {
+            return Err(ApiError::Validation(format!(
+                "'{name}' is not a valid branch name"
+            )));
+        }
+        if deployment.git().check_branch_exists(wt, name)? {
+            return Err(ApiError::Conflict(format!(
+                "Branch '{name}' already exists"
+            )));
+        }
+    }
 
-    let (mut sender, mut receiver) = socket.split();
+    // Nothing should be writing to the worktree while it is reset
+    deployment.container().try_stop(&task_attempt).await;
 
-    loop {
-        tokio::select! {
-            // Wait for next stream item
-            item = stream.next() => {
-                match item {
-                    Some(Ok(msg)) => {
-                        if sender.send(msg).await.is_err() {
-                            break;
-                        }
-                    }
-                    Some(Err(e)) => {
-                        tracing::error!("stream error: {}", e);
-                        break;
-                    }
-                    None => break,
-                }
-            }
-            // Detect client disconnection
-            msg = receiver.next() => {
-                if msg.is_none() {
-                    break;
-                }
-            }
-        }
+    if let Some(name) = &backup_branch {
+        deployment.git().create_branch(wt, name)?;
+    }
+    if target_oid != previous_head_oid {
+        deployment
+            .git()
+            .reset_worktree_to_commit(wt, &target_oid, force_when_dirty)?;
     }
-    Ok(())
-}
 
-#[derive(Debug, Serialize, TS)]
-pub struct CommitInfo {
-    pub sha: String,
-    pub subject: String,
+    let initial_executor_profile_id =
+        ExecutionProcess::latest_executor_profile_for_attempt(pool, task_attempt.id).await?;
+    let executor_profile_id = ExecutorProfileId {
+        executor: initial_executor_profile_id.executor,
+        variant: payload.variant.or(initial_executor_profile_id.variant),
+    };
+
+    let latest_session_id =
+        ExecutionProcess::find_latest_session_id_by_task_attempt(pool, task_attempt.id).await?;
+    let action = if let Some(session_id) = latest_session_id {
+        ExecutorAction::new(
+            ExecutorActionType::CodingAgentFollowUpRequest(CodingAgentFollowUpRequest {
+                prompt,
+                session_id,
+                executor_profile_id,
+            }),
+            None,
+        )
+    } else {
+        ExecutorAction::new(
+            ExecutorActionType::CodingAgentInitialRequest(
+                executors::actions::coding_agent_initial::CodingAgentInitialRequest {
+                    prompt,
+                    executor_profile_id,
+                },
+            ),
+            None,
+        )
+    };
+
+    let execution_process = deployment
+        .container()
+        .start_execution(
+            &task_attempt,
+            &action,
+            &ExecutionProcessRunReason::CodingAgent,
+        )
+        .await?;
+
+    Ok(ResponseJson(ApiResponse::success(
+        FollowUpFromCommitResult {
+            previous_head_oid,
+            target_oid,
+            backup_branch,
+            new_execution_id: execution_process.id,
+        },
+    )))
 }
 
-pub async fn get_commit_info(
+/// Reset the attempt branch to the commit it was created from, discarding all attempt commits
+/// and uncommitted changes, and soft-drop its execution history for a fresh start.
+#[axum::debug_handler]
+pub async fn reset_to_base(
     Extension(task_attempt): Extension<TaskAttempt>,
     State(deployment): State<DeploymentImpl>,
-    Query(params): Query<std::collections::HashMap<String, String>>,
-) -> Result<ResponseJson<ApiResponse<CommitInfo>>, ApiError> {
-    let Some(sha) = params.get("sha").cloned() else {
+    Json(payload): Json<ResetAttemptRequest>,
+) -> Result<ResponseJson<ApiResponse<ResetAttemptResult>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let force_when_dirty = payload.force_when_dirty.unwrap_or(false);
+    let wt_buf = ensure_worktree_path(&deployment, &task_attempt).await?;
+    let wt = wt_buf.as_path();
+
+    let project = task_attempt
+        .parent_task(pool)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?
+        .parent_project(pool)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    let base_oid = deployment
+        .git()
+        .get_base_commit(
+            &project.git_repo_path,
+            &task_attempt.branch,
+            &task_attempt.target_branch,
+        )?
+        .to_string();
+
+    if !force_when_dirty && !deployment.git().is_worktree_clean(wt)? {
+        return Err(ApiError::Conflict(
+            "Worktree has uncommitted changes. Commit or discard them, or retry with force_when_dirty."
+                .to_string(),
+        ));
+    }
+
+    // Nothing should be writing to the worktree while it is reset
+    deployment.container().try_stop(&task_attempt).await;
+
+    let previous_head_oid = deployment.git().get_head_info(wt)?.oid;
+    deployment
+        .git()
+        .reset_worktree_to_commit(wt, &base_oid, force_when_dirty)?;
+
+    let dropped_count = ExecutionProcess::drop_all_for_attempt(pool, task_attempt.id).await?;
+    // Best-effort: a pending retry draft no longer applies to the dropped history
+    let _ = Draft::clear_after_send(pool, task_attempt.id, DraftType::Retry).await;
+
+    Ok(ResponseJson(ApiResponse::success(ResetAttemptResult {
+        previous_head_oid,
+        base_oid,
+        dropped_count,
+    })))
+}
+
+/// Recover an attempt whose last process was orphaned by a crash (failed without an exit code)
+/// by resetting its worktree to the last known-good commit, optionally continuing with a
+/// follow-up.
+#[axum::debug_handler]
+pub async fn recover_attempt(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<RecoverAttemptRequest>,
+) -> Result<ResponseJson<ApiResponse<RecoverAttemptResult>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let force_when_dirty = payload.force_when_dirty.unwrap_or(false);
+
+    let processes: Vec<ExecutionProcess> =
+        ExecutionProcess::find_by_task_attempt_id(pool, task_attempt.id, false)
+            .await?
+            .into_iter()
+            .filter(|p| p.run_reason != ExecutionProcessRunReason::DevServer)
+            .collect();
+    if processes
+        .iter()
+        .any(|p| p.status == ExecutionProcessStatus::Running)
+    {
+        return Err(ApiError::Conflict(
+            "Attempt is still running; stop it before recovering".to_string(),
+        ));
+    }
+    let Some((crashed, earlier)) = processes.split_last() else {
+        return Err(ApiError::Validation("Attempt has no processes".to_string()));
+    };
+    if crashed.status != ExecutionProcessStatus::Failed || crashed.exit_code.is_some() {
+        return Err(ApiError::Validation(
+            "The attempt's last process did not crash".to_string(),
+        ));
+    }
+    let restored_oid = earlier
+        .iter()
+        .rev()
+        .filter(|p| p.status == ExecutionProcessStatus::Completed)
+        .find_map(|p| p.after_head_commit.clone())
+        .or_else(|| crashed.before_head_commit.clone())
+        .ok_or_else(|| {
+            ApiError::Validation("No known-good commit recorded for this attempt".to_string())
+        })?;
+
+    let wt_buf = ensure_worktree_path(&deployment, &task_attempt).await?;
+    let wt = wt_buf.as_path();
+    if !force_when_dirty && !deployment.git().is_worktree_clean(wt)? {
+        return Err(ApiError::Conflict(
+            "Worktree has uncommitted changes. Commit or discard them, or retry with force_when_dirty."
+                .to_string(),
+        ));
+    }
+
+    let previous_head_oid = deployment.git().get_head_info(wt)?.oid;
+    deployment
+        .git()
+        .reset_worktree_to_commit(wt, &restored_oid, force_when_dirty)?;
+
+    let follow_up = match payload
+        .follow_up_prompt
+        .filter(|prompt| !prompt.trim().is_empty())
+    {
+        Some(prompt) => {
+            let ResponseJson(response) = follow_up(
+                Extension(task_attempt.clone()),
+                State(deployment.clone()),
+                Json(CreateFollowUpAttempt {
+                    prompt,
+                    variant: None,
+                    agent_id: None,
+                    image_ids: None,
+                    retry_process_id: None,
+                    force_when_dirty: None,
+                    perform_git_reset: None,
+                }),
+            )
+            .await?;
+            response.into_data()
+        }
+        None => None,
+    };
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_attempt_recovered",
+            serde_json::json!({
+                "attempt_id": task_attempt.id.to_string(),
+                "follow_up": follow_up.is_some(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(RecoverAttemptResult {
+        previous_head_oid,
+        restored_oid,
+        follow_up,
+    })))
+}
+
+/// Run a one-off shell command in the attempt's worktree and wait for it to finish, or to be
+/// killed after a minute. This is arbitrary command execution with the server's privileges,
+/// like setup scripts, which is acceptable only because the server is bound to the local user.
+pub async fn exec_command(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<ExecCommandRequest>,
+) -> Result<ResponseJson<ApiResponse<ExecCommandResult>>, ApiError> {
+    use futures_util::StreamExt;
+
+    let command = payload.command.trim();
+    if command.is_empty() {
+        return Err(ApiError::Validation(
+            "Command must not be empty".to_string(),
+        ));
+    }
+
+    let handle = deployment
+        .container()
+        .exec_command(&task_attempt, command)
+        .await?;
+    // The store is marked finished once the command has exited and all its output is in
+    let output: String = handle
+        .msg_store
+        .history_plus_stream()
+        .take_while(|msg| futures_util::future::ready(!matches!(msg, Ok(LogMsg::Finished))))
+        .filter_map(|msg| async move {
+            match msg {
+                Ok(LogMsg::Stdout(s) | LogMsg::Stderr(s)) => Some(s),
+                _ => None,
+            }
+        })
+        .collect()
+        .await;
+    let exit_code = handle.exit_code.await.unwrap_or_else(|e| {
+        tracing::error!("Command {} monitor failed: {}", handle.id, e);
+        None
+    });
+
+    Ok(ResponseJson(ApiResponse::success(ExecCommandResult {
+        exit_code,
+        output: cap_command_output(output),
+    })))
+}
+
+/// Ask the attempt's coding agent to explain its diff. Opt-in via config because every
+/// summary that misses the cache spends agent usage.
+pub async fn get_diff_summary(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<DiffSummaryResult>>, ApiError> {
+    let (enabled, default_profile) = {
+        let config = deployment.config().read().await;
+        (
+            config.diff_summaries_enabled,
+            config.executor_profile.clone(),
+        )
+    };
+    if !enabled {
+        return Err(ApiError::Validation(
+            "Diff summaries are disabled; enable them in settings".to_string(),
+        ));
+    }
+
+    let executor_profile_id = ExecutionProcess::latest_executor_profile_for_attempt(
+        &deployment.db().pool,
+        task_attempt.id,
+    )
+    .await
+    .unwrap_or(default_profile);
+    let summary = deployment
+        .container()
+        .summarize_diff(&task_attempt, &executor_profile_id)
+        .await?;
+
+    Ok(ResponseJson(ApiResponse::success(DiffSummaryResult {
+        executor_profile_id,
+        summary,
+    })))
+}
+
+#[axum::debug_handler]
+pub async fn stream_task_attempt_diff_ws(
+    ws: WebSocketUpgrade,
+    Query(params): Query<DiffStreamQuery>,
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<impl IntoResponse, ApiError> {
+    let stats_only = params.stats_only;
+    let since_reviewed = params.since_reviewed;
+    let path_filter = match params.path_filter.as_deref() {
+        Some(path_filter) => parse_diff_path_filter(path_filter)?,
+        None => None,
+    };
+    let options = DiffContentOptions {
+        ignore_whitespace: params.ignore_whitespace,
+        path_filter,
+    };
+    Ok(ws.on_upgrade(move |socket| async move {
+        if let Err(e) = handle_task_attempt_diff_ws(
+            socket,
+            deployment,
+            task_attempt,
+            stats_only,
+            since_reviewed,
+            options,
+        )
+        .await
+        {
+            tracing::warn!("diff WS closed: {}", e);
+        }
+    }))
+}
+
+async fn handle_task_attempt_diff_ws(
+    socket: WebSocket,
+    deployment: DeploymentImpl,
+    task_attempt: TaskAttempt,
+    stats_only: bool,
+    since_reviewed: bool,
+    options: DiffContentOptions,
+) -> anyhow::Result<()> {
+    use futures_util::{SinkExt, StreamExt, TryStreamExt};
+
+    let stream = deployment
+        .container()
+        .stream_diff(&task_attempt, stats_only, since_reviewed, options)
+        .await?;
+
+    let mut stream = stream.map_ok(|msg: LogMsg| msg.to_ws_message_unchecked());
+
+    let (mut sender, mut receiver) = socket.split();
+
+    loop {
+        tokio::select! {
+            // Wait for next stream item
+            item = stream.next() => {
+                match item {
+                    Some(Ok(msg)) => {
+                        if sender.send(msg).await.is_err() {
+                            break;
+                        }
+                    }
+                    Some(Err(e)) => {
+                        tracing::error!("stream error: {}", e);
+                        break;
+                    }
+                    None => break,
+                }
+            }
+            // Detect client disconnection
+            msg = receiver.next() => {
+                if msg.is_none() {
+                    break;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct CommitInfo {
+    pub sha: String,
+    pub subject: String,
+}
+
+pub async fn get_commit_info(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+    Query(params): Query<std::collections::HashMap<String, String>>,
+) -> Result<ResponseJson<ApiResponse<CommitInfo>>, ApiError> {
+    let Some(sha) = params.get("sha").cloned() else {
         return Err(ApiError::TaskAttempt(TaskAttemptError::ValidationError(
             "Missing sha param".to_string(),
         )));
@@ -611,6 +1287,92 @@ pub async fn get_commit_info(
     })))
 }
 
+#[derive(Debug, Serialize, TS)]
+pub struct CommitExecutionProcess {
+    pub execution_process: ExecutionProcess,
+    pub prompt: Option<String>,
+}
+
+/// Find the execution process that produced a commit on this attempt.
+/// Returns None for commits that predate the attempt or were not made by any process.
+pub async fn get_commit_execution_process(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+    Query(params): Query<std::collections::HashMap<String, String>>,
+) -> Result<ResponseJson<ApiResponse<Option<CommitExecutionProcess>>>, ApiError> {
+    let Some(sha) = params.get("sha").cloned() else {
+        return Err(ApiError::TaskAttempt(TaskAttemptError::ValidationError(
+            "Missing sha param".to_string(),
+        )));
+    };
+    let pool = &deployment.db().pool;
+    let wt_buf = ensure_worktree_path(&deployment, &task_attempt).await?;
+    let wt = wt_buf.as_path();
+    let git = deployment.git();
+    let oid = git.resolve_commit_oid(wt, &sha)?;
+
+    let execution_process =
+        match ExecutionProcess::find_by_after_head_commit(pool, task_attempt.id, &oid).await? {
+            Some(process) => Some(process),
+            None => {
+                // A single process may create several commits; find the one whose
+                // before..after range contains this commit
+                ExecutionProcess::find_by_task_attempt_id(pool, task_attempt.id, false)
+                    .await?
+                    .into_iter()
+                    .find(|p| match (&p.before_head_commit, &p.after_head_commit) {
+                        (Some(before), Some(after)) => {
+                            before != after
+                                && git.is_ancestor_commit(wt, &oid, after).unwrap_or(false)
+                                && !git.is_ancestor_commit(wt, &oid, before).unwrap_or(true)
+                        }
+                        _ => false,
+                    })
+            }
+        };
+
+    Ok(ResponseJson(ApiResponse::success(execution_process.map(
+        |execution_process| CommitExecutionProcess {
+            prompt: execution_process.prompt().map(|p| p.to_string()),
+            execution_process,
+        },
+    ))))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BlameQuery {
+    pub path: String,
+}
+
+pub async fn get_task_attempt_blame(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<BlameQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<BlameLine>>>, ApiError> {
+    let worktree_path = ensure_worktree_path(&deployment, &task_attempt).await?;
+    let lines = deployment.git().blame(&worktree_path, &query.path)?;
+    Ok(ResponseJson(ApiResponse::success(lines)))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ConflictHunksQuery {
+    /// Repository-relative path of the conflicted file
+    pub path: String,
+}
+
+/// Conflict markers of one unresolved file, for rendering a 3-way view
+pub async fn get_task_attempt_conflict_hunks(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<ConflictHunksQuery>,
+) -> Result<ResponseJson<ApiResponse<FileConflict>>, ApiError> {
+    let worktree_path = ensure_worktree_path(&deployment, &task_attempt).await?;
+    let conflict = deployment
+        .git()
+        .get_conflict_hunks(&worktree_path, &query.path)?;
+    Ok(ResponseJson(ApiResponse::success(conflict)))
+}
+
 #[derive(Debug, Serialize, TS)]
 pub struct CommitCompareResult {
     pub head_oid: String,
@@ -651,7 +1413,7 @@ pub async fn compare_commit_to_head(
 pub async fn merge_task_attempt(
     Extension(task_attempt): Extension<TaskAttempt>,
     State(deployment): State<DeploymentImpl>,
-) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+) -> Result<ResponseJson<ApiResponse<MergeResult>>, ApiError> {
     let pool = &deployment.db().pool;
 
     let task = task_attempt
@@ -705,7 +1467,135 @@ pub async fn merge_task_attempt(
         )
         .await;
 
-    Ok(ResponseJson(ApiResponse::success(())))
+    push_attempt_git_update(
+        &deployment,
+        &ctx.project,
+        &ctx.task_attempt,
+        worktree_path,
+        AttemptGitOperation::Merge,
+    );
+
+    let pushed = push_after_merge(&deployment, &ctx.project, worktree_path, &task_attempt).await;
+    Ok(ResponseJson(ApiResponse::success(MergeResult { pushed })))
+}
+
+/// Stream where the attempt's branch stands after a rebase or merge so the UI doesn't stay
+/// stale until its next branch status poll. Counts that can't be computed, e.g. against a
+/// remote target branch, are left out.
+fn push_attempt_git_update(
+    deployment: &DeploymentImpl,
+    project: &Project,
+    task_attempt: &TaskAttempt,
+    worktree_path: &std::path::Path,
+    operation: AttemptGitOperation,
+) {
+    let git = deployment.git();
+    let head_oid = match git.get_head_info(worktree_path) {
+        Ok(head) => head.oid,
+        Err(e) => {
+            tracing::warn!(
+                "Failed to read HEAD of attempt {} after {:?}: {}",
+                task_attempt.id,
+                operation,
+                e
+            );
+            return;
+        }
+    };
+    let (commits_ahead, commits_behind) = git
+        .get_branch_status(
+            &project.git_repo_path,
+            &task_attempt.branch,
+            &task_attempt.target_branch,
+        )
+        .ok()
+        .unzip();
+    deployment
+        .events()
+        .push_attempt_git_update(&AttemptGitUpdate {
+            task_attempt_id: task_attempt.id,
+            task_id: task_attempt.task_id,
+            operation,
+            head_oid,
+            target_oid: git
+                .get_branch_oid(&project.git_repo_path, &task_attempt.target_branch)
+                .ok(),
+            commits_ahead,
+            commits_behind,
+        });
+}
+
+/// Push the freshly merged target branch when `auto_push_after_merge` is on. The merge has
+/// already landed, so a missing token or failed push is logged rather than returned.
+async fn push_after_merge(
+    deployment: &DeploymentImpl,
+    project: &Project,
+    worktree_path: &std::path::Path,
+    task_attempt: &TaskAttempt,
+) -> bool {
+    let (enabled, github_config) = {
+        let config = deployment.config().read().await;
+        (config.auto_push_after_merge, config.github.clone())
+    };
+    push_merged_target_branch(
+        deployment.git(),
+        enabled,
+        github_config.token(),
+        worktree_path,
+        &task_attempt.target_branch,
+        project.push_remote.as_deref(),
+    )
+}
+
+fn push_merged_target_branch(
+    git: &GitService,
+    enabled: bool,
+    github_token: Option<String>,
+    worktree_path: &std::path::Path,
+    target_branch: &str,
+    push_remote: Option<&str>,
+) -> bool {
+    if !enabled {
+        return false;
+    }
+    let Some(github_token) = github_token else {
+        tracing::warn!(
+            "Skipping push of {} after merging: no GitHub token configured",
+            target_branch
+        );
+        return false;
+    };
+
+    match git.push_to_github(worktree_path, target_branch, &github_token, push_remote) {
+        Ok(()) => true,
+        Err(e) => {
+            tracing::warn!("Failed to push {} after merging: {}", target_branch, e);
+            false
+        }
+    }
+}
+
+/// Dry run of `merge_task_attempt`: the diff that would land on the target branch and any
+/// files that would conflict
+pub async fn get_task_attempt_merge_preview(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<MergePreview>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let task = task_attempt
+        .parent_task(pool)
+        .await?
+        .ok_or(ApiError::TaskAttempt(TaskAttemptError::TaskNotFound))?;
+    let ctx = TaskAttempt::load_context(pool, task_attempt.id, task.id, task.project_id).await?;
+    let worktree_path = ensure_worktree_path(&deployment, &task_attempt).await?;
+
+    let preview = deployment.git().merge_preview(
+        &ctx.project.git_repo_path,
+        &worktree_path,
+        &ctx.task_attempt.branch,
+        &ctx.task_attempt.target_branch,
+    )?;
+    Ok(ResponseJson(ApiResponse::success(preview)))
 }
 
 pub async fn push_task_attempt_branch(
@@ -717,14 +1607,31 @@ pub async fn push_task_attempt_branch(
         return Err(GitHubServiceError::TokenInvalid.into());
     };
 
-    let github_service = GitHubService::new(&github_token)?;
-    github_service.check_token().await?;
+    deployment
+        .github_cache()
+        .verify_token(&github_token, false)
+        .await?;
+
+    let pool = &deployment.db().pool;
+    let task = task_attempt
+        .parent_task(pool)
+        .await?
+        .ok_or(ApiError::TaskAttempt(TaskAttemptError::TaskNotFound))?;
+    let project = Project::find_by_id(pool, task.project_id)
+        .await?
+        .ok_or(ApiError::Project(ProjectError::ProjectNotFound))?;
 
     let ws_path = ensure_worktree_path(&deployment, &task_attempt).await?;
 
+    deployment.git().push_to_github(
+        &ws_path,
+        &task_attempt.branch,
+        &github_token,
+        project.push_remote.as_deref(),
+    )?;
     deployment
-        .git()
-        .push_to_github(&ws_path, &task_attempt.branch, &github_token)?;
+        .github_cache()
+        .invalidate_remote_status(&project.git_repo_path, &task_attempt.branch);
     Ok(ResponseJson(ApiResponse::success(())))
 }
 
@@ -733,7 +1640,10 @@ pub async fn create_github_pr(
     State(deployment): State<DeploymentImpl>,
     Json(request): Json<CreateGitHubPrRequest>,
 ) -> Result<ResponseJson<ApiResponse<String, GitHubServiceError>>, ApiError> {
-    let github_config = deployment.config().read().await.github.clone();
+    let (github_config, title_branch_prefix) = {
+        let config = deployment.config().read().await;
+        (config.github.clone(), config.pr_title_branch_prefix.clone())
+    };
     let Some(github_token) = github_config.token() else {
         return Ok(ResponseJson(ApiResponse::error_with_data(
             GitHubServiceError::TokenInvalid,
@@ -741,19 +1651,14 @@ pub async fn create_github_pr(
     };
     // Create GitHub service instance
     let github_service = GitHubService::new(&github_token)?;
-    // Get the task attempt to access the stored target branch
-    let target_branch = request.target_branch.unwrap_or_else(|| {
-        // Use the stored target branch from the task attempt as the default
-        // Fall back to config default or "main" only if stored target branch is somehow invalid
-        if !task_attempt.target_branch.trim().is_empty() {
-            task_attempt.target_branch.clone()
-        } else {
-            github_config
-                .default_pr_base
-                .as_ref()
-                .map_or_else(|| "main".to_string(), |b| b.to_string())
-        }
-    });
+    // Prefer the requested base, then the attempt's stored target branch, then the config
+    // default
+    let default_base = GitHubService::default_pr_base(github_config.default_pr_base.as_deref());
+    let target_branch = request
+        .target_branch
+        .as_deref()
+        .filter(|branch| !branch.trim().is_empty())
+        .unwrap_or(&task_attempt.target_branch);
 
     let pool = &deployment.db().pool;
     let task = task_attempt
@@ -766,12 +1671,13 @@ pub async fn create_github_pr(
 
     let workspace_path = ensure_worktree_path(&deployment, &task_attempt).await?;
 
-    // Push the branch to GitHub first
-    if let Err(e) =
-        deployment
-            .git()
-            .push_to_github(&workspace_path, &task_attempt.branch, &github_token)
-    {
+    // Push the branch to GitHub first
+    if let Err(e) = deployment.git().push_to_github(
+        &workspace_path,
+        &task_attempt.branch,
+        &github_token,
+        project.push_remote.as_deref(),
+    ) {
         tracing::error!("Failed to push branch to GitHub: {}", e);
         let gh_e = GitHubServiceError::from(e);
         if gh_e.is_api_data() {
@@ -782,37 +1688,36 @@ pub async fn create_github_pr(
             )));
         }
     }
+    deployment
+        .github_cache()
+        .invalidate_remote_status(&project.git_repo_path, &task_attempt.branch);
 
-    let norm_target_branch_name = if matches!(
-        deployment
-            .git()
-            .find_branch_type(&project.git_repo_path, &target_branch)?,
-        BranchType::Remote
-    ) {
-        // Remote branches are formatted as {remote}/{branch} locally.
-        // For PR APIs, we must provide just the branch name.
-        let remote = deployment
-            .git()
-            .get_remote_name_from_branch_name(&workspace_path, &target_branch)?;
-        let remote_prefix = format!("{}/", remote);
-        target_branch
-            .strip_prefix(&remote_prefix)
-            .unwrap_or(&target_branch)
-            .to_string()
-    } else {
-        target_branch
-    };
+    // Remote branches are formatted as {remote}/{branch} locally.
+    // For PR APIs, we must provide just the branch name.
+    let norm_target_branch_name = GitHubService::pr_base_branch(
+        deployment.git(),
+        &project.git_repo_path,
+        target_branch,
+        project.pr_remote.as_deref(),
+        &default_base,
+    )?;
+    // Use GitService to get the remote URLs, then create GitHubRepoInfo
+    let (repo_info, head_repo) = deployment
+        .github_cache()
+        .pr_repositories(deployment.git(), &project)
+        .await?;
     // Create the PR using GitHub service
     let pr_request = CreatePrRequest {
-        title: request.title.clone(),
+        title: GitHubService::pr_title(
+            &request.title,
+            &task_attempt.branch,
+            title_branch_prefix.as_deref(),
+        ),
         body: request.body.clone(),
         head_branch: task_attempt.branch.clone(),
         base_branch: norm_target_branch_name.clone(),
+        head_repo: Some(head_repo),
     };
-    // Use GitService to get the remote URL, then create GitHubRepoInfo
-    let repo_info = deployment
-        .git()
-        .get_github_repo_info(&project.git_repo_path)?;
 
     match github_service.create_pr(&repo_info, &pr_request).await {
         Ok(pr_info) => {
@@ -942,11 +1847,24 @@ pub struct BranchStatus {
     pub conflict_op: Option<ConflictOp>,
     /// List of files currently in conflicted (unmerged) state
     pub conflicted_files: Vec<String>,
+    /// URL detected in the output of the attempt's running dev server
+    pub preview_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BranchStatusQuery {
+    /// Bypass cached remote status and fetch from the remote inline
+    #[serde(default)]
+    pub refresh: bool,
+    /// Compare the attempt branch with its remote-tracking branch even without an open PR
+    #[serde(default)]
+    pub include_remote: bool,
 }
 
 pub async fn get_task_attempt_branch_status(
     Extension(task_attempt): Extension<TaskAttempt>,
     State(deployment): State<DeploymentImpl>,
+    Query(query): Query<BranchStatusQuery>,
 ) -> Result<ResponseJson<ApiResponse<BranchStatus>>, ApiError> {
     let pool = &deployment.db().pool;
 
@@ -1009,18 +1927,27 @@ pub async fn get_task_attempt_branch_status(
             let token = github_config
                 .token()
                 .ok_or(ApiError::GitHubService(GitHubServiceError::TokenInvalid))?;
-            let (remote_commits_ahead, remote_commits_behind) =
-                deployment.git().get_remote_branch_status(
+            let (remote_commits_ahead, remote_commits_behind) = deployment
+                .github_cache()
+                .remote_branch_status(
+                    deployment.git(),
                     &ctx.project.git_repo_path,
                     &task_attempt.branch,
                     Some(&task_attempt.target_branch),
                     token,
-                )?;
+                    query.refresh,
+                )
+                .await?;
             (Some(remote_commits_ahead), Some(remote_commits_behind))
         }
     };
     // Fetch merges for this task attempt and add to branch status
     let merges = Merge::find_by_task_attempt_id(pool, task_attempt.id).await?;
+    let tracking_token = if query.include_remote {
+        deployment.config().read().await.github.token()
+    } else {
+        None
+    };
     let (remote_ahead, remote_behind) = if let Some(Merge::Pr(PrMerge {
         pr_info: PullRequestInfo {
             status: MergeStatus::Open,
@@ -1034,18 +1961,49 @@ pub async fn get_task_attempt_branch_status(
         let token = github_config
             .token()
             .ok_or(ApiError::GitHubService(GitHubServiceError::TokenInvalid))?;
-        let (remote_commits_ahead, remote_commits_behind) =
-            deployment.git().get_remote_branch_status(
+        let (remote_commits_ahead, remote_commits_behind) = deployment
+            .github_cache()
+            .remote_branch_status(
+                deployment.git(),
                 &ctx.project.git_repo_path,
                 &task_attempt.branch,
                 None,
                 token,
-            )?;
+                query.refresh,
+            )
+            .await?;
+        (Some(remote_commits_ahead), Some(remote_commits_behind))
+    } else if let Some(token) = tracking_token
+        && let Some(tracking_branch) = deployment
+            .git()
+            .find_remote_tracking_branch(&ctx.project.git_repo_path, &task_attempt.branch)?
+    {
+        // Manually pushed branches: compare against whatever the branch was pushed to
+        let (remote_commits_ahead, remote_commits_behind) = deployment
+            .github_cache()
+            .remote_branch_status(
+                deployment.git(),
+                &ctx.project.git_repo_path,
+                &task_attempt.branch,
+                Some(&tracking_branch),
+                token,
+                query.refresh,
+            )
+            .await?;
         (Some(remote_commits_ahead), Some(remote_commits_behind))
     } else {
         (None, None)
     };
 
+    let preview_url = ExecutionProcess::find_latest_by_task_attempt_and_run_reason(
+        pool,
+        task_attempt.id,
+        &ExecutionProcessRunReason::DevServer,
+    )
+    .await?
+    .filter(|process| process.status == ExecutionProcessStatus::Running)
+    .and_then(|process| process.preview_url);
+
     let branch_status = BranchStatus {
         commits_ahead,
         commits_behind,
@@ -1060,165 +2018,484 @@ pub async fn get_task_attempt_branch_status(
         is_rebase_in_progress,
         conflict_op,
         conflicted_files,
+        preview_url,
     };
     Ok(ResponseJson(ApiResponse::success(branch_status)))
 }
 
-#[derive(serde::Deserialize, Debug, TS)]
-pub struct ChangeTargetBranchRequest {
-    pub new_target_branch: String,
+#[derive(serde::Deserialize, Debug, TS)]
+pub struct ChangeTargetBranchRequest {
+    pub new_target_branch: String,
+}
+
+#[derive(serde::Serialize, Debug, TS)]
+pub struct ChangeTargetBranchResponse {
+    pub new_target_branch: String,
+    /// Commits ahead of and behind the new target. Any behind means the branch isn't based
+    /// on it; only a rebase moves its commits.
+    pub status: (usize, usize),
+}
+
+/// Change only the recorded target branch, used for branch status and merges. Unlike
+/// `rebase_task_attempt` the attempt's commits stay where they are.
+#[axum::debug_handler]
+pub async fn change_target_branch(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<ChangeTargetBranchRequest>,
+) -> Result<ResponseJson<ApiResponse<ChangeTargetBranchResponse>>, ApiError> {
+    // Extract new base branch from request body if provided
+    let new_target_branch = payload.new_target_branch;
+    let task = task_attempt
+        .parent_task(&deployment.db().pool)
+        .await?
+        .ok_or(ApiError::TaskAttempt(TaskAttemptError::TaskNotFound))?;
+    let project = Project::find_by_id(&deployment.db().pool, task.project_id)
+        .await?
+        .ok_or(ApiError::Project(ProjectError::ProjectNotFound))?;
+    match deployment
+        .git()
+        .check_branch_exists(&project.git_repo_path, &new_target_branch)?
+    {
+        true => {
+            TaskAttempt::update_target_branch(
+                &deployment.db().pool,
+                task_attempt.id,
+                &new_target_branch,
+            )
+            .await?;
+        }
+        false => {
+            return Ok(ResponseJson(ApiResponse::error(
+                format!(
+                    "Branch '{}' does not exist in the repository",
+                    new_target_branch
+                )
+                .as_str(),
+            )));
+        }
+    }
+    let status = deployment.git().get_branch_status(
+        &project.git_repo_path,
+        &task_attempt.branch,
+        &new_target_branch,
+    )?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_attempt_target_branch_changed",
+            serde_json::json!({
+                "attempt_id": task_attempt.id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(
+        ChangeTargetBranchResponse {
+            new_target_branch,
+            status,
+        },
+    )))
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct MarkReviewedRequest {
+    /// Commit reviewed up to; defaults to the attempt's current HEAD
+    pub commit: Option<String>,
+}
+
+/// Remember how far the user has reviewed the attempt so the diff can show only newer changes
+pub async fn mark_task_attempt_reviewed(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<MarkReviewedRequest>,
+) -> Result<ResponseJson<ApiResponse<TaskAttempt>>, ApiError> {
+    let wt_buf = ensure_worktree_path(&deployment, &task_attempt).await?;
+    let wt = wt_buf.as_path();
+    let git = deployment.git();
+    // A shared worktree may have another attempt's branch checked out
+    let head_oid = if deployment
+        .container()
+        .worktree_on_attempt_branch(&task_attempt, wt)
+        .await?
+    {
+        git.get_head_info(wt)?.oid
+    } else {
+        let project = task_attempt
+            .parent_task(&deployment.db().pool)
+            .await?
+            .ok_or(SqlxError::RowNotFound)?
+            .parent_project(&deployment.db().pool)
+            .await?
+            .ok_or(SqlxError::RowNotFound)?;
+        git.get_branch_oid(&project.git_repo_path, &task_attempt.branch)?
+    };
+
+    let reviewed_commit = match payload.commit.as_deref().map(str::trim) {
+        Some(rev) if !rev.is_empty() => {
+            let oid = git.resolve_commit_oid(wt, rev).map_err(|_| {
+                ApiError::Validation(format!("Commit '{rev}' does not exist in this attempt"))
+            })?;
+            let (_, behind_from_head) = git.ahead_behind_commits_by_oid(wt, &head_oid, &oid)?;
+            if behind_from_head > 0 {
+                return Err(ApiError::Validation(format!(
+                    "Commit '{rev}' is not part of the attempt's history"
+                )));
+            }
+            oid
+        }
+        _ => head_oid,
+    };
+
+    let pool = &deployment.db().pool;
+    TaskAttempt::update_reviewed_commit(pool, task_attempt.id, Some(&reviewed_commit)).await?;
+    let task_attempt = TaskAttempt::find_by_id(pool, task_attempt.id)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+
+    Ok(ResponseJson(ApiResponse::success(task_attempt)))
+}
+
+pub async fn clear_task_attempt_reviewed(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<TaskAttempt>>, ApiError> {
+    let pool = &deployment.db().pool;
+    TaskAttempt::update_reviewed_commit(pool, task_attempt.id, None).await?;
+    let task_attempt = TaskAttempt::find_by_id(pool, task_attempt.id)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+
+    Ok(ResponseJson(ApiResponse::success(task_attempt)))
+}
+
+/// Mark the attempt as the task's canonical one
+pub async fn pin_task_attempt(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<TaskAttempt>>, ApiError> {
+    set_task_attempt_pinned(&deployment, &task_attempt, true).await
+}
+
+pub async fn unpin_task_attempt(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<TaskAttempt>>, ApiError> {
+    set_task_attempt_pinned(&deployment, &task_attempt, false).await
+}
+
+async fn set_task_attempt_pinned(
+    deployment: &DeploymentImpl,
+    task_attempt: &TaskAttempt,
+    pinned: bool,
+) -> Result<ResponseJson<ApiResponse<TaskAttempt>>, ApiError> {
+    let pool = &deployment.db().pool;
+    TaskAttempt::update_pinned(pool, task_attempt.id, pinned).await?;
+    let task_attempt = TaskAttempt::find_by_id(pool, task_attempt.id)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+
+    Ok(ResponseJson(ApiResponse::success(task_attempt)))
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct UpdateAttemptNotesRequest {
+    /// Markdown notes; empty or missing clears them
+    pub notes: Option<String>,
+}
+
+pub async fn get_task_attempt_notes(
+    Extension(task_attempt): Extension<TaskAttempt>,
+) -> Result<ResponseJson<ApiResponse<Option<String>>>, ApiError> {
+    Ok(ResponseJson(ApiResponse::success(task_attempt.notes)))
+}
+
+/// Replace the reviewer notes on an attempt. Viewers pick the change up through the
+/// `task_attempt_patch` events emitted for the row update.
+pub async fn update_task_attempt_notes(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpdateAttemptNotesRequest>,
+) -> Result<ResponseJson<ApiResponse<TaskAttempt>>, ApiError> {
+    let notes = payload.notes.filter(|notes| !notes.trim().is_empty());
+
+    let pool = &deployment.db().pool;
+    TaskAttempt::update_notes(pool, task_attempt.id, notes.as_deref()).await?;
+    let task_attempt = TaskAttempt::find_by_id(pool, task_attempt.id)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+
+    Ok(ResponseJson(ApiResponse::success(task_attempt)))
+}
+
+#[axum::debug_handler]
+pub async fn rebase_task_attempt(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<RebaseTaskAttemptRequest>,
+) -> Result<ResponseJson<ApiResponse<(), GitOperationError>>, ApiError> {
+    let old_base_branch = payload
+        .old_base_branch
+        .unwrap_or(task_attempt.target_branch.clone());
+    let new_base_branch = payload
+        .new_base_branch
+        .unwrap_or(task_attempt.target_branch.clone());
+    let github_config = deployment.config().read().await.github.clone();
+
+    let pool = &deployment.db().pool;
+
+    let task = task_attempt
+        .parent_task(pool)
+        .await?
+        .ok_or(ApiError::TaskAttempt(TaskAttemptError::TaskNotFound))?;
+    let ctx = TaskAttempt::load_context(pool, task_attempt.id, task.id, task.project_id).await?;
+    if !deployment
+        .git()
+        .check_branch_exists(&ctx.project.git_repo_path, &new_base_branch)?
+    {
+        return Ok(ResponseJson(ApiResponse::error(
+            format!(
+                "Branch '{}' does not exist in the repository",
+                new_base_branch
+            )
+            .as_str(),
+        )));
+    }
+
+    // Resolve the commit before saving the new target branch, so a bad commit changes nothing
+    let onto_commit = match payload.onto_commit.as_deref() {
+        Some(commit) => match deployment
+            .git()
+            .resolve_commit_oid(&ctx.project.git_repo_path, commit)
+        {
+            Ok(oid) => Some(oid),
+            Err(_) => {
+                return Ok(ResponseJson(ApiResponse::error(
+                    format!("Commit '{}' does not exist in the repository", commit).as_str(),
+                )));
+            }
+        },
+        None => None,
+    };
+
+    TaskAttempt::update_target_branch(&deployment.db().pool, task_attempt.id, &new_base_branch)
+        .await?;
+
+    let worktree_path_buf = ensure_worktree_path(&deployment, &task_attempt).await?;
+    let worktree_path = worktree_path_buf.as_path();
+
+    // Rebasing onto a remote base fetches it first, which may wait for a fetch of the same
+    // repository already running, so the rebase runs off the async runtime
+    let result = {
+        let git = deployment.git().clone();
+        let repo_path = ctx.project.git_repo_path.clone();
+        let worktree_path = worktree_path_buf.clone();
+        let onto_commit = onto_commit.clone();
+        let new_base_branch = new_base_branch.clone();
+        let old_base_branch = old_base_branch.clone();
+        let task_branch = task_attempt.branch.clone();
+        let github_token = github_config.token();
+        tokio::task::spawn_blocking(move || match onto_commit.as_deref() {
+            Some(onto_commit) => git.rebase_branch_onto_commit(
+                &repo_path,
+                &worktree_path,
+                onto_commit,
+                &old_base_branch,
+                &task_branch,
+            ),
+            None => git.rebase_branch(
+                &repo_path,
+                &worktree_path,
+                &new_base_branch,
+                &old_base_branch,
+                &task_branch,
+                github_token,
+            ),
+        })
+        .await
+        .map_err(std::io::Error::other)?
+    };
+    if let Err(e) = result {
+        return rebase_error_response(e);
+    }
+
+    let mut rebased_attempt = ctx.task_attempt.clone();
+    rebased_attempt.target_branch = new_base_branch;
+    push_attempt_git_update(
+        &deployment,
+        &ctx.project,
+        &rebased_attempt,
+        worktree_path,
+        AttemptGitOperation::Rebase,
+    );
+    file_reviews::refresh_file_reviews(&deployment, &rebased_attempt, worktree_path).await;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_attempt_rebased",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "project_id": ctx.project.id.to_string(),
+                "attempt_id": task_attempt.id.to_string(),
+                "onto_commit": onto_commit.is_some(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// Surface rebase conflicts as typed `GitOperationError` data; other failures are API errors
+fn rebase_error_response(
+    e: GitServiceError,
+) -> Result<ResponseJson<ApiResponse<(), GitOperationError>>, ApiError> {
+    match e {
+        GitServiceError::MergeConflicts(msg) => Ok(ResponseJson(ApiResponse::<
+            (),
+            GitOperationError,
+        >::error_with_data(
+            GitOperationError::MergeConflicts {
+                message: msg,
+                op: ConflictOp::Rebase,
+            },
+        ))),
+        GitServiceError::RebaseInProgress => Ok(ResponseJson(
+            ApiResponse::<(), GitOperationError>::error_with_data(
+                GitOperationError::RebaseInProgress,
+            ),
+        )),
+        other => Err(ApiError::GitService(other)),
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, TS)]
+pub struct ReorderCommitsRequest {
+    /// All of the attempt's commits, oldest first, in the desired order
+    pub ordered_oids: Vec<String>,
 }
 
-#[derive(serde::Serialize, Debug, TS)]
-pub struct ChangeTargetBranchResponse {
-    pub new_target_branch: String,
-    pub status: (usize, usize),
+#[derive(Debug, Deserialize, Serialize, TS)]
+pub struct SquashCommitsRequest {
+    /// All of the attempt's commits; they are combined into a single commit
+    pub oids: Vec<String>,
+    pub message: String,
+}
+
+/// Returns an error message if the attempt's history must not be rewritten with `oid_count`
+/// commits: the count has to match the commits ahead of the target branch, and the branch
+/// must not have been pushed.
+fn check_history_rewrite_allowed(
+    deployment: &DeploymentImpl,
+    repo_path: &std::path::Path,
+    task_attempt: &TaskAttempt,
+    oid_count: usize,
+) -> Result<Option<String>, ApiError> {
+    let (ahead, _) = deployment.git().get_branch_status(
+        repo_path,
+        &task_attempt.branch,
+        &task_attempt.target_branch,
+    )?;
+    if oid_count != ahead {
+        return Ok(Some(format!(
+            "Expected exactly the attempt's {} commit(s) ahead of '{}', got {}",
+            ahead, task_attempt.target_branch, oid_count
+        )));
+    }
+    if deployment
+        .git()
+        .is_branch_pushed(repo_path, &task_attempt.branch)?
+    {
+        return Ok(Some(format!(
+            "Branch '{}' has already been pushed; refusing to rewrite its history",
+            task_attempt.branch
+        )));
+    }
+    Ok(None)
 }
 
 #[axum::debug_handler]
-pub async fn change_target_branch(
+pub async fn reorder_task_attempt_commits(
     Extension(task_attempt): Extension<TaskAttempt>,
     State(deployment): State<DeploymentImpl>,
-    Json(payload): Json<ChangeTargetBranchRequest>,
-) -> Result<ResponseJson<ApiResponse<ChangeTargetBranchResponse>>, ApiError> {
-    // Extract new base branch from request body if provided
-    let new_target_branch = payload.new_target_branch;
+    Json(payload): Json<ReorderCommitsRequest>,
+) -> Result<ResponseJson<ApiResponse<(), GitOperationError>>, ApiError> {
+    let pool = &deployment.db().pool;
     let task = task_attempt
-        .parent_task(&deployment.db().pool)
+        .parent_task(pool)
         .await?
         .ok_or(ApiError::TaskAttempt(TaskAttemptError::TaskNotFound))?;
-    let project = Project::find_by_id(&deployment.db().pool, task.project_id)
-        .await?
-        .ok_or(ApiError::Project(ProjectError::ProjectNotFound))?;
-    match deployment
+    let ctx = TaskAttempt::load_context(pool, task_attempt.id, task.id, task.project_id).await?;
+
+    if let Some(msg) = check_history_rewrite_allowed(
+        &deployment,
+        &ctx.project.git_repo_path,
+        &task_attempt,
+        payload.ordered_oids.len(),
+    )? {
+        return Ok(ResponseJson(ApiResponse::error(&msg)));
+    }
+
+    let worktree_path = ensure_worktree_path(&deployment, &task_attempt).await?;
+    if let Err(e) = deployment
         .git()
-        .check_branch_exists(&project.git_repo_path, &new_target_branch)?
+        .reorder_commits(&worktree_path, &payload.ordered_oids)
     {
-        true => {
-            TaskAttempt::update_target_branch(
-                &deployment.db().pool,
-                task_attempt.id,
-                &new_target_branch,
-            )
-            .await?;
-        }
-        false => {
-            return Ok(ResponseJson(ApiResponse::error(
-                format!(
-                    "Branch '{}' does not exist in the repository",
-                    new_target_branch
-                )
-                .as_str(),
-            )));
-        }
+        return rebase_error_response(e);
     }
-    let status = deployment.git().get_branch_status(
-        &project.git_repo_path,
-        &task_attempt.branch,
-        &new_target_branch,
-    )?;
 
     deployment
         .track_if_analytics_allowed(
-            "task_attempt_target_branch_changed",
+            "task_attempt_commits_reordered",
             serde_json::json!({
+                "task_id": task.id.to_string(),
+                "project_id": ctx.project.id.to_string(),
                 "attempt_id": task_attempt.id.to_string(),
+                "commit_count": payload.ordered_oids.len(),
             }),
         )
         .await;
 
-    Ok(ResponseJson(ApiResponse::success(
-        ChangeTargetBranchResponse {
-            new_target_branch,
-            status,
-        },
-    )))
+    Ok(ResponseJson(ApiResponse::success(())))
 }
 
 #[axum::debug_handler]
-pub async fn rebase_task_attempt(
+pub async fn squash_task_attempt_commits(
     Extension(task_attempt): Extension<TaskAttempt>,
     State(deployment): State<DeploymentImpl>,
-    Json(payload): Json<RebaseTaskAttemptRequest>,
+    Json(payload): Json<SquashCommitsRequest>,
 ) -> Result<ResponseJson<ApiResponse<(), GitOperationError>>, ApiError> {
-    let old_base_branch = payload
-        .old_base_branch
-        .unwrap_or(task_attempt.target_branch.clone());
-    let new_base_branch = payload
-        .new_base_branch
-        .unwrap_or(task_attempt.target_branch.clone());
-    let github_config = deployment.config().read().await.github.clone();
-
     let pool = &deployment.db().pool;
-
     let task = task_attempt
         .parent_task(pool)
         .await?
         .ok_or(ApiError::TaskAttempt(TaskAttemptError::TaskNotFound))?;
     let ctx = TaskAttempt::load_context(pool, task_attempt.id, task.id, task.project_id).await?;
-    match deployment
-        .git()
-        .check_branch_exists(&ctx.project.git_repo_path, &new_base_branch)?
-    {
-        true => {
-            TaskAttempt::update_target_branch(
-                &deployment.db().pool,
-                task_attempt.id,
-                &new_base_branch,
-            )
-            .await?;
-        }
-        false => {
-            return Ok(ResponseJson(ApiResponse::error(
-                format!(
-                    "Branch '{}' does not exist in the repository",
-                    new_base_branch
-                )
-                .as_str(),
-            )));
-        }
-    }
 
-    let worktree_path_buf = ensure_worktree_path(&deployment, &task_attempt).await?;
-    let worktree_path = worktree_path_buf.as_path();
-
-    let result = deployment.git().rebase_branch(
+    if let Some(msg) = check_history_rewrite_allowed(
+        &deployment,
         &ctx.project.git_repo_path,
-        worktree_path,
-        &new_base_branch,
-        &old_base_branch,
-        &task_attempt.branch.clone(),
-        github_config.token(),
-    );
-    if let Err(e) = result {
-        use services::services::git::GitServiceError;
-        return match e {
-            GitServiceError::MergeConflicts(msg) => Ok(ResponseJson(ApiResponse::<
-                (),
-                GitOperationError,
-            >::error_with_data(
-                GitOperationError::MergeConflicts {
-                    message: msg,
-                    op: ConflictOp::Rebase,
-                },
-            ))),
-            GitServiceError::RebaseInProgress => Ok(ResponseJson(ApiResponse::<
-                (),
-                GitOperationError,
-            >::error_with_data(
-                GitOperationError::RebaseInProgress,
-            ))),
-            other => Err(ApiError::GitService(other)),
-        };
+        &task_attempt,
+        payload.oids.len(),
+    )? {
+        return Ok(ResponseJson(ApiResponse::error(&msg)));
+    }
+
+    let worktree_path = ensure_worktree_path(&deployment, &task_attempt).await?;
+    if let Err(e) = deployment
+        .git()
+        .squash_commits(&worktree_path, &payload.oids, &payload.message)
+    {
+        return rebase_error_response(e);
     }
 
     deployment
         .track_if_analytics_allowed(
-            "task_attempt_rebased",
+            "task_attempt_commits_squashed",
             serde_json::json!({
                 "task_id": task.id.to_string(),
                 "project_id": ctx.project.id.to_string(),
                 "attempt_id": task_attempt.id.to_string(),
+                "commit_count": payload.oids.len(),
             }),
         )
         .await;
@@ -1240,6 +2517,51 @@ pub async fn abort_conflicts_task_attempt(
     Ok(ResponseJson(ApiResponse::success(())))
 }
 
+#[derive(Debug, Deserialize, Serialize, TS)]
+pub struct ResolveConflictRequest {
+    pub path: String,
+    pub strategy: ConflictResolution,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct RemainingConflictsResponse {
+    pub remaining_conflicts: Vec<String>,
+}
+
+#[axum::debug_handler]
+pub async fn resolve_conflict_task_attempt(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<ResolveConflictRequest>,
+) -> Result<ResponseJson<ApiResponse<RemainingConflictsResponse>>, ApiError> {
+    let worktree_path = ensure_worktree_path(&deployment, &task_attempt).await?;
+    let remaining_conflicts =
+        deployment
+            .git()
+            .resolve_conflict(&worktree_path, &payload.path, payload.strategy)?;
+    let response = RemainingConflictsResponse {
+        remaining_conflicts,
+    };
+
+    Ok(ResponseJson(ApiResponse::success(response)))
+}
+
+/// Stage hand-resolved files and resume the paused rebase or merge. A non-empty
+/// `remaining_conflicts` means the next rebased commit conflicted as well.
+#[axum::debug_handler]
+pub async fn continue_conflicts_task_attempt(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<RemainingConflictsResponse>>, ApiError> {
+    let worktree_path = ensure_worktree_path(&deployment, &task_attempt).await?;
+    let remaining_conflicts = deployment.git().continue_conflicts(&worktree_path)?;
+    let response = RemainingConflictsResponse {
+        remaining_conflicts,
+    };
+
+    Ok(ResponseJson(ApiResponse::success(response)))
+}
+
 #[derive(serde::Deserialize)]
 pub struct DeleteFileQuery {
     file_path: String,
@@ -1274,9 +2596,19 @@ pub async fn delete_task_attempt_file(
     Ok(ResponseJson(ApiResponse::success(())))
 }
 
+#[derive(Debug, Deserialize)]
+pub struct StartDevServerQuery {
+    /// One of the project's `dev_scripts`; the main `dev_script` is started when omitted
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+/// Start one of the project's dev servers. Only a running dev server with the same name is
+/// stopped first, so differently named servers (e.g. backend and frontend) run side by side.
 #[axum::debug_handler]
 pub async fn start_dev_server(
     Extension(task_attempt): Extension<TaskAttempt>,
+    Query(query): Query<StartDevServerQuery>,
     State(deployment): State<DeploymentImpl>,
 ) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
     let pool = &deployment.db().pool;
@@ -1292,8 +2624,24 @@ pub async fn start_dev_server(
         .parent_project(&deployment.db().pool)
         .await?
         .ok_or(SqlxError::RowNotFound)?;
+    let project = deployment
+        .container()
+        .repo_config()
+        .effective(project)
+        .await;
+
+    let name = query.name.filter(|name| !name.is_empty());
+    let Some(script) = project
+        .dev_script_named(name.as_deref())
+        .map(str::to_string)
+    else {
+        return Ok(ResponseJson(ApiResponse::error(&match &name {
+            Some(name) => format!("No dev server named '{name}' configured for this project"),
+            None => "No dev server script configured for this project".to_string(),
+        })));
+    };
 
-    // Stop any existing dev servers for this project
+    // Stop the existing dev server of the same name for this project
     let existing_dev_servers =
         match ExecutionProcess::find_running_dev_servers_by_project(pool, project.id).await {
             Ok(servers) => servers,
@@ -1309,7 +2657,10 @@ pub async fn start_dev_server(
             }
         };
 
-    for dev_server in existing_dev_servers {
+    for dev_server in existing_dev_servers
+        .into_iter()
+        .filter(|dev_server| dev_server.dev_server_name() == name.as_deref())
+    {
         tracing::info!(
             "Stopping existing dev server {} for project {}",
             dev_server.id,
@@ -1325,34 +2676,128 @@ pub async fn start_dev_server(
         }
     }
 
-    if let Some(dev_server) = project.dev_script {
-        // TODO: Derive script language from system config
-        let executor_action = ExecutorAction::new(
-            ExecutorActionType::ScriptRequest(ScriptRequest {
-                script: dev_server,
-                language: ScriptRequestLanguage::Bash,
-                context: ScriptContext::DevServer,
+    let executor_action = ExecutorAction::new(
+        ExecutorActionType::ScriptRequest(ScriptRequest {
+            script,
+            language: project.script_language(),
+            context: ScriptContext::DevServer,
+            cache_key: None,
+            name: name.clone(),
+        }),
+        None,
+    );
+
+    deployment
+        .container()
+        .start_execution(
+            &task_attempt,
+            &executor_action,
+            &ExecutionProcessRunReason::DevServer,
+        )
+        .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "dev_server_started",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "project_id": project.id.to_string(),
+                "attempt_id": task_attempt.id.to_string(),
+                "named": name.is_some(),
             }),
-            None,
-        );
+        )
+        .await;
 
-        deployment
-            .container()
-            .start_execution(
-                &task_attempt,
-                &executor_action,
-                &ExecutionProcessRunReason::DevServer,
-            )
-            .await?
-    } else {
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// Re-run the project's setup script in the attempt's existing worktree
+pub async fn run_setup_script(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<ExecutionProcess>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    let task = task_attempt
+        .parent_task(pool)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    let project = task
+        .parent_project(pool)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    let project = deployment
+        .container()
+        .repo_config()
+        .effective(project)
+        .await;
+
+    let Some(setup_script) = project.setup_script.clone() else {
         return Ok(ResponseJson(ApiResponse::error(
-            "No dev server script configured for this project",
+            "No setup script configured for this project",
         )));
     };
 
+    let running: Vec<ExecutionProcess> =
+        ExecutionProcess::find_by_task_attempt_id(pool, task_attempt.id, false)
+            .await?
+            .into_iter()
+            .filter(|p| p.status == ExecutionProcessStatus::Running)
+            .collect();
+    if running
+        .iter()
+        .any(|p| p.run_reason == ExecutionProcessRunReason::CodingAgent)
+    {
+        return Err(ApiError::Conflict(
+            "An agent is running in this attempt; stop it before re-running setup".to_string(),
+        ));
+    }
+
+    let _ = ensure_worktree_path(&deployment, &task_attempt).await?;
+    // Ensuring the worktree may have recreated it under a new container ref
+    let task_attempt = TaskAttempt::find_by_id(pool, task_attempt.id)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+
+    // Stop any setup script still running for this attempt
+    let running_setups = running
+        .into_iter()
+        .filter(|p| p.run_reason == ExecutionProcessRunReason::SetupScript);
+    for process in running_setups {
+        tracing::info!(
+            "Stopping running setup script {} for task attempt {}",
+            process.id,
+            task_attempt.id
+        );
+        if let Err(e) = deployment
+            .container()
+            .stop_execution(&process, ExecutionProcessStatus::Killed)
+            .await
+        {
+            tracing::error!("Failed to stop setup script {}: {}", process.id, e);
+        }
+    }
+
+    // Forced, so the script always runs, but keyed so a successful run refreshes the setup cache
+    let script_request = deployment
+        .container()
+        .setup_script_request(&task_attempt, &project, setup_script, true)
+        .await
+        .ok_or_else(|| ApiError::Conflict("The setup script was skipped".to_string()))?;
+    let executor_action =
+        ExecutorAction::new(ExecutorActionType::ScriptRequest(script_request), None);
+    let execution_process = deployment
+        .container()
+        .start_execution(
+            &task_attempt,
+            &executor_action,
+            &ExecutionProcessRunReason::SetupScript,
+        )
+        .await?;
+
     deployment
         .track_if_analytics_allowed(
-            "dev_server_started",
+            "setup_script_rerun",
             serde_json::json!({
                 "task_id": task.id.to_string(),
                 "project_id": project.id.to_string(),
@@ -1361,7 +2806,7 @@ pub async fn start_dev_server(
         )
         .await;
 
-    Ok(ResponseJson(ApiResponse::success(())))
+    Ok(ResponseJson(ApiResponse::success(execution_process)))
 }
 
 pub async fn get_task_attempt_children(
@@ -1453,13 +2898,14 @@ pub async fn attach_existing_pr(
     };
 
     let github_service = GitHubService::new(&github_token)?;
-    let repo_info = deployment
-        .git()
-        .get_github_repo_info(&project.git_repo_path)?;
+    let (repo_info, head_repo) = deployment
+        .github_cache()
+        .pr_repositories(deployment.git(), &project)
+        .await?;
 
     // List all PRs for branch (open, closed, and merged)
     let prs = github_service
-        .list_all_prs_for_branch(&repo_info, &task_attempt.branch)
+        .list_all_prs_for_branch(&repo_info, &head_repo, &task_attempt.branch)
         .await?;
 
     // Take the first PR (prefer open, but also accept merged/closed)
@@ -1510,6 +2956,7 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     let task_attempt_id_router = Router::new()
         .route("/", get(get_task_attempt))
         .route("/follow-up", post(follow_up))
+        .route("/clone", post(clone_task_attempt))
         .route(
             "/draft",
             get(drafts::get_draft)
@@ -1518,15 +2965,49 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
         )
         .route("/draft/queue", post(drafts::set_draft_queue))
         .route("/replace-process", post(replace_process))
+        .route("/follow-up-from-commit", post(follow_up_from_commit))
+        .route("/reset", post(reset_to_base))
+        .route("/recover", post(recover_attempt))
+        .route("/exec", post(exec_command))
+        .route("/diff-summary", post(get_diff_summary))
+        .route("/summary.md", get(summary::get_summary_markdown))
+        .route(
+            "/file-reviews",
+            get(file_reviews::get_file_reviews).put(file_reviews::set_file_reviewed),
+        )
+        .route(
+            "/kv",
+            get(kv::get_kv_entries)
+                .put(kv::set_kv_entry)
+                .delete(kv::delete_kv_entry),
+        )
         .route("/commit-info", get(get_commit_info))
         .route("/commit-compare", get(compare_commit_to_head))
+        .route("/blame", get(get_task_attempt_blame))
+        .route("/commit-execution", get(get_commit_execution_process))
         .route("/start-dev-server", post(start_dev_server))
+        .route("/run-setup", post(run_setup_script))
         .route("/branch-status", get(get_task_attempt_branch_status))
         .route("/diff/ws", get(stream_task_attempt_diff_ws))
+        .route(
+            "/reviewed",
+            post(mark_task_attempt_reviewed).delete(clear_task_attempt_reviewed),
+        )
+        .route(
+            "/notes",
+            get(get_task_attempt_notes).put(update_task_attempt_notes),
+        )
+        .route("/pin", post(pin_task_attempt).delete(unpin_task_attempt))
         .route("/merge", post(merge_task_attempt))
+        .route("/merge-preview", get(get_task_attempt_merge_preview))
         .route("/push", post(push_task_attempt_branch))
         .route("/rebase", post(rebase_task_attempt))
+        .route("/commits/reorder", post(reorder_task_attempt_commits))
+        .route("/commits/squash", post(squash_task_attempt_commits))
         .route("/conflicts/abort", post(abort_conflicts_task_attempt))
+        .route("/conflicts/continue", post(continue_conflicts_task_attempt))
+        .route("/conflicts/hunks", get(get_task_attempt_conflict_hunks))
+        .route("/conflicts/resolve", post(resolve_conflict_task_attempt))
         .route("/pr", post(create_github_pr))
         .route("/pr/attach", post(attach_existing_pr))
         .route("/open-editor", post(open_task_attempt_in_editor))
@@ -1545,3 +3026,109 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
 
     Router::new().nest("/task-attempts", task_attempts_router)
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::Path};
+
+    use git2::Repository;
+    use tempfile::TempDir;
+
+    use super::*;
+
+    /// A bare `remote.git` on `main` and a clone of it with one unpushed commit on `main`
+    fn clone_with_unpushed_commit(root: &TempDir) -> (std::path::PathBuf, std::path::PathBuf) {
+        let git = GitService::new();
+        let seed_path = root.path().join("seed");
+        git.initialize_repo_with_main_branch(&seed_path).unwrap();
+
+        let remote_path = root.path().join("remote.git");
+        git2::build::RepoBuilder::new()
+            .bare(true)
+            .clone(&seed_path.to_string_lossy(), &remote_path)
+            .unwrap();
+
+        let local_path = root.path().join("local");
+        Repository::clone(&remote_path.to_string_lossy(), &local_path).unwrap();
+        git.configure_user(&local_path, "Test User", "test@example.com")
+            .unwrap();
+        fs::write(local_path.join("merged.txt"), "merged\n").unwrap();
+        git.commit(&local_path, "merge attempt").unwrap();
+        (remote_path, local_path)
+    }
+
+    fn branch_oid(repo_path: &Path, branch: &str) -> git2::Oid {
+        Repository::open(repo_path)
+            .unwrap()
+            .find_branch(branch, BranchType::Local)
+            .unwrap()
+            .get()
+            .target()
+            .unwrap()
+    }
+
+    #[test]
+    fn push_after_merge_pushes_the_target_branch() {
+        let root = TempDir::new().unwrap();
+        let (remote_path, local_path) = clone_with_unpushed_commit(&root);
+
+        let pushed = push_merged_target_branch(
+            &GitService::new(),
+            true,
+            Some("token".to_string()),
+            &local_path,
+            "main",
+            None,
+        );
+
+        assert!(pushed);
+        assert_eq!(
+            branch_oid(&remote_path, "main"),
+            branch_oid(&local_path, "main")
+        );
+    }
+
+    #[test]
+    fn push_after_merge_skips_when_disabled_or_without_a_token() {
+        let root = TempDir::new().unwrap();
+        let (remote_path, local_path) = clone_with_unpushed_commit(&root);
+        let before = branch_oid(&remote_path, "main");
+        let git = GitService::new();
+
+        let token = Some("token".to_string());
+        assert!(!push_merged_target_branch(
+            &git,
+            false,
+            token,
+            &local_path,
+            "main",
+            None
+        ));
+        assert!(!push_merged_target_branch(
+            &git,
+            true,
+            None,
+            &local_path,
+            "main",
+            None
+        ));
+        assert_eq!(branch_oid(&remote_path, "main"), before);
+    }
+
+    #[test]
+    fn push_after_merge_reports_a_failed_push_without_erroring() {
+        let root = TempDir::new().unwrap();
+        let (_, local_path) = clone_with_unpushed_commit(&root);
+
+        let pushed = push_merged_target_branch(
+            &GitService::new(),
+            true,
+            Some("token".to_string()),
+            &local_path,
+            "main",
+            Some("missing-remote"),
+        );
+
+        assert!(!pushed);
+    }
+}
@@ -0,0 +1,212 @@
+use std::{
+    collections::BTreeSet,
+    path::{Path, PathBuf},
+};
+
+use axum::{Extension, Json, extract::State, response::Json as ResponseJson};
+use db::models::task_attempt::TaskAttempt;
+use deployment::Deployment;
+use serde::{Deserialize, Serialize};
+use services::services::{
+    container::ContainerService,
+    events::AttemptFileReviewUpdate,
+    file_review::{AttemptFiles, FileReviewService},
+    git::{DiffTarget, GitService},
+};
+use sqlx::Error as SqlxError;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+
+use crate::{DeploymentImpl, error::ApiError, routes::task_attempts::util::ensure_worktree_path};
+
+#[derive(Debug, Serialize, TS)]
+pub struct FileReviewStatus {
+    pub path: String,
+    pub reviewed: bool,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct SetFileReviewedRequest {
+    /// Path as listed in the attempt's diff
+    pub path: String,
+    pub reviewed: bool,
+}
+
+/// Review state of every file in the attempt's diff
+pub async fn get_file_reviews(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<FileReviewStatus>>>, ApiError> {
+    let source = AttemptSource::load(&deployment, &task_attempt).await?;
+    let files = diff_paths(&deployment, &task_attempt, &source)?;
+    let reviewed = FileReviewService::reviewed_paths(
+        &deployment.db().pool,
+        deployment.git(),
+        task_attempt.id,
+        source.files(&task_attempt),
+    )
+    .await?;
+
+    let statuses = file_statuses(files, &reviewed);
+    Ok(ResponseJson(ApiResponse::success(statuses)))
+}
+
+/// Check a file of the attempt's diff off as reviewed at its current contents, or un-check it.
+/// Everyone watching the project's tasks stream receives the new set of reviewed files.
+pub async fn set_file_reviewed(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<SetFileReviewedRequest>,
+) -> Result<ResponseJson<ApiResponse<Vec<FileReviewStatus>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let source = AttemptSource::load(&deployment, &task_attempt).await?;
+    let files = diff_paths(&deployment, &task_attempt, &source)?;
+
+    if payload.reviewed && !files.contains(&payload.path) {
+        return Err(ApiError::Validation(format!(
+            "'{}' is not part of this attempt's diff",
+            payload.path
+        )));
+    }
+    FileReviewService::set_reviewed(
+        pool,
+        deployment.git(),
+        task_attempt.id,
+        source.files(&task_attempt),
+        &payload.path,
+        payload.reviewed,
+    )
+    .await?;
+
+    let (reviewed, _) = FileReviewService::prune_stale(
+        pool,
+        deployment.git(),
+        task_attempt.id,
+        source.files(&task_attempt),
+    )
+    .await?;
+    push_update(&deployment, &task_attempt, &reviewed);
+
+    let statuses = file_statuses(files, &reviewed);
+    Ok(ResponseJson(ApiResponse::success(statuses)))
+}
+
+/// Where the attempt's changes live: its worktree, or only its branch when the attempt shares a
+/// worktree that has another attempt's branch checked out
+struct AttemptSource {
+    repo_path: PathBuf,
+    worktree_path: PathBuf,
+    on_attempt_branch: bool,
+}
+
+impl AttemptSource {
+    async fn load(
+        deployment: &DeploymentImpl,
+        task_attempt: &TaskAttempt,
+    ) -> Result<Self, ApiError> {
+        let project = task_attempt
+            .parent_task(&deployment.db().pool)
+            .await?
+            .ok_or(SqlxError::RowNotFound)?
+            .parent_project(&deployment.db().pool)
+            .await?
+            .ok_or(SqlxError::RowNotFound)?;
+        let worktree_path = ensure_worktree_path(deployment, task_attempt).await?;
+        let on_attempt_branch = deployment
+            .container()
+            .worktree_on_attempt_branch(task_attempt, &worktree_path)
+            .await?;
+        Ok(Self {
+            repo_path: project.git_repo_path,
+            worktree_path,
+            on_attempt_branch,
+        })
+    }
+
+    fn files<'a>(&'a self, task_attempt: &'a TaskAttempt) -> AttemptFiles<'a> {
+        if self.on_attempt_branch {
+            AttemptFiles::Worktree(&self.worktree_path)
+        } else {
+            AttemptFiles::Branch {
+                repo_path: &self.repo_path,
+                branch_name: &task_attempt.branch,
+            }
+        }
+    }
+}
+
+/// Paths of the files changed by the attempt, uncommitted changes included, sorted
+fn diff_paths(
+    deployment: &DeploymentImpl,
+    task_attempt: &TaskAttempt,
+    source: &AttemptSource,
+) -> Result<BTreeSet<String>, ApiError> {
+    let git = deployment.git();
+    let diffs = if source.on_attempt_branch {
+        let base_commit = git.get_base_commit(
+            &source.repo_path,
+            &task_attempt.branch,
+            &task_attempt.target_branch,
+        )?;
+        git.get_diffs(
+            DiffTarget::Worktree {
+                worktree_path: &source.worktree_path,
+                base_commit: &base_commit,
+            },
+            None,
+        )?
+    } else {
+        git.get_diffs(
+            DiffTarget::Branch {
+                repo_path: &source.repo_path,
+                branch_name: &task_attempt.branch,
+                base_branch: &task_attempt.target_branch,
+            },
+            None,
+        )?
+    };
+    Ok(diffs.iter().map(GitService::diff_path).collect())
+}
+
+/// Reset the reviews of files a git operation such as a rebase changed
+pub async fn refresh_file_reviews(
+    deployment: &DeploymentImpl,
+    task_attempt: &TaskAttempt,
+    worktree_path: &Path,
+) {
+    if let Err(e) = FileReviewService::refresh(
+        &deployment.db().pool,
+        deployment.git(),
+        deployment.events().msg_store(),
+        task_attempt,
+        worktree_path,
+    )
+    .await
+    {
+        tracing::warn!(
+            "Failed to refresh file reviews of attempt {}: {}",
+            task_attempt.id,
+            e
+        );
+    }
+}
+
+fn file_statuses(files: BTreeSet<String>, reviewed: &[String]) -> Vec<FileReviewStatus> {
+    files
+        .into_iter()
+        .map(|path| FileReviewStatus {
+            reviewed: reviewed.contains(&path),
+            path,
+        })
+        .collect()
+}
+
+fn push_update(deployment: &DeploymentImpl, task_attempt: &TaskAttempt, reviewed: &[String]) {
+    deployment
+        .events()
+        .push_attempt_file_review_update(&AttemptFileReviewUpdate {
+            task_attempt_id: task_attempt.id,
+            task_id: task_attempt.task_id,
+            reviewed_paths: reviewed.to_vec(),
+        });
+}
@@ -0,0 +1,87 @@
+use axum::{
+    Extension, Json,
+    extract::{Query, State},
+    response::Json as ResponseJson,
+};
+use db::models::{
+    attempt_kv::{AttemptKvEntry, MAX_KV_ENTRIES_PER_ATTEMPT, validate_kv_entry},
+    task_attempt::TaskAttempt,
+};
+use deployment::Deployment;
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+#[derive(Debug, Deserialize)]
+pub struct KvEntriesQuery {
+    /// Only return the entry stored under this key
+    pub key: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct KvKeyQuery {
+    pub key: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, TS)]
+pub struct SetKvEntryRequest {
+    pub key: String,
+    #[ts(type = "JsonValue")]
+    pub value: JsonValue,
+}
+
+/// The attempt's scratch entries, sorted by key, or just the one under `key`
+pub async fn get_kv_entries(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<KvEntriesQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<AttemptKvEntry>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let entries = match query.key {
+        Some(key) => AttemptKvEntry::find(pool, task_attempt.id, &key)
+            .await?
+            .into_iter()
+            .collect(),
+        None => AttemptKvEntry::find_by_task_attempt_id(pool, task_attempt.id).await?,
+    };
+    Ok(ResponseJson(ApiResponse::success(entries)))
+}
+
+/// Store a JSON value under a key, replacing any previous value
+pub async fn set_kv_entry(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<SetKvEntryRequest>,
+) -> Result<ResponseJson<ApiResponse<AttemptKvEntry>>, ApiError> {
+    validate_kv_entry(&payload.key, &payload.value).map_err(ApiError::Validation)?;
+
+    let entry = AttemptKvEntry::set(
+        &deployment.db().pool,
+        task_attempt.id,
+        &payload.key,
+        &payload.value,
+    )
+    .await?
+    .ok_or_else(|| {
+        ApiError::Validation(format!(
+            "The attempt already holds {MAX_KV_ENTRIES_PER_ATTEMPT} entries; delete one before adding '{}'",
+            payload.key
+        ))
+    })?;
+    Ok(ResponseJson(ApiResponse::success(entry)))
+}
+
+/// Remove the entry under `key`. Removing a key that isn't set is not an error; the result
+/// says whether an entry was removed.
+pub async fn delete_kv_entry(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<KvKeyQuery>,
+) -> Result<ResponseJson<ApiResponse<bool>>, ApiError> {
+    let rows_affected =
+        AttemptKvEntry::delete(&deployment.db().pool, task_attempt.id, &query.key).await?;
+    Ok(ResponseJson(ApiResponse::success(rows_affected > 0)))
+}
@@ -0,0 +1,146 @@
+use std::fmt::Write;
+
+use axum::{
+    Extension,
+    extract::State,
+    http::header,
+    response::{IntoResponse, Response},
+};
+use db::models::{
+    execution_process::{ExecutionProcess, ExecutionProcessRunReason},
+    executor_session::ExecutorSession,
+    task::Task,
+    task_attempt::TaskAttempt,
+};
+use deployment::Deployment;
+use services::services::git::DiffTarget;
+use sqlx::Error as SqlxError;
+use utils::diff::{Diff, compute_line_change_counts};
+
+use crate::{DeploymentImpl, error::ApiError, routes::task_attempts::util::ensure_worktree_path};
+
+/// Files listed before the rest are folded into a count
+const MAX_LISTED_FILES: usize = 50;
+
+struct ChangedFile {
+    path: String,
+    additions: usize,
+    deletions: usize,
+}
+
+impl From<&Diff> for ChangedFile {
+    fn from(diff: &Diff) -> Self {
+        let path = match (&diff.old_path, &diff.new_path) {
+            (Some(old), Some(new)) if old != new => format!("{old} → {new}"),
+            (_, Some(path)) | (Some(path), None) => path.clone(),
+            (None, None) => String::new(),
+        };
+        let (additions, deletions) = match (diff.additions, diff.deletions) {
+            (Some(additions), Some(deletions)) => (additions, deletions),
+            _ => compute_line_change_counts(
+                diff.old_content.as_deref().unwrap_or_default(),
+                diff.new_content.as_deref().unwrap_or_default(),
+            ),
+        };
+        ChangedFile {
+            path,
+            additions,
+            deletions,
+        }
+    }
+}
+
+/// Compact markdown summary of an attempt for pasting into a PR description: the task, the
+/// coding agent's final summary and the changed files. Unlike a transcript export it leaves
+/// out the conversation.
+pub async fn get_summary_markdown(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<Response, ApiError> {
+    let pool = &deployment.db().pool;
+    let task = task_attempt
+        .parent_task(pool)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    let project = task
+        .parent_project(pool)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+
+    let agent_summary = match ExecutionProcess::find_latest_by_task_attempt_and_run_reason(
+        pool,
+        task_attempt.id,
+        &ExecutionProcessRunReason::CodingAgent,
+    )
+    .await?
+    {
+        Some(process) => ExecutorSession::find_by_execution_process_id(pool, process.id)
+            .await?
+            .and_then(|session| session.summary),
+        None => None,
+    };
+
+    let worktree_path = ensure_worktree_path(&deployment, &task_attempt).await?;
+    let base_commit = deployment.git().get_base_commit(
+        &project.git_repo_path,
+        &task_attempt.branch,
+        &task_attempt.target_branch,
+    )?;
+    let diffs = deployment.git().get_diffs(
+        DiffTarget::Worktree {
+            worktree_path: &worktree_path,
+            base_commit: &base_commit,
+        },
+        None,
+    )?;
+    let mut files: Vec<ChangedFile> = diffs.iter().map(ChangedFile::from).collect();
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+
+    let markdown = render_summary(&task, &task_attempt, agent_summary.as_deref(), &files);
+    Ok((
+        [(header::CONTENT_TYPE, "text/markdown; charset=utf-8")],
+        markdown,
+    )
+        .into_response())
+}
+
+fn render_summary(
+    task: &Task,
+    task_attempt: &TaskAttempt,
+    agent_summary: Option<&str>,
+    files: &[ChangedFile],
+) -> String {
+    let mut out = format!("## {}\n\n", task.title.trim());
+    if let Some(description) = task.description.as_deref().map(str::trim)
+        && !description.is_empty()
+    {
+        let _ = writeln!(out, "{description}\n");
+    }
+    let _ = writeln!(
+        out,
+        "Branch `{}` into `{}`\n",
+        task_attempt.branch, task_attempt.target_branch
+    );
+
+    if let Some(summary) = agent_summary.map(str::trim)
+        && !summary.is_empty()
+    {
+        let _ = writeln!(out, "### Summary\n\n{summary}\n");
+    }
+
+    out.push_str("### Changed files\n\n");
+    if files.is_empty() {
+        out.push_str("No changes.\n");
+    }
+    for file in files.iter().take(MAX_LISTED_FILES) {
+        let _ = writeln!(
+            out,
+            "- `{}` (+{} -{})",
+            file.path, file.additions, file.deletions
+        );
+    }
+    if files.len() > MAX_LISTED_FILES {
+        let _ = writeln!(out, "- …and {} more files", files.len() - MAX_LISTED_FILES);
+    }
+    out
+}
@@ -1,6 +1,8 @@
-use db::models::image::TaskImage;
 use deployment::Deployment;
-use services::services::{container::ContainerService, image::ImageService};
+use services::services::{
+    container::ContainerService, image::ImageService, prompt_snippets::PromptSnippetService,
+};
+use sqlx::Error as SqlxError;
 use uuid::Uuid;
 
 use crate::error::ApiError;
@@ -17,12 +19,11 @@ pub async fn ensure_worktree_path(
     Ok(std::path::PathBuf::from(container_ref))
 }
 
-/// Associate images to the task, copy into worktree, and canonicalize paths in the prompt.
-/// Returns the transformed prompt.
+/// Copy images already resolved with `ImageService::resolve_prompt_images` into the worktree
+/// and canonicalize paths in the prompt. Returns the transformed prompt.
 pub async fn handle_images_for_prompt(
     deployment: &crate::DeploymentImpl,
     attempt: &db::models::task_attempt::TaskAttempt,
-    task_id: Uuid,
     image_ids: &[Uuid],
     prompt: &str,
 ) -> Result<String, ApiError> {
@@ -30,8 +31,6 @@ pub async fn handle_images_for_prompt(
         return Ok(prompt.to_string());
     }
 
-    TaskImage::associate_many_dedup(&deployment.db().pool, task_id, image_ids).await?;
-
     // Copy to worktree and canonicalize
     let worktree_path = ensure_worktree_path(deployment, attempt).await?;
     deployment
@@ -43,3 +42,18 @@ pub async fn handle_images_for_prompt(
         &worktree_path,
     ))
 }
+
+/// Expand `{{snippet:name}}` references in a prompt for one of the attempt's agents, using the
+/// snippets of the attempt's project plus the global ones.
+pub async fn expand_prompt_snippets(
+    deployment: &crate::DeploymentImpl,
+    attempt: &db::models::task_attempt::TaskAttempt,
+    prompt: &str,
+) -> Result<String, ApiError> {
+    let pool = &deployment.db().pool;
+    let task = attempt
+        .parent_task(pool)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    Ok(PromptSnippetService::expand(pool, task.project_id, prompt).await?)
+}
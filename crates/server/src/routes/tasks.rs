@@ -14,19 +14,25 @@ use axum::{
 };
 use db::models::{
     image::TaskImage,
+    label::{Label, LabelFilter, LabelMatch},
     task::{CreateTask, Task, TaskWithAttemptStatus, UpdateTask},
     task_attempt::{CreateTaskAttempt, TaskAttempt},
+    task_event::TaskEvent,
 };
 use deployment::Deployment;
 use executors::profile::ExecutorProfileId;
 use futures_util::{SinkExt, StreamExt, TryStreamExt};
 use serde::{Deserialize, Serialize};
-use services::services::container::{
-    ContainerService, WorktreeCleanupData, cleanup_worktrees_direct,
+use services::services::{
+    container::{ContainerService, WorktreeCleanupData, cleanup_worktrees_direct},
+    prompt_snippets::PromptSnippetService,
 };
 use sqlx::Error as SqlxError;
 use ts_rs::TS;
-use utils::response::ApiResponse;
+use utils::{
+    pagination::{Page, PageQuery},
+    response::ApiResponse,
+};
 use uuid::Uuid;
 
 use crate::{DeploymentImpl, error::ApiError, middleware::load_task_middleware};
@@ -36,38 +42,84 @@ pub struct TaskQuery {
     pub project_id: Uuid,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct TaskListQuery {
+    pub project_id: Uuid,
+    /// Comma-separated label ids to filter by
+    #[serde(default)]
+    pub labels: Option<String>,
+    /// Whether tasks must carry all of `labels` or any of them
+    #[serde(default)]
+    pub label_match: LabelMatch,
+}
+
+impl TaskListQuery {
+    fn label_filter(&self) -> Result<LabelFilter, ApiError> {
+        let label_ids = match self.labels.as_deref() {
+            Some(labels) => parse_label_ids(labels)?,
+            None => Vec::new(),
+        };
+        Ok(LabelFilter {
+            label_ids,
+            label_match: self.label_match,
+        })
+    }
+}
+
+fn parse_label_ids(labels: &str) -> Result<Vec<Uuid>, ApiError> {
+    labels
+        .split(',')
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .map(|id| {
+            Uuid::parse_str(id)
+                .map_err(|_| ApiError::Validation(format!("Invalid label id '{id}'")))
+        })
+        .collect()
+}
+
 pub async fn get_tasks(
     State(deployment): State<DeploymentImpl>,
-    Query(query): Query<TaskQuery>,
-) -> Result<ResponseJson<ApiResponse<Vec<TaskWithAttemptStatus>>>, ApiError> {
-    let tasks =
-        Task::find_by_project_id_with_attempt_status(&deployment.db().pool, query.project_id)
-            .await?;
+    Query(query): Query<TaskListQuery>,
+    Query(page): Query<PageQuery>,
+) -> Result<ResponseJson<ApiResponse<Page<TaskWithAttemptStatus>>>, ApiError> {
+    let label_filter = query.label_filter()?;
+
+    let tasks = Task::fetch_page_with_attempt_status(
+        &deployment.db().pool,
+        query.project_id,
+        &label_filter,
+        &page,
+    )
+    .await?;
 
     Ok(ResponseJson(ApiResponse::success(tasks)))
 }
 
+/// Live task board for a project, narrowed by the same label filter as `get_tasks`
 pub async fn stream_tasks_ws(
     ws: WebSocketUpgrade,
     State(deployment): State<DeploymentImpl>,
-    Query(query): Query<TaskQuery>,
-) -> impl IntoResponse {
-    ws.on_upgrade(move |socket| async move {
-        if let Err(e) = handle_tasks_ws(socket, deployment, query.project_id).await {
+    Query(query): Query<TaskListQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let label_filter = query.label_filter()?;
+    Ok(ws.on_upgrade(move |socket| async move {
+        if let Err(e) = handle_tasks_ws(socket, deployment, query.project_id, label_filter).await {
             tracing::warn!("tasks WS closed: {}", e);
         }
-    })
+    }))
 }
 
 async fn handle_tasks_ws(
     socket: WebSocket,
     deployment: DeploymentImpl,
     project_id: Uuid,
+    label_filter: LabelFilter,
 ) -> anyhow::Result<()> {
     // Get the raw stream and convert LogMsg to WebSocket messages
     let mut stream = deployment
         .events()
-        .stream_tasks_raw(project_id)
+        .stream_tasks_raw(project_id, label_filter)
         .await?
         .map_ok(|msg| msg.to_ws_message_unchecked());
 
@@ -163,10 +215,16 @@ pub async fn create_task_and_start(
             }),
         )
         .await;
+    deployment
+        .image()
+        .resolve_prompt_images(task.id, &task.to_prompt(), &[])
+        .await?;
+    PromptSnippetService::expand(&deployment.db().pool, task.project_id, &task.to_prompt()).await?;
+
     let attempt_id = Uuid::new_v4();
     let git_branch_name = deployment
         .container()
-        .git_branch_from_task_attempt(&attempt_id, &task.title)
+        .git_branch_from_task_attempt(&attempt_id, &task.id, &task.title)
         .await;
 
     let task_attempt = TaskAttempt::create(
@@ -182,7 +240,7 @@ pub async fn create_task_and_start(
     .await?;
     let execution_process = deployment
         .container()
-        .start_attempt(&task_attempt, payload.executor_profile_id.clone())
+        .start_attempt(&task_attempt, payload.executor_profile_id.clone(), false)
         .await?;
     deployment
         .track_if_analytics_allowed(
@@ -199,6 +257,7 @@ pub async fn create_task_and_start(
     let task = Task::find_by_id(&deployment.db().pool, task.id)
         .await?
         .ok_or(ApiError::Database(SqlxError::RowNotFound))?;
+    let labels = Label::find_by_task_id(&deployment.db().pool, task.id).await?;
 
     tracing::info!("Started execution process {}", execution_process.id);
     Ok(ResponseJson(ApiResponse::success(TaskWithAttemptStatus {
@@ -206,7 +265,9 @@ pub async fn create_task_and_start(
         has_in_progress_attempt: true,
         has_merged_attempt: false,
         last_attempt_failed: false,
+        needs_input: false,
         executor: task_attempt.executor,
+        labels,
     })))
 }
 
@@ -266,7 +327,7 @@ pub async fn delete_task(
     }
 
     // Gather task attempts data needed for background cleanup
-    let attempts = TaskAttempt::fetch_all(&deployment.db().pool, Some(task.id))
+    let attempts = TaskAttempt::fetch_all(&deployment.db().pool, Some(task.id), false)
         .await
         .map_err(|e| {
             tracing::error!("Failed to fetch task attempts for task {}: {}", task.id, e);
@@ -314,6 +375,11 @@ pub async fn delete_task(
     // Commit the transaction - if this fails, all changes are rolled back
     tx.commit().await?;
 
+    // Removing the last unfinished subtask releases a blocked parent
+    if let Some(parent_task_id) = task.parent_task_id {
+        Task::unblock_if_subtasks_complete(&deployment.db().pool, parent_task_id).await?;
+    }
+
     if total_children_affected > 0 {
         tracing::info!(
             "Nullified {} child task references before deleting task {}",
@@ -360,9 +426,243 @@ pub async fn delete_task(
     Ok((StatusCode::ACCEPTED, ResponseJson(ApiResponse::success(()))))
 }
 
+#[derive(Debug, Deserialize, TS)]
+#[serde(untagged)]
+pub enum SubtaskSpec {
+    /// Re-parent an existing task from the same project
+    Existing { task_id: Uuid },
+    /// Create a new task
+    New {
+        title: String,
+        description: Option<String>,
+    },
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct SplitTaskRequest {
+    pub subtasks: Vec<SubtaskSpec>,
+    /// Hold the parent back until every subtask is done or cancelled
+    #[serde(default)]
+    pub block_parent: bool,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct SplitTaskResponse {
+    pub parent: Task,
+    pub subtasks: Vec<Task>,
+}
+
+pub async fn split_task(
+    Extension(parent): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<SplitTaskRequest>,
+) -> Result<ResponseJson<ApiResponse<SplitTaskResponse>>, ApiError> {
+    if payload.subtasks.is_empty() {
+        return Err(ApiError::Validation(
+            "At least one subtask is required".to_string(),
+        ));
+    }
+    let pool = &deployment.db().pool;
+
+    // Validate every subtask before creating anything
+    for spec in &payload.subtasks {
+        match spec {
+            SubtaskSpec::New { title, .. } if title.trim().is_empty() => {
+                return Err(ApiError::Validation(
+                    "Subtask titles cannot be empty".to_string(),
+                ));
+            }
+            SubtaskSpec::New { .. } => {}
+            SubtaskSpec::Existing { task_id } => {
+                let task = Task::find_by_id_and_project_id(pool, *task_id, parent.project_id)
+                    .await?
+                    .ok_or_else(|| {
+                        ApiError::Validation(format!("Task {task_id} is not in this project"))
+                    })?;
+                if Task::would_create_cycle(pool, parent.id, task.id).await? {
+                    return Err(ApiError::Validation(format!(
+                        "Making '{}' a subtask of '{}' would create a cycle",
+                        task.title, parent.title
+                    )));
+                }
+            }
+        }
+    }
+
+    // Also link new subtasks through the parent's latest attempt, if it has one
+    let parent_attempt_id = TaskAttempt::fetch_all(pool, Some(parent.id), false)
+        .await?
+        .first()
+        .map(|attempt| attempt.id);
+
+    let mut subtasks = Vec::with_capacity(payload.subtasks.len());
+    for spec in payload.subtasks {
+        let subtask = match spec {
+            SubtaskSpec::New { title, description } => {
+                let description = description.filter(|d| !d.trim().is_empty());
+                Task::create_subtask(
+                    pool,
+                    &parent,
+                    title.trim(),
+                    description.as_deref(),
+                    parent_attempt_id,
+                    Uuid::new_v4(),
+                )
+                .await?
+            }
+            SubtaskSpec::Existing { task_id } => {
+                Task::set_parent_task(pool, task_id, Some(parent.id)).await?;
+                Task::find_by_id(pool, task_id)
+                    .await?
+                    .ok_or(ApiError::Database(SqlxError::RowNotFound))?
+            }
+        };
+        subtasks.push(subtask);
+    }
+
+    if payload.block_parent {
+        Task::set_blocked_on_subtasks(pool, parent.id, true).await?;
+        // Re-parented subtasks may all be finished already
+        Task::unblock_if_subtasks_complete(pool, parent.id).await?;
+    }
+
+    let parent = Task::find_by_id(pool, parent.id)
+        .await?
+        .ok_or(ApiError::Database(SqlxError::RowNotFound))?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_split",
+            serde_json::json!({
+                "task_id": parent.id.to_string(),
+                "subtask_count": subtasks.len(),
+                "block_parent": payload.block_parent,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(SplitTaskResponse {
+        parent,
+        subtasks,
+    })))
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct TaskLabelRequest {
+    pub label_id: Uuid,
+}
+
+pub async fn get_task_labels(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<Label>>>, ApiError> {
+    let labels = Label::find_by_task_id(&deployment.db().pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(labels)))
+}
+
+pub async fn add_task_label(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<TaskLabelRequest>,
+) -> Result<ResponseJson<ApiResponse<Vec<Label>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let label = Label::find_by_id(pool, payload.label_id)
+        .await?
+        .filter(|label| label.project_id == task.project_id)
+        .ok_or_else(|| {
+            ApiError::Validation(format!(
+                "Label {} does not belong to this task's project",
+                payload.label_id
+            ))
+        })?;
+
+    Label::add_to_task(pool, label.id, task.id).await?;
+    if let Err(e) = deployment.events().push_task_update(task.id).await {
+        tracing::error!("Failed to push task update for {}: {}", task.id, e);
+    }
+
+    let labels = Label::find_by_task_id(pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(labels)))
+}
+
+pub async fn remove_task_label(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<TaskLabelRequest>,
+) -> Result<ResponseJson<ApiResponse<Vec<Label>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    if Label::remove_from_task(pool, query.label_id, task.id).await? > 0
+        && let Err(e) = deployment.events().push_task_update(task.id).await
+    {
+        tracing::error!("Failed to push task update for {}: {}", task.id, e);
+    }
+
+    let labels = Label::find_by_task_id(pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(labels)))
+}
+
+#[derive(Debug, Serialize, Deserialize, TS)]
+pub struct TaskPriority {
+    /// Higher starts first in the attempt queue; 0 is the default
+    pub priority: i32,
+}
+
+pub async fn get_task_priority(
+    Extension(task): Extension<Task>,
+) -> ResponseJson<ApiResponse<TaskPriority>> {
+    ResponseJson(ApiResponse::success(TaskPriority {
+        priority: task.priority,
+    }))
+}
+
+/// Change the task's priority. Its queued attempts move ahead of or behind other tasks'
+/// accordingly; the task itself is republished through the task stream.
+pub async fn set_task_priority(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<TaskPriority>,
+) -> Result<ResponseJson<ApiResponse<TaskPriority>>, ApiError> {
+    if payload.priority != task.priority {
+        Task::set_priority(&deployment.db().pool, task.id, payload.priority).await?;
+        if let Err(e) = deployment.events().push_attempt_queue_update().await {
+            tracing::error!("Failed to push attempt queue update: {}", e);
+        }
+
+        deployment
+            .track_if_analytics_allowed(
+                "task_priority_changed",
+                serde_json::json!({
+                    "task_id": task.id.to_string(),
+                    "project_id": task.project_id.to_string(),
+                    "priority": payload.priority,
+                }),
+            )
+            .await;
+    }
+    Ok(ResponseJson(ApiResponse::success(payload)))
+}
+
+/// Chronological audit trail of the task: creation, status changes, attempts, merges and PRs
+pub async fn get_task_activity(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<TaskEvent>>>, ApiError> {
+    let events = TaskEvent::find_by_task_id(&deployment.db().pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(events)))
+}
+
 pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     let task_id_router = Router::new()
         .route("/", get(get_task).put(update_task).delete(delete_task))
+        .route("/split", post(split_task))
+        .route(
+            "/labels",
+            get(get_task_labels)
+                .post(add_task_label)
+                .delete(remove_task_label),
+        )
+        .route("/activity", get(get_task_activity))
+        .route("/priority", get(get_task_priority).put(set_task_priority))
         .layer(from_fn_with_state(deployment.clone(), load_task_middleware));
 
     let inner = Router::new()
@@ -11,6 +11,12 @@ use db::models::{
     station_transition::{StationTransition, CreateStationTransition, UpdateStationTransition},
 };
 use deployment::Deployment;
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use services::services::{
+    output_schema::OutputSchema, workflow_orchestrator::WorkflowOrchestrator,
+};
+use ts_rs::TS;
 use utils::response::ApiResponse;
 use uuid::Uuid;
 
@@ -117,6 +123,14 @@ pub async fn delete_workflow(
 // Station Routes
 // ========================================
 
+fn validate_output_schema(output_schema: Option<&str>) -> Result<(), ApiError> {
+    if let Some(schema) = output_schema {
+        OutputSchema::parse(schema)
+            .map_err(|e| ApiError::Validation(format!("Invalid output schema: {}", e)))?;
+    }
+    Ok(())
+}
+
 pub async fn get_stations_by_workflow(
     State(deployment): State<DeploymentImpl>,
     axum::extract::Path(workflow_id): axum::extract::Path<Uuid>,
@@ -135,6 +149,8 @@ pub async fn create_station(
     State(deployment): State<DeploymentImpl>,
     Json(payload): Json<CreateWorkflowStation>,
 ) -> Result<ResponseJson<ApiResponse<WorkflowStation>>, ApiError> {
+    validate_output_schema(payload.output_schema.as_deref())?;
+
     let station_id = Uuid::new_v4();
     let station = WorkflowStation::create(&deployment.db().pool, payload, station_id).await?;
 
@@ -157,6 +173,8 @@ pub async fn update_station(
     State(deployment): State<DeploymentImpl>,
     Json(payload): Json<UpdateWorkflowStation>,
 ) -> Result<ResponseJson<ApiResponse<WorkflowStation>>, ApiError> {
+    validate_output_schema(payload.output_schema.as_ref().and_then(Option::as_deref))?;
+
     let updated_station = WorkflowStation::update(&deployment.db().pool, station.id, payload).await?;
 
     deployment
@@ -273,6 +291,57 @@ pub async fn delete_transition(
     }
 }
 
+/// A transition condition plus sample station results to evaluate it against
+#[derive(Debug, Deserialize, TS)]
+pub struct DryRunTransitionConditionRequest {
+    pub condition_type: Option<String>,
+    pub condition_value: Option<String>,
+    /// Sample station status, defaults to `completed`
+    pub status: Option<String>,
+    /// Sample station `output_data`
+    pub output_data: Option<JsonValue>,
+    /// Sample merged outputs of earlier stations, read by conditions with `"scope": "context"`
+    pub context_data: Option<JsonValue>,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct DryRunTransitionConditionResponse {
+    /// Whether the transition would be taken; always `false` when there are errors
+    pub result: bool,
+    /// Validation or evaluation errors for the condition
+    pub errors: Vec<String>,
+}
+
+/// Evaluate a transition condition against sample output without touching the database,
+/// so conditions can be debugged before a workflow is deployed
+pub async fn dry_run_transition_condition(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<DryRunTransitionConditionRequest>,
+) -> Result<ResponseJson<ApiResponse<DryRunTransitionConditionResponse>>, ApiError> {
+    let orchestrator = WorkflowOrchestrator::new(deployment.db().clone());
+    let outcome = orchestrator
+        .dry_run_transition_condition(
+            payload.condition_type.as_deref(),
+            payload.condition_value.as_deref(),
+            payload.status.as_deref().unwrap_or("completed"),
+            payload.output_data.as_ref(),
+            payload.context_data.as_ref(),
+        )
+        .await;
+
+    let response = match outcome {
+        Ok(result) => DryRunTransitionConditionResponse {
+            result,
+            errors: Vec::new(),
+        },
+        Err(e) => DryRunTransitionConditionResponse {
+            result: false,
+            errors: vec![e.to_string()],
+        },
+    };
+    Ok(ResponseJson(ApiResponse::success(response)))
+}
+
 // ========================================
 // Router
 // ========================================
@@ -323,10 +392,15 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
         .nest("/stations/{id}", station_id_router)
         // Transition operations by ID: GET/PUT/DELETE /transitions/{id}
         .nest("/transitions/{id}", transition_id_router)
+        // Evaluate a condition against sample output without saving anything
+        .route("/transition-conditions/dry-run", post(dry_run_transition_condition))
         // Workflow execution monitoring endpoints
         .route("/workflow-executions/{id}", get(workflow_executions::get_workflow_execution))
         .route("/workflow-executions/{id}/stations", get(workflow_executions::get_workflow_execution_stations))
+        .route("/workflow-executions/{id}/history", get(workflow_executions::get_workflow_execution_history))
         .route("/workflow-executions/{id}/cancel", post(workflow_executions::cancel_workflow_execution))
+        .route("/workflow-executions/{id}/pause", post(workflow_executions::pause_workflow_execution))
+        .route("/workflow-executions/{id}/resume", post(workflow_executions::resume_workflow_execution))
         .route("/workflow-executions/{id}/retry-station", post(workflow_executions::retry_station_execution))
         .route("/workflow-executions/{id}/complete-station", post(workflow_executions::complete_station))
         // Get active workflow execution for a task
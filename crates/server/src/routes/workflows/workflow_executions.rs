@@ -5,12 +5,11 @@ use axum::{
 };
 use chrono::Utc;
 use db::models::{
-    execution_process::ExecutionProcess,
     station_execution::StationExecution,
-    task::Task,
+    task::{Task, TaskStatus},
     task_attempt::{CreateTaskAttempt, TaskAttempt},
     workflow::Workflow,
-    workflow_execution::{CreateWorkflowExecution, UpdateWorkflowExecution, WorkflowExecution},
+    workflow_execution::{UpdateWorkflowExecution, WorkflowExecution},
     workflow_station::WorkflowStation,
     station_transition::StationTransition,
 };
@@ -49,9 +48,11 @@ use crate::{DeploymentImpl, error::ApiError};
 // ### Workflow Progression (Phase 4.8 Implementation)
 //
 // **1. Initialization (execute_workflow)**
+// - Returns the active execution if the workflow is already running on the task
+//   (`force_restart` cancels it and starts over instead)
 // - Creates TaskAttempt with git branch
-// - Creates WorkflowExecution record
-// - Starts first station via WorkflowOrchestrator.execute_station()
+// - Creates WorkflowExecution record and starts the first station via
+//   WorkflowOrchestrator.start_workflow_execution()
 //
 // **2. Station Execution**
 // - Each station has:
@@ -82,6 +83,11 @@ use crate::{DeploymentImpl, error::ApiError};
 // - No matching transition → workflow halts with error
 // - Retry endpoint allows retrying failed stations
 //
+// **6. Pause / Resume**
+// - Pause sets the workflow to "paused"; a running station is never interrupted
+// - When that station completes its result is recorded, but no next station starts
+// - Resume advances from the completed station, or just lifts the pause if it is still running
+//
 // ### First Station Determination
 //
 // The first station is determined by finding the station with the **lowest `position` value**.
@@ -102,6 +108,9 @@ pub struct ExecuteWorkflowRequest {
     pub task_id: Uuid,
     pub base_branch: String,
     pub executor_profile_id: Option<ExecutorProfileId>,
+    /// Cancel the workflow's active execution on this task and start a fresh one, instead of
+    /// returning the active execution
+    pub force_restart: Option<bool>,
 }
 
 #[derive(Debug, Serialize, TS)]
@@ -160,6 +169,10 @@ pub async fn execute_workflow(
         .await?
         .ok_or(SqlxError::RowNotFound)?;
 
+    let orchestrator = services::services::workflow_orchestrator::WorkflowOrchestrator::new(
+        deployment.db().clone()
+    );
+
     // 2. Validate workflow has stations and transitions
     let first_station = validate_workflow(pool, workflow.id).await?;
 
@@ -177,7 +190,7 @@ pub async fn execute_workflow(
     let attempt_id = Uuid::new_v4();
     let git_branch_name = deployment
         .container()
-        .git_branch_from_task_attempt(&attempt_id, &task.title)
+        .git_branch_from_task_attempt(&attempt_id, &task.id, &task.title)
         .await;
 
     let task_attempt = TaskAttempt::create(
@@ -192,50 +205,42 @@ pub async fn execute_workflow(
     )
     .await?;
 
-    // 5. Create WorkflowExecution record
-    let execution_id = Uuid::new_v4();
-    let workflow_execution = WorkflowExecution::create(
-        pool,
-        CreateWorkflowExecution {
-            workflow_id: workflow.id,
-            task_id: request.task_id,
-            task_attempt_id: Some(task_attempt.id),
-            status: "running".to_string(),
-        },
-        execution_id,
-    )
-    .await?;
-
-    // 6. Update workflow execution with first station and started_at timestamp
-    let workflow_execution = WorkflowExecution::update(
-        pool,
-        workflow_execution.id,
-        db::models::workflow_execution::UpdateWorkflowExecution {
-            current_station_id: Some(first_station.id),
-            status: Some("running".to_string()),
-            started_at: Some(Utc::now()),
-            completed_at: None,
-        },
-    )
-    .await?;
-
-    // 7. Start execution for the first station using WorkflowOrchestrator
-    // This creates a StationExecution record and starts the agent with proper context
-    let orchestrator = services::services::workflow_orchestrator::WorkflowOrchestrator::new(
-        deployment.db().clone()
-    );
-
-    let _station_execution = orchestrator
-        .execute_station(
+    // 5. Create the WorkflowExecution and start the first station using WorkflowOrchestrator
+    // This creates a StationExecution record and starts the agent with proper context. If the
+    // workflow is already running on the task, that execution is returned instead, unless a
+    // restart is requested, which cancels it first.
+    let workflow_execution = orchestrator
+        .start_workflow_execution(
             deployment.container(),
-            workflow_execution.id,
-            first_station.id,
+            workflow.id,
+            task.id,
             task_attempt.id,
-            None, // No context for first station
+            request.force_restart.unwrap_or(false),
         )
         .await
         .map_err(|e| ApiError::Validation(format!("Failed to start first station: {}", e)))?;
 
+    // Already running on the task: the attempt created above was never used
+    if workflow_execution.task_attempt_id != Some(task_attempt.id) {
+        TaskAttempt::delete(pool, task_attempt.id).await?;
+        tracing::info!(
+            "Workflow {} is already running for task {}, returning execution {}",
+            workflow.id,
+            task.id,
+            workflow_execution.id
+        );
+        return Ok(ResponseJson(ApiResponse::success(
+            ExecuteWorkflowResponse {
+                workflow_execution_id: workflow_execution.id,
+                task_attempt_id: workflow_execution.task_attempt_id.ok_or_else(|| {
+                    ApiError::Validation("Workflow execution has no task_attempt_id".to_string())
+                })?,
+                current_station_id: workflow_execution.current_station_id,
+                status: workflow_execution.status,
+            },
+        )));
+    }
+
     // Track analytics
     deployment
         .track_if_analytics_allowed(
@@ -384,6 +389,115 @@ pub async fn get_workflow_execution_stations(
     Ok(ResponseJson(ApiResponse::success(stations)))
 }
 
+/// Timeline of a finished or running workflow execution, for spotting slow stations
+#[derive(Debug, Serialize, TS)]
+pub struct WorkflowExecutionHistoryResponse {
+    pub id: uuid::Uuid,
+    pub workflow_id: uuid::Uuid,
+    pub task_id: uuid::Uuid,
+    pub status: String,
+    /// Status of the task the workflow ran for
+    pub task_status: TaskStatus,
+    pub started_at: Option<chrono::DateTime<Utc>>,
+    pub completed_at: Option<chrono::DateTime<Utc>>,
+    /// Until completion, or until now while the workflow is still running
+    pub duration_seconds: Option<i64>,
+    /// Station executions in the order they ran, retries included
+    pub stations: Vec<StationTimelineEntry>,
+}
+
+/// One station execution in a workflow execution's timeline
+#[derive(Debug, Serialize, TS)]
+pub struct StationTimelineEntry {
+    pub id: uuid::Uuid,
+    pub station_id: uuid::Uuid,
+    pub station_name: Option<String>,
+    pub status: String,
+    pub started_at: Option<chrono::DateTime<Utc>>,
+    pub completed_at: Option<chrono::DateTime<Utc>>,
+    /// Until completion, or until now while the station is still running
+    pub duration_seconds: Option<i64>,
+    /// Transition from the previous station that led here, shared by retries of the station.
+    /// `None` for the first station.
+    pub transition_id: Option<uuid::Uuid>,
+    pub transition_label: Option<String>,
+}
+
+/// Seconds between `started_at` and `completed_at`, or until now while still running
+fn elapsed_seconds(
+    started_at: Option<chrono::DateTime<Utc>>,
+    completed_at: Option<chrono::DateTime<Utc>>,
+    status: &str,
+) -> Option<i64> {
+    let end = match completed_at {
+        Some(completed_at) => completed_at,
+        None if status == "running" => Utc::now(),
+        None => return None,
+    };
+    started_at.map(|started_at| (end - started_at).num_seconds().max(0))
+}
+
+/// Get the timeline of a workflow execution with per-station durations
+/// GET /api/workflow-executions/{id}/history
+pub async fn get_workflow_execution_history(
+    State(deployment): State<DeploymentImpl>,
+    Path(execution_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<WorkflowExecutionHistoryResponse>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    let workflow_execution = WorkflowExecution::find_by_id(pool, execution_id)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    let task = Task::find_by_id(pool, workflow_execution.task_id)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    let transitions =
+        StationTransition::find_by_workflow_id(pool, workflow_execution.workflow_id).await?;
+    let station_executions =
+        StationExecution::find_by_workflow_execution(pool, execution_id).await?;
+
+    let mut stations: Vec<StationTimelineEntry> = Vec::new();
+    for station_execution in station_executions {
+        let station = WorkflowStation::find_by_id(pool, station_execution.station_id).await?;
+        let transition = station_execution
+            .transition_id
+            .and_then(|id| transitions.iter().find(|t| t.id == id));
+        stations.push(StationTimelineEntry {
+            id: station_execution.id,
+            station_id: station_execution.station_id,
+            station_name: station.map(|s| s.name),
+            duration_seconds: elapsed_seconds(
+                station_execution.started_at,
+                station_execution.completed_at,
+                &station_execution.status,
+            ),
+            status: station_execution.status,
+            started_at: station_execution.started_at,
+            completed_at: station_execution.completed_at,
+            transition_id: station_execution.transition_id,
+            transition_label: transition.and_then(|t| t.label.clone()),
+        });
+    }
+
+    Ok(ResponseJson(ApiResponse::success(
+        WorkflowExecutionHistoryResponse {
+            id: workflow_execution.id,
+            workflow_id: workflow_execution.workflow_id,
+            task_id: workflow_execution.task_id,
+            duration_seconds: elapsed_seconds(
+                workflow_execution.started_at,
+                workflow_execution.completed_at,
+                &workflow_execution.status,
+            ),
+            status: workflow_execution.status,
+            task_status: task.status,
+            started_at: workflow_execution.started_at,
+            completed_at: workflow_execution.completed_at,
+            stations,
+        },
+    )))
+}
+
 /// Get active workflow execution for a task
 /// GET /api/tasks/{task_id}/workflow-execution
 pub async fn get_task_workflow_execution(
@@ -395,12 +509,13 @@ pub async fn get_task_workflow_execution(
     // Find all workflow executions for this task
     let executions = WorkflowExecution::find_by_task(pool, task_id).await?;
 
-    // Find the first running execution (there should only be one)
-    let running_execution = executions
+    // Find the active execution (there should only be one); a paused one is still active so
+    // the UI can offer to resume it
+    let active_execution = executions
         .into_iter()
-        .find(|e| e.status == "running");
+        .find(|e| matches!(e.status.as_str(), "running" | "paused"));
 
-    if let Some(execution) = running_execution {
+    if let Some(execution) = active_execution {
         // Load all station executions
         let station_executions = StationExecution::find_by_workflow_execution(pool, execution.id).await?;
 
@@ -435,7 +550,7 @@ pub async fn get_task_workflow_execution(
 
         Ok(ResponseJson(ApiResponse::success(Some(response))))
     } else {
-        // No running execution found
+        // No active execution found
         Ok(ResponseJson(ApiResponse::success(None)))
     }
 }
@@ -496,63 +611,14 @@ pub async fn cancel_workflow_execution(
         )));
     }
 
-    // Find any running station executions
-    let station_executions = StationExecution::find_by_workflow_execution(pool, execution_id).await?;
-
-    // Stop all running execution processes first (cannot be rolled back)
-    for station_execution in &station_executions {
-        if station_execution.status == "running" {
-            // Find the execution process for this station
-            if let Some(execution_process_id) = station_execution.execution_process_id {
-                // Load the execution process
-                if let Some(execution_process) = ExecutionProcess::find_by_id(pool, execution_process_id).await? {
-                    // Stop the execution process if it's still running
-                    if execution_process.status == db::models::execution_process::ExecutionProcessStatus::Running {
-                        deployment.container().stop_execution(
-                            &execution_process,
-                            db::models::execution_process::ExecutionProcessStatus::Killed
-                        ).await?;
-                        tracing::info!("Stopped execution process {} for station execution {}", execution_process_id, station_execution.id);
-                    }
-                }
-            }
-        }
-    }
-
-    // Start a database transaction for updating statuses
-    let mut tx = pool.begin().await?;
-
-    // Update station execution statuses to cancelled
-    for station_execution in station_executions {
-        if station_execution.status == "running" {
-            sqlx::query!(
-                "UPDATE station_executions SET status = $2, updated_at = CURRENT_TIMESTAMP WHERE id = $1",
-                station_execution.id,
-                "cancelled"
-            )
-            .execute(&mut *tx)
-            .await?;
-        }
-    }
-
-    // Update workflow execution status to cancelled
-    let completed_at = Utc::now();
-    sqlx::query!(
-        "UPDATE workflow_executions SET status = $2, completed_at = $3, updated_at = CURRENT_TIMESTAMP WHERE id = $1",
-        execution_id,
-        "cancelled",
-        completed_at
-    )
-    .execute(&mut *tx)
-    .await?;
-
-    // Commit the transaction
-    tx.commit().await?;
-
-    // Reload the workflow execution to get updated values
-    let workflow_execution = WorkflowExecution::find_by_id(pool, execution_id)
-        .await?
-        .ok_or(SqlxError::RowNotFound)?;
+    // Stop running station processes and mark everything cancelled
+    let orchestrator = services::services::workflow_orchestrator::WorkflowOrchestrator::new(
+        deployment.db().clone()
+    );
+    let workflow_execution = orchestrator
+        .cancel_workflow_execution(deployment.container(), execution_id)
+        .await
+        .map_err(|e| ApiError::Validation(format!("Failed to cancel workflow: {}", e)))?;
 
     let message = if let Some(ref reason) = request.reason {
         format!("Workflow execution cancelled: {}", reason)
@@ -583,6 +649,86 @@ pub async fn cancel_workflow_execution(
     )))
 }
 
+/// Pause a running workflow execution
+/// POST /api/workflow-executions/{id}/pause
+///
+/// The current station keeps running; the workflow stops at the next station boundary,
+/// recording that station's result without starting the next one.
+pub async fn pause_workflow_execution(
+    State(deployment): State<DeploymentImpl>,
+    Path(execution_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<WorkflowExecution>>, ApiError> {
+    let orchestrator = services::services::workflow_orchestrator::WorkflowOrchestrator::new(
+        deployment.db().clone()
+    );
+
+    let workflow_execution = orchestrator
+        .pause_workflow_execution(execution_id)
+        .await
+        .map_err(|e| ApiError::Validation(format!("Failed to pause workflow: {}", e)))?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "workflow_execution_paused",
+            serde_json::json!({
+                "workflow_execution_id": execution_id.to_string(),
+                "workflow_id": workflow_execution.workflow_id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(workflow_execution)))
+}
+
+/// Resume a paused workflow execution
+/// POST /api/workflow-executions/{id}/resume
+///
+/// If the current station is still running the pause is simply lifted; if it has completed,
+/// the workflow advances from it to the next station.
+pub async fn resume_workflow_execution(
+    State(deployment): State<DeploymentImpl>,
+    Path(execution_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<WorkflowExecution>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    let workflow_execution = WorkflowExecution::find_by_id(pool, execution_id)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+
+    if workflow_execution.status != "paused" {
+        return Err(ApiError::Validation(format!(
+            "Cannot resume workflow execution in '{}' state",
+            workflow_execution.status
+        )));
+    }
+
+    let orchestrator = services::services::workflow_orchestrator::WorkflowOrchestrator::new(
+        deployment.db().clone()
+    );
+
+    orchestrator
+        .resume_workflow_execution(deployment.container(), execution_id)
+        .await
+        .map_err(|e| ApiError::Validation(format!("Failed to resume workflow: {}", e)))?;
+
+    let workflow_execution = WorkflowExecution::find_by_id(pool, execution_id)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "workflow_execution_resumed",
+            serde_json::json!({
+                "workflow_execution_id": execution_id.to_string(),
+                "workflow_id": workflow_execution.workflow_id.to_string(),
+                "status": workflow_execution.status,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(workflow_execution)))
+}
+
 /// Request body for retrying a failed station
 #[derive(Debug, Deserialize, TS)]
 #[ts(export)]
@@ -678,6 +824,7 @@ pub async fn retry_station_execution(
             deployment.container(),
             execution_id,
             station_execution.station_id,
+            station_execution.transition_id,
             task_attempt_id,
             Some(context_data),
         )
@@ -746,7 +893,7 @@ pub struct StationProgressionResponse {
     pub workflow_execution_id: Uuid,
     pub completed_station_id: Uuid,
     pub next_station_id: Option<Uuid>,
-    pub workflow_status: String,  // "running", "completed", "failed"
+    pub workflow_status: String,  // "running", "paused", "completed", "failed"
     pub message: String,
 }
 
@@ -783,16 +930,16 @@ pub struct StationProgressionResponse {
 pub async fn complete_station(
     State(deployment): State<DeploymentImpl>,
     Path(execution_id): Path<Uuid>,
-    Json(request): Json<CompleteStationRequest>,
+    Json(mut request): Json<CompleteStationRequest>,
 ) -> Result<ResponseJson<ApiResponse<StationProgressionResponse>>, ApiError> {
     let pool = &deployment.db().pool;
 
-    // 1. Validate workflow execution exists and is running
+    // 1. Validate workflow execution exists and is running (or paused, awaiting this station)
     let workflow_execution = WorkflowExecution::find_by_id(pool, execution_id)
         .await?
         .ok_or(SqlxError::RowNotFound)?;
 
-    if workflow_execution.status != "running" {
+    if workflow_execution.status != "running" && workflow_execution.status != "paused" {
         return Err(ApiError::Validation(format!(
             "Cannot complete station for workflow execution in '{}' state",
             workflow_execution.status
@@ -826,6 +973,33 @@ pub async fn complete_station(
         )));
     }
 
+    // Output that doesn't match the station's output schema fails the station, which lets a
+    // failure transition route it (e.g. back for a retry)
+    let mut schema_error = None;
+    if request.status == "completed" {
+        let station = WorkflowStation::find_by_id(pool, station_execution.station_id)
+            .await?
+            .ok_or(SqlxError::RowNotFound)?;
+        if let Err(e) =
+            services::services::workflow_orchestrator::WorkflowOrchestrator::validate_station_output(
+                &station,
+                request.output_data.as_deref(),
+            )
+        {
+            tracing::warn!(
+                "Station execution {} failed output schema validation: {}",
+                station_execution.id,
+                e
+            );
+            request.status = "failed".to_string();
+            schema_error = Some(e);
+        }
+    }
+    let with_schema_error = |message: String| match &schema_error {
+        Some(e) => format!("{}. {}", e, message),
+        None => message,
+    };
+
     // 3. Update station execution status and output_data
     let completed_at = Utc::now();
     StationExecution::update(
@@ -853,8 +1027,24 @@ pub async fn complete_station(
         execution_id
     );
 
+    // A pause requested while the station ran takes effect here; resume picks up from it
+    if workflow_execution.status == "paused" {
+        return Ok(ResponseJson(ApiResponse::success(
+            StationProgressionResponse {
+                workflow_execution_id: execution_id,
+                completed_station_id: station_execution.station_id,
+                next_station_id: None,
+                workflow_status: "paused".to_string(),
+                message: with_schema_error(format!(
+                    "Station marked as {}, workflow is paused until resumed",
+                    request.status
+                )),
+            },
+        )));
+    }
+
     // 4. Evaluate transitions to determine next station
-    let next_station_id = services::services::transition_evaluator::TransitionEvaluator::evaluate_next_station(
+    let next_transition = services::services::transition_evaluator::TransitionEvaluator::evaluate_next_station(
         pool,
         workflow_execution.workflow_id,
         station_execution.station_id,
@@ -864,7 +1054,8 @@ pub async fn complete_station(
     .map_err(|e| ApiError::Validation(format!("Failed to evaluate transition: {}", e)))?;
 
     // 5. Progress workflow based on transition evaluation
-    let (workflow_status, message) = if let Some(next_id) = next_station_id {
+    let (workflow_status, message) = if let Some(next_transition) = next_transition {
+        let next_id = next_transition.target_station_id;
         // Load the next station to check if it's a terminator
         let next_station = WorkflowStation::find_by_id(pool, next_id)
             .await?
@@ -894,28 +1085,31 @@ pub async fn complete_station(
                 .await?
                 .ok_or(ApiError::Validation("Task attempt not found".to_string()))?;
 
-            // Get project for git repository path
+            // Get project for its repository and PR remotes
             let project = db::models::project::Project::find_by_id(pool, task.project_id)
                 .await?
                 .ok_or(ApiError::Validation("Project not found".to_string()))?;
 
-            let git_repo_path = project.git_repo_path
-                .to_str()
-                .ok_or_else(|| ApiError::Validation("Invalid git repository path".to_string()))?
-                .to_string();
-
-            // Get GitHub token (if available)
-            let github_config = deployment.config().read().await.github.clone();
-            let github_token = github_config.token();
+            // Get GitHub token (if available) and the PR settings the create-PR route uses
+            let pr_settings = {
+                let config = deployment.config().read().await;
+                services::services::terminator_handler::TerminatorPrSettings {
+                    github_token: config.github.token(),
+                    default_pr_base: config.github.default_pr_base.clone(),
+                    title_branch_prefix: config.pr_title_branch_prefix.clone(),
+                }
+            };
 
             // Execute terminator handler
             services::services::terminator_handler::TerminatorHandler::execute(
                 pool,
-                github_token,
-                git_repo_path,
+                pr_settings,
+                deployment.github_cache(),
+                &project,
                 &task,
                 &workflow_execution,
                 &next_station,
+                Some(next_transition.id),
                 &task_attempt,
             )
             .await
@@ -983,6 +1177,7 @@ pub async fn complete_station(
                     deployment.container(),
                     execution_id,
                     next_id,
+                    Some(next_transition.id),
                     task_attempt_id,
                     Some(context_data),
                 )
@@ -1076,7 +1271,7 @@ pub async fn complete_station(
             completed_station_id: station_execution.station_id,
             next_station_id,
             workflow_status,
-            message,
+            message: with_schema_error(message),
         },
     )))
 }
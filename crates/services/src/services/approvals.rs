@@ -189,6 +189,43 @@ impl Approvals {
         }
     }
 
+    /// Resolve every pending approval of a stopped execution as cancelled, so its waiters
+    /// don't later hand a decision to a dead process. Returns how many were cancelled.
+    pub async fn cancel_for_execution(&self, execution_process_id: Uuid) -> usize {
+        let ids: Vec<String> = self
+            .pending
+            .iter()
+            .filter(|p| p.execution_process_id == execution_process_id)
+            .map(|p| p.key().clone())
+            .collect();
+        let store = self.msg_store_by_id(&execution_process_id).await;
+
+        let mut cancelled = 0;
+        for id in ids {
+            let Some((_, p)) = self.pending.remove(&id) else {
+                continue;
+            };
+            self.completed.insert(id, ApprovalStatus::Cancelled);
+            let _ = p.response_tx.send(ApprovalStatus::Cancelled);
+            if let Some(store) = &store
+                && let Some(updated_entry) =
+                    ToolStatus::from_approval_status(&ApprovalStatus::Cancelled)
+                        .and_then(|status| p.entry.with_tool_status(status))
+            {
+                store.push_patch(ConversationPatch::replace(p.entry_index, updated_entry));
+            }
+            cancelled += 1;
+        }
+        if cancelled > 0 {
+            tracing::debug!(
+                "Cancelled {} pending approval(s) for execution process {}",
+                cancelled,
+                execution_process_id
+            );
+        }
+        cancelled
+    }
+
     #[tracing::instrument(skip(self, id, timeout_at, waiter))]
     fn spawn_timeout_watcher(
         &self,
@@ -398,4 +435,61 @@ mod tests {
             "Should not match different tool ids"
         );
     }
+
+    #[tokio::test]
+    async fn stopping_execution_cancels_pending_approval() {
+        let execution_process_id = Uuid::new_v4();
+        let store = Arc::new(MsgStore::new());
+        store.push_patch(
+            executors::logs::utils::patch::ConversationPatch::add_normalized_entry(
+                0,
+                create_tool_use_entry("Edit", "foo.rs", "edit-id", ToolStatus::Created),
+            ),
+        );
+        let msg_stores = Arc::new(RwLock::new(HashMap::from([(
+            execution_process_id,
+            store.clone(),
+        )])));
+        let approvals = Approvals::new(msg_stores);
+
+        let request = ApprovalRequest::from_create(
+            utils::approvals::CreateApprovalRequest {
+                tool_name: "Edit".to_string(),
+                tool_input: serde_json::json!({}),
+                tool_call_id: "edit-id".to_string(),
+            },
+            execution_process_id,
+        );
+        let (request, waiter) = approvals.create_with_waiter(request).await.unwrap();
+
+        // Approvals of other executions are left alone
+        assert_eq!(approvals.cancel_for_execution(Uuid::new_v4()).await, 0);
+        assert_eq!(
+            approvals.cancel_for_execution(execution_process_id).await,
+            1
+        );
+
+        assert!(matches!(waiter.await, ApprovalStatus::Cancelled));
+        assert!(approvals.pending.is_empty());
+        assert!(matches!(
+            approvals.completed.get(&request.id).as_deref(),
+            Some(ApprovalStatus::Cancelled)
+        ));
+        let (_, entry) = store
+            .get_history()
+            .iter()
+            .rev()
+            .find_map(|msg| match msg {
+                LogMsg::JsonPatch(patch) => extract_normalized_entry_from_patch(patch),
+                _ => None,
+            })
+            .unwrap();
+        assert!(matches!(
+            entry.entry_type,
+            NormalizedEntryType::ToolUse {
+                status: ToolStatus::Denied { .. },
+                ..
+            }
+        ));
+    }
 }
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use anyhow::Error;
 use executors::{executors::BaseCodingAgent, profile::ExecutorProfileId};
 use serde::{Deserialize, Serialize};
@@ -5,12 +7,28 @@ use strum_macros::EnumString;
 use ts_rs::TS;
 pub use v6::{EditorConfig, EditorType, GitHubConfig, NotificationConfig, SoundFile, UiLanguage};
 
-use crate::services::config::versions::v6;
+use crate::services::{config::versions::v6, git_cli::FetchSettings};
 
 fn default_git_branch_prefix() -> String {
     "vk".to_string()
 }
 
+fn default_branch_template() -> String {
+    utils::git::DEFAULT_BRANCH_TEMPLATE.to_string()
+}
+
+fn default_commit_message_timeout_secs() -> u32 {
+    15
+}
+
+fn default_git_fetch_prune() -> bool {
+    true
+}
+
+fn default_orphan_image_cleanup_concurrency() -> u32 {
+    4
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, TS, Default)]
 pub struct ShowcaseState {
     #[serde(default)]
@@ -47,11 +65,91 @@ pub struct Config {
     pub language: UiLanguage,
     #[serde(default = "default_git_branch_prefix")]
     pub git_branch_prefix: String,
+    /// Template for attempt branch names; supports `{prefix}`, `{attempt_id}`, `{task_id}`
+    /// and `{slug}`. `-{attempt_id}` is appended when the template omits it.
+    #[serde(default = "default_branch_template")]
+    pub branch_template: String,
     #[serde(default)]
     pub showcases: ShowcaseState,
+    /// Ask the coding agent to write a commit message from the diff when it left no summary
+    #[serde(default)]
+    pub generate_commit_messages: bool,
+    /// Seconds to wait for a generated commit message; capped at 20 because finishing the
+    /// attempt's run waits on it
+    #[serde(default = "default_commit_message_timeout_secs")]
+    pub commit_message_timeout_secs: u32,
+    /// Extra CLI arguments appended to every command of an executor, after variant-derived
+    /// flags and profile `additional_params`
+    #[serde(default)]
+    pub executor_extra_args: HashMap<BaseCodingAgent, Vec<String>>,
+    /// How many times in a row a coding agent turn that crashed (non-zero exit) is re-run
+    /// before the attempt is handed back for review. Stopped turns are never retried.
+    #[serde(default)]
+    pub auto_retry_failed_turns: u32,
+    /// How many attempts may run setup, coding agent or cleanup processes at once. Queued
+    /// attempts start as slots free up. 0 means no limit.
+    #[serde(default)]
+    pub max_concurrent_attempts: u32,
+    /// Extra regexes masked in agent conversation logs on top of the built-in secret
+    /// patterns. If a pattern has a capture group only that group is masked.
+    #[serde(default)]
+    pub log_redaction_patterns: Vec<String>,
+    /// Allow asking the coding agent to explain an attempt's diff. Off by default since each
+    /// uncached summary is a paid agent run.
+    #[serde(default)]
+    pub diff_summaries_enabled: bool,
+    /// Push the target branch to the project's remote after a successful direct merge
+    #[serde(default)]
+    pub auto_push_after_merge: bool,
+    /// Commit agent and cleanup script changes with `--no-verify`, for repos whose hooks
+    /// reject or hang on automated commits. The repo's hooks run when false.
+    #[serde(default)]
+    pub skip_agent_commit_hooks: bool,
+    /// Open a new attempt's worktree in the configured editor once it has been created
+    #[serde(default)]
+    pub open_editor_on_start: bool,
+    /// Orphaned images deleted at once during image cleanup
+    #[serde(default = "default_orphan_image_cleanup_concurrency")]
+    pub orphan_image_cleanup_concurrency: u32,
+    /// Most orphaned images deleted per cleanup run; the rest wait for the next run. 0 means
+    /// no limit.
+    #[serde(default)]
+    pub orphan_image_cleanup_limit: u32,
+    /// Prefix stripped from the head branch name where it appears in a PR title, e.g. `vk/`
+    #[serde(default)]
+    pub pr_title_branch_prefix: Option<String>,
+    /// Longest an attempt may keep running setup, coding agent and cleanup processes before
+    /// it is stopped and handed back for review, in minutes. Checked every minute. 0 means no
+    /// limit.
+    #[serde(default)]
+    pub max_attempt_duration_mins: u32,
+    /// Clone new projects with only this many commits per branch, which makes the clone
+    /// shallow. Fetches into existing repositories are never shallow. `None` clones the full
+    /// history.
+    #[serde(default)]
+    pub git_fetch_depth: Option<u32>,
+    /// Delete remote-tracking branches that no longer exist on the remote when fetching
+    #[serde(default = "default_git_fetch_prune")]
+    pub git_fetch_prune: bool,
+    /// Squash an attempt's commits into one, using the agent's summary as the message, when
+    /// its coding agent run finishes. Branches that have been pushed are left alone.
+    #[serde(default)]
+    pub squash_on_finalize: bool,
+    /// Repositories whose committed `.vibe` scripts may run, by repository path, with the
+    /// SHA-256 of the config file that was approved. Editing the file needs a new approval.
+    #[serde(default)]
+    pub trusted_repo_scripts: HashMap<String, String>,
 }
 
 impl Config {
+    /// Settings for clones and fetches from remotes
+    pub fn fetch_settings(&self) -> FetchSettings {
+        FetchSettings {
+            clone_depth: self.git_fetch_depth,
+            prune: self.git_fetch_prune,
+        }
+    }
+
     pub fn from_previous_version(raw_config: &str) -> Result<Self, Error> {
         let old_config = match serde_json::from_str::<v6::Config>(raw_config) {
             Ok(cfg) => cfg,
@@ -98,7 +196,26 @@ impl Config {
             show_release_notes: old_config.show_release_notes,
             language: old_config.language,
             git_branch_prefix: default_git_branch_prefix(),
+            branch_template: default_branch_template(),
             showcases: ShowcaseState::default(),
+            generate_commit_messages: false,
+            commit_message_timeout_secs: default_commit_message_timeout_secs(),
+            executor_extra_args: HashMap::new(),
+            auto_retry_failed_turns: 0,
+            max_concurrent_attempts: 0,
+            log_redaction_patterns: Vec::new(),
+            diff_summaries_enabled: false,
+            auto_push_after_merge: false,
+            skip_agent_commit_hooks: false,
+            open_editor_on_start: false,
+            orphan_image_cleanup_concurrency: default_orphan_image_cleanup_concurrency(),
+            orphan_image_cleanup_limit: 0,
+            pr_title_branch_prefix: None,
+            max_attempt_duration_mins: 0,
+            git_fetch_depth: None,
+            git_fetch_prune: default_git_fetch_prune(),
+            squash_on_finalize: false,
+            trusted_repo_scripts: HashMap::new(),
         })
     }
 }
@@ -143,7 +260,26 @@ impl Default for Config {
             show_release_notes: false,
             language: UiLanguage::default(),
             git_branch_prefix: default_git_branch_prefix(),
+            branch_template: default_branch_template(),
             showcases: ShowcaseState::default(),
+            generate_commit_messages: false,
+            commit_message_timeout_secs: default_commit_message_timeout_secs(),
+            executor_extra_args: HashMap::new(),
+            auto_retry_failed_turns: 0,
+            max_concurrent_attempts: 0,
+            log_redaction_patterns: Vec::new(),
+            diff_summaries_enabled: false,
+            auto_push_after_merge: false,
+            skip_agent_commit_hooks: false,
+            open_editor_on_start: false,
+            orphan_image_cleanup_concurrency: default_orphan_image_cleanup_concurrency(),
+            orphan_image_cleanup_limit: 0,
+            pr_title_branch_prefix: None,
+            max_attempt_duration_mins: 0,
+            git_fetch_depth: None,
+            git_fetch_prune: default_git_fetch_prune(),
+            squash_on_finalize: false,
+            trusted_repo_scripts: HashMap::new(),
         }
     }
 }
@@ -2,6 +2,7 @@ use std::{
     collections::HashMap,
     path::{Path, PathBuf},
     sync::Arc,
+    time::Duration,
 };
 
 use anyhow::{Error as AnyhowError, anyhow};
@@ -15,8 +16,10 @@ use db::{
         },
         execution_process_logs::ExecutionProcessLogs,
         executor_session::{CreateExecutorSession, ExecutorSession},
+        project::Project,
         task::{Task, TaskStatus},
         task_attempt::{TaskAttempt, TaskAttemptError},
+        task_event::{TaskEvent, TaskEventType},
     },
 };
 use executors::{
@@ -24,16 +27,20 @@ use executors::{
         ExecutorAction, ExecutorActionType,
         coding_agent_follow_up::CodingAgentFollowUpRequest,
         coding_agent_initial::CodingAgentInitialRequest,
-        script::{ScriptContext, ScriptRequest, ScriptRequestLanguage},
+        script::{ScriptContext, ScriptRequest},
     },
     executors::{ExecutorError, StandardCodingAgentExecutor},
     profile::{ExecutorConfigs, ExecutorProfileId, to_default_variant},
 };
 use futures::{StreamExt, future};
+use regex::Regex;
+use serde::Serialize;
 use sqlx::Error as SqlxError;
 use thiserror::Error;
 use tokio::{sync::RwLock, task::JoinHandle};
+use ts_rs::TS;
 use utils::{
+    git::{DEFAULT_BRANCH_TEMPLATE, render_branch_template},
     log_msg::LogMsg,
     msg_store::MsgStore,
     text::{git_branch_id, short_uuid},
@@ -41,8 +48,13 @@ use utils::{
 use uuid::Uuid;
 
 use crate::services::{
-    git::{GitService, GitServiceError},
+    events::AttemptProgressStage,
+    git::{DiffContentOptions, GitService, GitServiceError},
     image::ImageService,
+    preview_url::detect_preview_url,
+    prompt_snippets::{PromptSnippetError, PromptSnippetService},
+    repo_config::RepoConfigService,
+    setup_cache::{SetupCacheError, SetupCacheService},
     worktree_manager::{WorktreeError, WorktreeManager},
 };
 pub type ContainerRef = String;
@@ -81,6 +93,27 @@ pub async fn cleanup_worktrees_direct(data: &[WorktreeCleanupData]) -> Result<()
     Ok(())
 }
 
+/// A dev server stopped by [`ContainerService::stop_dev_servers_except`]
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct StoppedDevServer {
+    pub execution_process_id: Uuid,
+    pub task_attempt_id: Uuid,
+    pub project_id: Uuid,
+    /// True if the process was left over from a previous server run; it is killed by pid in
+    /// the background
+    pub orphaned: bool,
+}
+
+/// A command started with [`ContainerService::exec_command`]
+pub struct ExecCommandHandle {
+    /// Key of the command's `MsgStore` in `msg_stores` while it runs
+    pub id: Uuid,
+    /// Output of the command; stays readable after it has been removed from `msg_stores`
+    pub msg_store: Arc<MsgStore>,
+    /// Resolves to the exit code, or None if the command was killed or could not be waited on
+    pub exit_code: JoinHandle<Option<i32>>,
+}
+
 #[derive(Debug, Error)]
 pub enum ContainerError {
     #[error(transparent)]
@@ -98,6 +131,8 @@ pub enum ContainerError {
     #[error(transparent)]
     TaskAttemptError(#[from] TaskAttemptError),
     #[error(transparent)]
+    PromptSnippet(#[from] PromptSnippetError),
+    #[error(transparent)]
     Other(#[from] AnyhowError), // Catches any unclassified errors
 }
 
@@ -109,10 +144,20 @@ pub trait ContainerService {
 
     fn git(&self) -> &GitService;
 
+    fn repo_config(&self) -> &RepoConfigService;
+
     fn task_attempt_to_current_dir(&self, task_attempt: &TaskAttempt) -> PathBuf;
 
     async fn create(&self, task_attempt: &TaskAttempt) -> Result<ContainerRef, ContainerError>;
 
+    /// Report which preparation stage an attempt has reached, or clear it with `None` once
+    /// the coding agent takes over or preparation fails
+    fn report_attempt_progress(
+        &self,
+        task_attempt: &TaskAttempt,
+        stage: Option<AttemptProgressStage>,
+    );
+
     async fn delete(&self, task_attempt: &TaskAttempt) -> Result<(), ContainerError> {
         self.try_stop(task_attempt).await;
         self.delete_inner(task_attempt).await
@@ -120,7 +165,7 @@ pub trait ContainerService {
 
     /// Check if a task has any running execution processes
     async fn has_running_processes(&self, task_id: Uuid) -> Result<bool, ContainerError> {
-        let attempts = TaskAttempt::fetch_all(&self.db().pool, Some(task_id)).await?;
+        let attempts = TaskAttempt::fetch_all(&self.db().pool, Some(task_id), false).await?;
 
         for attempt in attempts {
             if let Ok(processes) =
@@ -148,13 +193,16 @@ pub trait ContainerService {
         Ok(())
     }
 
-    fn cleanup_action(&self, cleanup_script: Option<String>) -> Option<Box<ExecutorAction>> {
-        cleanup_script.map(|script| {
+    async fn cleanup_action(&self, project: &Project) -> Option<Box<ExecutorAction>> {
+        let project = self.repo_config().effective(project.clone()).await;
+        project.cleanup_script.clone().map(|script| {
             Box::new(ExecutorAction::new(
                 ExecutorActionType::ScriptRequest(ScriptRequest {
                     script,
-                    language: ScriptRequestLanguage::Bash,
+                    language: project.script_language(),
                     context: ScriptContext::CleanupScript,
+                    cache_key: None,
+                    name: None,
                 }),
                 None,
             ))
@@ -189,6 +237,36 @@ pub trait ContainerService {
         &self,
         task_attempt: &TaskAttempt,
     ) -> Result<ContainerRef, ContainerError>;
+
+    /// Check the attempt's branch out in its worktree before an execution runs there. Only a
+    /// worktree shared by several attempts of a task can be on another attempt's branch.
+    async fn checkout_attempt_branch(
+        &self,
+        task_attempt: &TaskAttempt,
+    ) -> Result<(), ContainerError>;
+
+    /// Whether the attempt's branch is checked out in its worktree at `worktree_path`. A worktree
+    /// shared by several attempts of a task holds the branch of the attempt that last started an
+    /// execution there, so the other attempts' changes have to be read from their branches.
+    async fn worktree_on_attempt_branch(
+        &self,
+        task_attempt: &TaskAttempt,
+        worktree_path: &Path,
+    ) -> Result<bool, ContainerError> {
+        let Some(container_ref) = &task_attempt.container_ref else {
+            return Ok(true);
+        };
+        if !TaskAttempt::container_ref_shared(&self.db().pool, container_ref, task_attempt.id)
+            .await?
+        {
+            return Ok(true);
+        }
+        Ok(self
+            .git()
+            .get_head_info(worktree_path)
+            .is_ok_and(|head| head.branch == task_attempt.branch))
+    }
+
     async fn is_container_clean(&self, task_attempt: &TaskAttempt) -> Result<bool, ContainerError>;
 
     async fn start_execution_inner(
@@ -204,6 +282,41 @@ pub trait ContainerService {
         status: ExecutionProcessStatus,
     ) -> Result<(), ContainerError>;
 
+    /// Stop every running dev server except those belonging to `keep_project_ids`, including
+    /// dev servers a previous server run left running. Returns what was stopped; leftover dev
+    /// servers are marked killed right away and their processes are killed in the background.
+    async fn stop_dev_servers_except(
+        &self,
+        keep_project_ids: &[Uuid],
+    ) -> Result<Vec<StoppedDevServer>, ContainerError>;
+
+    /// Run a one-off shell command in the attempt's worktree, outside the execution process
+    /// lifecycle: nothing is recorded in the database, changes are not committed and no next
+    /// action, status change or notification follows.
+    ///
+    /// The command runs with the server's privileges and the project env file, exactly like
+    /// a setup script would, so it must only be reachable by the local user who could run it
+    /// in a terminal anyway. Implementations kill it after a bounded runtime.
+    async fn exec_command(
+        &self,
+        task_attempt: &TaskAttempt,
+        command: &str,
+    ) -> Result<ExecCommandHandle, ContainerError>;
+
+    /// Ask the coding agent for `executor_profile_id` to explain the attempt's diff against its
+    /// base branch. The diff fed to the agent is size-capped. Implementations may cache
+    /// summaries per commit; an error is returned instead of a partial summary.
+    async fn summarize_diff(
+        &self,
+        task_attempt: &TaskAttempt,
+        executor_profile_id: &ExecutorProfileId,
+    ) -> Result<String, ContainerError>;
+
+    /// Stop accepting new executions and stop dev servers, give the remaining executions up to
+    /// `grace_period` to finish on their own (returning as soon as they have), then checkpoint
+    /// and kill whatever is still running
+    async fn shutdown(&self, grace_period: Duration);
+
     async fn try_commit_changes(&self, ctx: &ExecutionContext) -> Result<bool, ContainerError>;
 
     async fn copy_project_files(
@@ -213,11 +326,14 @@ pub trait ContainerService {
         copy_files: &str,
     ) -> Result<(), ContainerError>;
 
-    /// Stream diff updates as LogMsg for WebSocket endpoints.
+    /// Stream diff updates as LogMsg for WebSocket endpoints. With `since_reviewed`, only
+    /// changes since the attempt's `reviewed_commit` are streamed (the full diff when unset).
     async fn stream_diff(
         &self,
         task_attempt: &TaskAttempt,
         stats_only: bool,
+        since_reviewed: bool,
+        options: DiffContentOptions,
     ) -> Result<futures::stream::BoxStream<'static, Result<LogMsg, std::io::Error>>, ContainerError>;
 
     /// Fetch the MsgStore for a given execution ID, panicking if missing.
@@ -228,15 +344,37 @@ pub trait ContainerService {
 
     async fn git_branch_prefix(&self) -> String;
 
-    async fn git_branch_from_task_attempt(&self, attempt_id: &Uuid, task_title: &str) -> String {
-        let task_title_id = git_branch_id(task_title);
+    async fn git_branch_template(&self) -> String;
+
+    async fn git_branch_from_task_attempt(
+        &self,
+        attempt_id: &Uuid,
+        task_id: &Uuid,
+        task_title: &str,
+    ) -> String {
+        let slug = git_branch_id(task_title);
         let prefix = self.git_branch_prefix().await;
+        let attempt_id = short_uuid(attempt_id);
+        let task_id = short_uuid(task_id);
 
-        if prefix.is_empty() {
-            format!("{}-{}", short_uuid(attempt_id), task_title_id)
-        } else {
-            format!("{}/{}-{}", prefix, short_uuid(attempt_id), task_title_id)
+        let template = self.git_branch_template().await;
+        let branch = render_branch_template(&template, &prefix, &attempt_id, &task_id, &slug);
+        if git2::Branch::name_is_valid(&branch).unwrap_or_default() {
+            return branch;
         }
+
+        tracing::warn!(
+            "Branch template '{}' produced invalid branch name '{}', using default template",
+            template,
+            branch
+        );
+        render_branch_template(
+            DEFAULT_BRANCH_TEMPLATE,
+            &prefix,
+            &attempt_id,
+            &task_id,
+            &slug,
+        )
     }
 
     async fn stream_raw_logs(
@@ -489,83 +627,239 @@ pub trait ContainerService {
         })
     }
 
+    /// Create the worktree and start the attempt. `force_setup` runs the setup script even if
+    /// its artifacts could be restored from the project's setup cache.
     async fn start_attempt(
         &self,
         task_attempt: &TaskAttempt,
         executor_profile_id: ExecutorProfileId,
+        force_setup: bool,
     ) -> Result<ExecutionProcess, ContainerError> {
         // Create container
         self.create(task_attempt).await?;
 
-        // Get parent task
-        let task = task_attempt
-            .parent_task(&self.db().pool)
-            .await?
-            .ok_or(SqlxError::RowNotFound)?;
+        let result = async {
+            // Get parent task
+            let task = task_attempt
+                .parent_task(&self.db().pool)
+                .await?
+                .ok_or(SqlxError::RowNotFound)?;
+
+            // // Get latest version of task attempt
+            let task_attempt = TaskAttempt::find_by_id(&self.db().pool, task_attempt.id)
+                .await?
+                .ok_or(SqlxError::RowNotFound)?;
+
+            // TODO: this implementation will not work in cloud
+            let worktree_path = PathBuf::from(
+                task_attempt
+                    .container_ref
+                    .as_ref()
+                    .ok_or_else(|| ContainerError::Other(anyhow!("Container ref not found")))?,
+            );
+            let prompt =
+                PromptSnippetService::expand(&self.db().pool, task.project_id, &task.to_prompt())
+                    .await?;
+            let prompt = ImageService::canonicalise_image_paths(&prompt, &worktree_path);
+
+            self.start_initial_request(
+                &task_attempt,
+                CodingAgentInitialRequest {
+                    prompt,
+                    executor_profile_id,
+                },
+                force_setup,
+            )
+            .await
+        }
+        .await;
+        // Preparation stopped short of the agent; don't leave the card on its last stage
+        if result.is_err() {
+            self.report_attempt_progress(task_attempt, None);
+        }
+        result
+    }
+
+    /// Start a fresh attempt from an existing initial coding agent request, recreating the
+    /// worktree and re-pointing image paths that referenced the source attempt's worktree.
+    async fn clone_attempt(
+        &self,
+        source_attempt: &TaskAttempt,
+        task_attempt: &TaskAttempt,
+        mut request: CodingAgentInitialRequest,
+    ) -> Result<ExecutionProcess, ContainerError> {
+        let worktree_path = self.create(task_attempt).await?;
 
+        if let Some(source_worktree) = &source_attempt.container_ref {
+            request.prompt = request.prompt.replace(source_worktree, &worktree_path);
+        }
+
+        let result = async {
+            let task_attempt = TaskAttempt::find_by_id(&self.db().pool, task_attempt.id)
+                .await?
+                .ok_or(SqlxError::RowNotFound)?;
+
+            self.start_initial_request(&task_attempt, request, false)
+                .await
+        }
+        .await;
+        if result.is_err() {
+            self.report_attempt_progress(task_attempt, None);
+        }
+        result
+    }
+
+    /// Build the project's setup script request for the attempt's worktree, or `None` when
+    /// the script can be skipped: the worktree is shared with an earlier attempt of the task,
+    /// or its artifacts were restored from the setup cache
+    async fn setup_script_request(
+        &self,
+        task_attempt: &TaskAttempt,
+        project: &Project,
+        setup_script: String,
+        force_setup: bool,
+    ) -> Option<ScriptRequest> {
+        // A worktree shared with the task's earlier attempts was set up when it was created
+        if !force_setup && let Some(container_ref) = &task_attempt.container_ref {
+            match TaskAttempt::container_ref_shared(&self.db().pool, container_ref, task_attempt.id)
+                .await
+            {
+                Ok(true) => {
+                    tracing::info!(
+                        "Task attempt {} reuses an already set up worktree, skipping setup script",
+                        task_attempt.id
+                    );
+                    return None;
+                }
+                Ok(false) => {}
+                Err(e) => tracing::warn!(
+                    "Failed to check whether task attempt {} shares its worktree: {}",
+                    task_attempt.id,
+                    e
+                ),
+            }
+        }
+
+        let artifacts = SetupCacheService::parse_list(project.setup_cache_artifacts.as_deref());
+        let cache_lookup = match &task_attempt.container_ref {
+            Some(worktree) if !artifacts.is_empty() => {
+                let worktree = PathBuf::from(worktree);
+                let globs = SetupCacheService::parse_list(project.setup_cache_files.as_deref());
+                let script = setup_script.clone();
+                let project_id = project.id;
+                tokio::task::spawn_blocking(move || {
+                    let Some(key) = SetupCacheService::compute_key(&worktree, &script, &globs)?
+                    else {
+                        return Ok(None);
+                    };
+                    let restored = !force_setup
+                        && SetupCacheService::new()
+                            .restore(project_id, &key, &worktree, &artifacts)?;
+                    Ok::<_, SetupCacheError>(Some((key, restored)))
+                })
+                .await
+                .map_err(|e| SetupCacheError::Io(std::io::Error::other(e)))
+                .and_then(|result| result)
+            }
+            _ => Ok(None),
+        };
+
+        let cache_key = match cache_lookup {
+            Ok(Some((key, true))) => {
+                tracing::info!(
+                    "Restored setup cache {} for task attempt {}, skipping setup script",
+                    key,
+                    task_attempt.id
+                );
+                return None;
+            }
+            Ok(Some((key, false))) => Some(key),
+            Ok(None) => None,
+            Err(e) => {
+                tracing::warn!(
+                    "Setup cache lookup failed for task attempt {}, running setup script: {}",
+                    task_attempt.id,
+                    e
+                );
+                None
+            }
+        };
+
+        Some(ScriptRequest {
+            script: setup_script,
+            language: project.script_language(),
+            context: ScriptContext::SetupScript,
+            cache_key,
+            name: None,
+        })
+    }
+
+    /// Run the project's setup script (if any) followed by the initial coding agent request
+    /// and the cleanup script. The container must already exist. The setup script is skipped
+    /// for a reused worktree or when the project's setup cache can be restored, unless
+    /// `force_setup` is set.
+    async fn start_initial_request(
+        &self,
+        task_attempt: &TaskAttempt,
+        request: CodingAgentInitialRequest,
+        force_setup: bool,
+    ) -> Result<ExecutionProcess, ContainerError> {
         // Get parent project
-        let project = task
+        let project = task_attempt
+            .parent_task(&self.db().pool)
+            .await?
+            .ok_or(SqlxError::RowNotFound)?
             .parent_project(&self.db().pool)
             .await?
             .ok_or(SqlxError::RowNotFound)?;
+        let project = self.repo_config().effective(project).await;
 
-        // // Get latest version of task attempt
-        let task_attempt = TaskAttempt::find_by_id(&self.db().pool, task_attempt.id)
-            .await?
-            .ok_or(SqlxError::RowNotFound)?;
+        let cleanup_action = self.cleanup_action(&project).await;
+        let setup_request = match project.setup_script.clone() {
+            Some(setup_script) => {
+                self.setup_script_request(task_attempt, &project, setup_script, force_setup)
+                    .await
+            }
+            None => None,
+        };
 
-        // TODO: this implementation will not work in cloud
-        let worktree_path = PathBuf::from(
-            task_attempt
-                .container_ref
+        self.report_attempt_progress(
+            task_attempt,
+            setup_request
                 .as_ref()
-                .ok_or_else(|| ContainerError::Other(anyhow!("Container ref not found")))?,
+                .map(|_| AttemptProgressStage::RunningSetup),
         );
-        let prompt = ImageService::canonicalise_image_paths(&task.to_prompt(), &worktree_path);
-
-        let cleanup_action = self.cleanup_action(project.cleanup_script);
 
         // Choose whether to execute the setup_script or coding agent first
-        let execution_process = if let Some(setup_script) = project.setup_script {
+        if let Some(setup_request) = setup_request {
             let executor_action = ExecutorAction::new(
-                ExecutorActionType::ScriptRequest(ScriptRequest {
-                    script: setup_script,
-                    language: ScriptRequestLanguage::Bash,
-                    context: ScriptContext::SetupScript,
-                }),
+                ExecutorActionType::ScriptRequest(setup_request),
                 // once the setup script is done, run the initial coding agent request
                 Some(Box::new(ExecutorAction::new(
-                    ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
-                        prompt,
-                        executor_profile_id: executor_profile_id.clone(),
-                    }),
+                    ExecutorActionType::CodingAgentInitialRequest(request),
                     cleanup_action,
                 ))),
             );
 
             self.start_execution(
-                &task_attempt,
+                task_attempt,
                 &executor_action,
                 &ExecutionProcessRunReason::SetupScript,
             )
-            .await?
+            .await
         } else {
             let executor_action = ExecutorAction::new(
-                ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
-                    prompt,
-                    executor_profile_id: executor_profile_id.clone(),
-                }),
+                ExecutorActionType::CodingAgentInitialRequest(request),
                 cleanup_action,
             );
 
             self.start_execution(
-                &task_attempt,
+                task_attempt,
                 &executor_action,
                 &ExecutionProcessRunReason::CodingAgent,
             )
-            .await?
-        };
-        Ok(execution_process)
+            .await
+        }
     }
 
     async fn start_execution(
@@ -574,6 +868,7 @@ pub trait ContainerService {
         executor_action: &ExecutorAction,
         run_reason: &ExecutionProcessRunReason,
     ) -> Result<ExecutionProcess, ContainerError> {
+        self.checkout_attempt_branch(task_attempt).await?;
         // Update task status to InProgress when starting an attempt
         let task = task_attempt
             .parent_task(&self.db().pool)
@@ -584,6 +879,10 @@ pub trait ContainerService {
         {
             Task::update_status(&self.db().pool, task.id, TaskStatus::InProgress).await?;
         }
+        // A new agent turn answers whatever the previous one was waiting on
+        if task_attempt.needs_input && run_reason == &ExecutionProcessRunReason::CodingAgent {
+            TaskAttempt::update_needs_input(&self.db().pool, task_attempt.id, false).await?;
+        }
         // Create new execution process record
         // Capture current HEAD as the "before" commit for this execution
         let before_head_commit = {
@@ -709,6 +1008,29 @@ pub trait ContainerService {
                     }
                 }
             }
+            ExecutorActionType::ScriptRequest(request)
+                if request.context == ScriptContext::DevServer =>
+            {
+                let pattern = task
+                    .parent_project(&self.db().pool)
+                    .await
+                    .ok()
+                    .flatten()
+                    .and_then(|project| project.preview_url_pattern)
+                    .and_then(|pattern| match Regex::new(&pattern) {
+                        Ok(regex) => Some(regex),
+                        Err(e) => {
+                            tracing::warn!(
+                                "Invalid preview URL pattern '{}', using defaults: {}",
+                                pattern,
+                                e
+                            );
+                            None
+                        }
+                    });
+                self.spawn_preview_url_detector(&execution_process.id, task_attempt, pattern)
+                    .await;
+            }
             _ => {}
         };
 
@@ -716,18 +1038,66 @@ pub trait ContainerService {
         Ok(execution_process)
     }
 
+    /// Watch a dev server's output for the URL it is listening on. The first match is stored
+    /// on the execution process and recorded in the task's activity timeline.
+    async fn spawn_preview_url_detector(
+        &self,
+        execution_id: &Uuid,
+        task_attempt: &TaskAttempt,
+        pattern: Option<Regex>,
+    ) {
+        let Some(msg_store) = self.get_msg_store_by_id(execution_id).await else {
+            return;
+        };
+        let execution_id = *execution_id;
+        let task_id = task_attempt.task_id;
+        let attempt_id = task_attempt.id;
+        let db = self.db().clone();
+
+        tokio::spawn(async move {
+            let mut lines = futures::stream::select(
+                msg_store.stdout_lines_stream(),
+                msg_store.stderr_lines_stream(),
+            );
+            while let Some(Ok(line)) = lines.next().await {
+                let Some(url) = detect_preview_url(&line, pattern.as_ref()) else {
+                    continue;
+                };
+                if let Err(e) =
+                    ExecutionProcess::update_preview_url(&db.pool, execution_id, &url).await
+                {
+                    tracing::error!(
+                        "Failed to store preview URL for execution {}: {}",
+                        execution_id,
+                        e
+                    );
+                }
+                if let Err(e) = TaskEvent::record(
+                    &db.pool,
+                    task_id,
+                    Some(attempt_id),
+                    TaskEventType::PreviewAvailable,
+                    None,
+                    None,
+                    Some(&url),
+                )
+                .await
+                {
+                    tracing::error!(
+                        "Failed to record preview URL for execution {}: {}",
+                        execution_id,
+                        e
+                    );
+                }
+                break;
+            }
+        });
+    }
+
     async fn try_start_next_action(&self, ctx: &ExecutionContext) -> Result<(), ContainerError> {
         let action = ctx.execution_process.executor_action()?;
-        let next_action = if let Some(next_action) = action.next_action() {
-            next_action
-        } else if matches!(
-            ctx.execution_process.run_reason,
-            ExecutionProcessRunReason::SetupScript
-        ) {
-            return Err(ContainerError::Other(anyhow::anyhow!(
-                "No next action configured for SetupScript"
-            )));
-        } else {
+        let Some(next_action) = action.next_action() else {
+            // Includes setup scripts re-run on their own against an existing worktree
             tracing::debug!("No next action configured");
             return Ok(());
         };
@@ -21,7 +21,7 @@ use utils::{
 
 use crate::services::{
     filesystem_watcher::{self, FilesystemWatcherError},
-    git::{Commit, DiffTarget, GitService, GitServiceError},
+    git::{Commit, DiffContentOptions, DiffTarget, GitService, GitServiceError},
 };
 
 /// Maximum cumulative diff bytes to stream before omitting content (200MB)
@@ -87,6 +87,7 @@ struct DiffWatcherContext {
     cumulative: Arc<AtomicUsize>,
     full_sent: Arc<std::sync::RwLock<HashSet<String>>>,
     stats_only: bool,
+    options: DiffContentOptions,
     tx: mpsc::Sender<Result<LogMsg, io::Error>>,
 }
 
@@ -96,8 +97,11 @@ impl DiffWatcherContext {
         events: Vec<DebouncedEvent>,
         canonical_worktree_path: &Path,
     ) -> bool {
-        let changed_paths =
+        let mut changed_paths =
             extract_changed_paths(&events, canonical_worktree_path, &self.worktree_path);
+        if let Some(filter) = &self.options.path_filter {
+            changed_paths.retain(|path| filter.matches(path));
+        }
 
         if changed_paths.is_empty() {
             return true;
@@ -109,6 +113,7 @@ impl DiffWatcherContext {
         let cumulative = self.cumulative.clone();
         let full_sent = self.full_sent.clone();
         let stats_only = self.stats_only;
+        let options = self.options.clone();
 
         match tokio::task::spawn_blocking(move || {
             process_file_changes(
@@ -119,6 +124,7 @@ impl DiffWatcherContext {
                 &cumulative,
                 &full_sent,
                 stats_only,
+                options,
             )
         })
         .await
@@ -147,13 +153,15 @@ pub async fn create(
     worktree_path: PathBuf,
     base_commit: Commit,
     stats_only: bool,
+    options: DiffContentOptions,
 ) -> Result<DiffStreamHandle, DiffStreamError> {
-    let initial_diffs_raw = git_service.get_diffs(
+    let initial_diffs_raw = git_service.get_diffs_with_options(
         DiffTarget::Worktree {
             worktree_path: &worktree_path,
             base_commit: &base_commit,
         },
         None,
+        options.clone(),
     )?;
 
     let cumulative = Arc::new(AtomicUsize::new(0));
@@ -186,6 +194,7 @@ pub async fn create(
         cumulative,
         full_sent,
         stats_only,
+        options,
         tx: tx_clone,
     };
 
@@ -336,6 +345,7 @@ fn extract_changed_paths(
         .collect()
 }
 
+#[allow(clippy::too_many_arguments)]
 fn process_file_changes(
     git_service: &GitService,
     worktree_path: &Path,
@@ -344,15 +354,17 @@ fn process_file_changes(
     cumulative_bytes: &Arc<AtomicUsize>,
     full_sent_paths: &Arc<std::sync::RwLock<HashSet<String>>>,
     stats_only: bool,
+    options: DiffContentOptions,
 ) -> Result<Vec<LogMsg>, DiffStreamError> {
     let path_filter: Vec<&str> = changed_paths.iter().map(|s| s.as_str()).collect();
 
-    let current_diffs = git_service.get_diffs(
+    let current_diffs = git_service.get_diffs_with_options(
         DiffTarget::Worktree {
             worktree_path,
             base_commit,
         },
         Some(&path_filter),
+        options,
     )?;
 
     let mut msgs = Vec::new();
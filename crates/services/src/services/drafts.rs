@@ -27,6 +27,7 @@ use uuid::Uuid;
 use super::{
     container::{ContainerError, ContainerService},
     image::{ImageError, ImageService},
+    prompt_snippets::{PromptSnippetError, PromptSnippetService},
 };
 
 #[derive(Debug, Error)]
@@ -39,6 +40,8 @@ pub enum DraftsServiceError {
     Image(#[from] ImageError),
     #[error(transparent)]
     ExecutionProcess(#[from] ExecutionProcessError),
+    #[error(transparent)]
+    PromptSnippet(#[from] PromptSnippetError),
     #[error("Conflict: {0}")]
     Conflict(String),
 }
@@ -194,13 +197,16 @@ impl DraftsService {
         prompt: &str,
         worktree_path: &Path,
     ) -> Result<String, DraftsServiceError> {
+        let image_ids = self
+            .image
+            .resolve_prompt_images(task_id, prompt, image_ids)
+            .await?;
         if image_ids.is_empty() {
             return Ok(prompt.to_string());
         }
 
-        TaskImage::associate_many_dedup(self.pool(), task_id, image_ids).await?;
         self.image
-            .copy_images_by_ids_to_worktree(worktree_path, image_ids)
+            .copy_images_by_ids_to_worktree(worktree_path, &image_ids)
             .await?;
         Ok(ImageService::canonicalise_image_paths(
             prompt,
@@ -235,14 +241,13 @@ impl DraftsService {
             .ok_or(SqlxError::RowNotFound)
             .map_err(DraftsServiceError::from)?;
 
-        let cleanup_action = container.cleanup_action(project.cleanup_script);
+        let cleanup_action = container.cleanup_action(&project).await;
 
-        let mut prompt = draft.prompt.clone();
-        if let Some(image_ids) = &draft.image_ids {
-            prompt = self
-                .handle_images_for_prompt(task_attempt.task_id, image_ids, &prompt, &worktree_path)
-                .await?;
-        }
+        let prompt = PromptSnippetService::expand(self.pool(), project.id, &draft.prompt).await?;
+        let image_ids = draft.image_ids.as_deref().unwrap_or_default();
+        let prompt = self
+            .handle_images_for_prompt(task_attempt.task_id, image_ids, &prompt, &worktree_path)
+            .await?;
 
         let latest_session_id =
             ExecutionProcess::find_latest_session_id_by_task_attempt(self.pool(), task_attempt.id)
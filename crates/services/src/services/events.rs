@@ -3,6 +3,7 @@ use std::{str::FromStr, sync::Arc};
 use db::{
     DBService,
     models::{
+        attempt_queue::QueuedAttempt,
         draft::{Draft, DraftType},
         execution_process::ExecutionProcess,
         task::Task,
@@ -22,8 +23,14 @@ mod streams;
 #[path = "events/types.rs"]
 pub mod types;
 
-pub use patches::{draft_patch, execution_process_patch, task_attempt_patch, task_patch};
-pub use types::{EventError, EventPatch, EventPatchInner, HookTables, RecordTypes};
+pub use patches::{
+    attempt_file_review_patch, attempt_git_patch, attempt_progress_patch, attempt_queue_patch,
+    draft_patch, execution_process_patch, task_attempt_patch, task_patch,
+};
+pub use types::{
+    AttemptFileReviewUpdate, AttemptGitOperation, AttemptGitUpdate, AttemptProgress,
+    AttemptProgressStage, EventError, EventPatch, EventPatchInner, HookTables, RecordTypes,
+};
 
 #[derive(Clone)]
 pub struct EventService {
@@ -299,6 +306,12 @@ impl EventService {
                                     return;
                                 }
                                 RecordTypes::TaskAttempt(attempt) => {
+                                    let patch = match hook.operation {
+                                        SqliteOperation::Insert => task_attempt_patch::add(attempt),
+                                        _ => task_attempt_patch::replace(attempt),
+                                    };
+                                    msg_store_for_hook.push_patch(patch);
+
                                     // Task attempts should update the parent task with fresh data
                                     if let Ok(Some(task)) =
                                         Task::find_by_id(&db.pool, attempt.task_id).await
@@ -424,6 +437,31 @@ impl EventService {
         }
     }
 
+    /// Push a fresh patch for a task after a change the database hooks don't observe,
+    /// such as labels being attached or detached
+    pub async fn push_task_update(&self, task_id: Uuid) -> Result<(), SqlxError> {
+        Self::push_task_update_for_task(&self.db.pool, self.msg_store.clone(), task_id).await
+    }
+
+    /// Push the current attempt queue after it changes
+    pub async fn push_attempt_queue_update(&self) -> Result<(), SqlxError> {
+        let entries = QueuedAttempt::find_all(&self.db.pool).await?;
+        self.msg_store
+            .push_patch(attempt_queue_patch::replace(&entries));
+        Ok(())
+    }
+
+    /// Push where an attempt's branch stands after a git operation moved it
+    pub fn push_attempt_git_update(&self, update: &AttemptGitUpdate) {
+        self.msg_store.push_patch(attempt_git_patch::set(update));
+    }
+
+    /// Push an attempt's reviewed files after a reviewer checks one off or new commits reset some
+    pub fn push_attempt_file_review_update(&self, update: &AttemptFileReviewUpdate) {
+        self.msg_store
+            .push_patch(attempt_file_review_patch::set(update));
+    }
+
     pub fn msg_store(&self) -> &Arc<MsgStore> {
         &self.msg_store
     }
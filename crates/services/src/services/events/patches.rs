@@ -1,4 +1,5 @@
 use db::models::{
+    attempt_queue::QueuedAttempt,
     draft::{Draft, DraftType},
     execution_process::ExecutionProcess,
     task::TaskWithAttemptStatus,
@@ -7,6 +8,8 @@ use db::models::{
 use json_patch::{AddOperation, Patch, PatchOperation, RemoveOperation, ReplaceOperation};
 use uuid::Uuid;
 
+use super::types::{AttemptFileReviewUpdate, AttemptGitUpdate, AttemptProgress};
+
 // Shared helper to escape JSON Pointer segments
 fn escape_pointer_segment(s: &str) -> String {
     s.replace('~', "~0").replace('/', "~1")
@@ -202,3 +205,89 @@ pub mod task_attempt_patch {
         })])
     }
 }
+
+/// Helper functions for creating attempt queue patches
+pub mod attempt_queue_patch {
+    use super::*;
+
+    /// Replace the whole queue; it is short and reorders touch every entry anyway
+    pub fn replace(entries: &[QueuedAttempt]) -> Patch {
+        Patch(vec![PatchOperation::Replace(ReplaceOperation {
+            path: "/attempt_queue"
+                .try_into()
+                .expect("Attempt queue path should be valid"),
+            value: serde_json::to_value(entries)
+                .expect("Attempt queue serialization should not fail"),
+        })])
+    }
+}
+
+/// Helper functions for creating attempt preparation progress patches
+pub mod attempt_progress_patch {
+    use super::*;
+
+    fn progress_path(attempt_id: Uuid) -> String {
+        format!(
+            "/attempt_progress/{}",
+            escape_pointer_segment(&attempt_id.to_string())
+        )
+    }
+
+    /// Set the current stage for an attempt; `add` also overwrites an earlier stage
+    pub fn set(progress: &AttemptProgress) -> Patch {
+        Patch(vec![PatchOperation::Add(AddOperation {
+            path: progress_path(progress.task_attempt_id)
+                .try_into()
+                .expect("Attempt progress path should be valid"),
+            value: serde_json::to_value(progress)
+                .expect("Attempt progress serialization should not fail"),
+        })])
+    }
+
+    /// Clear an attempt's progress once the coding agent has taken over
+    pub fn clear(attempt_id: Uuid) -> Patch {
+        Patch(vec![PatchOperation::Remove(RemoveOperation {
+            path: progress_path(attempt_id)
+                .try_into()
+                .expect("Attempt progress path should be valid"),
+        })])
+    }
+}
+
+/// Helper functions for creating attempt git update patches
+pub mod attempt_git_patch {
+    use super::*;
+
+    /// Set the latest git state for an attempt, overwriting an earlier one
+    pub fn set(update: &AttemptGitUpdate) -> Patch {
+        Patch(vec![PatchOperation::Add(AddOperation {
+            path: format!(
+                "/attempt_git/{}",
+                escape_pointer_segment(&update.task_attempt_id.to_string())
+            )
+            .try_into()
+            .expect("Attempt git path should be valid"),
+            value: serde_json::to_value(update)
+                .expect("Attempt git update serialization should not fail"),
+        })])
+    }
+}
+
+/// Helper functions for creating attempt file review patches
+pub mod attempt_file_review_patch {
+    use super::*;
+
+    /// Set the reviewed files of an attempt, overwriting the earlier set
+    pub fn set(update: &AttemptFileReviewUpdate) -> Patch {
+        Patch(vec![PatchOperation::Add(AddOperation {
+            path: format!(
+                "/attempt_file_reviews/{}",
+                escape_pointer_segment(&update.task_attempt_id.to_string())
+            )
+            .try_into()
+            .expect("Attempt file review path should be valid"),
+            value: serde_json::to_value(update)
+                .expect("Attempt file review serialization should not fail"),
+        })])
+    }
+}
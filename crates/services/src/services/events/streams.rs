@@ -1,6 +1,7 @@
 use db::models::{
     draft::{Draft, DraftType},
     execution_process::ExecutionProcess,
+    label::LabelFilter,
     task::{Task, TaskWithAttemptStatus},
 };
 use futures::StreamExt;
@@ -11,8 +12,11 @@ use uuid::Uuid;
 
 use super::{
     EventService,
-    patches::execution_process_patch,
-    types::{EventError, EventPatch, RecordTypes},
+    patches::{execution_process_patch, task_patch},
+    types::{
+        AttemptFileReviewUpdate, AttemptGitUpdate, AttemptProgress, EventError, EventPatch,
+        RecordTypes,
+    },
 };
 
 impl EventService {
@@ -20,6 +24,7 @@ impl EventService {
     pub async fn stream_tasks_raw(
         &self,
         project_id: Uuid,
+        label_filter: LabelFilter,
     ) -> Result<futures::stream::BoxStream<'static, Result<LogMsg, std::io::Error>>, EventError>
     {
         // Get initial snapshot of tasks
@@ -28,14 +33,32 @@ impl EventService {
         // Convert task array to object keyed by task ID
         let tasks_map: serde_json::Map<String, serde_json::Value> = tasks
             .into_iter()
+            .filter(|task| label_filter.matches(&task.labels))
             .map(|task| (task.id.to_string(), serde_json::to_value(task).unwrap()))
             .collect();
 
-        let initial_patch = json!([{
-            "op": "replace",
-            "path": "/tasks",
-            "value": tasks_map
-        }]);
+        let initial_patch = json!([
+            {
+                "op": "replace",
+                "path": "/tasks",
+                "value": tasks_map
+            },
+            {
+                "op": "replace",
+                "path": "/attempt_progress",
+                "value": {}
+            },
+            {
+                "op": "replace",
+                "path": "/attempt_git",
+                "value": {}
+            },
+            {
+                "op": "replace",
+                "path": "/attempt_file_reviews",
+                "value": {}
+            }
+        ]);
         let initial_msg = LogMsg::JsonPatch(serde_json::from_value(initial_patch).unwrap());
 
         // Clone necessary data for the async filter
@@ -45,6 +68,7 @@ impl EventService {
         let filtered_stream =
             BroadcastStream::new(self.msg_store.get_receiver()).filter_map(move |msg_result| {
                 let db_pool = db_pool.clone();
+                let label_filter = label_filter.clone();
                 async move {
                     match msg_result {
                         Ok(LogMsg::JsonPatch(patch)) => {
@@ -60,6 +84,7 @@ impl EventService {
                                                     op.value.clone(),
                                                 )
                                                 && task.project_id == project_id
+                                                && label_filter.matches(&task.labels)
                                             {
                                                 return Some(Ok(LogMsg::JsonPatch(patch)));
                                             }
@@ -72,6 +97,14 @@ impl EventService {
                                                 )
                                                 && task.project_id == project_id
                                             {
+                                                // A task whose labels stop matching leaves
+                                                // the filtered board; one that starts matching
+                                                // joins it
+                                                let patch = if label_filter.matches(&task.labels) {
+                                                    task_patch::add(&task)
+                                                } else {
+                                                    task_patch::remove(task.id)
+                                                };
                                                 return Some(Ok(LogMsg::JsonPatch(patch)));
                                             }
                                         }
@@ -84,6 +117,52 @@ impl EventService {
                                         }
                                         _ => {}
                                     }
+                                } else if patch_op.path().starts_with("/attempt_progress/") {
+                                    match patch_op {
+                                        json_patch::PatchOperation::Add(op) => {
+                                            if let Ok(progress) =
+                                                serde_json::from_value::<AttemptProgress>(
+                                                    op.value.clone(),
+                                                )
+                                                && let Ok(Some(task)) =
+                                                    Task::find_by_id(&db_pool, progress.task_id)
+                                                        .await
+                                                && task.project_id == project_id
+                                            {
+                                                return Some(Ok(LogMsg::JsonPatch(patch)));
+                                            }
+                                        }
+                                        json_patch::PatchOperation::Remove(_) => {
+                                            // Clearing progress for another project's attempt
+                                            // is a no-op on the client
+                                            return Some(Ok(LogMsg::JsonPatch(patch)));
+                                        }
+                                        _ => {}
+                                    }
+                                } else if patch_op.path().starts_with("/attempt_git/") {
+                                    if let json_patch::PatchOperation::Add(op) = patch_op
+                                        && let Ok(update) =
+                                            serde_json::from_value::<AttemptGitUpdate>(
+                                                op.value.clone(),
+                                            )
+                                        && let Ok(Some(task)) =
+                                            Task::find_by_id(&db_pool, update.task_id).await
+                                        && task.project_id == project_id
+                                    {
+                                        return Some(Ok(LogMsg::JsonPatch(patch)));
+                                    }
+                                } else if patch_op.path().starts_with("/attempt_file_reviews/") {
+                                    if let json_patch::PatchOperation::Add(op) = patch_op
+                                        && let Ok(update) =
+                                            serde_json::from_value::<AttemptFileReviewUpdate>(
+                                                op.value.clone(),
+                                            )
+                                        && let Ok(Some(task)) =
+                                            Task::find_by_id(&db_pool, update.task_id).await
+                                        && task.project_id == project_id
+                                    {
+                                        return Some(Ok(LogMsg::JsonPatch(patch)));
+                                    }
                                 } else if let Ok(event_patch_value) = serde_json::to_value(patch_op)
                                     && let Ok(event_patch) =
                                         serde_json::from_value::<EventPatch>(event_patch_value)
@@ -75,3 +75,56 @@ pub struct EventPatch {
     pub(crate) path: String,
     pub(crate) value: EventPatchInner,
 }
+
+/// Stage an attempt has reached while its worktree is prepared for the coding agent
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+#[ts(rename_all = "snake_case")]
+pub enum AttemptProgressStage {
+    CreatingWorktree,
+    CopyingFiles,
+    RunningSetup,
+}
+
+/// Preparation progress for a task attempt, streamed until the coding agent starts
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct AttemptProgress {
+    pub task_attempt_id: Uuid,
+    pub task_id: Uuid,
+    pub stage: AttemptProgressStage,
+}
+
+/// Git operation that moved an attempt's branch
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+#[ts(rename_all = "snake_case")]
+pub enum AttemptGitOperation {
+    Rebase,
+    Merge,
+}
+
+/// Where an attempt's branch stands after a rebase or merge, streamed so clients don't have
+/// to wait for the next branch status poll
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct AttemptGitUpdate {
+    pub task_attempt_id: Uuid,
+    pub task_id: Uuid,
+    pub operation: AttemptGitOperation,
+    /// HEAD of the attempt's branch
+    pub head_oid: String,
+    /// Tip of the target branch, which a merge moves
+    pub target_oid: Option<String>,
+    /// Commits on the attempt's branch that are not on the target branch
+    pub commits_ahead: Option<usize>,
+    /// Commits on the target branch that are not on the attempt's branch
+    pub commits_behind: Option<usize>,
+}
+
+/// Files of an attempt's diff that reviewers have checked off, streamed so everyone reviewing
+/// the attempt sees the others' progress
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct AttemptFileReviewUpdate {
+    pub task_attempt_id: Uuid,
+    pub task_id: Uuid,
+    pub reviewed_paths: Vec<String>,
+}
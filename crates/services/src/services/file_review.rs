@@ -0,0 +1,133 @@
+use std::path::Path;
+
+use db::models::{file_review::FileReview, task_attempt::TaskAttempt};
+use sqlx::SqlitePool;
+use thiserror::Error;
+use utils::msg_store::MsgStore;
+use uuid::Uuid;
+
+use crate::services::{
+    events::{AttemptFileReviewUpdate, attempt_file_review_patch},
+    git::{GitService, GitServiceError},
+};
+
+#[derive(Debug, Error)]
+pub enum FileReviewError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error(transparent)]
+    GitService(#[from] GitServiceError),
+}
+
+/// Where the reviewed contents of an attempt's files are read from
+#[derive(Debug, Clone, Copy)]
+pub enum AttemptFiles<'a> {
+    /// The attempt's worktree, uncommitted edits included
+    Worktree(&'a Path),
+    /// The tip of the attempt's branch, for an attempt whose shared worktree has another
+    /// attempt's branch checked out
+    Branch {
+        repo_path: &'a Path,
+        branch_name: &'a str,
+    },
+}
+
+impl AttemptFiles<'_> {
+    fn file_oid(&self, git: &GitService, path: &str) -> Result<Option<String>, GitServiceError> {
+        match self {
+            AttemptFiles::Worktree(worktree_path) => git.worktree_file_oid(worktree_path, path),
+            AttemptFiles::Branch {
+                repo_path,
+                branch_name,
+            } => git.branch_file_oid(repo_path, branch_name, path),
+        }
+    }
+}
+
+/// Files of an attempt's diff that reviewers have checked off. A review holds the file's
+/// contents at the time, so any later change to the file, committed or not, resets it.
+pub struct FileReviewService;
+
+impl FileReviewService {
+    /// Check a file off at its current contents, or un-check it
+    pub async fn set_reviewed(
+        pool: &SqlitePool,
+        git: &GitService,
+        task_attempt_id: Uuid,
+        files: AttemptFiles<'_>,
+        path: &str,
+        reviewed: bool,
+    ) -> Result<(), FileReviewError> {
+        if reviewed {
+            let content_oid = files.file_oid(git, path)?;
+            FileReview::mark_reviewed(pool, task_attempt_id, path, content_oid.as_deref()).await?;
+        } else {
+            FileReview::unmark_reviewed(pool, task_attempt_id, path).await?;
+        }
+        Ok(())
+    }
+
+    /// Files still reviewed, i.e. unchanged since they were checked off. Stale reviews are
+    /// skipped but kept; [`Self::prune_stale`] drops them.
+    pub async fn reviewed_paths(
+        pool: &SqlitePool,
+        git: &GitService,
+        task_attempt_id: Uuid,
+        files: AttemptFiles<'_>,
+    ) -> Result<Vec<String>, FileReviewError> {
+        let mut reviewed = Vec::new();
+        for review in FileReview::find_by_task_attempt_id(pool, task_attempt_id).await? {
+            if files.file_oid(git, &review.path)? == review.content_oid {
+                reviewed.push(review.path);
+            }
+        }
+        Ok(reviewed)
+    }
+
+    /// Files still reviewed, dropping the reviews of files that changed since. The flag is set
+    /// when any review was dropped.
+    pub async fn prune_stale(
+        pool: &SqlitePool,
+        git: &GitService,
+        task_attempt_id: Uuid,
+        files: AttemptFiles<'_>,
+    ) -> Result<(Vec<String>, bool), FileReviewError> {
+        let mut reviewed = Vec::new();
+        let mut reset = false;
+        for review in FileReview::find_by_task_attempt_id(pool, task_attempt_id).await? {
+            if files.file_oid(git, &review.path)? == review.content_oid {
+                reviewed.push(review.path);
+            } else {
+                FileReview::unmark_reviewed(pool, task_attempt_id, &review.path).await?;
+                reset = true;
+            }
+        }
+        Ok((reviewed, reset))
+    }
+
+    /// Drop the reviews of files that changed since they were checked off, e.g. after a process
+    /// edited or committed in the worktree, and stream the remaining set when any were dropped
+    pub async fn refresh(
+        pool: &SqlitePool,
+        git: &GitService,
+        msg_store: &MsgStore,
+        task_attempt: &TaskAttempt,
+        worktree_path: &Path,
+    ) -> Result<(), FileReviewError> {
+        let (reviewed, reset) = Self::prune_stale(
+            pool,
+            git,
+            task_attempt.id,
+            AttemptFiles::Worktree(worktree_path),
+        )
+        .await?;
+        if reset {
+            msg_store.push_patch(attempt_file_review_patch::set(&AttemptFileReviewUpdate {
+                task_attempt_id: task_attempt.id,
+                task_id: task_attempt.task_id,
+                reviewed_paths: reviewed,
+            }));
+        }
+        Ok(())
+    }
+}
@@ -4,12 +4,16 @@ use std::{
     path::{Path, PathBuf},
 };
 
-use ignore::WalkBuilder;
+use db::models::project::{SearchMatchType, SearchResult};
+use ignore::{Walk, WalkBuilder};
 use serde::Serialize;
 use thiserror::Error;
+use tokio::sync::mpsc;
 use tokio_util::sync::CancellationToken;
 use ts_rs::TS;
 
+use super::file_search_cache::SearchMode;
+
 #[derive(Clone)]
 pub struct FilesystemService {}
 
@@ -246,6 +250,116 @@ impl FilesystemService {
         Ok(git_repos)
     }
 
+    /// Walker over a repository for file search. `TaskForm` mode respects gitignore rules;
+    /// `Settings` mode includes ignored files (e.g. `.env`) but skips build output and
+    /// dependency directories. The walk stops descending once `cancelled` returns true.
+    pub fn search_walker(
+        repo_path: &Path,
+        mode: &SearchMode,
+        cancelled: impl Fn() -> bool + Send + Sync + 'static,
+    ) -> Walk {
+        let mut builder = WalkBuilder::new(repo_path);
+        match mode {
+            SearchMode::Settings => {
+                builder
+                    .git_ignore(false) // Include ignored files like .env
+                    .git_global(false)
+                    .git_exclude(false)
+                    .hidden(false)
+                    .filter_entry(move |entry| {
+                        let name = entry.file_name().to_string_lossy();
+                        // Always exclude .git directories and performance killers
+                        !cancelled()
+                            && name != ".git"
+                            && name != "node_modules"
+                            && name != "target"
+                            && name != "dist"
+                            && name != "build"
+                    });
+            }
+            SearchMode::TaskForm => {
+                builder
+                    .git_ignore(true) // Respect .gitignore
+                    .git_global(true) // Respect global .gitignore
+                    .git_exclude(true) // Respect .git/info/exclude
+                    .hidden(false) // Still show hidden files like .env (if not gitignored)
+                    .filter_entry(move |entry| !cancelled() && entry.file_name() != ".git");
+            }
+        }
+        builder.build()
+    }
+
+    /// Match a walked path against a lowercased query, preferring file name matches over
+    /// directory name and full path matches
+    pub fn match_search_entry(
+        repo_path: &Path,
+        path: &Path,
+        query_lower: &str,
+    ) -> Option<SearchResult> {
+        // Skip the root directory itself
+        if path == repo_path {
+            return None;
+        }
+
+        let relative_path = path.strip_prefix(repo_path).ok()?;
+        let relative_path_str = relative_path.to_string_lossy().to_lowercase();
+        let lowercase_name = |path: Option<&Path>| {
+            path.and_then(|p| p.file_name())
+                .map(|name| name.to_string_lossy().to_lowercase())
+                .unwrap_or_default()
+        };
+
+        let match_type = if lowercase_name(Some(path)).contains(query_lower) {
+            SearchMatchType::FileName
+        } else if !relative_path_str.contains(query_lower) {
+            return None;
+        } else if lowercase_name(path.parent()).contains(query_lower) {
+            SearchMatchType::DirectoryName
+        } else {
+            SearchMatchType::FullPath
+        };
+
+        Some(SearchResult {
+            path: relative_path.to_string_lossy().to_string(),
+            is_file: path.is_file(),
+            match_type,
+        })
+    }
+
+    /// Search a repository on a blocking thread, sending each match as soon as it is found.
+    /// The walk ends after `limit` matches, or promptly once the receiver is dropped.
+    pub fn stream_search(
+        &self,
+        repo_path: PathBuf,
+        query: &str,
+        mode: SearchMode,
+        limit: usize,
+    ) -> mpsc::Receiver<SearchResult> {
+        let (tx, rx) = mpsc::channel(32);
+        let query_lower = query.to_lowercase();
+
+        tokio::task::spawn_blocking(move || {
+            let closed = tx.clone();
+            let walker = Self::search_walker(&repo_path, &mode, move || closed.is_closed());
+            let mut sent = 0;
+            for entry in walker.flatten() {
+                if sent >= limit || tx.is_closed() {
+                    break;
+                }
+                let Some(result) = Self::match_search_entry(&repo_path, entry.path(), &query_lower)
+                else {
+                    continue;
+                };
+                if tx.blocking_send(result).is_err() {
+                    break;
+                }
+                sent += 1;
+            }
+        });
+
+        rx
+    }
+
     fn get_home_directory() -> PathBuf {
         dirs::home_dir()
             .or_else(dirs::desktop_dir)
@@ -1,10 +1,16 @@
-use std::{collections::HashMap, path::Path};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::{Arc, Condvar, Mutex, RwLock},
+    time::Instant,
+};
 
 use chrono::{DateTime, Utc};
 use git2::{
     BranchType, Delta, DiffFindOptions, DiffOptions, Error as GitError, Reference, Remote,
     Repository, Sort, build::CheckoutBuilder,
 };
+use ignore::overrides::{Override, OverrideBuilder};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use ts_rs::TS;
@@ -12,9 +18,41 @@ use utils::diff::{Diff, DiffChangeKind, FileDiffDetails};
 
 // Import for file ranking functionality
 use super::file_ranker::FileStat;
-use super::git_cli::{ChangeType, GitCli, GitCliError, StatusDiffEntry, StatusDiffOptions};
+use super::git_cli::{
+    ChangeType, FetchSettings, GitCli, GitCliError, StatusDiffEntry, StatusDiffOptions,
+};
 use crate::services::github_service::GitHubRepoInfo;
 
+lazy_static::lazy_static! {
+    /// Fetch settings from the user config, see [`set_fetch_settings`]
+    static ref FETCH_SETTINGS: RwLock<FetchSettings> = RwLock::new(FetchSettings::default());
+    /// Fetches of each repository (keyed by its common git dir, so all worktrees share an
+    /// entry) and refspec, so concurrent callers wait for the running fetch instead of
+    /// repeating it
+    static ref FETCHES: Mutex<HashMap<(PathBuf, String), Arc<FetchSlot>>> =
+        Mutex::new(HashMap::new());
+}
+
+/// Single-flight state of the fetches of one repository and refspec
+#[derive(Default)]
+struct FetchSlot {
+    state: Mutex<FetchState>,
+    /// Signalled whenever a fetch finishes
+    finished: Condvar,
+}
+
+#[derive(Default)]
+struct FetchState {
+    running: bool,
+    /// When the last successful fetch started
+    last_started: Option<Instant>,
+}
+
+/// Replace the user-configured settings applied to clones and fetches from remotes
+pub fn set_fetch_settings(settings: FetchSettings) {
+    *FETCH_SETTINGS.write().unwrap() = settings;
+}
+
 #[derive(Debug, Error)]
 pub enum GitServiceError {
     #[error(transparent)]
@@ -37,6 +75,16 @@ pub enum GitServiceError {
     TokenUnavailable,
     #[error("Rebase in progress; resolve or abort it before retrying")]
     RebaseInProgress,
+    #[error("Cannot blame {0}: {1}")]
+    FileNotBlameable(String, String),
+    #[error("Invalid commit selection: {0}")]
+    InvalidCommitSelection(String),
+    #[error("{0} has no unresolved conflicts")]
+    FileNotConflicted(String),
+    #[error("Conflict markers are still present in: {}", .0.join(", "))]
+    UnresolvedConflictMarkers(Vec<String>),
+    #[error("Remote '{0}' not found")]
+    RemoteNotFound(String),
 }
 /// Service for managing Git operations in task execution workflows
 #[derive(Clone)]
@@ -46,6 +94,12 @@ pub struct GitService {}
 // their contents omitted from the diff stream to avoid UI crashes.
 const MAX_INLINE_DIFF_BYTES: usize = 2 * 1024 * 1024; // ~2MB
 
+// Max file size for blame; blaming is much more expensive than diffing.
+const MAX_BLAME_BYTES: usize = 1024 * 1024; // ~1MB
+
+// Stage bits of an index entry's flags; zero for resolved (stage 0) entries.
+const INDEX_ENTRY_STAGE_MASK: u16 = 0x3000;
+
 #[derive(Debug, Clone, Serialize, Deserialize, TS, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 #[ts(rename_all = "snake_case")]
@@ -65,6 +119,61 @@ pub struct GitBranch {
     pub last_commit_date: DateTime<Utc>,
 }
 
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct BlameLine {
+    pub line_number: usize,
+    pub content: String,
+    /// None for lines that have not been committed yet
+    pub commit_id: Option<String>,
+    pub author: Option<String>,
+    pub summary: Option<String>,
+}
+
+/// Which side to keep when resolving a conflicted file. During a rebase "ours" is the
+/// branch being rebased onto and "theirs" is the task commit being replayed.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+#[ts(rename_all = "snake_case")]
+pub enum ConflictResolution {
+    Ours,
+    Theirs,
+}
+
+/// One `<<<<<<< ... >>>>>>>` region of a conflicted file
+#[derive(Debug, Clone, Serialize, TS, PartialEq, Eq)]
+pub struct ConflictHunk {
+    /// 1-based line of the `<<<<<<<` marker
+    pub start_line: usize,
+    /// 1-based line of the `>>>>>>>` marker
+    pub end_line: usize,
+    pub ours_label: Option<String>,
+    pub ours: String,
+    /// Only present when the conflict was written in diff3 style
+    pub base: Option<String>,
+    pub theirs_label: Option<String>,
+    pub theirs: String,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct FileConflict {
+    pub path: String,
+    /// Binary conflicts carry no hunks
+    pub is_binary: bool,
+    pub hunks: Vec<ConflictHunk>,
+}
+
+/// What merging a task branch into its base would do, computed without committing
+#[derive(Debug, Serialize, TS)]
+pub struct MergePreview {
+    /// Changes the merge would land on the base branch; conflicted files are left out
+    pub diffs: Vec<Diff>,
+    pub conflicted_files: Vec<String>,
+    /// Base branch commits missing from the task branch; merging is refused until rebased
+    pub base_commits_ahead: usize,
+    /// Uncommitted worktree changes are not part of the merge
+    pub has_uncommitted_changes: bool,
+}
+
 #[derive(Debug, Clone)]
 pub struct HeadInfo {
     pub branch: String,
@@ -119,6 +228,129 @@ pub struct WorktreeResetOutcome {
     pub applied: bool,
 }
 
+/// Options for [`GitService::commit_with_options`]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct CommitOptions {
+    /// Pass `--no-verify` so the repo's pre-commit and commit-msg hooks do not run
+    pub skip_hooks: bool,
+}
+
+/// Options controlling how file contents are compared when generating diffs. Diffs carry
+/// whole file contents, so hunk context is up to the viewer.
+#[derive(Debug, Default, Clone)]
+pub struct DiffContentOptions {
+    /// Ignore whitespace when comparing lines; files with only whitespace changes are dropped
+    pub ignore_whitespace: bool,
+    /// Only return diffs for paths accepted by this filter
+    pub path_filter: Option<DiffPathFilter>,
+}
+
+/// Gitignore-style globs selecting which file diffs are returned. Plain globs include paths
+/// and `!`-prefixed globs exclude them; with no include globs every path not excluded passes.
+#[derive(Debug, Clone)]
+pub struct DiffPathFilter(Override);
+
+impl DiffPathFilter {
+    pub fn new<S: AsRef<str>>(globs: &[S]) -> Result<Self, ignore::Error> {
+        let mut builder = OverrideBuilder::new("");
+        for glob in globs {
+            builder.add(glob.as_ref())?;
+        }
+        Ok(Self(builder.build()?))
+    }
+
+    /// Whether `path` (relative to the repository root) passes the filter
+    pub fn matches(&self, path: &str) -> bool {
+        !self.0.matched(path, false).is_ignore()
+    }
+}
+
+impl DiffContentOptions {
+    fn apply(&self, diff_opts: &mut DiffOptions) {
+        if self.ignore_whitespace {
+            diff_opts.ignore_whitespace(true);
+        }
+    }
+}
+
+/// Parse `<<<<<<<`, `|||||||`, `=======` and `>>>>>>>` markers into hunks.
+/// An unterminated region at end of file is ignored.
+fn parse_conflict_markers(content: &str) -> Vec<ConflictHunk> {
+    enum Section {
+        Outside,
+        Ours,
+        Base,
+        Theirs,
+    }
+
+    fn marker_label(line: &str) -> Option<String> {
+        let label = line[7..].trim();
+        (!label.is_empty()).then(|| label.to_string())
+    }
+
+    let mut hunks = Vec::new();
+    let mut section = Section::Outside;
+    let mut current: Option<ConflictHunk> = None;
+    for (idx, line) in content.split_inclusive('\n').enumerate() {
+        let line_number = idx + 1;
+        let trimmed = line.trim_end_matches(['\n', '\r']);
+        match section {
+            Section::Outside if trimmed.starts_with("<<<<<<<") => {
+                current = Some(ConflictHunk {
+                    start_line: line_number,
+                    end_line: line_number,
+                    ours_label: marker_label(trimmed),
+                    ours: String::new(),
+                    base: None,
+                    theirs_label: None,
+                    theirs: String::new(),
+                });
+                section = Section::Ours;
+            }
+            Section::Outside => {}
+            Section::Ours if trimmed.starts_with("|||||||") => {
+                if let Some(hunk) = current.as_mut() {
+                    hunk.base = Some(String::new());
+                }
+                section = Section::Base;
+            }
+            Section::Ours | Section::Base if trimmed == "=======" => {
+                section = Section::Theirs;
+            }
+            Section::Theirs if trimmed.starts_with(">>>>>>>") => {
+                if let Some(mut hunk) = current.take() {
+                    hunk.end_line = line_number;
+                    hunk.theirs_label = marker_label(trimmed);
+                    hunks.push(hunk);
+                }
+                section = Section::Outside;
+            }
+            Section::Ours => {
+                if let Some(hunk) = current.as_mut() {
+                    hunk.ours.push_str(line);
+                }
+            }
+            Section::Base => {
+                if let Some(base) = current.as_mut().and_then(|h| h.base.as_mut()) {
+                    base.push_str(line);
+                }
+            }
+            Section::Theirs => {
+                if let Some(hunk) = current.as_mut() {
+                    hunk.theirs.push_str(line);
+                }
+            }
+        }
+    }
+    hunks
+}
+
+/// What a task branch is rebased onto
+enum RebaseTarget<'a> {
+    Branch(&'a str),
+    Commit(&'a str),
+}
+
 /// Target for diff generation
 pub enum DiffTarget<'p> {
     /// Work-in-progress branch checked out in this worktree
@@ -196,6 +428,83 @@ impl GitService {
         }
     }
 
+    /// `remote_name` if configured and present in the repo, otherwise the default remote
+    fn resolve_remote_name(
+        &self,
+        repo: &Repository,
+        remote_name: Option<&str>,
+    ) -> Result<String, GitServiceError> {
+        match remote_name {
+            Some(name) => {
+                repo.find_remote(name)
+                    .map_err(|_| GitServiceError::RemoteNotFound(name.to_string()))?;
+                Ok(name.to_string())
+            }
+            None => Ok(self.default_remote_name(repo)),
+        }
+    }
+
+    /// Check that a configured remote exists in the repository
+    pub fn ensure_remote_exists(
+        &self,
+        repo_path: &Path,
+        remote_name: &str,
+    ) -> Result<(), GitServiceError> {
+        let repo = self.open_repo(repo_path)?;
+        self.resolve_remote_name(&repo, Some(remote_name))
+            .map(|_| ())
+    }
+
+    /// Clone `url` into `dest`, reporting git's progress lines to `on_progress`. The GitHub
+    /// token is only used for GitHub URLs, which are cloned over HTTPS so it applies.
+    ///
+    /// A failed clone leaves `dest` as it found it: removed if it did not exist, emptied if
+    /// it was an empty directory, so the same path can be retried.
+    pub fn clone_with_progress(
+        &self,
+        url: &str,
+        dest: &Path,
+        github_token: Option<&str>,
+        on_progress: impl FnMut(&str),
+    ) -> Result<(), GitServiceError> {
+        let is_github = GitHubRepoInfo::from_remote_url(url).is_ok();
+        let (url, token) = match github_token {
+            Some(token) if is_github => (self.convert_to_https_url(url), Some(token)),
+            _ => (url.to_string(), None),
+        };
+        let depth = FETCH_SETTINGS.read().unwrap().clone_depth;
+        let existed = dest.exists();
+        if let Err(e) = GitCli::new().clone_with_progress(&url, dest, token, depth, on_progress) {
+            Self::remove_clone(dest, existed);
+            return Err(e.into());
+        }
+        Ok(())
+    }
+
+    /// Undo a clone into `dest`: remove it if it did not exist before the clone, otherwise
+    /// empty it again
+    pub fn remove_clone(dest: &Path, existed: bool) {
+        let result = if existed {
+            std::fs::read_dir(dest).and_then(|entries| {
+                entries.flatten().try_for_each(|entry| {
+                    let path = entry.path();
+                    if path.is_dir() {
+                        std::fs::remove_dir_all(&path)
+                    } else {
+                        std::fs::remove_file(&path)
+                    }
+                })
+            })
+        } else if dest.exists() {
+            std::fs::remove_dir_all(dest)
+        } else {
+            Ok(())
+        };
+        if let Err(e) = result {
+            tracing::warn!("Failed to remove clone at {}: {}", dest.display(), e);
+        }
+    }
+
     /// Initialize a new git repository with a main branch and initial commit
     pub fn initialize_repo_with_main_branch(
         &self,
@@ -220,14 +529,16 @@ impl GitService {
         Ok(())
     }
 
-    /// Ensure an existing repository has a main branch (for empty repos)
-    pub fn ensure_main_branch_exists(&self, repo_path: &Path) -> Result<(), GitServiceError> {
+    /// Ensure an existing repository has a branch to work from (for empty repos). The initial
+    /// commit goes on the branch `HEAD` already names, so a repo initialised with e.g. `master`
+    /// doesn't get a spurious `main`.
+    pub fn ensure_default_branch_exists(&self, repo_path: &Path) -> Result<(), GitServiceError> {
         let repo = self.open_repo(repo_path)?;
 
         match repo.branches(None) {
             Ok(branches) => {
                 if branches.count() == 0 {
-                    // No branches exist - create initial commit on main branch
+                    // No branches exist - create initial commit on the unborn HEAD branch
                     self.create_initial_commit(&repo)?;
                 }
             }
@@ -249,9 +560,16 @@ impl GitService {
         };
         let tree = repo.find_tree(tree_id)?;
 
-        // Create initial commit on main branch
+        // Commit onto the branch HEAD points at (honours `init.defaultBranch`), else main
+        let branch_ref = repo
+            .find_reference("HEAD")
+            .ok()
+            .and_then(|head| head.symbolic_target().map(str::to_string))
+            .filter(|target| target.starts_with("refs/heads/"))
+            .unwrap_or_else(|| "refs/heads/main".to_string());
+
         let _commit_id = repo.commit(
-            Some("refs/heads/main"),
+            Some(&branch_ref),
             &signature,
             &signature,
             "Initial commit",
@@ -259,13 +577,23 @@ impl GitService {
             &[],
         )?;
 
-        // Set HEAD to point to main branch
-        repo.set_head("refs/heads/main")?;
+        // Set HEAD to point to the new branch
+        repo.set_head(&branch_ref)?;
 
         Ok(())
     }
 
     pub fn commit(&self, path: &Path, message: &str) -> Result<bool, GitServiceError> {
+        self.commit_with_options(path, message, CommitOptions::default())
+    }
+
+    /// Like [`GitService::commit`], optionally bypassing the repo's commit hooks
+    pub fn commit_with_options(
+        &self,
+        path: &Path,
+        message: &str,
+        options: CommitOptions,
+    ) -> Result<bool, GitServiceError> {
         // Use Git CLI to respect sparse-checkout semantics for staging and commit
         let git = GitCli::new();
         let has_changes = git
@@ -280,7 +608,7 @@ impl GitService {
             .map_err(|e| GitServiceError::InvalidRepository(format!("git add failed: {e}")))?;
         // Only ensure identity once we know we're about to commit
         self.ensure_cli_commit_identity(path)?;
-        git.commit(path, message)
+        git.commit(path, message, options.skip_hooks)
             .map_err(|e| GitServiceError::InvalidRepository(format!("git commit failed: {e}")))?;
         Ok(true)
     }
@@ -290,6 +618,29 @@ impl GitService {
         &self,
         target: DiffTarget,
         path_filter: Option<&[&str]>,
+    ) -> Result<Vec<Diff>, GitServiceError> {
+        self.get_diffs_with_options(target, path_filter, DiffContentOptions::default())
+    }
+
+    /// Get diffs between branches or worktree changes with custom context/whitespace handling
+    pub fn get_diffs_with_options(
+        &self,
+        target: DiffTarget,
+        path_filter: Option<&[&str]>,
+        options: DiffContentOptions,
+    ) -> Result<Vec<Diff>, GitServiceError> {
+        let mut diffs = self.collect_diffs(target, path_filter, &options)?;
+        if let Some(filter) = &options.path_filter {
+            diffs.retain(|diff| filter.matches(&Self::diff_path(diff)));
+        }
+        Ok(diffs)
+    }
+
+    fn collect_diffs(
+        &self,
+        target: DiffTarget,
+        path_filter: Option<&[&str]>,
+        options: &DiffContentOptions,
     ) -> Result<Vec<Diff>, GitServiceError> {
         match target {
             DiffTarget::Worktree {
@@ -319,6 +670,7 @@ impl GitService {
                 Ok(entries
                     .into_iter()
                     .map(|e| Self::status_entry_to_diff(&repo, &base_tree, e))
+                    .filter(|d| !(options.ignore_whitespace && Self::is_whitespace_only_change(d)))
                     .collect())
             }
             DiffTarget::Branch {
@@ -338,6 +690,7 @@ impl GitService {
 
                 let mut diff_opts = DiffOptions::new();
                 diff_opts.include_typechange(true);
+                options.apply(&mut diff_opts);
 
                 // Add path filtering if specified
                 if let Some(paths) = path_filter {
@@ -356,7 +709,7 @@ impl GitService {
                 let mut find_opts = DiffFindOptions::new();
                 diff.find_similar(Some(&mut find_opts))?;
 
-                self.convert_diff_to_file_diffs(diff, &repo)
+                self.convert_diff_to_file_diffs(diff, &repo, options.ignore_whitespace)
             }
             DiffTarget::Commit {
                 repo_path,
@@ -382,6 +735,7 @@ impl GitService {
                 // Diff options
                 let mut diff_opts = git2::DiffOptions::new();
                 diff_opts.include_typechange(true);
+                options.apply(&mut diff_opts);
 
                 // Optional path filtering
                 if let Some(paths) = path_filter {
@@ -401,7 +755,7 @@ impl GitService {
                 let mut find_opts = git2::DiffFindOptions::new();
                 diff.find_similar(Some(&mut find_opts))?;
 
-                self.convert_diff_to_file_diffs(diff, &repo)
+                self.convert_diff_to_file_diffs(diff, &repo, options.ignore_whitespace)
             }
         }
     }
@@ -411,6 +765,7 @@ impl GitService {
         &self,
         diff: git2::Diff,
         repo: &Repository,
+        skip_empty_patches: bool,
     ) -> Result<Vec<Diff>, GitServiceError> {
         let mut file_diffs = Vec::new();
 
@@ -423,6 +778,16 @@ impl GitService {
 
                 let status = delta.status();
 
+                // With whitespace ignored, modified files whose patch has no hunks are unchanged
+                if skip_empty_patches
+                    && matches!(status, Delta::Modified)
+                    && let Ok(Some(patch)) = git2::Patch::from_diff(&diff, delta_index)
+                    && patch.num_hunks() == 0
+                {
+                    delta_index += 1;
+                    return true;
+                }
+
                 // Decide if we should omit content due to size
                 let mut content_omitted = false;
                 // Check old blob size when applicable
@@ -545,6 +910,19 @@ impl GitService {
         Ok(file_diffs)
     }
 
+    /// True when both sides are present and differ only in whitespace within lines
+    fn is_whitespace_only_change(diff: &Diff) -> bool {
+        let (Some(old), Some(new)) = (&diff.old_content, &diff.new_content) else {
+            return false;
+        };
+        let strip = |s: &str| -> Vec<String> {
+            s.lines()
+                .map(|line| line.split_whitespace().collect::<String>())
+                .collect()
+        };
+        old != new && strip(old) == strip(new)
+    }
+
     /// Extract file path from a Diff (for indexing and ConversationPatch)
     pub fn diff_path(diff: &Diff) -> String {
         diff.new_path
@@ -860,6 +1238,73 @@ impl GitService {
             }
         }
     }
+
+    /// Three-way merge `branch_name` into `base_branch_name` in memory and report the
+    /// resulting diff and conflicts. No refs, index or working tree are modified.
+    pub fn merge_preview(
+        &self,
+        repo_path: &Path,
+        worktree_path: &Path,
+        branch_name: &str,
+        base_branch_name: &str,
+    ) -> Result<MergePreview, GitServiceError> {
+        let repo = self.open_repo(repo_path)?;
+        let base_commit = Self::find_branch(&repo, base_branch_name)?
+            .get()
+            .peel_to_commit()?;
+        let task_commit = Self::find_branch(&repo, branch_name)?
+            .get()
+            .peel_to_commit()?;
+        let (_, base_commits_ahead) =
+            self.get_branch_status(repo_path, branch_name, base_branch_name)?;
+        let has_uncommitted_changes = !self.is_worktree_clean(worktree_path)?;
+
+        let mut merge_opts = git2::MergeOptions::new();
+        merge_opts.find_renames(true);
+        let mut index = repo.merge_commits(&base_commit, &task_commit, Some(&merge_opts))?;
+
+        // Keep the base side of each conflicted file so it drops out of the diff
+        let mut conflicted_files = Vec::new();
+        if index.has_conflicts() {
+            let conflicts = index.conflicts()?.collect::<Result<Vec<_>, _>>()?;
+            for conflict in conflicts {
+                let Some(path) = [&conflict.our, &conflict.their, &conflict.ancestor]
+                    .into_iter()
+                    .flatten()
+                    .next()
+                    .map(|entry| String::from_utf8_lossy(&entry.path).to_string())
+                else {
+                    continue;
+                };
+                index.conflict_remove(Path::new(&path))?;
+                if let Some(mut ours) = conflict.our {
+                    ours.flags &= !INDEX_ENTRY_STAGE_MASK;
+                    index.add(&ours)?;
+                }
+                conflicted_files.push(path);
+            }
+            conflicted_files.sort();
+            conflicted_files.dedup();
+        }
+
+        let merged_tree = repo.find_tree(index.write_tree_to(&repo)?)?;
+        let base_tree = base_commit.tree()?;
+        let mut diff_opts = DiffOptions::new();
+        diff_opts.include_typechange(true);
+        let mut diff =
+            repo.diff_tree_to_tree(Some(&base_tree), Some(&merged_tree), Some(&mut diff_opts))?;
+        let mut find_opts = DiffFindOptions::new();
+        diff.find_similar(Some(&mut find_opts))?;
+        let diffs = self.convert_diff_to_file_diffs(diff, &repo, false)?;
+
+        Ok(MergePreview {
+            diffs,
+            conflicted_files,
+            base_commits_ahead,
+            has_uncommitted_changes,
+        })
+    }
+
     fn get_branch_status_inner(
         &self,
         repo: &Repository,
@@ -936,6 +1381,99 @@ impl GitService {
         self.get_branch_status_inner(&repo, &branch_ref, &base_branch_ref)
     }
 
+    /// Blame each line of a worktree file, including uncommitted edits.
+    /// Only UTF-8 text files up to MAX_BLAME_BYTES are supported.
+    pub fn blame(
+        &self,
+        worktree_path: &Path,
+        file_path: &str,
+    ) -> Result<Vec<BlameLine>, GitServiceError> {
+        let not_blameable =
+            |reason: &str| GitServiceError::FileNotBlameable(file_path.to_string(), reason.into());
+
+        let rel_path = Path::new(file_path);
+        if file_path.is_empty()
+            || rel_path.is_absolute()
+            || rel_path
+                .components()
+                .any(|c| matches!(c, std::path::Component::ParentDir))
+        {
+            return Err(not_blameable("path must be relative to the worktree"));
+        }
+
+        // Resolve symlinks so a link cannot expose files outside the worktree
+        let abs_path = worktree_path
+            .join(rel_path)
+            .canonicalize()
+            .map_err(|_| not_blameable("file not found"))?;
+        let rel_path = abs_path
+            .strip_prefix(worktree_path.canonicalize()?)
+            .map_err(|_| not_blameable("path resolves outside the worktree"))?;
+        let metadata = std::fs::metadata(&abs_path).map_err(|_| not_blameable("file not found"))?;
+        if !metadata.is_file() {
+            return Err(not_blameable("not a file"));
+        }
+        if metadata.len() as usize > MAX_BLAME_BYTES {
+            return Err(not_blameable("file is too large"));
+        }
+        let bytes = std::fs::read(&abs_path)?;
+        if bytes.contains(&0) {
+            return Err(not_blameable("binary file"));
+        }
+        let content = String::from_utf8(bytes).map_err(|_| not_blameable("not valid UTF-8"))?;
+
+        let repo = self.open_repo(worktree_path)?;
+        // Blame committed history, then overlay the working copy so line numbers match disk
+        let blame = match repo.blame_file(rel_path, None) {
+            Ok(committed) => Some(committed.blame_buffer(content.as_bytes())?),
+            // Untracked files have no history
+            Err(e) if e.code() == git2::ErrorCode::NotFound => None,
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut commit_info: HashMap<git2::Oid, (Option<String>, Option<String>)> = HashMap::new();
+        let mut lines = Vec::new();
+        for (idx, line) in content.lines().enumerate() {
+            let line_number = idx + 1;
+            let commit_oid = blame
+                .as_ref()
+                .and_then(|b| b.get_line(line_number))
+                .map(|hunk| hunk.final_commit_id())
+                .filter(|oid| !oid.is_zero());
+
+            let (author, summary) = match commit_oid {
+                Some(oid) => commit_info
+                    .entry(oid)
+                    .or_insert_with(|| match repo.find_commit(oid) {
+                        Ok(commit) => (
+                            commit.author().name().map(|n| n.to_string()),
+                            commit.summary().map(|s| s.to_string()),
+                        ),
+                        Err(_) => (None, None),
+                    })
+                    .clone(),
+                None => (None, None),
+            };
+
+            lines.push(BlameLine {
+                line_number,
+                content: line.to_string(),
+                commit_id: commit_oid.map(|oid| oid.to_string()),
+                author,
+                summary,
+            });
+        }
+
+        Ok(lines)
+    }
+
+    /// Unified diff text of uncommitted changes (tracked and untracked) against HEAD
+    pub fn get_uncommitted_patch(&self, worktree_path: &Path) -> Result<String, GitServiceError> {
+        let git = GitCli::new();
+        git.diff_uncommitted_patch(worktree_path)
+            .map_err(|e| GitServiceError::InvalidRepository(format!("git diff failed: {e}")))
+    }
+
     pub fn is_worktree_clean(&self, worktree_path: &Path) -> Result<bool, GitServiceError> {
         let repo = self.open_repo(worktree_path)?;
         match self.check_worktree_clean(&repo) {
@@ -1082,6 +1620,97 @@ impl GitService {
         Ok((ahead, behind))
     }
 
+    /// Look up a commit by (possibly abbreviated) revision
+    pub fn find_commit(&self, repo_path: &Path, rev: &str) -> Result<Commit, GitServiceError> {
+        let repo = self.open_repo(repo_path)?;
+        let commit = repo.revparse_single(rev)?.peel_to_commit()?;
+        Ok(Commit::new(commit.id()))
+    }
+
+    /// Resolve a (possibly abbreviated) revision to a full commit OID
+    pub fn resolve_commit_oid(
+        &self,
+        repo_path: &Path,
+        rev: &str,
+    ) -> Result<String, GitServiceError> {
+        let repo = self.open_repo(repo_path)?;
+        let commit = repo.revparse_single(rev)?.peel_to_commit()?;
+        Ok(commit.id().to_string())
+    }
+
+    /// True if `ancestor_oid` is `descendant_oid` or reachable from it
+    pub fn is_ancestor_commit(
+        &self,
+        repo_path: &Path,
+        ancestor_oid: &str,
+        descendant_oid: &str,
+    ) -> Result<bool, GitServiceError> {
+        let repo = self.open_repo(repo_path)?;
+        let ancestor = git2::Oid::from_str(ancestor_oid)
+            .map_err(|_| GitServiceError::InvalidRepository("Invalid ancestor OID".into()))?;
+        let descendant = git2::Oid::from_str(descendant_oid)
+            .map_err(|_| GitServiceError::InvalidRepository("Invalid descendant OID".into()))?;
+        Ok(ancestor == descendant || repo.graph_descendant_of(descendant, ancestor)?)
+    }
+
+    /// Commits on the worktree's HEAD that are not reachable from `base`, newest first
+    pub fn commits_since(
+        &self,
+        worktree_path: &Path,
+        base: &Commit,
+    ) -> Result<Vec<String>, GitServiceError> {
+        let repo = self.open_repo(worktree_path)?;
+        let mut revwalk = repo.revwalk()?;
+        revwalk.push_head()?;
+        revwalk.hide(base.as_oid())?;
+        revwalk
+            .map(|oid| Ok(oid?.to_string()))
+            .collect::<Result<Vec<_>, git2::Error>>()
+            .map_err(GitServiceError::from)
+    }
+
+    /// Blob OID of a file's current contents in the worktree, committed or not, or `None` when
+    /// the file doesn't exist
+    pub fn worktree_file_oid(
+        &self,
+        worktree_path: &Path,
+        path: &str,
+    ) -> Result<Option<String>, GitServiceError> {
+        let full_path = worktree_path.join(path);
+        if std::fs::symlink_metadata(&full_path).is_err() {
+            return Ok(None);
+        }
+        let oid = git2::Oid::hash_file(git2::ObjectType::Blob, &full_path)?;
+        Ok(Some(oid.to_string()))
+    }
+
+    /// Blob OID of a file at the tip of a branch, or `None` when the branch doesn't have it
+    pub fn branch_file_oid(
+        &self,
+        repo_path: &Path,
+        branch_name: &str,
+        path: &str,
+    ) -> Result<Option<String>, GitServiceError> {
+        let repo = self.open_repo(repo_path)?;
+        let tree = Self::find_branch(&repo, branch_name)?
+            .get()
+            .peel_to_commit()?
+            .tree()?;
+        match tree.get_path(Path::new(path)) {
+            Ok(entry) => Ok(Some(entry.id().to_string())),
+            Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// OID of the tree a (possibly abbreviated) revision points at, for checking that two
+    /// commits hold the same content
+    pub fn commit_tree_oid(&self, repo_path: &Path, rev: &str) -> Result<String, GitServiceError> {
+        let repo = self.open_repo(repo_path)?;
+        let commit = repo.revparse_single(rev)?.peel_to_commit()?;
+        Ok(commit.tree_id().to_string())
+    }
+
     /// Return (uncommitted_tracked_changes, untracked_files) counts in worktree
     pub fn get_worktree_change_counts(
         &self,
@@ -1386,6 +2015,45 @@ impl GitService {
         old_base_branch: &str,
         task_branch: &str,
         github_token: Option<String>,
+    ) -> Result<String, GitServiceError> {
+        self.rebase_onto_target(
+            repo_path,
+            worktree_path,
+            RebaseTarget::Branch(new_base_branch),
+            old_base_branch,
+            task_branch,
+            github_token,
+        )
+    }
+
+    /// Rebase the task branch onto an arbitrary commit instead of a branch tip.
+    /// `old_base_branch` is still used to find where the task's own commits start.
+    pub fn rebase_branch_onto_commit(
+        &self,
+        repo_path: &Path,
+        worktree_path: &Path,
+        onto_commit: &str,
+        old_base_branch: &str,
+        task_branch: &str,
+    ) -> Result<String, GitServiceError> {
+        self.rebase_onto_target(
+            repo_path,
+            worktree_path,
+            RebaseTarget::Commit(onto_commit),
+            old_base_branch,
+            task_branch,
+            None,
+        )
+    }
+
+    fn rebase_onto_target(
+        &self,
+        repo_path: &Path,
+        worktree_path: &Path,
+        target: RebaseTarget,
+        old_base_branch: &str,
+        task_branch: &str,
+        github_token: Option<String>,
     ) -> Result<String, GitServiceError> {
         let worktree_repo = Repository::open(worktree_path)?;
         let main_repo = self.open_repo(repo_path)?;
@@ -1402,23 +2070,60 @@ impl GitService {
             return Err(GitServiceError::RebaseInProgress);
         }
 
-        // Get the target base branch reference
-        let nbr = Self::find_branch(&main_repo, new_base_branch)?.into_reference();
-        // If the target base is remote, update it first so CLI sees latest
-        if nbr.is_remote() {
-            let github_token = github_token.ok_or(GitServiceError::TokenUnavailable)?;
-            self.fetch_branch_from_remote(&main_repo, &github_token, &nbr)?;
-        }
+        let new_base = match target {
+            RebaseTarget::Branch(new_base_branch) => {
+                // Get the target base branch reference
+                let nbr = Self::find_branch(&main_repo, new_base_branch)?.into_reference();
+                // If the target base is remote, update it first so CLI sees latest
+                if nbr.is_remote() {
+                    let github_token = github_token.ok_or(GitServiceError::TokenUnavailable)?;
+                    self.fetch_branch_from_remote(&main_repo, &github_token, &nbr)?;
+                }
+                new_base_branch.to_string()
+            }
+            RebaseTarget::Commit(onto_commit) => {
+                // Validate the commit exists and normalise to a full OID
+                let commit = main_repo
+                    .revparse_single(onto_commit)
+                    .and_then(|obj| obj.peel_to_commit())
+                    .map_err(|_| {
+                        GitServiceError::InvalidRepository(format!(
+                            "Commit '{onto_commit}' does not exist in the repository"
+                        ))
+                    })?;
+                commit.id().to_string()
+            }
+        };
 
         // Ensure identity for any commits produced by rebase
         self.ensure_cli_commit_identity(worktree_path)?;
         // Use git CLI rebase to carry out the operation safely
-        match git.rebase_onto(worktree_path, new_base_branch, old_base_branch, task_branch) {
-            Ok(()) => {}
-            Err(GitCliError::RebaseInProgress) => {
-                return Err(GitServiceError::RebaseInProgress);
-            }
-            Err(GitCliError::CommandFailed(stderr)) => {
+        if let Err(e) = git.rebase_onto(worktree_path, &new_base, old_base_branch, task_branch) {
+            return Err(Self::rebase_error(
+                &worktree_repo,
+                worktree_path,
+                e,
+                &new_base,
+            ));
+        }
+
+        // Return resulting HEAD commit
+        let final_commit = worktree_repo.head()?.peel_to_commit()?;
+        Ok(final_commit.id().to_string())
+    }
+
+    /// Translate a failed CLI rebase into a service error, producing a concise,
+    /// actionable message when the failure was caused by conflicts.
+    fn rebase_error(
+        worktree_repo: &Repository,
+        worktree_path: &Path,
+        err: GitCliError,
+        onto: &str,
+    ) -> GitServiceError {
+        let git = GitCli::new();
+        match err {
+            GitCliError::RebaseInProgress => GitServiceError::RebaseInProgress,
+            GitCliError::CommandFailed(stderr) => {
                 // If the CLI indicates conflicts, return a concise, actionable error.
                 let looks_like_conflict = stderr.contains("could not apply")
                     || stderr.contains("CONFLICT")
@@ -1451,25 +2156,196 @@ impl GitService {
                         }
                     };
                     let msg = format!(
-                        "Rebase encountered merge conflicts while rebasing '{attempt_branch}' onto '{new_base_branch}'.{files_part} Resolve conflicts and then continue or abort."
+                        "Rebase encountered merge conflicts while rebasing '{attempt_branch}' onto '{onto}'.{files_part} Resolve conflicts and then continue or abort."
                     );
-                    return Err(GitServiceError::MergeConflicts(msg));
+                    return GitServiceError::MergeConflicts(msg);
                 }
-                return Err(GitServiceError::InvalidRepository(format!(
+                GitServiceError::InvalidRepository(format!(
                     "Rebase failed: {}",
                     stderr.lines().next().unwrap_or("")
+                ))
+            }
+            e => GitServiceError::InvalidRepository(format!("git rebase failed: {e}")),
+        }
+    }
+
+    /// Rewrite the most recent commits of the worktree branch so they are applied in
+    /// `ordered_oids` order (oldest first). `ordered_oids` must be exactly the top N
+    /// commits of the branch. Returns the new HEAD commit.
+    pub fn reorder_commits(
+        &self,
+        worktree_path: &Path,
+        ordered_oids: &[String],
+    ) -> Result<String, GitServiceError> {
+        let (base, requested, _) = self.prepare_history_rewrite(worktree_path, ordered_oids)?;
+        let todo: String = requested
+            .iter()
+            .map(|oid| format!("pick {oid}\n"))
+            .collect();
+        self.rewrite_history(worktree_path, &base, &todo)
+    }
+
+    /// Squash the most recent commits of the worktree branch into a single commit with
+    /// `message`. `oids` must be exactly the top N commits of the branch; their original
+    /// order is kept. Returns the new HEAD commit.
+    pub fn squash_commits(
+        &self,
+        worktree_path: &Path,
+        oids: &[String],
+        message: &str,
+    ) -> Result<String, GitServiceError> {
+        if message.trim().is_empty() {
+            return Err(GitServiceError::InvalidCommitSelection(
+                "Squash commit message must not be empty".to_string(),
+            ));
+        }
+        let (base, _, branch_order) = self.prepare_history_rewrite(worktree_path, oids)?;
+        let todo: String = branch_order
+            .iter()
+            .enumerate()
+            .map(|(i, oid)| {
+                let cmd = if i == 0 { "pick" } else { "fixup" };
+                format!("{cmd} {oid}\n")
+            })
+            .collect();
+        self.rewrite_history(worktree_path, &base, &todo)?;
+        GitCli::new().amend_message(worktree_path, message)?;
+        let head = Repository::open(worktree_path)?.head()?.peel_to_commit()?;
+        Ok(head.id().to_string())
+    }
+
+    /// Validate a history rewrite request. Returns the commit the rewritten commits sit on,
+    /// the requested OIDs resolved to full SHAs, and the same commits in branch order
+    /// (oldest first).
+    fn prepare_history_rewrite(
+        &self,
+        worktree_path: &Path,
+        oids: &[String],
+    ) -> Result<(String, Vec<String>, Vec<String>), GitServiceError> {
+        let repo = Repository::open(worktree_path)?;
+        self.check_worktree_clean(&repo)?;
+        if GitCli::new()
+            .is_rebase_in_progress(worktree_path)
+            .unwrap_or(false)
+        {
+            return Err(GitServiceError::RebaseInProgress);
+        }
+        if oids.is_empty() {
+            return Err(GitServiceError::InvalidCommitSelection(
+                "No commits provided".to_string(),
+            ));
+        }
+
+        let mut requested = Vec::with_capacity(oids.len());
+        for oid in oids {
+            let commit = repo
+                .revparse_single(oid)
+                .and_then(|obj| obj.peel_to_commit())
+                .map_err(|_| {
+                    GitServiceError::InvalidCommitSelection(format!("Unknown commit '{oid}'"))
+                })?;
+            let full = commit.id().to_string();
+            if requested.contains(&full) {
+                return Err(GitServiceError::InvalidCommitSelection(format!(
+                    "Commit '{oid}' was listed more than once"
                 )));
             }
-            Err(e) => {
-                return Err(GitServiceError::InvalidRepository(format!(
-                    "git rebase failed: {e}"
+            requested.push(full);
+        }
+
+        // Walk back from HEAD along first parents; the requested commits must be
+        // exactly the newest N commits and must not include merges.
+        let mut branch_order = Vec::with_capacity(requested.len());
+        let mut commit = repo.head()?.peel_to_commit()?;
+        for _ in 0..requested.len() {
+            if commit.parent_count() != 1 {
+                return Err(GitServiceError::InvalidCommitSelection(format!(
+                    "Commit {} is a merge or root commit and cannot be rewritten",
+                    commit.id()
                 )));
             }
+            branch_order.push(commit.id().to_string());
+            commit = commit.parent(0)?;
+        }
+        if branch_order.iter().any(|oid| !requested.contains(oid)) {
+            return Err(GitServiceError::InvalidCommitSelection(format!(
+                "Commits must be exactly the {} most recent commits of the branch",
+                requested.len()
+            )));
         }
+        branch_order.reverse();
 
-        // Return resulting HEAD commit
-        let final_commit = worktree_repo.head()?.peel_to_commit()?;
-        Ok(final_commit.id().to_string())
+        Ok((commit.id().to_string(), requested, branch_order))
+    }
+
+    fn rewrite_history(
+        &self,
+        worktree_path: &Path,
+        base: &str,
+        todo: &str,
+    ) -> Result<String, GitServiceError> {
+        let worktree_repo = Repository::open(worktree_path)?;
+        self.ensure_cli_commit_identity(worktree_path)?;
+        if let Err(e) = GitCli::new().rebase_with_todo(worktree_path, base, todo) {
+            return Err(Self::rebase_error(&worktree_repo, worktree_path, e, base));
+        }
+        let head = worktree_repo.head()?.peel_to_commit()?;
+        Ok(head.id().to_string())
+    }
+
+    /// True if the local branch has been pushed, i.e. it tracks the same branch on a remote
+    /// or a remote-tracking branch with the same name exists.
+    pub fn is_branch_pushed(
+        &self,
+        repo_path: &Path,
+        branch_name: &str,
+    ) -> Result<bool, GitServiceError> {
+        let repo = self.open_repo(repo_path)?;
+        if Self::same_name_upstream(&repo, branch_name)?.is_some() {
+            return Ok(true);
+        }
+        let remote_name = self.default_remote_name(&repo);
+        Ok(repo
+            .find_branch(&format!("{remote_name}/{branch_name}"), BranchType::Remote)
+            .is_ok())
+    }
+
+    /// The remote-tracking branch a local branch is compared against: its upstream if that is
+    /// the same branch on a remote, otherwise `<default remote>/<branch_name>` if that exists.
+    /// None if never pushed.
+    pub fn find_remote_tracking_branch(
+        &self,
+        repo_path: &Path,
+        branch_name: &str,
+    ) -> Result<Option<String>, GitServiceError> {
+        let repo = self.open_repo(repo_path)?;
+        if let Some(upstream) = Self::same_name_upstream(&repo, branch_name)? {
+            return Ok(Some(upstream));
+        }
+        let remote_branch = format!("{}/{branch_name}", self.default_remote_name(&repo));
+        Ok(repo
+            .find_branch(&remote_branch, BranchType::Remote)
+            .is_ok()
+            .then_some(remote_branch))
+    }
+
+    /// The branch's upstream, but only when it is the same branch on a remote. Attempt
+    /// branches created from a remote base track that base, which says nothing about
+    /// whether the attempt branch itself was pushed.
+    fn same_name_upstream(
+        repo: &Repository,
+        branch_name: &str,
+    ) -> Result<Option<String>, GitServiceError> {
+        let branch = repo.find_branch(branch_name, BranchType::Local)?;
+        let Ok(upstream) = branch.upstream() else {
+            return Ok(None);
+        };
+        let Some(upstream_name) = upstream.name()? else {
+            return Ok(None);
+        };
+        let remote = repo.branch_upstream_remote(&format!("refs/heads/{branch_name}"))?;
+        let remote = remote.as_str().unwrap_or_default();
+        Ok((upstream_name == format!("{remote}/{branch_name}")).then(|| upstream_name.to_string()))
     }
 
     pub fn find_branch_type(
@@ -1548,6 +2424,178 @@ impl GitService {
         })
     }
 
+    /// Parse the conflict markers of an unresolved file into ours/base/theirs sections.
+    /// Read-only: neither the file nor the index is modified.
+    pub fn get_conflict_hunks(
+        &self,
+        worktree_path: &Path,
+        file_path: &str,
+    ) -> Result<FileConflict, GitServiceError> {
+        // Only paths git reports as conflicted are read, which also keeps reads inside the worktree
+        if !self
+            .get_conflicted_files(worktree_path)?
+            .iter()
+            .any(|f| f == file_path)
+        {
+            return Err(GitServiceError::FileNotConflicted(file_path.to_string()));
+        }
+
+        let repo = self.open_repo(worktree_path)?;
+        let index = repo.index()?;
+        let mut is_binary = false;
+        for conflict in index.conflicts()? {
+            let conflict = conflict?;
+            let entries = [conflict.ancestor, conflict.our, conflict.their];
+            let matches_path = entries
+                .iter()
+                .flatten()
+                .any(|e| e.path.as_slice() == file_path.as_bytes());
+            if matches_path {
+                is_binary = entries
+                    .iter()
+                    .flatten()
+                    .any(|e| repo.find_blob(e.id).is_ok_and(|blob| blob.is_binary()));
+                break;
+            }
+        }
+
+        // Deleted-by-one-side conflicts may leave no file on disk
+        let bytes = match std::fs::read(worktree_path.join(file_path)) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(e.into()),
+        };
+        let content = match String::from_utf8(bytes) {
+            Ok(content) if !is_binary && !content.contains('\0') => content,
+            _ => {
+                return Ok(FileConflict {
+                    path: file_path.to_string(),
+                    is_binary: true,
+                    hunks: Vec::new(),
+                });
+            }
+        };
+
+        Ok(FileConflict {
+            path: file_path.to_string(),
+            is_binary: false,
+            hunks: parse_conflict_markers(&content),
+        })
+    }
+
+    /// Resolve one conflicted file by taking the chosen side and staging it.
+    /// Returns the files that are still conflicted.
+    pub fn resolve_conflict(
+        &self,
+        worktree_path: &Path,
+        file_path: &str,
+        strategy: ConflictResolution,
+    ) -> Result<Vec<String>, GitServiceError> {
+        if !self
+            .get_conflicted_files(worktree_path)?
+            .iter()
+            .any(|f| f == file_path)
+        {
+            return Err(GitServiceError::FileNotConflicted(file_path.to_string()));
+        }
+
+        let side = match strategy {
+            ConflictResolution::Ours => "--ours",
+            ConflictResolution::Theirs => "--theirs",
+        };
+        GitCli::new()
+            .checkout_conflict_side(worktree_path, file_path, side)
+            .map_err(|e| {
+                GitServiceError::InvalidRepository(format!(
+                    "Failed to resolve conflict in {file_path}: {e}"
+                ))
+            })?;
+
+        self.get_conflicted_files(worktree_path)
+    }
+
+    /// Stage conflicted files the user has fixed by hand. Fails without staging anything
+    /// if any of them still contains conflict markers.
+    fn stage_resolved_conflicts(&self, worktree_path: &Path) -> Result<(), GitServiceError> {
+        let conflicted = self.get_conflicted_files(worktree_path)?;
+        if conflicted.is_empty() {
+            return Ok(());
+        }
+        let unresolved: Vec<String> = conflicted
+            .iter()
+            .filter(|path| {
+                std::fs::read(worktree_path.join(path))
+                    .ok()
+                    .and_then(|bytes| String::from_utf8(bytes).ok())
+                    .is_some_and(|content| !parse_conflict_markers(&content).is_empty())
+            })
+            .cloned()
+            .collect();
+        if !unresolved.is_empty() {
+            return Err(GitServiceError::UnresolvedConflictMarkers(unresolved));
+        }
+        GitCli::new().add_paths(worktree_path, &conflicted)?;
+        Ok(())
+    }
+
+    /// Stage resolved files and continue an in-progress rebase. Returns the files that
+    /// conflict in the next replayed commit, or an empty list when the rebase finished.
+    pub fn continue_rebase(&self, worktree_path: &Path) -> Result<Vec<String>, GitServiceError> {
+        let git = GitCli::new();
+        if !git.is_rebase_in_progress(worktree_path).unwrap_or(false) {
+            return Err(GitServiceError::InvalidRepository(
+                "No rebase in progress".to_string(),
+            ));
+        }
+        self.stage_resolved_conflicts(worktree_path)?;
+
+        let worktree_repo = Repository::open(worktree_path)?;
+        self.ensure_cli_commit_identity(worktree_path)?;
+        // Read the rebase target before continuing; git removes its state when done
+        let onto = std::fs::read_to_string(worktree_repo.path().join("rebase-merge/onto"))
+            .map(|s| s.trim().to_string())
+            .unwrap_or_else(|_| "(unknown)".to_string());
+        if let Err(e) = git.continue_rebase(worktree_path) {
+            // A later commit conflicting leaves the rebase paused with new conflicts
+            let remaining = self.get_conflicted_files(worktree_path)?;
+            if !remaining.is_empty() {
+                return Ok(remaining);
+            }
+            return Err(Self::rebase_error(&worktree_repo, worktree_path, e, &onto));
+        }
+        Ok(Vec::new())
+    }
+
+    /// Stage resolved files and conclude an in-progress merge with its prepared message.
+    pub fn continue_merge(&self, worktree_path: &Path) -> Result<Vec<String>, GitServiceError> {
+        let git = GitCli::new();
+        if !git.is_merge_in_progress(worktree_path).unwrap_or(false) {
+            return Err(GitServiceError::InvalidRepository(
+                "No merge in progress".to_string(),
+            ));
+        }
+        self.stage_resolved_conflicts(worktree_path)?;
+        self.ensure_cli_commit_identity(worktree_path)?;
+        git.continue_merge(worktree_path).map_err(|e| {
+            GitServiceError::InvalidRepository(format!("Failed to conclude merge: {e}"))
+        })?;
+        self.get_conflicted_files(worktree_path)
+    }
+
+    /// Continue whichever rebase or merge is paused on conflicts in this worktree
+    pub fn continue_conflicts(&self, worktree_path: &Path) -> Result<Vec<String>, GitServiceError> {
+        match self.detect_conflict_op(worktree_path)? {
+            Some(ConflictOp::Rebase) => self.continue_rebase(worktree_path),
+            Some(ConflictOp::Merge) => self.continue_merge(worktree_path),
+            Some(op) => Err(GitServiceError::InvalidRepository(format!(
+                "Continuing a {op:?} operation is not supported; abort it instead"
+            ))),
+            None => Err(GitServiceError::InvalidRepository(
+                "No rebase or merge in progress".to_string(),
+            )),
+        }
+    }
+
     /// Abort an in-progress rebase in this worktree (no-op if none).
     pub fn abort_rebase(&self, worktree_path: &Path) -> Result<(), GitServiceError> {
         let git = GitCli::new();
@@ -1657,29 +2705,63 @@ impl GitService {
         Ok(commit_id.to_string())
     }
 
-    /// Get the default branch name for the repository
-    pub fn get_default_branch_name(&self, repo_path: &Path) -> Result<String, GitServiceError> {
+    /// Detect the repository's default branch: the branch the default remote's `HEAD` points
+    /// at (e.g. `origin/HEAD -> origin/develop`), else the first of `main`/`master` that exists
+    /// locally, else the checked-out branch. Remote-only branches are returned remote-qualified
+    /// (`origin/develop`). Returns `None` when the repository is empty (has no branches yet).
+    pub fn get_default_branch(&self, repo_path: &Path) -> Result<Option<String>, GitServiceError> {
         let repo = self.open_repo(repo_path)?;
 
-        match repo.head() {
-            Ok(head_ref) => Ok(head_ref.shorthand().unwrap_or("main").to_string()),
-            Err(e)
-                if e.class() == git2::ErrorClass::Reference
-                    && e.code() == git2::ErrorCode::UnbornBranch =>
-            {
-                Ok("main".to_string()) // Repository has no commits yet
+        let remote_name = self.default_remote_name(&repo);
+        let remote_prefix = format!("refs/remotes/{remote_name}/");
+        if let Ok(remote_head) = repo.find_reference(&format!("{remote_prefix}HEAD"))
+            && let Some(branch) = remote_head
+                .symbolic_target()
+                .and_then(|target| target.strip_prefix(&remote_prefix))
+        {
+            if repo.find_branch(branch, BranchType::Local).is_ok() {
+                return Ok(Some(branch.to_string()));
+            }
+            return Ok(Some(format!("{remote_name}/{branch}")));
+        }
+
+        for candidate in ["main", "master"] {
+            if repo.find_branch(candidate, BranchType::Local).is_ok() {
+                return Ok(Some(candidate.to_string()));
             }
-            Err(_) => Ok("main".to_string()), // Fallback
         }
+
+        if let Ok(head) = repo.head()
+            && head.is_branch()
+            && let Some(name) = head.shorthand()
+        {
+            return Ok(Some(name.to_string()));
+        }
+
+        // Detached HEAD or unborn branch: any local branch, if there is one
+        let first_local = repo
+            .branches(Some(BranchType::Local))?
+            .flatten()
+            .find_map(|(branch, _)| branch.name().ok().flatten().map(str::to_string));
+        Ok(first_local)
+    }
+
+    /// Get the default branch name for the repository, `main` when the repository is empty
+    pub fn get_default_branch_name(&self, repo_path: &Path) -> Result<String, GitServiceError> {
+        Ok(self
+            .get_default_branch(repo_path)?
+            .unwrap_or_else(|| "main".to_string()))
     }
 
-    /// Extract GitHub owner and repo name from git repo path
+    /// Extract GitHub owner and repo name from a remote of the git repo, the default remote
+    /// when `remote_name` is None
     pub fn get_github_repo_info(
         &self,
         repo_path: &Path,
+        remote_name: Option<&str>,
     ) -> Result<GitHubRepoInfo, GitServiceError> {
         let repo = self.open_repo(repo_path)?;
-        let remote_name = self.default_remote_name(&repo);
+        let remote_name = self.resolve_remote_name(&repo, remote_name)?;
         let remote = repo.find_remote(&remote_name).map_err(|_| {
             GitServiceError::InvalidRepository(format!("No '{remote_name}' remote found"))
         })?;
@@ -1692,14 +2774,17 @@ impl GitService {
         })
     }
 
+    /// Remote a branch belongs to, falling back to `default_remote` (or the repo's default
+    /// remote) when the remote can't be named
     pub fn get_remote_name_from_branch_name(
         &self,
         repo_path: &Path,
         branch_name: &str,
+        default_remote: Option<&str>,
     ) -> Result<String, GitServiceError> {
         let repo = Repository::open(repo_path)?;
         let branch_ref = Self::find_branch(&repo, branch_name)?.into_reference();
-        let default_remote = self.default_remote_name(&repo);
+        let default_remote = self.resolve_remote_name(&repo, default_remote)?;
         self.get_remote_from_branch_ref(&repo, &branch_ref)
             .map(|r| r.name().unwrap_or(&default_remote).to_string())
     }
@@ -1729,17 +2814,19 @@ impl GitService {
         })
     }
 
+    /// Push `branch_name` to `remote_name`, or the default remote when None, and track it
     pub fn push_to_github(
         &self,
         worktree_path: &Path,
         branch_name: &str,
         github_token: &str,
+        remote_name: Option<&str>,
     ) -> Result<(), GitServiceError> {
         let repo = Repository::open(worktree_path)?;
         self.check_worktree_clean(&repo)?;
 
         // Get the remote
-        let remote_name = self.default_remote_name(&repo);
+        let remote_name = self.resolve_remote_name(&repo, remote_name)?;
         let remote = repo.find_remote(&remote_name)?;
 
         let remote_url = remote
@@ -1790,7 +2877,8 @@ impl GitService {
         normalized
     }
 
-    /// Fetch from remote repository using GitHub token authentication
+    /// Fetch from remote repository using GitHub token authentication. Blocks while another
+    /// fetch of the same repository and refspec runs, so call it off the async runtime.
     fn fetch_from_remote(
         &self,
         repo: &Repository,
@@ -1804,10 +2892,46 @@ impl GitService {
             .ok_or_else(|| GitServiceError::InvalidRepository("Remote has no URL".to_string()))?;
 
         let https_url = self.convert_to_https_url(remote_url);
-        let git_cli = GitCli::new();
-        if let Err(e) =
-            git_cli.fetch_with_token_and_refspec(repo.path(), &https_url, refspec, github_token)
+
+        let requested_at = Instant::now();
+        let slot = FETCHES
+            .lock()
+            .unwrap()
+            .entry((repo.commondir().to_path_buf(), refspec.to_string()))
+            .or_default()
+            .clone();
+        {
+            // Waiting releases the lock, and it is only held to read and update the state
+            let mut state = slot
+                .finished
+                .wait_while(slot.state.lock().unwrap(), |state| state.running)
+                .unwrap();
+            if state.last_started.is_some_and(|at| at >= requested_at) {
+                // A fetch that started while this one waited already brought the refs up to date
+                return Ok(());
+            }
+            state.running = true;
+        }
+
+        let started_at = Instant::now();
+        let prune = FETCH_SETTINGS.read().unwrap().prune;
+        let result = GitCli::new().fetch_with_token_and_prune(
+            repo.path(),
+            &https_url,
+            refspec,
+            github_token,
+            prune,
+        );
         {
+            let mut state = slot.state.lock().unwrap();
+            state.running = false;
+            if result.is_ok() {
+                state.last_started = Some(started_at);
+            }
+        }
+        slot.finished.notify_all();
+
+        if let Err(e) = result {
             tracing::error!("Fetch from GitHub failed: {}", e);
             return Err(e.into());
         }
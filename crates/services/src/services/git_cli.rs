@@ -16,9 +16,11 @@
 //! `git` CLI, while keeping libgit2 for read‑only graph queries and credentialed
 //! network operations when useful.
 use std::{
+    collections::VecDeque,
     ffi::{OsStr, OsString},
+    io::Read,
     path::Path,
-    process::Command,
+    process::{Command, Stdio},
 };
 
 use base64::{Engine, engine::general_purpose::STANDARD as BASE64_STANDARD};
@@ -27,6 +29,26 @@ use utils::shell::resolve_executable_path;
 
 use crate::services::git::Commit;
 
+/// How repositories are cloned and fetched from a remote, from the user config
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FetchSettings {
+    /// Clone new projects with only this many commits of history per branch. `None` clones
+    /// the full history. Fetches never pass a depth, so a user's own repository never turns
+    /// shallow.
+    pub clone_depth: Option<u32>,
+    /// Delete remote-tracking refs whose branch no longer exists on the remote
+    pub prune: bool,
+}
+
+impl Default for FetchSettings {
+    fn default() -> Self {
+        Self {
+            clone_depth: None,
+            prune: true,
+        }
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum GitCliError {
     #[error("git executable not found or not runnable")]
@@ -188,6 +210,26 @@ impl GitCli {
         Ok(Self::parse_name_status(&out))
     }
 
+    /// Unified diff of all uncommitted changes (including untracked files) against HEAD.
+    /// Uses a temporary index so the worktree's real index is left untouched.
+    pub fn diff_uncommitted_patch(&self, worktree_path: &Path) -> Result<String, GitCliError> {
+        let tmp_dir = tempfile::TempDir::new()
+            .map_err(|e| GitCliError::CommandFailed(format!("temp dir create failed: {e}")))?;
+        let tmp_index = tmp_dir.path().join("index");
+        let envs = vec![(
+            OsString::from("GIT_INDEX_FILE"),
+            tmp_index.as_os_str().to_os_string(),
+        )];
+
+        let _ = self.git_with_env(worktree_path, ["read-tree", "HEAD"], &envs)?;
+        let _ = self.git_with_env(worktree_path, ["add", "-A"], &envs)?;
+        self.git_with_env(
+            worktree_path,
+            ["-c", "core.quotepath=false", "diff", "--cached", "HEAD"],
+            &envs,
+        )
+    }
+
     /// Return `git status --porcelain` parsed into a structured summary
     pub fn get_worktree_status(&self, worktree_path: &Path) -> Result<WorktreeStatus, GitCliError> {
         let out = self.git(worktree_path, ["status", "--porcelain"])?;
@@ -293,9 +335,19 @@ impl GitCli {
         Ok(entries)
     }
 
-    /// Commit staged changes with the given message.
-    pub fn commit(&self, worktree_path: &Path, message: &str) -> Result<(), GitCliError> {
-        self.git(worktree_path, ["commit", "-m", message])?;
+    /// Commit staged changes with the given message. With `no_verify` the pre-commit and
+    /// commit-msg hooks are bypassed.
+    pub fn commit(
+        &self,
+        worktree_path: &Path,
+        message: &str,
+        no_verify: bool,
+    ) -> Result<(), GitCliError> {
+        if no_verify {
+            self.git(worktree_path, ["commit", "--no-verify", "-m", message])?;
+        } else {
+            self.git(worktree_path, ["commit", "-m", message])?;
+        }
         Ok(())
     }
     /// Fetch a branch to the given remote using an HTTPS token for authentication.
@@ -305,19 +357,35 @@ impl GitCli {
         remote_url: &str,
         refspec: &str,
         token: &str,
+    ) -> Result<(), GitCliError> {
+        self.fetch_with_token_and_prune(repo_path, remote_url, refspec, token, false)
+    }
+
+    /// Like [`Self::fetch_with_token_and_refspec`], optionally pruning remote-tracking refs
+    /// deleted on the remote.
+    pub fn fetch_with_token_and_prune(
+        &self,
+        repo_path: &Path,
+        remote_url: &str,
+        refspec: &str,
+        token: &str,
+        prune: bool,
     ) -> Result<(), GitCliError> {
         let auth_header = self.build_auth_header(token);
         let envs = self.build_token_env(&auth_header);
 
-        let args = [
+        let mut args = vec![
             OsString::from("-c"),
             OsString::from("credential.helper="),
             OsString::from("--config-env"),
             OsString::from("http.extraHeader=GIT_HTTP_EXTRAHEADER"),
             OsString::from("fetch"),
-            OsString::from(remote_url),
-            OsString::from(refspec),
         ];
+        if prune {
+            args.push(OsString::from("--prune"));
+        }
+        args.push(OsString::from(remote_url));
+        args.push(OsString::from(refspec));
 
         match self.git_with_env(repo_path, args, &envs) {
             Ok(_) => Ok(()),
@@ -355,6 +423,97 @@ impl GitCli {
         }
     }
 
+    /// Clone `url` into `dest`, calling `on_progress` with each progress line git reports
+    /// (e.g. `Receiving objects:  42% (420/1000)`). When `token` is set it is sent as an
+    /// HTTPS auth header, so callers must only pass it for hosts it belongs to. A `depth`
+    /// makes a shallow clone that still has every branch.
+    pub fn clone_with_progress(
+        &self,
+        url: &str,
+        dest: &Path,
+        token: Option<&str>,
+        depth: Option<u32>,
+        mut on_progress: impl FnMut(&str),
+    ) -> Result<(), GitCliError> {
+        /// Trailing stderr lines kept for the error message
+        const ERROR_TAIL_LINES: usize = 5;
+
+        self.ensure_available()?;
+        let git = resolve_executable_path("git").ok_or(GitCliError::NotAvailable)?;
+        let mut cmd = Command::new(&git);
+        let auth_header = token.map(|token| self.build_auth_header(token));
+        match &auth_header {
+            Some(auth_header) => {
+                cmd.envs(self.build_token_env(auth_header)).args([
+                    "-c",
+                    "credential.helper=",
+                    "--config-env",
+                    "http.extraHeader=GIT_HTTP_EXTRAHEADER",
+                ]);
+            }
+            // Fail instead of waiting on a credential prompt nobody can answer
+            None => {
+                cmd.env("GIT_TERMINAL_PROMPT", "0");
+            }
+        }
+        cmd.arg("clone").arg("--progress");
+        if let Some(depth) = depth {
+            cmd.arg(format!("--depth={depth}"))
+                .arg("--no-single-branch");
+        }
+        cmd.arg(url)
+            .arg(dest)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped());
+        let mut child = cmd
+            .spawn()
+            .map_err(|e| GitCliError::CommandFailed(e.to_string()))?;
+
+        // Progress lines are redrawn with `\r`, so split on both line endings
+        let mut stderr = child.stderr.take().expect("stderr is piped");
+        let mut tail: VecDeque<String> = VecDeque::with_capacity(ERROR_TAIL_LINES);
+        let mut line = Vec::new();
+        let mut buf = [0u8; 4096];
+        let mut emit = |line: &mut Vec<u8>| {
+            let text = String::from_utf8_lossy(line).trim().to_string();
+            line.clear();
+            if text.is_empty() {
+                return;
+            }
+            on_progress(&text);
+            if tail.len() == ERROR_TAIL_LINES {
+                tail.pop_front();
+            }
+            tail.push_back(text);
+        };
+        loop {
+            let n = stderr
+                .read(&mut buf)
+                .map_err(|e| GitCliError::CommandFailed(e.to_string()))?;
+            if n == 0 {
+                break;
+            }
+            for &byte in &buf[..n] {
+                if byte == b'\r' || byte == b'\n' {
+                    emit(&mut line);
+                } else {
+                    line.push(byte);
+                }
+            }
+        }
+        emit(&mut line);
+
+        let status = child
+            .wait()
+            .map_err(|e| GitCliError::CommandFailed(e.to_string()))?;
+        if !status.success() {
+            let msg = Vec::from(tail).join("\n");
+            return Err(self.classify_cli_error(msg));
+        }
+        Ok(())
+    }
+
     // Parse `git diff --name-status` output into structured entries.
     // Handles rename/copy scores like `R100` by matching the first letter.
     fn parse_name_status(output: &str) -> Vec<StatusDiffEntry> {
@@ -435,6 +594,47 @@ impl GitCli {
         Ok(())
     }
 
+    /// Non-interactively run `git rebase -i <base>` in `worktree_path` with `todo` as the
+    /// instruction list (`pick <sha>` / `fixup <sha>` lines). The todo is written to a temp
+    /// file and installed through `GIT_SEQUENCE_EDITOR`, so no editor is ever opened.
+    pub fn rebase_with_todo(
+        &self,
+        worktree_path: &Path,
+        base: &str,
+        todo: &str,
+    ) -> Result<(), GitCliError> {
+        if self.is_rebase_in_progress(worktree_path).unwrap_or(false) {
+            return Err(GitCliError::RebaseInProgress);
+        }
+        let tmp_dir = tempfile::TempDir::new()
+            .map_err(|e| GitCliError::CommandFailed(format!("temp dir create failed: {e}")))?;
+        let todo_path = tmp_dir.path().join("git-rebase-todo");
+        std::fs::write(&todo_path, todo)
+            .map_err(|e| GitCliError::CommandFailed(format!("write rebase todo failed: {e}")))?;
+
+        // Git runs the sequence editor through the shell with the todo path appended
+        let quoted = todo_path.to_string_lossy().replace('\'', "'\\''");
+        let envs = vec![
+            (
+                OsString::from("GIT_SEQUENCE_EDITOR"),
+                OsString::from(format!("cp '{quoted}'")),
+            ),
+            (OsString::from("GIT_EDITOR"), OsString::from("true")),
+        ];
+        self.git_with_env(
+            worktree_path,
+            ["rebase", "-i", "--no-autosquash", base],
+            &envs,
+        )?;
+        Ok(())
+    }
+
+    /// Replace the message of the HEAD commit.
+    pub fn amend_message(&self, worktree_path: &Path, message: &str) -> Result<(), GitCliError> {
+        self.git(worktree_path, ["commit", "--amend", "-m", message])
+            .map(|_| ())
+    }
+
     /// Return true if there is a rebase in progress in this worktree.
     /// We treat this as true when either of Git's rebase state directories exists:
     /// - rebase-merge (interactive rebase)
@@ -485,6 +685,47 @@ impl GitCli {
         self.git(worktree_path, ["rebase", "--abort"]).map(|_| ())
     }
 
+    /// Continue an in-progress rebase after conflicts were resolved, keeping the
+    /// original commit messages (no editor is opened).
+    pub fn continue_rebase(&self, worktree_path: &Path) -> Result<(), GitCliError> {
+        let envs = vec![(OsString::from("GIT_EDITOR"), OsString::from("true"))];
+        self.git_with_env(worktree_path, ["rebase", "--continue"], &envs)
+            .map(|_| ())
+    }
+
+    /// Conclude an in-progress merge using the prepared merge message.
+    pub fn continue_merge(&self, worktree_path: &Path) -> Result<(), GitCliError> {
+        let envs = vec![(OsString::from("GIT_EDITOR"), OsString::from("true"))];
+        self.git_with_env(worktree_path, ["commit", "--no-edit"], &envs)
+            .map(|_| ())
+    }
+
+    /// Stage the given paths.
+    pub fn add_paths(&self, worktree_path: &Path, paths: &[String]) -> Result<(), GitCliError> {
+        let mut args = vec!["add", "--"];
+        args.extend(paths.iter().map(String::as_str));
+        self.git(worktree_path, args).map(|_| ())
+    }
+
+    /// Resolve a conflicted path by taking one side (`--ours` or `--theirs`) and staging it.
+    /// If the chosen side deleted the file, the deletion is staged instead.
+    pub fn checkout_conflict_side(
+        &self,
+        worktree_path: &Path,
+        file_path: &str,
+        side: &str,
+    ) -> Result<(), GitCliError> {
+        match self.git(worktree_path, ["checkout", side, "--", file_path]) {
+            Ok(_) => self
+                .git(worktree_path, ["add", "--", file_path])
+                .map(|_| ()),
+            Err(GitCliError::CommandFailed(stderr)) if stderr.contains("does not have") => self
+                .git(worktree_path, ["rm", "--quiet", "--", file_path])
+                .map(|_| ()),
+            Err(e) => Err(e),
+        }
+    }
+
     /// Quit an in-progress rebase (cleanup metadata without modifying commits).
     /// If no rebase is in progress, it's a no-op.
     pub fn quit_rebase(&self, worktree_path: &Path) -> Result<(), GitCliError> {
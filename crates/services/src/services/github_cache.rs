@@ -0,0 +1,248 @@
+use std::{
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use db::models::project::Project;
+use moka::future::Cache;
+use tracing::warn;
+
+use super::{
+    git::{GitService, GitServiceError},
+    github_service::{GitHubRepoInfo, GitHubService, GitHubServiceError},
+};
+
+/// Repo info only changes when the remote URL does
+const REPO_INFO_TTL: Duration = Duration::from_secs(30 * 60);
+/// How long a successful token check is trusted
+const TOKEN_CHECK_TTL: Duration = Duration::from_secs(10 * 60);
+/// Remote ahead/behind counts are dropped entirely after this long
+const REMOTE_STATUS_TTL: Duration = Duration::from_secs(10 * 60);
+/// Cached remote counts older than this are served once more and refreshed in the background
+const REMOTE_STATUS_REFRESH_AFTER: Duration = Duration::from_secs(30);
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+struct RemoteStatusKey {
+    repo_path: PathBuf,
+    branch: String,
+    base_branch: Option<String>,
+}
+
+#[derive(Clone, Debug)]
+struct CachedRemoteStatus {
+    ahead: usize,
+    behind: usize,
+    fetched_at: Instant,
+}
+
+/// In-memory TTL cache for GitHub metadata that PR and branch status requests would
+/// otherwise fetch on every call
+#[derive(Clone)]
+pub struct GitHubMetadataCache {
+    repo_info: Cache<(PathBuf, Option<String>), GitHubRepoInfo>,
+    valid_tokens: Cache<String, ()>,
+    remote_status: Cache<RemoteStatusKey, CachedRemoteStatus>,
+}
+
+impl Default for GitHubMetadataCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GitHubMetadataCache {
+    pub fn new() -> Self {
+        Self {
+            repo_info: Cache::builder()
+                .max_capacity(200)
+                .time_to_live(REPO_INFO_TTL)
+                .build(),
+            valid_tokens: Cache::builder()
+                .max_capacity(10)
+                .time_to_live(TOKEN_CHECK_TTL)
+                .build(),
+            remote_status: Cache::builder()
+                .max_capacity(1000)
+                .time_to_live(REMOTE_STATUS_TTL)
+                .support_invalidation_closures()
+                .build(),
+        }
+    }
+
+    /// Owner and repo name of a GitHub remote of the project, the default remote when
+    /// `remote_name` is None
+    pub async fn repo_info(
+        &self,
+        git: &GitService,
+        repo_path: &Path,
+        remote_name: Option<&str>,
+        force_refresh: bool,
+    ) -> Result<GitHubRepoInfo, GitServiceError> {
+        let key = (repo_path.to_path_buf(), remote_name.map(|r| r.to_string()));
+        if !force_refresh && let Some(info) = self.repo_info.get(&key).await {
+            return Ok(info);
+        }
+        let info = git.get_github_repo_info(repo_path, remote_name)?;
+        self.repo_info.insert(key, info.clone()).await;
+        Ok(info)
+    }
+
+    /// The repository PRs are opened against and the repository attempt branches are pushed
+    /// to. They differ when the project pushes to a fork.
+    pub async fn pr_repositories(
+        &self,
+        git: &GitService,
+        project: &Project,
+    ) -> Result<(GitHubRepoInfo, GitHubRepoInfo), GitServiceError> {
+        let base_repo = self
+            .repo_info(
+                git,
+                &project.git_repo_path,
+                project.pr_remote.as_deref(),
+                false,
+            )
+            .await?;
+        let head_repo = self
+            .repo_info(
+                git,
+                &project.git_repo_path,
+                project.push_remote.as_deref(),
+                false,
+            )
+            .await?;
+        Ok((base_repo, head_repo))
+    }
+
+    /// Verify `token` with GitHub unless it passed recently. Only successful checks are
+    /// cached, keyed by token, so a changed token is always checked again.
+    pub async fn verify_token(
+        &self,
+        token: &str,
+        force_refresh: bool,
+    ) -> Result<(), GitHubServiceError> {
+        if !force_refresh && self.valid_tokens.contains_key(token) {
+            return Ok(());
+        }
+        match GitHubService::new(token)?.check_token().await {
+            Ok(()) => {
+                self.valid_tokens.insert(token.to_string(), ()).await;
+                Ok(())
+            }
+            Err(e) => {
+                self.valid_tokens.invalidate(token).await;
+                Err(e)
+            }
+        }
+    }
+
+    /// Forget all token checks, e.g. after the user signs in with a different account
+    pub fn invalidate_tokens(&self) {
+        self.valid_tokens.invalidate_all();
+    }
+
+    /// Commits ahead/behind a remote branch. Cached counts are returned immediately and
+    /// refreshed in the background once stale; `force_refresh` fetches inline instead.
+    pub async fn remote_branch_status(
+        &self,
+        git: &GitService,
+        repo_path: &Path,
+        branch_name: &str,
+        base_branch_name: Option<&str>,
+        github_token: String,
+        force_refresh: bool,
+    ) -> Result<(usize, usize), GitServiceError> {
+        let key = RemoteStatusKey {
+            repo_path: repo_path.to_path_buf(),
+            branch: branch_name.to_string(),
+            base_branch: base_branch_name.map(|b| b.to_string()),
+        };
+
+        if !force_refresh && let Some(cached) = self.remote_status.get(&key).await {
+            if cached.fetched_at.elapsed() > REMOTE_STATUS_REFRESH_AFTER {
+                // Bump the timestamp first so concurrent requests don't all spawn a refresh
+                self.remote_status
+                    .insert(
+                        key.clone(),
+                        CachedRemoteStatus {
+                            fetched_at: Instant::now(),
+                            ..cached.clone()
+                        },
+                    )
+                    .await;
+                self.spawn_remote_status_refresh(git.clone(), key, github_token);
+            }
+            return Ok((cached.ahead, cached.behind));
+        }
+
+        let (ahead, behind) = Self::fetch_remote_status(git.clone(), key.clone(), github_token)
+            .await
+            .map_err(|e| {
+                GitServiceError::InvalidRepository(format!("Remote status task failed: {e}"))
+            })??;
+        self.remote_status
+            .insert(
+                key,
+                CachedRemoteStatus {
+                    ahead,
+                    behind,
+                    fetched_at: Instant::now(),
+                },
+            )
+            .await;
+        Ok((ahead, behind))
+    }
+
+    /// Forget cached remote counts for `branch_name`, whatever it was compared against, e.g.
+    /// after pushing it
+    pub fn invalidate_remote_status(&self, repo_path: &Path, branch_name: &str) {
+        let repo_path = repo_path.to_path_buf();
+        let branch_name = branch_name.to_string();
+        if let Err(e) = self.remote_status.invalidate_entries_if(move |key, _| {
+            key.repo_path == repo_path && key.branch == branch_name
+        }) {
+            warn!("Failed to invalidate cached remote status: {}", e);
+        }
+    }
+
+    fn spawn_remote_status_refresh(&self, git: GitService, key: RemoteStatusKey, token: String) {
+        let cache = self.remote_status.clone();
+        tokio::spawn(async move {
+            match Self::fetch_remote_status(git, key.clone(), token).await {
+                Ok(Ok((ahead, behind))) => {
+                    cache
+                        .insert(
+                            key,
+                            CachedRemoteStatus {
+                                ahead,
+                                behind,
+                                fetched_at: Instant::now(),
+                            },
+                        )
+                        .await;
+                }
+                Ok(Err(e)) => warn!(
+                    "Background refresh of remote status for {} failed: {}",
+                    key.branch, e
+                ),
+                Err(e) => warn!("Background refresh task for {} failed: {}", key.branch, e),
+            }
+        });
+    }
+
+    /// Fetching from the remote is blocking git work, so it runs off the async runtime
+    async fn fetch_remote_status(
+        git: GitService,
+        key: RemoteStatusKey,
+        token: String,
+    ) -> Result<Result<(usize, usize), GitServiceError>, tokio::task::JoinError> {
+        tokio::task::spawn_blocking(move || {
+            git.get_remote_branch_status(
+                &key.repo_path,
+                &key.branch,
+                key.base_branch.as_deref(),
+                token,
+            )
+        })
+        .await
+    }
+}
@@ -1,7 +1,9 @@
-use std::time::Duration;
+use std::{future::Future, path::Path, time::Duration};
 
-use backon::{ExponentialBuilder, Retryable};
+use backon::{BackoffBuilder, ExponentialBuilder};
+use chrono::{DateTime, Utc};
 use db::models::merge::{MergeStatus, PullRequestInfo};
+use git2::BranchType;
 use octocrab::{Octocrab, OctocrabBuilder, models::IssueState};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
@@ -9,7 +11,10 @@ use thiserror::Error;
 use tracing::info;
 use ts_rs::TS;
 
-use crate::services::{git::GitServiceError, git_cli::GitCliError};
+use crate::services::{
+    git::{GitService, GitServiceError},
+    git_cli::GitCliError,
+};
 
 #[derive(Debug, Error, Serialize, Deserialize, TS)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
@@ -35,6 +40,9 @@ pub enum GitHubServiceError {
     #[error("GitHub repository not found or no access")]
     RepoNotFoundOrNoAccess,
     #[ts(skip)]
+    #[error("GitHub API rate limit exceeded; try again later.")]
+    RateLimited,
+    #[ts(skip)]
     #[serde(skip)]
     #[error(transparent)]
     GitService(GitServiceError),
@@ -49,6 +57,9 @@ impl From<octocrab::Error> for GitHubServiceError {
                 if status == 401 || msg.contains("bad credentials") || msg.contains("token expired")
                 {
                     GitHubServiceError::TokenInvalid
+                } else if status == 429 || (status == 403 && msg.contains("rate limit")) {
+                    // GitHub reports both primary and secondary rate limits as 403
+                    GitHubServiceError::RateLimited
                 } else if status == 403 {
                     GitHubServiceError::InsufficientPermissions
                 } else {
@@ -111,11 +122,24 @@ impl GitHubServiceError {
     }
 
     pub fn should_retry(&self) -> bool {
-        !self.is_api_data()
+        !self.is_api_data() && !matches!(self, GitHubServiceError::RateLimited)
     }
 }
 
+/// Primary rate limit window as reported by GitHub, so callers can throttle
 #[derive(Debug, Clone)]
+pub struct RateLimitStatus {
+    pub limit: u64,
+    pub remaining: u64,
+    pub reset_at: DateTime<Utc>,
+}
+
+/// Retries after the first failure of a GitHub API call
+const MAX_RETRIES: usize = 3;
+/// Longest a rate-limited call waits for the limit to reset before giving up
+const MAX_RATE_LIMIT_WAIT: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct GitHubRepoInfo {
     pub owner: String,
     pub repo_name: String,
@@ -139,12 +163,30 @@ impl GitHubRepoInfo {
     }
 }
 
+/// Base branch PRs target when neither the attempt nor the config names one
+pub const DEFAULT_PR_BASE: &str = "main";
+
 #[derive(Debug, Clone)]
 pub struct CreatePrRequest {
     pub title: String,
     pub body: Option<String>,
     pub head_branch: String,
     pub base_branch: String,
+    /// Repository the head branch lives in when it differs from the PR's base repository,
+    /// e.g. a fork
+    pub head_repo: Option<GitHubRepoInfo>,
+}
+
+impl CreatePrRequest {
+    /// Head ref as the pulls API expects it: `owner:branch` for cross-repository PRs
+    fn qualified_head(&self, base_repo: &GitHubRepoInfo) -> String {
+        match &self.head_repo {
+            Some(head_repo) if head_repo != base_repo => {
+                format!("{}:{}", head_repo.owner, self.head_branch)
+            }
+            _ => self.head_branch.clone(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
@@ -175,9 +217,148 @@ impl GitHubService {
         Ok(Self { client })
     }
 
+    /// Normalize a branch name for the PR APIs. A blank name falls back to `default`, and
+    /// `prefix` (a remote's `origin/`, or a configured head branch prefix) is stripped unless
+    /// nothing of the name would be left.
+    pub fn normalize_ref(branch: &str, prefix: Option<&str>, default: &str) -> String {
+        let branch = match branch.trim() {
+            "" => default.trim(),
+            branch => branch,
+        };
+        prefix
+            .filter(|prefix| !prefix.is_empty())
+            .and_then(|prefix| branch.strip_prefix(prefix))
+            .filter(|rest| !rest.is_empty())
+            .unwrap_or(branch)
+            .to_string()
+    }
+
+    /// The base branch used when neither the request nor the attempt names one: the
+    /// configured `default_pr_base`, or [`DEFAULT_PR_BASE`]
+    pub fn default_pr_base(configured: Option<&str>) -> String {
+        Self::normalize_ref(configured.unwrap_or_default(), None, DEFAULT_PR_BASE)
+    }
+
+    /// Base branch of a PR into `target_branch` as the PR APIs expect it. A blank target falls
+    /// back to `default_base`, and a remote branch loses the `{remote}/` prefix it carries
+    /// locally.
+    pub fn pr_base_branch(
+        git: &GitService,
+        repo_path: &Path,
+        target_branch: &str,
+        pr_remote: Option<&str>,
+        default_base: &str,
+    ) -> Result<String, GitServiceError> {
+        let target_branch = Self::normalize_ref(target_branch, None, default_base);
+        let remote_prefix = if matches!(
+            git.find_branch_type(repo_path, &target_branch)?,
+            BranchType::Remote
+        ) {
+            let remote =
+                git.get_remote_name_from_branch_name(repo_path, &target_branch, pr_remote)?;
+            Some(format!("{}/", remote))
+        } else {
+            None
+        };
+        Ok(Self::normalize_ref(
+            &target_branch,
+            remote_prefix.as_deref(),
+            default_base,
+        ))
+    }
+
+    /// PR title with the head branch shown without `prefix`. A blank title becomes the
+    /// normalized head branch name.
+    pub fn pr_title(title: &str, head_branch: &str, prefix: Option<&str>) -> String {
+        let head = Self::normalize_ref(head_branch, prefix, head_branch);
+        if title.trim().is_empty() {
+            head
+        } else if head != head_branch {
+            title.replace(head_branch, &head)
+        } else {
+            title.to_string()
+        }
+    }
+
     pub async fn check_token(&self) -> Result<(), GitHubServiceError> {
-        self.client.current().user().await?;
-        Ok(())
+        self.with_retry(|| async {
+            self.client.current().user().await?;
+            Ok(())
+        })
+        .await
+    }
+
+    /// Current primary rate limit window. Querying `/rate_limit` does not count against it.
+    pub async fn rate_limit_status(&self) -> Result<RateLimitStatus, GitHubServiceError> {
+        let rate_limit = self.client.ratelimit().get().await?;
+        let rate = rate_limit.resources.core;
+        Ok(RateLimitStatus {
+            limit: rate.limit as u64,
+            remaining: rate.remaining as u64,
+            reset_at: DateTime::from_timestamp(rate.reset as i64, 0).unwrap_or_else(Utc::now),
+        })
+    }
+
+    /// Run a GitHub API call, retrying transient failures with exponential backoff. All API
+    /// calls should go through here.
+    ///
+    /// Rate-limited calls are retried once, after waiting for the window reported by
+    /// `/rate_limit` to reset (octocrab's errors don't carry the `Retry-After` /
+    /// `X-RateLimit-Reset` headers). The wait is capped at [`MAX_RATE_LIMIT_WAIT`]; if the limit
+    /// still applies, [`GitHubServiceError::RateLimited`] is returned, which the API reports as
+    /// `429 Too Many Requests`.
+    async fn with_retry<T, F, Fut>(&self, mut call: F) -> Result<T, GitHubServiceError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, GitHubServiceError>>,
+    {
+        let mut backoff = ExponentialBuilder::default()
+            .with_min_delay(Duration::from_secs(1))
+            .with_max_delay(Duration::from_secs(30))
+            .with_max_times(MAX_RETRIES)
+            .with_jitter()
+            .build();
+        let mut retries = 0;
+        let mut waited_for_rate_limit = false;
+        loop {
+            let err = match call().await {
+                Ok(value) => return Ok(value),
+                Err(GitHubServiceError::RateLimited) if !waited_for_rate_limit => {
+                    let delay = self.rate_limit_wait().await;
+                    tracing::warn!(
+                        "GitHub API rate limit exceeded, retrying after {:.0}s",
+                        delay.as_secs_f64()
+                    );
+                    tokio::time::sleep(delay).await;
+                    waited_for_rate_limit = true;
+                    continue;
+                }
+                Err(err) if !err.should_retry() || retries >= MAX_RETRIES => return Err(err),
+                Err(err) => err,
+            };
+            let Some(delay) = backoff.next() else {
+                return Err(err);
+            };
+            tracing::warn!(
+                "GitHub API call failed, retrying after {:.2}s: {}",
+                delay.as_secs_f64(),
+                err
+            );
+            tokio::time::sleep(delay).await;
+            retries += 1;
+        }
+    }
+
+    /// How long to wait for the rate limit window to reset, capped at [`MAX_RATE_LIMIT_WAIT`].
+    /// Secondary limits don't show up in `/rate_limit`, so they get the full cap.
+    async fn rate_limit_wait(&self) -> Duration {
+        match self.rate_limit_status().await {
+            Ok(status) if status.remaining == 0 => (status.reset_at - Utc::now())
+                .to_std()
+                .unwrap_or_default()
+                .clamp(Duration::from_secs(1), MAX_RATE_LIMIT_WAIT),
+            _ => MAX_RATE_LIMIT_WAIT,
+        }
     }
 
     /// Create a pull request on GitHub
@@ -186,22 +367,7 @@ impl GitHubService {
         repo_info: &GitHubRepoInfo,
         request: &CreatePrRequest,
     ) -> Result<PullRequestInfo, GitHubServiceError> {
-        (|| async { self.create_pr_internal(repo_info, request).await })
-            .retry(
-                &ExponentialBuilder::default()
-                    .with_min_delay(Duration::from_secs(1))
-                    .with_max_delay(Duration::from_secs(30))
-                    .with_max_times(3)
-                    .with_jitter(),
-            )
-            .when(|e| e.should_retry())
-            .notify(|err: &GitHubServiceError, dur: Duration| {
-                tracing::warn!(
-                    "GitHub API call failed, retrying after {:.2}s: {}",
-                    dur.as_secs_f64(),
-                    err
-                );
-            })
+        self.with_retry(|| async { self.create_pr_internal(repo_info, request).await })
             .await
     }
 
@@ -250,26 +416,30 @@ impl GitHubService {
             })?;
 
         // Check if the head branch exists
+        let head_repo = request.head_repo.as_ref().unwrap_or(repo_info);
         self.client
-            .repos(&repo_info.owner, &repo_info.repo_name)
+            .repos(&head_repo.owner, &head_repo.repo_name)
             .get_ref(&octocrab::params::repos::Reference::Branch(
                 request.head_branch.to_string(),
             ))
             .await
             .map_err(|err| match GitHubServiceError::from(err) {
                 GitHubServiceError::Client(source) => GitHubServiceError::Branch(format!(
-                    "Head branch '{}' does not exist: {}",
+                    "Head branch '{}' does not exist in {}/{}: {}",
                     request.head_branch,
+                    head_repo.owner,
+                    head_repo.repo_name,
                     format_octocrab_error(&source)
                 )),
                 other => other,
             })?;
 
         // Create the pull request
+        let head = request.qualified_head(repo_info);
         let pr_info = self
             .client
             .pulls(&repo_info.owner, &repo_info.repo_name)
-            .create(&request.title, &request.head_branch, &request.base_branch)
+            .create(&request.title, &head, &request.base_branch)
             .body(request.body.as_deref().unwrap_or(""))
             .send()
             .await
@@ -298,7 +468,7 @@ impl GitHubService {
         repo_info: &GitHubRepoInfo,
         pr_number: i64,
     ) -> Result<PullRequestInfo, GitHubServiceError> {
-        (|| async {
+        self.with_retry(|| async {
             self.client
                 .pulls(&repo_info.owner, &repo_info.repo_name)
                 .get(pr_number as u64)
@@ -312,21 +482,6 @@ impl GitHubService {
                     other => other,
                 })
         })
-        .retry(
-            &ExponentialBuilder::default()
-                .with_min_delay(Duration::from_secs(1))
-                .with_max_delay(Duration::from_secs(30))
-                .with_max_times(3)
-                .with_jitter(),
-        )
-        .when(|err| err.should_retry())
-        .notify(|err: &GitHubServiceError, dur: Duration| {
-            tracing::warn!(
-                "GitHub API call failed, retrying after {:.2}s: {}",
-                dur.as_secs_f64(),
-                err
-            );
-        })
         .await
     }
 
@@ -354,36 +509,25 @@ impl GitHubService {
     }
 
     /// List all pull requests for a branch (including closed/merged)
+    /// PRs in `repo_info` whose head is `branch_name` in `head_repo`, which is `repo_info`
+    /// itself unless the branch was pushed to a fork
     pub async fn list_all_prs_for_branch(
         &self,
         repo_info: &GitHubRepoInfo,
+        head_repo: &GitHubRepoInfo,
         branch_name: &str,
     ) -> Result<Vec<PullRequestInfo>, GitHubServiceError> {
-        (|| async {
-            self.list_all_prs_for_branch_internal(repo_info, branch_name)
+        self.with_retry(|| async {
+            self.list_all_prs_for_branch_internal(repo_info, head_repo, branch_name)
                 .await
         })
-        .retry(
-            &ExponentialBuilder::default()
-                .with_min_delay(Duration::from_secs(1))
-                .with_max_delay(Duration::from_secs(30))
-                .with_max_times(3)
-                .with_jitter(),
-        )
-        .when(|e| e.should_retry())
-        .notify(|err: &GitHubServiceError, dur: Duration| {
-            tracing::warn!(
-                "GitHub API call failed, retrying after {:.2}s: {}",
-                dur.as_secs_f64(),
-                err
-            );
-        })
         .await
     }
 
     async fn list_all_prs_for_branch_internal(
         &self,
         repo_info: &GitHubRepoInfo,
+        head_repo: &GitHubRepoInfo,
         branch_name: &str,
     ) -> Result<Vec<PullRequestInfo>, GitHubServiceError> {
         let prs = self
@@ -391,7 +535,7 @@ impl GitHubService {
             .pulls(&repo_info.owner, &repo_info.repo_name)
             .list()
             .state(octocrab::params::State::All)
-            .head(format!("{}:{}", repo_info.owner, branch_name))
+            .head(format!("{}:{}", head_repo.owner, branch_name))
             .per_page(100)
             .send()
             .await
@@ -414,22 +558,7 @@ impl GitHubService {
         &self,
         page: u8,
     ) -> Result<Vec<RepositoryInfo>, GitHubServiceError> {
-        (|| async { self.list_repositories_internal(page).await })
-            .retry(
-                &ExponentialBuilder::default()
-                    .with_min_delay(Duration::from_secs(1))
-                    .with_max_delay(Duration::from_secs(30))
-                    .with_max_times(3)
-                    .with_jitter(),
-            )
-            .when(|err| err.should_retry())
-            .notify(|err: &GitHubServiceError, dur: Duration| {
-                tracing::warn!(
-                    "GitHub API call failed, retrying after {:.2}s: {}",
-                    dur.as_secs_f64(),
-                    err
-                );
-            })
+        self.with_retry(|| async { self.list_repositories_internal(page).await })
             .await
     }
 
@@ -480,3 +609,82 @@ impl GitHubService {
         Ok(repositories)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_ref_strips_remote_prefix() {
+        assert_eq!(
+            GitHubService::normalize_ref("origin/main", Some("origin/"), DEFAULT_PR_BASE),
+            "main"
+        );
+        assert_eq!(
+            GitHubService::normalize_ref("origin/release/1.0", Some("origin/"), DEFAULT_PR_BASE),
+            "release/1.0"
+        );
+        // Only a leading prefix is stripped
+        assert_eq!(
+            GitHubService::normalize_ref("feature/origin/x", Some("origin/"), DEFAULT_PR_BASE),
+            "feature/origin/x"
+        );
+    }
+
+    #[test]
+    fn normalize_ref_keeps_local_branches() {
+        assert_eq!(
+            GitHubService::normalize_ref("feature/login", None, DEFAULT_PR_BASE),
+            "feature/login"
+        );
+        assert_eq!(
+            GitHubService::normalize_ref("  feature/login ", Some(""), DEFAULT_PR_BASE),
+            "feature/login"
+        );
+    }
+
+    #[test]
+    fn normalize_ref_falls_back_to_default_for_empty_base() {
+        assert_eq!(GitHubService::normalize_ref("", None, "trunk"), "trunk");
+        assert_eq!(
+            GitHubService::normalize_ref("   ", Some("origin/"), " trunk "),
+            "trunk"
+        );
+    }
+
+    #[test]
+    fn normalize_ref_uses_config_default_unless_blank() {
+        assert_eq!(
+            GitHubService::normalize_ref("develop", None, DEFAULT_PR_BASE),
+            "develop"
+        );
+        assert_eq!(
+            GitHubService::normalize_ref("  ", None, DEFAULT_PR_BASE),
+            DEFAULT_PR_BASE
+        );
+    }
+
+    #[test]
+    fn normalize_ref_keeps_branch_that_is_only_the_prefix() {
+        assert_eq!(
+            GitHubService::normalize_ref("vk/", Some("vk/"), DEFAULT_PR_BASE),
+            "vk/"
+        );
+    }
+
+    #[test]
+    fn pr_title_strips_head_branch_prefix() {
+        assert_eq!(
+            GitHubService::pr_title("Merge vk/1a2b-fix-login", "vk/1a2b-fix-login", Some("vk/")),
+            "Merge 1a2b-fix-login"
+        );
+        assert_eq!(
+            GitHubService::pr_title("", "vk/1a2b-fix-login", Some("vk/")),
+            "1a2b-fix-login"
+        );
+        assert_eq!(
+            GitHubService::pr_title("Fix login", "vk/1a2b-fix-login", None),
+            "Fix login"
+        );
+    }
+}
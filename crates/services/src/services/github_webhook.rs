@@ -0,0 +1,267 @@
+use std::sync::Arc;
+
+use db::{
+    DBService,
+    models::merge::{Merge, MergeStatus},
+};
+use hmac::{Hmac, Mac};
+use serde::Deserialize;
+use sha2::Sha256;
+use thiserror::Error;
+use tokio::sync::RwLock;
+use tracing::{debug, info, warn};
+
+use crate::services::{
+    analytics::AnalyticsContext,
+    config::Config,
+    github_service::{GitHubRepoInfo, GitHubService, GitHubServiceError},
+    pr_monitor::PrMonitorService,
+};
+
+pub const SIGNATURE_HEADER: &str = "X-Hub-Signature-256";
+pub const EVENT_HEADER: &str = "X-GitHub-Event";
+/// Shared secret configured on the GitHub webhook; when unset, PR status is polled instead
+const WEBHOOK_SECRET_ENV: &str = "GITHUB_WEBHOOK_SECRET";
+
+#[derive(Debug, Error)]
+pub enum GitHubWebhookError {
+    #[error("GitHub webhooks are not configured")]
+    NotConfigured,
+    #[error("Missing or invalid webhook signature")]
+    InvalidSignature,
+    #[error("Invalid webhook payload: {0}")]
+    InvalidPayload(#[from] serde_json::Error),
+    #[error(transparent)]
+    GitHubService(#[from] GitHubServiceError),
+    #[error(transparent)]
+    Sqlx(#[from] sqlx::Error),
+}
+
+/// The webhook secret, if webhook delivery is enabled for this deployment
+pub fn webhook_secret() -> Option<String> {
+    std::env::var(WEBHOOK_SECRET_ENV)
+        .ok()
+        .filter(|secret| !secret.trim().is_empty())
+}
+
+/// Check a `sha256=<hex>` signature header against the HMAC of the raw request body.
+/// The comparison is constant-time.
+pub fn verify_signature(secret: &str, body: &[u8], signature: &str) -> bool {
+    let Some(expected) = signature
+        .strip_prefix("sha256=")
+        .and_then(|hex_sig| hex::decode(hex_sig).ok())
+    else {
+        return false;
+    };
+    let Ok(mut mac) = Hmac::<Sha256>::new_from_slice(secret.as_bytes()) else {
+        return false;
+    };
+    mac.update(body);
+    mac.verify_slice(&expected).is_ok()
+}
+
+#[derive(Debug, Deserialize)]
+struct PullRequestEvent {
+    action: String,
+    pull_request: PullRequestPayload,
+}
+
+#[derive(Debug, Deserialize)]
+struct PullRequestPayload {
+    html_url: String,
+    #[serde(default)]
+    merged: bool,
+    merge_commit_sha: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CheckSuiteEvent {
+    action: String,
+    check_suite: CheckSuitePayload,
+    repository: RepositoryPayload,
+}
+
+#[derive(Debug, Deserialize)]
+struct CheckSuitePayload {
+    #[serde(default)]
+    pull_requests: Vec<PullRequestRef>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PullRequestRef {
+    number: i64,
+}
+
+#[derive(Debug, Deserialize)]
+struct RepositoryPayload {
+    html_url: String,
+}
+
+/// Applies GitHub webhook deliveries to tracked PR merges
+pub struct GitHubWebhookService {
+    db: DBService,
+    config: Arc<RwLock<Config>>,
+    analytics: Option<AnalyticsContext>,
+}
+
+impl GitHubWebhookService {
+    pub fn new(
+        db: DBService,
+        config: Arc<RwLock<Config>>,
+        analytics: Option<AnalyticsContext>,
+    ) -> Self {
+        Self {
+            db,
+            config,
+            analytics,
+        }
+    }
+
+    /// Handle a verified delivery of `event`. Returns the number of PR merges whose status
+    /// changed; events for untracked PRs and unsubscribed event types are ignored.
+    pub async fn handle_event(
+        &self,
+        event: &str,
+        body: &[u8],
+    ) -> Result<usize, GitHubWebhookError> {
+        match event {
+            "pull_request" => {
+                self.handle_pull_request(serde_json::from_slice(body)?)
+                    .await
+            }
+            "check_suite" => self.handle_check_suite(serde_json::from_slice(body)?).await,
+            "ping" => {
+                info!("Received GitHub webhook ping");
+                Ok(0)
+            }
+            other => {
+                debug!("Ignoring GitHub webhook event '{}'", other);
+                Ok(0)
+            }
+        }
+    }
+
+    async fn handle_pull_request(
+        &self,
+        event: PullRequestEvent,
+    ) -> Result<usize, GitHubWebhookError> {
+        let status = match event.action.as_str() {
+            "closed" if event.pull_request.merged => MergeStatus::Merged,
+            "closed" => MergeStatus::Closed,
+            "reopened" => MergeStatus::Open,
+            _ => return Ok(0),
+        };
+
+        let Some(pr_merge) =
+            Merge::find_pr_by_url(&self.db.pool, &event.pull_request.html_url).await?
+        else {
+            debug!(
+                "Ignoring webhook for untracked PR {}",
+                event.pull_request.html_url
+            );
+            return Ok(0);
+        };
+        // GitHub may redeliver events, so only act on actual transitions
+        if pr_merge.pr_info.status == status {
+            return Ok(0);
+        }
+
+        info!(
+            "PR #{} is now {:?} (via webhook)",
+            pr_merge.pr_info.number, status
+        );
+        PrMonitorService::record_pr_status(
+            &self.db,
+            self.analytics.as_ref(),
+            &pr_merge,
+            status,
+            event.pull_request.merge_commit_sha,
+        )
+        .await?;
+        Ok(1)
+    }
+
+    /// A finished check suite is often what unblocks an auto-merge, so re-read the status
+    /// of any tracked open PR it belongs to
+    async fn handle_check_suite(
+        &self,
+        event: CheckSuiteEvent,
+    ) -> Result<usize, GitHubWebhookError> {
+        if event.action != "completed" || event.check_suite.pull_requests.is_empty() {
+            return Ok(0);
+        }
+        let Some(token) = self.config.read().await.github.token() else {
+            warn!("No GitHub token configured, cannot refresh PR status for check suite");
+            return Ok(0);
+        };
+        let github_service = GitHubService::new(&token)?;
+
+        let mut updated = 0;
+        for pr_ref in &event.check_suite.pull_requests {
+            let pr_url = format!(
+                "{}/pull/{}",
+                event.repository.html_url.trim_end_matches('/'),
+                pr_ref.number
+            );
+            let Some(pr_merge) = Merge::find_pr_by_url(&self.db.pool, &pr_url).await? else {
+                continue;
+            };
+            if !matches!(pr_merge.pr_info.status, MergeStatus::Open) {
+                continue;
+            }
+
+            let repo_info = GitHubRepoInfo::from_remote_url(&pr_merge.pr_info.url)?;
+            let pr_status = github_service
+                .update_pr_status(&repo_info, pr_merge.pr_info.number)
+                .await?;
+            if matches!(pr_status.status, MergeStatus::Open) {
+                continue;
+            }
+            PrMonitorService::record_pr_status(
+                &self.db,
+                self.analytics.as_ref(),
+                &pr_merge,
+                pr_status.status,
+                pr_status.merge_commit_sha,
+            )
+            .await?;
+            updated += 1;
+        }
+        Ok(updated)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sign(secret: &str, body: &[u8]) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(body);
+        format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+    }
+
+    #[test]
+    fn verify_signature_accepts_matching_hmac() {
+        let body = br#"{"action":"closed"}"#;
+        assert!(verify_signature("s3cret", body, &sign("s3cret", body)));
+    }
+
+    #[test]
+    fn verify_signature_rejects_tampering() {
+        let body = br#"{"action":"closed"}"#;
+        let signature = sign("s3cret", body);
+        assert!(!verify_signature("other", body, &signature));
+        assert!(!verify_signature(
+            "s3cret",
+            br#"{"action":"opened"}"#,
+            &signature
+        ));
+        assert!(!verify_signature(
+            "s3cret",
+            body,
+            signature.trim_start_matches("sha256=")
+        ));
+        assert!(!verify_signature("s3cret", body, "sha256=not-hex"));
+    }
+}
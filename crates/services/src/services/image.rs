@@ -1,14 +1,30 @@
 use std::{
+    collections::HashSet,
     fs,
     path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
 };
 
-use db::models::image::{CreateImage, Image};
+use chrono::Utc;
+use db::models::image::{CreateImage, Image, TaskImage};
+use futures::future::join_all;
 use regex::{Captures, Regex};
+use serde::Serialize;
 use sha2::{Digest, Sha256};
 use sqlx::SqlitePool;
+use tokio::sync::Mutex;
+use ts_rs::TS;
 use uuid::Uuid;
 
+use crate::services::config::Config;
+
+/// Pause between orphan cleanup batches so a large backlog doesn't saturate the disk
+const ORPHAN_CLEANUP_BATCH_PAUSE: Duration = Duration::from_millis(100);
+/// Unattached images younger than this are kept, since the task they were uploaded for may
+/// not have been created yet
+const ORPHAN_IMAGE_MIN_AGE_HOURS: i64 = 1;
+
 #[derive(Debug, thiserror::Error)]
 pub enum ImageError {
     #[error("IO error: {0}")]
@@ -28,6 +44,52 @@ pub enum ImageError {
 
     #[error("Failed to build response: {0}")]
     ResponseBuildError(String),
+
+    #[error("Prompt references images that were never uploaded: {}", .0.join(", "))]
+    MissingReferences(Vec<String>),
+}
+
+/// Controls how [`ImageService::delete_orphaned_images`] works through orphaned images
+#[derive(Debug, Clone, Copy)]
+pub struct OrphanCleanupOptions {
+    /// Images deleted at once; batches of this size run one after another
+    pub concurrency: usize,
+    /// Stop after this many images and leave the rest for a later run; `None` for no limit
+    pub limit: Option<usize>,
+    /// Only report what would be deleted
+    pub dry_run: bool,
+}
+
+impl OrphanCleanupOptions {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            concurrency: config.orphan_image_cleanup_concurrency as usize,
+            limit: (config.orphan_image_cleanup_limit > 0)
+                .then_some(config.orphan_image_cleanup_limit as usize),
+            dry_run: false,
+        }
+    }
+}
+
+impl Default for OrphanCleanupOptions {
+    fn default() -> Self {
+        Self {
+            concurrency: 4,
+            limit: None,
+            dry_run: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, TS)]
+pub struct OrphanCleanupReport {
+    pub dry_run: bool,
+    /// Images deleted, or that would be deleted in a dry run
+    pub image_ids: Vec<Uuid>,
+    pub bytes: i64,
+    pub failed: usize,
+    /// Orphans left over because of the limit
+    pub remaining: usize,
 }
 
 #[derive(Clone)]
@@ -35,6 +97,8 @@ pub struct ImageService {
     cache_dir: PathBuf,
     pool: SqlitePool,
     max_size_bytes: u64,
+    // Keeps the startup and manually triggered cleanups from racing each other
+    cleanup_lock: Arc<Mutex<()>>,
 }
 
 impl ImageService {
@@ -45,6 +109,7 @@ impl ImageService {
             cache_dir,
             pool,
             max_size_bytes: 20 * 1024 * 1024, // 20MB default
+            cleanup_lock: Arc::new(Mutex::new(())),
         })
     }
 
@@ -106,40 +171,77 @@ impl ImageService {
         Ok(image)
     }
 
-    pub async fn delete_orphaned_images(&self) -> Result<(), ImageError> {
-        let orphaned_images = Image::find_orphaned_images(&self.pool).await?;
+    /// Delete images no task or draft references that are at least
+    /// [`ORPHAN_IMAGE_MIN_AGE_HOURS`] old, `options.concurrency` at a time with a short pause
+    /// between batches.
+    pub async fn delete_orphaned_images(
+        &self,
+        options: OrphanCleanupOptions,
+    ) -> Result<OrphanCleanupReport, ImageError> {
+        let _guard = self.cleanup_lock.lock().await;
+
+        let created_before = Utc::now() - chrono::Duration::hours(ORPHAN_IMAGE_MIN_AGE_HOURS);
+        let mut orphaned_images = Image::find_orphaned_images(&self.pool, created_before).await?;
         if orphaned_images.is_empty() {
             tracing::debug!("No orphaned images found during cleanup");
-            return Ok(());
+            return Ok(OrphanCleanupReport {
+                dry_run: options.dry_run,
+                ..Default::default()
+            });
         }
 
         tracing::debug!(
             "Found {} orphaned images to clean up",
             orphaned_images.len()
         );
-        let mut deleted_count = 0;
-        let mut failed_count = 0;
-
-        for image in orphaned_images {
-            match self.delete_image(image.id).await {
-                Ok(_) => {
-                    deleted_count += 1;
-                    tracing::debug!("Deleted orphaned image: {}", image.id);
-                }
-                Err(e) => {
-                    failed_count += 1;
-                    tracing::error!("Failed to delete orphaned image {}: {}", image.id, e);
+        let mut report = OrphanCleanupReport {
+            dry_run: options.dry_run,
+            ..Default::default()
+        };
+        if let Some(limit) = options.limit
+            && orphaned_images.len() > limit
+        {
+            report.remaining = orphaned_images.len() - limit;
+            orphaned_images.truncate(limit);
+        }
+
+        if options.dry_run {
+            report.bytes = orphaned_images.iter().map(|image| image.size_bytes).sum();
+            report.image_ids = orphaned_images.iter().map(|image| image.id).collect();
+            return Ok(report);
+        }
+
+        let mut batches = orphaned_images
+            .chunks(options.concurrency.max(1))
+            .peekable();
+        while let Some(batch) = batches.next() {
+            let results = join_all(batch.iter().map(|image| self.delete_image(image.id))).await;
+            for (image, result) in batch.iter().zip(results) {
+                match result {
+                    Ok(_) => {
+                        report.image_ids.push(image.id);
+                        report.bytes += image.size_bytes;
+                        tracing::debug!("Deleted orphaned image: {}", image.id);
+                    }
+                    Err(e) => {
+                        report.failed += 1;
+                        tracing::error!("Failed to delete orphaned image {}: {}", image.id, e);
+                    }
                 }
             }
+            if batches.peek().is_some() {
+                tokio::time::sleep(ORPHAN_CLEANUP_BATCH_PAUSE).await;
+            }
         }
 
         tracing::info!(
-            "Image cleanup completed: {} deleted, {} failed",
-            deleted_count,
-            failed_count
+            "Image cleanup completed: {} deleted, {} failed, {} left for later",
+            report.image_ids.len(),
+            report.failed,
+            report.remaining
         );
 
-        Ok(())
+        Ok(report)
     }
 
     pub fn get_absolute_path(&self, image: &Image) -> PathBuf {
@@ -217,12 +319,66 @@ impl ImageService {
         Ok(())
     }
 
-    pub fn canonicalise_image_paths(prompt: &str, worktree_path: &Path) -> String {
+    /// Check the images a prompt will use before it is sent: `image_ids` plus every
+    /// `.vibe-images/<file>` markdown reference in `prompt`. Fails listing any that were never
+    /// uploaded; otherwise links them all to the task and returns their ids.
+    pub async fn resolve_prompt_images(
+        &self,
+        task_id: Uuid,
+        prompt: &str,
+        image_ids: &[Uuid],
+    ) -> Result<Vec<Uuid>, ImageError> {
+        let mut resolved = Vec::new();
+        let mut missing = Vec::new();
+        for &id in image_ids {
+            match Image::find_by_id(&self.pool, id).await? {
+                Some(image) => resolved.push(image.id),
+                None => missing.push(id.to_string()),
+            }
+        }
+        for file_path in Self::referenced_image_files(prompt) {
+            match Image::find_by_file_path(&self.pool, &file_path).await? {
+                Some(image) => resolved.push(image.id),
+                None => missing.push(file_path),
+            }
+        }
+
+        if !missing.is_empty() {
+            missing.sort();
+            missing.dedup();
+            return Err(ImageError::MissingReferences(missing));
+        }
+
+        let mut seen = HashSet::new();
+        resolved.retain(|id| seen.insert(*id));
+        if !resolved.is_empty() {
+            TaskImage::associate_many_dedup(&self.pool, task_id, &resolved).await?;
+        }
+        Ok(resolved)
+    }
+
+    fn image_reference_regex() -> Regex {
         let pattern = format!(
             r#"!\[([^\]]*)\]\(({}/[^)\s]+)\)"#,
             regex::escape(utils::path::VIBE_IMAGES_DIR)
         );
-        let re = Regex::new(&pattern).unwrap();
+        Regex::new(&pattern).unwrap()
+    }
+
+    /// Cache file names of the images referenced as `![alt](.vibe-images/<file>)`
+    pub fn referenced_image_files(prompt: &str) -> Vec<String> {
+        let prefix = format!("{}/", utils::path::VIBE_IMAGES_DIR);
+        let mut files: Vec<String> = Self::image_reference_regex()
+            .captures_iter(prompt)
+            .filter_map(|caps| caps[2].strip_prefix(&prefix).map(str::to_string))
+            .collect();
+        files.sort();
+        files.dedup();
+        files
+    }
+
+    pub fn canonicalise_image_paths(prompt: &str, worktree_path: &Path) -> String {
+        let re = Self::image_reference_regex();
 
         re.replace_all(prompt, |caps: &Captures| {
             let alt = &caps[1];
@@ -234,3 +390,19 @@ impl ImageService {
         .into_owned()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn referenced_image_files_finds_markdown_image_references() {
+        let prompt = "See ![shot](.vibe-images/a.png) and ![](.vibe-images/b.jpg), \
+                      again ![shot](.vibe-images/a.png), not [link](.vibe-images/c.png) \
+                      or ![abs](/tmp/.vibe-images/d.png)";
+        assert_eq!(
+            ImageService::referenced_image_files(prompt),
+            vec!["a.png".to_string(), "b.jpg".to_string()]
+        );
+    }
+}
@@ -7,15 +7,23 @@ pub mod diff_stream;
 pub mod drafts;
 pub mod events;
 pub mod file_ranker;
+pub mod file_review;
 pub mod file_search_cache;
 pub mod filesystem;
 pub mod filesystem_watcher;
 pub mod git;
 pub mod git_cli;
+pub mod github_cache;
 pub mod github_service;
+pub mod github_webhook;
 pub mod image;
 pub mod notification;
+pub mod output_schema;
 pub mod pr_monitor;
+pub mod preview_url;
+pub mod prompt_snippets;
+pub mod repo_config;
+pub mod setup_cache;
 pub mod terminator_handler;
 pub mod transition_evaluator;
 pub mod workflow_orchestrator;
@@ -45,6 +45,17 @@ impl NotificationService {
         Self::notify(config, &title, &message).await;
     }
 
+    /// Sent instead of the completion notification when the coding agent finished its turn by
+    /// asking the user something
+    pub async fn notify_awaiting_input(config: NotificationConfig, ctx: &ExecutionContext) {
+        let title = format!("Input Needed: {}", ctx.task.title);
+        let message = format!(
+            "💬 '{}': the agent is waiting for your reply\nBranch: {:?}\nExecutor: {}",
+            ctx.task.title, ctx.task_attempt.branch, ctx.task_attempt.executor
+        );
+        Self::notify(config, &title, &message).await;
+    }
+
     /// Send both sound and push notifications if enabled
     pub async fn notify(config: NotificationConfig, title: &str, message: &str) {
         if config.sound_enabled {
@@ -0,0 +1,371 @@
+//! Station Output Schema
+//!
+//! A station can declare a JSON Schema (`workflow_stations.output_schema`) describing the
+//! output its agent is expected to produce. When the station completes, the extracted
+//! `output_data` is checked against it so malformed output fails the station instead of
+//! silently breaking downstream transition conditions.
+//!
+//! ## Supported Keywords
+//!
+//! The schema language is the commonly used subset of JSON Schema:
+//! - `type` (a type name or an array of them: object, array, string, number, integer, boolean, null)
+//! - `enum`, `const`
+//! - `properties`, `required`, `additionalProperties` (boolean or schema)
+//! - `items`, `minItems`, `maxItems`
+//! - `minLength`, `maxLength`, `minimum`, `maximum`
+//!
+//! Annotations (`$schema`, `$id`, `title`, `description`, `default`, `examples`) are ignored.
+//! Any other keyword is rejected when the schema is parsed, so a station never appears to be
+//! validated by a keyword that is not enforced.
+//!
+//! ## Example
+//! ```text
+//! {
+//!   "type": "object",
+//!   "required": ["review_passed"],
+//!   "properties": {
+//!     "review_passed": {"type": "boolean"},
+//!     "severity": {"enum": ["low", "medium", "high"]}
+//!   }
+//! }
+//! ```
+
+use serde_json::Value as JsonValue;
+
+const ANNOTATION_KEYWORDS: &[&str] = &[
+    "$schema",
+    "$id",
+    "title",
+    "description",
+    "default",
+    "examples",
+];
+
+const TYPE_NAMES: &[&str] = &[
+    "object", "array", "string", "number", "integer", "boolean", "null",
+];
+
+/// A parsed and checked station output schema
+#[derive(Debug, Clone)]
+pub struct OutputSchema {
+    schema: JsonValue,
+}
+
+impl OutputSchema {
+    /// Parse a schema from its JSON text, rejecting malformed schemas and unsupported keywords
+    pub fn parse(schema: &str) -> Result<Self, String> {
+        let schema: JsonValue = serde_json::from_str(schema)
+            .map_err(|e| format!("Output schema is not valid JSON: {}", e))?;
+        check_schema(&schema, "$")?;
+        Ok(Self { schema })
+    }
+
+    /// Validate a station's output against the schema
+    ///
+    /// Returns every violation found, each prefixed with the path of the offending value
+    /// (e.g. `$.review_passed: expected boolean, got string`). An empty list means the
+    /// output conforms.
+    pub fn validate(&self, output: &JsonValue) -> Vec<String> {
+        let mut errors = Vec::new();
+        validate_value(&self.schema, output, "$", &mut errors);
+        errors
+    }
+}
+
+fn check_schema(schema: &JsonValue, path: &str) -> Result<(), String> {
+    let obj = schema
+        .as_object()
+        .ok_or_else(|| format!("{}: schema must be a JSON object", path))?;
+
+    for (keyword, value) in obj {
+        if ANNOTATION_KEYWORDS.contains(&keyword.as_str()) {
+            continue;
+        }
+        match keyword.as_str() {
+            "type" => {
+                let names: Vec<&JsonValue> = match value {
+                    JsonValue::Array(names) => names.iter().collect(),
+                    other => vec![other],
+                };
+                for name in names {
+                    match name.as_str() {
+                        Some(name) if TYPE_NAMES.contains(&name) => {}
+                        _ => return Err(format!("{}: unknown type {}", path, name)),
+                    }
+                }
+            }
+            "enum" => {
+                if !value.is_array() {
+                    return Err(format!("{}: 'enum' must be an array", path));
+                }
+            }
+            "const" => {}
+            "properties" => {
+                let properties = value
+                    .as_object()
+                    .ok_or_else(|| format!("{}: 'properties' must be an object", path))?;
+                for (name, property) in properties {
+                    check_schema(property, &format!("{}.{}", path, name))?;
+                }
+            }
+            "required" => {
+                let all_strings = value
+                    .as_array()
+                    .is_some_and(|names| names.iter().all(JsonValue::is_string));
+                if !all_strings {
+                    return Err(format!("{}: 'required' must be an array of strings", path));
+                }
+            }
+            "additionalProperties" => {
+                if !value.is_boolean() {
+                    check_schema(value, &format!("{}.*", path))?;
+                }
+            }
+            "items" => check_schema(value, &format!("{}[]", path))?,
+            "minItems" | "maxItems" | "minLength" | "maxLength" => {
+                if !value.is_u64() {
+                    return Err(format!(
+                        "{}: '{}' must be a non-negative integer",
+                        path, keyword
+                    ));
+                }
+            }
+            "minimum" | "maximum" => {
+                if !value.is_number() {
+                    return Err(format!("{}: '{}' must be a number", path, keyword));
+                }
+            }
+            unsupported => {
+                return Err(format!(
+                    "{}: unsupported schema keyword '{}'",
+                    path, unsupported
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn type_name(value: &JsonValue) -> &'static str {
+    match value {
+        JsonValue::Null => "null",
+        JsonValue::Bool(_) => "boolean",
+        JsonValue::Number(_) => "number",
+        JsonValue::String(_) => "string",
+        JsonValue::Array(_) => "array",
+        JsonValue::Object(_) => "object",
+    }
+}
+
+fn matches_type(value: &JsonValue, name: &str) -> bool {
+    match name {
+        "integer" => {
+            value.is_i64() || value.is_u64() || value.as_f64().is_some_and(|n| n.fract() == 0.0)
+        }
+        name => type_name(value) == name,
+    }
+}
+
+fn validate_value(schema: &JsonValue, value: &JsonValue, path: &str, errors: &mut Vec<String>) {
+    let Some(schema) = schema.as_object() else {
+        return;
+    };
+
+    if let Some(expected) = schema.get("type") {
+        let names: Vec<&str> = match expected {
+            JsonValue::Array(names) => names.iter().filter_map(JsonValue::as_str).collect(),
+            other => other.as_str().into_iter().collect(),
+        };
+        if !names.iter().any(|name| matches_type(value, name)) {
+            errors.push(format!(
+                "{}: expected {}, got {}",
+                path,
+                names.join(" or "),
+                type_name(value)
+            ));
+            // Further keywords would only repeat the type mismatch
+            return;
+        }
+    }
+
+    if let Some(allowed) = schema.get("enum").and_then(JsonValue::as_array)
+        && !allowed.contains(value)
+    {
+        errors.push(format!(
+            "{}: {} is not one of {}",
+            path,
+            value,
+            JsonValue::Array(allowed.clone())
+        ));
+    }
+
+    if let Some(expected) = schema.get("const")
+        && expected != value
+    {
+        errors.push(format!("{}: expected {}, got {}", path, expected, value));
+    }
+
+    match value {
+        JsonValue::Object(obj) => {
+            if let Some(required) = schema.get("required").and_then(JsonValue::as_array) {
+                for key in required.iter().filter_map(JsonValue::as_str) {
+                    if !obj.contains_key(key) {
+                        errors.push(format!("{}: missing required key '{}'", path, key));
+                    }
+                }
+            }
+
+            let properties = schema.get("properties").and_then(JsonValue::as_object);
+            for (key, property_value) in obj {
+                let property_path = format!("{}.{}", path, key);
+                match properties.and_then(|properties| properties.get(key)) {
+                    Some(property_schema) => {
+                        validate_value(property_schema, property_value, &property_path, errors)
+                    }
+                    None => match schema.get("additionalProperties") {
+                        Some(JsonValue::Bool(false)) => {
+                            errors.push(format!("{}: unexpected key '{}'", path, key));
+                        }
+                        Some(additional @ JsonValue::Object(_)) => {
+                            validate_value(additional, property_value, &property_path, errors)
+                        }
+                        _ => {}
+                    },
+                }
+            }
+        }
+        JsonValue::Array(items) => {
+            if let Some(min) = schema.get("minItems").and_then(JsonValue::as_u64)
+                && (items.len() as u64) < min
+            {
+                errors.push(format!("{}: expected at least {} items", path, min));
+            }
+            if let Some(max) = schema.get("maxItems").and_then(JsonValue::as_u64)
+                && (items.len() as u64) > max
+            {
+                errors.push(format!("{}: expected at most {} items", path, max));
+            }
+            if let Some(item_schema) = schema.get("items") {
+                for (i, item) in items.iter().enumerate() {
+                    validate_value(item_schema, item, &format!("{}[{}]", path, i), errors);
+                }
+            }
+        }
+        JsonValue::String(s) => {
+            let len = s.chars().count() as u64;
+            if let Some(min) = schema.get("minLength").and_then(JsonValue::as_u64)
+                && len < min
+            {
+                errors.push(format!("{}: expected at least {} characters", path, min));
+            }
+            if let Some(max) = schema.get("maxLength").and_then(JsonValue::as_u64)
+                && len > max
+            {
+                errors.push(format!("{}: expected at most {} characters", path, max));
+            }
+        }
+        JsonValue::Number(n) => {
+            let n = n.as_f64().unwrap_or_default();
+            if let Some(min) = schema.get("minimum").and_then(JsonValue::as_f64)
+                && n < min
+            {
+                errors.push(format!("{}: {} is less than the minimum {}", path, n, min));
+            }
+            if let Some(max) = schema.get("maximum").and_then(JsonValue::as_f64)
+                && n > max
+            {
+                errors.push(format!(
+                    "{}: {} is greater than the maximum {}",
+                    path, n, max
+                ));
+            }
+        }
+        JsonValue::Null | JsonValue::Bool(_) => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    const REVIEW_SCHEMA: &str = r#"{
+        "type": "object",
+        "required": ["review_passed"],
+        "properties": {
+            "review_passed": {"type": "boolean"},
+            "severity": {"enum": ["low", "medium", "high"]},
+            "files": {"type": "array", "items": {"type": "string"}, "minItems": 1}
+        },
+        "additionalProperties": false
+    }"#;
+
+    #[test]
+    fn test_conforming_output_has_no_errors() {
+        let schema = OutputSchema::parse(REVIEW_SCHEMA).unwrap();
+        let output = json!({"review_passed": true, "severity": "low", "files": ["src/lib.rs"]});
+        assert!(schema.validate(&output).is_empty());
+    }
+
+    #[test]
+    fn test_violations_are_reported_with_paths() {
+        let schema = OutputSchema::parse(REVIEW_SCHEMA).unwrap();
+        let output =
+            json!({"review_passed": "yes", "severity": "critical", "files": [1], "extra": 1});
+        let errors = schema.validate(&output);
+        assert_eq!(
+            errors,
+            vec![
+                "$.review_passed: expected boolean, got string",
+                r#"$.severity: "critical" is not one of ["low","medium","high"]"#,
+                "$.files[0]: expected string, got number",
+                "$: unexpected key 'extra'",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_missing_output_fails_object_schema() {
+        let schema = OutputSchema::parse(REVIEW_SCHEMA).unwrap();
+        assert_eq!(
+            schema.validate(&JsonValue::Null),
+            vec!["$: expected object, got null"]
+        );
+        assert_eq!(
+            schema.validate(&json!({})),
+            vec!["$: missing required key 'review_passed'"]
+        );
+    }
+
+    #[test]
+    fn test_integer_and_bounds() {
+        let schema =
+            OutputSchema::parse(r#"{"type": "integer", "minimum": 1, "maximum": 5}"#).unwrap();
+        assert!(schema.validate(&json!(3)).is_empty());
+        assert_eq!(
+            schema.validate(&json!(2.5)),
+            vec!["$: expected integer, got number"]
+        );
+        assert_eq!(
+            schema.validate(&json!(9)),
+            vec!["$: 9 is greater than the maximum 5"]
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_schemas() {
+        assert!(OutputSchema::parse("not json").is_err());
+        assert!(OutputSchema::parse(r#"["object"]"#).is_err());
+        assert_eq!(
+            OutputSchema::parse(r#"{"type": "text"}"#).unwrap_err(),
+            r#"$: unknown type "text""#
+        );
+        assert_eq!(
+            OutputSchema::parse(r#"{"properties": {"name": {"pattern": "^a"}}}"#).unwrap_err(),
+            "$.name: unsupported schema keyword 'pattern'"
+        );
+        assert!(OutputSchema::parse(r#"{"title": "Review", "type": ["object", "null"]}"#).is_ok());
+    }
+}
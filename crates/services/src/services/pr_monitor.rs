@@ -18,8 +18,12 @@ use crate::services::{
     analytics::AnalyticsContext,
     config::Config,
     github_service::{GitHubRepoInfo, GitHubService, GitHubServiceError},
+    github_webhook,
 };
 
+/// Polling only backs up webhook delivery when a webhook secret is configured
+const WEBHOOK_RECONCILE_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
 #[derive(Debug, Error)]
 enum PrMonitorError {
     #[error("No GitHub token configured")]
@@ -46,10 +50,15 @@ impl PrMonitorService {
         config: Arc<RwLock<Config>>,
         analytics: Option<AnalyticsContext>,
     ) -> tokio::task::JoinHandle<()> {
+        let poll_interval = if github_webhook::webhook_secret().is_some() {
+            WEBHOOK_RECONCILE_INTERVAL
+        } else {
+            Duration::from_secs(60) // Check every minute
+        };
         let service = Self {
             db,
             config,
-            poll_interval: Duration::from_secs(60), // Check every minute
+            poll_interval,
             analytics,
         };
         tokio::spawn(async move {
@@ -82,6 +91,20 @@ impl PrMonitorService {
             return Ok(());
         }
 
+        // Skip this round rather than use up the rate limit that PR creation needs
+        let github_token = self.config.read().await.github.token();
+        if let Some(token) = github_token
+            && let Ok(github_service) = GitHubService::new(&token)
+            && let Ok(rate) = github_service.rate_limit_status().await
+            && (rate.remaining as usize) < open_prs.len()
+        {
+            warn!(
+                "GitHub rate limit nearly exhausted ({} of {} left, resets at {}), skipping PR checks",
+                rate.remaining, rate.limit, rate.reset_at
+            );
+            return Ok(());
+        }
+
         info!("Checking {} open PRs", open_prs.len());
 
         for pr_merge in open_prs {
@@ -121,41 +144,54 @@ impl PrMonitorService {
 
         // Update the PR status in the database
         if !matches!(&pr_status.status, MergeStatus::Open) {
-            // Update merge status with the latest information from GitHub
-            Merge::update_status(
-                &self.db.pool,
-                pr_merge.id,
-                pr_status.status.clone(),
+            Self::record_pr_status(
+                &self.db,
+                self.analytics.as_ref(),
+                pr_merge,
+                pr_status.status,
                 pr_status.merge_commit_sha,
             )
             .await?;
+        }
 
-            // If the PR was merged, update the task status to done
-            if matches!(&pr_status.status, MergeStatus::Merged)
-                && let Some(task_attempt) =
-                    TaskAttempt::find_by_id(&self.db.pool, pr_merge.task_attempt_id).await?
+        Ok(())
+    }
+
+    /// Store the latest GitHub status for a PR and mark its task done once merged. Shared by
+    /// polling and the webhook receiver.
+    pub async fn record_pr_status(
+        db: &DBService,
+        analytics: Option<&AnalyticsContext>,
+        pr_merge: &PrMerge,
+        status: MergeStatus,
+        merge_commit_sha: Option<String>,
+    ) -> Result<(), SqlxError> {
+        Merge::update_status(&db.pool, pr_merge.id, status.clone(), merge_commit_sha).await?;
+
+        // If the PR was merged, update the task status to done
+        if matches!(&status, MergeStatus::Merged)
+            && let Some(task_attempt) =
+                TaskAttempt::find_by_id(&db.pool, pr_merge.task_attempt_id).await?
+        {
+            info!(
+                "PR #{} was merged, updating task {} to done",
+                pr_merge.pr_info.number, task_attempt.task_id
+            );
+            Task::update_status(&db.pool, task_attempt.task_id, TaskStatus::Done).await?;
+
+            // Track analytics event
+            if let Some(analytics) = analytics
+                && let Ok(Some(task)) = Task::find_by_id(&db.pool, task_attempt.task_id).await
             {
-                info!(
-                    "PR #{} was merged, updating task {} to done",
-                    pr_merge.pr_info.number, task_attempt.task_id
+                analytics.analytics_service.track_event(
+                    &analytics.user_id,
+                    "pr_merged",
+                    Some(json!({
+                        "task_id": task_attempt.task_id.to_string(),
+                        "task_attempt_id": task_attempt.id.to_string(),
+                        "project_id": task.project_id.to_string(),
+                    })),
                 );
-                Task::update_status(&self.db.pool, task_attempt.task_id, TaskStatus::Done).await?;
-
-                // Track analytics event
-                if let Some(analytics) = &self.analytics
-                    && let Ok(Some(task)) =
-                        Task::find_by_id(&self.db.pool, task_attempt.task_id).await
-                {
-                    analytics.analytics_service.track_event(
-                        &analytics.user_id,
-                        "pr_merged",
-                        Some(json!({
-                            "task_id": task_attempt.task_id.to_string(),
-                            "task_attempt_id": task_attempt.id.to_string(),
-                            "project_id": task.project_id.to_string(),
-                        })),
-                    );
-                }
             }
         }
 
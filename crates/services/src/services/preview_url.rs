@@ -0,0 +1,105 @@
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+/// Full URLs on a local or IP host, e.g. `http://localhost:5173/`
+static URL_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)https?://(?:\[[0-9a-f:]+\]|localhost|127\.0\.0\.1|0\.0\.0\.0|\d{1,3}(?:\.\d{1,3}){3})(?::\d{2,5})?(?:/\S*)?",
+    )
+    .unwrap()
+});
+
+/// Bare `host:port` pairs, e.g. `listening on 0.0.0.0:3000`
+static HOST_PORT_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)(?:localhost|127\.0\.0\.1|0\.0\.0\.0|\[[0-9a-f:]+\]|(?:\d{1,3}\.){3}\d{1,3}):(\d{2,5})",
+    )
+    .unwrap()
+});
+
+static ANSI_ESCAPE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\x1b\[[0-9;?]*[ -/]*[@-~]").unwrap());
+
+/// Find the URL a dev server reports it is listening on in a line of its output.
+///
+/// With a project `pattern`, its first capture group (or the whole match) is used and the
+/// defaults are skipped, so a server printing several ports can be pinned to the right one.
+/// Wildcard hosts are rewritten to `localhost` and a bare port becomes
+/// `http://localhost:<port>`.
+pub fn detect_preview_url(line: &str, pattern: Option<&Regex>) -> Option<String> {
+    let line = ANSI_ESCAPE.replace_all(line, "");
+
+    if let Some(pattern) = pattern {
+        let captures = pattern.captures(&line)?;
+        let matched = captures.get(1).or_else(|| captures.get(0))?.as_str().trim();
+        return normalize_candidate(matched);
+    }
+
+    if let Some(url) = URL_PATTERN.find(&line) {
+        return normalize_candidate(url.as_str());
+    }
+
+    let port = HOST_PORT_PATTERN.captures(&line)?.get(1)?.as_str();
+    let scheme = if line.to_lowercase().contains("https") {
+        "https"
+    } else {
+        "http"
+    };
+    Some(format!("{scheme}://localhost:{port}"))
+}
+
+fn normalize_candidate(candidate: &str) -> Option<String> {
+    if candidate.is_empty() {
+        return None;
+    }
+    if candidate.chars().all(|c| c.is_ascii_digit()) {
+        return Some(format!("http://localhost:{candidate}"));
+    }
+
+    let url = if candidate.contains("://") {
+        candidate.to_string()
+    } else {
+        format!("http://{candidate}")
+    };
+    Some(
+        url.replacen("://0.0.0.0", "://localhost", 1)
+            .replacen("://[::]", "://localhost", 1),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_default_formats() {
+        assert_eq!(
+            detect_preview_url("  ➜  Local:   http://localhost:5173/", None).as_deref(),
+            Some("http://localhost:5173/")
+        );
+        assert_eq!(
+            detect_preview_url("\x1b[32mready\x1b[0m on http://0.0.0.0:3000", None).as_deref(),
+            Some("http://localhost:3000")
+        );
+        assert_eq!(
+            detect_preview_url("Listening on 127.0.0.1:8080", None).as_deref(),
+            Some("http://localhost:8080")
+        );
+        assert_eq!(detect_preview_url("Compiling assets...", None), None);
+    }
+
+    #[test]
+    fn project_pattern_overrides_defaults() {
+        let pattern = Regex::new(r"web server on port (\d+)").unwrap();
+        let line = "api on http://localhost:4000, web server on port 5173";
+        assert_eq!(
+            detect_preview_url(line, Some(&pattern)).as_deref(),
+            Some("http://localhost:5173")
+        );
+        assert_eq!(
+            detect_preview_url(line, None).as_deref(),
+            Some("http://localhost:4000")
+        );
+    }
+}
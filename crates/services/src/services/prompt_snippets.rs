@@ -0,0 +1,125 @@
+use std::collections::HashMap;
+
+use db::models::prompt_snippet::PromptSnippet;
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+static SNIPPET_REFERENCE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\{\{\s*snippet:([A-Za-z0-9._-]+)\s*\}\}").unwrap());
+
+#[derive(Debug, thiserror::Error)]
+pub enum PromptSnippetError {
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+
+    #[error("Prompt references unknown snippets: {}", .0.join(", "))]
+    UnknownSnippets(Vec<String>),
+}
+
+/// Expands `{{snippet:name}}` references in prompts before they reach a coding agent
+pub struct PromptSnippetService;
+
+impl PromptSnippetService {
+    /// Replace every snippet reference in `prompt` with the snippet's content. A project's own
+    /// snippet wins over a global one of the same name. Fails listing every unknown name.
+    pub async fn expand(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        prompt: &str,
+    ) -> Result<String, PromptSnippetError> {
+        if !SNIPPET_REFERENCE.is_match(prompt) {
+            return Ok(prompt.to_string());
+        }
+        let snippets = PromptSnippet::find_for_project(pool, Some(project_id)).await?;
+        Self::expand_with(prompt, &snippets)
+    }
+
+    /// Snippet contents are inserted verbatim; references inside them are not expanded again.
+    pub fn expand_with(
+        prompt: &str,
+        snippets: &[PromptSnippet],
+    ) -> Result<String, PromptSnippetError> {
+        let mut by_name: HashMap<&str, &PromptSnippet> = HashMap::new();
+        for snippet in snippets {
+            let entry = by_name.entry(snippet.name.as_str()).or_insert(snippet);
+            if entry.project_id.is_none() && snippet.project_id.is_some() {
+                *entry = snippet;
+            }
+        }
+
+        let mut unknown = Vec::new();
+        let expanded =
+            SNIPPET_REFERENCE.replace_all(prompt, |caps: &Captures| match by_name.get(&caps[1]) {
+                Some(snippet) => snippet.content.clone(),
+                None => {
+                    unknown.push(caps[1].to_string());
+                    caps[0].to_string()
+                }
+            });
+
+        if !unknown.is_empty() {
+            unknown.sort();
+            unknown.dedup();
+            return Err(PromptSnippetError::UnknownSnippets(unknown));
+        }
+        Ok(expanded.into_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+
+    fn snippet(project_id: Option<Uuid>, name: &str, content: &str) -> PromptSnippet {
+        PromptSnippet {
+            id: Uuid::new_v4(),
+            project_id,
+            name: name.to_string(),
+            content: content.to_string(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn expands_references_preferring_project_snippets() {
+        let snippets = vec![
+            snippet(None, "style", "Follow the global style guide."),
+            snippet(
+                Some(Uuid::new_v4()),
+                "style",
+                "Follow the project style guide.",
+            ),
+            snippet(None, "tests", "Add tests. {{snippet:style}}"),
+        ];
+
+        let expanded = PromptSnippetService::expand_with(
+            "Fix the bug.\n{{snippet:style}}\n{{ snippet:tests }}",
+            &snippets,
+        )
+        .unwrap();
+        assert_eq!(
+            expanded,
+            "Fix the bug.\nFollow the project style guide.\nAdd tests. {{snippet:style}}"
+        );
+    }
+
+    #[test]
+    fn lists_every_unknown_snippet() {
+        let snippets = vec![snippet(None, "style", "Follow the style guide.")];
+
+        let err = PromptSnippetService::expand_with(
+            "{{snippet:style}} {{snippet:tests}} {{snippet:docs}} {{snippet:tests}}",
+            &snippets,
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            PromptSnippetError::UnknownSnippets(names) if names == ["docs", "tests"]
+        ));
+    }
+}
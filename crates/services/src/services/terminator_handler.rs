@@ -1,8 +1,10 @@
 use chrono::Utc;
 use db::models::{
+    project::Project,
     station_execution::{CreateStationExecution, StationExecution},
     task::{Task, TaskStatus},
     task_attempt::TaskAttempt,
+    task_event::{TaskEvent, TaskEventType},
     workflow_execution::WorkflowExecution,
     workflow_station::WorkflowStation,
 };
@@ -10,7 +12,11 @@ use thiserror::Error;
 use tracing::{error, info};
 use uuid::Uuid;
 
-use super::github_service::{CreatePrRequest, GitHubService, GitHubServiceError};
+use super::{
+    git::GitService,
+    github_cache::GitHubMetadataCache,
+    github_service::{CreatePrRequest, GitHubService, GitHubServiceError},
+};
 
 #[derive(Debug, Error)]
 pub enum TerminatorHandlerError {
@@ -32,6 +38,16 @@ pub enum TerminatorHandlerError {
     NoGitRepository,
 }
 
+/// GitHub settings the terminator's PR follows, the same ones the create-PR route uses
+#[derive(Debug, Clone, Default)]
+pub struct TerminatorPrSettings {
+    pub github_token: Option<String>,
+    /// Configured `default_pr_base`, used when the attempt has no target branch
+    pub default_pr_base: Option<String>,
+    /// Configured `pr_title_branch_prefix`, hidden from the head branch in PR titles
+    pub title_branch_prefix: Option<String>,
+}
+
 /// Service to handle workflow completion actions when execution reaches a terminator station
 pub struct TerminatorHandler;
 
@@ -53,19 +69,24 @@ impl TerminatorHandler {
     ///
     /// # Parameters
     /// - `pool`: Database connection pool
-    /// - `github_token`: Optional GitHub token for PR creation
-    /// - `git_repo_path`: Path to the git repository
+    /// - `pr_settings`: GitHub token and PR naming settings for PR creation
+    /// - `github_cache`: Cache the PR's repositories are looked up through
+    /// - `project`: The task's project, whose `pr_remote` / `push_remote` pick the repositories
     /// - `task`: The task being executed
     /// - `workflow_execution`: The workflow execution
     /// - `station`: The terminator station
+    /// - `transition_id`: The transition that led to the terminator station
     /// - `task_attempt`: The task attempt
+    #[allow(clippy::too_many_arguments)]
     pub async fn execute(
         pool: &sqlx::SqlitePool,
-        github_token: Option<String>,
-        git_repo_path: String,
+        pr_settings: TerminatorPrSettings,
+        github_cache: &GitHubMetadataCache,
+        project: &Project,
         task: &Task,
         workflow_execution: &WorkflowExecution,
         station: &WorkflowStation,
+        transition_id: Option<Uuid>,
         task_attempt: &TaskAttempt,
     ) -> Result<(), TerminatorHandlerError> {
 
@@ -80,13 +101,9 @@ impl TerminatorHandler {
         );
 
         // 2. Create or update GitHub PR for the task attempt (non-blocking)
-        let pr_url = Self::create_pull_request_safe(
-            github_token,
-            &git_repo_path,
-            task,
-            task_attempt,
-        )
-        .await;
+        let pr_url =
+            Self::create_pull_request_safe(pr_settings, github_cache, project, task, task_attempt)
+                .await;
 
         // 3. Start database transaction for station execution, task, and workflow updates
         let mut tx = pool.begin().await?;
@@ -101,6 +118,7 @@ impl TerminatorHandler {
                 station_id: station.id,
                 status: "completed".to_string(),
                 execution_process_id: None,
+                transition_id,
             },
             station_execution_id,
         )
@@ -128,6 +146,18 @@ impl TerminatorHandler {
         )
         .execute(&mut *tx)
         .await?;
+        if task.status != TaskStatus::InReview {
+            TaskEvent::record(
+                &mut *tx,
+                task.id,
+                None,
+                TaskEventType::StatusChanged,
+                Some(task.status.clone()),
+                Some(TaskStatus::InReview),
+                None,
+            )
+            .await?;
+        }
 
         info!(
             "Updated task {} status to InReview for terminator station {}",
@@ -174,12 +204,15 @@ impl TerminatorHandler {
     /// This method attempts to create a GitHub PR but logs errors instead of failing.
     /// Returns the PR URL if successful, None if it fails.
     async fn create_pull_request_safe(
-        github_token: Option<String>,
-        git_repo_path: &str,
+        pr_settings: TerminatorPrSettings,
+        github_cache: &GitHubMetadataCache,
+        project: &Project,
         task: &Task,
         task_attempt: &TaskAttempt,
     ) -> Option<String> {
-        match Self::create_pull_request(github_token, git_repo_path, task, task_attempt).await {
+        match Self::create_pull_request(pr_settings, github_cache, project, task, task_attempt)
+            .await
+        {
             Ok(url) => Some(url),
             Err(e) => {
                 error!(
@@ -194,22 +227,26 @@ impl TerminatorHandler {
     /// Create GitHub PR for completed workflow
     ///
     /// Creates a PR from the task attempt branch to the base branch.
-    /// - PR title is from task title
+    /// - PR title is from task title, with the configured branch prefix hidden
     /// - PR body is from task description (if available)
-    /// - Base branch comes from task_attempt.target_branch
+    /// - Base branch comes from task_attempt.target_branch, else the configured default
+    /// - Opened against the project's PR remote, from the branch on its push remote
     async fn create_pull_request(
-        github_token: Option<String>,
-        git_repo_path: &str,
+        pr_settings: TerminatorPrSettings,
+        github_cache: &GitHubMetadataCache,
+        project: &Project,
         task: &Task,
         task_attempt: &TaskAttempt,
     ) -> Result<String, TerminatorHandlerError> {
         // Get GitHub token
-        let github_token = github_token.ok_or(TerminatorHandlerError::NoGitHubToken)?;
+        let github_token = pr_settings
+            .github_token
+            .ok_or(TerminatorHandlerError::NoGitHubToken)?;
 
         // Get GitHub repository info
-        let git_service = super::git::GitService::new();
-        let repo_info = git_service
-            .get_github_repo_info(std::path::Path::new(git_repo_path))
+        let (repo_info, head_repo) = github_cache
+            .pr_repositories(&GitService::new(), project)
+            .await
             .map_err(|e| {
                 GitHubServiceError::Repository(format!("Failed to get GitHub repo info: {}", e))
             })?;
@@ -218,16 +255,28 @@ impl TerminatorHandler {
         let github_service = GitHubService::new(&github_token)?;
 
         // Prepare PR request
-        let pr_title = task.title.clone();
+        let pr_title = GitHubService::pr_title(
+            &task.title,
+            &task_attempt.branch,
+            pr_settings.title_branch_prefix.as_deref(),
+        );
         let pr_body = task.description.clone();
         let head_branch = task_attempt.branch.clone();
-        let base_branch = task_attempt.target_branch.clone();
+        let base_branch = GitHubService::pr_base_branch(
+            &GitService::new(),
+            &project.git_repo_path,
+            &task_attempt.target_branch,
+            project.pr_remote.as_deref(),
+            &GitHubService::default_pr_base(pr_settings.default_pr_base.as_deref()),
+        )
+        .map_err(|e| GitHubServiceError::Branch(format!("Failed to resolve PR base: {}", e)))?;
 
         let pr_request = CreatePrRequest {
             title: pr_title,
             body: pr_body,
             head_branch,
             base_branch,
+            head_repo: Some(head_repo),
         };
 
         // Create the pull request
@@ -46,38 +46,42 @@
 //! → Returns true if output_data.review_passed == true
 //! ```
 //!
-//! ## Evaluation Algorithm
-//!
-//! The `evaluate_next_station` method:
-//! 1. Fetches all transitions from the current station (ordered by created_at)
-//! 2. Evaluates each transition in order (first match wins)
-//! 3. Returns the target_station_id of the first matching transition
-//! 4. Returns None if no transitions exist (workflow complete)
-//! 5. Returns error if no transitions match (workflow stuck)
-//!
-//! ## ⚠️ Important Limitations
-//!
-//! ### Context Accumulation
+//! **Earlier Station Outputs:**
+//! ```text
+//! condition_type: "conditional"
+//! condition_value: {"key": "design_approved", "value": true, "scope": "context"}
+//! → Returns true if design_approved == true in the merged outputs of all completed stations
+//! ```
 //!
-//! **Current behavior:** Conditional expressions only evaluate against the **current station's**
-//! `output_data`. They cannot access outputs from previous stations in the workflow.
+//! ## Condition Scope
 //!
-//! **Example limitation:**
+//! Object conditions read their key from the **current station's** `output_data` by default
+//! (`"scope": "station"`). With `"scope": "context"` they read the merged outputs of every
+//! completed station in the workflow execution instead, so a transition out of Station C can
+//! depend on Station A's output:
 //! ```text
 //! Station A outputs: {"design_approved": true}
 //! Station B outputs: {"code_complete": true}
 //!
-//! At Station C, you CANNOT evaluate:
-//! condition_value: {"key": "design_approved", "value": true}
-//! → This will return false because "design_approved" is not in Station C's output
+//! At Station C:
+//! condition_value: {"key": "design_approved", "value": true, "scope": "context"}
+//! → true
 //! ```
 //!
-//! **Workaround:** Context accumulation is handled by `WorkflowOrchestrator::gather_context_data()`
-//! before executing each station. The orchestrator merges outputs from all previous stations and
-//! passes them to the agent prompt. Agents can then include relevant context in their own
-//! `output_data` for downstream transitions.
+//! **Precedence:** when a key exists in both scopes, the current station's value wins. Among
+//! earlier stations, the most recently created station execution wins. Plain key-name
+//! conditions (`"review_passed"`) always use the station scope.
+//!
+//! ## Evaluation Algorithm
+//!
+//! The `evaluate_next_station` method:
+//! 1. Fetches all transitions from the current station (ordered by created_at)
+//! 2. Evaluates each transition in order (first match wins)
+//! 3. Returns the first matching transition
+//! 4. Returns None if no transitions exist (workflow complete)
+//! 5. Returns error if no transitions match (workflow stuck)
 //!
-//! **Future enhancement:** Add `evaluate_with_context()` method that accepts merged context data.
+//! ## ⚠️ Important Limitations
 //!
 //! ### Circular Workflow Detection
 //!
@@ -136,14 +140,16 @@
 //! - `CircularWorkflow`: Station was already visited (prevents infinite loops)
 
 use anyhow::Result;
-use db::models::{
-    station_execution::StationExecution,
-    station_transition::StationTransition,
+use db::{
+    DBService,
+    models::{station_execution::StationExecution, station_transition::StationTransition},
 };
 use serde_json::Value as JsonValue;
 use sqlx::SqlitePool;
 use uuid::Uuid;
 
+use crate::services::workflow_orchestrator::{WorkflowOrchestrator, WorkflowOrchestratorError};
+
 /// Errors that can occur during transition evaluation
 #[derive(Debug, thiserror::Error)]
 pub enum TransitionEvaluatorError {
@@ -169,6 +175,58 @@ pub enum TransitionEvaluatorError {
 /// Result type for transition evaluator operations
 pub type TransitionEvaluatorResult<T> = Result<T, TransitionEvaluatorError>;
 
+/// Which outputs an object condition reads its key from, set with its optional `"scope"` field
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ConditionScope {
+    /// `"station"`: only the output of the station that just completed
+    #[default]
+    Station,
+    /// `"context"`: the merged outputs of every completed station in the workflow execution,
+    /// with the current station's value winning on a key present in both
+    Context,
+}
+
+impl ConditionScope {
+    /// Scope of a `condition_value`; plain key-name conditions always use the station scope
+    pub fn of_condition(condition: &str) -> Result<Self, String> {
+        let Ok(JsonValue::Object(condition_obj)) = serde_json::from_str::<JsonValue>(condition)
+        else {
+            return Ok(Self::Station);
+        };
+        match condition_obj.get("scope") {
+            None => Ok(Self::Station),
+            Some(JsonValue::String(scope)) if scope == "station" => Ok(Self::Station),
+            Some(JsonValue::String(scope)) if scope == "context" => Ok(Self::Context),
+            Some(other) => Err(format!(
+                "Unknown condition scope {}, expected \"station\" or \"context\"",
+                other
+            )),
+        }
+    }
+
+    /// The output a condition in this scope is evaluated against, `None` if there is nothing
+    /// to read. `context` is the merged output of the workflow's completed stations.
+    pub fn output(
+        self,
+        station_output: Option<JsonValue>,
+        context: Option<&JsonValue>,
+    ) -> Option<JsonValue> {
+        match self {
+            Self::Station => station_output,
+            Self::Context => {
+                let mut merged = context
+                    .and_then(JsonValue::as_object)
+                    .cloned()
+                    .unwrap_or_default();
+                if let Some(JsonValue::Object(own)) = station_output {
+                    merged.extend(own);
+                }
+                Some(JsonValue::Object(merged))
+            }
+        }
+    }
+}
+
 /// Transition evaluator service
 ///
 /// This service encapsulates the logic for determining which station to execute next
@@ -176,7 +234,7 @@ pub type TransitionEvaluatorResult<T> = Result<T, TransitionEvaluatorError>;
 pub struct TransitionEvaluator;
 
 impl TransitionEvaluator {
-    /// Evaluate transitions from current station and return next station ID
+    /// Evaluate transitions from current station and return the one to take
     ///
     /// # Arguments
     /// * `pool` - Database connection pool
@@ -185,22 +243,22 @@ impl TransitionEvaluator {
     /// * `station_execution` - The execution record with status and output_data
     ///
     /// # Returns
-    /// * `Ok(Some(station_id))` - The next station to execute
+    /// * `Ok(Some(transition))` - The transition to take; its target is the next station
     /// * `Ok(None)` - No transitions found (workflow is complete)
     /// * `Err(NoValidTransition)` - Transitions exist but none matched (workflow stuck)
     /// * `Err(...)` - Validation or evaluation error
     ///
     /// # Example
     /// ```ignore
-    /// let next_station_id = TransitionEvaluator::evaluate_next_station(
+    /// let next_transition = TransitionEvaluator::evaluate_next_station(
     ///     &pool,
     ///     workflow_id,
     ///     current_station_id,
     ///     &station_execution
     /// ).await?;
     ///
-    /// match next_station_id {
-    ///     Some(id) => println!("Next station: {}", id),
+    /// match next_transition {
+    ///     Some(transition) => println!("Next station: {}", transition.target_station_id),
     ///     None => println!("Workflow complete"),
     /// }
     /// ```
@@ -209,7 +267,7 @@ impl TransitionEvaluator {
         _workflow_id: Uuid,
         current_station_id: Uuid,
         station_execution: &StationExecution,
-    ) -> TransitionEvaluatorResult<Option<Uuid>> {
+    ) -> TransitionEvaluatorResult<Option<StationTransition>> {
         // 1. Fetch all outgoing transitions from current station
         let transitions =
             StationTransition::find_by_source_station(pool, current_station_id).await?;
@@ -223,7 +281,19 @@ impl TransitionEvaluator {
             return Ok(None);
         }
 
-        // 3. Evaluate each transition in order (first match wins)
+        // 3. Merge earlier station outputs once if any condition reads the workflow context
+        let reads_context = transitions.iter().any(|transition| {
+            transition.condition_value.as_deref().is_some_and(|value| {
+                ConditionScope::of_condition(value) == Ok(ConditionScope::Context)
+            })
+        });
+        let context = if reads_context {
+            Some(Self::gather_context(pool, station_execution.workflow_execution_id).await?)
+        } else {
+            None
+        };
+
+        // 4. Evaluate each transition in order (first match wins)
         for transition in transitions {
             // Validate transition syntax before evaluation
             Self::validate_transition_condition(
@@ -232,7 +302,13 @@ impl TransitionEvaluator {
             )?;
 
             // Evaluate the transition
-            if Self::evaluate_transition(&transition, station_execution).await? {
+            if Self::evaluate_transition_with_context(
+                &transition,
+                station_execution,
+                context.as_ref(),
+            )
+            .await?
+            {
                 tracing::info!(
                     transition_id = ?transition.id,
                     current_station_id = ?current_station_id,
@@ -241,11 +317,11 @@ impl TransitionEvaluator {
                     "Transition matched, moving to next station"
                 );
 
-                return Ok(Some(transition.target_station_id));
+                return Ok(Some(transition));
             }
         }
 
-        // 4. No transitions matched - workflow is stuck
+        // 5. No transitions matched - workflow is stuck
         tracing::error!(
             current_station_id = ?current_station_id,
             "No valid transition found from station"
@@ -261,14 +337,17 @@ impl TransitionEvaluator {
     /// # Arguments
     /// * `transition` - The transition to evaluate
     /// * `station_execution` - The station execution with status and output_data
+    /// * `context` - Merged outputs of the workflow's completed stations, read by conditions
+    ///   scoped to `"context"`
     ///
     /// # Returns
     /// * `Ok(true)` - Transition should be taken
     /// * `Ok(false)` - Transition should not be taken
     /// * `Err(...)` - Evaluation failed
-    async fn evaluate_transition(
+    async fn evaluate_transition_with_context(
         transition: &StationTransition,
         station_execution: &StationExecution,
+        context: Option<&JsonValue>,
     ) -> TransitionEvaluatorResult<bool> {
         match transition.condition_type.as_deref() {
             // Unconditional: Always transition
@@ -313,7 +392,11 @@ impl TransitionEvaluator {
                         "Evaluating conditional transition"
                     );
 
-                    let result = Self::evaluate_condition(condition_value, station_execution)?;
+                    let result = Self::evaluate_condition_with_context(
+                        condition_value,
+                        station_execution,
+                        context,
+                    )?;
 
                     tracing::debug!(
                         transition_id = ?transition.id,
@@ -343,6 +426,16 @@ impl TransitionEvaluator {
         }
     }
 
+    /// [`Self::evaluate_transition_with_context`] for a transition whose conditions only read the
+    /// current station's output
+    #[cfg(test)]
+    async fn evaluate_transition(
+        transition: &StationTransition,
+        station_execution: &StationExecution,
+    ) -> TransitionEvaluatorResult<bool> {
+        Self::evaluate_transition_with_context(transition, station_execution, None).await
+    }
+
     /// Evaluate a conditional expression against station output
     ///
     /// Supports multiple condition formats:
@@ -353,24 +446,33 @@ impl TransitionEvaluator {
     /// # Arguments
     /// * `condition` - The condition expression (from transition.condition_value)
     /// * `station_output` - The station execution with output_data
+    /// * `context` - Merged outputs of the workflow's completed stations, read by conditions
+    ///   scoped to `"context"`
     ///
     /// # Returns
     /// * `Ok(true)` - Condition is satisfied
     /// * `Ok(false)` - Condition is not satisfied (including missing keys)
     /// * `Err(...)` - Evaluation failed (invalid JSON, etc.)
-    fn evaluate_condition(
+    fn evaluate_condition_with_context(
         condition: &str,
         station_execution: &StationExecution,
+        context: Option<&JsonValue>,
     ) -> TransitionEvaluatorResult<bool> {
         // Parse station output_data (if available)
-        let output = if let Some(output_data) = &station_execution.output_data {
-            serde_json::from_str::<JsonValue>(output_data).map_err(|e| {
+        let station_output = station_execution
+            .output_data
+            .as_deref()
+            .map(serde_json::from_str::<JsonValue>)
+            .transpose()
+            .map_err(|e| {
                 TransitionEvaluatorError::OutputDataParseError(format!(
                     "Failed to parse station output data: {}",
                     e
                 ))
-            })?
-        } else {
+            })?;
+        let scope = ConditionScope::of_condition(condition)
+            .map_err(TransitionEvaluatorError::InvalidTransitionSyntax)?;
+        let Some(output) = scope.output(station_output, context) else {
             // No output data - condition cannot be satisfied
             tracing::debug!("No output data available, condition evaluates to false");
             return Ok(false);
@@ -473,6 +575,15 @@ impl TransitionEvaluator {
         }
     }
 
+    /// [`Self::evaluate_condition_with_context`] for a condition in the station scope
+    #[cfg(test)]
+    fn evaluate_condition(
+        condition: &str,
+        station_execution: &StationExecution,
+    ) -> TransitionEvaluatorResult<bool> {
+        Self::evaluate_condition_with_context(condition, station_execution, None)
+    }
+
     /// Validate transition condition syntax
     ///
     /// This validates that a transition condition is well-formed before evaluation.
@@ -492,7 +603,7 @@ impl TransitionEvaluator {
         match condition_type {
             Some("conditional") => {
                 if let Some(value) = condition_value {
-                    // If it starts with '{', validate JSON syntax
+                    // If it starts with '{', validate JSON syntax and scope
                     if value.starts_with('{') {
                         serde_json::from_str::<JsonValue>(value).map_err(|e| {
                             TransitionEvaluatorError::InvalidTransitionSyntax(format!(
@@ -500,6 +611,8 @@ impl TransitionEvaluator {
                                 e
                             ))
                         })?;
+                        ConditionScope::of_condition(value)
+                            .map_err(TransitionEvaluatorError::InvalidTransitionSyntax)?;
                     }
                     Ok(())
                 } else {
@@ -517,6 +630,24 @@ impl TransitionEvaluator {
         }
     }
 
+    /// Merge the `output_data` of the workflow execution's completed stations, using the same
+    /// merge as `WorkflowOrchestrator::gather_context_data`
+    async fn gather_context(
+        pool: &SqlitePool,
+        workflow_execution_id: Uuid,
+    ) -> TransitionEvaluatorResult<JsonValue> {
+        WorkflowOrchestrator::new(DBService { pool: pool.clone() })
+            .gather_context_data(workflow_execution_id)
+            .await
+            .map_err(|e| match e {
+                WorkflowOrchestratorError::Database(e) => TransitionEvaluatorError::Database(e),
+                WorkflowOrchestratorError::OutputDataParseError(msg) => {
+                    TransitionEvaluatorError::OutputDataParseError(msg)
+                }
+                other => TransitionEvaluatorError::TransitionEvaluationError(other.to_string()),
+            })
+    }
+
     /// Detect circular workflows by checking if next station was already visited
     ///
     /// This prevents infinite loops by tracking which stations have been executed
@@ -568,6 +699,7 @@ impl TransitionEvaluator {
 mod tests {
     use super::*;
     use chrono::Utc;
+    use serde_json::json;
 
     /// Test helper to create a station execution with output data
     fn create_test_station_execution(
@@ -579,6 +711,7 @@ mod tests {
             workflow_execution_id: Uuid::new_v4(),
             station_id: Uuid::new_v4(),
             execution_process_id: None,
+            transition_id: None,
             status: status.to_string(),
             output_data: output_data.map(|s| s.to_string()),
             started_at: None,
@@ -820,6 +953,65 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_evaluate_condition_context_scope() {
+        let context = json!({"design_approved": true, "reviewer": "alice"});
+        let station_execution =
+            create_test_station_execution(Some(r#"{"reviewer": "bob"}"#), "completed");
+
+        // Keys from earlier stations are only visible in context scope
+        let condition = r#"{"key": "design_approved", "value": true, "scope": "context"}"#;
+        assert!(
+            TransitionEvaluator::evaluate_condition_with_context(
+                condition,
+                &station_execution,
+                Some(&context)
+            )
+            .unwrap()
+        );
+        let condition = r#"{"key": "design_approved", "value": true}"#;
+        assert!(
+            !TransitionEvaluator::evaluate_condition_with_context(
+                condition,
+                &station_execution,
+                Some(&context)
+            )
+            .unwrap()
+        );
+
+        // The current station's value wins over the context
+        let condition = r#"{"key": "reviewer", "value": "bob", "scope": "context"}"#;
+        assert!(
+            TransitionEvaluator::evaluate_condition_with_context(
+                condition,
+                &station_execution,
+                Some(&context)
+            )
+            .unwrap()
+        );
+
+        // Context scope still applies when the current station produced no output
+        let station_execution = create_test_station_execution(None, "completed");
+        let condition = r#"{"key": "reviewer", "value": "alice", "scope": "context"}"#;
+        assert!(
+            TransitionEvaluator::evaluate_condition_with_context(
+                condition,
+                &station_execution,
+                Some(&context)
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_validate_transition_condition_unknown_scope() {
+        assert!(TransitionEvaluator::validate_transition_condition(
+            Some("conditional"),
+            Some(r#"{"key": "test", "value": true, "scope": "workflow"}"#)
+        )
+        .is_err());
+    }
+
     #[test]
     fn test_validate_transition_condition_success() {
         assert!(TransitionEvaluator::validate_transition_condition(Some("success"), None).is_ok());
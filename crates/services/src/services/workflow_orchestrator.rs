@@ -36,6 +36,15 @@
 //! → Returns true if output_data.review_passed == true
 //! ```
 //!
+//! **Earlier station outputs:**
+//! ```text
+//! condition_type: "conditional"
+//! condition_value: {"key": "design_approved", "value": true, "scope": "context"}
+//! → Reads the key from the merged outputs of all completed stations (`gather_context_data()`)
+//! ```
+//! Without `"scope"` (or with `"scope": "station"`) only the current station's output is read.
+//! When a key exists in both, the current station's value wins over the merged context.
+//!
 //! **Example Workflow:**
 //! ```text
 //! Station A: Code Review
@@ -61,6 +70,7 @@
 //!
 //! 3. **Station Completion** (`handle_station_completion`)
 //!    - Updates StationExecution status
+//!    - Fails the station if its output doesn't match the station's `output_schema`
 //!    - Determines next station via transitions
 //!    - Either:
 //!      a) Starts next station execution (advances workflow)
@@ -126,7 +136,11 @@ use serde_json::Value as JsonValue;
 use sqlx::SqlitePool;
 use uuid::Uuid;
 
-use crate::services::container::{ContainerError, ContainerService};
+use crate::services::{
+    container::{ContainerError, ContainerService},
+    output_schema::OutputSchema,
+    transition_evaluator::ConditionScope,
+};
 
 /// Result type for workflow orchestration operations
 pub type WorkflowOrchestratorResult<T> = Result<T, WorkflowOrchestratorError>;
@@ -292,6 +306,7 @@ impl WorkflowOrchestrator {
         container_service: &C,
         workflow_execution_id: Uuid,
         station_id: Uuid,
+        transition_id: Option<Uuid>,
         task_attempt_id: Uuid,
         context_data: Option<JsonValue>,
     ) -> WorkflowOrchestratorResult<StationExecution> {
@@ -323,6 +338,7 @@ impl WorkflowOrchestrator {
                 station_id,
                 status: "running".to_string(),
                 execution_process_id: None,
+                transition_id,
             },
             station_execution_id,
         )
@@ -349,7 +365,7 @@ impl WorkflowOrchestrator {
                 .ok_or_else(|| anyhow!("Task attempt not found: {}", task_attempt_id))?;
 
         // Parse executor profile from agent.executor
-        let executor_profile_id = self.parse_executor_profile(&agent.executor)?;
+        let executor_profile_id = Self::parse_executor_profile(&agent.executor)?;
 
         // Determine if this is an initial request or a follow-up
         // Check if there are any previous execution processes for this task attempt
@@ -523,11 +539,22 @@ impl WorkflowOrchestrator {
             }
         }
 
+        // Show the schema the output will be validated against
+        if let Some(output_schema) = &station.output_schema {
+            prompt.push_str("\n\n");
+            prompt.push_str("## Output Schema\n");
+            prompt.push_str("Your JSON output must conform to this JSON Schema:\n");
+            prompt.push_str(&format!("```json\n{}\n```\n", output_schema.trim()));
+        }
+
         Ok(prompt)
     }
 
     /// Parse executor profile from agent executor string
-    fn parse_executor_profile(&self, executor: &str) -> WorkflowOrchestratorResult<ExecutorProfileId> {
+    ///
+    /// Also used to validate an agent's executor when it is saved, so a misconfigured agent
+    /// is rejected before a station runs it.
+    pub fn parse_executor_profile(executor: &str) -> WorkflowOrchestratorResult<ExecutorProfileId> {
         // Parse the executor string as a BaseCodingAgent
         let base_agent = BaseCodingAgent::from_str(executor).map_err(|_| {
             WorkflowOrchestratorError::Other(anyhow!(
@@ -540,19 +567,49 @@ impl WorkflowOrchestrator {
         Ok(ExecutorProfileId::new(base_agent))
     }
 
+    /// Check a station's output against the station's `output_schema`
+    ///
+    /// Stations without a schema accept any output. Missing output is validated as `null`,
+    /// so a schema requiring an object rejects a station that produced nothing.
+    pub fn validate_station_output(
+        station: &WorkflowStation,
+        output_data: Option<&str>,
+    ) -> Result<(), String> {
+        let Some(schema) = station.output_schema.as_deref() else {
+            return Ok(());
+        };
+        let schema = OutputSchema::parse(schema)?;
+
+        let output = match output_data {
+            Some(data) => serde_json::from_str(data)
+                .map_err(|e| format!("Station output is not valid JSON: {}", e))?,
+            None => JsonValue::Null,
+        };
+
+        let errors = schema.validate(&output);
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(format!(
+                "Station output does not match its output schema: {}",
+                errors.join("; ")
+            ))
+        }
+    }
+
     /// Determine the next station to execute
     ///
     /// This function:
     /// 1. Evaluates transition conditions (if conditional)
     /// 2. Handles unconditional transitions
     /// 3. Detects circular workflows
-    /// 4. Returns the next station or None if workflow is complete
+    /// 4. Returns the matched transition or None if workflow is complete
     pub async fn advance_to_next_station(
         &self,
         workflow_execution_id: Uuid,
         current_station_id: Uuid,
         station_execution: &StationExecution,
-    ) -> WorkflowOrchestratorResult<Option<Uuid>> {
+    ) -> WorkflowOrchestratorResult<Option<StationTransition>> {
         // Get all transitions from the current station
         let transitions =
             StationTransition::find_by_source_station(self.pool(), current_station_id).await?;
@@ -589,7 +646,7 @@ impl WorkflowOrchestrator {
                 // Detect circular workflows before advancing
                 self.detect_circular_workflow(workflow_execution_id, next_station_id).await?;
 
-                return Ok(Some(next_station_id));
+                return Ok(Some(transition));
             }
         }
 
@@ -650,6 +707,61 @@ impl WorkflowOrchestrator {
             .await
     }
 
+    /// Dry-run a transition condition against sample station results
+    ///
+    /// Validates the condition like a saved transition and then evaluates it exactly as a
+    /// real run would, against an in-memory station execution with the given `status` and
+    /// `output_data`. Conditions scoped to `"context"` read `context_data` in place of earlier
+    /// stations' outputs. Nothing is read from or written to the database, so conditions can
+    /// be debugged before a workflow is deployed.
+    ///
+    /// # Returns
+    /// - `Ok(true)` / `Ok(false)` whether the transition would be taken
+    /// - `Err(InvalidTransitionSyntax)` if the condition is malformed
+    /// - `Err(...)` if evaluation fails, e.g. for an unsupported condition object
+    pub async fn dry_run_transition_condition(
+        &self,
+        condition_type: Option<&str>,
+        condition_value: Option<&str>,
+        status: &str,
+        output_data: Option<&JsonValue>,
+        context_data: Option<&JsonValue>,
+    ) -> WorkflowOrchestratorResult<bool> {
+        Self::validate_transition_condition(condition_type, condition_value)?;
+
+        let now = Utc::now();
+        let transition = StationTransition {
+            id: Uuid::nil(),
+            workflow_id: Uuid::nil(),
+            source_station_id: Uuid::nil(),
+            target_station_id: Uuid::nil(),
+            condition: None,
+            label: None,
+            condition_type: condition_type.map(str::to_string),
+            condition_value: condition_value.map(str::to_string),
+            created_at: now,
+            updated_at: now,
+        };
+        let station_execution = StationExecution {
+            id: Uuid::nil(),
+            workflow_execution_id: Uuid::nil(),
+            station_id: Uuid::nil(),
+            execution_process_id: None,
+            transition_id: None,
+            status: status.to_string(),
+            output_data: output_data.map(JsonValue::to_string),
+            started_at: None,
+            completed_at: None,
+            created_at: now,
+            updated_at: now,
+        };
+        let context_data = context_data
+            .cloned()
+            .unwrap_or_else(|| JsonValue::Object(serde_json::Map::new()));
+        self.evaluate_transition_with_context(&transition, &station_execution, Some(&context_data))
+            .await
+    }
+
     /// Evaluate whether a transition should be taken
     ///
     /// # Condition Types
@@ -718,6 +830,19 @@ impl WorkflowOrchestrator {
         &self,
         transition: &StationTransition,
         station_execution: &StationExecution,
+    ) -> WorkflowOrchestratorResult<bool> {
+        self.evaluate_transition_with_context(transition, station_execution, None)
+            .await
+    }
+
+    /// Like `evaluate_transition`, with `context` standing in for the merged outputs of
+    /// earlier stations. When `None`, conditions scoped to `"context"` gather it from the
+    /// database.
+    async fn evaluate_transition_with_context(
+        &self,
+        transition: &StationTransition,
+        station_execution: &StationExecution,
+        context: Option<&JsonValue>,
     ) -> WorkflowOrchestratorResult<bool> {
         // Check condition_type
         match transition.condition_type.as_deref() {
@@ -760,7 +885,11 @@ impl WorkflowOrchestrator {
                         condition_value
                     );
                     let result = self
-                        .evaluate_condition_expression(condition_value, station_execution)
+                        .evaluate_condition_expression_with_context(
+                            condition_value,
+                            station_execution,
+                            context,
+                        )
                         .await?;
                     tracing::debug!(
                         "Conditional transition {} evaluation result: {}",
@@ -819,6 +948,13 @@ impl WorkflowOrchestrator {
     /// {"check_output_key": "review_passed", "expected_value": "true"}
     /// ```
     ///
+    /// **Earlier Station Outputs:**
+    /// ```json
+    /// {"key": "design_approved", "value": true, "scope": "context"}
+    /// ```
+    /// Object conditions with `"scope": "context"` read the merged outputs of all completed
+    /// stations, with the current station's output layered on top
+    ///
     /// # Error Handling
     /// - Returns error if output_data cannot be parsed
     /// - Returns error if condition_value has invalid syntax
@@ -827,16 +963,41 @@ impl WorkflowOrchestrator {
         &self,
         condition_value: &str,
         station_execution: &StationExecution,
+    ) -> WorkflowOrchestratorResult<bool> {
+        self.evaluate_condition_expression_with_context(condition_value, station_execution, None)
+            .await
+    }
+
+    async fn evaluate_condition_expression_with_context(
+        &self,
+        condition_value: &str,
+        station_execution: &StationExecution,
+        context: Option<&JsonValue>,
     ) -> WorkflowOrchestratorResult<bool> {
         // Parse station output_data (if available)
-        let output = if let Some(output_data) = &station_execution.output_data {
-            serde_json::from_str::<JsonValue>(output_data).map_err(|e| {
+        let station_output = station_execution
+            .output_data
+            .as_deref()
+            .map(serde_json::from_str::<JsonValue>)
+            .transpose()
+            .map_err(|e| {
                 WorkflowOrchestratorError::OutputDataParseError(format!(
                     "Failed to parse station output data: {}",
                     e
                 ))
-            })?
-        } else {
+            })?;
+
+        let scope = ConditionScope::of_condition(condition_value)
+            .map_err(WorkflowOrchestratorError::InvalidTransitionSyntax)?;
+        let gathered_context = match (scope, context) {
+            (ConditionScope::Context, None) => Some(
+                self.gather_context_data(station_execution.workflow_execution_id)
+                    .await?,
+            ),
+            _ => None,
+        };
+        let Some(output) = scope.output(station_output, context.or(gathered_context.as_ref()))
+        else {
             // No output data, condition cannot be satisfied
             return Ok(false);
         };
@@ -903,7 +1064,7 @@ impl WorkflowOrchestrator {
     /// This function:
     /// 1. Updates station_execution status
     /// 2. Checks if station succeeded/failed
-    /// 3. Advances to next station or completes workflow
+    /// 3. Advances to next station or completes workflow, unless the workflow is paused
     /// 4. Moves task to "inreview" when all stations complete
     /// 5. Handles errors with structured logging and recovery
     pub async fn handle_station_completion<C: ContainerService + Sync>(
@@ -914,12 +1075,33 @@ impl WorkflowOrchestrator {
         output_data: Option<String>,
     ) -> WorkflowOrchestratorResult<()> {
         // Load the station execution
-        let _station_execution = StationExecution::find_by_id(self.pool(), station_execution_id)
+        let station_execution = StationExecution::find_by_id(self.pool(), station_execution_id)
             .await?
             .ok_or(WorkflowOrchestratorError::StationExecutionNotFound(
                 station_execution_id
             ))?;
 
+        // Output that doesn't match the station's schema fails the station, so malformed
+        // output never reaches downstream transition conditions
+        let mut schema_error = None;
+        if success {
+            let station = WorkflowStation::find_by_id(self.pool(), station_execution.station_id)
+                .await?
+                .ok_or(WorkflowOrchestratorError::StationNotFound(
+                    station_execution.station_id,
+                ))?;
+            if let Err(e) = Self::validate_station_output(&station, output_data.as_deref()) {
+                tracing::warn!(
+                    station_id = ?station.id,
+                    station_execution_id = ?station_execution_id,
+                    "{}",
+                    e
+                );
+                schema_error = Some(e);
+            }
+        }
+        let success = success && schema_error.is_none();
+
         // Update status and output data
         let status = if success { "completed" } else { "failed" };
         let station_execution = StationExecution::update(
@@ -953,10 +1135,12 @@ impl WorkflowOrchestrator {
 
         // Only advance if the station succeeded
         if !success {
-            let error_ctx = ErrorContext::new(format!("Station execution failed: {}", status))
-                .with_station(station_execution.station_id)
-                .with_station_execution(station_execution_id)
-                .with_workflow_execution(workflow_execution.id);
+            let error_ctx = ErrorContext::new(
+                schema_error.unwrap_or_else(|| format!("Station execution failed: {}", status)),
+            )
+            .with_station(station_execution.station_id)
+            .with_station_execution(station_execution_id)
+            .with_workflow_execution(workflow_execution.id);
 
             error_ctx.log_error();
 
@@ -982,8 +1166,18 @@ impl WorkflowOrchestrator {
             return Ok(());
         }
 
+        // A pause requested while the station ran takes effect here, at the station boundary
+        if workflow_execution.status == "paused" {
+            tracing::info!(
+                station_execution_id = ?station_execution_id,
+                workflow_execution_id = ?workflow_execution.id,
+                "Workflow execution is paused, not advancing until resumed"
+            );
+            return Ok(());
+        }
+
         // Try to advance to the next station
-        let next_station_id = self
+        let next_transition = self
             .advance_to_next_station(
                 workflow_execution.id,
                 station_execution.station_id,
@@ -991,8 +1185,10 @@ impl WorkflowOrchestrator {
             )
             .await?;
 
-        match next_station_id {
-            Some(next_station_id) => {
+        match next_transition {
+            Some(next_transition) => {
+                let next_station_id = next_transition.target_station_id;
+
                 // Update workflow execution with next station
                 WorkflowExecution::update(
                     self.pool(),
@@ -1025,6 +1221,7 @@ impl WorkflowOrchestrator {
                     container_service,
                     workflow_execution.id,
                     next_station_id,
+                    Some(next_transition.id),
                     task_attempt_id,
                     Some(context_data),
                 )
@@ -1077,37 +1274,93 @@ impl WorkflowOrchestrator {
             .ok_or(WorkflowOrchestratorError::NoStationConfigured)
     }
 
+    /// Create the active execution of a workflow on a task, or return the one that already
+    /// holds that slot. The flag is true when this call created the execution.
+    ///
+    /// `idx_workflow_executions_active_task` allows one active execution per workflow and task,
+    /// so of two concurrent starts only one creates a record; the other gets the winner's.
+    pub async fn create_or_get_active_execution(
+        &self,
+        workflow_id: Uuid,
+        task_id: Uuid,
+        task_attempt_id: Uuid,
+    ) -> WorkflowOrchestratorResult<(WorkflowExecution, bool)> {
+        if let Some(existing) =
+            WorkflowExecution::find_active_by_task(self.pool(), workflow_id, task_id).await?
+        {
+            return Ok((existing, false));
+        }
+
+        let created = WorkflowExecution::create(
+            self.pool(),
+            db::models::workflow_execution::CreateWorkflowExecution {
+                workflow_id,
+                task_id,
+                task_attempt_id: Some(task_attempt_id),
+                status: "running".to_string(),
+            },
+            Uuid::new_v4(),
+        )
+        .await;
+        match created {
+            Ok(workflow_execution) => Ok((workflow_execution, true)),
+            Err(sqlx::Error::Database(db_err)) if db_err.is_unique_violation() => {
+                let existing =
+                    WorkflowExecution::find_active_by_task(self.pool(), workflow_id, task_id)
+                        .await?
+                        .ok_or(WorkflowOrchestratorError::InvalidWorkflowState {
+                            expected: "an active workflow execution".to_string(),
+                            actual: "none".to_string(),
+                        })?;
+                Ok((existing, false))
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
     /// Start a workflow execution
     ///
-    /// This creates the workflow execution record and starts the first station
+    /// This creates the workflow execution record and starts the first station.
+    ///
+    /// Starting is idempotent per (workflow, task): if an execution is already pending, running
+    /// or paused on any of the task's attempts, it is returned instead of starting a second one,
+    /// see [`Self::create_or_get_active_execution`]. With `force_restart`, the existing execution
+    /// is cancelled first and a fresh one started.
     pub async fn start_workflow_execution<C: ContainerService + Sync>(
         &self,
         container_service: &C,
         workflow_id: Uuid,
         task_id: Uuid,
         task_attempt_id: Uuid,
+        force_restart: bool,
     ) -> WorkflowOrchestratorResult<WorkflowExecution> {
         // Verify workflow exists
         let _workflow = Workflow::find_by_id(self.pool(), workflow_id)
             .await?
             .ok_or(WorkflowOrchestratorError::WorkflowNotFound(workflow_id))?;
 
+        if force_restart
+            && let Some(existing) =
+                WorkflowExecution::find_active_by_task(self.pool(), workflow_id, task_id).await?
+        {
+            self.cancel_workflow_execution(container_service, existing.id)
+                .await?;
+        }
+
         // Get the first station
         let first_station = self.get_first_station(workflow_id).await?;
 
-        // Create workflow execution record
-        let workflow_execution_id = Uuid::new_v4();
-        let workflow_execution = WorkflowExecution::create(
-            self.pool(),
-            db::models::workflow_execution::CreateWorkflowExecution {
-                workflow_id,
-                task_id,
-                task_attempt_id: Some(task_attempt_id),
-                status: "running".to_string(),
-            },
-            workflow_execution_id,
-        )
-        .await?;
+        let (workflow_execution, created) = self
+            .create_or_get_active_execution(workflow_id, task_id, task_attempt_id)
+            .await?;
+        if !created {
+            tracing::info!(
+                workflow_execution_id = ?workflow_execution.id,
+                task_id = ?task_id,
+                "Workflow already running on this task, returning existing execution"
+            );
+            return Ok(workflow_execution);
+        }
 
         // Update with first station and start time
         let workflow_execution = WorkflowExecution::update(
@@ -1134,6 +1387,7 @@ impl WorkflowOrchestrator {
             container_service,
             workflow_execution.id,
             first_station.id,
+            None,
             task_attempt_id,
             None,
         )
@@ -1142,6 +1396,90 @@ impl WorkflowOrchestrator {
         Ok(workflow_execution)
     }
 
+    /// Cancel a workflow execution, stopping the process of any running station
+    ///
+    /// Processes are stopped first since that cannot be rolled back; station and workflow
+    /// statuses are then updated in one transaction. If that fails the cancel can simply be
+    /// retried.
+    pub async fn cancel_workflow_execution<C: ContainerService + Sync>(
+        &self,
+        container_service: &C,
+        workflow_execution_id: Uuid,
+    ) -> WorkflowOrchestratorResult<WorkflowExecution> {
+        let workflow_execution = WorkflowExecution::find_by_id(self.pool(), workflow_execution_id)
+            .await?
+            .ok_or(WorkflowOrchestratorError::WorkflowExecutionNotFound(
+                workflow_execution_id,
+            ))?;
+
+        if workflow_execution.status == "completed" || workflow_execution.status == "cancelled" {
+            return Err(WorkflowOrchestratorError::InvalidWorkflowState {
+                expected: "an unfinished workflow execution".to_string(),
+                actual: workflow_execution.status,
+            });
+        }
+
+        let station_executions =
+            StationExecution::find_by_workflow_execution(self.pool(), workflow_execution_id)
+                .await?;
+        let running_stations: Vec<&StationExecution> = station_executions
+            .iter()
+            .filter(|se| se.status == "running")
+            .collect();
+
+        // Stop all running execution processes first (cannot be rolled back)
+        for station_execution in &running_stations {
+            let Some(execution_process_id) = station_execution.execution_process_id else {
+                continue;
+            };
+            if let Some(execution_process) =
+                ExecutionProcess::find_by_id(self.pool(), execution_process_id).await?
+                && execution_process.status == ExecutionProcessStatus::Running
+            {
+                container_service
+                    .stop_execution(&execution_process, ExecutionProcessStatus::Killed)
+                    .await?;
+                tracing::info!(
+                    "Stopped execution process {} for station execution {}",
+                    execution_process_id,
+                    station_execution.id
+                );
+            }
+        }
+
+        let mut tx = self.pool().begin().await?;
+        for station_execution in &running_stations {
+            sqlx::query!(
+                "UPDATE station_executions SET status = $2, updated_at = CURRENT_TIMESTAMP WHERE id = $1",
+                station_execution.id,
+                "cancelled"
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        let completed_at = Utc::now();
+        sqlx::query!(
+            "UPDATE workflow_executions SET status = $2, completed_at = $3, updated_at = CURRENT_TIMESTAMP WHERE id = $1",
+            workflow_execution_id,
+            "cancelled",
+            completed_at
+        )
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        tracing::info!(
+            workflow_execution_id = ?workflow_execution_id,
+            "Workflow execution cancelled"
+        );
+
+        WorkflowExecution::find_by_id(self.pool(), workflow_execution_id)
+            .await?
+            .ok_or(WorkflowOrchestratorError::WorkflowExecutionNotFound(
+                workflow_execution_id,
+            ))
+    }
+
     /// Extract output data from agent response text
     ///
     /// This function implements Phase 1.1's manual extraction approach:
@@ -1277,8 +1615,10 @@ impl WorkflowOrchestrator {
 
     /// Trigger workflow progression after an execution completes
     ///
-    /// This is the main integration point called from LocalContainerService::spawn_exit_monitor.
-    /// It should be called after commit/next-action logic completes.
+    /// Convenience wrapper around `check_execution_for_workflow` followed by
+    /// `handle_station_completion`. `LocalContainerService::spawn_exit_monitor` performs
+    /// these two steps itself so it can extract output data (using the station's
+    /// `output_context_keys`) between them.
     ///
     /// # Arguments
     /// * `container_service` - Container service for starting next station executions
@@ -1293,11 +1633,12 @@ impl WorkflowOrchestrator {
     ///     .check_execution_for_workflow(exec_id, success)
     ///     .await
     /// {
-    ///     // Extract output data from execution (Phase 1.1: manual for now)
-    ///     let output_data = None; // TODO: Implement output extraction
+    ///     let output_data = container
+    ///         .extract_workflow_output_data(&exec_id, &station_execution)
+    ///         .await;
     ///
     ///     if let Err(e) = workflow_orchestrator
-    ///         .handle_station_completion(&container_service, station_execution.id, success, output_data)
+    ///         .handle_station_completion(&container, station_execution.id, success, output_data)
     ///         .await
     ///     {
     ///         tracing::error!("Failed to handle workflow station completion: {}", e);
@@ -1329,20 +1670,63 @@ impl WorkflowOrchestrator {
         Ok(())
     }
 
+    /// Pause a running workflow execution at the next station boundary
+    ///
+    /// The station currently executing is left to finish. When it completes,
+    /// `handle_station_completion` records its status and output but does not advance the
+    /// workflow until `resume_workflow_execution` is called.
+    ///
+    /// `"paused"` is its own status, set and cleared only by the user, so it is never confused
+    /// with a workflow waiting on an approval.
+    pub async fn pause_workflow_execution(
+        &self,
+        workflow_execution_id: Uuid,
+    ) -> WorkflowOrchestratorResult<WorkflowExecution> {
+        let workflow_execution = WorkflowExecution::find_by_id(self.pool(), workflow_execution_id)
+            .await?
+            .ok_or(WorkflowOrchestratorError::WorkflowExecutionNotFound(
+                workflow_execution_id,
+            ))?;
+
+        if workflow_execution.status != "running" {
+            return Err(WorkflowOrchestratorError::InvalidWorkflowState {
+                expected: "running".to_string(),
+                actual: workflow_execution.status,
+            });
+        }
+
+        WorkflowExecution::update_status(self.pool(), workflow_execution_id, "paused").await?;
+
+        tracing::info!(
+            workflow_execution_id = ?workflow_execution_id,
+            current_station_id = ?workflow_execution.current_station_id,
+            "Workflow execution paused"
+        );
+
+        WorkflowExecution::find_by_id(self.pool(), workflow_execution_id)
+            .await?
+            .ok_or(WorkflowOrchestratorError::WorkflowExecutionNotFound(
+                workflow_execution_id,
+            ))
+    }
+
     /// Resume a workflow execution from its last checkpoint (current_station_id)
     ///
     /// This function allows recovering from system failures by restarting execution
-    /// from the current station. It handles the following recovery scenarios:
+    /// from the current station, and continuing a workflow paused with
+    /// `pause_workflow_execution`. It handles the following scenarios:
     /// - Process crash/restart
     /// - Database connection lost during execution
     /// - System shutdown during workflow execution
+    /// - User pause, either before or after the current station finished
     ///
     /// # Arguments
     /// * `container_service` - Container service for starting station executions
     /// * `workflow_execution_id` - The workflow execution to resume
     ///
     /// # Recovery Logic
-    /// 1. Validate workflow execution exists and is in "running" state
+    /// 1. Validate workflow execution exists and is in "running" or "paused" state, moving a
+    ///    paused workflow back to "running"
     /// 2. Check if current_station_id is set (indicates checkpoint)
     /// 3. Find the station execution for the current station
     /// 4. If station execution is "running" but process is dead, restart it
@@ -1366,12 +1750,16 @@ impl WorkflowOrchestrator {
             ))?;
 
         // Validate state
-        if workflow_execution.status != "running" {
-            return Err(WorkflowOrchestratorError::InvalidWorkflowState {
-                expected: "running".to_string(),
-                actual: workflow_execution.status.clone(),
-            });
-        }
+        let paused = match workflow_execution.status.as_str() {
+            "running" => false,
+            "paused" => true,
+            _ => {
+                return Err(WorkflowOrchestratorError::InvalidWorkflowState {
+                    expected: "running or paused".to_string(),
+                    actual: workflow_execution.status.clone(),
+                });
+            }
+        };
 
         // Get current station from checkpoint
         let current_station_id = workflow_execution
@@ -1390,13 +1778,30 @@ impl WorkflowOrchestrator {
         let station_executions =
             StationExecution::find_by_workflow_execution(self.pool(), workflow_execution_id).await?;
 
+        // Latest attempt at the current station, in case it was retried
         let current_station_exec = station_executions
             .iter()
+            .rev()
             .find(|se| se.station_id == current_station_id)
             .ok_or_else(|| WorkflowOrchestratorError::ResumeError(
                 format!("No station execution found for current station {}", current_station_id)
             ))?;
 
+        // Unpause only when the current station can be picked up, so a failed station stays
+        // paused for a retry instead of leaving a running workflow with nothing executing
+        let resumable = matches!(
+            current_station_exec.status.as_str(),
+            "running" | "completed"
+        );
+        if paused && resumable {
+            WorkflowExecution::update_status(self.pool(), workflow_execution_id, "running").await?;
+            tracing::info!(
+                workflow_execution_id = ?workflow_execution_id,
+                station_execution_id = ?current_station_exec.id,
+                "Workflow execution unpaused"
+            );
+        }
+
         match current_station_exec.status.as_str() {
             "running" => {
                 // Station was running when interrupted - check if process is still alive
@@ -1456,7 +1861,7 @@ impl WorkflowOrchestrator {
                 let context_data = self.gather_context_data(workflow_execution_id).await?;
 
                 // Try to advance to next station
-                let next_station_id = self
+                let next_transition = self
                     .advance_to_next_station(
                         workflow_execution_id,
                         current_station_id,
@@ -1464,7 +1869,9 @@ impl WorkflowOrchestrator {
                     )
                     .await?;
 
-                if let Some(next_station_id) = next_station_id {
+                if let Some(next_transition) = next_transition {
+                    let next_station_id = next_transition.target_station_id;
+
                     // Update workflow execution with next station
                     WorkflowExecution::update(
                         self.pool(),
@@ -1487,6 +1894,7 @@ impl WorkflowOrchestrator {
                         container_service,
                         workflow_execution_id,
                         next_station_id,
+                        Some(next_transition.id),
                         task_attempt_id,
                         Some(context_data),
                     )
@@ -1550,11 +1958,23 @@ impl WorkflowOrchestrator {
         // Gather context from previously completed stations
         let context_data = self.gather_context_data(workflow_execution_id).await?;
 
+        // The retry was reached through the same transition as the attempt it replaces
+        let transition_id = StationExecution::find_by_workflow_execution(
+            self.pool(),
+            workflow_execution_id,
+        )
+        .await?
+        .into_iter()
+        .rev()
+        .find(|se| se.station_id == station_id)
+        .and_then(|se| se.transition_id);
+
         // Create a new station execution (the old one stays as "failed" or "running" for audit)
         self.execute_station(
             container_service,
             workflow_execution_id,
             station_id,
+            transition_id,
             task_attempt_id,
             Some(context_data),
         )
@@ -1616,7 +2036,7 @@ impl WorkflowOrchestrator {
         match condition_type {
             Some("conditional") => {
                 if let Some(value) = condition_value {
-                    // Try parsing as JSON to validate syntax
+                    // Try parsing as JSON to validate syntax and scope
                     if value.starts_with('{') {
                         serde_json::from_str::<JsonValue>(value).map_err(|e| {
                             WorkflowOrchestratorError::InvalidTransitionSyntax(format!(
@@ -1624,6 +2044,8 @@ impl WorkflowOrchestrator {
                                 e
                             ))
                         })?;
+                        ConditionScope::of_condition(value)
+                            .map_err(WorkflowOrchestratorError::InvalidTransitionSyntax)?;
                     }
                     Ok(())
                 } else {
@@ -1657,6 +2079,7 @@ mod tests {
             workflow_execution_id: Uuid::new_v4(),
             station_id: Uuid::new_v4(),
             execution_process_id: None,
+            transition_id: None,
             status: status.to_string(),
             output_data: output_data.map(|s| s.to_string()),
             started_at: None,
@@ -1847,6 +2270,110 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_dry_run_transition_condition() {
+        let pool = sqlx::SqlitePool::connect(":memory:").await.unwrap();
+        let db = DBService { pool };
+        let orchestrator = WorkflowOrchestrator::new(db);
+        let output = serde_json::json!({"review_passed": true});
+
+        let taken = orchestrator
+            .dry_run_transition_condition(
+                Some("conditional"),
+                Some(r#"{"key": "review_passed", "value": true}"#),
+                "completed",
+                Some(&output),
+                None,
+            )
+            .await
+            .unwrap();
+        assert!(taken, "Matching output should take the transition");
+
+        let taken = orchestrator
+            .dry_run_transition_condition(Some("failure"), None, "completed", Some(&output), None)
+            .await
+            .unwrap();
+        assert!(
+            !taken,
+            "Failure transition should not be taken for completed status"
+        );
+
+        let err = orchestrator
+            .dry_run_transition_condition(
+                Some("conditional"),
+                Some(r#"{"key": "review_passed""#),
+                "completed",
+                Some(&output),
+                None,
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            WorkflowOrchestratorError::InvalidTransitionSyntax(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_context_scoped_condition() {
+        let pool = sqlx::SqlitePool::connect(":memory:").await.unwrap();
+        let db = DBService { pool };
+        let orchestrator = WorkflowOrchestrator::new(db);
+        let output = serde_json::json!({"reviewer": "bob"});
+        let context = serde_json::json!({"design_approved": true, "reviewer": "alice"});
+
+        let taken = orchestrator
+            .dry_run_transition_condition(
+                Some("conditional"),
+                Some(r#"{"key": "design_approved", "value": true, "scope": "context"}"#),
+                "completed",
+                Some(&output),
+                Some(&context),
+            )
+            .await
+            .unwrap();
+        assert!(taken, "Context scope should read earlier stations' outputs");
+
+        let taken = orchestrator
+            .dry_run_transition_condition(
+                Some("conditional"),
+                Some(r#"{"key": "design_approved", "value": true}"#),
+                "completed",
+                Some(&output),
+                Some(&context),
+            )
+            .await
+            .unwrap();
+        assert!(!taken, "Station scope should only read the current output");
+
+        let taken = orchestrator
+            .dry_run_transition_condition(
+                Some("conditional"),
+                Some(r#"{"key": "reviewer", "value": "bob", "scope": "context"}"#),
+                "completed",
+                Some(&output),
+                Some(&context),
+            )
+            .await
+            .unwrap();
+        assert!(taken, "Current station output should win over the context");
+
+        let err = orchestrator
+            .dry_run_transition_condition(
+                Some("conditional"),
+                Some(r#"{"key": "reviewer", "value": "bob", "scope": "workflow"}"#),
+                "completed",
+                Some(&output),
+                None,
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            WorkflowOrchestratorError::InvalidTransitionSyntax(_)
+        ));
+    }
+
     // NOTE: Circular workflow detection test is in the integration tests
     // because it requires a full database setup with foreign keys
 
@@ -1879,6 +2406,65 @@ mod tests {
         assert!(WorkflowOrchestrator::validate_transition_condition(Some("unknown"), None).is_err());
     }
 
+    #[test]
+    fn test_parse_executor_profile() {
+        let profile = WorkflowOrchestrator::parse_executor_profile("CLAUDE_CODE").unwrap();
+        assert_eq!(profile.executor, BaseCodingAgent::ClaudeCode);
+
+        // Aliases accepted by BaseCodingAgent are valid executors too
+        let profile = WorkflowOrchestrator::parse_executor_profile("CURSOR").unwrap();
+        assert_eq!(profile.executor, BaseCodingAgent::CursorAgent);
+
+        assert!(WorkflowOrchestrator::parse_executor_profile("claude").is_err());
+        assert!(WorkflowOrchestrator::parse_executor_profile("").is_err());
+    }
+
+    #[test]
+    fn test_validate_station_output() {
+        let mut station = WorkflowStation {
+            id: Uuid::new_v4(),
+            workflow_id: Uuid::new_v4(),
+            name: "Review".to_string(),
+            position: 0,
+            description: None,
+            x_position: 0.0,
+            y_position: 0.0,
+            agent_id: None,
+            station_prompt: None,
+            output_context_keys: Some("review_passed".to_string()),
+            output_schema: None,
+            is_terminator: false,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+
+        // Without a schema any output is accepted
+        assert!(WorkflowOrchestrator::validate_station_output(&station, None).is_ok());
+
+        station.output_schema = Some(
+            r#"{"type": "object", "required": ["review_passed"], "properties": {"review_passed": {"type": "boolean"}}}"#
+                .to_string(),
+        );
+        assert!(
+            WorkflowOrchestrator::validate_station_output(
+                &station,
+                Some(r#"{"review_passed": true}"#)
+            )
+            .is_ok()
+        );
+
+        let err = WorkflowOrchestrator::validate_station_output(
+            &station,
+            Some(r#"{"review_passed": "yes"}"#),
+        )
+        .unwrap_err();
+        assert!(err.contains("$.review_passed: expected boolean, got string"));
+
+        // A station that produced nothing doesn't satisfy an object schema
+        assert!(WorkflowOrchestrator::validate_station_output(&station, None).is_err());
+        assert!(WorkflowOrchestrator::validate_station_output(&station, Some("not json")).is_err());
+    }
+
     #[test]
     fn test_error_context_builder() {
         let station_id = Uuid::new_v4();
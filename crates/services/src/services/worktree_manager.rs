@@ -52,28 +52,43 @@ impl WorktreeManager {
         create_branch: bool,
     ) -> Result<(), WorktreeError> {
         if create_branch {
-            let repo_path_owned = repo_path.to_path_buf();
-            let branch_name_owned = branch_name.to_string();
-            let base_branch_owned = base_branch.to_string();
-
-            tokio::task::spawn_blocking(move || {
-                let repo = Repository::open(&repo_path_owned)?;
-                let base_branch_ref =
-                    GitService::find_branch(&repo, &base_branch_owned)?.into_reference();
-                repo.branch(
-                    &branch_name_owned,
-                    &base_branch_ref.peel_to_commit()?,
-                    false,
-                )?;
-                Ok::<(), GitServiceError>(())
-            })
-            .await
-            .map_err(|e| WorktreeError::TaskJoin(format!("Task join error: {e}")))??;
+            Self::create_branch(repo_path, branch_name, base_branch).await?;
         }
 
         Self::ensure_worktree_exists(repo_path, branch_name, worktree_path).await
     }
 
+    /// Create `branch_name` at the tip of `base_branch` without checking it out
+    pub async fn create_branch(
+        repo_path: &Path,
+        branch_name: &str,
+        base_branch: &str,
+    ) -> Result<(), WorktreeError> {
+        let repo_path_owned = repo_path.to_path_buf();
+        let branch_name_owned = branch_name.to_string();
+        let base_branch_owned = base_branch.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            let repo = Repository::open(&repo_path_owned)?;
+            let base_branch = GitService::find_branch(&repo, &base_branch_owned)?;
+            let base_is_remote = base_branch.get().is_remote();
+            let base_branch_ref = base_branch.into_reference();
+            let mut branch = repo.branch(
+                &branch_name_owned,
+                &base_branch_ref.peel_to_commit()?,
+                false,
+            )?;
+            // Track the remote base so `git status` in the worktree reports ahead/behind
+            if base_is_remote {
+                branch.set_upstream(Some(&base_branch_owned))?;
+            }
+            Ok::<(), GitServiceError>(())
+        })
+        .await
+        .map_err(|e| WorktreeError::TaskJoin(format!("Task join error: {e}")))??;
+        Ok(())
+    }
+
     /// Ensure worktree exists, recreating if necessary with proper synchronization
     /// This is the main entry point for ensuring a worktree exists and prevents race conditions
     pub async fn ensure_worktree_exists(
@@ -106,6 +121,55 @@ impl WorktreeManager {
         Self::recreate_worktree_internal(repo_path, branch_name, worktree_path).await
     }
 
+    /// Check `branch_name` out in a worktree shared by several attempts, creating the worktree
+    /// when it is missing. Ignored files such as installed dependencies survive the switch.
+    /// Refuses while tracked files have uncommitted changes, so one attempt's work is never
+    /// discarded for another's.
+    pub async fn switch_shared_worktree(
+        repo_path: &Path,
+        branch_name: &str,
+        worktree_path: &Path,
+    ) -> Result<(), WorktreeError> {
+        let path_str = worktree_path.to_string_lossy().to_string();
+        let lock = {
+            let mut locks = WORKTREE_CREATION_LOCKS.lock().unwrap();
+            locks
+                .entry(path_str.clone())
+                .or_insert_with(|| Arc::new(tokio::sync::Mutex::new(())))
+                .clone()
+        };
+        let _guard = lock.lock().await;
+
+        if !Self::is_worktree_properly_set_up(repo_path, worktree_path).await? {
+            info!("Shared worktree needs recreation at path: {}", path_str);
+            return Self::recreate_worktree_internal(repo_path, branch_name, worktree_path).await;
+        }
+
+        let branch_name = branch_name.to_string();
+        let worktree_path = worktree_path.to_path_buf();
+        tokio::task::spawn_blocking(move || {
+            let git = GitService::new();
+            let head = git.get_head_info(&worktree_path)?;
+            if head.branch == branch_name {
+                return Ok(());
+            }
+            if !git.is_worktree_clean(&worktree_path)? {
+                return Err(WorktreeError::Repository(format!(
+                    "Shared worktree {path_str} has uncommitted changes on '{}'; commit or discard them before switching to '{branch_name}'",
+                    head.branch
+                )));
+            }
+            info!(
+                "Switching shared worktree {} from {} to {}",
+                path_str, head.branch, branch_name
+            );
+            git.checkout_branch(&worktree_path, &branch_name)?;
+            Ok(())
+        })
+        .await
+        .map_err(|e| WorktreeError::TaskJoin(format!("Task join error: {e}")))?
+    }
+
     /// Internal worktree recreation function (always recreates)
     async fn recreate_worktree_internal(
         repo_path: &Path,
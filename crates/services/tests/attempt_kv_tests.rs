@@ -0,0 +1,173 @@
+//! Attempt Key-Value Store Tests
+//!
+//! These tests cover the limits and lifecycle of an attempt's scratch entries.
+
+use db::models::{
+    attempt_kv::{
+        AttemptKvEntry, MAX_KV_ENTRIES_PER_ATTEMPT, MAX_KV_KEY_CHARS, MAX_KV_VALUE_BYTES,
+        validate_kv_entry,
+    },
+    project::{CreateProject, Project},
+    task::{CreateTask, Task},
+    task_attempt::{CreateTaskAttempt, TaskAttempt},
+};
+use executors::executors::BaseCodingAgent;
+use serde_json::json;
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+async fn create_test_attempt(
+    pool: &SqlitePool,
+) -> Result<(Task, TaskAttempt), Box<dyn std::error::Error>> {
+    let temp_dir = tempfile::tempdir()?;
+    let project = Project::create(
+        pool,
+        &CreateProject {
+            name: "Test Project".to_string(),
+            git_repo_path: temp_dir.path().to_string_lossy().to_string(),
+            use_existing_repo: false,
+            git_url: None,
+            setup_script: None,
+            dev_script: None,
+            cleanup_script: None,
+            copy_files: None,
+            worktree_dir: None,
+            push_remote: None,
+            pr_remote: None,
+            always_run_cleanup: false,
+            setup_cache_files: None,
+            setup_cache_artifacts: None,
+            script_language: None,
+            preview_url_pattern: None,
+            env_file: None,
+            env_file_required: false,
+            dev_scripts: Vec::new(),
+            reuse_worktree: false,
+        },
+        Uuid::new_v4(),
+    )
+    .await?;
+    let task = Task::create(
+        pool,
+        &CreateTask::from_title_description(project.id, "Test Task".to_string(), None),
+        Uuid::new_v4(),
+    )
+    .await?;
+    let task_attempt = TaskAttempt::create(
+        pool,
+        &CreateTaskAttempt {
+            executor: BaseCodingAgent::ClaudeCode,
+            base_branch: "main".to_string(),
+            branch: "test-branch".to_string(),
+        },
+        Uuid::new_v4(),
+        task.id,
+    )
+    .await?;
+    Ok((task, task_attempt))
+}
+
+#[test]
+fn test_validate_kv_entry_limits() {
+    assert!(validate_kv_entry("plan.step", &json!({ "done": [1, 2] })).is_ok());
+
+    assert!(validate_kv_entry("", &json!(1)).is_err());
+    assert!(validate_kv_entry("line\nbreak", &json!(1)).is_err());
+    assert!(validate_kv_entry(&"k".repeat(MAX_KV_KEY_CHARS), &json!(1)).is_ok());
+    assert!(validate_kv_entry(&"k".repeat(MAX_KV_KEY_CHARS + 1), &json!(1)).is_err());
+
+    // A JSON string serializes with its two quotes
+    let at_limit = json!("x".repeat(MAX_KV_VALUE_BYTES - 2));
+    assert!(validate_kv_entry("big", &at_limit).is_ok());
+    let over_limit = json!("x".repeat(MAX_KV_VALUE_BYTES - 1));
+    assert_eq!(
+        validate_kv_entry("big", &over_limit).unwrap_err(),
+        format!(
+            "Value for 'big' is {} bytes; the limit is {MAX_KV_VALUE_BYTES}",
+            MAX_KV_VALUE_BYTES + 1
+        )
+    );
+}
+
+#[sqlx::test(migrations = "../db/migrations")]
+async fn test_set_replaces_existing_value(
+    pool: SqlitePool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let (_, attempt) = create_test_attempt(&pool).await?;
+
+    let first = AttemptKvEntry::set(&pool, attempt.id, "step", &json!(1))
+        .await?
+        .unwrap();
+    let second = AttemptKvEntry::set(&pool, attempt.id, "step", &json!({ "n": 2 }))
+        .await?
+        .unwrap();
+
+    assert_eq!(second.value.0, json!({ "n": 2 }));
+    assert_eq!(second.created_at, first.created_at);
+    assert!(second.updated_at >= first.updated_at);
+    assert_eq!(
+        AttemptKvEntry::find_by_task_attempt_id(&pool, attempt.id)
+            .await?
+            .len(),
+        1
+    );
+
+    assert_eq!(AttemptKvEntry::delete(&pool, attempt.id, "step").await?, 1);
+    assert_eq!(AttemptKvEntry::delete(&pool, attempt.id, "step").await?, 0);
+    assert!(
+        AttemptKvEntry::find(&pool, attempt.id, "step")
+            .await?
+            .is_none()
+    );
+
+    Ok(())
+}
+
+#[sqlx::test(migrations = "../db/migrations")]
+async fn test_set_refuses_new_keys_past_the_entry_limit(
+    pool: SqlitePool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let (_, attempt) = create_test_attempt(&pool).await?;
+    for i in 0..MAX_KV_ENTRIES_PER_ATTEMPT {
+        let entry = AttemptKvEntry::set(&pool, attempt.id, &format!("key-{i}"), &json!(i)).await?;
+        assert!(entry.is_some());
+    }
+
+    assert!(
+        AttemptKvEntry::set(&pool, attempt.id, "one-too-many", &json!(true))
+            .await?
+            .is_none()
+    );
+    // Existing keys can still be updated at the limit
+    let updated = AttemptKvEntry::set(&pool, attempt.id, "key-0", &json!("again")).await?;
+    assert_eq!(updated.unwrap().value.0, json!("again"));
+
+    // The limit is per attempt
+    let (_, other) = create_test_attempt(&pool).await?;
+    assert!(
+        AttemptKvEntry::set(&pool, other.id, "key-0", &json!(0))
+            .await?
+            .is_some()
+    );
+
+    Ok(())
+}
+
+#[sqlx::test(migrations = "../db/migrations")]
+async fn test_entries_are_removed_with_their_attempt(
+    pool: SqlitePool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let (task, attempt) = create_test_attempt(&pool).await?;
+    AttemptKvEntry::set(&pool, attempt.id, "a", &json!(1)).await?;
+    AttemptKvEntry::set(&pool, attempt.id, "b", &json!(2)).await?;
+
+    Task::delete(&pool, task.id).await?;
+
+    assert!(
+        AttemptKvEntry::find_by_task_attempt_id(&pool, attempt.id)
+            .await?
+            .is_empty()
+    );
+
+    Ok(())
+}
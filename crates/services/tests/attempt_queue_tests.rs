@@ -0,0 +1,145 @@
+//! Attempt Queue Tests
+//!
+//! These tests cover the order queued attempts start in: task priority first, then their
+//! place in the queue.
+
+use db::models::{
+    attempt_queue::{CreateQueuedAttempt, QueuedAttempt},
+    project::{CreateProject, Project},
+    task::{CreateTask, Task},
+};
+use executors::{executors::BaseCodingAgent, profile::ExecutorProfileId};
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+async fn create_test_project(pool: &SqlitePool) -> Result<Project, Box<dyn std::error::Error>> {
+    let temp_dir = tempfile::tempdir()?;
+    let project = Project::create(
+        pool,
+        &CreateProject {
+            name: "Test Project".to_string(),
+            git_repo_path: temp_dir.path().to_string_lossy().to_string(),
+            use_existing_repo: false,
+            git_url: None,
+            setup_script: None,
+            dev_script: None,
+            cleanup_script: None,
+            copy_files: None,
+            worktree_dir: None,
+            push_remote: None,
+            pr_remote: None,
+            always_run_cleanup: false,
+            setup_cache_files: None,
+            setup_cache_artifacts: None,
+            script_language: None,
+            preview_url_pattern: None,
+            env_file: None,
+            env_file_required: false,
+            dev_scripts: Vec::new(),
+            reuse_worktree: false,
+        },
+        Uuid::new_v4(),
+    )
+    .await?;
+    Ok(project)
+}
+
+/// Queue an attempt for a new task of the project
+async fn enqueue_task(
+    pool: &SqlitePool,
+    project_id: Uuid,
+    title: &str,
+) -> Result<(Task, QueuedAttempt), Box<dyn std::error::Error>> {
+    let task = Task::create(
+        pool,
+        &CreateTask::from_title_description(project_id, title.to_string(), None),
+        Uuid::new_v4(),
+    )
+    .await?;
+    let queued = QueuedAttempt::create(
+        pool,
+        &CreateQueuedAttempt {
+            task_id: task.id,
+            executor_profile_id: ExecutorProfileId::new(BaseCodingAgent::ClaudeCode),
+            base_branch: "main".to_string(),
+            force_setup: false,
+        },
+        Uuid::new_v4(),
+    )
+    .await?;
+    Ok((task, queued))
+}
+
+async fn queue_order(pool: &SqlitePool) -> Result<Vec<Uuid>, sqlx::Error> {
+    Ok(QueuedAttempt::find_all(pool)
+        .await?
+        .into_iter()
+        .map(|queued| queued.id)
+        .collect())
+}
+
+#[sqlx::test(migrations = "../db/migrations")]
+async fn test_queue_is_fifo_at_default_priority(
+    pool: SqlitePool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let project = create_test_project(&pool).await?;
+    let (_, first) = enqueue_task(&pool, project.id, "First").await?;
+    let (_, second) = enqueue_task(&pool, project.id, "Second").await?;
+    let (_, third) = enqueue_task(&pool, project.id, "Third").await?;
+
+    assert_eq!(
+        queue_order(&pool).await?,
+        vec![first.id, second.id, third.id]
+    );
+
+    Ok(())
+}
+
+#[sqlx::test(migrations = "../db/migrations")]
+async fn test_higher_priority_tasks_start_first(
+    pool: SqlitePool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let project = create_test_project(&pool).await?;
+    let (_, first) = enqueue_task(&pool, project.id, "First").await?;
+    let (_, second) = enqueue_task(&pool, project.id, "Second").await?;
+    let (urgent_task, urgent) = enqueue_task(&pool, project.id, "Urgent").await?;
+    let (low_task, low) = enqueue_task(&pool, project.id, "Low").await?;
+
+    Task::set_priority(&pool, urgent_task.id, 5).await?;
+    Task::set_priority(&pool, low_task.id, -1).await?;
+    assert_eq!(
+        queue_order(&pool).await?,
+        vec![urgent.id, first.id, second.id, low.id]
+    );
+
+    // Resetting the priority puts the entry back in its original place
+    Task::set_priority(&pool, urgent_task.id, 0).await?;
+    assert_eq!(
+        queue_order(&pool).await?,
+        vec![first.id, second.id, urgent.id, low.id]
+    );
+
+    Ok(())
+}
+
+#[sqlx::test(migrations = "../db/migrations")]
+async fn test_move_only_reorders_within_a_priority(
+    pool: SqlitePool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let project = create_test_project(&pool).await?;
+    let (urgent_task, urgent) = enqueue_task(&pool, project.id, "Urgent").await?;
+    let (_, first) = enqueue_task(&pool, project.id, "First").await?;
+    let (_, second) = enqueue_task(&pool, project.id, "Second").await?;
+    Task::set_priority(&pool, urgent_task.id, 1).await?;
+
+    assert!(QueuedAttempt::move_to(&pool, second.id, 0).await?);
+    assert_eq!(
+        queue_order(&pool).await?,
+        vec![urgent.id, second.id, first.id]
+    );
+
+    QueuedAttempt::delete(&pool, first.id).await?;
+    assert!(!QueuedAttempt::move_to(&pool, first.id, 0).await?);
+
+    Ok(())
+}
@@ -0,0 +1,78 @@
+//! Named Dev Script Tests
+//!
+//! These tests cover validating a project's named dev servers and looking them up by name.
+
+use db::models::project::{CreateProject, NamedScript, Project, invalid_dev_scripts_message};
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+fn named(name: &str, script: &str) -> NamedScript {
+    NamedScript {
+        name: name.to_string(),
+        script: script.to_string(),
+    }
+}
+
+#[test]
+fn test_invalid_dev_scripts_message() {
+    assert_eq!(invalid_dev_scripts_message(&[]), None);
+    assert_eq!(
+        invalid_dev_scripts_message(&[named("api", "cargo run"), named("web", "npm run dev")]),
+        None
+    );
+
+    assert_eq!(
+        invalid_dev_scripts_message(&[named("  ", "cargo run")]).as_deref(),
+        Some("Dev server names cannot be empty")
+    );
+    assert_eq!(
+        invalid_dev_scripts_message(&[named("api", "\n")]).as_deref(),
+        Some("Dev server 'api' has no script")
+    );
+    assert_eq!(
+        invalid_dev_scripts_message(&[named("api", "cargo run"), named("api", "npm start")])
+            .as_deref(),
+        Some("Dev server name 'api' is used twice")
+    );
+}
+
+#[sqlx::test(migrations = "../db/migrations")]
+async fn test_dev_script_named(pool: SqlitePool) -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempfile::tempdir()?;
+    let project = Project::create(
+        &pool,
+        &CreateProject {
+            name: "Test Project".to_string(),
+            git_repo_path: temp_dir.path().to_string_lossy().to_string(),
+            use_existing_repo: false,
+            git_url: None,
+            setup_script: None,
+            dev_script: Some("npm run dev".to_string()),
+            cleanup_script: None,
+            copy_files: None,
+            worktree_dir: None,
+            push_remote: None,
+            pr_remote: None,
+            always_run_cleanup: false,
+            setup_cache_files: None,
+            setup_cache_artifacts: None,
+            script_language: None,
+            preview_url_pattern: None,
+            env_file: None,
+            env_file_required: false,
+            dev_scripts: vec![named("api", "cargo run"), named("docs", "mdbook serve")],
+            reuse_worktree: false,
+        },
+        Uuid::new_v4(),
+    )
+    .await?;
+
+    // Dev scripts survive the round trip through the database
+    let project = Project::find_by_id(&pool, project.id).await?.unwrap();
+    assert_eq!(project.dev_script_named(None), Some("npm run dev"));
+    assert_eq!(project.dev_script_named(Some("api")), Some("cargo run"));
+    assert_eq!(project.dev_script_named(Some("docs")), Some("mdbook serve"));
+    assert_eq!(project.dev_script_named(Some("missing")), None);
+
+    Ok(())
+}
@@ -2,7 +2,7 @@
 mod filesystem_tests {
     use std::{fs, path::Path};
 
-    use services::services::filesystem::FilesystemService;
+    use services::services::{file_search_cache::SearchMode, filesystem::FilesystemService};
     use tempfile::TempDir;
 
     /// Helper function to create a directory structure
@@ -179,4 +179,43 @@ mod filesystem_tests {
         // Should not find deep repo due to depth limit
         assert!(!repo_names.contains(&"deep_repo".to_string()));
     }
+
+    #[tokio::test]
+    async fn test_stream_search_respects_gitignore_and_limit() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = temp_dir.path();
+        create_git_repo(repo, ".");
+        fs::write(repo.join(".gitignore"), "ignored/\n").unwrap();
+        create_dir_structure(repo, "src");
+        create_dir_structure(repo, "ignored");
+        for name in ["config_a.rs", "config_b.rs", "config_c.rs"] {
+            fs::write(repo.join("src").join(name), "").unwrap();
+        }
+        fs::write(repo.join("ignored/config_d.rs"), "").unwrap();
+
+        let filesystem_service = FilesystemService::new();
+        let collect = |mode: SearchMode, limit: usize| {
+            let mut receiver =
+                filesystem_service.stream_search(repo.to_path_buf(), "Config", mode, limit);
+            async move {
+                let mut paths = Vec::new();
+                while let Some(result) = receiver.recv().await {
+                    paths.push(result.path.replace('\\', "/"));
+                }
+                paths.sort();
+                paths
+            }
+        };
+
+        assert_eq!(
+            collect(SearchMode::TaskForm, 10).await,
+            vec!["src/config_a.rs", "src/config_b.rs", "src/config_c.rs"]
+        );
+        assert!(
+            collect(SearchMode::Settings, 10)
+                .await
+                .contains(&"ignored/config_d.rs".to_string())
+        );
+        assert_eq!(collect(SearchMode::TaskForm, 2).await.len(), 2);
+    }
 }
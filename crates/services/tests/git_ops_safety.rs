@@ -6,7 +6,7 @@ use std::{
 
 use git2::{PushOptions, Repository, build::CheckoutBuilder};
 use services::services::{
-    git::GitService,
+    git::{Commit, ConflictResolution, GitService, GitServiceError},
     git_cli::{GitCli, GitCliError},
 };
 use tempfile::TempDir;
@@ -429,6 +429,175 @@ fn rebase_preserves_untracked_files() {
     assert_eq!(content, "temporary note\n");
 }
 
+#[test]
+fn rebase_onto_specific_commit() {
+    let td = TempDir::new().unwrap();
+    let (repo_path, worktree_path) = setup_repo_with_worktree(&td);
+
+    let repo = Repository::open(&repo_path).unwrap();
+    let main_oid = repo
+        .find_branch("main", git2::BranchType::Local)
+        .unwrap()
+        .get()
+        .target()
+        .unwrap()
+        .to_string();
+
+    let service = GitService::new();
+    let res = service.rebase_branch_onto_commit(
+        &repo_path,
+        &worktree_path,
+        &main_oid,
+        "old-base",
+        "feature",
+    );
+    assert!(res.is_ok(), "rebase onto commit should succeed: {res:?}");
+
+    // old-base's own commit is dropped, the feature commit sits on top of main
+    assert!(!worktree_path.join("base.txt").exists());
+    assert_eq!(
+        fs::read_to_string(worktree_path.join("feat.txt")).unwrap(),
+        "feat change\n"
+    );
+    let wt_repo = Repository::open(&worktree_path).unwrap();
+    let head = wt_repo.head().unwrap().peel_to_commit().unwrap();
+    assert_eq!(head.parent_id(0).unwrap().to_string(), main_oid);
+
+    let missing = service.rebase_branch_onto_commit(
+        &repo_path,
+        &worktree_path,
+        "0123456789abcdef0123456789abcdef01234567",
+        "old-base",
+        "feature",
+    );
+    assert!(missing.is_err(), "unknown commit should be rejected");
+}
+
+fn head_subjects(repo: &Repository, n: usize) -> Vec<String> {
+    let mut commit = repo.head().unwrap().peel_to_commit().unwrap();
+    let mut subjects = Vec::new();
+    for _ in 0..n {
+        subjects.push(commit.summary().unwrap().to_string());
+        commit = commit.parent(0).unwrap();
+    }
+    subjects.reverse();
+    subjects
+}
+
+#[test]
+fn reorder_and_squash_attempt_commits() {
+    let td = TempDir::new().unwrap();
+    let (_repo_path, worktree_path) = setup_repo_with_worktree(&td);
+    let wt_repo = Repository::open(&worktree_path).unwrap();
+    write_file(&worktree_path, "second.txt", "second\n");
+    commit_all(&wt_repo, "second commit");
+
+    let head = wt_repo.head().unwrap().peel_to_commit().unwrap();
+    let second = head.id().to_string();
+    let first = head.parent_id(0).unwrap().to_string();
+    let base = head.parent(0).unwrap().parent_id(0).unwrap();
+
+    let service = GitService::new();
+    // Only the newest N commits may be rewritten
+    let res = service.reorder_commits(&worktree_path, std::slice::from_ref(&first));
+    assert!(res.is_err(), "non-tip selection should be rejected");
+
+    service
+        .reorder_commits(&worktree_path, &[second.clone(), first.clone()])
+        .expect("reorder succeeds");
+    assert_eq!(
+        head_subjects(&wt_repo, 2),
+        vec!["second commit", "feature commit"]
+    );
+
+    let reordered: Vec<String> = {
+        let head = wt_repo.head().unwrap().peel_to_commit().unwrap();
+        vec![
+            head.parent_id(0).unwrap().to_string(),
+            head.id().to_string(),
+        ]
+    };
+    service
+        .squash_commits(&worktree_path, &reordered, "feat: combined")
+        .expect("squash succeeds");
+    let head = wt_repo.head().unwrap().peel_to_commit().unwrap();
+    assert_eq!(head.message().unwrap().trim(), "feat: combined");
+    assert_eq!(head.parent_id(0).unwrap(), base);
+    assert!(worktree_path.join("feat.txt").exists());
+    assert!(worktree_path.join("second.txt").exists());
+}
+
+#[test]
+fn squash_all_commits_since_base_keeps_tree() {
+    let td = TempDir::new().unwrap();
+    let (_repo_path, worktree_path) = setup_repo_with_worktree(&td);
+    let wt_repo = Repository::open(&worktree_path).unwrap();
+    write_file(&worktree_path, "second.txt", "second\n");
+    commit_all(&wt_repo, "second commit");
+
+    let head = wt_repo.head().unwrap().peel_to_commit().unwrap();
+    let base = head.parent(0).unwrap().parent_id(0).unwrap();
+
+    let service = GitService::new();
+    let oids = service
+        .commits_since(&worktree_path, &Commit::new(base))
+        .unwrap();
+    assert_eq!(oids.len(), 2);
+    assert_eq!(oids[0], head.id().to_string());
+
+    let original_tree = service.commit_tree_oid(&worktree_path, &oids[0]).unwrap();
+    let squashed = service
+        .squash_commits(&worktree_path, &oids, "feat: everything")
+        .expect("squash succeeds");
+    assert_eq!(
+        service.commit_tree_oid(&worktree_path, &squashed).unwrap(),
+        original_tree
+    );
+    assert_eq!(
+        service
+            .commits_since(&worktree_path, &Commit::new(base))
+            .unwrap(),
+        vec![squashed]
+    );
+}
+
+#[test]
+fn worktree_file_oid_follows_uncommitted_edits() {
+    let td = TempDir::new().unwrap();
+    let (_repo_path, worktree_path) = setup_repo_with_worktree(&td);
+    let service = GitService::new();
+
+    write_file(&worktree_path, "notes.txt", "first\n");
+    let first = service
+        .worktree_file_oid(&worktree_path, "notes.txt")
+        .unwrap();
+    assert!(first.is_some());
+    let wt_repo = Repository::open(&worktree_path).unwrap();
+    commit_all(&wt_repo, "add notes");
+    assert_eq!(
+        service
+            .worktree_file_oid(&worktree_path, "notes.txt")
+            .unwrap(),
+        first
+    );
+
+    write_file(&worktree_path, "notes.txt", "second\n");
+    assert_ne!(
+        service
+            .worktree_file_oid(&worktree_path, "notes.txt")
+            .unwrap(),
+        first
+    );
+
+    fs::remove_file(worktree_path.join("notes.txt")).unwrap();
+    assert_eq!(
+        service
+            .worktree_file_oid(&worktree_path, "notes.txt")
+            .unwrap(),
+        None
+    );
+}
+
 #[test]
 fn rebase_aborts_on_uncommitted_tracked_changes() {
     let td = TempDir::new().unwrap();
@@ -1410,3 +1579,259 @@ fn merge_base_ahead_of_task_should_error() {
         "Merge should error when base branch is ahead of task branch"
     );
 }
+
+#[test]
+fn conflict_hunks_expose_both_sides() {
+    let td = TempDir::new().unwrap();
+    let (repo_path, worktree_path) = setup_conflict_repo_with_worktree(&td);
+
+    let service = GitService::new();
+    let _ = service
+        .rebase_branch(
+            &repo_path,
+            &worktree_path,
+            "new-base",
+            "old-base",
+            "feature",
+            None,
+        )
+        .expect_err("rebase should stop on conflicts");
+
+    let conflict = service
+        .get_conflict_hunks(&worktree_path, "conflict.txt")
+        .expect("conflict hunks");
+    assert!(!conflict.is_binary);
+    assert_eq!(conflict.hunks.len(), 1);
+    let hunk = &conflict.hunks[0];
+    assert_eq!(hunk.start_line, 1);
+    assert_eq!(hunk.ours, "new-base version\n");
+    assert_eq!(hunk.theirs, "feature version\n");
+
+    // Reading hunks must not resolve anything
+    assert_eq!(
+        service.get_conflicted_files(&worktree_path).unwrap(),
+        vec!["conflict.txt".to_string()]
+    );
+    assert!(
+        service
+            .get_conflict_hunks(&worktree_path, "missing.txt")
+            .is_err()
+    );
+}
+
+#[test]
+fn resolve_conflict_then_continue_rebase() {
+    let td = TempDir::new().unwrap();
+    let (repo_path, worktree_path) = setup_conflict_repo_with_worktree(&td);
+
+    let service = GitService::new();
+    let _ = service
+        .rebase_branch(
+            &repo_path,
+            &worktree_path,
+            "new-base",
+            "old-base",
+            "feature",
+            None,
+        )
+        .expect_err("rebase should stop on conflicts");
+
+    // Continuing with unresolved files is refused
+    assert!(service.continue_rebase(&worktree_path).is_err());
+    assert!(service.is_rebase_in_progress(&worktree_path).unwrap());
+
+    // Only conflicted files can be resolved
+    assert!(
+        service
+            .resolve_conflict(&worktree_path, "other.txt", ConflictResolution::Theirs)
+            .is_err()
+    );
+
+    let remaining = service
+        .resolve_conflict(&worktree_path, "conflict.txt", ConflictResolution::Theirs)
+        .expect("resolve conflict");
+    assert!(remaining.is_empty());
+    assert_eq!(
+        fs::read_to_string(worktree_path.join("conflict.txt")).unwrap(),
+        "feature version\n"
+    );
+
+    let remaining = service
+        .continue_rebase(&worktree_path)
+        .expect("continue rebase");
+    assert!(remaining.is_empty());
+    assert!(!service.is_rebase_in_progress(&worktree_path).unwrap());
+
+    let wt_repo = Repository::open(&worktree_path).unwrap();
+    let head = wt_repo.head().unwrap().peel_to_commit().unwrap();
+    assert_eq!(head.summary().unwrap(), "feature conflicting change");
+}
+
+#[test]
+fn continue_conflicts_after_manual_resolution() {
+    let td = TempDir::new().unwrap();
+    let (repo_path, worktree_path) = setup_conflict_repo_with_worktree(&td);
+
+    let service = GitService::new();
+    let _ = service
+        .rebase_branch(
+            &repo_path,
+            &worktree_path,
+            "new-base",
+            "old-base",
+            "feature",
+            None,
+        )
+        .expect_err("rebase should stop on conflicts");
+
+    // Markers left in the file are reported by name
+    match service.continue_conflicts(&worktree_path) {
+        Err(GitServiceError::UnresolvedConflictMarkers(files)) => {
+            assert_eq!(files, vec!["conflict.txt".to_string()]);
+        }
+        other => panic!("expected unresolved markers error, got {other:?}"),
+    }
+
+    write_file(&worktree_path, "conflict.txt", "merged version\n");
+    let remaining = service
+        .continue_conflicts(&worktree_path)
+        .expect("continue after manual resolution");
+    assert!(remaining.is_empty());
+    assert!(!service.is_rebase_in_progress(&worktree_path).unwrap());
+    assert_eq!(
+        fs::read_to_string(worktree_path.join("conflict.txt")).unwrap(),
+        "merged version\n"
+    );
+}
+
+#[test]
+fn merge_preview_reports_diff_without_moving_base() {
+    let td = TempDir::new().unwrap();
+    let (repo_path, worktree_path) = setup_repo_with_worktree(&td);
+    let service = GitService::new();
+    let before = service.get_branch_oid(&repo_path, "main").unwrap();
+
+    let preview = service
+        .merge_preview(&repo_path, &worktree_path, "feature", "main")
+        .expect("merge preview");
+
+    assert!(preview.conflicted_files.is_empty());
+    assert_eq!(preview.base_commits_ahead, 0);
+    assert!(!preview.has_uncommitted_changes);
+    let mut paths: Vec<_> = preview
+        .diffs
+        .iter()
+        .filter_map(|d| d.new_path.clone())
+        .collect();
+    paths.sort();
+    assert_eq!(paths, vec!["base.txt".to_string(), "feat.txt".to_string()]);
+    assert_eq!(service.get_branch_oid(&repo_path, "main").unwrap(), before);
+}
+
+#[test]
+fn merge_preview_lists_conflicts_and_leaves_them_out_of_diff() {
+    let td = TempDir::new().unwrap();
+    let (repo_path, worktree_path) = setup_conflict_repo_with_worktree(&td);
+    let service = GitService::new();
+    let before = service.get_branch_oid(&repo_path, "new-base").unwrap();
+
+    let preview = service
+        .merge_preview(&repo_path, &worktree_path, "feature", "new-base")
+        .expect("merge preview");
+
+    assert_eq!(preview.conflicted_files, vec!["conflict.txt".to_string()]);
+    assert_eq!(preview.base_commits_ahead, 1);
+    assert!(preview.diffs.is_empty());
+    assert_eq!(
+        service.get_branch_oid(&repo_path, "new-base").unwrap(),
+        before
+    );
+    assert!(!service.is_rebase_in_progress(&worktree_path).unwrap());
+}
+
+#[test]
+fn find_remote_tracking_branch_without_upstream() {
+    let temp_dir = TempDir::new().unwrap();
+    let remote_path = temp_dir.path().join("remote.git");
+    Repository::init_bare(&remote_path).expect("init bare remote");
+    let remote_url = remote_path.to_str().expect("remote path str");
+
+    let repo_path = temp_dir.path().join("repo");
+    let service = GitService::new();
+    service
+        .initialize_repo_with_main_branch(&repo_path)
+        .expect("init repo");
+    let repo = Repository::open(&repo_path).expect("open repo");
+    configure_user(&repo);
+    repo.remote("origin", remote_url).expect("add remote");
+    create_branch_from_head(&repo, "feature");
+
+    // Never pushed
+    assert_eq!(
+        service
+            .find_remote_tracking_branch(&repo_path, "feature")
+            .unwrap(),
+        None
+    );
+
+    // Pushed by hand without setting an upstream
+    push_ref(&repo, "refs/heads/feature", "refs/heads/feature");
+    let head = repo.head().unwrap().peel_to_commit().unwrap().id();
+    repo.reference("refs/remotes/origin/feature", head, true, "fetch")
+        .unwrap();
+    assert_eq!(
+        service
+            .find_remote_tracking_branch(&repo_path, "feature")
+            .unwrap()
+            .as_deref(),
+        Some("origin/feature")
+    );
+}
+
+#[test]
+fn upstream_set_to_remote_base_does_not_count_as_pushed() {
+    let temp_dir = TempDir::new().unwrap();
+    let remote_path = temp_dir.path().join("remote.git");
+    Repository::init_bare(&remote_path).expect("init bare remote");
+    let remote_url = remote_path.to_str().expect("remote path str");
+
+    let repo_path = temp_dir.path().join("repo");
+    let service = GitService::new();
+    service
+        .initialize_repo_with_main_branch(&repo_path)
+        .expect("init repo");
+    let repo = Repository::open(&repo_path).expect("open repo");
+    configure_user(&repo);
+    repo.remote("origin", remote_url).expect("add remote");
+    push_ref(&repo, "refs/heads/main", "refs/heads/main");
+    let head = repo.head().unwrap().peel_to_commit().unwrap().id();
+    repo.reference("refs/remotes/origin/main", head, true, "fetch")
+        .unwrap();
+
+    // Attempt branches created from a remote base track that base
+    create_branch_from_head(&repo, "feature");
+    repo.find_branch("feature", git2::BranchType::Local)
+        .unwrap()
+        .set_upstream(Some("origin/main"))
+        .unwrap();
+
+    assert!(!service.is_branch_pushed(&repo_path, "feature").unwrap());
+    assert_eq!(
+        service
+            .find_remote_tracking_branch(&repo_path, "feature")
+            .unwrap(),
+        None
+    );
+
+    push_ref(&repo, "refs/heads/feature", "refs/heads/feature");
+    repo.reference("refs/remotes/origin/feature", head, true, "fetch")
+        .unwrap();
+    assert!(service.is_branch_pushed(&repo_path, "feature").unwrap());
+    assert_eq!(
+        service
+            .find_remote_tracking_branch(&repo_path, "feature")
+            .unwrap()
+            .as_deref(),
+        Some("origin/feature")
+    );
+}
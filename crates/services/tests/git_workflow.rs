@@ -5,8 +5,10 @@ use std::{
 };
 
 use services::services::{
-    git::{DiffTarget, GitService},
-    github_service::{GitHubRepoInfo, GitHubServiceError},
+    git::{
+        CommitOptions, DiffContentOptions, DiffPathFilter, DiffTarget, GitService, GitServiceError,
+    },
+    github_service::{GitHubRepoInfo, GitHubService, GitHubServiceError},
 };
 use tempfile::TempDir;
 use utils::diff::DiffChangeKind;
@@ -215,6 +217,70 @@ fn initialize_and_default_branch_and_head_info() {
     assert!(!head.oid.is_empty());
 }
 
+#[test]
+fn default_branch_detection_without_main() {
+    let td = TempDir::new().unwrap();
+    let repo_path = td.path().join("repo_master");
+    let s = GitService::new();
+
+    let repo = git2::Repository::init_opts(
+        &repo_path,
+        git2::RepositoryInitOptions::new().initial_head("master"),
+    )
+    .unwrap();
+    // Empty repository: nothing to detect yet
+    assert_eq!(s.get_default_branch(&repo_path).unwrap(), None);
+
+    // The initial commit lands on the unborn HEAD branch instead of a new `main`
+    s.ensure_default_branch_exists(&repo_path).unwrap();
+    assert_eq!(
+        s.get_default_branch(&repo_path).unwrap().as_deref(),
+        Some("master")
+    );
+    assert!(repo.find_branch("main", git2::BranchType::Local).is_err());
+
+    // A branch named by the remote HEAD wins over main/master
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    repo.reference("refs/remotes/origin/develop", head.id(), true, "test")
+        .unwrap();
+    repo.reference_symbolic(
+        "refs/remotes/origin/HEAD",
+        "refs/remotes/origin/develop",
+        true,
+        "test",
+    )
+    .unwrap();
+    assert_eq!(
+        s.get_default_branch(&repo_path).unwrap().as_deref(),
+        Some("origin/develop")
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn commit_with_options_can_skip_failing_hooks() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let hook = repo_path.join(".git/hooks/pre-commit");
+    write_file(&repo_path, ".git/hooks/pre-commit", "#!/bin/sh\nexit 1\n");
+    fs::set_permissions(&hook, fs::Permissions::from_mode(0o755)).unwrap();
+    write_file(&repo_path, "hooked.txt", "x\n");
+
+    let s = GitService::new();
+    assert!(s.commit(&repo_path, "rejected by hook").is_err());
+    let committed = s
+        .commit_with_options(
+            &repo_path,
+            "bypass hook",
+            CommitOptions { skip_hooks: true },
+        )
+        .unwrap();
+    assert!(committed);
+    assert!(s.is_worktree_clean(&repo_path).unwrap());
+}
+
 #[test]
 fn commit_and_is_worktree_clean() {
     let td = TempDir::new().unwrap();
@@ -335,11 +401,59 @@ fn get_github_repo_info_parses_origin() {
     let s = GitService::new();
     s.set_remote(&repo_path, "origin", "https://github.com/foo/bar.git")
         .unwrap();
-    let info = s.get_github_repo_info(&repo_path).unwrap();
+    let info = s.get_github_repo_info(&repo_path, None).unwrap();
     assert_eq!(info.owner, "foo");
     assert_eq!(info.repo_name, "bar");
 }
 
+#[test]
+fn get_github_repo_info_uses_named_remote() {
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let s = GitService::new();
+    s.set_remote(&repo_path, "origin", "https://github.com/me/bar.git")
+        .unwrap();
+    s.set_remote(&repo_path, "upstream", "git@github.com:foo/bar.git")
+        .unwrap();
+
+    let fork = s.get_github_repo_info(&repo_path, Some("origin")).unwrap();
+    assert_eq!(fork.owner, "me");
+    let upstream = s
+        .get_github_repo_info(&repo_path, Some("upstream"))
+        .unwrap();
+    assert_eq!(upstream.owner, "foo");
+    assert_eq!(upstream.repo_name, "bar");
+
+    assert!(matches!(
+        s.get_github_repo_info(&repo_path, Some("missing")),
+        Err(GitServiceError::RemoteNotFound(name)) if name == "missing"
+    ));
+    assert!(s.ensure_remote_exists(&repo_path, "upstream").is_ok());
+}
+
+#[test]
+fn pr_base_branch_strips_remote_and_falls_back_to_default() {
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let s = GitService::new();
+    write_file(&repo_path, "a.txt", "a\n");
+    s.commit(&repo_path, "add a").unwrap();
+    s.set_remote(&repo_path, "origin", "https://github.com/me/bar.git")
+        .unwrap();
+    let repo = git2::Repository::open(&repo_path).unwrap();
+    let head = repo.head().unwrap().peel_to_commit().unwrap().id();
+    repo.reference("refs/remotes/origin/develop", head, true, "fetch")
+        .unwrap();
+
+    let base =
+        |target: &str| GitHubService::pr_base_branch(&s, &repo_path, target, None, "main").unwrap();
+    assert_eq!(base("origin/develop"), "develop");
+    assert_eq!(base("main"), "main");
+    assert_eq!(base("  "), "main");
+    assert_eq!(GitHubService::default_pr_base(Some(" trunk ")), "trunk");
+    assert_eq!(GitHubService::default_pr_base(None), "main");
+}
+
 #[test]
 fn get_branch_diffs_between_branches() {
     let td = TempDir::new().unwrap();
@@ -369,6 +483,163 @@ fn get_branch_diffs_between_branches() {
     assert!(diffs.iter().any(|d| d.new_path.as_deref() == Some("b.txt")));
 }
 
+#[test]
+fn branch_diff_ignore_whitespace_drops_whitespace_only_changes() {
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let s = GitService::new();
+    write_file(&repo_path, "fmt.txt", "fn a() {\n    1\n}\n");
+    write_file(&repo_path, "real.txt", "one\n");
+    let _ = s.commit(&repo_path, "baseline").unwrap();
+
+    s.create_branch(&repo_path, "feature").unwrap();
+    s.checkout_branch(&repo_path, "feature").unwrap();
+    write_file(&repo_path, "fmt.txt", "fn a() {\n\t1  \n}\n");
+    write_file(&repo_path, "real.txt", "two\n");
+    let _ = s.commit(&repo_path, "reformat and edit").unwrap();
+
+    let target = || DiffTarget::Branch {
+        repo_path: Path::new(&repo_path),
+        branch_name: "feature",
+        base_branch: "main",
+    };
+    let diffs = s.get_diffs(target(), None).unwrap();
+    assert_eq!(diffs.len(), 2);
+
+    let diffs = s
+        .get_diffs_with_options(
+            target(),
+            None,
+            DiffContentOptions {
+                ignore_whitespace: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    assert_eq!(diffs.len(), 1);
+    assert_eq!(diffs[0].new_path.as_deref(), Some("real.txt"));
+}
+
+#[test]
+fn worktree_diff_ignore_whitespace_drops_whitespace_only_changes() {
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let s = GitService::new();
+    write_file(&repo_path, "fmt.txt", "a b\n");
+    write_file(&repo_path, "real.txt", "one\n");
+    let _ = s.commit(&repo_path, "baseline").unwrap();
+    s.create_branch(&repo_path, "feature").unwrap();
+
+    write_file(&repo_path, "fmt.txt", "a   b \n");
+    write_file(&repo_path, "real.txt", "two\n");
+
+    let base_commit = s.get_base_commit(&repo_path, "feature", "main").unwrap();
+    let diffs = s
+        .get_diffs_with_options(
+            DiffTarget::Worktree {
+                worktree_path: Path::new(&repo_path),
+                base_commit: &base_commit,
+            },
+            None,
+            DiffContentOptions {
+                ignore_whitespace: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    assert_eq!(diffs.len(), 1);
+    assert_eq!(diffs[0].new_path.as_deref(), Some("real.txt"));
+}
+
+#[test]
+fn worktree_diff_path_filter_includes_and_excludes_globs() {
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let s = GitService::new();
+    write_file(&repo_path, "README.md", "readme\n");
+    let _ = s.commit(&repo_path, "baseline").unwrap();
+    s.create_branch(&repo_path, "feature").unwrap();
+
+    write_file(&repo_path, "README.md", "readme v2\n");
+    write_file(&repo_path, "src/lib.rs", "pub fn a() {}\n");
+    write_file(&repo_path, "src/Cargo.lock", "# lock\n");
+
+    let base_commit = s.get_base_commit(&repo_path, "feature", "main").unwrap();
+    let filtered_paths = |globs: &[&str]| {
+        let mut paths: Vec<String> = s
+            .get_diffs_with_options(
+                DiffTarget::Worktree {
+                    worktree_path: Path::new(&repo_path),
+                    base_commit: &base_commit,
+                },
+                None,
+                DiffContentOptions {
+                    path_filter: Some(DiffPathFilter::new(globs).unwrap()),
+                    ..Default::default()
+                },
+            )
+            .unwrap()
+            .iter()
+            .map(GitService::diff_path)
+            .collect();
+        paths.sort();
+        paths
+    };
+
+    assert_eq!(
+        filtered_paths(&["src/**", "!**/*.lock"]),
+        vec!["src/lib.rs"]
+    );
+    assert_eq!(
+        filtered_paths(&["!**/*.lock"]),
+        vec!["README.md", "src/lib.rs"]
+    );
+}
+
+#[test]
+fn diff_path_filter_rejects_invalid_glob() {
+    assert!(DiffPathFilter::new(&["src/[a"]).is_err());
+}
+
+#[test]
+fn blame_attributes_committed_and_uncommitted_lines() {
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let s = GitService::new();
+    write_file(&repo_path, "notes.txt", "first\nsecond\n");
+    let _ = s.commit(&repo_path, "add notes").unwrap();
+    write_file(&repo_path, "notes.txt", "first\nsecond\nthird\n");
+
+    let lines = s.blame(&repo_path, "notes.txt").unwrap();
+    assert_eq!(lines.len(), 3);
+    assert_eq!(lines[0].summary.as_deref(), Some("add notes"));
+    assert_eq!(lines[0].author.as_deref(), Some("Test User"));
+    assert!(lines[1].commit_id.is_some());
+    assert_eq!(lines[2].content, "third");
+    assert!(lines[2].commit_id.is_none());
+
+    assert!(s.blame(&repo_path, "../outside.txt").is_err());
+    assert!(s.blame(&repo_path, "missing.txt").is_err());
+}
+
+#[cfg(unix)]
+#[test]
+fn blame_refuses_symlinks_leading_outside_the_worktree() {
+    use std::os::unix::fs::symlink;
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let s = GitService::new();
+    let outside = td.path().join("secret.txt");
+    fs::write(&outside, "secret\n").unwrap();
+    symlink(&outside, repo_path.join("escape.txt")).unwrap();
+    write_file(&repo_path, "notes.txt", "first\n");
+    symlink("notes.txt", repo_path.join("alias.txt")).unwrap();
+
+    assert!(s.blame(&repo_path, "escape.txt").is_err());
+    let lines = s.blame(&repo_path, "alias.txt").unwrap();
+    assert_eq!(lines[0].content, "first");
+}
+
 #[test]
 fn worktree_diff_respects_path_filter() {
     // Use git CLI status diff under the hood
@@ -644,3 +915,44 @@ fn squash_merge_libgit2_sets_author_without_user() {
         assert_eq!(email.as_deref(), Some("noreply@vibekanban.com"));
     }
 }
+
+#[test]
+fn clone_with_progress_copies_local_repo() {
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let s = GitService::new();
+    write_file(&repo_path, "a.txt", "a\n");
+    s.commit(&repo_path, "add a").unwrap();
+
+    let dest = td.path().join("clone");
+    let url = repo_path.to_string_lossy().to_string();
+    s.clone_with_progress(&url, &dest, None, |_| {}).unwrap();
+
+    assert_eq!(fs::read_to_string(dest.join("a.txt")).unwrap(), "a\n");
+    assert_eq!(s.get_head_info(&dest).unwrap().branch, "main");
+}
+
+#[test]
+fn clone_with_progress_missing_source_fails() {
+    let td = TempDir::new().unwrap();
+    let s = GitService::new();
+    let missing = td.path().join("missing").to_string_lossy().to_string();
+    let dest = td.path().join("clone");
+    let res = s.clone_with_progress(&missing, &dest, None, |_| {});
+    assert!(res.is_err());
+    assert!(!dest.exists());
+}
+
+#[test]
+fn clone_with_progress_failure_leaves_empty_destination_empty() {
+    let td = TempDir::new().unwrap();
+    let s = GitService::new();
+    let missing = td.path().join("missing").to_string_lossy().to_string();
+    let dest = td.path().join("clone");
+    fs::create_dir_all(&dest).unwrap();
+
+    let res = s.clone_with_progress(&missing, &dest, None, |_| {});
+    assert!(res.is_err());
+    assert!(dest.is_dir());
+    assert_eq!(fs::read_dir(&dest).unwrap().count(), 0);
+}
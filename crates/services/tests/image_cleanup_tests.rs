@@ -0,0 +1,113 @@
+//! Orphaned Image Tests
+//!
+//! These tests cover which unattached images the orphan cleanup considers safe to delete.
+
+use chrono::{Duration, Utc};
+use db::models::{
+    draft::{Draft, DraftType, UpsertDraft},
+    image::{CreateImage, Image},
+    project::{CreateProject, Project},
+    task::{CreateTask, Task},
+    task_attempt::{CreateTaskAttempt, TaskAttempt},
+};
+use executors::executors::BaseCodingAgent;
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+async fn create_test_attempt(pool: &SqlitePool) -> Result<TaskAttempt, Box<dyn std::error::Error>> {
+    let temp_dir = tempfile::tempdir()?;
+    let project = Project::create(
+        pool,
+        &CreateProject {
+            name: "Test Project".to_string(),
+            git_repo_path: temp_dir.path().to_string_lossy().to_string(),
+            use_existing_repo: false,
+            git_url: None,
+            setup_script: None,
+            dev_script: None,
+            cleanup_script: None,
+            copy_files: None,
+            worktree_dir: None,
+            push_remote: None,
+            pr_remote: None,
+            always_run_cleanup: false,
+            setup_cache_files: None,
+            setup_cache_artifacts: None,
+            script_language: None,
+            preview_url_pattern: None,
+            env_file: None,
+            env_file_required: false,
+            dev_scripts: Vec::new(),
+            reuse_worktree: false,
+        },
+        Uuid::new_v4(),
+    )
+    .await?;
+    let task = Task::create(
+        pool,
+        &CreateTask::from_title_description(project.id, "Test Task".to_string(), None),
+        Uuid::new_v4(),
+    )
+    .await?;
+    let task_attempt = TaskAttempt::create(
+        pool,
+        &CreateTaskAttempt {
+            executor: BaseCodingAgent::ClaudeCode,
+            base_branch: "main".to_string(),
+            branch: "test-branch".to_string(),
+        },
+        Uuid::new_v4(),
+        task.id,
+    )
+    .await?;
+    Ok(task_attempt)
+}
+
+async fn create_image(pool: &SqlitePool, name: &str) -> Result<Image, sqlx::Error> {
+    Image::create(
+        pool,
+        &CreateImage {
+            file_path: format!("{name}.png"),
+            original_name: format!("{name}.png"),
+            mime_type: Some("image/png".to_string()),
+            size_bytes: 1,
+            hash: name.to_string(),
+        },
+    )
+    .await
+}
+
+#[sqlx::test(migrations = "../db/migrations")]
+async fn test_orphans_exclude_recent_and_draft_images(
+    pool: SqlitePool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let attempt = create_test_attempt(&pool).await?;
+    let unused = create_image(&pool, "unused").await?;
+    let in_draft = create_image(&pool, "in-draft").await?;
+    Draft::upsert(
+        &pool,
+        &UpsertDraft {
+            task_attempt_id: attempt.id,
+            draft_type: DraftType::FollowUp,
+            retry_process_id: None,
+            prompt: "see the screenshot".to_string(),
+            queued: false,
+            variant: None,
+            image_ids: Some(vec![in_draft.id]),
+        },
+    )
+    .await?;
+
+    // Nothing is old enough yet
+    assert!(
+        Image::find_orphaned_images(&pool, Utc::now() - Duration::hours(1))
+            .await?
+            .is_empty()
+    );
+
+    let orphans = Image::find_orphaned_images(&pool, Utc::now() + Duration::hours(1)).await?;
+    let orphan_ids: Vec<Uuid> = orphans.iter().map(|image| image.id).collect();
+    assert_eq!(orphan_ids, vec![unused.id]);
+
+    Ok(())
+}
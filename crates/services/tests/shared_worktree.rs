@@ -0,0 +1,277 @@
+//! Shared Worktree Tests
+//!
+//! These tests cover projects that reuse one worktree for every attempt of a task: switching
+//! the worktree between attempt branches, and keeping it until its last attempt is cleaned up.
+
+use std::{
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use db::models::{
+    project::{CreateProject, Project},
+    task::{CreateTask, Task},
+    task_attempt::{CreateTaskAttempt, TaskAttempt},
+};
+use executors::executors::BaseCodingAgent;
+use services::services::{
+    file_review::{AttemptFiles, FileReviewService},
+    git::{DiffTarget, GitService},
+    worktree_manager::WorktreeManager,
+};
+use sqlx::SqlitePool;
+use tempfile::TempDir;
+use uuid::Uuid;
+
+fn write_file<P: AsRef<Path>>(base: P, rel: &str, content: &str) {
+    let path = base.as_ref().join(rel);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).unwrap();
+    }
+    let mut f = fs::File::create(&path).unwrap();
+    f.write_all(content.as_bytes()).unwrap();
+}
+
+/// Repository with `deps/` ignored and two attempt branches off `main`, plus a worktree on
+/// the first attempt's branch
+async fn init_shared_worktree(root: &TempDir) -> (PathBuf, PathBuf) {
+    let repo_path = root.path().join("repo");
+    let git = GitService::new();
+    git.initialize_repo_with_main_branch(&repo_path).unwrap();
+    git.configure_user(&repo_path, "Test User", "test@example.com")
+        .unwrap();
+    git.checkout_branch(&repo_path, "main").unwrap();
+    write_file(&repo_path, ".gitignore", "deps/\n");
+    git.commit(&repo_path, "ignore deps").unwrap();
+
+    WorktreeManager::create_branch(&repo_path, "attempt-a", "main")
+        .await
+        .unwrap();
+    WorktreeManager::create_branch(&repo_path, "attempt-b", "main")
+        .await
+        .unwrap();
+
+    let worktree_path = root.path().join("worktrees").join("shared");
+    WorktreeManager::ensure_worktree_exists(&repo_path, "attempt-a", &worktree_path)
+        .await
+        .unwrap();
+    (repo_path, worktree_path)
+}
+
+#[tokio::test]
+async fn switch_shared_worktree_keeps_ignored_files() {
+    let root = TempDir::new().unwrap();
+    let (repo_path, worktree_path) = init_shared_worktree(&root).await;
+    write_file(&worktree_path, "deps/installed.txt", "cached");
+
+    WorktreeManager::switch_shared_worktree(&repo_path, "attempt-b", &worktree_path)
+        .await
+        .unwrap();
+
+    let head = GitService::new().get_head_info(&worktree_path).unwrap();
+    assert_eq!(head.branch, "attempt-b");
+    assert_eq!(
+        fs::read_to_string(worktree_path.join("deps/installed.txt")).unwrap(),
+        "cached"
+    );
+}
+
+#[tokio::test]
+async fn switch_shared_worktree_refuses_uncommitted_changes() {
+    let root = TempDir::new().unwrap();
+    let (repo_path, worktree_path) = init_shared_worktree(&root).await;
+    write_file(&worktree_path, ".gitignore", "deps/\nbuild/\n");
+
+    let result =
+        WorktreeManager::switch_shared_worktree(&repo_path, "attempt-b", &worktree_path).await;
+
+    assert!(result.is_err());
+    let head = GitService::new().get_head_info(&worktree_path).unwrap();
+    assert_eq!(head.branch, "attempt-a");
+    assert_eq!(
+        fs::read_to_string(worktree_path.join(".gitignore")).unwrap(),
+        "deps/\nbuild/\n"
+    );
+}
+
+#[tokio::test]
+async fn switch_shared_worktree_recreates_a_missing_worktree() {
+    let root = TempDir::new().unwrap();
+    let (repo_path, worktree_path) = init_shared_worktree(&root).await;
+    WorktreeManager::cleanup_worktree(&worktree_path, Some(&repo_path))
+        .await
+        .unwrap();
+    assert!(!worktree_path.exists());
+
+    WorktreeManager::switch_shared_worktree(&repo_path, "attempt-b", &worktree_path)
+        .await
+        .unwrap();
+
+    let head = GitService::new().get_head_info(&worktree_path).unwrap();
+    assert_eq!(head.branch, "attempt-b");
+}
+
+async fn create_test_task(pool: &SqlitePool) -> Result<Task, Box<dyn std::error::Error>> {
+    let temp_dir = tempfile::tempdir()?;
+    let project = Project::create(
+        pool,
+        &CreateProject {
+            name: "Test Project".to_string(),
+            git_repo_path: temp_dir.path().to_string_lossy().to_string(),
+            use_existing_repo: false,
+            git_url: None,
+            setup_script: None,
+            dev_script: None,
+            cleanup_script: None,
+            copy_files: None,
+            worktree_dir: None,
+            push_remote: None,
+            pr_remote: None,
+            always_run_cleanup: false,
+            setup_cache_files: None,
+            setup_cache_artifacts: None,
+            script_language: None,
+            preview_url_pattern: None,
+            env_file: None,
+            env_file_required: false,
+            dev_scripts: Vec::new(),
+            reuse_worktree: true,
+        },
+        Uuid::new_v4(),
+    )
+    .await?;
+    let task = Task::create(
+        pool,
+        &CreateTask::from_title_description(project.id, "Test Task".to_string(), None),
+        Uuid::new_v4(),
+    )
+    .await?;
+    Ok(task)
+}
+
+async fn create_test_attempt(
+    pool: &SqlitePool,
+    task_id: Uuid,
+    branch: &str,
+) -> Result<TaskAttempt, Box<dyn std::error::Error>> {
+    let task_attempt = TaskAttempt::create(
+        pool,
+        &CreateTaskAttempt {
+            executor: BaseCodingAgent::ClaudeCode,
+            base_branch: "main".to_string(),
+            branch: branch.to_string(),
+        },
+        Uuid::new_v4(),
+        task_id,
+    )
+    .await?;
+    Ok(task_attempt)
+}
+
+#[sqlx::test(migrations = "../db/migrations")]
+async fn test_shared_worktree_is_kept_until_its_last_attempt(
+    pool: SqlitePool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let task = create_test_task(&pool).await?;
+    let first = create_test_attempt(&pool, task.id, "attempt-a").await?;
+    let container_ref = "/tmp/worktrees/shared";
+    TaskAttempt::update_container_ref(&pool, first.id, container_ref).await?;
+
+    // A second attempt picks up the first attempt's worktree
+    let second = create_test_attempt(&pool, task.id, "attempt-b").await?;
+    assert_eq!(
+        TaskAttempt::find_reusable_container_ref(&pool, task.id, second.id)
+            .await?
+            .as_deref(),
+        Some(container_ref)
+    );
+    TaskAttempt::update_container_ref(&pool, second.id, container_ref).await?;
+
+    assert!(TaskAttempt::container_ref_shared(&pool, container_ref, first.id).await?);
+    assert!(TaskAttempt::container_ref_shared(&pool, container_ref, second.id).await?);
+
+    // Cleaning up one attempt leaves the other as the worktree's last user
+    TaskAttempt::mark_worktree_deleted(&pool, second.id).await?;
+    assert!(!TaskAttempt::container_ref_shared(&pool, container_ref, first.id).await?);
+
+    TaskAttempt::mark_worktree_deleted(&pool, first.id).await?;
+    let third = create_test_attempt(&pool, task.id, "attempt-c").await?;
+    assert!(
+        TaskAttempt::find_reusable_container_ref(&pool, task.id, third.id)
+            .await?
+            .is_none()
+    );
+
+    Ok(())
+}
+
+#[sqlx::test(migrations = "../db/migrations")]
+async fn test_attempt_off_the_shared_worktree_is_read_from_its_branch(
+    pool: SqlitePool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let root = TempDir::new()?;
+    let (repo_path, worktree_path) = init_shared_worktree(&root).await;
+    let git = GitService::new();
+    let task = create_test_task(&pool).await?;
+    let first = create_test_attempt(&pool, task.id, "attempt-a").await?;
+    create_test_attempt(&pool, task.id, "attempt-b").await?;
+
+    write_file(&worktree_path, "a.txt", "first attempt");
+    git.commit(&worktree_path, "first attempt").unwrap();
+    WorktreeManager::switch_shared_worktree(&repo_path, "attempt-b", &worktree_path).await?;
+    write_file(&worktree_path, "b.txt", "second attempt");
+    git.commit(&worktree_path, "second attempt").unwrap();
+
+    // The worktree now only holds the second attempt's changes
+    let base_commit = git.get_base_commit(&repo_path, "attempt-a", "main")?;
+    let worktree_paths: Vec<String> = git
+        .get_diffs(
+            DiffTarget::Worktree {
+                worktree_path: &worktree_path,
+                base_commit: &base_commit,
+            },
+            None,
+        )?
+        .iter()
+        .map(GitService::diff_path)
+        .collect();
+    assert_eq!(worktree_paths, vec!["b.txt".to_string()]);
+
+    let branch_paths: Vec<String> = git
+        .get_diffs(
+            DiffTarget::Branch {
+                repo_path: &repo_path,
+                branch_name: "attempt-a",
+                base_branch: "main",
+            },
+            None,
+        )?
+        .iter()
+        .map(GitService::diff_path)
+        .collect();
+    assert_eq!(branch_paths, vec!["a.txt".to_string()]);
+
+    // Reviews of the first attempt hold its branch's contents, not the worktree's
+    let files = AttemptFiles::Branch {
+        repo_path: &repo_path,
+        branch_name: "attempt-a",
+    };
+    FileReviewService::set_reviewed(&pool, &git, first.id, files, "a.txt", true).await?;
+    assert_eq!(
+        FileReviewService::reviewed_paths(&pool, &git, first.id, files).await?,
+        vec!["a.txt".to_string()]
+    );
+    assert!(
+        FileReviewService::reviewed_paths(
+            &pool,
+            &git,
+            first.id,
+            AttemptFiles::Worktree(&worktree_path)
+        )
+        .await?
+        .is_empty()
+    );
+
+    Ok(())
+}
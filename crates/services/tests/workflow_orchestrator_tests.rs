@@ -37,11 +37,23 @@ async fn create_test_project(pool: &SqlitePool) -> Result<Project, Box<dyn std::
             name: "Test Project".to_string(),
             git_repo_path: temp_dir.path().to_string_lossy().to_string(),
             use_existing_repo: false,
+            git_url: None,
             setup_script: None,
             dev_script: None,
             cleanup_script: None,
             copy_files: None,
             worktree_dir: None,
+            push_remote: None,
+            pr_remote: None,
+            always_run_cleanup: false,
+            setup_cache_files: None,
+            setup_cache_artifacts: None,
+            script_language: None,
+            preview_url_pattern: None,
+            env_file: None,
+            env_file_required: false,
+            dev_scripts: Vec::new(),
+            reuse_worktree: false,
         },
         project_id,
     )
@@ -148,6 +160,7 @@ async fn test_transition_always_succeeds(pool: SqlitePool) -> Result<(), Box<dyn
             agent_id: Some(agent.id),
             station_prompt: Some("Test prompt".to_string()),
             output_context_keys: None,
+            output_schema: None,
         },
         station1_id,
     )
@@ -166,6 +179,7 @@ async fn test_transition_always_succeeds(pool: SqlitePool) -> Result<(), Box<dyn
             agent_id: Some(agent.id),
             station_prompt: Some("Test prompt 2".to_string()),
             output_context_keys: None,
+            output_schema: None,
         },
         station2_id,
     )
@@ -211,6 +225,7 @@ async fn test_transition_always_succeeds(pool: SqlitePool) -> Result<(), Box<dyn
             station_id: station1.id,
             status: "completed".to_string(),
             execution_process_id: None,
+            transition_id: None,
         },
         station_execution_id,
     )
@@ -228,7 +243,7 @@ async fn test_transition_always_succeeds(pool: SqlitePool) -> Result<(), Box<dyn
         )
         .await?;
 
-    assert_eq!(next_station, Some(station2_id));
+    assert_eq!(next_station.map(|t| t.target_station_id), Some(station2_id));
 
     Ok(())
 }
@@ -268,6 +283,7 @@ async fn test_transition_success_condition_with_completed_station(
             agent_id: Some(agent.id),
             station_prompt: None,
             output_context_keys: None,
+            output_schema: None,
         },
         station1_id,
     )
@@ -286,6 +302,7 @@ async fn test_transition_success_condition_with_completed_station(
             agent_id: Some(agent.id),
             station_prompt: None,
             output_context_keys: None,
+            output_schema: None,
         },
         station2_id,
     )
@@ -331,6 +348,7 @@ async fn test_transition_success_condition_with_completed_station(
             station_id: station1.id,
             status: "completed".to_string(),
             execution_process_id: None,
+            transition_id: None,
         },
         station_execution_id,
     )
@@ -348,7 +366,7 @@ async fn test_transition_success_condition_with_completed_station(
         )
         .await?;
 
-    assert_eq!(next_station, Some(station2_id));
+    assert_eq!(next_station.map(|t| t.target_station_id), Some(station2_id));
 
     Ok(())
 }
@@ -388,6 +406,7 @@ async fn test_transition_success_condition_with_failed_station(
             agent_id: Some(agent.id),
             station_prompt: None,
             output_context_keys: None,
+            output_schema: None,
         },
         station1_id,
     )
@@ -406,6 +425,7 @@ async fn test_transition_success_condition_with_failed_station(
             agent_id: Some(agent.id),
             station_prompt: None,
             output_context_keys: None,
+            output_schema: None,
         },
         station2_id,
     )
@@ -451,6 +471,7 @@ async fn test_transition_success_condition_with_failed_station(
             station_id: station1.id,
             status: "failed".to_string(),
             execution_process_id: None,
+            transition_id: None,
         },
         station_execution_id,
     )
@@ -513,6 +534,7 @@ async fn test_conditional_transition_evaluation(
             agent_id: Some(agent.id),
             station_prompt: None,
             output_context_keys: Some(r#"["test_key"]"#.to_string()),
+            output_schema: None,
         },
         station1_id,
     )
@@ -531,6 +553,7 @@ async fn test_conditional_transition_evaluation(
             agent_id: Some(agent.id),
             station_prompt: None,
             output_context_keys: None,
+            output_schema: None,
         },
         station2_id,
     )
@@ -578,6 +601,7 @@ async fn test_conditional_transition_evaluation(
             station_id: station1.id,
             status: "completed".to_string(),
             execution_process_id: None,
+            transition_id: None,
         },
         station_execution_id,
     )
@@ -609,7 +633,7 @@ async fn test_conditional_transition_evaluation(
         )
         .await?;
 
-    assert_eq!(next_station, Some(station2_id));
+    assert_eq!(next_station.map(|t| t.target_station_id), Some(station2_id));
 
     Ok(())
 }
@@ -654,6 +678,7 @@ async fn test_gather_context_data_from_multiple_stations(
             agent_id: Some(agent.id),
             station_prompt: None,
             output_context_keys: Some(r#"["key1"]"#.to_string()),
+            output_schema: None,
         },
         station1_id,
     )
@@ -672,6 +697,7 @@ async fn test_gather_context_data_from_multiple_stations(
             agent_id: Some(agent.id),
             station_prompt: None,
             output_context_keys: Some(r#"["key2"]"#.to_string()),
+            output_schema: None,
         },
         station2_id,
     )
@@ -700,6 +726,7 @@ async fn test_gather_context_data_from_multiple_stations(
             station_id: station1.id,
             status: "completed".to_string(),
             execution_process_id: None,
+            transition_id: None,
         },
         se1_id,
     )
@@ -726,6 +753,7 @@ async fn test_gather_context_data_from_multiple_stations(
             station_id: station2.id,
             status: "completed".to_string(),
             execution_process_id: None,
+            transition_id: None,
         },
         se2_id,
     )
@@ -758,6 +786,129 @@ async fn test_gather_context_data_from_multiple_stations(
     Ok(())
 }
 
+// ============================================================================
+// WORKFLOW START TESTS
+// ============================================================================
+
+async fn create_empty_workflow(
+    pool: &SqlitePool,
+    project_id: Uuid,
+) -> Result<Workflow, Box<dyn std::error::Error>> {
+    let workflow = Workflow::create(
+        pool,
+        CreateWorkflow {
+            project_id,
+            name: "Start Workflow".to_string(),
+            description: None,
+        },
+        Uuid::new_v4(),
+    )
+    .await?;
+    Ok(workflow)
+}
+
+#[sqlx::test(migrations = "../db/migrations")]
+async fn test_concurrent_starts_share_one_active_execution(
+    pool: SqlitePool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let project = create_test_project(&pool).await?;
+    let task = create_test_task(&pool, project.id).await?;
+    let workflow = create_empty_workflow(&pool, project.id).await?;
+    // Each start creates its own attempt before the execution
+    let attempt1 = create_test_task_attempt(&pool, task.id).await?;
+    let attempt2 = create_test_task_attempt(&pool, task.id).await?;
+
+    let orchestrator = WorkflowOrchestrator::new(DBService { pool: pool.clone() });
+    let (first, second) = tokio::join!(
+        orchestrator.create_or_get_active_execution(workflow.id, task.id, attempt1.id),
+        orchestrator.create_or_get_active_execution(workflow.id, task.id, attempt2.id),
+    );
+    let (first, first_created) = first?;
+    let (second, second_created) = second?;
+
+    assert_eq!(first.id, second.id);
+    assert!(first_created != second_created);
+    assert_eq!(
+        WorkflowExecution::find_by_task(&pool, task.id).await?.len(),
+        1
+    );
+
+    Ok(())
+}
+
+#[sqlx::test(migrations = "../db/migrations")]
+async fn test_one_active_execution_per_workflow_and_task(
+    pool: SqlitePool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let project = create_test_project(&pool).await?;
+    let task = create_test_task(&pool, project.id).await?;
+    let workflow = create_empty_workflow(&pool, project.id).await?;
+    let attempt1 = create_test_task_attempt(&pool, task.id).await?;
+    let attempt2 = create_test_task_attempt(&pool, task.id).await?;
+
+    let create = |task_attempt_id: Uuid, status: &str| {
+        WorkflowExecution::create(
+            &pool,
+            CreateWorkflowExecution {
+                workflow_id: workflow.id,
+                task_id: task.id,
+                task_attempt_id: Some(task_attempt_id),
+                status: status.to_string(),
+            },
+            Uuid::new_v4(),
+        )
+    };
+
+    let first = create(attempt1.id, "running").await?;
+    WorkflowExecution::update_status(&pool, first.id, "paused").await?;
+
+    // A paused execution still holds the slot, even for another attempt of the task
+    let err = create(attempt2.id, "running").await.unwrap_err();
+    assert!(matches!(err, sqlx::Error::Database(ref e) if e.is_unique_violation()));
+
+    // Finished executions don't
+    WorkflowExecution::update_status(&pool, first.id, "cancelled").await?;
+    create(attempt2.id, "running").await?;
+
+    Ok(())
+}
+
+#[sqlx::test(migrations = "../db/migrations")]
+async fn test_restart_after_cancel_creates_new_execution(
+    pool: SqlitePool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let project = create_test_project(&pool).await?;
+    let task = create_test_task(&pool, project.id).await?;
+    let workflow = create_empty_workflow(&pool, project.id).await?;
+    let attempt1 = create_test_task_attempt(&pool, task.id).await?;
+    let attempt2 = create_test_task_attempt(&pool, task.id).await?;
+
+    let orchestrator = WorkflowOrchestrator::new(DBService { pool: pool.clone() });
+    let (first, created) = orchestrator
+        .create_or_get_active_execution(workflow.id, task.id, attempt1.id)
+        .await?;
+    assert!(created);
+
+    // Without a restart, starting again returns the active execution
+    let (again, created) = orchestrator
+        .create_or_get_active_execution(workflow.id, task.id, attempt2.id)
+        .await?;
+    assert!(!created);
+    assert_eq!(again.id, first.id);
+    assert_eq!(again.task_attempt_id, Some(attempt1.id));
+
+    // `force_restart` cancels the active execution before starting a new one
+    WorkflowExecution::update_status(&pool, first.id, "cancelled").await?;
+    let (restarted, created) = orchestrator
+        .create_or_get_active_execution(workflow.id, task.id, attempt2.id)
+        .await?;
+    assert!(created);
+    assert_ne!(restarted.id, first.id);
+    assert_eq!(restarted.task_attempt_id, Some(attempt2.id));
+
+    Ok(())
+}
+
 // ============================================================================
 // ERROR HANDLING TESTS
 // ============================================================================
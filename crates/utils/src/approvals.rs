@@ -50,6 +50,8 @@ pub enum ApprovalStatus {
         reason: Option<String>,
     },
     TimedOut,
+    /// The execution was stopped while waiting for a decision
+    Cancelled,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
@@ -0,0 +1,167 @@
+use std::{collections::HashMap, fmt};
+
+/// A malformed line in a `.env`-style file. Only the line number is kept so values, which
+/// are usually secrets, never end up in logs or API responses.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnvFileError {
+    pub line: usize,
+    pub reason: &'static str,
+}
+
+impl fmt::Display for EnvFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.reason)
+    }
+}
+
+impl std::error::Error for EnvFileError {}
+
+fn is_valid_key(key: &str) -> bool {
+    let mut chars = key.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Anything after a closing quote must be whitespace or a comment
+fn check_trailing(rest: &str, line: usize) -> Result<(), EnvFileError> {
+    let rest = rest.trim_start();
+    if rest.is_empty() || rest.starts_with('#') {
+        Ok(())
+    } else {
+        Err(EnvFileError {
+            line,
+            reason: "unexpected characters after closing quote",
+        })
+    }
+}
+
+fn parse_value(raw: &str, line: usize) -> Result<String, EnvFileError> {
+    let unterminated = EnvFileError {
+        line,
+        reason: "unterminated quoted value",
+    };
+
+    if let Some(body) = raw.strip_prefix('\'') {
+        // Single quotes are literal
+        let end = body.find('\'').ok_or(unterminated)?;
+        check_trailing(&body[end + 1..], line)?;
+        return Ok(body[..end].to_string());
+    }
+
+    if let Some(body) = raw.strip_prefix('"') {
+        let mut value = String::new();
+        let mut chars = body.char_indices();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => {
+                    check_trailing(&body[i + 1..], line)?;
+                    return Ok(value);
+                }
+                '\\' => match chars.next() {
+                    Some((_, 'n')) => value.push('\n'),
+                    Some((_, 'r')) => value.push('\r'),
+                    Some((_, 't')) => value.push('\t'),
+                    Some((_, '"')) => value.push('"'),
+                    Some((_, '\\')) => value.push('\\'),
+                    Some((_, other)) => {
+                        value.push('\\');
+                        value.push(other);
+                    }
+                    None => return Err(unterminated),
+                },
+                _ => value.push(c),
+            }
+        }
+        return Err(unterminated);
+    }
+
+    // Unquoted: a `#` starts a comment only after whitespace, so `a#b` stays intact
+    let mut end = raw.len();
+    let mut prev_is_space = true;
+    for (i, c) in raw.char_indices() {
+        if c == '#' && prev_is_space {
+            end = i;
+            break;
+        }
+        prev_is_space = c.is_whitespace();
+    }
+    Ok(raw[..end].trim().to_string())
+}
+
+/// Parse a `.env`-style file: `KEY=value` lines with optional `export ` prefixes, `#`
+/// comments, and single- (literal) or double-quoted (with `\n`, `\t`, `\"` and `\\` escapes)
+/// values. Later assignments win. Variables are not expanded.
+pub fn parse_env_file(contents: &str) -> Result<HashMap<String, String>, EnvFileError> {
+    let mut vars = HashMap::new();
+    for (index, line) in contents.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").unwrap_or(line);
+
+        let (key, raw_value) = line.split_once('=').ok_or(EnvFileError {
+            line: line_number,
+            reason: "expected KEY=value",
+        })?;
+        let key = key.trim();
+        if !is_valid_key(key) {
+            return Err(EnvFileError {
+                line: line_number,
+                reason: "invalid variable name",
+            });
+        }
+
+        let value = parse_value(raw_value.trim_start(), line_number)?;
+        vars.insert(key.to_string(), value);
+    }
+    Ok(vars)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_plain_and_exported_assignments() {
+        let vars = parse_env_file("# comment\n\nFOO=bar\nexport BAZ = qux \nEMPTY=\n").unwrap();
+        assert_eq!(vars.get("FOO").map(String::as_str), Some("bar"));
+        assert_eq!(vars.get("BAZ").map(String::as_str), Some("qux"));
+        assert_eq!(vars.get("EMPTY").map(String::as_str), Some(""));
+        assert_eq!(vars.len(), 3);
+    }
+
+    #[test]
+    fn handles_quotes_and_comments() {
+        let vars = parse_env_file(
+            "A=\"hello world\" # trailing\nB='lit\\n $x'\nC=\"line\\nnext \\\"q\\\"\"\nD=a#b # c\nE=\"#not comment\"",
+        )
+        .unwrap();
+        assert_eq!(vars["A"], "hello world");
+        assert_eq!(vars["B"], "lit\\n $x");
+        assert_eq!(vars["C"], "line\nnext \"q\"");
+        assert_eq!(vars["D"], "a#b");
+        assert_eq!(vars["E"], "#not comment");
+
+        assert!(parse_env_file("A='x'y").is_err());
+    }
+
+    #[test]
+    fn later_assignments_win() {
+        let vars = parse_env_file("KEY=first\nKEY=second").unwrap();
+        assert_eq!(vars["KEY"], "second");
+    }
+
+    #[test]
+    fn errors_report_line_without_value() {
+        let err = parse_env_file("OK=1\nSECRET=\"s3cr3t").unwrap_err();
+        assert_eq!(err.line, 2);
+        assert!(!err.to_string().contains("s3cr3t"));
+
+        assert_eq!(parse_env_file("no equals sign").unwrap_err().line, 1);
+        assert_eq!(parse_env_file("1BAD=x").unwrap_err().line, 1);
+    }
+}
@@ -10,6 +10,47 @@ pub fn is_valid_branch_prefix(prefix: &str) -> bool {
     git2::Branch::name_is_valid(&format!("{prefix}/x")).unwrap_or_default()
 }
 
+/// Branch naming template matching the original `{prefix}/{short-id}-{slug}` scheme
+pub const DEFAULT_BRANCH_TEMPLATE: &str = "{prefix}/{attempt_id}-{slug}";
+
+/// Render a branch name from `template`, substituting `{prefix}`, `{attempt_id}`,
+/// `{task_id}` and `{slug}`. Empty path segments (e.g. from an empty prefix) are dropped.
+/// Every attempt needs its own branch, so `-{attempt_id}` is appended when the template
+/// does not reference the attempt id.
+pub fn render_branch_template(
+    template: &str,
+    prefix: &str,
+    attempt_id: &str,
+    task_id: &str,
+    slug: &str,
+) -> String {
+    let mut rendered = template
+        .replace("{prefix}", prefix)
+        .replace("{attempt_id}", attempt_id)
+        .replace("{task_id}", task_id)
+        .replace("{slug}", slug);
+    if !template.contains("{attempt_id}") {
+        rendered.push('-');
+        rendered.push_str(attempt_id);
+    }
+    rendered
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// True if `template` only uses known placeholders and renders to a legal branch name
+pub fn is_valid_branch_template(template: &str) -> bool {
+    if template.trim().is_empty() {
+        return false;
+    }
+    let rendered = render_branch_template(template, "vk", "a1b2", "c3d4", "task-title");
+    !rendered.contains(['{', '}'])
+        && !rendered.starts_with('-')
+        && git2::Branch::name_is_valid(&rendered).unwrap_or_default()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -44,4 +85,31 @@ mod tests {
         assert!(!is_valid_branch_prefix("foo/"));
         assert!(!is_valid_branch_prefix(".foo"));
     }
+
+    #[test]
+    fn test_render_branch_template() {
+        assert_eq!(
+            render_branch_template(DEFAULT_BRANCH_TEMPLATE, "vk", "a1b2", "c3d4", "fix-login"),
+            "vk/a1b2-fix-login"
+        );
+        assert_eq!(
+            render_branch_template(DEFAULT_BRANCH_TEMPLATE, "", "a1b2", "c3d4", "fix-login"),
+            "a1b2-fix-login"
+        );
+        assert_eq!(
+            render_branch_template("{prefix}/{task_id}/{slug}", "vk", "a1b2", "c3d4", "fix"),
+            "vk/c3d4/fix-a1b2"
+        );
+    }
+
+    #[test]
+    fn test_branch_template_validation() {
+        assert!(is_valid_branch_template(DEFAULT_BRANCH_TEMPLATE));
+        assert!(is_valid_branch_template("{prefix}/{task_id}/{slug}"));
+        assert!(is_valid_branch_template("team/{attempt_id}"));
+        assert!(!is_valid_branch_template(""));
+        assert!(!is_valid_branch_template("{prefix} {slug}"));
+        assert!(!is_valid_branch_template("{prefix}/{unknown}"));
+        assert!(!is_valid_branch_template("{prefix}/{slug}.."));
+    }
 }
@@ -6,9 +6,11 @@ pub mod approvals;
 pub mod assets;
 pub mod browser;
 pub mod diff;
+pub mod env_file;
 pub mod git;
 pub mod log_msg;
 pub mod msg_store;
+pub mod pagination;
 pub mod path;
 pub mod port_file;
 pub mod response;
@@ -1,32 +1,214 @@
 use std::{
     collections::VecDeque,
-    sync::{Arc, RwLock},
+    sync::{Arc, LazyLock, RwLock},
+    time::{Duration, Instant},
 };
 
 use axum::response::sse::Event;
 use futures::{StreamExt, TryStreamExt, future};
+use json_patch::{Patch, PatchOperation, ReplaceOperation};
+use serde_json::Value;
 use tokio::{sync::broadcast, task::JoinHandle};
 use tokio_stream::wrappers::BroadcastStream;
 
 use crate::{log_msg::LogMsg, stream_lines::LinesStreamExt};
 
 // 100 MB Limit
-const HISTORY_BYTES: usize = 100000 * 1024;
+const DEFAULT_HISTORY_BYTES: usize = 100000 * 1024;
+
+/// History budget for stores created with `MsgStore::new`, overridable in megabytes with
+/// `MSG_STORE_HISTORY_MB`.
+static HISTORY_BYTES: LazyLock<usize> = LazyLock::new(|| {
+    std::env::var("MSG_STORE_HISTORY_MB")
+        .ok()
+        .and_then(|mb| mb.parse::<usize>().ok())
+        .filter(|mb| *mb > 0)
+        .map(|mb| mb * 1024 * 1024)
+        .unwrap_or(DEFAULT_HISTORY_BYTES)
+});
+
+/// Patches kept verbatim by a store with [`PatchRetention`] unless `EVENTS_HISTORY_MAX_PATCHES`
+/// says otherwise
+const DEFAULT_MAX_PATCHES: usize = 10_000;
+
+/// How many JSON patches a store keeps verbatim for late subscribers. Older patches are
+/// folded into a baseline document that is replayed first, so history stays bounded while
+/// still adding up to the current state.
+#[derive(Debug, Clone, Copy)]
+pub struct PatchRetention {
+    pub max_patches: usize,
+    /// Patches older than this are folded regardless of count
+    pub max_age: Option<Duration>,
+}
+
+impl PatchRetention {
+    /// Limits from `EVENTS_HISTORY_MAX_PATCHES` (default 10000) and
+    /// `EVENTS_HISTORY_MAX_AGE_SECS` (unset or 0 disables the age limit)
+    pub fn from_env() -> Self {
+        let env_number = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .and_then(|value| value.parse::<u64>().ok())
+                .filter(|value| *value > 0)
+        };
+        Self {
+            max_patches: env_number("EVENTS_HISTORY_MAX_PATCHES")
+                .map_or(DEFAULT_MAX_PATCHES, |max| max as usize),
+            max_age: env_number("EVENTS_HISTORY_MAX_AGE_SECS").map(Duration::from_secs),
+        }
+    }
+}
+
+pub const LOG_TRUNCATED_MARKER: &str =
+    "[log truncated: earlier output exceeded the history limit and was dropped]";
 
 #[derive(Clone)]
 struct StoredMsg {
     msg: LogMsg,
     bytes: usize,
+    pushed_at: Instant,
+}
+
+impl StoredMsg {
+    fn is_raw_output(&self) -> bool {
+        matches!(self.msg, LogMsg::Stdout(_) | LogMsg::Stderr(_))
+    }
 }
 
 struct Inner {
-    history: VecDeque<StoredMsg>,
+    /// Evicted raw output leaves a `None` hole so indices stay stable; holes are popped as
+    /// they reach the front and compacted away once they make up half the history.
+    history: VecDeque<Option<StoredMsg>>,
+    holes: usize,
     total_bytes: usize,
+    /// Index of the oldest entry that may still hold raw output
+    raw_cursor: usize,
+    truncated: bool,
+    /// State folded out of history by [`PatchRetention`]
+    baseline: Option<Value>,
+}
+
+impl Inner {
+    /// Drop the oldest raw stdout/stderr message, replacing the first one dropped with the
+    /// truncation marker. Returns false if no raw output is left to drop.
+    fn evict_oldest_raw(&mut self) -> bool {
+        while self.raw_cursor < self.history.len() {
+            let index = self.raw_cursor;
+            self.raw_cursor += 1;
+            let Some(stored) = self.history[index].take_if(|s| s.is_raw_output()) else {
+                continue;
+            };
+            self.total_bytes = self.total_bytes.saturating_sub(stored.bytes);
+            if !self.truncated {
+                self.truncated = true;
+                let marker = LogMsg::Stderr(LOG_TRUNCATED_MARKER.to_string());
+                let bytes = marker.approx_bytes();
+                self.total_bytes = self.total_bytes.saturating_add(bytes);
+                self.history[index] = Some(StoredMsg {
+                    msg: marker,
+                    bytes,
+                    pushed_at: stored.pushed_at,
+                });
+            } else {
+                self.holes += 1;
+            }
+            self.pop_front_holes();
+            if self.holes * 2 > self.history.len() {
+                self.compact();
+            }
+            return true;
+        }
+        false
+    }
+
+    /// Last resort once only structured messages remain
+    fn evict_oldest(&mut self) -> bool {
+        let Some(front) = self.history.pop_front() else {
+            return false;
+        };
+        self.raw_cursor = self.raw_cursor.saturating_sub(1);
+        match front {
+            Some(front) => self.total_bytes = self.total_bytes.saturating_sub(front.bytes),
+            None => self.holes -= 1,
+        }
+        self.pop_front_holes();
+        true
+    }
+
+    /// Fold the oldest messages into the baseline until the retention limits hold. Messages
+    /// other than JSON patches carry no state and are dropped.
+    fn fold_into_baseline(&mut self, retention: &PatchRetention, now: Instant) {
+        while let Some(Some(front)) = self.history.front() {
+            let expired = retention
+                .max_age
+                .is_some_and(|max_age| now.duration_since(front.pushed_at) > max_age);
+            if self.history.len() <= retention.max_patches && !expired {
+                break;
+            }
+            let Some(Some(stored)) = self.history.pop_front() else {
+                break;
+            };
+            self.raw_cursor = self.raw_cursor.saturating_sub(1);
+            self.total_bytes = self.total_bytes.saturating_sub(stored.bytes);
+            if let LogMsg::JsonPatch(patch) = stored.msg {
+                apply_leniently(
+                    self.baseline
+                        .get_or_insert_with(|| Value::Object(Default::default())),
+                    patch,
+                );
+            }
+            self.pop_front_holes();
+        }
+    }
+
+    fn pop_front_holes(&mut self) {
+        while matches!(self.history.front(), Some(None)) {
+            self.history.pop_front();
+            self.holes -= 1;
+            self.raw_cursor = self.raw_cursor.saturating_sub(1);
+        }
+    }
+
+    fn compact(&mut self) {
+        let cursor = self.raw_cursor.min(self.history.len());
+        self.raw_cursor = self.history.range(..cursor).flatten().count();
+        self.history.retain(Option::is_some);
+        self.holes = 0;
+    }
+}
+
+/// Apply a patch to the baseline even where earlier patches it depended on went missing:
+/// writes create missing parents and removals of absent values are ignored
+fn apply_leniently(doc: &mut Value, patch: Patch) {
+    for op in patch.0 {
+        if json_patch::patch(doc, std::slice::from_ref(&op)).is_ok() {
+            continue;
+        }
+        let (path, value) = match op {
+            PatchOperation::Add(op) => (op.path, op.value),
+            PatchOperation::Replace(op) => (op.path, op.value),
+            _ => continue,
+        };
+        let mut target = &mut *doc;
+        for token in path.tokens() {
+            if !target.is_object() {
+                *target = Value::Object(Default::default());
+            }
+            target = target
+                .as_object_mut()
+                .expect("target was just made an object")
+                .entry(token.decoded().into_owned())
+                .or_insert(Value::Null);
+        }
+        *target = value;
+    }
 }
 
 pub struct MsgStore {
     inner: RwLock<Inner>,
     sender: broadcast::Sender<LogMsg>,
+    history_limit: usize,
+    patch_retention: Option<PatchRetention>,
 }
 
 impl Default for MsgStore {
@@ -37,13 +219,38 @@ impl Default for MsgStore {
 
 impl MsgStore {
     pub fn new() -> Self {
+        Self::with_history_limit(*HISTORY_BYTES)
+    }
+
+    /// Keep roughly `history_limit` bytes of history for late subscribers. Once exceeded,
+    /// the oldest raw stdout/stderr is dropped first so normalized entries (JSON patches)
+    /// and session ids survive, and a single `LOG_TRUNCATED_MARKER` stderr message is left
+    /// where output went missing. Live subscribers always receive every message.
+    pub fn with_history_limit(history_limit: usize) -> Self {
         let (sender, _) = broadcast::channel(10000);
         Self {
             inner: RwLock::new(Inner {
                 history: VecDeque::with_capacity(32),
+                holes: 0,
                 total_bytes: 0,
+                raw_cursor: 0,
+                truncated: false,
+                baseline: None,
             }),
             sender,
+            history_limit,
+            patch_retention: None,
+        }
+    }
+
+    /// Like [`MsgStore::new`], but keeping at most `retention` worth of JSON patches and
+    /// folding older ones into a baseline. For long-lived stores of state patches such as
+    /// the event stream. The patch count bounds history instead of its size, since evicting
+    /// by size would drop patches without folding them.
+    pub fn with_patch_retention(retention: PatchRetention) -> Self {
+        Self {
+            patch_retention: Some(retention),
+            ..Self::with_history_limit(usize::MAX)
         }
     }
 
@@ -52,15 +259,21 @@ impl MsgStore {
         let bytes = msg.approx_bytes();
 
         let mut inner = self.inner.write().unwrap();
-        while inner.total_bytes.saturating_add(bytes) > HISTORY_BYTES {
-            if let Some(front) = inner.history.pop_front() {
-                inner.total_bytes = inner.total_bytes.saturating_sub(front.bytes);
-            } else {
+        while inner.total_bytes.saturating_add(bytes) > self.history_limit {
+            if !inner.evict_oldest_raw() && !inner.evict_oldest() {
                 break;
             }
         }
-        inner.history.push_back(StoredMsg { msg, bytes });
+        let pushed_at = Instant::now();
+        inner.history.push_back(Some(StoredMsg {
+            msg,
+            bytes,
+            pushed_at,
+        }));
         inner.total_bytes = inner.total_bytes.saturating_add(bytes);
+        if let Some(retention) = &self.patch_retention {
+            inner.fold_into_baseline(retention, pushed_at);
+        }
     }
 
     // Convenience
@@ -87,13 +300,19 @@ impl MsgStore {
         self.sender.subscribe()
     }
 
+    /// Stored messages, preceded by a patch replacing the whole document with the folded
+    /// baseline when [`PatchRetention`] has compacted older patches
     pub fn get_history(&self) -> Vec<LogMsg> {
-        self.inner
-            .read()
-            .unwrap()
-            .history
-            .iter()
-            .map(|s| s.msg.clone())
+        let inner = self.inner.read().unwrap();
+        let baseline = inner.baseline.as_ref().map(|baseline| {
+            LogMsg::JsonPatch(Patch(vec![PatchOperation::Replace(ReplaceOperation {
+                path: "".try_into().expect("Root path should be valid"),
+                value: baseline.clone(),
+            })]))
+        });
+        baseline
+            .into_iter()
+            .chain(inner.history.iter().flatten().map(|s| s.msg.clone()))
             .collect()
     }
 
@@ -175,3 +394,98 @@ impl MsgStore {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history_text(store: &MsgStore) -> Vec<String> {
+        store
+            .get_history()
+            .into_iter()
+            .map(|msg| match msg {
+                LogMsg::Stdout(s) => format!("out:{s}"),
+                LogMsg::Stderr(s) => format!("err:{s}"),
+                LogMsg::SessionId(s) => format!("session:{s}"),
+                other => other.name().to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn keeps_everything_under_the_limit() {
+        let store = MsgStore::with_history_limit(1024);
+        store.push_stdout("a");
+        store.push_session_id("s".to_string());
+        store.push_stdout("b");
+        assert_eq!(history_text(&store), vec!["out:a", "session:s", "out:b"]);
+    }
+
+    #[test]
+    fn drops_oldest_raw_output_before_structured_messages() {
+        let chunk = "x".repeat(100);
+        let limit = LogMsg::Stdout(chunk.clone()).approx_bytes() * 3;
+        let store = MsgStore::with_history_limit(limit);
+
+        store.push_session_id("s".to_string());
+        for _ in 0..10 {
+            store.push_stdout(chunk.clone());
+        }
+
+        let history = history_text(&store);
+        assert_eq!(history[0], "session:s");
+        assert_eq!(history[1], format!("err:{LOG_TRUNCATED_MARKER}"));
+        assert_eq!(
+            history.iter().filter(|h| h.starts_with("err:")).count(),
+            1,
+            "only one truncation marker"
+        );
+        assert_eq!(history.last().unwrap(), &format!("out:{chunk}"));
+        let inner = store.inner.read().unwrap();
+        assert!(inner.total_bytes <= limit);
+        assert!(inner.holes * 2 <= inner.history.len());
+    }
+
+    #[test]
+    fn falls_back_to_oldest_when_no_raw_output_is_left() {
+        let limit = LogMsg::SessionId("0".to_string()).approx_bytes() * 2;
+        let store = MsgStore::with_history_limit(limit);
+        for i in 0..5 {
+            store.push_session_id(i.to_string());
+        }
+        assert_eq!(history_text(&store), vec!["session:3", "session:4"]);
+    }
+
+    #[test]
+    fn folds_old_patches_into_a_baseline() {
+        let patches: Vec<Patch> = [
+            serde_json::json!([{"op": "replace", "path": "/tasks", "value": {}}]),
+            serde_json::json!([{"op": "add", "path": "/tasks/a", "value": 1}]),
+            serde_json::json!([{"op": "add", "path": "/tasks/b", "value": 2}]),
+            serde_json::json!([{"op": "replace", "path": "/tasks/a", "value": 3}]),
+            serde_json::json!([{"op": "remove", "path": "/tasks/b"}]),
+            serde_json::json!([{"op": "add", "path": "/tasks/c", "value": 4}]),
+        ]
+        .into_iter()
+        .map(|patch| serde_json::from_value(patch).unwrap())
+        .collect();
+        let store = MsgStore::with_patch_retention(PatchRetention {
+            max_patches: 2,
+            max_age: None,
+        });
+        for patch in patches {
+            store.push_patch(patch);
+        }
+
+        let history = store.get_history();
+        assert_eq!(history.len(), 3, "baseline plus the two newest patches");
+        let mut doc = serde_json::json!({});
+        for msg in history {
+            let LogMsg::JsonPatch(patch) = msg else {
+                panic!("expected only patches");
+            };
+            json_patch::patch(&mut doc, &patch.0).unwrap();
+        }
+        assert_eq!(doc, serde_json::json!({"tasks": {"a": 3, "c": 4}}));
+    }
+}
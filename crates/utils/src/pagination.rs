@@ -0,0 +1,101 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// Page size used when a listing request does not ask for one
+pub const DEFAULT_PAGE_SIZE: u32 = 50;
+/// Largest page a listing request may ask for
+pub const MAX_PAGE_SIZE: u32 = 500;
+
+/// `limit`/`offset` query parameters for paginated listings
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+pub struct PageQuery {
+    #[serde(default)]
+    pub limit: Option<u32>,
+    #[serde(default)]
+    pub offset: Option<u32>,
+}
+
+impl PageQuery {
+    /// The requested page size, defaulted and clamped to [`MAX_PAGE_SIZE`]
+    pub fn limit(&self) -> u32 {
+        self.limit
+            .unwrap_or(DEFAULT_PAGE_SIZE)
+            .clamp(1, MAX_PAGE_SIZE)
+    }
+
+    pub fn offset(&self) -> u32 {
+        self.offset.unwrap_or(0)
+    }
+
+    /// Take this page out of an already filtered and ordered listing
+    pub fn paginate<T>(&self, items: Vec<T>) -> Page<T> {
+        let total = items.len() as u32;
+        let items = items
+            .into_iter()
+            .skip(self.offset() as usize)
+            .take(self.limit() as usize)
+            .collect();
+        Page::new(items, total, self)
+    }
+}
+
+/// One page of a listing
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Number of items across all pages
+    pub total: u32,
+    /// Offset of the next page, or `None` on the last page
+    pub next_offset: Option<u32>,
+}
+
+impl<T> Page<T> {
+    pub fn new(items: Vec<T>, total: u32, query: &PageQuery) -> Self {
+        let end = query.offset() + items.len() as u32;
+        Page {
+            next_offset: (!items.is_empty() && end < total).then_some(end),
+            items,
+            total,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paginates_with_next_offset() {
+        let query = PageQuery {
+            limit: Some(2),
+            offset: None,
+        };
+        let page = query.paginate(vec![1, 2, 3]);
+        assert_eq!(page.items, vec![1, 2]);
+        assert_eq!(page.total, 3);
+        assert_eq!(page.next_offset, Some(2));
+
+        let query = PageQuery {
+            limit: Some(2),
+            offset: Some(2),
+        };
+        let page = query.paginate(vec![1, 2, 3]);
+        assert_eq!(page.items, vec![3]);
+        assert_eq!(page.next_offset, None);
+    }
+
+    #[test]
+    fn clamps_limit() {
+        assert_eq!(PageQuery::default().limit(), DEFAULT_PAGE_SIZE);
+        let query = PageQuery {
+            limit: Some(0),
+            offset: None,
+        };
+        assert_eq!(query.limit(), 1);
+        let query = PageQuery {
+            limit: Some(MAX_PAGE_SIZE + 1),
+            offset: None,
+        };
+        assert_eq!(query.limit(), MAX_PAGE_SIZE);
+    }
+}
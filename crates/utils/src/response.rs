@@ -1,12 +1,73 @@
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 
+/// Stable, machine-readable identifier for a failed request.
+///
+/// Clients should branch on these rather than on `message`, which is meant for display and
+/// may change wording at any time. New codes may be added; unknown codes should be treated
+/// like `INTERNAL_ERROR`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ApiErrorCode {
+    /// Unexpected server-side failure; see the logs for the response's `request_id`
+    InternalError,
+    /// The request was malformed or failed validation
+    ValidationFailed,
+    /// The addressed resource does not exist
+    NotFound,
+    /// The request conflicts with the current state of the resource
+    Conflict,
+    /// A merge or rebase stopped on conflicting changes
+    MergeConflict,
+    /// A rebase is already in progress in the worktree
+    RebaseInProgress,
+    /// Conflict markers remain in files that were marked as resolved
+    UnresolvedConflictMarkers,
+    /// The worktree has uncommitted changes blocking the operation
+    WorktreeDirty,
+    /// The branch and its base have diverged
+    BranchesDiverged,
+    /// A referenced branch does not exist
+    BranchNotFound,
+    /// A referenced git remote does not exist
+    RemoteNotFound,
+    /// No GitHub token is configured
+    TokenUnavailable,
+    /// The GitHub token is invalid or expired
+    TokenInvalid,
+    /// The GitHub token lacks the permissions the operation needs
+    InsufficientPermissions,
+    /// The GitHub repository does not exist or the token cannot access it
+    RepoNotFoundOrNoAccess,
+    /// GitHub's API rate limit was exceeded
+    RateLimited,
+    /// No GitHub device flow login has been started
+    NoSession,
+    /// The GitHub device flow login is still waiting on the user
+    AuthPending,
+    /// GitHub webhooks are not configured
+    WebhookNotConfigured,
+    /// The GitHub webhook signature did not verify
+    InvalidSignature,
+    /// The uploaded file is not a supported image format
+    InvalidImageFormat,
+    /// The uploaded image exceeds the size limit
+    ImageTooLarge,
+    /// The multipart upload could not be read
+    UploadFailed,
+}
+
 #[derive(Debug, Serialize, Deserialize, TS)]
 pub struct ApiResponse<T, E = T> {
     success: bool,
     data: Option<T>,
     error_data: Option<E>,
     message: Option<String>,
+    #[serde(default)]
+    error_code: Option<ApiErrorCode>,
+    /// Id of the request that failed, so a report can be matched to the server logs
+    #[serde(default)]
+    request_id: Option<String>,
 }
 
 impl<T, E> ApiResponse<T, E> {
@@ -17,6 +78,8 @@ impl<T, E> ApiResponse<T, E> {
             data: Some(data),
             message: None,
             error_data: None,
+            error_code: None,
+            request_id: None,
         }
     }
 
@@ -27,8 +90,29 @@ impl<T, E> ApiResponse<T, E> {
             data: None,
             message: Some(message.to_string()),
             error_data: None,
+            error_code: None,
+            request_id: None,
         }
     }
+
+    /// Creates an error response, with `message`, a machine-readable `code`, and no data.
+    pub fn error_with_code(message: &str, code: ApiErrorCode) -> Self {
+        ApiResponse {
+            success: false,
+            data: None,
+            message: Some(message.to_string()),
+            error_data: None,
+            error_code: Some(code),
+            request_id: None,
+        }
+    }
+
+    /// Tags the response with the id of the request it answers.
+    pub fn with_request_id(mut self, request_id: Option<String>) -> Self {
+        self.request_id = request_id;
+        self
+    }
+
     /// Creates an error response, with no `data`, no `message`, but with arbitrary `error_data`.
     pub fn error_with_data(data: E) -> Self {
         ApiResponse {
@@ -36,6 +120,8 @@ impl<T, E> ApiResponse<T, E> {
             data: None,
             error_data: Some(data),
             message: None,
+            error_code: None,
+            request_id: None,
         }
     }
 
@@ -53,4 +139,14 @@ impl<T, E> ApiResponse<T, E> {
     pub fn message(&self) -> Option<&str> {
         self.message.as_deref()
     }
+
+    /// Returns the machine-readable error code if present.
+    pub fn error_code(&self) -> Option<ApiErrorCode> {
+        self.error_code
+    }
+
+    /// Returns the id of the request the response answers, if it was tagged with one.
+    pub fn request_id(&self) -> Option<&str> {
+        self.request_id.as_deref()
+    }
 }
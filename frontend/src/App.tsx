@@ -17,6 +17,7 @@ import {
   GeneralSettings,
   McpSettings,
   ProjectSettings,
+  PromptSnippetSettings,
   SettingsLayout,
 } from '@/pages/settings/';
 import {
@@ -211,6 +212,7 @@ function AppContent() {
                   <Route path="projects" element={<ProjectSettings />} />
                   <Route path="agents" element={<AgentSettings />} />
                   <Route path="mcp" element={<McpSettings />} />
+                  <Route path="snippets" element={<PromptSnippetSettings />} />
                 </Route>
                 <Route
                   path="/mcp-servers"
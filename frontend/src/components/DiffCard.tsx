@@ -7,6 +7,7 @@ import { getHighLightLanguageFromPath } from '@/utils/extToLanguage';
 import { getActualTheme } from '@/utils/theme';
 import { stripLineEnding } from '@/utils/string';
 import { Button } from '@/components/ui/button';
+import { Checkbox } from '@/components/ui/checkbox';
 import {
   ChevronRight,
   ChevronUp,
@@ -35,6 +36,8 @@ type Props = {
   diff: Diff;
   expanded: boolean;
   onToggle: () => void;
+  reviewed?: boolean;
+  onReviewedChange?: (reviewed: boolean) => void;
   selectedAttempt: TaskAttempt | null;
 };
 
@@ -73,6 +76,8 @@ export default function DiffCard({
   diff,
   expanded,
   onToggle,
+  reviewed = false,
+  onReviewedChange,
   selectedAttempt,
 }: Props) {
   const { config } = useUserSystem();
@@ -271,6 +276,18 @@ export default function DiffCard({
           </Button>
         )}
         {title}
+        {onReviewedChange && (
+          <label
+            className="flex items-center gap-1 ml-2 text-xs text-muted-foreground cursor-pointer"
+            title="Mark as reviewed"
+          >
+            <Checkbox
+              checked={reviewed}
+              onCheckedChange={(checked) => onReviewedChange(checked === true)}
+            />
+            Reviewed
+          </label>
+        )}
         <Button
           variant="ghost"
           size="sm"
@@ -18,7 +18,8 @@ type ExecutorType =
   | 'CURSOR_AGENT'
   | 'COPILOT'
   | 'OPENCODE'
-  | 'QWEN_CODE';
+  | 'QWEN_CODE'
+  | 'ECHO';
 
 interface ExecutorConfigFormProps {
   executor: ExecutorType;
@@ -3,6 +3,7 @@ import MarkdownRenderer from '@/components/ui/markdown-renderer.tsx';
 import {
   ActionType,
   NormalizedEntry,
+  PlanStep,
   TaskAttempt,
   ToolStatus,
   type NormalizedEntryType,
@@ -163,8 +164,24 @@ const shouldRenderMarkdown = (entryType: NormalizedEntryType) =>
   entryType.type === 'thinking' ||
   entryType.type === 'tool_use';
 
-const getContentClassName = (entryType: NormalizedEntryType) => {
+// Entries normalized from the process's stderr carry the channel in their metadata
+const isStderrEntry = (entry: NormalizedEntry) => {
+  const metadata = entry.metadata;
+  return (
+    typeof metadata === 'object' &&
+    metadata !== null &&
+    !Array.isArray(metadata) &&
+    metadata.channel === 'stderr'
+  );
+};
+
+const getContentClassName = (
+  entryType: NormalizedEntryType,
+  isStderr = false
+) => {
   const base = ' whitespace-pre-wrap break-words';
+  if (isStderr) return `${base} font-mono text-amber-700 dark:text-amber-300`;
+
   if (
     entryType.type === 'tool_use' &&
     entryType.action_type.action === 'command_run'
@@ -200,7 +217,7 @@ const getContentClassName = (entryType: NormalizedEntryType) => {
  * Unified card      *
  *********************/
 
-type CardVariant = 'system' | 'error';
+type CardVariant = 'system' | 'error' | 'stderr';
 
 const MessageCard: React.FC<{
   children: React.ReactNode;
@@ -213,12 +230,17 @@ const MessageCard: React.FC<{
   const systemTheme = 'border-400/40 text-zinc-500';
   const errorTheme =
     'border-red-400/40 bg-red-50 dark:bg-[hsl(var(--card))] text-[hsl(var(--foreground))]';
+  const stderrTheme =
+    'border-amber-400/40 bg-amber-50 dark:bg-[hsl(var(--card))] text-[hsl(var(--foreground))]';
+  const theme = {
+    system: systemTheme,
+    error: errorTheme,
+    stderr: stderrTheme,
+  };
 
   return (
     <div
-      className={`${frameBase} ${
-        variant === 'system' ? systemTheme : errorTheme
-      }`}
+      className={`${frameBase} ${theme[variant]}`}
       onClick={onToggle}
     >
       <div className="flex items-center gap-1.5">
@@ -239,17 +261,18 @@ const MessageCard: React.FC<{
  * Collapsible container *
  ************************/
 
-type CollapsibleVariant = 'system' | 'error';
+type CollapsibleVariant = CardVariant;
 
 const ExpandChevron: React.FC<{
   expanded: boolean;
   onClick: () => void;
   variant: CollapsibleVariant;
 }> = ({ expanded, onClick, variant }) => {
-  const color =
-    variant === 'system'
-      ? 'text-700 dark:text-300'
-      : 'text-red-700 dark:text-red-300';
+  const color = {
+    system: 'text-700 dark:text-300',
+    error: 'text-red-700 dark:text-red-300',
+    stderr: 'text-amber-700 dark:text-amber-300',
+  }[variant];
 
   return (
     <ChevronDown
@@ -352,11 +375,13 @@ const PLAN_APPEARANCE: Record<
 
 const PlanPresentationCard: React.FC<{
   plan: string;
+  steps?: PlanStep[];
   expansionKey: string;
   defaultExpanded?: boolean;
   statusAppearance?: ToolStatusAppearance;
 }> = ({
   plan,
+  steps = [],
   expansionKey,
   defaultExpanded = false,
   statusAppearance = 'default',
@@ -367,6 +392,7 @@ const PlanPresentationCard: React.FC<{
     defaultExpanded
   );
   const tone = PLAN_APPEARANCE[statusAppearance];
+  const doneSteps = steps.filter((step) => step.done).length;
 
   return (
     <div className="inline-block w-full">
@@ -394,6 +420,11 @@ const PlanPresentationCard: React.FC<{
             <span className="font-semibold">{t('conversation.plan')}</span>
           </span>
           <div className="ml-auto flex items-center gap-2">
+            {steps.length > 1 && (
+              <span className="text-xs tabular-nums opacity-70">
+                {doneSteps}/{steps.length}
+              </span>
+            )}
             <ExpandChevron
               expanded={expanded}
               onClick={toggle}
@@ -713,6 +744,7 @@ function DisplayConversationEntry({
         return (
           <PlanPresentationCard
             plan={toolEntry.action_type.plan}
+            steps={toolEntry.action_type.plan_steps}
             expansionKey={expansionKey}
             defaultExpanded={defaultExpanded}
             statusAppearance={statusAppearance}
@@ -759,6 +791,7 @@ function DisplayConversationEntry({
   }
 
   if (isSystem || isError) {
+    const isStderr = isNormalizedEntry(entry) && isStderrEntry(entry);
     return (
       <div
         className={`px-4 py-2 text-sm ${greyed ? 'opacity-50 pointer-events-none' : ''}`}
@@ -767,8 +800,8 @@ function DisplayConversationEntry({
           content={isNormalizedEntry(entry) ? entry.content : ''}
           markdown={shouldRenderMarkdown(entryType)}
           expansionKey={expansionKey}
-          variant={isSystem ? 'system' : 'error'}
-          contentClassName={getContentClassName(entryType)}
+          variant={isSystem ? 'system' : isStderr ? 'stderr' : 'error'}
+          contentClassName={getContentClassName(entryType, isStderr)}
         />
       </div>
     );
@@ -3,8 +3,8 @@ import { Button } from '@/components/ui/button';
 import { Pencil } from 'lucide-react';
 import { useEffect, useState } from 'react';
 import { useProcessRetry } from '@/hooks/useProcessRetry';
-import { TaskAttempt, type BaseAgentCapability } from 'shared/types';
-import { useUserSystem } from '@/components/config-provider';
+import type { BaseCodingAgent, TaskAttempt } from 'shared/types';
+import { useExecutorCapabilities } from '@/hooks';
 import { useDraftStream } from '@/hooks/follow-up/useDraftStream';
 import { RetryEditorInline } from './RetryEditorInline';
 import { useRetryUi } from '@/contexts/RetryUiContext';
@@ -20,15 +20,15 @@ const UserMessage = ({
 }) => {
   const [isEditing, setIsEditing] = useState(false);
   const retryHook = useProcessRetry(taskAttempt);
-  const { capabilities } = useUserSystem();
+  const { byExecutor } = useExecutorCapabilities();
   const attemptId = taskAttempt?.id;
   const { retryDraft } = useDraftStream(attemptId);
   const { activeRetryProcessId, isProcessGreyed } = useRetryUi();
 
   const canFork = !!(
     taskAttempt?.executor &&
-    capabilities?.[taskAttempt.executor]?.includes(
-      'SESSION_FORK' as BaseAgentCapability
+    byExecutor[taskAttempt.executor as BaseCodingAgent]?.capabilities.includes(
+      'SESSION_FORK'
     )
   );
 
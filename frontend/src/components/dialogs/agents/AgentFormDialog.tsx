@@ -19,6 +19,7 @@ import {
   SelectValue,
 } from '@/components/ui/select';
 import { agentsApi } from '@/lib/api';
+import { useExecutorCapabilities } from '@/hooks';
 import type {
   Agent,
   ContextFile,
@@ -31,22 +32,13 @@ export interface AgentFormDialogProps {
   agent?: Agent | null;
 }
 
-const EXECUTOR_TYPES = [
-  'CLAUDE_CODE',
-  'GEMINI',
-  'AMP',
-  'CODEX',
-  'OPENCODE',
-  'CURSOR_AGENT',
-  'QWEN_CODE',
-  'COPILOT',
-];
-
 export const AgentFormDialog = NiceModal.create<AgentFormDialogProps>(
   ({ agent }) => {
     const modal = useModal();
     const queryClient = useQueryClient();
     const isEditMode = Boolean(agent);
+    // The server only lists agents this build can run
+    const { data: executorCapabilities = [] } = useExecutorCapabilities();
 
     const [name, setName] = useState('');
     const [role, setRole] = useState('');
@@ -204,7 +196,7 @@ export const AgentFormDialog = NiceModal.create<AgentFormDialogProps>(
                   <SelectValue />
                 </SelectTrigger>
                 <SelectContent>
-                  {EXECUTOR_TYPES.map((type) => (
+                  {executorCapabilities.map(({ executor: type }) => (
                     <SelectItem key={type} value={type}>
                       {type}
                     </SelectItem>
@@ -62,6 +62,17 @@ export const ProjectFormDialog = NiceModal.create<ProjectFormDialogProps>(
         cleanup_script: null,
         copy_files: null,
         worktree_dir: null,
+        push_remote: null,
+        pr_remote: null,
+        always_run_cleanup: false,
+        setup_cache_files: null,
+        setup_cache_artifacts: null,
+        script_language: null,
+        preview_url_pattern: null,
+        env_file: null,
+        env_file_required: false,
+        dev_scripts: [],
+        reuse_worktree: false,
       };
 
       createProject.mutate(createData);
@@ -93,6 +104,17 @@ export const ProjectFormDialog = NiceModal.create<ProjectFormDialogProps>(
         cleanup_script: null,
         copy_files: null,
         worktree_dir: null,
+        push_remote: null,
+        pr_remote: null,
+        always_run_cleanup: false,
+        setup_cache_files: null,
+        setup_cache_artifacts: null,
+        script_language: null,
+        preview_url_pattern: null,
+        env_file: null,
+        env_file_required: false,
+        dev_scripts: [],
+        reuse_worktree: false,
       };
 
       createProject.mutate(createData);
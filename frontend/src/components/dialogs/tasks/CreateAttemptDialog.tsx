@@ -10,6 +10,7 @@ import {
 } from '@/components/ui/dialog';
 import { Button } from '@/components/ui/button';
 import { Label } from '@/components/ui/label';
+import { Checkbox } from '@/components/ui/checkbox';
 import BranchSelector from '@/components/tasks/BranchSelector';
 import { ExecutorProfileSelector } from '@/components/settings';
 import { useAttemptCreation } from '@/hooks/useAttemptCreation';
@@ -35,7 +36,7 @@ export const CreateAttemptDialog = NiceModal.create<CreateAttemptDialogProps>(
   ({ taskId, latestAttempt }) => {
     const modal = useModal();
     const navigate = useNavigateWithSearch();
-    const { projectId } = useProject();
+    const { projectId, project } = useProject();
     const { t } = useTranslation('tasks');
     const { profiles, config } = useUserSystem();
     const { createAttempt, isCreating, error } = useAttemptCreation({
@@ -52,6 +53,11 @@ export const CreateAttemptDialog = NiceModal.create<CreateAttemptDialogProps>(
     const [selectedBranch, setSelectedBranch] = useState<string | null>(null);
     const [branches, setBranches] = useState<GitBranch[]>([]);
     const [isLoadingBranches, setIsLoadingBranches] = useState(false);
+    const [forceSetup, setForceSetup] = useState(false);
+    // Profile suggested by the repo's .vibe config; undefined until it has loaded
+    const [repoProfile, setRepoProfile] = useState<
+      ExecutorProfileId | null | undefined
+    >(undefined);
 
     useEffect(() => {
       if (modal.visible && projectId) {
@@ -67,6 +73,15 @@ export const CreateAttemptDialog = NiceModal.create<CreateAttemptDialogProps>(
           .finally(() => {
             setIsLoadingBranches(false);
           });
+        projectsApi
+          .getRepoConfig(projectId)
+          .then((status) => {
+            setRepoProfile(status.config?.executor_profile ?? null);
+          })
+          .catch((err) => {
+            console.error('Failed to load repo config:', err);
+            setRepoProfile(null);
+          });
       }
     }, [modal.visible, projectId]);
 
@@ -74,6 +89,8 @@ export const CreateAttemptDialog = NiceModal.create<CreateAttemptDialogProps>(
       if (!modal.visible) {
         setSelectedProfile(null);
         setSelectedBranch(null);
+        setForceSetup(false);
+        setRepoProfile(undefined);
       }
     }, [modal.visible]);
 
@@ -90,13 +107,15 @@ export const CreateAttemptDialog = NiceModal.create<CreateAttemptDialogProps>(
             }
           : null;
 
-        return fromAttempt ?? config?.executor_profile ?? null;
+        if (!fromAttempt && repoProfile === undefined) return null;
+        return fromAttempt ?? repoProfile ?? config?.executor_profile ?? null;
       });
 
       setSelectedBranch((prev) => {
         if (prev) return prev;
         return (
           latestAttempt?.target_branch ??
+          project?.default_base_branch ??
           branches.find((b) => b.is_current)?.name ??
           null
         );
@@ -105,7 +124,9 @@ export const CreateAttemptDialog = NiceModal.create<CreateAttemptDialogProps>(
       modal.visible,
       latestAttempt?.executor,
       latestAttempt?.target_branch,
+      repoProfile,
       config?.executor_profile,
+      project?.default_base_branch,
       branches,
     ]);
 
@@ -116,6 +137,7 @@ export const CreateAttemptDialog = NiceModal.create<CreateAttemptDialogProps>(
         await createAttempt({
           profile: selectedProfile,
           baseBranch: selectedBranch,
+          forceSetup,
         });
         modal.hide();
       } catch (err) {
@@ -170,6 +192,19 @@ export const CreateAttemptDialog = NiceModal.create<CreateAttemptDialogProps>(
               />
             </div>
 
+            {project?.setup_script && project.setup_cache_artifacts && (
+              <div className="flex items-center space-x-2">
+                <Checkbox
+                  id="force-setup"
+                  checked={forceSetup}
+                  onCheckedChange={(checked: boolean) => setForceSetup(checked)}
+                />
+                <Label htmlFor="force-setup" className="cursor-pointer">
+                  {t('createAttemptDialog.forceSetup')}
+                </Label>
+              </div>
+            )}
+
             {error && (
               <div className="text-sm text-destructive">
                 {t('createAttemptDialog.error')}
@@ -34,6 +34,7 @@ export function StationConfigPanel({
   const [agentId, setAgentId] = useState<string | null>(null);
   const [stationPrompt, setStationPrompt] = useState('');
   const [outputContextKeys, setOutputContextKeys] = useState('');
+  const [outputSchema, setOutputSchema] = useState('');
   const [xPosition, setXPosition] = useState('0');
   const [yPosition, setYPosition] = useState('0');
   const [isTerminator, setIsTerminator] = useState(false);
@@ -41,6 +42,7 @@ export function StationConfigPanel({
   // Validation state
   const [errors, setErrors] = useState<{
     name?: string;
+    outputSchema?: string;
     xPosition?: string;
     yPosition?: string;
   }>({});
@@ -52,6 +54,7 @@ export function StationConfigPanel({
       setAgentId(station.agent_id);
       setStationPrompt(station.station_prompt || '');
       setOutputContextKeys(station.output_context_keys || '');
+      setOutputSchema(station.output_schema || '');
       setXPosition(String(station.x_position || 0));
       setYPosition(String(station.y_position || 0));
       setIsTerminator(station.is_terminator || false);
@@ -67,6 +70,14 @@ export function StationConfigPanel({
       newErrors.name = 'Station name is required';
     }
 
+    if (outputSchema.trim()) {
+      try {
+        JSON.parse(outputSchema);
+      } catch {
+        newErrors.outputSchema = 'Output schema must be valid JSON';
+      }
+    }
+
     const x = parseFloat(xPosition);
     if (isNaN(x)) {
       newErrors.xPosition = 'X position must be a valid number';
@@ -98,6 +109,7 @@ export function StationConfigPanel({
       agent_id: agentId,
       station_prompt: stationPrompt.trim() || null,
       output_context_keys: outputContextKeys.trim() || null,
+      output_schema: outputSchema.trim() || null,
       is_terminator: isTerminator,
     };
 
@@ -228,6 +240,35 @@ export function StationConfigPanel({
                 </p>
               </div>
 
+              {/* Output Schema */}
+              <div>
+                <Label htmlFor="output-schema" className="text-sm font-medium">
+                  Output Schema
+                </Label>
+                <Textarea
+                  id="output-schema"
+                  value={outputSchema}
+                  onChange={(e) => setOutputSchema(e.target.value)}
+                  placeholder='e.g., {"type": "object", "required": ["review_result"]}'
+                  disabled={isSaving}
+                  className={
+                    errors.outputSchema
+                      ? 'border-destructive mt-1.5 min-h-[80px] font-mono text-xs'
+                      : 'mt-1.5 min-h-[80px] font-mono text-xs'
+                  }
+                />
+                {errors.outputSchema ? (
+                  <p className="text-sm text-destructive mt-1">
+                    {errors.outputSchema}
+                  </p>
+                ) : (
+                  <p className="text-xs text-muted-foreground mt-1">
+                    Optional JSON Schema the output must match; output that
+                    doesn't fails the station
+                  </p>
+                )}
+              </div>
+
               {/* Terminator Status */}
               <div className="flex items-start gap-3 p-3 border rounded-md bg-muted/30">
                 <input
@@ -8,7 +8,14 @@ import {
   SelectTrigger,
   SelectValue,
 } from '@/components/ui/select';
-import { CheckCircle, Loader2, XCircle, Play, Eye } from 'lucide-react';
+import {
+  CheckCircle,
+  Loader2,
+  XCircle,
+  Play,
+  Eye,
+  MessageCircleQuestion,
+} from 'lucide-react';
 import type { TaskWithAttemptStatus, Workflow } from 'shared/types';
 import { cn } from '@/lib/utils';
 import { workflowsApi } from '@/lib/api';
@@ -62,6 +69,7 @@ export function TaskTrayCard({ task, horizontal = false, projectId, onSelect }:
       task_id: task.id,
       base_branch: 'master', // TODO: Make this configurable or get from project settings
       executor_profile_id: null, // Use default executor
+      force_restart: null, // Reuse an execution that is already running
     });
 
     // Note: Task status update (todo → inprogress) is handled automatically
@@ -101,6 +109,10 @@ export function TaskTrayCard({ task, horizontal = false, projectId, onSelect }:
             {task.last_attempt_failed && !task.has_merged_attempt && (
               <XCircle className="h-3 w-3 text-destructive" />
             )}
+            {/* Waiting for the user's reply */}
+            {task.needs_input && !task.has_in_progress_attempt && (
+              <MessageCircleQuestion className="h-3 w-3 text-amber-500" />
+            )}
           </div>
         </div>
         {task.description && (
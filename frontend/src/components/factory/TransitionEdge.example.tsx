@@ -35,6 +35,7 @@ const exampleStations: WorkflowStation[] = [
     agent_id: null,
     station_prompt: null,
     output_context_keys: null,
+    output_schema: null,
     is_terminator: false,
     description: 'Review code changes',
     created_at: new Date().toISOString(),
@@ -50,6 +51,7 @@ const exampleStations: WorkflowStation[] = [
     agent_id: null,
     station_prompt: null,
     output_context_keys: null,
+    output_schema: null,
     is_terminator: false,
     description: 'Run automated tests',
     created_at: new Date().toISOString(),
@@ -65,6 +67,7 @@ const exampleStations: WorkflowStation[] = [
     agent_id: null,
     station_prompt: null,
     output_context_keys: null,
+    output_schema: null,
     is_terminator: false,
     description: 'Deploy to production',
     created_at: new Date().toISOString(),
@@ -80,6 +83,7 @@ const exampleStations: WorkflowStation[] = [
     agent_id: null,
     station_prompt: null,
     output_context_keys: null,
+    output_schema: null,
     is_terminator: false,
     description: 'Fix failed tests',
     created_at: new Date().toISOString(),
@@ -95,6 +99,7 @@ const exampleStations: WorkflowStation[] = [
     agent_id: null,
     station_prompt: null,
     output_context_keys: null,
+    output_schema: null,
     is_terminator: true, // Phase 3.6: Terminator station
     description: 'Automatically archive completed work',
     created_at: new Date().toISOString(),
@@ -110,6 +115,7 @@ const exampleStations: WorkflowStation[] = [
     agent_id: null,
     station_prompt: null,
     output_context_keys: null,
+    output_schema: null,
     is_terminator: false,
     description: 'Manual review for rejected items',
     created_at: new Date().toISOString(),
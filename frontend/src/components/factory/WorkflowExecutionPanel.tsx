@@ -9,6 +9,8 @@ import {
   ExternalLink,
   StopCircle,
   RotateCcw,
+  PauseCircle,
+  PlayCircle,
 } from 'lucide-react';
 import { useWorkflowExecutionDetails } from '@/hooks/useWorkflowExecutionDetails';
 import { useExecutionProcesses } from '@/hooks/useExecutionProcesses';
@@ -71,6 +73,29 @@ export function WorkflowExecutionPanel({
     }
   };
 
+  // Handle pause/resume workflow
+  const handleTogglePause = async () => {
+    if (!executionId || !execution) return;
+
+    const paused = execution.status === 'paused';
+    setIsActioning(true);
+    try {
+      if (paused) {
+        await workflowExecutionsApi.resume(executionId);
+      } else {
+        await workflowExecutionsApi.pause(executionId);
+      }
+      await refetch();
+    } catch (err) {
+      console.error(`Failed to ${paused ? 'resume' : 'pause'} workflow:`, err);
+      alert(
+        `Failed to ${paused ? 'resume' : 'pause'} workflow: ${err instanceof Error ? err.message : 'Unknown error'}`
+      );
+    } finally {
+      setIsActioning(false);
+    }
+  };
+
   // Handle retry failed station
   const handleRetryStation = async (stationExecutionId: string) => {
     if (!executionId) return;
@@ -98,6 +123,8 @@ export function WorkflowExecutionPanel({
         return 'bg-green-500/10 text-green-500 border-green-500/20';
       case 'running':
         return 'bg-blue-500/10 text-blue-500 border-blue-500/20 animate-pulse';
+      case 'paused':
+        return 'bg-yellow-500/10 text-yellow-500 border-yellow-500/20';
       case 'failed':
         return 'bg-red-500/10 text-red-500 border-red-500/20';
       case 'cancelled':
@@ -116,6 +143,8 @@ export function WorkflowExecutionPanel({
         return <CheckCircle2 className="h-4 w-4" />;
       case 'running':
         return <Clock className="h-4 w-4 animate-spin" />;
+      case 'paused':
+        return <PauseCircle className="h-4 w-4" />;
       case 'failed':
         return <XCircle className="h-4 w-4" />;
       case 'cancelled':
@@ -270,8 +299,30 @@ export function WorkflowExecutionPanel({
 
                 {/* Controls */}
                 {(execution.status === 'running' ||
-                  execution.status === 'pending') && (
+                  execution.status === 'pending' ||
+                  execution.status === 'paused') && (
                   <div className="flex gap-2">
+                    {(execution.status === 'running' ||
+                      execution.status === 'paused') && (
+                      <Button
+                        variant="outline"
+                        size="sm"
+                        onClick={handleTogglePause}
+                        disabled={isActioning}
+                      >
+                        {execution.status === 'paused' ? (
+                          <>
+                            <PlayCircle className="h-4 w-4 mr-2" />
+                            Resume Workflow
+                          </>
+                        ) : (
+                          <>
+                            <PauseCircle className="h-4 w-4 mr-2" />
+                            Pause Workflow
+                          </>
+                        )}
+                      </Button>
+                    )}
                     <Button
                       variant="destructive"
                       size="sm"
@@ -6,6 +6,7 @@ import { Button } from '@/components/ui/button';
 import DiffViewSwitch from '@/components/diff-view-switch';
 import DiffCard from '@/components/DiffCard';
 import { useDiffSummary } from '@/hooks/useDiffSummary';
+import { useFileReviews } from '@/hooks/useFileReviews';
 import { NewCardHeader } from '@/components/ui/new-card';
 import { ChevronsUp, ChevronsDown } from 'lucide-react';
 import {
@@ -22,9 +23,15 @@ import GitOperations, {
 interface DiffsPanelProps {
   selectedAttempt: TaskAttempt | null;
   gitOps?: GitOperationsInputs;
+  // Reviewed files as last streamed for the attempt
+  reviewedPaths?: string[];
 }
 
-export function DiffsPanel({ selectedAttempt, gitOps }: DiffsPanelProps) {
+export function DiffsPanel({
+  selectedAttempt,
+  gitOps,
+  reviewedPaths: liveReviewedPaths,
+}: DiffsPanelProps) {
   const { t } = useTranslation('tasks');
   const [loading, setLoading] = useState(true);
   const [collapsedIds, setCollapsedIds] = useState<Set<string>>(new Set());
@@ -33,6 +40,18 @@ export function DiffsPanel({ selectedAttempt, gitOps }: DiffsPanelProps) {
   const { fileCount, added, deleted } = useDiffSummary(
     selectedAttempt?.id ?? null
   );
+  const {
+    reviewedPaths,
+    setReviewed,
+    refresh: refreshReviews,
+  } = useFileReviews(selectedAttempt?.id, liveReviewedPaths);
+
+  // Edits reset a file's review; check again once the diff settles
+  useEffect(() => {
+    if (diffs.length === 0) return;
+    const timer = setTimeout(() => refreshReviews(), 1000);
+    return () => clearTimeout(timer);
+  }, [diffs, refreshReviews]);
 
   useEffect(() => {
     setLoading(true);
@@ -83,6 +102,11 @@ export function DiffsPanel({ selectedAttempt, gitOps }: DiffsPanelProps) {
     return diffs.map((d, i) => d.newPath || d.oldPath || String(i));
   }, [diffs]);
 
+  const reviewedCount = useMemo(
+    () => ids.filter((id) => reviewedPaths.has(id)).length,
+    [ids, reviewedPaths]
+  );
+
   const toggle = useCallback((id: string) => {
     setCollapsedIds((prev) => {
       const next = new Set(prev);
@@ -116,6 +140,9 @@ export function DiffsPanel({ selectedAttempt, gitOps }: DiffsPanelProps) {
       allCollapsed={allCollapsed}
       handleCollapseAll={handleCollapseAll}
       toggle={toggle}
+      reviewedPaths={reviewedPaths}
+      reviewedCount={reviewedCount}
+      onReviewedChange={(path, reviewed) => setReviewed({ path, reviewed })}
       selectedAttempt={selectedAttempt}
       gitOps={gitOps}
       loading={loading}
@@ -133,6 +160,9 @@ interface DiffsPanelContentProps {
   allCollapsed: boolean;
   handleCollapseAll: () => void;
   toggle: (id: string) => void;
+  reviewedPaths: Set<string>;
+  reviewedCount: number;
+  onReviewedChange: (path: string, reviewed: boolean) => void;
   selectedAttempt: TaskAttempt | null;
   gitOps?: GitOperationsInputs;
   loading: boolean;
@@ -148,6 +178,9 @@ function DiffsPanelContent({
   allCollapsed,
   handleCollapseAll,
   toggle,
+  reviewedPaths,
+  reviewedCount,
+  onReviewedChange,
   selectedAttempt,
   gitOps,
   loading,
@@ -201,6 +234,12 @@ function DiffsPanelContent({
               </span>{' '}
               <span className="text-red-600 dark:text-red-500">-{deleted}</span>
             </span>
+            <span className="ml-3 text-sm text-muted-foreground whitespace-nowrap">
+              {t('diff.filesReviewed', {
+                reviewed: reviewedCount,
+                count: diffs.length,
+              })}
+            </span>
           </div>
         </NewCardHeader>
       )}
@@ -227,6 +266,8 @@ function DiffsPanelContent({
                 diff={diff}
                 expanded={!collapsedIds.has(id)}
                 onToggle={() => toggle(id)}
+                reviewed={reviewedPaths.has(id)}
+                onReviewedChange={(reviewed) => onReviewedChange(id, reviewed)}
                 selectedAttempt={selectedAttempt}
               />
             );
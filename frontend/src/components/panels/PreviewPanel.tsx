@@ -15,6 +15,7 @@ import { DevServerLogsView } from '@/components/tasks/TaskDetails/preview/DevSer
 import { PreviewToolbar } from '@/components/tasks/TaskDetails/preview/PreviewToolbar';
 import { NoServerContent } from '@/components/tasks/TaskDetails/preview/NoServerContent';
 import { ReadyContent } from '@/components/tasks/TaskDetails/preview/ReadyContent';
+import { NamedDevServers } from '@/components/tasks/TaskDetails/preview/NamedDevServers';
 
 export function PreviewPanel() {
   const [iframeError, setIframeError] = useState(false);
@@ -36,9 +37,11 @@ export function PreviewPanel() {
   const {
     start: startDevServer,
     stop: stopDevServer,
+    stopProcess: stopDevServerProcess,
     isStarting: isStartingDevServer,
     isStopping: isStoppingDevServer,
     runningDevServer,
+    runningDevServers,
     latestDevServerProcess,
   } = useDevServer(attemptId);
 
@@ -239,6 +242,14 @@ export function PreviewPanel() {
             </div>
           </Alert>
         )}
+        <NamedDevServers
+          devScripts={project?.dev_scripts ?? []}
+          runningDevServers={runningDevServers}
+          onStart={(name) => startDevServer(name)}
+          onStop={(processId) => stopDevServerProcess(processId)}
+          isStarting={isStartingDevServer}
+          isStopping={isStoppingDevServer}
+        />
         <DevServerLogsView
           latestDevServerProcess={latestDevServerProcess}
           showLogs={showLogs}
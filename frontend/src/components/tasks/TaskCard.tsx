@@ -1,7 +1,14 @@
 import { useCallback, useEffect, useRef } from 'react';
+import { useTranslation } from 'react-i18next';
 import { KanbanCard } from '@/components/ui/shadcn-io/kanban';
-import { CheckCircle, Loader2, XCircle } from 'lucide-react';
-import type { TaskWithAttemptStatus } from 'shared/types';
+import {
+  CheckCircle,
+  Loader2,
+  Lock,
+  MessageCircleQuestion,
+  XCircle,
+} from 'lucide-react';
+import type { AttemptProgressStage, TaskWithAttemptStatus } from 'shared/types';
 import { ActionsDropdown } from '@/components/ui/ActionsDropdown';
 
 type Task = TaskWithAttemptStatus;
@@ -12,6 +19,7 @@ interface TaskCardProps {
   status: string;
   onViewDetails: (task: Task) => void;
   isOpen?: boolean;
+  progress?: AttemptProgressStage;
 }
 
 export function TaskCard({
@@ -20,7 +28,9 @@ export function TaskCard({
   status,
   onViewDetails,
   isOpen,
+  progress,
 }: TaskCardProps) {
+  const { t } = useTranslation('tasks');
   const handleClick = useCallback(() => {
     onViewDetails(task);
   }, [task, onViewDetails]);
@@ -55,8 +65,12 @@ export function TaskCard({
           {task.title}
         </h4>
         <div className="flex items-center space-x-1">
+          {/* Waiting on subtasks */}
+          {task.blocked_on_subtasks && (
+            <Lock className="h-3 w-3 text-muted-foreground" />
+          )}
           {/* In Progress Spinner */}
-          {task.has_in_progress_attempt && (
+          {(task.has_in_progress_attempt || progress) && (
             <Loader2 className="h-3 w-3 animate-spin text-blue-500" />
           )}
           {/* Merged Indicator */}
@@ -67,6 +81,12 @@ export function TaskCard({
           {task.last_attempt_failed && !task.has_merged_attempt && (
             <XCircle className="h-3 w-3 text-destructive" />
           )}
+          {/* Waiting for the user's reply */}
+          {task.needs_input && !task.has_in_progress_attempt && (
+            <span title={t('attempt.needsInput')}>
+              <MessageCircleQuestion className="h-3 w-3 text-amber-500" />
+            </span>
+          )}
           {/* Actions Menu */}
           <div
             onPointerDown={(e) => e.stopPropagation()}
@@ -77,6 +97,11 @@ export function TaskCard({
           </div>
         </div>
       </div>
+      {progress && (
+        <p className="text-xs text-muted-foreground">
+          {t(`attempt.progress.${progress}`)}
+        </p>
+      )}
       {task.description && (
         <p className="flex-1 text-sm text-secondary-foreground break-words">
           {task.description.length > 130
@@ -84,6 +109,19 @@ export function TaskCard({
             : task.description}
         </p>
       )}
+      {task.labels.length > 0 && (
+        <div className="flex flex-wrap gap-1">
+          {task.labels.map((label) => (
+            <span
+              key={label.id}
+              className="inline-flex items-center rounded-full border px-2 py-0.5 text-xs"
+              style={{ borderColor: label.color, color: label.color }}
+            >
+              {label.name}
+            </span>
+          ))}
+        </div>
+      )}
     </KanbanCard>
   );
 }
@@ -0,0 +1,43 @@
+import { useTranslation } from 'react-i18next';
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from '@/components/ui/select';
+import type { LogChannel } from 'shared/types';
+
+const ALL_CHANNELS = 'all';
+
+interface LogChannelSelectProps {
+  value: LogChannel | undefined;
+  onChange: (channel: LogChannel | undefined) => void;
+}
+
+export function LogChannelSelect({ value, onChange }: LogChannelSelectProps) {
+  const { t } = useTranslation('tasks');
+
+  return (
+    <Select
+      value={value ?? ALL_CHANNELS}
+      onValueChange={(v) =>
+        onChange(v === ALL_CHANNELS ? undefined : (v as LogChannel))
+      }
+    >
+      <SelectTrigger
+        className="h-8 w-[130px]"
+        aria-label={t('processes.logChannel.label')}
+      >
+        <SelectValue />
+      </SelectTrigger>
+      <SelectContent>
+        <SelectItem value={ALL_CHANNELS}>
+          {t('processes.logChannel.all')}
+        </SelectItem>
+        <SelectItem value="stdout">{t('processes.logChannel.stdout')}</SelectItem>
+        <SelectItem value="stderr">{t('processes.logChannel.stderr')}</SelectItem>
+      </SelectContent>
+    </Select>
+  );
+}
@@ -3,12 +3,14 @@ import { Virtuoso, VirtuosoHandle } from 'react-virtuoso';
 import { AlertCircle } from 'lucide-react';
 import { useLogStream } from '@/hooks/useLogStream';
 import RawLogText from '@/components/common/RawLogText';
-import type { PatchType } from 'shared/types';
+import type { LogChannel, PatchType } from 'shared/types';
 
 type LogEntry = Extract<PatchType, { type: 'STDOUT' } | { type: 'STDERR' }>;
 
 interface ProcessLogsViewerProps {
   processId: string;
+  /** Show only this output channel */
+  channel?: LogChannel;
 }
 
 export function ProcessLogsViewerContent({
@@ -98,7 +100,8 @@ export function ProcessLogsViewerContent({
 
 export default function ProcessLogsViewer({
   processId,
+  channel,
 }: ProcessLogsViewerProps) {
-  const { logs, error } = useLogStream(processId);
+  const { logs, error } = useLogStream(processId, channel);
   return <ProcessLogsViewerContent logs={logs} error={error} />;
 }
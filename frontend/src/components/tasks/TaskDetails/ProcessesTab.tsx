@@ -13,7 +13,12 @@ import { executionProcessesApi } from '@/lib/api.ts';
 import { ProfileVariantBadge } from '@/components/common/ProfileVariantBadge.tsx';
 import { useExecutionProcesses } from '@/hooks/useExecutionProcesses';
 import ProcessLogsViewer from './ProcessLogsViewer';
-import type { ExecutionProcessStatus, ExecutionProcess } from 'shared/types';
+import { LogChannelSelect } from './LogChannelSelect';
+import type {
+  ExecutionProcessStatus,
+  ExecutionProcess,
+  LogChannel,
+} from 'shared/types';
 
 import { useProcessSelection } from '@/contexts/ProcessSelectionContext';
 import { useRetryUi } from '@/contexts/RetryUiContext';
@@ -36,6 +41,7 @@ function ProcessesTab({ attemptId }: ProcessesTabProps) {
   const [localProcessDetails, setLocalProcessDetails] = useState<
     Record<string, ExecutionProcess>
   >({});
+  const [logChannel, setLogChannel] = useState<LogChannel | undefined>();
 
   useEffect(() => {
     setLocalProcessDetails({});
@@ -261,17 +267,23 @@ function ProcessesTab({ attemptId }: ProcessesTabProps) {
             <h2 className="text-lg font-semibold">
               {t('processes.detailsTitle')}
             </h2>
-            <button
-              onClick={() => setSelectedProcessId(null)}
-              className="flex items-center gap-2 px-3 py-2 text-sm font-medium text-muted-foreground hover:text-foreground hover:bg-muted/50 rounded-md border border-border transition-colors"
-            >
-              <ArrowLeft className="h-4 w-4" />
-              {t('processes.backToList')}
-            </button>
+            <div className="flex items-center gap-2">
+              <LogChannelSelect value={logChannel} onChange={setLogChannel} />
+              <button
+                onClick={() => setSelectedProcessId(null)}
+                className="flex items-center gap-2 px-3 py-2 text-sm font-medium text-muted-foreground hover:text-foreground hover:bg-muted/50 rounded-md border border-border transition-colors"
+              >
+                <ArrowLeft className="h-4 w-4" />
+                {t('processes.backToList')}
+              </button>
+            </div>
           </div>
           <div className="flex-1">
             {selectedProcess ? (
-              <ProcessLogsViewer processId={selectedProcess.id} />
+              <ProcessLogsViewer
+                processId={selectedProcess.id}
+                channel={logChannel}
+              />
             ) : loadingProcessId === selectedProcessId ? (
               <div className="text-center text-muted-foreground">
                 <p>{t('processes.loadingDetails')}</p>
@@ -1,10 +1,12 @@
+import { useState } from 'react';
 import { useTranslation } from 'react-i18next';
 import { Terminal, ChevronDown } from 'lucide-react';
 import { Button } from '@/components/ui/button';
 import ProcessLogsViewer, {
   ProcessLogsViewerContent,
 } from '../ProcessLogsViewer';
-import { ExecutionProcess } from 'shared/types';
+import { LogChannelSelect } from '../LogChannelSelect';
+import { ExecutionProcess, LogChannel } from 'shared/types';
 
 interface DevServerLogsViewProps {
   latestDevServerProcess: ExecutionProcess | undefined;
@@ -26,6 +28,7 @@ export function DevServerLogsView({
   error,
 }: DevServerLogsViewProps) {
   const { t } = useTranslation('tasks');
+  const [channel, setChannel] = useState<LogChannel | undefined>();
 
   if (!latestDevServerProcess) {
     return null;
@@ -41,25 +44,42 @@ export function DevServerLogsView({
             {t('preview.logs.title')}
           </span>
         </div>
-        <Button size="sm" variant="ghost" onClick={onToggle}>
-          <ChevronDown
-            className={`h-4 w-4 mr-1 ${showToggleText ? 'transition-transform' : ''} ${showLogs ? '' : 'rotate-180'}`}
-          />
-          {showToggleText
-            ? showLogs
-              ? t('preview.logs.hide')
-              : t('preview.logs.show')
-            : t('preview.logs.hide')}
-        </Button>
+        <div className="flex items-center gap-2">
+          {showLogs && (
+            <LogChannelSelect value={channel} onChange={setChannel} />
+          )}
+          <Button size="sm" variant="ghost" onClick={onToggle}>
+            <ChevronDown
+              className={`h-4 w-4 mr-1 ${showToggleText ? 'transition-transform' : ''} ${showLogs ? '' : 'rotate-180'}`}
+            />
+            {showToggleText
+              ? showLogs
+                ? t('preview.logs.hide')
+                : t('preview.logs.show')
+              : t('preview.logs.hide')}
+          </Button>
+        </div>
       </div>
 
       {/* Logs viewer */}
       {showLogs && (
         <div className={height}>
           {logs ? (
-            <ProcessLogsViewerContent logs={logs} error={error ?? null} />
+            <ProcessLogsViewerContent
+              logs={
+                channel
+                  ? logs.filter(
+                      (entry) => entry.type === channel.toUpperCase()
+                    )
+                  : logs
+              }
+              error={error ?? null}
+            />
           ) : (
-            <ProcessLogsViewer processId={latestDevServerProcess.id} />
+            <ProcessLogsViewer
+              processId={latestDevServerProcess.id}
+              channel={channel}
+            />
           )}
         </div>
       )}
@@ -0,0 +1,85 @@
+import { Loader2, Play, Square } from 'lucide-react';
+import { useTranslation } from 'react-i18next';
+import { Button } from '@/components/ui/button';
+import { devServerName } from '@/hooks/useDevServer';
+import type { ExecutionProcess, NamedScript } from 'shared/types';
+
+interface NamedDevServersProps {
+  devScripts: NamedScript[];
+  runningDevServers: ExecutionProcess[];
+  onStart: (name: string) => void;
+  onStop: (processId: string) => void;
+  isStarting: boolean;
+  isStopping: boolean;
+}
+
+/**
+ * The project's named dev servers, each started and stopped on its own next to
+ * the main dev server
+ */
+export function NamedDevServers({
+  devScripts,
+  runningDevServers,
+  onStart,
+  onStop,
+  isStarting,
+  isStopping,
+}: NamedDevServersProps) {
+  const { t } = useTranslation('tasks');
+
+  if (devScripts.length === 0) return null;
+
+  return (
+    <div className="border-t px-3 py-2 space-y-1">
+      <p className="text-xs font-medium text-muted-foreground">
+        {t('preview.devServers.title')}
+      </p>
+      {devScripts.map((dev) => {
+        const running = runningDevServers.find(
+          (process) => devServerName(process) === dev.name
+        );
+        return (
+          <div
+            key={dev.name}
+            className="flex items-center justify-between gap-2 text-sm"
+          >
+            <span className="flex items-center gap-2 min-w-0">
+              <span
+                className={`h-2 w-2 shrink-0 rounded-full ${
+                  running ? 'bg-green-500' : 'bg-muted-foreground/40'
+                }`}
+              />
+              <span className="truncate font-mono">{dev.name}</span>
+              {running && (
+                <span className="text-xs text-muted-foreground">
+                  {t('preview.devServers.running')}
+                </span>
+              )}
+            </span>
+            <Button
+              variant="ghost"
+              size="sm"
+              onClick={() =>
+                running ? onStop(running.id) : onStart(dev.name)
+              }
+              disabled={running ? isStopping : isStarting}
+              title={
+                running
+                  ? t('preview.devServers.stop', { name: dev.name })
+                  : t('preview.devServers.start', { name: dev.name })
+              }
+            >
+              {(running ? isStopping : isStarting) ? (
+                <Loader2 className="h-3 w-3 animate-spin" />
+              ) : running ? (
+                <Square className="h-3 w-3" />
+              ) : (
+                <Play className="h-3 w-3" />
+              )}
+            </Button>
+          </div>
+        );
+      })}
+    </div>
+  );
+}
@@ -19,6 +19,7 @@ import {
 } from '@/utils/script-placeholders';
 import { useUserSystem } from '@/components/config-provider';
 import { useProjectMutations } from '@/hooks/useProjectMutations';
+import { projectsApi } from '@/lib/api';
 import { useTaskMutations } from '@/hooks/useTaskMutations';
 import {
   COMPANION_INSTALL_TASK_TITLE,
@@ -46,6 +47,10 @@ export function NoServerContent({
   const [devScriptInput, setDevScriptInput] = useState('');
   const [saveError, setSaveError] = useState<string | null>(null);
   const [isEditingExistingScript, setIsEditingExistingScript] = useState(false);
+  const [isStoppingOthers, setIsStoppingOthers] = useState(false);
+  const [stopOthersMessage, setStopOthersMessage] = useState<string | null>(
+    null
+  );
   const { system, config } = useUserSystem();
 
   const { updateProject } = useProjectMutations({
@@ -95,6 +100,17 @@ export function NoServerContent({
           cleanup_script: project.cleanup_script ?? null,
           copy_files: project.copy_files ?? null,
           worktree_dir: project.worktree_dir ?? null,
+          push_remote: project.push_remote ?? null,
+          pr_remote: project.pr_remote ?? null,
+          always_run_cleanup: project.always_run_cleanup,
+          setup_cache_files: project.setup_cache_files ?? null,
+          setup_cache_artifacts: project.setup_cache_artifacts ?? null,
+          script_language: project.script_language ?? null,
+          preview_url_pattern: project.preview_url_pattern ?? null,
+          env_file: project.env_file ?? null,
+          env_file_required: project.env_file_required,
+          dev_scripts: null,
+          reuse_worktree: project.reuse_worktree,
         },
       },
       {
@@ -121,6 +137,24 @@ export function NoServerContent({
     setSaveError(null);
   };
 
+  const handleStopOtherDevServers = async () => {
+    if (!project) return;
+    setIsStoppingOthers(true);
+    setStopOthersMessage(null);
+    try {
+      const stopped = await projectsApi.stopOtherDevServers(project.id);
+      setStopOthersMessage(
+        t('preview.noServer.stopOthersResult', { count: stopped.length })
+      );
+    } catch (err) {
+      setStopOthersMessage(
+        (err as Error)?.message || t('preview.noServer.stopOthersError')
+      );
+    } finally {
+      setIsStoppingOthers(false);
+    }
+  };
+
   const handleInstallCompanion = () => {
     if (!project || !config) return;
 
@@ -197,6 +231,19 @@ export function NoServerContent({
                   {t('preview.noServer.editButton')}
                 </Button>
               )}
+
+              {!runningDevServer && project && (
+                <Button
+                  size="sm"
+                  variant="outline"
+                  onClick={handleStopOtherDevServers}
+                  disabled={isStoppingOthers}
+                  className="gap-1"
+                >
+                  <Square className="h-3 w-3" />
+                  {t('preview.noServer.stopOthersButton')}
+                </Button>
+              )}
             </div>
           ) : (
             <div className="text-left">
@@ -267,6 +314,10 @@ export function NoServerContent({
             </div>
           )}
 
+          {stopOthersMessage && (
+            <p className="text-sm text-muted-foreground">{stopOthersMessage}</p>
+          )}
+
           <div className="space-y-4 pt-6 border-t border-border">
             <p className="text-sm text-muted-foreground">
               {t('preview.noServer.companionPrompt')}
@@ -7,7 +7,11 @@ import {
   KanbanProvider,
 } from '@/components/ui/shadcn-io/kanban';
 import { TaskCard } from './TaskCard';
-import type { TaskStatus, TaskWithAttemptStatus } from 'shared/types';
+import type {
+  AttemptProgressStage,
+  TaskStatus,
+  TaskWithAttemptStatus,
+} from 'shared/types';
 // import { useParams } from 'react-router-dom';
 
 import { statusBoardColors, statusLabels } from '@/utils/status-labels';
@@ -20,6 +24,7 @@ interface TaskKanbanBoardProps {
   onViewTaskDetails: (task: Task) => void;
   selectedTask?: Task;
   onCreateTask?: () => void;
+  progressByTaskId?: Record<string, AttemptProgressStage>;
 }
 
 function TaskKanbanBoard({
@@ -28,6 +33,7 @@ function TaskKanbanBoard({
   onViewTaskDetails,
   selectedTask,
   onCreateTask,
+  progressByTaskId,
 }: TaskKanbanBoardProps) {
   return (
     <KanbanProvider onDragEnd={onDragEnd}>
@@ -47,6 +53,7 @@ function TaskKanbanBoard({
                 status={status}
                 onViewDetails={onViewTaskDetails}
                 isOpen={selectedTask?.id === task.id}
+                progress={progressByTaskId?.[task.id]}
               />
             ))}
           </KanbanCards>
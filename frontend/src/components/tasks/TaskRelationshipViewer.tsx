@@ -1,4 +1,4 @@
-import { useEffect, useState } from 'react';
+import { useEffect, useMemo, useState } from 'react';
 import { Card } from '@/components/ui/card';
 import { TaskRelationshipCard } from './TaskRelationshipCard';
 import { attemptsApi } from '@/lib/api';
@@ -66,7 +66,9 @@ export function TaskRelationshipViewer({
       return;
     }
 
-    if (task?.parent_task_attempt && tasksById) {
+    if (task?.parent_task_id && tasksById) {
+      setParentTask(tasksById[task.parent_task_id] || null);
+    } else if (task?.parent_task_attempt && tasksById) {
       attemptsApi
         .get(task.parent_task_attempt)
         .then((parentAttempt) => {
@@ -78,10 +80,28 @@ export function TaskRelationshipViewer({
     } else {
       setParentTask(null);
     }
-  }, [selectedAttempt?.id, task?.parent_task_attempt, tasksById]);
+  }, [
+    selectedAttempt?.id,
+    task?.parent_task_id,
+    task?.parent_task_attempt,
+    tasksById,
+  ]);
 
   const displayParentTask = relationships?.parent_task || parentTask;
-  const childTasks = relationships?.children || [];
+  // Subtasks split from this task may also be linked through the attempt
+  const childTasks = useMemo(() => {
+    const subtasks = relationships
+      ? relationships.subtasks
+      : Object.values(tasksById ?? {}).filter(
+          (t) => task && t.parent_task_id === task.id
+        );
+    const seen = new Set<string>();
+    return [...(relationships?.children ?? []), ...subtasks].filter((t) => {
+      if (seen.has(t.id)) return false;
+      seen.add(t.id);
+      return true;
+    });
+  }, [relationships, tasksById, task]);
   const hasParent = displayParentTask !== null;
   const hasChildren = childTasks.length > 0;
 
@@ -81,12 +81,23 @@ function GitOperations({
   const [rebasing, setRebasing] = useState(false);
   const [mergeSuccess, setMergeSuccess] = useState(false);
   const [pushSuccess, setPushSuccess] = useState(false);
+  // Retargeting leaves commits in place; warn while the branch lags the target
+  const [retargetWarning, setRetargetWarning] = useState<{
+    branch: string;
+    behind: number;
+  } | null>(null);
 
   // Target branch change handlers
   const handleChangeTargetBranchClick = async (newBranch: string) => {
     await changeTargetBranchMutation
       .mutateAsync(newBranch)
-      .then(() => setError(null))
+      .then((data) => {
+        setError(null);
+        const behind = data.status[1];
+        setRetargetWarning(
+          behind > 0 ? { branch: data.new_target_branch, behind } : null
+        );
+      })
       .catch((error) => {
         setError(error.message || t('git.errors.changeTargetBranch'));
       });
@@ -212,7 +223,10 @@ function GitOperations({
         newBaseBranch: newBaseBranch,
         oldBaseBranch: selectedUpstream,
       })
-      .then(() => setError(null))
+      .then(() => {
+        setError(null);
+        setRetargetWarning(null);
+      })
       .catch((err: Err<GitOperationError>) => {
         const data = err?.error;
         const isConflict =
@@ -498,6 +512,15 @@ function GitOperations({
           </div>
         )}
       </div>
+      {retargetWarning && (
+        <div className="mt-2 flex items-center gap-1 text-xs text-amber-700 dark:text-amber-300">
+          <AlertTriangle className="h-3.5 w-3.5 shrink-0" />
+          {t('git.warnings.targetNotMoved', {
+            count: retargetWarning.behind,
+            branch: retargetWarning.branch,
+          })}
+        </div>
+      )}
     </div>
   );
 }
@@ -14,6 +14,7 @@ import { useOpenInEditor } from '@/hooks/useOpenInEditor';
 import NiceModal from '@ebay/nice-modal-react';
 import { useProject } from '@/contexts/project-context';
 import { openTaskForm } from '@/lib/openTaskForm';
+import { tasksApi } from '@/lib/api';
 
 interface ActionsDropdownProps {
   task?: TaskWithAttemptStatus | null;
@@ -50,6 +51,17 @@ export function ActionsDropdown({ task, attempt }: ActionsDropdownProps) {
     }
   };
 
+  // Higher priority tasks start first in the attempt queue
+  const handleSetPriority = async (e: React.MouseEvent, priority: number) => {
+    e.stopPropagation();
+    if (!task) return;
+    try {
+      await tasksApi.setPriority(task.id, priority);
+    } catch (err) {
+      console.error('Failed to set task priority:', err);
+    }
+  };
+
   const handleOpenInEditor = (e: React.MouseEvent) => {
     e.stopPropagation();
     if (!attempt?.id) return;
@@ -147,6 +159,24 @@ export function ActionsDropdown({ task, attempt }: ActionsDropdownProps) {
               <DropdownMenuItem disabled={!projectId} onClick={handleDuplicate}>
                 {t('actionsMenu.duplicate')}
               </DropdownMenuItem>
+              {task && (
+                <>
+                  <DropdownMenuItem
+                    onClick={(e) => handleSetPriority(e, task.priority + 1)}
+                  >
+                    {t('actionsMenu.raisePriority', {
+                      priority: task.priority,
+                    })}
+                  </DropdownMenuItem>
+                  <DropdownMenuItem
+                    onClick={(e) => handleSetPriority(e, task.priority - 1)}
+                  >
+                    {t('actionsMenu.lowerPriority', {
+                      priority: task.priority,
+                    })}
+                  </DropdownMenuItem>
+                </>
+              )}
               <DropdownMenuItem
                 disabled={!projectId}
                 onClick={handleDelete}
@@ -2,6 +2,9 @@ export { useBranchStatus } from './useBranchStatus';
 export { useAttemptExecution } from './useAttemptExecution';
 export { useOpenInEditor } from './useOpenInEditor';
 export { useDevServer } from './useDevServer';
+export { useExecutorCapabilities } from './useExecutorCapabilities';
+export { useFileReviews } from './useFileReviews';
+export { usePromptSnippets } from './usePromptSnippets';
 export { useRebase } from './useRebase';
 export { useChangeTargetBranch } from './useChangeTargetBranch';
 export { useMerge } from './useMerge';
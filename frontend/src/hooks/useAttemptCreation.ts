@@ -4,7 +4,8 @@ import type { TaskAttempt, ExecutorProfileId } from 'shared/types';
 
 type CreateAttemptArgs = {
   profile: ExecutorProfileId;
-  baseBranch: string;
+  baseBranch?: string;
+  forceSetup?: boolean;
 };
 
 type UseAttemptCreationArgs = {
@@ -19,11 +20,12 @@ export function useAttemptCreation({
   const queryClient = useQueryClient();
 
   const mutation = useMutation({
-    mutationFn: ({ profile, baseBranch }: CreateAttemptArgs) =>
+    mutationFn: ({ profile, baseBranch, forceSetup }: CreateAttemptArgs) =>
       attemptsApi.create({
         task_id: taskId,
         executor_profile_id: profile,
         base_branch: baseBranch,
+        force_setup: forceSetup,
       }),
     onSuccess: (newAttempt: TaskAttempt) => {
       queryClient.setQueryData(
@@ -1,10 +1,14 @@
 import { useQuery } from '@tanstack/react-query';
 import { attemptsApi } from '@/lib/api';
 
-export function useBranchStatus(attemptId?: string) {
+export function useBranchStatus(
+  attemptId?: string,
+  options?: { includeRemote?: boolean }
+) {
+  const includeRemote = options?.includeRemote ?? false;
   return useQuery({
-    queryKey: ['branchStatus', attemptId],
-    queryFn: () => attemptsApi.getBranchStatus(attemptId!),
+    queryKey: ['branchStatus', attemptId, includeRemote],
+    queryFn: () => attemptsApi.getBranchStatus(attemptId!, { includeRemote }),
     enabled: !!attemptId,
     // Poll faster to promptly reflect rebase/abort transitions
     refetchInterval: 5000,
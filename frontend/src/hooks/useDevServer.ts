@@ -4,6 +4,15 @@ import { attemptsApi, executionProcessesApi } from '@/lib/api';
 import { useAttemptExecution } from '@/hooks/useAttemptExecution';
 import type { ExecutionProcess } from 'shared/types';
 
+/**
+ * Name of the project dev server a process runs; `null` for the main
+ * `dev_script`
+ */
+export function devServerName(process: ExecutionProcess): string | null {
+  const action = process.executor_action.typ;
+  return action.type === 'ScriptRequest' ? (action.name ?? null) : null;
+}
+
 interface UseDevServerOptions {
   onStartSuccess?: () => void;
   onStartError?: (err: unknown) => void;
@@ -26,6 +35,14 @@ export function useDevServer(
     );
   }, [attemptData.processes]);
 
+  // Every running dev server, named ones included
+  const runningDevServers = useMemo<ExecutionProcess[]>(() => {
+    return attemptData.processes.filter(
+      (process) =>
+        process.run_reason === 'devserver' && process.status === 'running'
+    );
+  }, [attemptData.processes]);
+
   // Find latest dev server process (for logs viewing)
   const latestDevServerProcess = useMemo<ExecutionProcess | undefined>(() => {
     return [...attemptData.processes]
@@ -39,9 +56,9 @@ export function useDevServer(
   // Start mutation
   const startMutation = useMutation({
     mutationKey: ['startDevServer', attemptId],
-    mutationFn: async () => {
+    mutationFn: async (name?: string) => {
       if (!attemptId) return;
-      await attemptsApi.startDevServer(attemptId);
+      await attemptsApi.startDevServer(attemptId, name);
     },
     onSuccess: async () => {
       await queryClient.invalidateQueries({
@@ -81,12 +98,34 @@ export function useDevServer(
     },
   });
 
+  // Stop one dev server among several, e.g. a named one
+  const stopProcessMutation = useMutation({
+    mutationFn: (processId: string) =>
+      executionProcessesApi.stopExecutionProcess(processId),
+    onSuccess: async (_, processId) => {
+      await Promise.all([
+        queryClient.invalidateQueries({
+          queryKey: ['executionProcesses', attemptId],
+        }),
+        queryClient.invalidateQueries({
+          queryKey: ['processDetails', processId],
+        }),
+      ]);
+    },
+    onError: (err) => {
+      console.error('Failed to stop dev server:', err);
+      options?.onStopError?.(err);
+    },
+  });
+
   return {
     start: startMutation.mutate,
     stop: stopMutation.mutate,
+    stopProcess: stopProcessMutation.mutate,
     isStarting: startMutation.isPending,
-    isStopping: stopMutation.isPending,
+    isStopping: stopMutation.isPending || stopProcessMutation.isPending,
     runningDevServer,
+    runningDevServers,
     latestDevServerProcess,
   };
 }
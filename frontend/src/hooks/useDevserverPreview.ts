@@ -51,6 +51,22 @@ export function useDevserverPreview(
     )[0];
   }, [executionProcesses]);
 
+  // Prefer the URL the backend detected from the dev server's output
+  const knownUrl = useMemo(() => {
+    if (!selectedProcess?.preview_url) return lastKnownUrl;
+    try {
+      const parsed = new URL(selectedProcess.preview_url);
+      return {
+        url: parsed.toString(),
+        port: parsed.port ? Number(parsed.port) : undefined,
+        scheme:
+          parsed.protocol === 'https:' ? ('https' as const) : ('http' as const),
+      };
+    } catch {
+      return lastKnownUrl;
+    }
+  }, [selectedProcess?.preview_url, lastKnownUrl]);
+
   useEffect(() => {
     if (processesError) {
       setState((prev) => ({ ...prev, status: 'error' }));
@@ -67,22 +83,22 @@ export function useDevserverPreview(
       return;
     }
 
-    if (lastKnownUrl) {
+    if (knownUrl) {
       setState((prev) => {
         if (
           prev.status === 'ready' &&
-          prev.url === lastKnownUrl.url &&
-          prev.port === lastKnownUrl.port &&
-          prev.scheme === lastKnownUrl.scheme
+          prev.url === knownUrl.url &&
+          prev.port === knownUrl.port &&
+          prev.scheme === knownUrl.scheme
         ) {
           return prev;
         }
 
         return {
           status: 'ready',
-          url: lastKnownUrl.url,
-          port: lastKnownUrl.port,
-          scheme: lastKnownUrl.scheme ?? 'http',
+          url: knownUrl.url,
+          port: knownUrl.port,
+          scheme: knownUrl.scheme ?? 'http',
         };
       });
       return;
@@ -94,7 +110,7 @@ export function useDevserverPreview(
       url: undefined,
       port: undefined,
     }));
-  }, [processesError, selectedProcess, lastKnownUrl, projectHasDevScript]);
+  }, [processesError, selectedProcess, knownUrl, projectHasDevScript]);
 
   useEffect(() => {
     setState({
@@ -1,4 +1,5 @@
 import { useCallback, useMemo } from 'react';
+import { useQuery } from '@tanstack/react-query';
 import type { Diff, PatchType } from 'shared/types';
 import { useJsonPatchWsStream } from './useJsonPatchWsStream';
 
@@ -12,6 +13,10 @@ type DiffStreamEvent = {
 
 export interface UseDiffStreamOptions {
   statsOnly?: boolean;
+  /** Only changes since the attempt's reviewed commit (full diff when unset) */
+  sinceReviewed?: boolean;
+  /** Path globs to include; `!`-prefixed globs exclude (e.g. `['src/**', '!**/*.lock']`) */
+  pathFilter?: string[];
 }
 
 interface UseDiffStreamResult {
@@ -27,15 +32,29 @@ export const useDiffStream = (
   const endpoint = (() => {
     if (!attemptId) return undefined;
     const query = `/api/task-attempts/${attemptId}/diff/ws`;
+    const params = new URLSearchParams();
     if (typeof options?.statsOnly === 'boolean') {
-      const params = new URLSearchParams();
       params.set('stats_only', String(options.statsOnly));
-      return `${query}?${params.toString()}`;
-    } else {
-      return query;
     }
+    if (options?.sinceReviewed) {
+      params.set('since_reviewed', 'true');
+    }
+    if (options?.pathFilter?.length) {
+      params.set('path_filter', options.pathFilter.join(','));
+    }
+    const search = params.toString();
+    return search ? `${query}?${search}` : query;
   })();
 
+  // Rebases and merges move the diff's base commit; invalidating this key
+  // restarts the stream against the new base
+  const { isFetching: baseMoving } = useQuery({
+    queryKey: ['diffStream', attemptId],
+    queryFn: async () => null,
+    enabled: !!attemptId,
+    staleTime: Infinity,
+  });
+
   const initialData = useCallback(
     (): DiffStreamEvent => ({
       entries: {},
@@ -45,7 +64,7 @@ export const useDiffStream = (
 
   const { data, error } = useJsonPatchWsStream<DiffStreamEvent>(
     endpoint,
-    enabled && !!attemptId,
+    enabled && !!attemptId && !baseMoving,
     initialData
     // No need for injectInitialEntry or deduplicatePatches for diffs
   );
@@ -0,0 +1,23 @@
+import { useMemo } from 'react';
+import { useQuery } from '@tanstack/react-query';
+import { configApi } from '@/lib/api';
+import type { BaseCodingAgent, ExecutorCapabilities } from 'shared/types';
+
+// Feature matrix of the configured coding agents, keyed by executor
+export function useExecutorCapabilities() {
+  const query = useQuery({
+    queryKey: ['executorCapabilities'],
+    queryFn: () => configApi.getExecutorCapabilities(),
+    staleTime: 1000 * 60 * 5,
+  });
+
+  const byExecutor = useMemo(() => {
+    const map: Partial<Record<BaseCodingAgent, ExecutorCapabilities>> = {};
+    for (const entry of query.data ?? []) {
+      map[entry.executor] = entry;
+    }
+    return map;
+  }, [query.data]);
+
+  return { ...query, byExecutor };
+}
@@ -0,0 +1,52 @@
+import { useEffect, useMemo } from 'react';
+import { useMutation, useQuery, useQueryClient } from '@tanstack/react-query';
+import { attemptsApi } from '@/lib/api';
+import type { FileReviewStatus } from 'shared/types';
+
+const toReviewedPaths = (statuses: FileReviewStatus[]) =>
+  statuses.filter((status) => status.reviewed).map((status) => status.path);
+
+/**
+ * Files of an attempt's diff checked off during review. Other reviewers' changes,
+ * and reviews reset by new edits, arrive through the project's tasks stream as
+ * `liveReviewedPaths`.
+ */
+export function useFileReviews(
+  attemptId: string | undefined,
+  liveReviewedPaths?: string[]
+) {
+  const queryClient = useQueryClient();
+  const queryKey = useMemo(() => ['fileReviews', attemptId], [attemptId]);
+
+  const query = useQuery({
+    queryKey,
+    queryFn: async () =>
+      toReviewedPaths(await attemptsApi.getFileReviews(attemptId!)),
+    enabled: !!attemptId,
+  });
+
+  useEffect(() => {
+    if (liveReviewedPaths) {
+      queryClient.setQueryData(queryKey, liveReviewedPaths);
+    }
+  }, [liveReviewedPaths, queryClient, queryKey]);
+
+  const mutation = useMutation({
+    mutationFn: ({ path, reviewed }: { path: string; reviewed: boolean }) =>
+      attemptsApi.setFileReviewed(attemptId!, { path, reviewed }),
+    onSuccess: (statuses) => {
+      queryClient.setQueryData(queryKey, toReviewedPaths(statuses));
+    },
+    onError: (err) => {
+      console.error('Failed to update file review:', err);
+    },
+  });
+
+  const reviewedPaths = useMemo(() => new Set(query.data ?? []), [query.data]);
+
+  return {
+    reviewedPaths,
+    setReviewed: mutation.mutate,
+    refresh: query.refetch,
+  };
+}
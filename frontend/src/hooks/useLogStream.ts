@@ -1,5 +1,5 @@
 import { useEffect, useState, useRef } from 'react';
-import type { PatchType } from 'shared/types';
+import type { LogChannel, PatchType } from 'shared/types';
 
 type LogEntry = Extract<PatchType, { type: 'STDOUT' } | { type: 'STDERR' }>;
 
@@ -8,7 +8,10 @@ interface UseLogStreamResult {
   error: string | null;
 }
 
-export const useLogStream = (processId: string): UseLogStreamResult => {
+export const useLogStream = (
+  processId: string,
+  channel?: LogChannel
+): UseLogStreamResult => {
   const [logs, setLogs] = useState<LogEntry[]>([]);
   const [error, setError] = useState<string | null>(null);
   const wsRef = useRef<WebSocket | null>(null);
@@ -28,8 +31,10 @@ export const useLogStream = (processId: string): UseLogStreamResult => {
     const open = () => {
       const protocol = window.location.protocol === 'https:' ? 'wss:' : 'ws:';
       const host = window.location.host;
+      // The server drops the other channel when one is requested
+      const query = channel ? `?channel=${channel}` : '';
       const ws = new WebSocket(
-        `${protocol}//${host}/api/execution-processes/${processId}/raw-logs/ws`
+        `${protocol}//${host}/api/execution-processes/${processId}/raw-logs/ws${query}`
       );
       wsRef.current = ws;
       isIntentionallyClosed.current = false;
@@ -106,7 +111,7 @@ export const useLogStream = (processId: string): UseLogStreamResult => {
         retryTimerRef.current = null;
       }
     };
-  }, [processId]);
+  }, [processId, channel]);
 
   return { logs, error };
 };
@@ -1,14 +1,27 @@
-import { useCallback } from 'react';
+import { useCallback, useEffect, useRef } from 'react';
+import { useQueryClient } from '@tanstack/react-query';
 import { useJsonPatchWsStream } from './useJsonPatchWsStream';
-import type { TaskWithAttemptStatus } from 'shared/types';
+import type {
+  AttemptFileReviewUpdate,
+  AttemptGitUpdate,
+  AttemptProgress,
+  AttemptProgressStage,
+  LabelMatch,
+  TaskWithAttemptStatus,
+} from 'shared/types';
 
 type TasksState = {
   tasks: Record<string, TaskWithAttemptStatus>;
+  attempt_progress: Record<string, AttemptProgress>;
+  attempt_git: Record<string, AttemptGitUpdate>;
+  attempt_file_reviews: Record<string, AttemptFileReviewUpdate>;
 };
 
 interface UseProjectTasksResult {
   tasks: TaskWithAttemptStatus[];
   tasksById: Record<string, TaskWithAttemptStatus>;
+  progressByTaskId: Record<string, AttemptProgressStage>;
+  reviewedPathsByAttemptId: Record<string, string[]>;
   isLoading: boolean;
   isConnected: boolean;
   error: string | null;
@@ -18,11 +31,34 @@ interface UseProjectTasksResult {
  * Stream tasks for a project via WebSocket (JSON Patch) and expose as array + map.
  * Server sends initial snapshot: replace /tasks with an object keyed by id.
  * Live updates arrive at /tasks/<id> via add/replace/remove operations.
+ * Attempts being prepared report their stage at /attempt_progress/<attempt_id>.
+ * Rebases and merges report the new branch state at /attempt_git/<attempt_id>,
+ * which refreshes that attempt's branch status, diff and commit details right
+ * away.
+ * Files checked off during review are shared at /attempt_file_reviews/<attempt_id>.
+ * With a label filter, only matching tasks are streamed; a task whose labels
+ * stop matching is removed.
  */
-export const useProjectTasks = (projectId: string): UseProjectTasksResult => {
-  const endpoint = `/api/tasks/stream/ws?project_id=${encodeURIComponent(projectId)}`;
+export const useProjectTasks = (
+  projectId: string,
+  labelFilter?: { labelIds: string[]; match?: LabelMatch }
+): UseProjectTasksResult => {
+  const params = new URLSearchParams({ project_id: projectId });
+  if (labelFilter && labelFilter.labelIds.length > 0) {
+    params.set('labels', labelFilter.labelIds.join(','));
+    params.set('label_match', labelFilter.match ?? 'all');
+  }
+  const endpoint = `/api/tasks/stream/ws?${params.toString()}`;
 
-  const initialData = useCallback((): TasksState => ({ tasks: {} }), []);
+  const initialData = useCallback(
+    (): TasksState => ({
+      tasks: {},
+      attempt_progress: {},
+      attempt_git: {},
+      attempt_file_reviews: {},
+    }),
+    []
+  );
 
   const { data, isConnected, error } = useJsonPatchWsStream(
     endpoint,
@@ -36,7 +72,43 @@ export const useProjectTasks = (projectId: string): UseProjectTasksResult => {
       new Date(b.created_at as unknown as string).getTime() -
       new Date(a.created_at as unknown as string).getTime()
   );
+  const progressByTaskId: Record<string, AttemptProgressStage> = {};
+  for (const progress of Object.values(data?.attempt_progress ?? {})) {
+    progressByTaskId[progress.task_id] = progress.stage;
+  }
+  const reviewedPathsByAttemptId: Record<string, string[]> = {};
+  for (const update of Object.values(data?.attempt_file_reviews ?? {})) {
+    reviewedPathsByAttemptId[update.task_attempt_id] = update.reviewed_paths;
+  }
   const isLoading = !data && !error; // until first snapshot
 
-  return { tasks, tasksById, isLoading, isConnected, error };
+  const queryClient = useQueryClient();
+  const seenGitUpdates = useRef<Record<string, string>>({});
+  const gitUpdates = data?.attempt_git;
+  useEffect(() => {
+    for (const update of Object.values(gitUpdates ?? {})) {
+      const key = `${update.operation}:${update.head_oid}:${update.target_oid}`;
+      if (seenGitUpdates.current[update.task_attempt_id] === key) continue;
+      seenGitUpdates.current[update.task_attempt_id] = key;
+      for (const queryKey of [
+        ['branchStatus', update.task_attempt_id],
+        ['diffStream', update.task_attempt_id],
+        ['taskAttempt', update.task_attempt_id],
+        ['attempt', update.task_attempt_id],
+        ['attemptBranch', update.task_attempt_id],
+      ]) {
+        queryClient.invalidateQueries({ queryKey });
+      }
+    }
+  }, [gitUpdates, queryClient]);
+
+  return {
+    tasks,
+    tasksById,
+    progressByTaskId,
+    reviewedPathsByAttemptId,
+    isLoading,
+    isConnected,
+    error,
+  };
 };
@@ -0,0 +1,53 @@
+import { useMutation, useQuery, useQueryClient } from '@tanstack/react-query';
+import { promptSnippetsApi } from '@/lib/api';
+import type {
+  CreatePromptSnippet,
+  PromptSnippet,
+  UpdatePromptSnippet,
+} from 'shared/types';
+
+/**
+ * Global prompt snippets, plus the given project's own snippets
+ */
+export function usePromptSnippets(projectId?: string) {
+  const queryClient = useQueryClient();
+  const queryKey = ['promptSnippets', projectId ?? null];
+
+  const query = useQuery<PromptSnippet[]>({
+    queryKey,
+    queryFn: () => promptSnippetsApi.list(projectId),
+  });
+
+  const invalidate = () =>
+    queryClient.invalidateQueries({ queryKey: ['promptSnippets'] });
+
+  const createSnippet = useMutation({
+    mutationFn: (data: CreatePromptSnippet) => promptSnippetsApi.create(data),
+    onSuccess: invalidate,
+  });
+
+  const updateSnippet = useMutation({
+    mutationFn: ({
+      snippetId,
+      data,
+    }: {
+      snippetId: string;
+      data: UpdatePromptSnippet;
+    }) => promptSnippetsApi.update(snippetId, data),
+    onSuccess: invalidate,
+  });
+
+  const deleteSnippet = useMutation({
+    mutationFn: (snippetId: string) => promptSnippetsApi.delete(snippetId),
+    onSuccess: invalidate,
+  });
+
+  return {
+    snippets: query.data ?? [],
+    isLoading: query.isLoading,
+    error: query.error,
+    createSnippet,
+    updateSnippet,
+    deleteSnippet,
+  };
+}
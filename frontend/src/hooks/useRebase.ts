@@ -14,17 +14,19 @@ export function useRebase(
   type RebaseMutationArgs = {
     newBaseBranch?: string;
     oldBaseBranch?: string;
+    ontoCommit?: string;
   };
 
   return useMutation<void, Result<void, GitOperationError>, RebaseMutationArgs>(
     {
       mutationFn: (args) => {
         if (!attemptId) return Promise.resolve();
-        const { newBaseBranch, oldBaseBranch } = args ?? {};
+        const { newBaseBranch, oldBaseBranch, ontoCommit } = args ?? {};
 
         const data: RebaseTaskAttemptRequest = {
           old_base_branch: oldBaseBranch ?? null,
           new_base_branch: newBaseBranch ?? null,
+          onto_commit: ontoCommit ?? null,
         };
 
         return attemptsApi.rebase(attemptId, data).then((res) => {
@@ -7,6 +7,20 @@ export const taskAttemptKeys = {
   byTask: (taskId: string | undefined) => ['taskAttempts', taskId] as const,
 };
 
+// Largest page the attempts listing serves
+const PAGE_SIZE = 500;
+
+async function fetchAllAttempts(taskId: string): Promise<TaskAttempt[]> {
+  const attempts: TaskAttempt[] = [];
+  let offset: number | null = 0;
+  while (offset !== null) {
+    const page = await attemptsApi.getAll(taskId, { limit: PAGE_SIZE, offset });
+    attempts.push(...page.items);
+    offset = page.next_offset;
+  }
+  return attempts;
+}
+
 type Options = {
   enabled?: boolean;
   refetchInterval?: number | false;
@@ -18,7 +32,7 @@ export function useTaskAttempts(taskId?: string, opts?: Options) {
 
   return useQuery<TaskAttempt[]>({
     queryKey: taskAttemptKeys.byTask(taskId),
-    queryFn: () => attemptsApi.getAll(taskId!),
+    queryFn: () => fetchAllAttempts(taskId!),
     enabled,
     refetchInterval,
   });
@@ -67,6 +67,7 @@ export function useWorkflowStations(options: UseWorkflowStationsOptions = {}) {
         agent_id: data.agent_id,
         station_prompt: data.station_prompt,
         output_context_keys: data.output_context_keys,
+        output_schema: data.output_schema,
         is_terminator: data.is_terminator ?? false,
         created_at: new Date().toISOString(),
         updated_at: new Date().toISOString(),
@@ -150,6 +151,10 @@ export function useWorkflowStations(options: UseWorkflowStationsOptions = {}) {
                     data.output_context_keys !== undefined
                       ? data.output_context_keys
                       : station.output_context_keys,
+                  output_schema:
+                    data.output_schema !== undefined
+                      ? data.output_schema
+                      : station.output_schema,
                   is_terminator: data.is_terminator ?? station.is_terminator,
                 }
               : station
@@ -3,6 +3,7 @@
 import {
   Agent,
   ApprovalStatus,
+  ApiErrorCode,
   ApiResponse,
   BranchStatus,
   CheckTokenResponse,
@@ -15,19 +16,45 @@ import {
   CreateAndStartTaskRequest,
   CreateTaskAttemptBody,
   CreateTag,
+  CreateLabel,
+  CreatePromptSnippet,
   DeviceFlowStartResponse,
   DevicePollStatus,
   DirectoryListResponse,
   DirectoryEntry,
   EditorType,
+  ExecCommandRequest,
+  ExecCommandResult,
+  DiffSummaryResult,
+  MergeResult,
   ExecutionProcess,
+  ExecutorCapabilities,
+  RunningProcessInfo,
+  StopRunningProcessesRequest,
+  FollowUpFromCommitRequest,
+  FollowUpFromCommitResult,
   GitBranch,
+  Label,
+  LabelMatch,
+  Page,
   Project,
   CreateProject,
+  PromptSnippet,
   RepositoryInfo,
+  ResetAttemptRequest,
+  ResetAttemptResult,
+  RecoverAttemptRequest,
+  RecoverAttemptResult,
   SearchResult,
+  SetDefaultBaseBranchRequest,
+  SplitTaskRequest,
+  SplitTaskResponse,
+  StoppedDevServer,
+  RunningDevServer,
   Task,
   TaskAttempt,
+  TaskEvent,
+  TaskPriority,
   TaskRelationships,
   Tag,
   TagSearchParams,
@@ -36,6 +63,8 @@ import {
   UpdateProject,
   UpdateTask,
   UpdateTag,
+  UpdateLabel,
+  UpdatePromptSnippet,
   UserSystemInfo,
   GitHubServiceError,
   UpdateRetryFollowUpDraftRequest,
@@ -43,6 +72,9 @@ import {
   UpdateMcpServersBody,
   GetMcpServerResponse,
   ImageResponse,
+  OrphanCleanupReport,
+  RepoConfigStatus,
+  TrustRepoConfigRequest,
   DraftResponse,
   UpdateFollowUpDraftRequest,
   GitOperationError,
@@ -50,6 +82,12 @@ import {
   RebaseTaskAttemptRequest,
   ChangeTargetBranchRequest,
   ChangeTargetBranchResponse,
+  MarkReviewedRequest,
+  FileReviewStatus,
+  SetFileReviewedRequest,
+  UpdateAttemptNotesRequest,
+  MoveQueuedAttemptRequest,
+  QueuedAttempt,
   Workflow,
   CreateWorkflow,
   UpdateWorkflow,
@@ -59,10 +97,14 @@ import {
   StationTransition,
   CreateStationTransition,
   UpdateStationTransition,
+  DryRunTransitionConditionRequest,
+  DryRunTransitionConditionResponse,
   ExecuteWorkflowRequest,
   ExecuteWorkflowResponse,
+  WorkflowExecution,
   WorkflowExecutionDetailsResponse,
   StationExecutionSummary,
+  WorkflowExecutionHistoryResponse,
   CancelWorkflowExecutionRequest,
   CancelWorkflowExecutionResponse,
   RetryStationRequest,
@@ -76,20 +118,24 @@ export type {
   UpdateRetryFollowUpDraftRequest,
 } from 'shared/types';
 
-class ApiError<E = unknown> extends Error {
+export class ApiError<E = unknown> extends Error {
   public status?: number;
   public error_data?: E;
+  // Stable code to branch on; `message` is for display only
+  public error_code?: ApiErrorCode;
 
   constructor(
     message: string,
     public statusCode?: number,
     public response?: Response,
-    error_data?: E
+    error_data?: E,
+    error_code?: ApiErrorCode
   ) {
     super(message);
     this.name = 'ApiError';
     this.status = statusCode;
     this.error_data = error_data;
+    this.error_code = error_code;
   }
 }
 
@@ -135,12 +181,14 @@ const handleApiResponseAsResult = async <T, E>(
   if (!response.ok) {
     // HTTP error - no structured error data
     let errorMessage = `Request failed with status ${response.status}`;
+    let errorCode: ApiErrorCode | undefined;
 
     try {
       const errorData = await response.json();
       if (errorData.message) {
         errorMessage = errorData.message;
       }
+      errorCode = errorData.error_code ?? undefined;
     } catch {
       errorMessage = response.statusText || errorMessage;
     }
@@ -168,12 +216,16 @@ const handleApiResponseAsResult = async <T, E>(
 const handleApiResponse = async <T, E = T>(response: Response): Promise<T> => {
   if (!response.ok) {
     let errorMessage = `Request failed with status ${response.status}`;
+    let errorCode: ApiErrorCode | undefined;
+    let requestId: string | undefined;
 
     try {
       const errorData = await response.json();
       if (errorData.message) {
         errorMessage = errorData.message;
       }
+      errorCode = errorData.error_code ?? undefined;
+      requestId = errorData.request_id ?? undefined;
     } catch {
       // Fallback to status text if JSON parsing fails
       errorMessage = response.statusText || errorMessage;
@@ -182,11 +234,19 @@ const handleApiResponse = async <T, E = T>(response: Response): Promise<T> => {
     console.error('[API Error]', {
       message: errorMessage,
       status: response.status,
+      error_code: errorCode,
+      request_id: requestId,
       response,
       endpoint: response.url,
       timestamp: new Date().toISOString(),
     });
-    throw new ApiError<E>(errorMessage, response.status, response);
+    throw new ApiError<E>(
+      errorMessage,
+      response.status,
+      response,
+      undefined,
+      errorCode
+    );
   }
 
   const result: ApiResponse<T, E> = await response.json();
@@ -207,7 +267,8 @@ const handleApiResponse = async <T, E = T>(response: Response): Promise<T> => {
         result.message || 'API request failed',
         response.status,
         response,
-        result.error_data
+        result.error_data,
+        result.error_code ?? undefined
       );
     }
 
@@ -221,7 +282,9 @@ const handleApiResponse = async <T, E = T>(response: Response): Promise<T> => {
     throw new ApiError<E>(
       result.message || 'API request failed',
       response.status,
-      response
+      response,
+      undefined,
+      result.error_code ?? undefined
     );
   }
 
@@ -276,11 +339,57 @@ export const projectsApi = {
     return handleApiResponse<void>(response);
   },
 
+  getRunningDevServers: async (id: string): Promise<RunningDevServer[]> => {
+    const response = await makeRequest(`/api/projects/${id}/dev-servers`);
+    return handleApiResponse<RunningDevServer[]>(response);
+  },
+
+  stopOtherDevServers: async (id: string): Promise<StoppedDevServer[]> => {
+    const response = await makeRequest(
+      `/api/projects/${id}/stop-other-dev-servers`,
+      { method: 'POST' }
+    );
+    return handleApiResponse<StoppedDevServer[]>(response);
+  },
+
   getBranches: async (id: string): Promise<GitBranch[]> => {
     const response = await makeRequest(`/api/projects/${id}/branches`);
     return handleApiResponse<GitBranch[]>(response);
   },
 
+  getRepoConfig: async (id: string): Promise<RepoConfigStatus> => {
+    const response = await makeRequest(`/api/projects/${id}/repo-config`);
+    return handleApiResponse<RepoConfigStatus>(response);
+  },
+
+  trustRepoConfig: async (
+    id: string,
+    data: TrustRepoConfigRequest
+  ): Promise<RepoConfigStatus> => {
+    const response = await makeRequest(
+      `/api/projects/${id}/repo-config/trust`,
+      {
+        method: 'PUT',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<RepoConfigStatus>(response);
+  },
+
+  setDefaultBaseBranch: async (
+    id: string,
+    data: SetDefaultBaseBranchRequest
+  ): Promise<Project> => {
+    const response = await makeRequest(
+      `/api/projects/${id}/default-base-branch`,
+      {
+        method: 'PUT',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<Project>(response);
+  },
+
   searchFiles: async (
     id: string,
     query: string,
@@ -298,9 +407,20 @@ export const projectsApi = {
 
 // Task Management APIs
 export const tasksApi = {
-  getAll: async (projectId: string): Promise<TaskWithAttemptStatus[]> => {
-    const response = await makeRequest(`/api/tasks?project_id=${projectId}`);
-    return handleApiResponse<TaskWithAttemptStatus[]>(response);
+  getAll: async (
+    projectId: string,
+    labelFilter?: { labelIds: string[]; match?: LabelMatch },
+    page: { limit?: number; offset?: number } = {}
+  ): Promise<Page<TaskWithAttemptStatus>> => {
+    const params = new URLSearchParams({ project_id: projectId });
+    if (labelFilter && labelFilter.labelIds.length > 0) {
+      params.set('labels', labelFilter.labelIds.join(','));
+      params.set('label_match', labelFilter.match ?? 'all');
+    }
+    if (page.limit !== undefined) params.set('limit', String(page.limit));
+    if (page.offset !== undefined) params.set('offset', String(page.offset));
+    const response = await makeRequest(`/api/tasks?${params.toString()}`);
+    return handleApiResponse<Page<TaskWithAttemptStatus>>(response);
   },
 
   getById: async (taskId: string): Promise<Task> => {
@@ -340,6 +460,54 @@ export const tasksApi = {
     });
     return handleApiResponse<void>(response);
   },
+
+  split: async (
+    taskId: string,
+    data: SplitTaskRequest
+  ): Promise<SplitTaskResponse> => {
+    const response = await makeRequest(`/api/tasks/${taskId}/split`, {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<SplitTaskResponse>(response);
+  },
+
+  getActivity: async (taskId: string): Promise<TaskEvent[]> => {
+    const response = await makeRequest(`/api/tasks/${taskId}/activity`);
+    return handleApiResponse<TaskEvent[]>(response);
+  },
+
+  getLabels: async (taskId: string): Promise<Label[]> => {
+    const response = await makeRequest(`/api/tasks/${taskId}/labels`);
+    return handleApiResponse<Label[]>(response);
+  },
+
+  addLabel: async (taskId: string, labelId: string): Promise<Label[]> => {
+    const response = await makeRequest(`/api/tasks/${taskId}/labels`, {
+      method: 'POST',
+      body: JSON.stringify({ label_id: labelId }),
+    });
+    return handleApiResponse<Label[]>(response);
+  },
+
+  removeLabel: async (taskId: string, labelId: string): Promise<Label[]> => {
+    const response = await makeRequest(
+      `/api/tasks/${taskId}/labels?label_id=${encodeURIComponent(labelId)}`,
+      { method: 'DELETE' }
+    );
+    return handleApiResponse<Label[]>(response);
+  },
+
+  setPriority: async (
+    taskId: string,
+    priority: number
+  ): Promise<TaskPriority> => {
+    const response = await makeRequest(`/api/tasks/${taskId}/priority`, {
+      method: 'PUT',
+      body: JSON.stringify({ priority }),
+    });
+    return handleApiResponse<TaskPriority>(response);
+  },
 };
 
 // Task Attempts APIs
@@ -351,9 +519,20 @@ export const attemptsApi = {
     return handleApiResponse<TaskRelationships>(response);
   },
 
-  getAll: async (taskId: string): Promise<TaskAttempt[]> => {
-    const response = await makeRequest(`/api/task-attempts?task_id=${taskId}`);
-    return handleApiResponse<TaskAttempt[]>(response);
+  getAll: async (
+    taskId: string,
+    options: { pinnedFirst?: boolean; limit?: number; offset?: number } = {}
+  ): Promise<Page<TaskAttempt>> => {
+    const params = new URLSearchParams({ task_id: taskId });
+    if (options.pinnedFirst) params.set('pinned_first', 'true');
+    if (options.limit !== undefined) params.set('limit', String(options.limit));
+    if (options.offset !== undefined) {
+      params.set('offset', String(options.offset));
+    }
+    const response = await makeRequest(
+      `/api/task-attempts?${params.toString()}`
+    );
+    return handleApiResponse<Page<TaskAttempt>>(response);
   },
 
   get: async (attemptId: string): Promise<TaskAttempt> => {
@@ -396,6 +575,67 @@ export const attemptsApi = {
     return handleApiResponse(response);
   },
 
+  followUpFromCommit: async (
+    attemptId: string,
+    data: FollowUpFromCommitRequest
+  ): Promise<FollowUpFromCommitResult> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/follow-up-from-commit`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<FollowUpFromCommitResult>(response);
+  },
+
+  resetToBase: async (
+    attemptId: string,
+    data: ResetAttemptRequest
+  ): Promise<ResetAttemptResult> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/reset`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<ResetAttemptResult>(response);
+  },
+
+  recover: async (
+    attemptId: string,
+    data: RecoverAttemptRequest
+  ): Promise<RecoverAttemptResult> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/recover`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<RecoverAttemptResult>(response);
+  },
+
+  execCommand: async (
+    attemptId: string,
+    data: ExecCommandRequest
+  ): Promise<ExecCommandResult> => {
+    const response = await makeRequest(`/api/task-attempts/${attemptId}/exec`, {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<ExecCommandResult>(response);
+  },
+
+  getDiffSummary: async (attemptId: string): Promise<DiffSummaryResult> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/diff-summary`,
+      { method: 'POST' }
+    );
+    return handleApiResponse<DiffSummaryResult>(response);
+  },
+
   followUp: async (
     attemptId: string,
     data: CreateFollowUpAttempt
@@ -503,21 +743,25 @@ export const attemptsApi = {
     return handleApiResponse<void>(response);
   },
 
-  getBranchStatus: async (attemptId: string): Promise<BranchStatus> => {
+  getBranchStatus: async (
+    attemptId: string,
+    options?: { includeRemote?: boolean }
+  ): Promise<BranchStatus> => {
+    const query = options?.includeRemote ? '?include_remote=true' : '';
     const response = await makeRequest(
-      `/api/task-attempts/${attemptId}/branch-status`
+      `/api/task-attempts/${attemptId}/branch-status${query}`
     );
     return handleApiResponse<BranchStatus>(response);
   },
 
-  merge: async (attemptId: string): Promise<void> => {
+  merge: async (attemptId: string): Promise<MergeResult> => {
     const response = await makeRequest(
       `/api/task-attempts/${attemptId}/merge`,
       {
         method: 'POST',
       }
     );
-    return handleApiResponse<void>(response);
+    return handleApiResponse<MergeResult>(response);
   },
 
   push: async (attemptId: string): Promise<void> => {
@@ -555,6 +799,86 @@ export const attemptsApi = {
     return handleApiResponse<ChangeTargetBranchResponse>(response);
   },
 
+  markReviewed: async (
+    attemptId: string,
+    data: MarkReviewedRequest
+  ): Promise<TaskAttempt> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/reviewed`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<TaskAttempt>(response);
+  },
+
+  clearReviewed: async (attemptId: string): Promise<TaskAttempt> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/reviewed`,
+      {
+        method: 'DELETE',
+      }
+    );
+    return handleApiResponse<TaskAttempt>(response);
+  },
+
+  getFileReviews: async (attemptId: string): Promise<FileReviewStatus[]> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/file-reviews`
+    );
+    return handleApiResponse<FileReviewStatus[]>(response);
+  },
+
+  setFileReviewed: async (
+    attemptId: string,
+    data: SetFileReviewedRequest
+  ): Promise<FileReviewStatus[]> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/file-reviews`,
+      {
+        method: 'PUT',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<FileReviewStatus[]>(response);
+  },
+
+  pin: async (attemptId: string): Promise<TaskAttempt> => {
+    const response = await makeRequest(`/api/task-attempts/${attemptId}/pin`, {
+      method: 'POST',
+    });
+    return handleApiResponse<TaskAttempt>(response);
+  },
+
+  unpin: async (attemptId: string): Promise<TaskAttempt> => {
+    const response = await makeRequest(`/api/task-attempts/${attemptId}/pin`, {
+      method: 'DELETE',
+    });
+    return handleApiResponse<TaskAttempt>(response);
+  },
+
+  getNotes: async (attemptId: string): Promise<string | null> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/notes`
+    );
+    return handleApiResponse<string | null>(response);
+  },
+
+  updateNotes: async (
+    attemptId: string,
+    data: UpdateAttemptNotesRequest
+  ): Promise<TaskAttempt> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/notes`,
+      {
+        method: 'PUT',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<TaskAttempt>(response);
+  },
+
   abortConflicts: async (attemptId: string): Promise<void> => {
     const response = await makeRequest(
       `/api/task-attempts/${attemptId}/conflicts/abort`,
@@ -576,15 +900,70 @@ export const attemptsApi = {
     return handleApiResponseAsResult<string, GitHubServiceError>(response);
   },
 
-  startDevServer: async (attemptId: string): Promise<void> => {
+  startDevServer: async (attemptId: string, name?: string): Promise<void> => {
+    const nameParam = name ? `?name=${encodeURIComponent(name)}` : '';
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/start-dev-server${nameParam}`,
+      {
+        method: 'POST',
+      }
+    );
+    return handleApiResponse<void>(response);
+  },
+
+  runSetup: async (attemptId: string): Promise<ExecutionProcess> => {
     const response = await makeRequest(
-      `/api/task-attempts/${attemptId}/start-dev-server`,
+      `/api/task-attempts/${attemptId}/run-setup`,
       {
         method: 'POST',
       }
     );
+    return handleApiResponse<ExecutionProcess>(response);
+  },
+
+  getSummaryMarkdown: async (attemptId: string): Promise<string> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/summary.md`
+    );
+    if (!response.ok) {
+      // Errors are still reported as JSON
+      return handleApiResponse<string>(response);
+    }
+    return response.text();
+  },
+};
+
+export const attemptQueueApi = {
+  list: async (): Promise<QueuedAttempt[]> => {
+    const response = await makeRequest('/api/attempt-queue');
+    return handleApiResponse<QueuedAttempt[]>(response);
+  },
+
+  enqueue: async (data: CreateTaskAttemptBody): Promise<QueuedAttempt> => {
+    const response = await makeRequest('/api/attempt-queue', {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<QueuedAttempt>(response);
+  },
+
+  cancel: async (queuedId: string): Promise<void> => {
+    const response = await makeRequest(`/api/attempt-queue/${queuedId}`, {
+      method: 'DELETE',
+    });
     return handleApiResponse<void>(response);
   },
+
+  move: async (
+    queuedId: string,
+    data: MoveQueuedAttemptRequest
+  ): Promise<QueuedAttempt[]> => {
+    const response = await makeRequest(`/api/attempt-queue/${queuedId}/move`, {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<QueuedAttempt[]>(response);
+  },
 };
 
 // Extra helpers
@@ -641,6 +1020,24 @@ export const executionProcessesApi = {
     );
     return handleApiResponse<void>(response);
   },
+
+  getRunning: async (): Promise<RunningProcessInfo[]> => {
+    const response = await makeRequest('/api/execution-processes/running');
+    return handleApiResponse<RunningProcessInfo[]>(response);
+  },
+
+  stopRunning: async (
+    data: StopRunningProcessesRequest
+  ): Promise<RunningProcessInfo[]> => {
+    const response = await makeRequest(
+      '/api/execution-processes/running/stop',
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<RunningProcessInfo[]>(response);
+  },
 };
 
 // File System APIs
@@ -675,6 +1072,10 @@ export const configApi = {
     });
     return handleApiResponse<Config>(response);
   },
+  getExecutorCapabilities: async (): Promise<ExecutorCapabilities[]> => {
+    const response = await makeRequest('/api/executor-capabilities');
+    return handleApiResponse<ExecutorCapabilities[]>(response);
+  },
 };
 
 // GitHub Device Auth APIs
@@ -744,6 +1145,76 @@ export const tagsApi = {
   },
 };
 
+// Labels APIs (per-project task categories)
+export const labelsApi = {
+  list: async (projectId: string): Promise<Label[]> => {
+    const response = await makeRequest(
+      `/api/labels?project_id=${encodeURIComponent(projectId)}`
+    );
+    return handleApiResponse<Label[]>(response);
+  },
+
+  create: async (data: CreateLabel): Promise<Label> => {
+    const response = await makeRequest('/api/labels', {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<Label>(response);
+  },
+
+  update: async (labelId: string, data: UpdateLabel): Promise<Label> => {
+    const response = await makeRequest(`/api/labels/${labelId}`, {
+      method: 'PUT',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<Label>(response);
+  },
+
+  delete: async (labelId: string): Promise<void> => {
+    const response = await makeRequest(`/api/labels/${labelId}`, {
+      method: 'DELETE',
+    });
+    return handleApiResponse<void>(response);
+  },
+};
+
+// Prompt snippet APIs (reusable fragments expanded from {{snippet:name}})
+export const promptSnippetsApi = {
+  list: async (projectId?: string): Promise<PromptSnippet[]> => {
+    const query = projectId
+      ? `?project_id=${encodeURIComponent(projectId)}`
+      : '';
+    const response = await makeRequest(`/api/prompt-snippets${query}`);
+    return handleApiResponse<PromptSnippet[]>(response);
+  },
+
+  create: async (data: CreatePromptSnippet): Promise<PromptSnippet> => {
+    const response = await makeRequest('/api/prompt-snippets', {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<PromptSnippet>(response);
+  },
+
+  update: async (
+    snippetId: string,
+    data: UpdatePromptSnippet
+  ): Promise<PromptSnippet> => {
+    const response = await makeRequest(`/api/prompt-snippets/${snippetId}`, {
+      method: 'PUT',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<PromptSnippet>(response);
+  },
+
+  delete: async (snippetId: string): Promise<void> => {
+    const response = await makeRequest(`/api/prompt-snippets/${snippetId}`, {
+      method: 'DELETE',
+    });
+    return handleApiResponse<void>(response);
+  },
+};
+
 // Agents APIs (Factory Floor agents)
 export const agentsApi = {
   list: async (): Promise<Agent[]> => {
@@ -890,6 +1361,20 @@ export const imagesApi = {
     return handleApiResponse<ImageResponse[]>(response);
   },
 
+  cleanupOrphans: async (
+    options: { dryRun?: boolean; limit?: number } = {}
+  ): Promise<OrphanCleanupReport> => {
+    const params = new URLSearchParams();
+    if (options.dryRun) params.set('dry_run', 'true');
+    if (options.limit !== undefined) params.set('limit', String(options.limit));
+    const search = params.toString();
+    const response = await makeRequest(
+      `/api/images/orphans/cleanup${search ? `?${search}` : ''}`,
+      { method: 'POST' }
+    );
+    return handleApiResponse<OrphanCleanupReport>(response);
+  },
+
   getImageUrl: (imageId: string): string => {
     return `/api/images/${imageId}/file`;
   },
@@ -1053,6 +1538,19 @@ export const stationTransitionsApi = {
     });
     return handleApiResponse<void>(response);
   },
+
+  dryRunCondition: async (
+    data: DryRunTransitionConditionRequest
+  ): Promise<DryRunTransitionConditionResponse> => {
+    const response = await makeRequest(
+      '/api/transition-conditions/dry-run',
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<DryRunTransitionConditionResponse>(response);
+  },
 };
 
 // Workflow Execution APIs
@@ -1092,6 +1590,15 @@ export const workflowExecutionsApi = {
     return handleApiResponse<StationExecutionSummary[]>(response);
   },
 
+  getHistory: async (
+    executionId: string
+  ): Promise<WorkflowExecutionHistoryResponse> => {
+    const response = await makeRequest(
+      `/api/workflow-executions/${executionId}/history`
+    );
+    return handleApiResponse<WorkflowExecutionHistoryResponse>(response);
+  },
+
   cancel: async (
     executionId: string,
     data: CancelWorkflowExecutionRequest
@@ -1106,6 +1613,26 @@ export const workflowExecutionsApi = {
     return handleApiResponse<CancelWorkflowExecutionResponse>(response);
   },
 
+  pause: async (executionId: string): Promise<WorkflowExecution> => {
+    const response = await makeRequest(
+      `/api/workflow-executions/${executionId}/pause`,
+      {
+        method: 'POST',
+      }
+    );
+    return handleApiResponse<WorkflowExecution>(response);
+  },
+
+  resume: async (executionId: string): Promise<WorkflowExecution> => {
+    const response = await makeRequest(
+      `/api/workflow-executions/${executionId}/resume`,
+      {
+        method: 'POST',
+      }
+    );
+    return handleApiResponse<WorkflowExecution>(response);
+  },
+
   retryStation: async (
     executionId: string,
     data: RetryStationRequest
@@ -480,6 +480,7 @@ function FactoryFloorContent() {
         agent_id: null,
         station_prompt: null,
         output_context_keys: null,
+        output_schema: null,
         is_terminator: false, // Explicit default - stations are non-terminators by default
       },
     });
@@ -80,6 +80,7 @@ function DiffsPanelContainer({
   branchStatus,
   branches,
   setGitError,
+  reviewedPaths,
 }: {
   attempt: any;
   selectedTask: any;
@@ -87,12 +88,14 @@ function DiffsPanelContainer({
   branchStatus: any;
   branches: GitBranch[];
   setGitError: (error: string | null) => void;
+  reviewedPaths?: string[];
 }) {
   const { isAttemptRunning } = useAttemptExecution(attempt?.id);
 
   return (
     <DiffsPanel
       selectedAttempt={attempt}
+      reviewedPaths={reviewedPaths}
       gitOps={
         attempt && selectedTask
           ? {
@@ -148,6 +151,8 @@ export function ProjectTasks() {
   const {
     tasks,
     tasksById,
+    progressByTaskId,
+    reviewedPathsByAttemptId,
     isLoading,
     error: streamError,
   } = useProjectTasks(projectId || '');
@@ -660,6 +665,7 @@ export function ProjectTasks() {
           onViewTaskDetails={handleViewTaskDetails}
           selectedTask={selectedTask || undefined}
           onCreateTask={handleCreateNewTask}
+          progressByTaskId={progressByTaskId}
         />
       </div>
     );
@@ -765,6 +771,7 @@ export function ProjectTasks() {
           branchStatus={branchStatus}
           branches={branches}
           setGitError={setGitError}
+          reviewedPaths={reviewedPathsByAttemptId[attempt.id]}
         />
       )}
     </div>
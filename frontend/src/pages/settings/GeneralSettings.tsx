@@ -491,6 +491,23 @@ export function GeneralSettings() {
               </p>
             </div>
           )}
+          <div className="flex items-center space-x-2">
+            <Checkbox
+              id="open-editor-on-start"
+              checked={draft?.open_editor_on_start ?? false}
+              onCheckedChange={(checked: boolean) =>
+                updateDraft({ open_editor_on_start: checked })
+              }
+            />
+            <div className="space-y-0.5">
+              <Label htmlFor="open-editor-on-start" className="cursor-pointer">
+                {t('settings.general.editor.openOnStart.label')}
+              </Label>
+              <p className="text-sm text-muted-foreground">
+                {t('settings.general.editor.openOnStart.helper')}
+              </p>
+            </div>
+          </div>
         </CardContent>
       </Card>
 
@@ -625,6 +642,43 @@ export function GeneralSettings() {
               )}
             </p>
           </div>
+          <div className="flex items-center space-x-2">
+            <Checkbox
+              id="skip-agent-commit-hooks"
+              checked={draft?.skip_agent_commit_hooks ?? false}
+              onCheckedChange={(checked: boolean) =>
+                updateDraft({ skip_agent_commit_hooks: checked })
+              }
+            />
+            <div className="space-y-0.5">
+              <Label
+                htmlFor="skip-agent-commit-hooks"
+                className="cursor-pointer"
+              >
+                {t('settings.general.git.skipCommitHooks.label')}
+              </Label>
+              <p className="text-sm text-muted-foreground">
+                {t('settings.general.git.skipCommitHooks.helper')}
+              </p>
+            </div>
+          </div>
+          <div className="flex items-center space-x-2">
+            <Checkbox
+              id="squash-on-finalize"
+              checked={draft?.squash_on_finalize ?? false}
+              onCheckedChange={(checked: boolean) =>
+                updateDraft({ squash_on_finalize: checked })
+              }
+            />
+            <div className="space-y-0.5">
+              <Label htmlFor="squash-on-finalize" className="cursor-pointer">
+                {t('settings.general.git.squashOnFinalize.label')}
+              </Label>
+              <p className="text-sm text-muted-foreground">
+                {t('settings.general.git.squashOnFinalize.helper')}
+              </p>
+            </div>
+          </div>
         </CardContent>
       </Card>
 
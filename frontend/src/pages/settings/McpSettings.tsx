@@ -30,6 +30,7 @@ import type { BaseCodingAgent, ExecutorConfig } from 'shared/types';
 import { McpConfig } from 'shared/types';
 import { useUserSystem } from '@/components/config-provider';
 import { mcpServersApi } from '@/lib/api';
+import { useExecutorCapabilities } from '@/hooks';
 import { McpConfigStrategyGeneral } from '@/lib/mcp-strategies';
 
 export function McpSettings() {
@@ -45,6 +46,21 @@ export function McpSettings() {
   const [mcpApplying, setMcpApplying] = useState(false);
   const [mcpConfigPath, setMcpConfigPath] = useState<string>('');
   const [success, setSuccess] = useState(false);
+  const { data: executorCapabilities = [], byExecutor } =
+    useExecutorCapabilities();
+
+  const selectedProfileKey =
+    profiles && selectedProfile
+      ? Object.keys(profiles).find((key) => profiles[key] === selectedProfile)
+      : undefined;
+  // Unknown until the capability matrix loads; the server rejects it anyway
+  const mcpSupported =
+    !selectedProfileKey ||
+    byExecutor[selectedProfileKey as BaseCodingAgent]?.supports_mcp !== false;
+  const mcpExecutors = executorCapabilities
+    .filter((entry) => entry.supports_mcp)
+    .map((entry) => entry.executor)
+    .join(', ');
 
   // Initialize selected profile when config loads
   useEffect(() => {
@@ -91,22 +107,18 @@ export function McpSettings() {
         const configJson = JSON.stringify(fullConfig, null, 2);
         setMcpServers(configJson);
         setMcpConfigPath(result.config_path);
-      } catch (err: any) {
-        if (err?.message && err.message.includes('does not support MCP')) {
-          setMcpError(err.message);
-        } else {
-          console.error('Error loading MCP servers:', err);
-        }
+      } catch (err) {
+        console.error('Error loading MCP servers:', err);
       } finally {
         setMcpLoading(false);
       }
     };
 
     // Load MCP servers for the selected profile
-    if (selectedProfile) {
+    if (selectedProfile && mcpSupported) {
       loadMcpServersForProfile(selectedProfile);
     }
-  }, [selectedProfile]);
+  }, [selectedProfile, mcpSupported]);
 
   const handleMcpServersChange = (value: string) => {
     setMcpServers(value);
@@ -261,13 +273,7 @@ export function McpSettings() {
               {t('settings.mcp.labels.agent')}
             </Label>
             <Select
-              value={
-                selectedProfile
-                  ? Object.keys(profiles || {}).find(
-                      (key) => profiles![key] === selectedProfile
-                    ) || ''
-                  : ''
-              }
+              value={selectedProfileKey ?? ''}
               onValueChange={(value: string) => {
                 const profile = profiles?.[value];
                 if (profile) setSelectedProfile(profile);
@@ -294,7 +300,7 @@ export function McpSettings() {
             </p>
           </div>
 
-          {mcpError && mcpError.includes('does not support MCP') ? (
+          {!mcpSupported ? (
             <div className="rounded-lg border border-amber-200 bg-amber-50 p-4 dark:border-amber-800 dark:bg-amber-950">
               <div className="flex">
                 <div className="ml-3">
@@ -302,9 +308,10 @@ export function McpSettings() {
                     {t('settings.mcp.errors.notSupported')}
                   </h3>
                   <div className="mt-2 text-sm text-amber-700 dark:text-amber-300">
-                    <p>{mcpError}</p>
-                    <p className="mt-1">
-                      {t('settings.mcp.errors.supportMessage')}
+                    <p>
+                      {t('settings.mcp.errors.supportMessage', {
+                        executors: mcpExecutors,
+                      })}
                     </p>
                   </div>
                 </div>
@@ -329,7 +336,7 @@ export function McpSettings() {
                 disabled={mcpLoading}
                 minHeight={300}
               />
-              {mcpError && !mcpError.includes('does not support MCP') && (
+              {mcpError && (
                 <p className="text-sm text-destructive dark:text-red-400">
                   {mcpError}
                 </p>
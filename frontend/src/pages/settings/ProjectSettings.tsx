@@ -19,15 +19,32 @@ import {
 } from '@/components/ui/select';
 import { Label } from '@/components/ui/label';
 import { Input } from '@/components/ui/input';
+import { Checkbox } from '@/components/ui/checkbox';
 import { Alert, AlertDescription } from '@/components/ui/alert';
-import { Loader2, Folder } from 'lucide-react';
+import { Loader2, Folder, Plus, Trash2 } from 'lucide-react';
 import { useProjects } from '@/hooks/useProjects';
 import { useProjectMutations } from '@/hooks/useProjectMutations';
 import { useScriptPlaceholders } from '@/hooks/useScriptPlaceholders';
 import { CopyFilesField } from '@/components/projects/copy-files-field';
 import { AutoExpandingTextarea } from '@/components/ui/auto-expanding-textarea';
 import { showFolderPicker } from '@/lib/modals';
-import type { Project, UpdateProject } from 'shared/types';
+import { projectsApi } from '@/lib/api';
+import { useUserSystem } from '@/components/config-provider';
+import type {
+  NamedScript,
+  Project,
+  RepoConfigStatus,
+  ScriptRequestLanguage,
+  UpdateProject,
+} from 'shared/types';
+
+const SCRIPT_LANGUAGES: ScriptRequestLanguage[] = [
+  'Bash',
+  'Sh',
+  'PowerShell',
+  'Node',
+];
+const DEFAULT_SCRIPT_LANGUAGE = 'default';
 
 interface ProjectFormState {
   name: string;
@@ -37,6 +54,17 @@ interface ProjectFormState {
   cleanup_script: string;
   copy_files: string;
   worktree_dir: string;
+  push_remote: string;
+  pr_remote: string;
+  always_run_cleanup: boolean;
+  setup_cache_artifacts: string;
+  setup_cache_files: string;
+  script_language: ScriptRequestLanguage | null;
+  preview_url_pattern: string;
+  env_file: string;
+  env_file_required: boolean;
+  dev_scripts: NamedScript[];
+  reuse_worktree: boolean;
 }
 
 function projectToFormState(project: Project): ProjectFormState {
@@ -48,13 +76,32 @@ function projectToFormState(project: Project): ProjectFormState {
     cleanup_script: project.cleanup_script ?? '',
     copy_files: project.copy_files ?? '',
     worktree_dir: project.worktree_dir ?? '',
+    push_remote: project.push_remote ?? '',
+    pr_remote: project.pr_remote ?? '',
+    always_run_cleanup: project.always_run_cleanup,
+    setup_cache_artifacts: project.setup_cache_artifacts ?? '',
+    setup_cache_files: project.setup_cache_files ?? '',
+    script_language: project.script_language,
+    preview_url_pattern: project.preview_url_pattern ?? '',
+    env_file: project.env_file ?? '',
+    env_file_required: project.env_file_required,
+    dev_scripts: project.dev_scripts,
+    reuse_worktree: project.reuse_worktree,
   };
 }
 
+function formatRepoValue(value: string | NamedScript[] | null | undefined) {
+  if (Array.isArray(value)) {
+    return value.map((dev) => `${dev.name}: ${dev.script}`).join('\n');
+  }
+  return value ?? '';
+}
+
 export function ProjectSettings() {
   const [searchParams, setSearchParams] = useSearchParams();
   const projectIdParam = searchParams.get('projectId') ?? '';
   const { t } = useTranslation('settings');
+  const { reloadSystem } = useUserSystem();
 
   // Fetch all projects
   const {
@@ -68,6 +115,7 @@ export function ProjectSettings() {
     searchParams.get('projectId') || ''
   );
   const [selectedProject, setSelectedProject] = useState<Project | null>(null);
+  const [repoConfig, setRepoConfig] = useState<RepoConfigStatus | null>(null);
 
   // Form state
   const [draft, setDraft] = useState<ProjectFormState | null>(null);
@@ -172,6 +220,66 @@ export function ProjectSettings() {
     setDraft(projectToFormState(nextProject));
   }, [projects, selectedProjectId, hasUnsavedChanges]);
 
+  // Scripts committed to the repo's .vibe config override the project's own
+  useEffect(() => {
+    setRepoConfig(null);
+    if (!selectedProject) return;
+    let cancelled = false;
+    projectsApi
+      .getRepoConfig(selectedProject.id)
+      .then((status) => {
+        if (!cancelled) setRepoConfig(status);
+      })
+      .catch((err) => console.error('Failed to load repo config:', err));
+    return () => {
+      cancelled = true;
+    };
+  }, [selectedProject]);
+
+  const repoManagedFields = useMemo(() => {
+    const config = repoConfig?.config;
+    if (!config) return [];
+    return (
+      [
+        'setup_script',
+        'dev_script',
+        'dev_scripts',
+        'cleanup_script',
+        'copy_files',
+      ] as const
+    ).filter((field) => config[field] != null);
+  }, [repoConfig]);
+
+  // Repo scripts only apply once the user trusts them
+  const repoScriptFields = useMemo(
+    () => repoManagedFields.filter((field) => field !== 'copy_files'),
+    [repoManagedFields]
+  );
+  const repoAppliedFields = repoConfig?.scripts_trusted
+    ? repoManagedFields
+    : repoManagedFields.filter((field) => field === 'copy_files');
+
+  const handleTrustRepoScripts = useCallback(
+    async (trusted: boolean) => {
+      if (!selectedProject || !repoConfig) return;
+      setError(null);
+      try {
+        const status = await projectsApi.trustRepoConfig(selectedProject.id, {
+          fingerprint: trusted ? repoConfig.fingerprint : null,
+        });
+        setRepoConfig(status);
+        await reloadSystem();
+      } catch (err) {
+        setError(
+          err instanceof Error
+            ? err.message
+            : t('settings.projects.scripts.repoConfig.trustError')
+        );
+      }
+    },
+    [selectedProject, repoConfig, reloadSystem, t]
+  );
+
   // Warn on tab close/navigation with unsaved changes
   useEffect(() => {
     const handler = (e: BeforeUnloadEvent) => {
@@ -217,6 +325,20 @@ export function ProjectSettings() {
         cleanup_script: draft.cleanup_script.trim() || null,
         copy_files: draft.copy_files.trim() || null,
         worktree_dir: draft.worktree_dir.trim() || null,
+        push_remote: draft.push_remote.trim() || null,
+        pr_remote: draft.pr_remote.trim() || null,
+        always_run_cleanup: draft.always_run_cleanup,
+        setup_cache_artifacts: draft.setup_cache_artifacts.trim() || null,
+        setup_cache_files: draft.setup_cache_files.trim() || null,
+        script_language: draft.script_language,
+        preview_url_pattern: draft.preview_url_pattern.trim() || null,
+        env_file: draft.env_file.trim() || null,
+        env_file_required: draft.env_file_required,
+        dev_scripts: draft.dev_scripts.map((dev) => ({
+          name: dev.name.trim(),
+          script: dev.script.trim(),
+        })),
+        reuse_worktree: draft.reuse_worktree,
       };
 
       updateProject.mutate({
@@ -242,6 +364,15 @@ export function ProjectSettings() {
     });
   };
 
+  const updateDevScript = (index: number, updates: Partial<NamedScript>) => {
+    if (!draft) return;
+    updateDraft({
+      dev_scripts: draft.dev_scripts.map((dev, i) =>
+        i === index ? { ...dev, ...updates } : dev
+      ),
+    });
+  };
+
   if (projectsLoading) {
     return (
       <div className="flex items-center justify-center py-8">
@@ -400,6 +531,97 @@ export function ProjectSettings() {
               </CardDescription>
             </CardHeader>
             <CardContent className="space-y-4">
+              {repoConfig?.error && (
+                <Alert variant="destructive">
+                  <AlertDescription>
+                    {t('settings.projects.scripts.repoConfig.invalid', {
+                      error: repoConfig.error,
+                    })}
+                  </AlertDescription>
+                </Alert>
+              )}
+              {repoConfig?.path && repoAppliedFields.length > 0 && (
+                <Alert>
+                  <AlertDescription>
+                    {t('settings.projects.scripts.repoConfig.overrides', {
+                      path: repoConfig.path,
+                      fields: repoAppliedFields.join(', '),
+                    })}
+                  </AlertDescription>
+                </Alert>
+              )}
+              {repoConfig?.path && repoScriptFields.length > 0 && (
+                <Alert>
+                  <AlertDescription className="space-y-2">
+                    <p>
+                      {t(
+                        repoConfig.scripts_trusted
+                          ? 'settings.projects.scripts.repoConfig.trusted'
+                          : 'settings.projects.scripts.repoConfig.untrusted',
+                        {
+                          path: repoConfig.path,
+                          fields: repoScriptFields.join(', '),
+                        }
+                      )}
+                    </p>
+                    {!repoConfig.scripts_trusted && repoConfig.config && (
+                      <pre className="overflow-auto bg-muted p-2 text-xs">
+                        {repoScriptFields
+                          .map(
+                            (field) =>
+                              `${field}:\n${formatRepoValue(repoConfig.config?.[field])}`
+                          )
+                          .join('\n\n')}
+                      </pre>
+                    )}
+                    <Button
+                      size="sm"
+                      variant="outline"
+                      onClick={() =>
+                        handleTrustRepoScripts(!repoConfig.scripts_trusted)
+                      }
+                    >
+                      {repoConfig.scripts_trusted
+                        ? t('settings.projects.scripts.repoConfig.untrust')
+                        : t('settings.projects.scripts.repoConfig.trust')}
+                    </Button>
+                  </AlertDescription>
+                </Alert>
+              )}
+              <div className="space-y-2">
+                <Label htmlFor="script-language">
+                  {t('settings.projects.scripts.language.label')}
+                </Label>
+                <Select
+                  value={draft.script_language ?? DEFAULT_SCRIPT_LANGUAGE}
+                  onValueChange={(value) =>
+                    updateDraft({
+                      script_language:
+                        value === DEFAULT_SCRIPT_LANGUAGE
+                          ? null
+                          : (value as ScriptRequestLanguage),
+                    })
+                  }
+                >
+                  <SelectTrigger id="script-language">
+                    <SelectValue />
+                  </SelectTrigger>
+                  <SelectContent>
+                    <SelectItem value={DEFAULT_SCRIPT_LANGUAGE}>
+                      {t('settings.projects.scripts.language.default')}
+                    </SelectItem>
+                    {SCRIPT_LANGUAGES.map((language) => (
+                      <SelectItem key={language} value={language}>
+                        {language}
+                      </SelectItem>
+                    ))}
+                  </SelectContent>
+                </Select>
+                <p className="text-sm text-muted-foreground">
+                  {t('settings.projects.scripts.language.helper')}
+                </p>
+              </div>
+
               <div className="space-y-2">
                 <Label htmlFor="setup-script">
                   {t('settings.projects.scripts.setup.label')}
@@ -419,6 +641,45 @@ export function ProjectSettings() {
                 </p>
               </div>
 
+              <div className="grid gap-4 sm:grid-cols-2">
+                <div className="space-y-2">
+                  <Label htmlFor="setup-cache-artifacts">
+                    {t('settings.projects.scripts.setupCacheArtifacts.label')}
+                  </Label>
+                  <Input
+                    id="setup-cache-artifacts"
+                    type="text"
+                    value={draft.setup_cache_artifacts}
+                    onChange={(e) =>
+                      updateDraft({ setup_cache_artifacts: e.target.value })
+                    }
+                    placeholder="node_modules"
+                    className="font-mono"
+                  />
+                  <p className="text-sm text-muted-foreground">
+                    {t('settings.projects.scripts.setupCacheArtifacts.helper')}
+                  </p>
+                </div>
+                <div className="space-y-2">
+                  <Label htmlFor="setup-cache-files">
+                    {t('settings.projects.scripts.setupCacheFiles.label')}
+                  </Label>
+                  <Input
+                    id="setup-cache-files"
+                    type="text"
+                    value={draft.setup_cache_files}
+                    onChange={(e) =>
+                      updateDraft({ setup_cache_files: e.target.value })
+                    }
+                    placeholder="package-lock.json, Cargo.lock"
+                    className="font-mono"
+                  />
+                  <p className="text-sm text-muted-foreground">
+                    {t('settings.projects.scripts.setupCacheFiles.helper')}
+                  </p>
+                </div>
+              </div>
+
               <div className="space-y-2">
                 <Label htmlFor="dev-script">
                   {t('settings.projects.scripts.dev.label')}
@@ -436,6 +697,85 @@ export function ProjectSettings() {
                 </p>
               </div>
 
+              <div className="space-y-2">
+                <Label>{t('settings.projects.scripts.devScripts.label')}</Label>
+                {draft.dev_scripts.map((dev, index) => (
+                  <div key={index} className="flex items-start gap-2">
+                    <Input
+                      type="text"
+                      value={dev.name}
+                      onChange={(e) =>
+                        updateDevScript(index, { name: e.target.value })
+                      }
+                      placeholder={t(
+                        'settings.projects.scripts.devScripts.namePlaceholder'
+                      )}
+                      className="w-40 shrink-0"
+                    />
+                    <AutoExpandingTextarea
+                      value={dev.script}
+                      onChange={(e) =>
+                        updateDevScript(index, { script: e.target.value })
+                      }
+                      placeholder={placeholders.dev}
+                      maxRows={6}
+                      className="w-full px-3 py-2 border border-input bg-background text-foreground rounded-md focus:outline-none focus:ring-2 focus:ring-ring font-mono"
+                    />
+                    <Button
+                      variant="ghost"
+                      size="sm"
+                      onClick={() =>
+                        updateDraft({
+                          dev_scripts: draft.dev_scripts.filter(
+                            (_, i) => i !== index
+                          ),
+                        })
+                      }
+                      title={t('settings.projects.scripts.devScripts.remove')}
+                    >
+                      <Trash2 className="h-4 w-4" />
+                    </Button>
+                  </div>
+                ))}
+                <Button
+                  variant="outline"
+                  size="sm"
+                  onClick={() =>
+                    updateDraft({
+                      dev_scripts: [
+                        ...draft.dev_scripts,
+                        { name: '', script: '' },
+                      ],
+                    })
+                  }
+                >
+                  <Plus className="mr-2 h-4 w-4" />
+                  {t('settings.projects.scripts.devScripts.add')}
+                </Button>
+                <p className="text-sm text-muted-foreground">
+                  {t('settings.projects.scripts.devScripts.helper')}
+                </p>
+              </div>
+
+              <div className="space-y-2">
+                <Label htmlFor="preview-url-pattern">
+                  {t('settings.projects.scripts.previewUrlPattern.label')}
+                </Label>
+                <Input
+                  id="preview-url-pattern"
+                  type="text"
+                  value={draft.preview_url_pattern}
+                  onChange={(e) =>
+                    updateDraft({ preview_url_pattern: e.target.value })
+                  }
+                  placeholder="Local:\s+(http://\S+)"
+                  className="font-mono"
+                />
+                <p className="text-sm text-muted-foreground">
+                  {t('settings.projects.scripts.previewUrlPattern.helper')}
+                </p>
+              </div>
+
               <div className="space-y-2">
                 <Label htmlFor="cleanup-script">
                   {t('settings.projects.scripts.cleanup.label')}
@@ -455,6 +795,62 @@ export function ProjectSettings() {
                 </p>
               </div>
 
+              <div className="flex items-center space-x-2">
+                <Checkbox
+                  id="always-run-cleanup"
+                  checked={draft.always_run_cleanup}
+                  onCheckedChange={(checked: boolean) =>
+                    updateDraft({ always_run_cleanup: checked })
+                  }
+                />
+                <div className="space-y-0.5">
+                  <Label
+                    htmlFor="always-run-cleanup"
+                    className="cursor-pointer"
+                  >
+                    {t('settings.projects.scripts.alwaysRunCleanup.label')}
+                  </Label>
+                  <p className="text-sm text-muted-foreground">
+                    {t('settings.projects.scripts.alwaysRunCleanup.helper')}
+                  </p>
+                </div>
+              </div>
+
+              <div className="space-y-2">
+                <Label htmlFor="env-file">
+                  {t('settings.projects.scripts.envFile.label')}
+                </Label>
+                <Input
+                  id="env-file"
+                  type="text"
+                  value={draft.env_file}
+                  onChange={(e) => updateDraft({ env_file: e.target.value })}
+                  placeholder=".env.local"
+                  className="font-mono"
+                />
+                <p className="text-sm text-muted-foreground">
+                  {t('settings.projects.scripts.envFile.helper')}
+                </p>
+              </div>
+
+              <div className="flex items-center space-x-2">
+                <Checkbox
+                  id="env-file-required"
+                  checked={draft.env_file_required}
+                  onCheckedChange={(checked: boolean) =>
+                    updateDraft({ env_file_required: checked })
+                  }
+                />
+                <div className="space-y-0.5">
+                  <Label htmlFor="env-file-required" className="cursor-pointer">
+                    {t('settings.projects.scripts.envFileRequired.label')}
+                  </Label>
+                  <p className="text-sm text-muted-foreground">
+                    {t('settings.projects.scripts.envFileRequired.helper')}
+                  </p>
+                </div>
+              </div>
+
               <div className="space-y-2">
                 <Label>{t('settings.projects.scripts.copyFiles.label')}</Label>
                 <CopyFilesField
@@ -508,6 +904,60 @@ export function ProjectSettings() {
                   the default temp directory.
                 </p>
               </div>
+
+              <div className="flex items-center space-x-2">
+                <Checkbox
+                  id="reuse-worktree"
+                  checked={draft.reuse_worktree}
+                  onCheckedChange={(checked: boolean) =>
+                    updateDraft({ reuse_worktree: checked })
+                  }
+                />
+                <div className="space-y-0.5">
+                  <Label htmlFor="reuse-worktree" className="cursor-pointer">
+                    Reuse one worktree per task
+                  </Label>
+                  <p className="text-sm text-muted-foreground">
+                    New attempts of a task reset the task's worktree to their
+                    base branch instead of creating a fresh one, so installed
+                    dependencies and build output are kept. Attempts of the
+                    same task can no longer run in parallel, and opening an
+                    older attempt switches the worktree back to its branch.
+                  </p>
+                </div>
+              </div>
+
+              <div className="grid gap-4 sm:grid-cols-2">
+                <div className="space-y-2">
+                  <Label htmlFor="push-remote">Push Remote</Label>
+                  <Input
+                    id="push-remote"
+                    type="text"
+                    value={draft.push_remote}
+                    onChange={(e) =>
+                      updateDraft({ push_remote: e.target.value })
+                    }
+                    placeholder="origin"
+                    className="font-mono"
+                  />
+                </div>
+                <div className="space-y-2">
+                  <Label htmlFor="pr-remote">Pull Request Remote</Label>
+                  <Input
+                    id="pr-remote"
+                    type="text"
+                    value={draft.pr_remote}
+                    onChange={(e) => updateDraft({ pr_remote: e.target.value })}
+                    placeholder="upstream"
+                    className="font-mono"
+                  />
+                </div>
+                <p className="text-sm text-muted-foreground sm:col-span-2">
+                  For fork workflows, push attempt branches to your fork and
+                  open pull requests against upstream. Leave empty to use the
+                  repository&apos;s first remote for both.
+                </p>
+              </div>
             </CardContent>
           </Card>
 
@@ -0,0 +1,274 @@
+import { useState } from 'react';
+import { useTranslation } from 'react-i18next';
+import {
+  Card,
+  CardContent,
+  CardDescription,
+  CardHeader,
+  CardTitle,
+} from '@/components/ui/card';
+import { Button } from '@/components/ui/button';
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from '@/components/ui/select';
+import { Label } from '@/components/ui/label';
+import { Input } from '@/components/ui/input';
+import { Textarea } from '@/components/ui/textarea';
+import { Alert, AlertDescription } from '@/components/ui/alert';
+import { Loader2, Pencil, Plus, Trash2 } from 'lucide-react';
+import { useProjects } from '@/hooks/useProjects';
+import { usePromptSnippets } from '@/hooks/usePromptSnippets';
+import type { PromptSnippet } from 'shared/types';
+
+const GLOBAL_SCOPE = 'global';
+
+interface SnippetFormState {
+  name: string;
+  content: string;
+}
+
+const EMPTY_FORM: SnippetFormState = { name: '', content: '' };
+
+export function PromptSnippetSettings() {
+  const { t } = useTranslation('settings');
+  const { data: projects } = useProjects();
+  const [scope, setScope] = useState<string>(GLOBAL_SCOPE);
+  const projectId = scope === GLOBAL_SCOPE ? undefined : scope;
+  const {
+    snippets,
+    isLoading,
+    error: loadError,
+    createSnippet,
+    updateSnippet,
+    deleteSnippet,
+  } = usePromptSnippets(projectId);
+
+  // `null` while adding a new snippet
+  const [editingId, setEditingId] = useState<string | null>(null);
+  const [form, setForm] = useState<SnippetFormState>(EMPTY_FORM);
+  const [error, setError] = useState<string | null>(null);
+
+  // The list also returns global snippets when a project is selected
+  const scopedSnippets = snippets.filter(
+    (snippet) => snippet.project_id === (projectId ?? null)
+  );
+
+  const resetForm = () => {
+    setEditingId(null);
+    setForm(EMPTY_FORM);
+  };
+
+  const handleScopeChange = (value: string) => {
+    setScope(value);
+    resetForm();
+    setError(null);
+  };
+
+  const handleEdit = (snippet: PromptSnippet) => {
+    setEditingId(snippet.id);
+    setForm({ name: snippet.name, content: snippet.content });
+    setError(null);
+  };
+
+  const handleError = (err: unknown) => {
+    setError(
+      err instanceof Error ? err.message : t('settings.snippets.errors.save')
+    );
+  };
+
+  const handleSave = () => {
+    setError(null);
+    const data = { name: form.name.trim(), content: form.content };
+    if (editingId) {
+      updateSnippet.mutate(
+        { snippetId: editingId, data },
+        { onSuccess: resetForm, onError: handleError }
+      );
+    } else {
+      createSnippet.mutate(
+        { ...data, project_id: projectId ?? null },
+        { onSuccess: resetForm, onError: handleError }
+      );
+    }
+  };
+
+  const handleDelete = (snippet: PromptSnippet) => {
+    if (
+      !window.confirm(
+        t('settings.snippets.deleteConfirm', { name: snippet.name })
+      )
+    ) {
+      return;
+    }
+    setError(null);
+    deleteSnippet.mutate(snippet.id, {
+      onSuccess: () => {
+        if (editingId === snippet.id) resetForm();
+      },
+      onError: handleError,
+    });
+  };
+
+  const saving = createSnippet.isPending || updateSnippet.isPending;
+
+  return (
+    <div className="space-y-6">
+      {error && (
+        <Alert variant="destructive">
+          <AlertDescription>{error}</AlertDescription>
+        </Alert>
+      )}
+
+      <Card>
+        <CardHeader>
+          <CardTitle>{t('settings.snippets.title')}</CardTitle>
+          <CardDescription>
+            {t('settings.snippets.description')}
+          </CardDescription>
+        </CardHeader>
+        <CardContent className="space-y-4">
+          <div className="space-y-2">
+            <Label htmlFor="snippet-scope">
+              {t('settings.snippets.scope.label')}
+            </Label>
+            <Select value={scope} onValueChange={handleScopeChange}>
+              <SelectTrigger id="snippet-scope">
+                <SelectValue />
+              </SelectTrigger>
+              <SelectContent>
+                <SelectItem value={GLOBAL_SCOPE}>
+                  {t('settings.snippets.scope.global')}
+                </SelectItem>
+                {projects?.map((project) => (
+                  <SelectItem key={project.id} value={project.id}>
+                    {project.name}
+                  </SelectItem>
+                ))}
+              </SelectContent>
+            </Select>
+            <p className="text-sm text-muted-foreground">
+              {t('settings.snippets.scope.helper')}
+            </p>
+          </div>
+
+          {isLoading ? (
+            <div className="flex items-center py-4">
+              <Loader2 className="h-4 w-4 animate-spin" />
+              <span className="ml-2 text-sm">
+                {t('settings.snippets.loading')}
+              </span>
+            </div>
+          ) : loadError ? (
+            <Alert variant="destructive">
+              <AlertDescription>
+                {t('settings.snippets.errors.load')}
+              </AlertDescription>
+            </Alert>
+          ) : scopedSnippets.length === 0 ? (
+            <p className="text-sm text-muted-foreground">
+              {t('settings.snippets.empty')}
+            </p>
+          ) : (
+            <div className="divide-y border">
+              {scopedSnippets.map((snippet) => (
+                <div
+                  key={snippet.id}
+                  className="flex items-start justify-between gap-4 p-3"
+                >
+                  <div className="min-w-0 flex-1">
+                    <code className="text-sm font-medium">
+                      {`{{snippet:${snippet.name}}}`}
+                    </code>
+                    <p className="mt-1 text-sm text-muted-foreground whitespace-pre-wrap line-clamp-3">
+                      {snippet.content}
+                    </p>
+                  </div>
+                  <div className="flex shrink-0 gap-1">
+                    <Button
+                      variant="ghost"
+                      size="sm"
+                      onClick={() => handleEdit(snippet)}
+                      title={t('settings.snippets.edit')}
+                    >
+                      <Pencil className="h-3 w-3" />
+                    </Button>
+                    <Button
+                      variant="ghost"
+                      size="sm"
+                      onClick={() => handleDelete(snippet)}
+                      disabled={deleteSnippet.isPending}
+                      title={t('settings.snippets.delete')}
+                    >
+                      <Trash2 className="h-3 w-3" />
+                    </Button>
+                  </div>
+                </div>
+              ))}
+            </div>
+          )}
+        </CardContent>
+      </Card>
+
+      <Card>
+        <CardHeader>
+          <CardTitle>
+            {editingId
+              ? t('settings.snippets.form.editTitle')
+              : t('settings.snippets.form.addTitle')}
+          </CardTitle>
+        </CardHeader>
+        <CardContent className="space-y-4">
+          <div className="space-y-2">
+            <Label htmlFor="snippet-name">
+              {t('settings.snippets.form.name.label')}
+            </Label>
+            <Input
+              id="snippet-name"
+              value={form.name}
+              onChange={(e) => setForm({ ...form, name: e.target.value })}
+              placeholder="style-guide"
+            />
+            <p className="text-sm text-muted-foreground">
+              {t('settings.snippets.form.name.helper')}
+            </p>
+          </div>
+          <div className="space-y-2">
+            <Label htmlFor="snippet-content">
+              {t('settings.snippets.form.content.label')}
+            </Label>
+            <Textarea
+              id="snippet-content"
+              value={form.content}
+              onChange={(e) => setForm({ ...form, content: e.target.value })}
+              rows={6}
+            />
+          </div>
+          <div className="flex gap-2">
+            <Button
+              onClick={handleSave}
+              disabled={saving || !form.name.trim() || !form.content.trim()}
+            >
+              {saving ? (
+                <Loader2 className="mr-2 h-4 w-4 animate-spin" />
+              ) : (
+                !editingId && <Plus className="mr-2 h-4 w-4" />
+              )}
+              {editingId
+                ? t('settings.snippets.form.save')
+                : t('settings.snippets.form.add')}
+            </Button>
+            {editingId && (
+              <Button variant="outline" onClick={resetForm} disabled={saving}>
+                {t('settings.snippets.form.cancel')}
+              </Button>
+            )}
+          </div>
+        </CardContent>
+      </Card>
+    </div>
+  );
+}
@@ -1,6 +1,6 @@
 import { NavLink, Outlet, useNavigate, useLocation } from 'react-router-dom';
 import { useTranslation } from 'react-i18next';
-import { Settings, Cpu, Server, X, FolderOpen } from 'lucide-react';
+import { Settings, Cpu, Server, X, FolderOpen, FileText } from 'lucide-react';
 import { cn } from '@/lib/utils';
 import { Button } from '@/components/ui/button';
 import { useEffect } from 'react';
@@ -25,6 +25,10 @@ const settingsNavigation = [
     path: 'mcp',
     icon: Server,
   },
+  {
+    path: 'snippets',
+    icon: FileText,
+  },
 ];
 
 export function SettingsLayout() {
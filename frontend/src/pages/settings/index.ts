@@ -3,3 +3,4 @@ export { GeneralSettings } from './GeneralSettings';
 export { ProjectSettings } from './ProjectSettings';
 export { AgentSettings } from './AgentSettings';
 export { McpSettings } from './McpSettings';
+export { PromptSnippetSettings } from './PromptSnippetSettings';
@@ -8,11 +8,159 @@ export type DirectoryEntry = { name: string, path: string, is_directory: boolean
 
 export type DirectoryListResponse = { entries: Array<DirectoryEntry>, current_path: string, };
 
-export type Project = { id: string, name: string, git_repo_path: string, setup_script: string | null, dev_script: string | null, cleanup_script: string | null, copy_files: string | null, worktree_dir: string | null, created_at: Date, updated_at: Date, };
+export type Project = { id: string, name: string, git_repo_path: string, setup_script: string | null, dev_script: string | null, cleanup_script: string | null, copy_files: string | null, worktree_dir: string | null, 
+/**
+ * Remote attempt branches are pushed to, e.g. a fork. Defaults to the first remote.
+ */
+push_remote: string | null, 
+/**
+ * Remote pull requests are opened against, e.g. upstream. Defaults to the first remote.
+ */
+pr_remote: string | null, 
+/**
+ * Run the cleanup script even when the coding agent made no changes
+ */
+always_run_cleanup: boolean, 
+/**
+ * Comma-separated lockfile globs hashed to decide whether the setup script can be skipped.
+ * Defaults to common lockfiles such as `package-lock.json` and `Cargo.lock`.
+ */
+setup_cache_files: string | null, 
+/**
+ * Comma-separated paths (e.g. `node_modules`) restored from the setup cache instead of
+ * re-running the setup script. Caching is disabled when unset.
+ */
+setup_cache_artifacts: string | null, 
+/**
+ * Interpreter for the setup, dev server and cleanup scripts. Defaults to PowerShell on
+ * Windows and Bash elsewhere.
+ */
+script_language: ScriptRequestLanguage | null, 
+/**
+ * Regex matched against dev server output to find the preview URL. Its first capture
+ * group (or the whole match) is used; a bare port number becomes `http://localhost:<port>`.
+ */
+preview_url_pattern: string | null, 
+/**
+ * Branch new task attempts are based on when no base branch is given. Detected from the
+ * repository when the project is created; attempts fall back to the repo's current
+ * default branch when unset.
+ */
+default_base_branch: string | null, 
+/**
+ * `.env`-style file whose variables are set for every process run in the project's
+ * worktrees. Relative paths are resolved against the repository root.
+ */
+env_file: string | null, 
+/**
+ * Refuse to start processes when `env_file` is missing or unreadable, instead of
+ * warning and running without it
+ */
+env_file_required: boolean, 
+/**
+ * Dev servers that can run alongside `dev_script`, each started and stopped by name
+ */
+dev_scripts: Array<NamedScript>, 
+/**
+ * Share one worktree between all attempts of a task, resetting it to the new attempt's
+ * base instead of creating a fresh one, so setup output such as installed dependencies
+ * is kept. Attempts of the same task can then no longer run in parallel.
+ */
+reuse_worktree: boolean, created_at: Date, updated_at: Date, };
+
+export type CreateProject = { name: string, git_repo_path: string, use_existing_repo: boolean, 
+/**
+ * Clone this URL into `git_repo_path` instead of initializing an empty repo. Only used
+ * when `use_existing_repo` is false.
+ */
+git_url: string | null, setup_script: string | null, dev_script: string | null, cleanup_script: string | null, copy_files: string | null, worktree_dir: string | null, push_remote: string | null, pr_remote: string | null, always_run_cleanup: boolean, setup_cache_files: string | null, setup_cache_artifacts: string | null, script_language: ScriptRequestLanguage | null, preview_url_pattern: string | null, env_file: string | null, env_file_required: boolean, dev_scripts: Array<NamedScript>, reuse_worktree: boolean, };
+
+export type UpdateProject = { name: string | null, git_repo_path: string | null, setup_script: string | null, dev_script: string | null, cleanup_script: string | null, copy_files: string | null, worktree_dir: string | null, push_remote: string | null, pr_remote: string | null, 
+/**
+ * Leaves the current setting unchanged when omitted
+ */
+always_run_cleanup: boolean | null, setup_cache_files: string | null, setup_cache_artifacts: string | null, script_language: ScriptRequestLanguage | null, preview_url_pattern: string | null, env_file: string | null, 
+/**
+ * Leaves the current setting unchanged when omitted
+ */
+env_file_required: boolean | null, 
+/**
+ * Leaves the current dev servers unchanged when omitted
+ */
+dev_scripts: Array<NamedScript> | null, 
+/**
+ * Leaves the current setting unchanged when omitted
+ */
+reuse_worktree: boolean | null, };
+
+/**
+ * A script identified by a name, e.g. one of several dev servers
+ */
+export type NamedScript = { name: string, script: string, };
+
+export type SetDefaultBaseBranchRequest = { 
+/**
+ * Branch new attempts are based on; `null` or blank clears it
+ */
+default_base_branch: string | null, };
+
+/**
+ * A dev server running in one of the project's attempts
+ */
+export type RunningDevServer = { execution_process_id: string, task_attempt_id: string, 
+/**
+ * Name from the project's `dev_scripts`; `None` for the main `dev_script`
+ */
+name: string | null, preview_url: string | null, 
+/**
+ * Port of `preview_url`, once the dev server has reported it
+ */
+port: number | null, };
+
+export type TrustRepoConfigRequest = { 
+/**
+ * Fingerprint of the config file the user reviewed, or `None` to stop trusting the
+ * repository's scripts
+ */
+fingerprint: string | null, };
 
-export type CreateProject = { name: string, git_repo_path: string, use_existing_repo: boolean, setup_script: string | null, dev_script: string | null, cleanup_script: string | null, copy_files: string | null, worktree_dir: string | null, };
+/**
+ * Project settings committed to the repository in `.vibe/config.toml` (or `config.json`) so
+ * everyone working on it shares them. Script fields take precedence over the project's own
+ * settings once the user trusts them, since they run commands from whoever last edited the
+ * repository.
+ */
+export type RepoProjectConfig = { setup_script: string | null, dev_script: string | null, cleanup_script: string | null, copy_files: string | null, 
+/**
+ * Named dev servers, replacing the project's own list as a whole
+ */
+dev_scripts: Array<NamedScript> | null, 
+/**
+ * Executor profile suggested for new attempts
+ */
+executor_profile: ExecutorProfileId | null, };
 
-export type UpdateProject = { name: string | null, git_repo_path: string | null, setup_script: string | null, dev_script: string | null, cleanup_script: string | null, copy_files: string | null, worktree_dir: string | null, };
+/**
+ * The repository config as last loaded for a project
+ */
+export type RepoConfigStatus = { 
+/**
+ * Config file in use, relative to the repository root
+ */
+path: string | null, config: RepoProjectConfig | null, 
+/**
+ * SHA-256 of the config file, approved by the user to let its scripts run
+ */
+fingerprint: string | null, 
+/**
+ * Whether the user approved this version of the file's scripts. Until then only its other
+ * settings apply.
+ */
+scripts_trusted: boolean, 
+/**
+ * Why the config file could not be used; the project's own settings apply meanwhile
+ */
+error: string | null, };
 
 export type SearchResult = { path: string, is_file: boolean, match_type: SearchMatchType, };
 
@@ -26,13 +174,26 @@ export type ExecutorActionType = { "type": "CodingAgentInitialRequest" } & Codin
 
 export type ScriptContext = "SetupScript" | "CleanupScript" | "DevServer";
 
-export type ScriptRequest = { script: string, language: ScriptRequestLanguage, context: ScriptContext, };
+export type ScriptRequest = { script: string, 
+/**
+ * Interpreter for the script; `None` runs it in PowerShell on Windows and the default shell
+ * elsewhere
+ */
+language: ScriptRequestLanguage | null, context: ScriptContext, 
+/**
+ * Setup cache key of the worktree; the script's artifacts are cached under it on success
+ */
+cache_key?: string, 
+/**
+ * Which of the project's dev servers this is; unset for the main `dev_script`
+ */
+name?: string, };
 
-export type ScriptRequestLanguage = "Bash";
+export type ScriptRequestLanguage = "Bash" | "Sh" | "PowerShell" | "Node";
 
-export enum BaseCodingAgent { CLAUDE_CODE = "CLAUDE_CODE", AMP = "AMP", GEMINI = "GEMINI", CODEX = "CODEX", OPENCODE = "OPENCODE", CURSOR_AGENT = "CURSOR_AGENT", QWEN_CODE = "QWEN_CODE", COPILOT = "COPILOT" }
+export enum BaseCodingAgent { CLAUDE_CODE = "CLAUDE_CODE", AMP = "AMP", GEMINI = "GEMINI", CODEX = "CODEX", OPENCODE = "OPENCODE", CURSOR_AGENT = "CURSOR_AGENT", QWEN_CODE = "QWEN_CODE", COPILOT = "COPILOT", ECHO = "ECHO" }
 
-export type CodingAgent = { "CLAUDE_CODE": ClaudeCode } | { "AMP": Amp } | { "GEMINI": Gemini } | { "CODEX": Codex } | { "OPENCODE": Opencode } | { "CURSOR_AGENT": CursorAgent } | { "QWEN_CODE": QwenCode } | { "COPILOT": Copilot };
+export type CodingAgent = { "CLAUDE_CODE": ClaudeCode } | { "AMP": Amp } | { "GEMINI": Gemini } | { "CODEX": Codex } | { "OPENCODE": Opencode } | { "CURSOR_AGENT": CursorAgent } | { "QWEN_CODE": QwenCode } | { "COPILOT": Copilot } | { "ECHO": Echo };
 
 export type Tag = { id: string, tag_name: string, content: string, created_at: string, updated_at: string, };
 
@@ -42,23 +203,155 @@ export type UpdateTag = { tag_name: string | null, content: string | null, };
 
 export type TagSearchParams = { search: string | null, };
 
+/**
+ * A project-scoped category (bug, feature, chore, ...) that tasks can be tagged with
+ */
+export type Label = { id: string, project_id: string, name: string, 
+/**
+ * Hex color used to render the label, e.g. `#ef4444`
+ */
+color: string, created_at: string, updated_at: string, };
+
+export type CreateLabel = { project_id: string, name: string, color: string | null, };
+
+export type UpdateLabel = { name: string | null, color: string | null, };
+
+/**
+ * How a multi-label task filter combines its labels
+ */
+export type LabelMatch = "all" | "any";
+
+/**
+ * A reusable prompt fragment, pulled into prompts with `{{snippet:name}}`
+ */
+export type PromptSnippet = { id: string, 
+/**
+ * `None` for global snippets, available in every project
+ */
+project_id: string | null, name: string, content: string, created_at: string, updated_at: string, };
+
+export type CreatePromptSnippet = { 
+/**
+ * Omit to create a global snippet
+ */
+project_id: string | null, name: string, content: string, };
+
+export type UpdatePromptSnippet = { name: string | null, content: string | null, };
+
+/**
+ * A small JSON value an agent keeps across the turns of an attempt. Entries are removed
+ * together with their attempt.
+ */
+export type AttemptKvEntry = { task_attempt_id: string, key: string, value: JsonValue, created_at: string, updated_at: string, };
+
 export type TaskStatus = "todo" | "inprogress" | "inreview" | "done" | "cancelled";
 
-export type Task = { id: string, project_id: string, title: string, description: string | null, status: TaskStatus, parent_task_attempt: string | null, agent_id: string | null, workflow_id: string | null, current_station_id: string | null, created_at: string, updated_at: string, };
+export type Task = { id: string, project_id: string, title: string, description: string | null, status: TaskStatus, parent_task_attempt: string | null, agent_id: string | null, workflow_id: string | null, current_station_id: string | null, parent_task_id: string | null, blocked_on_subtasks: boolean, 
+/**
+ * Queued attempts of higher priority tasks start first. Defaults to 0, so equal
+ * priorities keep first-in, first-out order.
+ */
+priority: number, created_at: string, updated_at: string, };
 
-export type TaskWithAttemptStatus = { has_in_progress_attempt: boolean, has_merged_attempt: boolean, last_attempt_failed: boolean, executor: string, id: string, project_id: string, title: string, description: string | null, status: TaskStatus, parent_task_attempt: string | null, agent_id: string | null, workflow_id: string | null, current_station_id: string | null, created_at: string, updated_at: string, };
+export type TaskWithAttemptStatus = { has_in_progress_attempt: boolean, has_merged_attempt: boolean, last_attempt_failed: boolean, 
+/**
+ * The latest attempt's agent ended its turn by asking the user something
+ */
+needs_input: boolean, executor: string, labels: Array<Label>, id: string, project_id: string, title: string, description: string | null, status: TaskStatus, parent_task_attempt: string | null, agent_id: string | null, workflow_id: string | null, current_station_id: string | null, parent_task_id: string | null, blocked_on_subtasks: boolean, 
+/**
+ * Queued attempts of higher priority tasks start first. Defaults to 0, so equal
+ * priorities keep first-in, first-out order.
+ */
+priority: number, created_at: string, updated_at: string, };
 
-export type TaskRelationships = { parent_task: Task | null, current_attempt: TaskAttempt, children: Array<Task>, };
+export type TaskRelationships = { parent_task: Task | null, current_attempt: TaskAttempt, children: Array<Task>, subtasks: Array<Task>, };
 
 export type CreateTask = { project_id: string, title: string, description: string | null, parent_task_attempt: string | null, agent_id: string | null, workflow_id: string | null, image_ids: Array<string> | null, };
 
 export type UpdateTask = { title: string | null, description: string | null, status: TaskStatus | null, parent_task_attempt: string | null, agent_id: string | null, workflow_id: string | null, current_station_id: string | null, image_ids: Array<string> | null, };
 
+export type TaskEventType = "created" | "status_changed" | "attempt_created" | "merged" | "pr_created" | "pr_merged" | "pr_closed" | "timed_out" | "preview_available";
+
+/**
+ * One entry in a task's activity timeline
+ */
+export type TaskEvent = { id: string, task_id: string, task_attempt_id: string | null, event_type: TaskEventType, 
+/**
+ * Set for `status_changed` events
+ */
+from_status: TaskStatus | null, 
+/**
+ * The new status for `status_changed` events, the initial one for `created`
+ */
+to_status: TaskStatus | null, 
+/**
+ * Event-specific context: the base branch of a new attempt, the target branch of a merge,
+ * or the PR URL
+ */
+details: string | null, 
+/**
+ * Who triggered the event, when known. Local deployments have a single user and leave
+ * it unset.
+ */
+actor: string | null, created_at: string, };
+
+/**
+ * An attempt waiting for a free execution slot. Entries of higher priority tasks start
+ * first, otherwise they start in `position` order.
+ */
+export type QueuedAttempt = { id: string, task_id: string, executor_profile_id: ExecutorProfileId, base_branch: string, force_setup: boolean, position: bigint, created_at: string, };
+
 export type Image = { id: string, file_path: string, original_name: string, mime_type: string | null, size_bytes: bigint, hash: string, created_at: string, updated_at: string, };
 
 export type CreateImage = { file_path: string, original_name: string, mime_type: string | null, size_bytes: bigint, hash: string, };
 
-export type ApiResponse<T, E = T> = { success: boolean, data: T | null, error_data: E | null, message: string | null, };
+export type ApiResponse<T, E = T> = { success: boolean, data: T | null, error_data: E | null, message: string | null, error_code: ApiErrorCode | null, 
+/**
+ * Id of the request that failed, so a report can be matched to the server logs
+ */
+request_id: string | null, };
+
+/**
+ * Stable, machine-readable identifier for a failed request.
+ *
+ * Clients should branch on these rather than on `message`, which is meant for display and
+ * may change wording at any time. New codes may be added; unknown codes should be treated
+ * like `INTERNAL_ERROR`.
+ */
+export type ApiErrorCode = "INTERNAL_ERROR" | "VALIDATION_FAILED" | "NOT_FOUND" | "CONFLICT" | "MERGE_CONFLICT" | "REBASE_IN_PROGRESS" | "UNRESOLVED_CONFLICT_MARKERS" | "WORKTREE_DIRTY" | "BRANCHES_DIVERGED" | "BRANCH_NOT_FOUND" | "REMOTE_NOT_FOUND" | "TOKEN_UNAVAILABLE" | "TOKEN_INVALID" | "INSUFFICIENT_PERMISSIONS" | "REPO_NOT_FOUND_OR_NO_ACCESS" | "RATE_LIMITED" | "NO_SESSION" | "AUTH_PENDING" | "WEBHOOK_NOT_CONFIGURED" | "INVALID_SIGNATURE" | "INVALID_IMAGE_FORMAT" | "IMAGE_TOO_LARGE" | "UPLOAD_FAILED";
+
+/**
+ * One page of a listing
+ */
+export type Page<T> = { items: Array<T>, 
+/**
+ * Number of items across all pages
+ */
+total: number, 
+/**
+ * Offset of the next page, or `None` on the last page
+ */
+next_offset: number | null, };
+
+export type BatchSubRequest = { 
+/**
+ * HTTP method, e.g. `GET`
+ */
+method: string, 
+/**
+ * Path and query relative to `/api`, e.g. `/tasks/<id>`. A leading `/api` is accepted.
+ */
+path: string, 
+/**
+ * JSON request body, if the route takes one
+ */
+body?: JsonValue, };
+
+export type BatchSubResponse = { status: number, 
+/**
+ * The route's JSON response, a string if it wasn't JSON, or `null` if it was empty
+ */
+body: JsonValue, };
 
 export type UserSystemInfo = { config: Config, analytics_user_id: string, environment: Environment, 
 /**
@@ -74,27 +367,205 @@ export type UpdateMcpServersBody = { servers: { [key in string]?: JsonValue }, }
 
 export type GetMcpServerResponse = { mcp_config: McpConfig, config_path: string, };
 
+/**
+ * What a coding agent supports, so clients don't hardcode the feature matrix
+ */
+export type ExecutorCapabilities = { executor: BaseCodingAgent, capabilities: Array<BaseAgentCapability>, supports_mcp: boolean, 
+/**
+ * Configured variants, e.g. `DEFAULT` and `PLAN`
+ */
+variants: Array<string>, };
+
+/**
+ * A process running anywhere on this server
+ */
+export type RunningProcessInfo = { id: string, task_attempt_id: string, task_id: string, project_id: string, project_name: string, run_reason: ExecutionProcessRunReason, started_at: string, uptime_seconds: bigint, };
+
+export type StopRunningProcessesRequest = { 
+/**
+ * Stop only this process; every running process is stopped when omitted
+ */
+execution_process_id: string | null, };
+
 export type CreateFollowUpAttempt = { prompt: string, variant: string | null, agent_id: string | null, image_ids: Array<string> | null, retry_process_id: string | null, force_when_dirty: boolean | null, perform_git_reset: boolean | null, };
 
+export type StoppedDevServer = { execution_process_id: string, task_attempt_id: string, project_id: string, 
+/**
+ * True if the process was left over from a previous server run and was killed by pid
+ */
+orphaned: boolean, };
+
 export type DraftResponse = { task_attempt_id: string, draft_type: DraftType, retry_process_id: string | null, prompt: string, queued: boolean, variant: string | null, image_ids: Array<string> | null, version: bigint, };
 
 export type UpdateFollowUpDraftRequest = { prompt: string | null, variant: string | null | null, image_ids: Array<string> | null, version: bigint | null, };
 
 export type UpdateRetryFollowUpDraftRequest = { retry_process_id: string, prompt: string | null, variant: string | null | null, image_ids: Array<string> | null, version: bigint | null, };
 
+export type MarkReviewedRequest = { 
+/**
+ * Commit reviewed up to; defaults to the attempt's current HEAD
+ */
+commit: string | null, };
+
+export type UpdateAttemptNotesRequest = { 
+/**
+ * Markdown notes; empty or missing clears them
+ */
+notes: string | null, };
+
 export type ChangeTargetBranchRequest = { new_target_branch: string, };
 
-export type ChangeTargetBranchResponse = { new_target_branch: string, status: [number, number], };
+export type ChangeTargetBranchResponse = { new_target_branch: string, 
+/**
+ * Commits ahead of and behind the new target. Any behind means the branch isn't based
+ * on it; only a rebase moves its commits.
+ */
+status: [number, number], };
+
+export type FileReviewStatus = { path: string, reviewed: boolean, };
+
+export type SetFileReviewedRequest = { 
+/**
+ * Path as listed in the attempt's diff
+ */
+path: string, reviewed: boolean, };
+
+export type SetKvEntryRequest = { key: string, value: JsonValue, };
 
 export type CreateAndStartTaskRequest = { task: CreateTask, executor_profile_id: ExecutorProfileId, base_branch: string, };
 
+export type MoveQueuedAttemptRequest = { 
+/**
+ * New place in the queue; 0 starts next. Values past the end move the entry last.
+ */
+position: number, };
+
+export type SubtaskSpec = { task_id: string, } | { title: string, description: string | null, };
+
+export type SplitTaskRequest = { subtasks: Array<SubtaskSpec>, 
+/**
+ * Hold the parent back until every subtask is done or cancelled
+ */
+block_parent: boolean, };
+
+export type SplitTaskResponse = { parent: Task, subtasks: Array<Task>, };
+
+export type TaskLabelRequest = { label_id: string, };
+
+export type TaskPriority = { 
+/**
+ * Higher starts first in the attempt queue; 0 is the default
+ */
+priority: number, };
+
 export type CreateGitHubPrRequest = { title: string, body: string | null, target_branch: string | null, };
 
 export type ImageResponse = { id: string, file_path: string, original_name: string, mime_type: string | null, size_bytes: bigint, hash: string, created_at: string, updated_at: string, };
 
+export type OrphanCleanupReport = { dry_run: boolean, 
+/**
+ * Images deleted, or that would be deleted in a dry run
+ */
+image_ids: Array<string>, bytes: bigint, failed: number, 
+/**
+ * Orphans left over because of the limit
+ */
+remaining: number, };
+
 export enum GitHubServiceError { TOKEN_INVALID = "TOKEN_INVALID", INSUFFICIENT_PERMISSIONS = "INSUFFICIENT_PERMISSIONS", REPO_NOT_FOUND_OR_NO_ACCESS = "REPO_NOT_FOUND_OR_NO_ACCESS" }
 
-export type Config = { config_version: string, theme: ThemeMode, executor_profile: ExecutorProfileId, disclaimer_acknowledged: boolean, onboarding_acknowledged: boolean, github_login_acknowledged: boolean, telemetry_acknowledged: boolean, notifications: NotificationConfig, editor: EditorConfig, github: GitHubConfig, analytics_enabled: boolean | null, workspace_dir: string | null, last_app_version: string | null, show_release_notes: boolean, language: UiLanguage, git_branch_prefix: string, showcases: ShowcaseState, };
+export type Config = { config_version: string, theme: ThemeMode, executor_profile: ExecutorProfileId, disclaimer_acknowledged: boolean, onboarding_acknowledged: boolean, github_login_acknowledged: boolean, telemetry_acknowledged: boolean, notifications: NotificationConfig, editor: EditorConfig, github: GitHubConfig, analytics_enabled: boolean | null, workspace_dir: string | null, last_app_version: string | null, show_release_notes: boolean, language: UiLanguage, git_branch_prefix: string, 
+/**
+ * Template for attempt branch names; supports `{prefix}`, `{attempt_id}`, `{task_id}`
+ * and `{slug}`. `-{attempt_id}` is appended when the template omits it.
+ */
+branch_template: string, showcases: ShowcaseState, 
+/**
+ * Ask the coding agent to write a commit message from the diff when it left no summary
+ */
+generate_commit_messages: boolean, 
+/**
+ * Seconds to wait for a generated commit message; capped at 20 because finishing the
+ * attempt's run waits on it
+ */
+commit_message_timeout_secs: number, 
+/**
+ * Extra CLI arguments appended to every command of an executor, after variant-derived
+ * flags and profile `additional_params`
+ */
+executor_extra_args: { [key in BaseCodingAgent]?: Array<string> }, 
+/**
+ * How many times in a row a coding agent turn that crashed (non-zero exit) is re-run
+ * before the attempt is handed back for review. Stopped turns are never retried.
+ */
+auto_retry_failed_turns: number, 
+/**
+ * How many attempts may run setup, coding agent or cleanup processes at once. Queued
+ * attempts start as slots free up. 0 means no limit.
+ */
+max_concurrent_attempts: number, 
+/**
+ * Extra regexes masked in agent conversation logs on top of the built-in secret
+ * patterns. If a pattern has a capture group only that group is masked.
+ */
+log_redaction_patterns: Array<string>, 
+/**
+ * Allow asking the coding agent to explain an attempt's diff. Off by default since each
+ * uncached summary is a paid agent run.
+ */
+diff_summaries_enabled: boolean, 
+/**
+ * Push the target branch to the project's remote after a successful direct merge
+ */
+auto_push_after_merge: boolean, 
+/**
+ * Commit agent and cleanup script changes with `--no-verify`, for repos whose hooks
+ * reject or hang on automated commits. The repo's hooks run when false.
+ */
+skip_agent_commit_hooks: boolean, 
+/**
+ * Open a new attempt's worktree in the configured editor once it has been created
+ */
+open_editor_on_start: boolean, 
+/**
+ * Orphaned images deleted at once during image cleanup
+ */
+orphan_image_cleanup_concurrency: number, 
+/**
+ * Most orphaned images deleted per cleanup run; the rest wait for the next run. 0 means
+ * no limit.
+ */
+orphan_image_cleanup_limit: number, 
+/**
+ * Prefix stripped from the head branch name where it appears in a PR title, e.g. `vk/`
+ */
+pr_title_branch_prefix: string | null, 
+/**
+ * Longest an attempt may keep running setup, coding agent and cleanup processes before
+ * it is stopped and handed back for review, in minutes. Checked every minute. 0 means no
+ * limit.
+ */
+max_attempt_duration_mins: number, 
+/**
+ * Clone new projects with only this many commits per branch, which makes the clone
+ * shallow. Fetches into existing repositories are never shallow. `None` clones the full
+ * history.
+ */
+git_fetch_depth: number | null, 
+/**
+ * Delete remote-tracking branches that no longer exist on the remote when fetching
+ */
+git_fetch_prune: boolean, 
+/**
+ * Squash an attempt's commits into one, using the agent's summary as the message, when
+ * its coding agent run finishes. Branches that have been pushed are left alone.
+ */
+squash_on_finalize: boolean, 
+/**
+ * Repositories whose committed `.vibe` scripts may run, by repository path, with the
+ * SHA-256 of the config file that was approved. Editing the file needs a new approval.
+ */
+trusted_repo_scripts: { [key in string]?: string }, };
 
 export type NotificationConfig = { sound_enabled: boolean, push_enabled: boolean, sound_file: SoundFile, };
 
@@ -120,6 +591,48 @@ export enum CheckTokenResponse { VALID = "VALID", INVALID = "INVALID" }
 
 export type GitBranch = { name: string, is_current: boolean, is_remote: boolean, last_commit_date: Date, };
 
+export type BlameLine = { line_number: number, content: string, 
+/**
+ * None for lines that have not been committed yet
+ */
+commit_id: string | null, author: string | null, summary: string | null, };
+
+export type ConflictResolution = "ours" | "theirs";
+
+export type ConflictHunk = { 
+/**
+ * 1-based line of the `<<<<<<<` marker
+ */
+start_line: number, 
+/**
+ * 1-based line of the `>>>>>>>` marker
+ */
+end_line: number, ours_label: string | null, ours: string, 
+/**
+ * Only present when the conflict was written in diff3 style
+ */
+base: string | null, theirs_label: string | null, theirs: string, };
+
+export type FileConflict = { path: string, 
+/**
+ * Binary conflicts carry no hunks
+ */
+is_binary: boolean, hunks: Array<ConflictHunk>, };
+
+export type MergePreview = { 
+/**
+ * Changes the merge would land on the base branch; conflicted files are left out
+ */
+diffs: Array<Diff>, conflicted_files: Array<string>, 
+/**
+ * Base branch commits missing from the task branch; merging is refused until rebased
+ */
+base_commits_ahead: number, 
+/**
+ * Uncommitted worktree changes are not part of the merge
+ */
+has_uncommitted_changes: boolean, };
+
 export type Diff = { change: DiffChangeKind, oldPath: string | null, newPath: string | null, oldContent: string | null, newContent: string | null, 
 /**
  * True when file contents are intentionally omitted (e.g., too large)
@@ -154,19 +667,19 @@ executor: BaseCodingAgent,
  */
 variant: string | null, };
 
-export type ExecutorConfig = { [key in string]?: { "CLAUDE_CODE": ClaudeCode } | { "AMP": Amp } | { "GEMINI": Gemini } | { "CODEX": Codex } | { "OPENCODE": Opencode } | { "CURSOR_AGENT": CursorAgent } | { "QWEN_CODE": QwenCode } | { "COPILOT": Copilot } };
+export type ExecutorConfig = { [key in string]?: { "CLAUDE_CODE": ClaudeCode } | { "AMP": Amp } | { "GEMINI": Gemini } | { "CODEX": Codex } | { "OPENCODE": Opencode } | { "CURSOR_AGENT": CursorAgent } | { "QWEN_CODE": QwenCode } | { "COPILOT": Copilot } | { "ECHO": Echo } };
 
 export type BaseAgentCapability = "SESSION_FORK";
 
-export type ClaudeCode = { append_prompt: AppendPrompt, claude_code_router?: boolean | null, plan?: boolean | null, approvals?: boolean | null, model?: string | null, dangerously_skip_permissions?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, };
+export type ClaudeCode = { prompt_template: PromptTemplate, claude_code_router?: boolean | null, plan?: boolean | null, approvals?: boolean | null, model?: string | null, dangerously_skip_permissions?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, };
 
-export type Gemini = { append_prompt: AppendPrompt, model: GeminiModel, yolo?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, };
+export type Gemini = { prompt_template: PromptTemplate, model: GeminiModel, yolo?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, };
 
 export type GeminiModel = "default" | "flash";
 
-export type Amp = { append_prompt: AppendPrompt, dangerously_allow_all?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, };
+export type Amp = { prompt_template: PromptTemplate, dangerously_allow_all?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, };
 
-export type Codex = { append_prompt: AppendPrompt, sandbox?: SandboxMode | null, ask_for_approval?: AskForApproval | null, oss?: boolean | null, model?: string | null, model_reasoning_effort?: ReasoningEffort | null, model_reasoning_summary?: ReasoningSummary | null, model_reasoning_summary_format?: ReasoningSummaryFormat | null, profile?: string | null, base_instructions?: string | null, include_plan_tool?: boolean | null, include_apply_patch_tool?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, };
+export type Codex = { prompt_template: PromptTemplate, sandbox?: SandboxMode | null, ask_for_approval?: AskForApproval | null, oss?: boolean | null, model?: string | null, model_reasoning_effort?: ReasoningEffort | null, model_reasoning_summary?: ReasoningSummary | null, model_reasoning_summary_format?: ReasoningSummaryFormat | null, profile?: string | null, base_instructions?: string | null, include_plan_tool?: boolean | null, include_apply_patch_tool?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, };
 
 export type SandboxMode = "auto" | "read-only" | "workspace-write" | "danger-full-access";
 
@@ -178,15 +691,26 @@ export type ReasoningSummary = "auto" | "concise" | "detailed" | "none";
 
 export type ReasoningSummaryFormat = "none" | "experimental";
 
-export type CursorAgent = { append_prompt: AppendPrompt, force?: boolean | null, model?: string | null, base_command_override?: string | null, additional_params?: Array<string> | null, };
+export type CursorAgent = { prompt_template: PromptTemplate, force?: boolean | null, model?: string | null, base_command_override?: string | null, additional_params?: Array<string> | null, };
 
-export type Copilot = { append_prompt: AppendPrompt, model?: string | null, allow_all_tools?: boolean | null, allow_tool?: string | null, deny_tool?: string | null, add_dir?: Array<string> | null, disable_mcp_server?: Array<string> | null, base_command_override?: string | null, additional_params?: Array<string> | null, };
+export type Copilot = { prompt_template: PromptTemplate, model?: string | null, allow_all_tools?: boolean | null, allow_tool?: string | null, deny_tool?: string | null, add_dir?: Array<string> | null, disable_mcp_server?: Array<string> | null, base_command_override?: string | null, additional_params?: Array<string> | null, };
+
+export type Echo = { prompt_template: PromptTemplate, 
+/**
+ * Response printed as the assistant message
+ */
+response?: string | null, 
+/**
+ * Values for the JSON output block; requested keys missing here are echoed back as
+ * `"echo:<key>"`
+ */
+outputs?: { [key in string]?: string } | null, };
 
-export type Opencode = { append_prompt: AppendPrompt, model?: string | null, agent?: string | null, base_command_override?: string | null, additional_params?: Array<string> | null, };
+export type Opencode = { prompt_template: PromptTemplate, model?: string | null, agent?: string | null, base_command_override?: string | null, additional_params?: Array<string> | null, };
 
-export type QwenCode = { append_prompt: AppendPrompt, yolo?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, };
+export type QwenCode = { prompt_template: PromptTemplate, yolo?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, };
 
-export type AppendPrompt = string | null;
+export type PromptTemplate = { prefix?: string | null, suffix?: string | null, };
 
 export type CodingAgentInitialRequest = { prompt: string, 
 /**
@@ -204,9 +728,38 @@ export type CreateTaskAttemptBody = { task_id: string,
 /**
  * Executor profile specification
  */
-executor_profile_id: ExecutorProfileId, base_branch: string, };
+executor_profile_id: ExecutorProfileId, 
+/**
+ * Branch to base the attempt on. Defaults to the project's default base branch, then to
+ * the repository's default branch.
+ */
+base_branch?: string | null, 
+/**
+ * Run the setup script even if the project's setup cache can be restored
+ */
+force_setup?: boolean, };
 
-export type RebaseTaskAttemptRequest = { old_base_branch: string | null, new_base_branch: string | null, };
+export type RebaseTaskAttemptRequest = { old_base_branch: string | null, new_base_branch: string | null, 
+/**
+ * Rebase onto this commit instead of the tip of the new base branch
+ */
+onto_commit: string | null, };
+
+export type ReorderCommitsRequest = { 
+/**
+ * All of the attempt's commits, oldest first, in the desired order
+ */
+ordered_oids: Array<string>, };
+
+export type SquashCommitsRequest = { 
+/**
+ * All of the attempt's commits; they are combined into a single commit
+ */
+oids: Array<string>, message: string, };
+
+export type ResolveConflictRequest = { path: string, strategy: ConflictResolution, };
+
+export type RemainingConflictsResponse = { remaining_conflicts: Array<string>, };
 
 export type GitOperationError = { "type": "merge_conflicts", message: string, op: ConflictOp, } | { "type": "rebase_in_progress" };
 
@@ -232,8 +785,83 @@ force_when_dirty: boolean | null,
  */
 perform_git_reset: boolean | null, };
 
+export type FollowUpFromCommitRequest = { 
+/**
+ * Commit on the attempt branch to continue from (full or abbreviated OID)
+ */
+commit_oid: string, 
+/**
+ * Prompt for the follow-up started from that commit
+ */
+prompt: string, 
+/**
+ * Optional variant override
+ */
+variant: string | null, 
+/**
+ * If true, discard uncommitted changes instead of refusing to reset
+ */
+force_when_dirty: boolean | null, 
+/**
+ * If set, keep the current branch tip on a new branch with this name before resetting
+ */
+backup_branch: string | null, };
+
+export type FollowUpFromCommitResult = { previous_head_oid: string, target_oid: string, backup_branch: string | null, new_execution_id: string, };
+
+export type ResetAttemptRequest = { 
+/**
+ * If true, discard uncommitted changes instead of refusing to reset
+ */
+force_when_dirty: boolean | null, };
+
+export type ResetAttemptResult = { previous_head_oid: string, base_oid: string, dropped_count: bigint, };
+
+export type RecoverAttemptRequest = { 
+/**
+ * If true, discard uncommitted changes instead of refusing to reset
+ */
+force_when_dirty: boolean | null, 
+/**
+ * Send this follow-up once the worktree is restored, to let the agent continue
+ */
+follow_up_prompt: string | null, };
+
+export type RecoverAttemptResult = { previous_head_oid: string, 
+/**
+ * The last commit a process completed successfully on, or the crashed process's
+ * starting commit when none did
+ */
+restored_oid: string, follow_up: ExecutionProcess | null, };
+
+export type ExecCommandRequest = { 
+/**
+ * Shell command run in the attempt's worktree
+ */
+command: string, };
+
+export type ExecCommandResult = { 
+/**
+ * None if the command was killed, e.g. after running too long
+ */
+exit_code: number | null, 
+/**
+ * Interleaved stdout and stderr, keeping only the tail of long output
+ */
+output: string, };
+
+export type MergeResult = { 
+/**
+ * Whether the target branch was pushed after merging, see `auto_push_after_merge`
+ */
+pushed: boolean, };
+
+export type DiffSummaryResult = { executor_profile_id: ExecutorProfileId, summary: string, };
+
 export type CommitInfo = { sha: string, subject: string, };
 
+export type CommitExecutionProcess = { execution_process: ExecutionProcess, prompt: string | null, };
+
 export type BranchStatus = { commits_behind: number | null, commits_ahead: number | null, has_uncommitted_changes: boolean | null, head_oid: string | null, uncommitted_count: number | null, untracked_count: number | null, target_branch_name: string, remote_commits_behind: number | null, remote_commits_ahead: number | null, merges: Array<Merge>, 
 /**
  * True if a `git rebase` is currently in progress in this worktree
@@ -246,11 +874,15 @@ conflict_op: ConflictOp | null,
 /**
  * List of files currently in conflicted (unmerged) state
  */
-conflicted_files: Array<string>, };
+conflicted_files: Array<string>, 
+/**
+ * URL detected in the output of the attempt's running dev server
+ */
+preview_url: string | null, };
 
 export type ConflictOp = "rebase" | "merge" | "cherry_pick" | "revert";
 
-export type TaskAttempt = { id: string, task_id: string, container_ref: string | null, branch: string, target_branch: string, executor: string, worktree_deleted: boolean, setup_completed_at: string | null, created_at: string, updated_at: string, };
+export type TaskAttempt = { id: string, task_id: string, container_ref: string | null, branch: string, target_branch: string, executor: string, worktree_deleted: boolean, setup_completed_at: string | null, reviewed_commit: string | null, notes: string | null, pinned: boolean, needs_input: boolean, created_at: string, updated_at: string, };
 
 export type ExecutionProcess = { id: string, task_attempt_id: string, run_reason: ExecutionProcessRunReason, executor_action: ExecutorAction, 
 /**
@@ -266,12 +898,21 @@ after_head_commit: string | null, status: ExecutionProcessStatus, exit_code: big
  * history view (due to restore/trimming). Hidden from logs/timeline;
  * still listed in the Processes tab.
  */
-dropped: boolean, started_at: string, completed_at: string | null, created_at: string, updated_at: string, };
+dropped: boolean, 
+/**
+ * URL the dev server reported it is listening on, detected from its output
+ */
+preview_url: string | null, started_at: string, completed_at: string | null, created_at: string, updated_at: string, };
 
 export enum ExecutionProcessStatus { running = "running", completed = "completed", failed = "failed", killed = "killed" }
 
 export type ExecutionProcessRunReason = "setupscript" | "cleanupscript" | "codingagent" | "devserver";
 
+/**
+ * A running execution process together with the attempt, task and project it belongs to
+ */
+export type RunningExecutionProcess = { id: string, task_attempt_id: string, task_id: string, project_id: string, project_name: string, run_reason: ExecutionProcessRunReason, started_at: string, };
+
 export type Merge = { "type": "direct" } & DirectMerge | { "type": "pr" } & PrMerge;
 
 export type DirectMerge = { id: string, task_attempt_id: string, merge_commit: string, target_branch_name: string, created_at: string, };
@@ -286,6 +927,49 @@ export type Draft = { id: string, task_attempt_id: string, draft_type: DraftType
 
 export type DraftType = "follow_up" | "retry";
 
+/**
+ * Stage an attempt has reached while its worktree is prepared for the coding agent
+ */
+export type AttemptProgressStage = "creating_worktree" | "copying_files" | "running_setup";
+
+/**
+ * Preparation progress for a task attempt, streamed until the coding agent starts
+ */
+export type AttemptProgress = { task_attempt_id: string, task_id: string, stage: AttemptProgressStage, };
+
+/**
+ * Git operation that moved an attempt's branch
+ */
+export type AttemptGitOperation = "rebase" | "merge";
+
+/**
+ * Where an attempt's branch stands after a rebase or merge, streamed so clients don't have
+ * to wait for the next branch status poll
+ */
+export type AttemptGitUpdate = { task_attempt_id: string, task_id: string, operation: AttemptGitOperation, 
+/**
+ * HEAD of the attempt's branch
+ */
+head_oid: string, 
+/**
+ * Tip of the target branch, which a merge moves
+ */
+target_oid: string | null, 
+/**
+ * Commits on the attempt's branch that are not on the target branch
+ */
+commits_ahead: number | null, 
+/**
+ * Commits on the target branch that are not on the attempt's branch
+ */
+commits_behind: number | null, };
+
+/**
+ * Files of an attempt's diff that reviewers have checked off, streamed so everyone reviewing
+ * the attempt sees the others' progress
+ */
+export type AttemptFileReviewUpdate = { task_attempt_id: string, task_id: string, reviewed_paths: Array<string>, };
+
 export type ContextFile = { pattern: string, instruction: string | null, };
 
 export type Agent = { id: string, name: string, role: string, system_prompt: string, capabilities: string | null, tools: string | null, description: string | null, context_files: string | null, executor: string, created_at: string, updated_at: string, };
@@ -300,11 +984,15 @@ export type CreateWorkflow = { project_id: string, name: string, description: st
 
 export type UpdateWorkflow = { name: string | null, description: string | null, };
 
-export type WorkflowStation = { id: string, workflow_id: string, name: string, position: bigint, description: string | null, x_position: number, y_position: number, agent_id: string | null, station_prompt: string | null, output_context_keys: string | null, is_terminator: boolean, created_at: string, updated_at: string, };
+export type WorkflowStation = { id: string, workflow_id: string, name: string, position: bigint, description: string | null, x_position: number, y_position: number, agent_id: string | null, station_prompt: string | null, output_context_keys: string | null, output_schema: string | null, is_terminator: boolean, created_at: string, updated_at: string, };
 
-export type CreateWorkflowStation = { workflow_id: string, name: string, position: bigint, description: string | null, x_position: number | null, y_position: number | null, agent_id: string | null, station_prompt: string | null, output_context_keys: string | null, is_terminator: boolean | null, };
+export type CreateWorkflowStation = { workflow_id: string, name: string, position: bigint, description: string | null, x_position: number | null, y_position: number | null, agent_id: string | null, station_prompt: string | null, output_context_keys: string | null, output_schema: string | null, is_terminator: boolean | null, };
 
-export type UpdateWorkflowStation = { name: string | null, position: bigint | null, description: string | null, x_position: number | null, y_position: number | null, agent_id: string | null, station_prompt: string | null, output_context_keys: string | null, is_terminator: boolean | null, };
+export type UpdateWorkflowStation = { name: string | null, position: bigint | null, description: string | null, x_position: number | null, y_position: number | null, agent_id: string | null, station_prompt: string | null, output_context_keys: string | null, 
+/**
+ * Omit to keep the current schema; `null` removes it
+ */
+output_schema?: string | null, is_terminator: boolean | null, };
 
 export type StationContext = { id: string, task_id: string, station_id: string, context_key: string, context_value: string, context_type: string, created_by_agent_id: string | null, created_at: string, };
 
@@ -318,6 +1006,33 @@ export type CreateStationTransition = { workflow_id: string, source_station_id:
 
 export type UpdateStationTransition = { condition: string | null, label: string | null, condition_type: string | null, condition_value: string | null, };
 
+/**
+ * A transition condition plus sample station results to evaluate it against
+ */
+export type DryRunTransitionConditionRequest = { condition_type: string | null, condition_value: string | null, 
+/**
+ * Sample station status, defaults to `completed`
+ */
+status: string | null, 
+/**
+ * Sample station `output_data`
+ */
+output_data: JsonValue | null, 
+/**
+ * Sample merged outputs of earlier stations, read by conditions with `"scope": "context"`
+ */
+context_data: JsonValue | null, };
+
+export type DryRunTransitionConditionResponse = { 
+/**
+ * Whether the transition would be taken; always `false` when there are errors
+ */
+result: boolean, 
+/**
+ * Validation or evaluation errors for the condition
+ */
+errors: Array<string>, };
+
 export type TaskStationExecution = { id: string, task_id: string, station_id: string, status: string, transition_taken_id: string | null, attempt_number: bigint, started_at: string | null, completed_at: string | null, error_message: string | null, created_at: string, updated_at: string, };
 
 export type CreateTaskStationExecution = { task_id: string, station_id: string, status: string, attempt_number: bigint, };
@@ -330,13 +1045,22 @@ export type CreateWorkflowExecution = { workflow_id: string, task_id: string, ta
 
 export type UpdateWorkflowExecution = { current_station_id: string | null, status: string | null, started_at: string | null, completed_at: string | null, };
 
-export type StationExecution = { id: string, workflow_execution_id: string, station_id: string, execution_process_id: string | null, status: string, output_data: string | null, started_at: string | null, completed_at: string | null, created_at: string, updated_at: string, };
+export type StationExecution = { id: string, workflow_execution_id: string, station_id: string, execution_process_id: string | null, 
+/**
+ * Transition taken from the previous station into this one, `None` for the first station
+ */
+transition_id: string | null, status: string, output_data: string | null, started_at: string | null, completed_at: string | null, created_at: string, updated_at: string, };
 
-export type CreateStationExecution = { workflow_execution_id: string, station_id: string, status: string, execution_process_id: string | null, };
+export type CreateStationExecution = { workflow_execution_id: string, station_id: string, status: string, execution_process_id: string | null, transition_id: string | null, };
 
 export type UpdateStationExecution = { execution_process_id: string | null, status: string | null, output_data: string | null, started_at: string | null, completed_at: string | null, };
 
-export type ExecuteWorkflowRequest = { task_id: string, base_branch: string, executor_profile_id: ExecutorProfileId | null, };
+export type ExecuteWorkflowRequest = { task_id: string, base_branch: string, executor_profile_id: ExecutorProfileId | null, 
+/**
+ * Cancel the workflow's active execution on this task and start a fresh one, instead of
+ * returning the active execution
+ */
+force_restart: boolean | null, };
 
 export type ExecuteWorkflowResponse = { workflow_execution_id: string, task_attempt_id: string, current_station_id: string | null, status: string, };
 
@@ -344,6 +1068,37 @@ export type WorkflowExecutionDetailsResponse = { id: string, workflow_id: string
 
 export type StationExecutionSummary = { id: string, station_id: string, station_name: string | null, status: string, output_data: string | null, started_at: string | null, completed_at: string | null, };
 
+/**
+ * Timeline of a finished or running workflow execution, for spotting slow stations
+ */
+export type WorkflowExecutionHistoryResponse = { id: string, workflow_id: string, task_id: string, status: string, 
+/**
+ * Status of the task the workflow ran for
+ */
+task_status: TaskStatus, started_at: string | null, completed_at: string | null, 
+/**
+ * Until completion, or until now while the workflow is still running
+ */
+duration_seconds: bigint | null, 
+/**
+ * Station executions in the order they ran, retries included
+ */
+stations: Array<StationTimelineEntry>, };
+
+/**
+ * One station execution in a workflow execution's timeline
+ */
+export type StationTimelineEntry = { id: string, station_id: string, station_name: string | null, status: string, started_at: string | null, completed_at: string | null, 
+/**
+ * Until completion, or until now while the station is still running
+ */
+duration_seconds: bigint | null, 
+/**
+ * Transition from the previous station that led here, shared by retries of the station.
+ * `None` for the first station.
+ */
+transition_id: string | null, transition_label: string | null, };
+
 export type CancelWorkflowExecutionRequest = { 
 /**
  * Optional reason for cancellation
@@ -360,7 +1115,16 @@ export type CommandExitStatus = { "type": "exit_code", code: number, } | { "type
 
 export type CommandRunResult = { exit_status: CommandExitStatus | null, output: string | null, };
 
-export type NormalizedEntry = { timestamp: string | null, entry_type: NormalizedEntryType, content: string, };
+/**
+ * The process output stream an entry was normalized from
+ */
+export type LogChannel = "stdout" | "stderr";
+
+export type NormalizedEntry = { timestamp: string | null, entry_type: NormalizedEntryType, content: string, 
+/**
+ * Executor-specific details; `channel` records the [`LogChannel`] the entry came from
+ */
+metadata?: JsonValue, };
 
 export type NormalizedEntryType = { "type": "user_message" } | { "type": "user_feedback", denied_tool: string, } | { "type": "assistant_message" } | { "type": "tool_use", tool_name: string, action_type: ActionType, status: ToolStatus, } | { "type": "system_message" } | { "type": "error_message" } | { "type": "thinking" } | { "type": "loading" } | { "type": "next_action", failed: boolean, execution_processes: number, };
 
@@ -374,10 +1138,24 @@ unified_diff: string,
  */
 has_line_numbers: boolean, };
 
-export type ActionType = { "action": "file_read", path: string, } | { "action": "file_edit", path: string, changes: Array<FileChange>, } | { "action": "command_run", command: string, result: CommandRunResult | null, } | { "action": "search", query: string, } | { "action": "web_fetch", url: string, } | { "action": "tool", tool_name: string, arguments: JsonValue | null, result: ToolResult | null, } | { "action": "task_create", description: string, } | { "action": "plan_presentation", plan: string, } | { "action": "todo_management", todos: Array<TodoItem>, operation: string, } | { "action": "other", description: string, };
+export type ActionType = { "action": "file_read", path: string, } | { "action": "file_edit", path: string, changes: Array<FileChange>, } | { "action": "command_run", command: string, result: CommandRunResult | null, } | { "action": "search", query: string, } | { "action": "web_fetch", url: string, } | { "action": "tool", tool_name: string, arguments: JsonValue | null, result: ToolResult | null, } | { "action": "task_create", description: string, } | { "action": "plan_presentation", plan: string, 
+/**
+ * Steps parsed from `plan`; a single step with the raw text for free-form plans
+ */
+plan_steps: Array<PlanStep>, } | { "action": "todo_management", todos: Array<TodoItem>, operation: string, } | { "action": "other", description: string, };
 
 export type TodoItem = { content: string, status: string, priority: string | null, };
 
+export type PlanStep = { text: string, 
+/**
+ * The agent marked the step as completed (`- [x]`)
+ */
+done: boolean, 
+/**
+ * Nesting depth, 0 for top-level steps
+ */
+level: number, };
+
 export type ToolResult = { type: ToolResultValueType, 
 /**
  * For Markdown, this will be a JSON string; for JSON, a structured value
@@ -390,7 +1168,7 @@ export type ToolStatus = { "status": "created" } | { "status": "success" } | { "
 
 export type PatchType = { "type": "NORMALIZED_ENTRY", "content": NormalizedEntry } | { "type": "STDOUT", "content": string } | { "type": "STDERR", "content": string } | { "type": "DIFF", "content": Diff };
 
-export type ApprovalStatus = { "status": "pending" } | { "status": "approved" } | { "status": "denied", reason?: string, } | { "status": "timed_out" };
+export type ApprovalStatus = { "status": "pending" } | { "status": "approved" } | { "status": "denied", reason?: string, } | { "status": "timed_out" } | { "status": "cancelled" };
 
 export type CreateApprovalRequest = { tool_name: string, tool_input: JsonValue, tool_call_id: string, };
 